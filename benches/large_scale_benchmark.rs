@@ -3,6 +3,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use offline_election::engine::ElectionEngine;
 use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_overrides::ElectionOverrides;
 use offline_election::types::AlgorithmType;
 
 // Import test utilities
@@ -65,12 +66,11 @@ fn benchmark_election_execution(c: &mut Criterion) {
     
     for (candidate_count, nominator_count, name) in scales {
         let election_data = generate_benchmark_data(candidate_count, nominator_count);
-        let config = ElectionConfiguration {
-            active_set_size: 100,
-            algorithm: AlgorithmType::SequentialPhragmen,
-            overrides: None,
-            block_number: None,
-        };
+        let config = ElectionConfiguration::builder()
+            .algorithm(AlgorithmType::SequentialPhragmen)
+            .active_set_size(100)
+            .build()
+            .unwrap();
         
         group.bench_with_input(
             BenchmarkId::new("sequential_phragmen", name),
@@ -96,12 +96,11 @@ fn benchmark_different_active_set_sizes(c: &mut Criterion) {
     let active_set_sizes = vec![10, 50, 100, 200, 500];
     
     for active_set_size in active_set_sizes {
-        let config = ElectionConfiguration {
-            active_set_size,
-            algorithm: AlgorithmType::SequentialPhragmen,
-            overrides: None,
-            block_number: None,
-        };
+        let config = ElectionConfiguration::builder()
+            .algorithm(AlgorithmType::SequentialPhragmen)
+            .active_set_size(active_set_size)
+            .build()
+            .unwrap();
         
         group.bench_with_input(
             BenchmarkId::from_parameter(active_set_size),
@@ -118,6 +117,77 @@ fn benchmark_different_active_set_sizes(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_election_execution, benchmark_different_active_set_sizes);
+/// Compare runs that borrow the input data with runs that must copy it
+///
+/// An empty override set changes nothing but still forces the engine to copy
+/// the data before applying it, so the difference is the cost of that copy.
+fn benchmark_input_data_path(c: &mut Criterion) {
+    let engine = ElectionEngine::new();
+    let election_data = generate_benchmark_data(2_000, 20_000);
+
+    let mut group = c.benchmark_group("input_data_path");
+    group.sample_size(10);
+
+    let builder = ElectionConfiguration::builder().algorithm(AlgorithmType::SequentialPhragmen).active_set_size(100);
+    let borrowed = builder.clone().build().unwrap();
+    let copied = builder.overrides(ElectionOverrides::default()).build().unwrap();
+
+    for (name, config) in [("borrowed", borrowed), ("copied", copied)] {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &config, |b, config| {
+            b.iter(|| {
+                let result = engine.execute(black_box(config), black_box(&election_data));
+                black_box(result)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Sweep active set sizes over one snapshot, preparing the data once
+/// versus converting it on every run
+fn benchmark_prepared_sweep(c: &mut Criterion) {
+    let engine = ElectionEngine::new();
+    let election_data = generate_benchmark_data(1_000, 10_000);
+    let configs: Vec<ElectionConfiguration> = [10, 50, 100]
+        .into_iter()
+        .map(|active_set_size| {
+            ElectionConfiguration::builder()
+                .algorithm(AlgorithmType::SequentialPhragmen)
+                .active_set_size(active_set_size)
+                .build()
+                .unwrap()
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("active_set_sweep");
+    group.sample_size(10);
+
+    group.bench_function("execute", |b| {
+        b.iter(|| {
+            for config in &configs {
+                black_box(engine.execute(black_box(config), black_box(&election_data)).ok());
+            }
+        })
+    });
+    group.bench_function("execute_prepared", |b| {
+        b.iter(|| {
+            let prepared = engine.prepare(black_box(&election_data)).unwrap();
+            for config in &configs {
+                black_box(engine.execute_prepared(black_box(config), &prepared, false).ok());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_election_execution,
+    benchmark_different_active_set_sizes,
+    benchmark_input_data_path,
+    benchmark_prepared_sweep
+);
 criterion_main!(benches);
 
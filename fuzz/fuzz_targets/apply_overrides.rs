@@ -0,0 +1,101 @@
+//! Fuzz `ElectionOverrides::apply` and the elections run with overrides
+//!
+//! Builds small elections and overrides over a shared pool of account IDs, so
+//! most overrides hit an account and some do not, then checks that applying
+//! them keeps the data consistent and that the engine never panics on it.
+
+#![no_main]
+
+use libfuzzer_sys::arbitrary::{Result, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use offline_election::engine::ElectionEngine;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_overrides::{EdgeAction, EdgeModification, ElectionOverrides};
+use offline_election::models::nominator::Nominator;
+use offline_election::models::validation::ValidationProfile;
+use offline_election::models::validator::ValidatorCandidate;
+
+/// Account IDs shared by candidates, nominators and overrides
+const ACCOUNTS: [&str; 8] = ["a", "b", "c", "d", "e", "f", "g", "h"];
+
+fn account(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(u.choose(&ACCOUNTS)?.to_string())
+}
+
+fn election(u: &mut Unstructured<'_>) -> Result<ElectionData> {
+    let mut data = ElectionData {
+        candidates: Vec::new(),
+        nominators: Vec::new(),
+        invulnerables: Vec::new(),
+        metadata: None,
+    };
+    for _ in 0..u.int_in_range(0..=6)? {
+        data.candidates.push(ValidatorCandidate::new(account(u)?, u.arbitrary()?));
+    }
+    for _ in 0..u.int_in_range(0..=8)? {
+        let mut nominator = Nominator::new(account(u)?, u.arbitrary()?);
+        for _ in 0..u.int_in_range(0..=4)? {
+            nominator.targets.push(account(u)?);
+        }
+        data.nominators.push(nominator);
+    }
+    Ok(data)
+}
+
+fn overrides(u: &mut Unstructured<'_>) -> Result<ElectionOverrides> {
+    let mut overrides = ElectionOverrides::new();
+    for _ in 0..u.int_in_range(0..=4)? {
+        overrides.candidate_stakes.insert(account(u)?, u.arbitrary()?);
+    }
+    for _ in 0..u.int_in_range(0..=4)? {
+        overrides.nominator_stakes.insert(account(u)?, u.arbitrary()?);
+    }
+    for _ in 0..u.int_in_range(0..=3)? {
+        overrides.candidate_blocked.insert(account(u)?, u.arbitrary()?);
+    }
+    for _ in 0..u.int_in_range(0..=6)? {
+        overrides.voting_edges.push(EdgeModification {
+            action: *u.choose(&[EdgeAction::Add, EdgeAction::Remove, EdgeAction::Modify])?,
+            nominator_id: account(u)?,
+            candidate_id: account(u)?,
+            weight: u.arbitrary()?,
+        });
+    }
+    Ok(overrides)
+}
+
+fn check(u: &mut Unstructured<'_>) -> Result<()> {
+    let data = election(u)?;
+    let overrides = overrides(u)?;
+    let active_set_size = u.int_in_range(0..=8)?;
+
+    let mut applied = data.clone();
+    let unmatched = overrides.apply(&mut applied);
+    let override_count = overrides.candidate_stakes.len()
+        + overrides.nominator_stakes.len()
+        + overrides.candidate_blocked.len()
+        + overrides.voting_edges.len();
+    assert!(unmatched.len() <= override_count);
+    assert_eq!(applied.candidates.len(), data.candidates.len());
+    assert_eq!(applied.nominators.len(), data.nominators.len());
+    for (account_id, stake) in &overrides.candidate_stakes {
+        if let Some(candidate) = applied.candidates.iter().find(|c| c.account_id == *account_id) {
+            assert_eq!(candidate.stake, *stake);
+        }
+    }
+
+    if let Ok(config) = ElectionConfiguration::builder()
+        .active_set_size(active_set_size)
+        .validation_profile(ValidationProfile::Permissive)
+        .overrides(overrides)
+        .build()
+    {
+        let _ = ElectionEngine::new().execute(&config, &data);
+    }
+    Ok(())
+}
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = check(&mut Unstructured::new(bytes));
+});
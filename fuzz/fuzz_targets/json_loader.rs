@@ -0,0 +1,35 @@
+//! Fuzz `JsonLoader::load_from_str` with arbitrary documents
+//!
+//! Loading must reject bad input with an error under every validation profile
+//! and dedup policy, and whatever loads must survive a round trip.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use offline_election::input::JsonLoader;
+use offline_election::models::validation::{DedupPolicy, ValidationProfile};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    for profile in [ValidationProfile::Strict, ValidationProfile::ChainMirroring, ValidationProfile::Permissive] {
+        let Ok(loaded) = JsonLoader::new().with_validation_profile(profile).load_from_str(content) else {
+            continue;
+        };
+        let json = serde_json::to_string(&loaded).expect("loaded data serializes");
+        let reloaded = JsonLoader::new()
+            .with_validation_profile(ValidationProfile::Permissive)
+            .load_from_str(&json)
+            .expect("serialized data loads again");
+        assert_eq!(reloaded.content_hash(), loaded.content_hash());
+    }
+
+    for policy in [DedupPolicy::KeepFirst, DedupPolicy::KeepLast, DedupPolicy::KeepHighestStake, DedupPolicy::Sum] {
+        let _ = JsonLoader::new()
+            .with_validation_profile(ValidationProfile::Permissive)
+            .with_dedup(policy)
+            .load_from_str(content);
+    }
+});
@@ -0,0 +1,24 @@
+//! Fuzz decoding of binary snapshots and solutions
+//!
+//! Covers the SCALE-encoded `RoundSnapshot` and `ReadySolution` read over RPC
+//! and every form of staking-miner dump. Malformed bytes must fail to decode,
+//! never panic or allocate without bound.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use offline_election::input::StakingMinerLoader;
+use offline_election::models::onchain_solution::decode_snapshot;
+use offline_election::models::validation::ValidationProfile;
+use offline_election::models::OnchainSolution;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(snapshot) = decode_snapshot(data, None) {
+        // Decoded voters only name targets as hex account IDs
+        assert!(snapshot.nominators.iter().flat_map(|n| &n.targets).all(|t| t.len() == 66));
+    }
+    let _ = OnchainSolution::decode(data);
+    let _ = StakingMinerLoader::new()
+        .with_validation_profile(ValidationProfile::Permissive)
+        .load_from_bytes(data);
+});
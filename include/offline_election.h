@@ -0,0 +1,57 @@
+/*
+ * C interface of the offline NPoS election tool.
+ *
+ * Build the library with
+ *     cargo rustc --lib --release --features capi --crate-type cdylib
+ * and link against liboffline_election. Data, configurations and results
+ * are JSON in the same shapes the CLI reads and writes.
+ *
+ * Fallible calls return OFFLINE_ELECTION_OK (0) or the CLI exit code of the
+ * error (3 validation, 4 RPC, 5 algorithm, 6 I/O, 130 cancelled), and
+ * offline_election_last_error() describes the error.
+ */
+
+#ifndef OFFLINE_ELECTION_H
+#define OFFLINE_ELECTION_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define OFFLINE_ELECTION_OK 0
+
+typedef struct OfflineElectionEngine OfflineElectionEngine;
+typedef struct OfflineElectionData OfflineElectionData;
+
+/* Create an engine; free it with offline_election_engine_free. */
+OfflineElectionEngine *offline_election_engine_new(void);
+void offline_election_engine_free(OfflineElectionEngine *engine);
+
+/* Load and validate election data from len bytes of JSON; NULL on failure. */
+OfflineElectionData *offline_election_data_from_json(const uint8_t *json, size_t len);
+void offline_election_data_free(OfflineElectionData *data);
+
+/*
+ * Run an election configured by config_len bytes of configuration JSON.
+ * On success *result_json holds the result JSON; free it with
+ * offline_election_string_free.
+ */
+int32_t offline_election_run(const OfflineElectionEngine *engine,
+                             const OfflineElectionData *data,
+                             const uint8_t *config_json,
+                             size_t config_len,
+                             char **result_json);
+
+void offline_election_string_free(char *string);
+
+/* Last error on the calling thread, or NULL; valid until the next failing call. */
+const char *offline_election_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* OFFLINE_ELECTION_H */
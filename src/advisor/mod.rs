@@ -0,0 +1,15 @@
+//! Staking parameter recommendations for new chains
+//!
+//! A chain launching NPoS has to pick its validator set size and the number
+//! of targets each nominator may name before it has any history. Given a
+//! synthetic or projected stake distribution, the [`ParameterAdvisor`]
+//! elects a set for every combination of set size and nomination cap in a
+//! [`ParameterGrid`] and reports which combinations meet the
+//! [`AdvisorTargets`], such as a minimum Nakamoto coefficient or a minimum
+//! backing per validator.
+
+pub mod models;
+pub mod sweep;
+
+pub use models::{AdvisorTargets, ParameterGrid, ParameterRange, Recommendation, SweepPoint};
+pub use sweep::ParameterAdvisor;
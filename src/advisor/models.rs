@@ -0,0 +1,150 @@
+//! Parameter advisor data models
+
+use crate::error::ElectionError;
+use serde::{Deserialize, Serialize};
+
+/// Set sizes and nomination caps to sweep
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterGrid {
+    /// Validator set sizes, in the order they are reported
+    pub set_sizes: Vec<u32>,
+    /// Most targets a nominator may name; `None` keeps every target
+    pub nomination_caps: Vec<Option<usize>>,
+}
+
+impl ParameterGrid {
+    /// Grid of the given set sizes, without a nomination cap
+    pub fn new(set_sizes: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            set_sizes: set_sizes.into_iter().collect(),
+            nomination_caps: vec![None],
+        }
+    }
+
+    /// Sweep these nomination caps instead of keeping every target
+    pub fn nomination_caps(mut self, caps: impl IntoIterator<Item = usize>) -> Self {
+        self.nomination_caps = caps.into_iter().map(Some).collect();
+        self
+    }
+
+    /// Check the grid is not empty and its values can be elected
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        if self.set_sizes.is_empty() || self.nomination_caps.is_empty() {
+            return Err(ElectionError::ValidationError {
+                message: "The parameter grid needs at least one set size and nomination cap".to_string(),
+                field: Some("grid".to_string()),
+            });
+        }
+        if self.set_sizes.contains(&0) {
+            return Err(ElectionError::ValidationError {
+                message: "Set sizes in the parameter grid must be positive".to_string(),
+                field: Some("set_sizes".to_string()),
+            });
+        }
+        if self.nomination_caps.contains(&Some(0)) {
+            return Err(ElectionError::ValidationError {
+                message: "Nomination caps in the parameter grid must be positive".to_string(),
+                field: Some("nomination_caps".to_string()),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// What a recommended parameter combination must achieve
+///
+/// Unset targets are not checked; with none set every combination that
+/// fills its seats qualifies.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdvisorTargets {
+    /// Fewest validators whose combined backing must exceed one third of the elected backing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_nakamoto_coefficient: Option<usize>,
+    /// Backing every elected validator must have, in plancks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_backing: Option<u128>,
+}
+
+/// Outcome of one set size and nomination cap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepPoint {
+    /// Seats the election was asked to fill
+    pub set_size: u32,
+    /// Most targets kept per nominator, if capped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nomination_cap: Option<usize>,
+    /// Validators elected
+    pub elected_count: usize,
+    /// Backing of the weakest elected validator
+    pub minimal_backing: u128,
+    /// Nakamoto coefficient of the elected backing
+    pub nakamoto_coefficient: usize,
+    /// Share of the nominator stake backing the elected set, in basis points
+    pub stake_utilization_bps: u32,
+    /// Whether every seat was filled and every target met
+    pub meets_targets: bool,
+}
+
+/// Consecutive set sizes of the grid that meet the targets under one cap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterRange {
+    /// Most targets kept per nominator, if capped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nomination_cap: Option<usize>,
+    /// Smallest set size in the range
+    pub min_set_size: u32,
+    /// Largest set size in the range
+    pub max_set_size: u32,
+}
+
+/// Sweep results and the parameter ranges that meet the targets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recommendation {
+    /// Targets the sweep was checked against
+    pub targets: AdvisorTargets,
+    /// Every combination, by nomination cap then set size in grid order
+    pub points: Vec<SweepPoint>,
+    /// Runs of set sizes meeting the targets, per nomination cap
+    pub ranges: Vec<ParameterRange>,
+    /// Qualifying combination with the highest Nakamoto coefficient, then the
+    /// highest minimal backing, then the smallest set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested: Option<SweepPoint>,
+}
+
+impl Recommendation {
+    /// Build a recommendation from the sweep's points
+    pub fn from_points(targets: AdvisorTargets, points: Vec<SweepPoint>) -> Self {
+        let mut ranges: Vec<ParameterRange> = Vec::new();
+        let mut previous: Option<&SweepPoint> = None;
+        for point in &points {
+            let extends = previous.is_some_and(|p| p.meets_targets && p.nomination_cap == point.nomination_cap);
+            match ranges.last_mut() {
+                Some(range) if point.meets_targets && extends => range.max_set_size = point.set_size,
+                _ if point.meets_targets => ranges.push(ParameterRange {
+                    nomination_cap: point.nomination_cap,
+                    min_set_size: point.set_size,
+                    max_set_size: point.set_size,
+                }),
+                _ => {}
+            }
+            previous = Some(point);
+        }
+        let suggested = points
+            .iter()
+            .filter(|p| p.meets_targets)
+            .max_by(|a, b| {
+                a.nakamoto_coefficient
+                    .cmp(&b.nakamoto_coefficient)
+                    .then_with(|| a.minimal_backing.cmp(&b.minimal_backing))
+                    .then_with(|| b.set_size.cmp(&a.set_size))
+            })
+            .cloned();
+        Self { targets, points, ranges, suggested }
+    }
+
+    /// Whether any combination met the targets
+    pub fn is_satisfiable(&self) -> bool {
+        self.suggested.is_some()
+    }
+}
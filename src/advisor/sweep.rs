@@ -0,0 +1,113 @@
+//! Parameter sweep over set sizes and nomination caps
+
+use crate::advisor::models::{AdvisorTargets, ParameterGrid, Recommendation, SweepPoint};
+use crate::engine::ElectionEngine;
+use crate::error::ElectionError;
+use crate::models::chain_comparison::ChainProfile;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::types::AlgorithmType;
+
+/// Sweeps staking parameters over a stake distribution
+///
+/// # Example
+///
+/// ```no_run
+/// use offline_election::advisor::{AdvisorTargets, ParameterAdvisor, ParameterGrid};
+/// use offline_election::ElectionData;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let data = ElectionData::default();
+/// let grid = ParameterGrid::new((50..=200).step_by(25)).nomination_caps([8, 16, 24]);
+/// let targets = AdvisorTargets { min_nakamoto_coefficient: Some(10), min_backing: Some(10_000_000_000_000) };
+///
+/// let recommendation = ParameterAdvisor::new().recommend(&data, &grid, &targets)?;
+/// for range in &recommendation.ranges {
+///     println!("cap {:?}: {}..={} validators", range.nomination_cap, range.min_set_size, range.max_set_size);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ParameterAdvisor {
+    engine: ElectionEngine,
+    algorithm: AlgorithmType,
+}
+
+impl ParameterAdvisor {
+    /// Advisor electing with sequential Phragmén on a default engine
+    pub fn new() -> Self {
+        Self {
+            engine: ElectionEngine::new(),
+            algorithm: AlgorithmType::SequentialPhragmen,
+        }
+    }
+
+    /// Elect with this algorithm
+    pub fn with_algorithm(mut self, algorithm: AlgorithmType) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Run the sweeps on this engine, e.g. one with a larger thread pool
+    pub fn with_engine(mut self, engine: ElectionEngine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Elect every combination of the grid on `data` and check it against `targets`
+    ///
+    /// For each nomination cap, every nominator keeps only its first targets
+    /// (its order of preference), and the set sizes are elected in one batch.
+    pub fn recommend(
+        &self,
+        data: &ElectionData,
+        grid: &ParameterGrid,
+        targets: &AdvisorTargets,
+    ) -> Result<Recommendation, ElectionError> {
+        grid.validate()?;
+        let configs = grid
+            .set_sizes
+            .iter()
+            .map(|&size| ElectionConfiguration::builder().algorithm(self.algorithm).active_set_size(size).build())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut points = Vec::with_capacity(grid.set_sizes.len() * grid.nomination_caps.len());
+        for &cap in &grid.nomination_caps {
+            let capped = cap.map(|cap| capped_data(data, cap));
+            let data = capped.as_ref().unwrap_or(data);
+            let results = self.engine.execute_batch(&configs, data)?;
+            for (&set_size, result) in grid.set_sizes.iter().zip(&results) {
+                let profile = ChainProfile::from_result("", data, result, set_size);
+                let meets_targets = profile.elected_count == set_size as usize
+                    && targets.min_nakamoto_coefficient.map_or(true, |min| profile.nakamoto_coefficient >= min)
+                    && targets.min_backing.map_or(true, |min| profile.minimal_backing.plancks() >= min);
+                points.push(SweepPoint {
+                    set_size,
+                    nomination_cap: cap,
+                    elected_count: profile.elected_count,
+                    minimal_backing: profile.minimal_backing.plancks(),
+                    nakamoto_coefficient: profile.nakamoto_coefficient,
+                    stake_utilization_bps: profile.stake_utilization_bps,
+                    meets_targets,
+                });
+            }
+        }
+
+        Ok(Recommendation::from_points(targets.clone(), points))
+    }
+}
+
+impl Default for ParameterAdvisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `data` with every nominator's targets cut to the first `cap`
+fn capped_data(data: &ElectionData, cap: usize) -> ElectionData {
+    let mut capped = data.clone();
+    for nominator in capped.nominators_mut() {
+        nominator.targets.truncate(cap);
+    }
+    capped
+}
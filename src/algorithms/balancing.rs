@@ -0,0 +1,209 @@
+//! Stake balancing of an election solution
+//!
+//! The same algorithm as `sp_npos_elections::balance`: each round rebalances
+//! every voter once, in voter order, spreading its stake so the elected
+//! targets it backs end up as even as possible. It is reimplemented here
+//! because the upstream version reports neither how many rounds it ran nor
+//! how much imbalance is left when it stops, and it is not a drop-in copy:
+//!
+//! - Each voter's elected targets are deduplicated and kept in winner order
+//!   rather than in the order the voter listed them, so backings that tie
+//!   can be filled in a different order than upstream.
+//! - Weights stay exact `u128` amounts for the whole run; they are turned
+//!   into normalized `Perbill` portions once, at the end, rather than after
+//!   every voter is balanced.
+//!
+//! Backings can therefore differ from upstream by rounding and tie-breaking.
+
+use crate::algorithms::prepared::PreparedElectionData;
+use crate::cancellation::ElectionPhase;
+use crate::progress::RunControl;
+use crate::error::ElectionError;
+use crate::models::election_config::BalancingSettings;
+use sp_npos_elections::Assignment;
+use sp_runtime::Perbill;
+use std::time::Instant;
+
+/// How a balancing run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalancingOutcome {
+    /// Rounds run
+    pub iterations: usize,
+    /// Largest amount a single voter could still shift between its elected targets
+    pub residual: u128,
+    /// Whether the last round stayed within the tolerance
+    pub converged: bool,
+}
+
+/// A voter with its weight on each elected target it backs
+struct BalancingVoter {
+    who: u32,
+    budget: u128,
+    /// `(winner slot, weight)` pairs
+    edges: Vec<(usize, u128)>,
+}
+
+impl BalancingVoter {
+    /// Amount the voter could shift: the gap between its most backed funded
+    /// target and its least backed target, plus any stake it leaves unused
+    fn imbalance(&self, backings: &[u128]) -> u128 {
+        if self.edges.len() <= 1 {
+            return 0;
+        }
+        let used: u128 = self.edges.iter().map(|(_, weight)| weight).sum();
+        let Some(max_funded) = self.edges.iter().filter(|(_, weight)| *weight > 0).map(|(slot, _)| backings[*slot]).max()
+        else {
+            return self.budget;
+        };
+        let min_backed = self.edges.iter().map(|(slot, _)| backings[*slot]).min().unwrap_or(0);
+        max_funded.saturating_sub(min_backed).saturating_add(self.budget.saturating_sub(used))
+    }
+
+    /// Redistribute the voter's stake, returning its imbalance before the round
+    fn balance(&mut self, backings: &mut [u128], tolerance: u128) -> u128 {
+        if self.edges.len() <= 1 {
+            return 0;
+        }
+        let difference = self.imbalance(backings);
+        if difference < tolerance && self.edges.iter().any(|(_, weight)| *weight > 0) {
+            return difference;
+        }
+
+        for (slot, weight) in self.edges.iter_mut() {
+            backings[*slot] = backings[*slot].saturating_sub(*weight);
+            *weight = 0;
+        }
+        self.edges.sort_by_key(|(slot, _)| backings[*slot]);
+
+        // Fill the least backed targets up to a common level
+        let mut cumulative = 0u128;
+        let mut last_index = self.edges.len() - 1;
+        for (index, (slot, _)) in self.edges.iter().enumerate() {
+            let backed = backings[*slot];
+            if backed.saturating_mul(index as u128).saturating_sub(cumulative) > self.budget {
+                last_index = index - 1;
+                break;
+            }
+            cumulative = cumulative.saturating_add(backed);
+        }
+        let last_stake = backings[self.edges[last_index].0];
+        let ways = (last_index + 1) as u128;
+        let excess = self.budget.saturating_add(cumulative).saturating_sub(last_stake.saturating_mul(ways));
+        for (slot, weight) in self.edges[..=last_index].iter_mut() {
+            *weight = (excess / ways + last_stake).saturating_sub(backings[*slot]);
+            backings[*slot] = backings[*slot].saturating_add(*weight);
+        }
+
+        difference
+    }
+}
+
+/// Balance `solution` in place within `settings`
+///
+/// Every voter with stake on the elected set takes part, including stake the
+/// solution left unassigned. Winner backings and assignments are rewritten
+/// from the balanced weights; winner order is kept. The run's cancellation
+/// token is checked before every round and each finished round is reported
+/// with the smallest backing; a cancelled run leaves `solution` untouched.
+pub fn balance(
+    prepared: &PreparedElectionData<'_>,
+    solution: &mut sp_npos_elections::ElectionResult<u32, Perbill>,
+    settings: &BalancingSettings,
+    control: &RunControl,
+) -> Result<BalancingOutcome, ElectionError> {
+    let mut winner_slot = vec![usize::MAX; prepared.candidate_count()];
+    for (slot, (winner, _)) in solution.winners.iter().enumerate() {
+        winner_slot[*winner as usize] = slot;
+    }
+    let mut assignment_of = vec![usize::MAX; prepared.voter_count()];
+    for (index, assignment) in solution.assignments.iter().enumerate() {
+        assignment_of[assignment.who as usize] = index;
+    }
+
+    let mut backings = vec![0u128; solution.winners.len()];
+    let mut voters = Vec::new();
+    for (voter, &assignment) in assignment_of.iter().enumerate() {
+        let budget = prepared.voter_stake(voter) as u128;
+        let mut slots: Vec<usize> = prepared
+            .voter_targets(voter)
+            .iter()
+            .map(|&target| winner_slot[target as usize])
+            .filter(|&slot| slot != usize::MAX)
+            .collect();
+        slots.sort_unstable();
+        slots.dedup();
+        if budget == 0 || slots.is_empty() {
+            continue;
+        }
+        let distribution = solution
+            .assignments
+            .get(assignment)
+            .map(|a| a.distribution.as_slice())
+            .unwrap_or_default();
+        let edges: Vec<(usize, u128)> = slots
+            .into_iter()
+            .map(|slot| {
+                let winner = solution.winners[slot].0;
+                let weight = distribution
+                    .iter()
+                    .filter(|(target, _)| *target == winner)
+                    .map(|(_, portion)| *portion * budget)
+                    .sum::<u128>();
+                backings[slot] += weight;
+                (slot, weight)
+            })
+            .collect();
+        voters.push(BalancingVoter { who: voter as u32, budget, edges });
+    }
+
+    let mut iterations = 0;
+    let mut converged = false;
+    let started = Instant::now();
+    while iterations < settings.max_iterations {
+        control.cancel.check_round(ElectionPhase::Balancing, iterations as u32)?;
+        let mut max_difference = 0;
+        for voter in voters.iter_mut() {
+            max_difference = max_difference.max(voter.balance(&mut backings, settings.tolerance));
+        }
+        iterations += 1;
+        if control.reports_progress() {
+            let minimum_support = backings.iter().min().copied();
+            control.report(
+                ElectionPhase::Balancing,
+                iterations as u32,
+                settings.max_iterations as u32,
+                started,
+                minimum_support,
+            );
+        }
+        if max_difference <= settings.tolerance {
+            converged = true;
+            break;
+        }
+    }
+    let residual = voters.iter().map(|v| v.imbalance(&backings)).max().unwrap_or(0);
+
+    for (slot, (_, backing)) in solution.winners.iter_mut().enumerate() {
+        *backing = backings[slot];
+    }
+    solution.assignments = voters
+        .iter()
+        .filter_map(|voter| {
+            let distribution: Vec<(u32, Perbill)> = voter
+                .edges
+                .iter()
+                .filter(|(_, weight)| *weight > 0)
+                .map(|(slot, weight)| (solution.winners[*slot].0, Perbill::from_rational(*weight, voter.budget)))
+                .collect();
+            if distribution.is_empty() {
+                return None;
+            }
+            let mut assignment = Assignment { who: voter.who, distribution };
+            // Rounding can leave the portions a few parts short of the whole
+            let _ = assignment.try_normalize();
+            Some(assignment)
+        })
+        .collect();
+
+    Ok(BalancingOutcome { iterations, residual, converged })
+}
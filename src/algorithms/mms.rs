@@ -0,0 +1,315 @@
+//! Maximin support (MMS) approximation
+//!
+//! Maximin support asks for the elected set and stake distribution whose
+//! smallest backing is as large as possible. Choosing the set is NP-hard, so
+//! [`Mms`] approximates it: PhragMMS, balancing after every round, picks the
+//! set, then [`balance_optimally`] distributes stake over that set with the
+//! largest possible minimum backing, found by parametric max-flow.
+//!
+//! This is experimental and meant for comparing other algorithms against a
+//! near-optimal minimal backing, not for producing on-chain solutions.
+
+use crate::algorithms::phragmms::phragmms;
+use crate::algorithms::prepared::PreparedElectionData;
+use crate::algorithms::trait_def::ElectionAlgorithm;
+use crate::cancellation::ElectionPhase;
+use crate::progress::RunControl;
+use crate::error::ElectionError;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_result::ElectionResult;
+use crate::types::AlgorithmType;
+use sp_npos_elections::{Assignment, BalancingConfig};
+use sp_runtime::Perbill;
+
+/// Balancing iterations PhragMMS runs after electing each candidate
+pub const MMS_BALANCING_ITERATIONS: usize = 2;
+
+/// Maximin support approximation: PhragMMS selection with max-flow balancing
+pub struct Mms;
+
+/// Stake distribution over a fixed elected set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalancedSupport {
+    /// Largest minimum backing any distribution over the set achieves
+    pub minimal_support: u128,
+    /// Backing of each elected candidate, in the order given
+    pub backings: Vec<u128>,
+    /// Stake of each voter on each of its elected targets: `(voter, candidate, amount)`
+    pub allocations: Vec<(u32, u32, u128)>,
+}
+
+impl ElectionAlgorithm for Mms {
+    fn execute_prepared(
+        &self,
+        prepared: &PreparedElectionData<'_>,
+        config: &ElectionConfiguration,
+    ) -> Result<ElectionResult, ElectionError> {
+        self.execute_controlled(prepared, config, &RunControl::new())
+    }
+
+    fn execute_controlled(
+        &self,
+        prepared: &PreparedElectionData<'_>,
+        config: &ElectionConfiguration,
+        control: &RunControl,
+    ) -> Result<ElectionResult, ElectionError> {
+        if prepared.candidate_count() == 0 {
+            return Err(ElectionError::ValidationError {
+                message: "Cannot run election with zero candidates".to_string(),
+                field: None,
+            });
+        }
+
+        let selection = phragmms(
+            prepared,
+            config.active_set_size as usize,
+            Some(BalancingConfig { iterations: MMS_BALANCING_ITERATIONS, tolerance: 0 }),
+            AlgorithmType::Mms,
+            control,
+        )?;
+
+        let winners: Vec<u32> = selection.winners.iter().map(|(winner, _)| *winner).collect();
+        control.cancel.check(ElectionPhase::Balancing)?;
+        let balanced = balance_optimally(prepared, &winners);
+
+        let mut assignments: Vec<Assignment<u32, Perbill>> = Vec::new();
+        for &(voter, candidate, amount) in &balanced.allocations {
+            let stake = prepared.voter_stake(voter as usize) as u128;
+            let portion = Perbill::from_rational(amount, stake.max(1));
+            match assignments.last_mut() {
+                Some(assignment) if assignment.who == voter => assignment.distribution.push((candidate, portion)),
+                _ => assignments.push(Assignment { who: voter, distribution: vec![(candidate, portion)] }),
+            }
+        }
+        let solution = sp_npos_elections::ElectionResult {
+            winners: winners.into_iter().zip(balanced.backings).collect(),
+            assignments,
+        };
+
+        let mut result = prepared.to_result(solution, AlgorithmType::Mms, config, control)?;
+        result.execution_metadata.balancing_iterations = Some(MMS_BALANCING_ITERATIONS as u32);
+        Ok(result)
+    }
+
+    fn name(&self) -> &'static str {
+        "mms"
+    }
+}
+
+/// Voter index, stake, and `(winner slot, flow edge)` pairs of a voter backing a winner
+type ConnectedVoter = (u32, u128, Vec<(usize, usize)>);
+
+/// Distribute voter stake over a fixed elected set maximizing the minimum backing
+///
+/// Binary searches the largest `t` for which a flow network (source to each
+/// voter with its stake, voter to each elected target, each elected candidate
+/// to sink with capacity `t`) saturates every candidate. Stake left over at
+/// that `t` goes to each voter's least backed elected target. Voters without
+/// an elected target are left out.
+pub fn balance_optimally(prepared: &PreparedElectionData<'_>, winners: &[u32]) -> BalancedSupport {
+    let mut winner_slot = vec![usize::MAX; prepared.candidate_count()];
+    for (slot, &winner) in winners.iter().enumerate() {
+        winner_slot[winner as usize] = slot;
+    }
+
+    // Nodes: source, sink, then one per connected voter, then one per winner
+    let mut network = FlowNetwork::new(2);
+    let mut voters: Vec<ConnectedVoter> = Vec::new();
+    let mut approval = vec![0u128; winners.len()];
+    let mut connected_stake = 0u128;
+    for voter in 0..prepared.voter_count() {
+        let stake = prepared.voter_stake(voter) as u128;
+        let mut targets: Vec<usize> = prepared
+            .voter_targets(voter)
+            .iter()
+            .map(|&target| winner_slot[target as usize])
+            .filter(|&slot| slot != usize::MAX)
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+        if stake == 0 || targets.is_empty() {
+            continue;
+        }
+        for &slot in &targets {
+            approval[slot] += stake;
+        }
+        connected_stake += stake;
+        voters.push((voter as u32, stake, targets.into_iter().map(|slot| (slot, 0)).collect()));
+    }
+
+    let first_winner_node = 2 + voters.len();
+    network.add_nodes(voters.len() + winners.len());
+    let mut source_edges = Vec::with_capacity(voters.len());
+    for (index, (_, stake, targets)) in voters.iter_mut().enumerate() {
+        source_edges.push(network.add_edge(FlowNetwork::SOURCE, 2 + index, *stake));
+        for (slot, edge) in targets.iter_mut() {
+            *edge = network.add_edge(2 + index, first_winner_node + *slot, *stake);
+        }
+    }
+    let sink_edges: Vec<usize> = (0..winners.len())
+        .map(|slot| network.add_edge(first_winner_node + slot, FlowNetwork::SINK, 0))
+        .collect();
+
+    let mut feasible = |t: u128| {
+        network.reset();
+        for &edge in &sink_edges {
+            network.set_capacity(edge, t);
+        }
+        network.max_flow() == t * winners.len() as u128
+    };
+
+    // No candidate can be backed by more than its approval stake or the average
+    let mut low = 0u128;
+    let mut high = match approval.iter().min() {
+        Some(&min_approval) => min_approval.min(connected_stake / winners.len() as u128),
+        None => 0,
+    };
+    while low < high {
+        let middle = low + (high - low + 1) / 2;
+        if feasible(middle) {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    feasible(low);
+
+    let mut backings = vec![0u128; winners.len()];
+    let mut allocations = Vec::new();
+    for (index, (voter, stake, targets)) in voters.iter().enumerate() {
+        let mut amounts: Vec<(usize, u128)> =
+            targets.iter().map(|&(slot, edge)| (slot, network.flow(edge))).collect();
+        let leftover = stake - network.flow(source_edges[index]);
+        for &(slot, amount) in &amounts {
+            backings[slot] += amount;
+        }
+        if leftover > 0 {
+            let position = (0..amounts.len()).min_by_key(|&i| backings[amounts[i].0]).unwrap_or(0);
+            amounts[position].1 += leftover;
+            backings[amounts[position].0] += leftover;
+        }
+        for (slot, amount) in amounts {
+            if amount > 0 {
+                allocations.push((*voter, winners[slot], amount));
+            }
+        }
+    }
+
+    BalancedSupport {
+        minimal_support: if winners.is_empty() { 0 } else { low },
+        backings,
+        allocations,
+    }
+}
+
+/// Residual graph for Dinic's max-flow algorithm
+struct FlowNetwork {
+    /// Outgoing edge indices per node; edge `e ^ 1` is the reverse of edge `e`
+    adjacency: Vec<Vec<usize>>,
+    targets: Vec<usize>,
+    capacities: Vec<u128>,
+    residual: Vec<u128>,
+    levels: Vec<u32>,
+    next_edge: Vec<usize>,
+}
+
+impl FlowNetwork {
+    const SOURCE: usize = 0;
+    const SINK: usize = 1;
+
+    fn new(nodes: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); nodes],
+            targets: Vec::new(),
+            capacities: Vec::new(),
+            residual: Vec::new(),
+            levels: Vec::new(),
+            next_edge: Vec::new(),
+        }
+    }
+
+    fn add_nodes(&mut self, count: usize) {
+        self.adjacency.resize(self.adjacency.len() + count, Vec::new());
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, capacity: u128) -> usize {
+        let edge = self.targets.len();
+        self.adjacency[from].push(edge);
+        self.targets.push(to);
+        self.capacities.push(capacity);
+        self.residual.push(capacity);
+        self.adjacency[to].push(edge + 1);
+        self.targets.push(from);
+        self.capacities.push(0);
+        self.residual.push(0);
+        edge
+    }
+
+    fn set_capacity(&mut self, edge: usize, capacity: u128) {
+        self.capacities[edge] = capacity;
+        self.residual[edge] = capacity;
+    }
+
+    /// Remove all flow
+    fn reset(&mut self) {
+        self.residual.copy_from_slice(&self.capacities);
+    }
+
+    /// Flow currently on `edge`
+    fn flow(&self, edge: usize) -> u128 {
+        self.capacities[edge] - self.residual[edge]
+    }
+
+    fn max_flow(&mut self) -> u128 {
+        let mut total = 0;
+        while self.build_levels() {
+            self.next_edge = vec![0; self.adjacency.len()];
+            loop {
+                let pushed = self.push(Self::SOURCE, u128::MAX);
+                if pushed == 0 {
+                    break;
+                }
+                total += pushed;
+            }
+        }
+        total
+    }
+
+    /// Breadth-first levels from the source; false once the sink is unreachable
+    fn build_levels(&mut self) -> bool {
+        self.levels = vec![u32::MAX; self.adjacency.len()];
+        self.levels[Self::SOURCE] = 0;
+        let mut queue = std::collections::VecDeque::from([Self::SOURCE]);
+        while let Some(node) = queue.pop_front() {
+            for &edge in &self.adjacency[node] {
+                let to = self.targets[edge];
+                if self.residual[edge] > 0 && self.levels[to] == u32::MAX {
+                    self.levels[to] = self.levels[node] + 1;
+                    queue.push_back(to);
+                }
+            }
+        }
+        self.levels[Self::SINK] != u32::MAX
+    }
+
+    /// Push up to `limit` along level-increasing paths from `node` to the sink
+    fn push(&mut self, node: usize, limit: u128) -> u128 {
+        if node == Self::SINK {
+            return limit;
+        }
+        while self.next_edge[node] < self.adjacency[node].len() {
+            let edge = self.adjacency[node][self.next_edge[node]];
+            let to = self.targets[edge];
+            if self.residual[edge] > 0 && self.levels[to] == self.levels[node] + 1 {
+                let pushed = self.push(to, limit.min(self.residual[edge]));
+                if pushed > 0 {
+                    self.residual[edge] -= pushed;
+                    self.residual[edge ^ 1] += pushed;
+                    return pushed;
+                }
+            }
+            self.next_edge[node] += 1;
+        }
+        0
+    }
+}
@@ -1,12 +1,19 @@
 //! Election algorithm implementations
 
 pub mod trait_def;
+pub mod prepared;
+pub mod balancing;
+pub mod phragmen;
+pub mod phragmms;
 pub mod sequential_phragmen;
 pub mod parallel_phragmen;
 pub mod multi_phase;
+pub mod mms;
 
 pub use trait_def::ElectionAlgorithm;
+pub use prepared::PreparedElectionData;
 pub use sequential_phragmen::SequentialPhragmen;
 pub use parallel_phragmen::ParallelPhragmen;
 pub use multi_phase::MultiPhase;
+pub use mms::Mms;
 
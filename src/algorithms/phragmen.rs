@@ -0,0 +1,212 @@
+//! Sequential Phragmén, round by round
+//!
+//! Follows `sp_npos_elections::seq_phragmen` step for step, with the same
+//! rational loads, rounding and normalization, so it elects the same winners
+//! with the same assignments. It is reimplemented here because the upstream
+//! version runs every round in one call, leaving no way to report progress
+//! or stop between rounds of a long election.
+
+use crate::algorithms::prepared::PreparedElectionData;
+use crate::cancellation::ElectionPhase;
+use crate::error::ElectionError;
+use crate::progress::RunControl;
+use crate::types::AlgorithmType;
+use sp_arithmetic::helpers_128bit::multiply_by_rational_with_rounding;
+use sp_arithmetic::traits::Bounded;
+use sp_arithmetic::{Normalizable, Rational128, Rounding};
+use sp_npos_elections::{Assignment, ElectionResult};
+use sp_runtime::Perbill;
+use std::time::Instant;
+
+/// Denominator of the initial candidate scores, as upstream
+const DEN: u128 = u128::MAX;
+
+/// A candidate's state across rounds
+struct Candidate {
+    score: Rational128,
+    approval_stake: u128,
+    backed_stake: u128,
+    elected: bool,
+    round: usize,
+}
+
+/// A vote from a voter to a candidate
+struct Edge {
+    candidate: u32,
+    load: Rational128,
+    weight: u128,
+}
+
+/// A voter with its load and votes
+struct Voter {
+    who: u32,
+    budget: u128,
+    load: Rational128,
+    edges: Vec<Edge>,
+}
+
+/// Elect `to_elect` candidates from `prepared` with sequential Phragmén
+///
+/// The run's cancellation token is checked before every round and each
+/// finished round is reported with the inverse of its winning score, the
+/// smallest backing the method guarantees every winner so far.
+pub fn seq_phragmen(
+    prepared: &PreparedElectionData<'_>,
+    to_elect: usize,
+    algorithm: AlgorithmType,
+    control: &RunControl,
+) -> Result<ElectionResult<u32, Perbill>, ElectionError> {
+    let arithmetic_error = || ElectionError::AlgorithmError {
+        message: match algorithm {
+            AlgorithmType::MultiPhase => "Multi-phase algorithm failed: ArithmeticError".to_string(),
+            _ => "Sequential phragmen algorithm failed: ArithmeticError".to_string(),
+        },
+        algorithm,
+    };
+
+    let (mut candidates, mut voters) = setup(prepared);
+    let to_elect = to_elect.min(candidates.len());
+    let started = Instant::now();
+
+    for round in 0..to_elect {
+        control.cancel.check_round(ElectionPhase::Selection, round as u32)?;
+
+        for candidate in candidates.iter_mut().filter(|c| !c.elected) {
+            candidate.score = DEN
+                .checked_div(candidate.approval_stake)
+                .map_or_else(Rational128::max_value, |n| Rational128::from(n, DEN));
+        }
+
+        for voter in &voters {
+            for edge in &voter.edges {
+                let candidate = &mut candidates[edge.candidate as usize];
+                if !candidate.elected && candidate.approval_stake != 0 {
+                    let temp_n = multiply_by_rational_with_rounding(
+                        voter.load.n(),
+                        voter.budget,
+                        candidate.approval_stake,
+                        Rounding::Down,
+                    )
+                    .unwrap_or(u128::MAX);
+                    let temp = Rational128::from(temp_n, voter.load.d());
+                    candidate.score = candidate.score.lazy_saturating_add(temp);
+                }
+            }
+        }
+
+        let Some((winner, _)) = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.elected)
+            .min_by_key(|(_, c)| c.score)
+        else {
+            break;
+        };
+        let candidate = &mut candidates[winner];
+        candidate.elected = true;
+        candidate.round = round;
+        let score = candidate.score;
+        for voter in &mut voters {
+            for edge in &mut voter.edges {
+                if edge.candidate as usize == winner {
+                    edge.load = score.lazy_saturating_sub(voter.load);
+                    voter.load = score;
+                }
+            }
+        }
+
+        if control.reports_progress() {
+            let minimum_support = score.d().checked_div(score.n()).unwrap_or(0);
+            control.report(
+                ElectionPhase::Selection,
+                round as u32 + 1,
+                to_elect as u32,
+                started,
+                Some(minimum_support),
+            );
+        }
+    }
+
+    for voter in &mut voters {
+        for edge in &mut voter.edges {
+            let candidate = &mut candidates[edge.candidate as usize];
+            edge.weight = if candidate.elected {
+                multiply_by_rational_with_rounding(voter.budget, edge.load.n(), voter.load.n(), Rounding::Down)
+                    .unwrap_or(u128::MAX)
+            } else {
+                0
+            };
+            candidate.backed_stake = candidate.backed_stake.saturating_add(edge.weight);
+        }
+        voter.edges.retain(|edge| edge.weight > 0);
+
+        let weights: Vec<u128> = voter.edges.iter().map(|edge| edge.weight).collect();
+        let normalized = weights.normalize(voter.budget).map_err(|_| arithmetic_error())?;
+        for (edge, corrected) in voter.edges.iter_mut().zip(normalized) {
+            let candidate = &mut candidates[edge.candidate as usize];
+            candidate.backed_stake = candidate.backed_stake.saturating_sub(edge.weight);
+            edge.weight = corrected;
+            candidate.backed_stake = candidate.backed_stake.saturating_add(edge.weight);
+        }
+    }
+
+    let mut winners: Vec<u32> = (0..candidates.len() as u32)
+        .filter(|&index| candidates[index as usize].elected)
+        .take(to_elect)
+        .collect();
+    winners.sort_by_key(|&index| candidates[index as usize].round);
+
+    let mut assignments: Vec<Assignment<u32, Perbill>> = voters
+        .into_iter()
+        .filter_map(|voter| {
+            let distribution: Vec<(u32, Perbill)> = voter
+                .edges
+                .iter()
+                .map(|edge| (edge.candidate, Perbill::from_rational(edge.weight, voter.budget)))
+                .filter(|(_, portion)| !portion.is_zero())
+                .collect();
+            (!distribution.is_empty()).then_some(Assignment { who: voter.who, distribution })
+        })
+        .collect();
+    for assignment in &mut assignments {
+        assignment.try_normalize().map_err(|_| arithmetic_error())?;
+    }
+
+    Ok(ElectionResult {
+        winners: winners
+            .into_iter()
+            .map(|index| (index, candidates[index as usize].backed_stake))
+            .collect(),
+        assignments,
+    })
+}
+
+/// Build candidates with their approval stake and voters with their votes,
+/// dropping repeated votes for the same candidate
+fn setup(prepared: &PreparedElectionData<'_>) -> (Vec<Candidate>, Vec<Voter>) {
+    let mut candidates: Vec<Candidate> = (0..prepared.candidate_count())
+        .map(|_| Candidate {
+            score: Rational128::default(),
+            approval_stake: 0,
+            backed_stake: 0,
+            elected: false,
+            round: 0,
+        })
+        .collect();
+
+    let mut voters = Vec::with_capacity(prepared.voter_count());
+    for voter in 0..prepared.voter_count() {
+        let budget = prepared.voter_stake(voter) as u128;
+        let mut edges: Vec<Edge> = Vec::with_capacity(prepared.voter_targets(voter).len());
+        for &target in prepared.voter_targets(voter) {
+            if edges.iter().any(|edge| edge.candidate == target) {
+                continue;
+            }
+            let candidate = &mut candidates[target as usize];
+            candidate.approval_stake = candidate.approval_stake.saturating_add(budget);
+            edges.push(Edge { candidate: target, load: Rational128::default(), weight: 0 });
+        }
+        voters.push(Voter { who: voter as u32, budget, load: Rational128::zero(), edges });
+    }
+    (candidates, voters)
+}
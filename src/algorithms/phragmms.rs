@@ -0,0 +1,313 @@
+//! PhragMMS, round by round
+//!
+//! Follows `sp_npos_elections::phragmms` step for step, with the same scores,
+//! stake moves, balancing and normalization, so it elects the same winners
+//! with the same assignments. It is reimplemented here for the same reason as
+//! [`seq_phragmen`](crate::algorithms::phragmen::seq_phragmen): the upstream
+//! version runs every round in one call, leaving no way to report progress or
+//! stop between rounds of a long election.
+
+use crate::algorithms::prepared::PreparedElectionData;
+use crate::cancellation::ElectionPhase;
+use crate::error::ElectionError;
+use crate::progress::RunControl;
+use crate::types::AlgorithmType;
+use sp_arithmetic::traits::Bounded;
+use sp_arithmetic::{Normalizable, PerThing, Rational128};
+use sp_npos_elections::{Assignment, BalancingConfig, ElectionResult};
+use sp_runtime::Perbill;
+use std::time::Instant;
+
+/// A candidate's state across rounds
+struct Candidate {
+    score: Rational128,
+    approval_stake: u128,
+    backed_stake: u128,
+    elected: bool,
+}
+
+/// A vote from a voter to a candidate
+struct Edge {
+    candidate: u32,
+    weight: u128,
+}
+
+/// A voter with its votes
+struct Voter {
+    who: u32,
+    budget: u128,
+    edges: Vec<Edge>,
+}
+
+/// Elect `to_elect` candidates from `prepared` with PhragMMS
+///
+/// With `balancing`, the votes are balanced after every round, as upstream.
+/// The run's cancellation token is checked before every round and each
+/// finished round is reported with the smallest backing of the winners so far.
+pub fn phragmms(
+    prepared: &PreparedElectionData<'_>,
+    to_elect: usize,
+    balancing: Option<BalancingConfig>,
+    algorithm: AlgorithmType,
+    control: &RunControl,
+) -> Result<ElectionResult<u32, Perbill>, ElectionError> {
+    let (mut candidates, mut voters) = setup(prepared);
+    let started = Instant::now();
+
+    let mut winners: Vec<u32> = Vec::new();
+    for round in 0..to_elect {
+        control.cancel.check_round(ElectionPhase::Selection, round as u32)?;
+
+        let Some(winner) = calculate_max_score(&mut candidates, &voters) else {
+            break;
+        };
+        apply_elected(&mut candidates, &mut voters, winner);
+        candidates[winner as usize].elected = true;
+        winners.push(winner);
+        if let Some(ref config) = balancing {
+            balance(&mut candidates, &mut voters, config);
+        }
+
+        if control.reports_progress() {
+            let minimum_support = winners.iter().map(|&winner| candidates[winner as usize].backed_stake).min();
+            control.report(
+                ElectionPhase::Selection,
+                round as u32 + 1,
+                to_elect as u32,
+                started,
+                minimum_support,
+            );
+        }
+    }
+
+    let mut assignments: Vec<Assignment<u32, Perbill>> = voters
+        .into_iter()
+        .filter_map(|voter| {
+            let distribution: Vec<(u32, Perbill)> = voter
+                .edges
+                .iter()
+                .map(|edge| (edge.candidate, Perbill::from_rational(edge.weight, voter.budget)))
+                .filter(|(_, portion)| !portion.is_zero())
+                .collect();
+            (!distribution.is_empty()).then_some(Assignment { who: voter.who, distribution })
+        })
+        .collect();
+    for assignment in &mut assignments {
+        assignment.try_normalize().map_err(|_| ElectionError::AlgorithmError {
+            message: match algorithm {
+                AlgorithmType::Mms => "MMS algorithm failed: ArithmeticError".to_string(),
+                _ => "Parallel phragmen algorithm failed: ArithmeticError".to_string(),
+            },
+            algorithm,
+        })?;
+    }
+
+    Ok(ElectionResult {
+        winners: winners
+            .into_iter()
+            .map(|winner| (winner, candidates[winner as usize].backed_stake))
+            .collect(),
+        assignments,
+    })
+}
+
+/// Score every candidate and return the unelected one with the highest score
+fn calculate_max_score(candidates: &mut [Candidate], voters: &[Voter]) -> Option<u32> {
+    let one: u128 = Perbill::ACCURACY.into();
+    for candidate in candidates.iter_mut().filter(|c| !c.elected) {
+        candidate.score = Rational128::from(1, one);
+    }
+
+    for voter in voters {
+        let mut denominator_contribution: u128 = 0;
+        for edge in &voter.edges {
+            let candidate = &candidates[edge.candidate as usize];
+            if candidate.elected {
+                let contribution: u128 =
+                    Perbill::from_rational(edge.weight, candidate.backed_stake).deconstruct().into();
+                denominator_contribution += contribution;
+            }
+        }
+        for edge in &voter.edges {
+            let candidate = &mut candidates[edge.candidate as usize];
+            if !candidate.elected {
+                let previous = candidate.score.d();
+                candidate.score = Rational128::from(1, denominator_contribution + previous);
+            }
+        }
+    }
+
+    let mut best_score = Rational128::zero();
+    let mut best_candidate = None;
+    for (index, candidate) in candidates.iter_mut().enumerate() {
+        if candidate.approval_stake > 0 {
+            let score_n = candidate.approval_stake.checked_mul(one).unwrap_or_else(Bounded::max_value);
+            candidate.score = Rational128::from(score_n, candidate.score.d());
+            if !candidate.elected && candidate.score > best_score {
+                best_score = candidate.score;
+                best_candidate = Some(index as u32);
+            }
+        } else {
+            candidate.score = Rational128::zero();
+        }
+    }
+    best_candidate
+}
+
+/// Move stake to `winner` from its voters' other targets backed above its score
+fn apply_elected(candidates: &mut [Candidate], voters: &mut [Voter], winner: u32) {
+    let cutoff = candidates[winner as usize]
+        .score
+        .to_den(1)
+        .expect("(n / d) < u128::MAX and (n' / 1) == (n / d), thus n' < u128::MAX'; qed.")
+        .n();
+
+    let mut elected_backed_stake = candidates[winner as usize].backed_stake;
+    for voter in voters.iter_mut() {
+        let Some(new_edge_index) = voter.edges.iter().position(|edge| edge.candidate == winner) else {
+            continue;
+        };
+        let used_budget: u128 = voter.edges.iter().map(|edge| edge.weight).sum();
+        let mut new_edge_weight = voter.budget.saturating_sub(used_budget);
+        elected_backed_stake = elected_backed_stake.saturating_add(new_edge_weight);
+
+        for (index, edge) in voter.edges.iter_mut().enumerate() {
+            if index == new_edge_index || edge.weight == 0 {
+                continue;
+            }
+            let candidate = &mut candidates[edge.candidate as usize];
+            if candidate.backed_stake > cutoff {
+                let stake_to_take = edge.weight.saturating_mul(cutoff) / candidate.backed_stake.max(1);
+                edge.weight = edge.weight.saturating_sub(stake_to_take);
+                candidate.backed_stake = candidate.backed_stake.saturating_sub(stake_to_take);
+                elected_backed_stake = elected_backed_stake.saturating_add(stake_to_take);
+                new_edge_weight = new_edge_weight.saturating_add(stake_to_take);
+            }
+        }
+        voter.edges[new_edge_index].weight = new_edge_weight;
+    }
+    candidates[winner as usize].backed_stake = elected_backed_stake;
+}
+
+/// Balance every voter's stake over its elected targets, as `sp_npos_elections::balance`
+fn balance(candidates: &mut [Candidate], voters: &mut [Voter], config: &BalancingConfig) {
+    if config.iterations == 0 {
+        return;
+    }
+    let mut iterations = 0;
+    loop {
+        let mut max_difference = 0;
+        for voter in voters.iter_mut() {
+            max_difference = max_difference.max(balance_voter(candidates, voter, config.tolerance));
+        }
+        iterations += 1;
+        if max_difference <= config.tolerance || iterations >= config.iterations {
+            break;
+        }
+    }
+}
+
+/// Spread one voter's stake so its elected targets end up as even as possible
+fn balance_voter(candidates: &mut [Candidate], voter: &mut Voter, tolerance: u128) -> u128 {
+    let mut elected: Vec<usize> =
+        (0..voter.edges.len()).filter(|&index| candidates[voter.edges[index].candidate as usize].elected).collect();
+    if elected.len() <= 1 {
+        return 0;
+    }
+    let backed = |candidates: &[Candidate], edge: &Edge| candidates[edge.candidate as usize].backed_stake;
+
+    let stake_used = elected.iter().fold(0u128, |total, &index| total.saturating_add(voter.edges[index].weight));
+    let min_backed = elected.iter().map(|&index| backed(candidates, &voter.edges[index])).min().unwrap_or(0);
+    let max_funded = elected
+        .iter()
+        .filter(|&&index| voter.edges[index].weight > 0)
+        .map(|&index| backed(candidates, &voter.edges[index]))
+        .max();
+    let difference = match max_funded {
+        Some(max_funded) => {
+            let difference =
+                max_funded.saturating_sub(min_backed).saturating_add(voter.budget.saturating_sub(stake_used));
+            if difference < tolerance {
+                return difference;
+            }
+            difference
+        }
+        None => voter.budget,
+    };
+
+    for &index in &elected {
+        let edge = &mut voter.edges[index];
+        let candidate = &mut candidates[edge.candidate as usize];
+        candidate.backed_stake = candidate.backed_stake.saturating_sub(edge.weight);
+        edge.weight = 0;
+    }
+    elected.sort_by_key(|&index| backed(candidates, &voter.edges[index]));
+
+    let mut cumulative = 0u128;
+    let mut last_index = elected.len() - 1;
+    for (position, &index) in elected.iter().enumerate() {
+        let backed_stake = backed(candidates, &voter.edges[index]);
+        if backed_stake.saturating_mul(position as u128).saturating_sub(cumulative) > voter.budget {
+            last_index = position.saturating_sub(1);
+            break;
+        }
+        cumulative = cumulative.saturating_add(backed_stake);
+    }
+    let last_stake = backed(candidates, &voter.edges[elected[last_index]]);
+    let ways = last_index + 1;
+    let excess = voter
+        .budget
+        .saturating_add(cumulative)
+        .saturating_sub(last_stake.saturating_mul(ways as u128));
+    for &index in &elected[..ways] {
+        let edge = &mut voter.edges[index];
+        let candidate = &mut candidates[edge.candidate as usize];
+        edge.weight = (excess / ways as u128).saturating_add(last_stake).saturating_sub(candidate.backed_stake);
+        candidate.backed_stake = candidate.backed_stake.saturating_add(edge.weight);
+    }
+
+    // Rounding can leave the elected weights short of the budget; upstream
+    // normalizes them in vote order and ignores a failure
+    elected.sort_unstable();
+    let weights: Vec<u128> = elected.iter().map(|&index| voter.edges[index].weight).collect();
+    if let Ok(normalized) = weights.normalize(voter.budget) {
+        for (&index, corrected) in elected.iter().zip(normalized) {
+            let edge = &mut voter.edges[index];
+            let candidate = &mut candidates[edge.candidate as usize];
+            candidate.backed_stake = candidate.backed_stake.saturating_sub(edge.weight);
+            edge.weight = corrected;
+            candidate.backed_stake = candidate.backed_stake.saturating_add(edge.weight);
+        }
+    }
+
+    difference
+}
+
+/// Build candidates with their approval stake and voters with their votes,
+/// dropping repeated votes for the same candidate
+fn setup(prepared: &PreparedElectionData<'_>) -> (Vec<Candidate>, Vec<Voter>) {
+    let mut candidates: Vec<Candidate> = (0..prepared.candidate_count())
+        .map(|_| Candidate {
+            score: Rational128::default(),
+            approval_stake: 0,
+            backed_stake: 0,
+            elected: false,
+        })
+        .collect();
+
+    let mut voters = Vec::with_capacity(prepared.voter_count());
+    for voter in 0..prepared.voter_count() {
+        let budget = prepared.voter_stake(voter) as u128;
+        let mut edges: Vec<Edge> = Vec::with_capacity(prepared.voter_targets(voter).len());
+        for &target in prepared.voter_targets(voter) {
+            if edges.iter().any(|edge| edge.candidate == target) {
+                continue;
+            }
+            let candidate = &mut candidates[target as usize];
+            candidate.approval_stake = candidate.approval_stake.saturating_add(budget);
+            edges.push(Edge { candidate: target, weight: 0 });
+        }
+        voters.push(Voter { who: voter as u32, budget, edges });
+    }
+    (candidates, voters)
+}
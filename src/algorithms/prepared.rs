@@ -0,0 +1,418 @@
+//! Prepared solver input in struct-of-arrays form
+//!
+//! Converting [`ElectionData`] for `sp-npos-elections` means resolving every
+//! vote to a candidate and copying account IDs. [`PreparedElectionData`] does
+//! that work once: candidates and voters are addressed by index, and each
+//! voter's targets are stored as a slice of one flat edge array (CSR layout).
+//! The same prepared data can then be fed to any algorithm repeatedly, e.g.
+//! when sweeping active set sizes. What-if runs prepare a shared snapshot
+//! with a [`SnapshotDelta`] on top instead of copying it per run.
+
+use crate::cancellation::ElectionPhase;
+use crate::progress::RunControl;
+use crate::error::ElectionError;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::{
+    ElectionCounts, ElectionResult, ExecutionMetadata, SelectedValidator, StakeAllocation,
+};
+use crate::models::nominator::Nominator;
+use crate::models::raw_solution::{RawAssignment, RawSolution, RawSupport, ResultDetail};
+use crate::models::snapshot_delta::SnapshotDelta;
+use crate::models::validator::ValidatorCandidate;
+use crate::types::AlgorithmType;
+use sp_runtime::Perbill;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// Solver-ready view of election data
+///
+/// Voters are the nominators with at least one vote for a known candidate;
+/// votes for unknown candidates are dropped, as the algorithms always did.
+#[derive(Debug, Clone)]
+pub struct PreparedElectionData<'a> {
+    data: Cow<'a, ElectionData>,
+    /// Candidates and nominators a delta adds or changes, see
+    /// [`with_delta`](Self::with_delta); entries are addressed after those of `data`
+    overlay: ElectionData,
+    /// Index into `data.candidates`, then `overlay.candidates`, for each candidate
+    candidate_sources: Vec<u32>,
+    /// Index into `data.nominators`, then `overlay.nominators`, for each voter
+    voter_nominators: Vec<u32>,
+    /// Stake of each voter, capped to the solver's vote weight type
+    voter_stakes: Vec<u64>,
+    /// Voter `v`'s targets are `edge_targets[edge_offsets[v]..edge_offsets[v + 1]]`
+    edge_offsets: Vec<usize>,
+    /// Candidate index of each vote
+    edge_targets: Vec<u32>,
+    total_nominator_stake: u128,
+    /// Every nominator, candidate and vote of the input
+    input_counts: ElectionCounts,
+    /// [`ElectionData::content_hash`], computed on first use
+    data_hash: std::sync::OnceLock<String>,
+}
+
+impl<'a> PreparedElectionData<'a> {
+    /// Prepare borrowed election data
+    pub fn new(data: &'a ElectionData) -> Self {
+        Self::from_cow(Cow::Borrowed(data))
+    }
+
+    /// Prepare owned election data
+    pub fn from_owned(data: ElectionData) -> PreparedElectionData<'static> {
+        PreparedElectionData::from_cow(Cow::Owned(data))
+    }
+
+    fn from_cow(data: Cow<'a, ElectionData>) -> Self {
+        let candidate_sources = (0..data.candidates.len() as u32).collect();
+        let nominator_sources: Vec<u32> = (0..data.nominators.len() as u32).collect();
+        Self::index(data, ElectionData::default(), candidate_sources, &nominator_sources)
+    }
+
+    /// Prepare `data` as [`SnapshotDelta::apply`] would leave it, without copying it
+    ///
+    /// Only the candidates and nominators the delta adds or changes are
+    /// copied. The result is the same as preparing a changed copy, except that
+    /// [`data`](Self::data) is `data` as given and [`data_hash`](Self::data_hash)
+    /// its hash, so runs on it leave the data hash unrecorded.
+    pub(crate) fn with_delta(data: &'a ElectionData, delta: &SnapshotDelta) -> Self {
+        let mut overlay = ElectionData::default();
+
+        let removed_candidates: HashSet<&str> = delta.removed_candidates.iter().map(String::as_str).collect();
+        let mut candidate_sources = Vec::with_capacity(data.candidates.len() + delta.added_candidates.len());
+        let mut candidate_ids = HashSet::with_capacity(candidate_sources.capacity());
+        for (index, candidate) in data.candidates.iter().enumerate() {
+            if !removed_candidates.contains(candidate.account_id.as_str()) {
+                candidate_ids.insert(candidate.account_id.as_str());
+                candidate_sources.push(index as u32);
+            }
+        }
+        for candidate in &delta.added_candidates {
+            if candidate_ids.insert(candidate.account_id.as_str()) {
+                candidate_sources.push((data.candidates.len() + overlay.candidates.len()) as u32);
+                overlay.candidates.push(candidate.clone());
+            }
+        }
+
+        // Nominators whose stake or votes the delta changes are copied into the overlay
+        let removed_nominators: HashSet<&str> = delta.removed_nominators.iter().map(String::as_str).collect();
+        let changed: HashSet<&str> = delta
+            .overrides
+            .nominator_stakes
+            .keys()
+            .chain(delta.overrides.voting_edges.iter().map(|edge| &edge.nominator_id))
+            .map(String::as_str)
+            .collect();
+        let mut nominator_sources = Vec::with_capacity(data.nominators.len() + delta.added_nominators.len());
+        let mut nominator_ids = HashSet::new();
+        for (index, nominator) in data.nominators.iter().enumerate() {
+            let id = nominator.account_id.as_str();
+            if removed_nominators.contains(id) {
+                continue;
+            }
+            nominator_ids.insert(id);
+            if changed.contains(id) {
+                nominator_sources.push((data.nominators.len() + overlay.nominators.len()) as u32);
+                overlay.nominators.push(delta.changed_nominator(nominator));
+            } else {
+                nominator_sources.push(index as u32);
+            }
+        }
+        for nominator in &delta.added_nominators {
+            if nominator_ids.insert(nominator.account_id.as_str()) {
+                nominator_sources.push((data.nominators.len() + overlay.nominators.len()) as u32);
+                overlay.nominators.push(delta.changed_nominator(nominator));
+            }
+        }
+
+        Self::index(Cow::Borrowed(data), overlay, candidate_sources, &nominator_sources)
+    }
+
+    /// Index the candidates at `candidate_sources` and the voters among the
+    /// nominators at `nominator_sources`
+    fn index(
+        data: Cow<'a, ElectionData>,
+        overlay: ElectionData,
+        candidate_sources: Vec<u32>,
+        nominator_sources: &[u32],
+    ) -> Self {
+        let mut prepared = Self {
+            data,
+            overlay,
+            candidate_sources,
+            voter_nominators: Vec::new(),
+            voter_stakes: Vec::new(),
+            edge_offsets: vec![0],
+            edge_targets: Vec::new(),
+            total_nominator_stake: 0,
+            input_counts: ElectionCounts::default(),
+            data_hash: std::sync::OnceLock::new(),
+        };
+
+        let candidate_index: HashMap<&str, u32> = (0..prepared.candidate_sources.len() as u32)
+            .map(|index| (prepared.candidate(index).account_id.as_str(), index))
+            .collect();
+        let mut voter_nominators = Vec::new();
+        let mut voter_stakes = Vec::new();
+        let mut edge_offsets = vec![0];
+        let mut edge_targets = Vec::new();
+        let mut total_nominator_stake = 0;
+        let mut input_edges = 0;
+        for &source in nominator_sources {
+            let nominator = prepared.nominator(source);
+            total_nominator_stake += nominator.stake;
+            input_edges += nominator.targets.len();
+            let start = edge_targets.len();
+            edge_targets.extend(
+                nominator
+                    .targets
+                    .iter()
+                    .filter_map(|target| candidate_index.get(target.as_str()).copied()),
+            );
+            if edge_targets.len() == start {
+                continue;
+            }
+            voter_nominators.push(source);
+            voter_stakes.push(nominator.stake.min(u64::MAX as u128) as u64);
+            edge_offsets.push(edge_targets.len());
+        }
+
+        let input_counts = ElectionCounts {
+            voters: nominator_sources.len(),
+            candidates: prepared.candidate_sources.len(),
+            edges: input_edges,
+        };
+        prepared.voter_nominators = voter_nominators;
+        prepared.voter_stakes = voter_stakes;
+        prepared.edge_offsets = edge_offsets;
+        prepared.edge_targets = edge_targets;
+        prepared.total_nominator_stake = total_nominator_stake;
+        prepared.input_counts = input_counts;
+        prepared
+    }
+
+    /// Candidate at solver index `index`
+    fn candidate(&self, index: u32) -> &ValidatorCandidate {
+        let source = self.candidate_sources[index as usize] as usize;
+        match source.checked_sub(self.data.candidates.len()) {
+            Some(added) => &self.overlay.candidates[added],
+            None => &self.data.candidates[source],
+        }
+    }
+
+    /// Nominator at index `source` of `data.nominators`, then `overlay.nominators`
+    fn nominator(&self, source: u32) -> &Nominator {
+        let source = source as usize;
+        match source.checked_sub(self.data.nominators.len()) {
+            Some(added) => &self.overlay.nominators[added],
+            None => &self.data.nominators[source],
+        }
+    }
+
+    /// The election data this was prepared from
+    pub fn data(&self) -> &ElectionData {
+        &self.data
+    }
+
+    /// Hash of the election data, computed once and reused for every run
+    pub fn data_hash(&self) -> &str {
+        self.data_hash.get_or_init(|| self.data.content_hash())
+    }
+
+    /// Number of candidates
+    pub fn candidate_count(&self) -> usize {
+        self.candidate_sources.len()
+    }
+
+    /// Every nominator, candidate and vote of the input, including nominators without known targets
+    pub(crate) fn input_counts(&self) -> ElectionCounts {
+        self.input_counts
+    }
+
+    /// Number of nominators with at least one vote for a known candidate
+    pub fn voter_count(&self) -> usize {
+        self.voter_nominators.len()
+    }
+
+    /// Number of votes for known candidates
+    pub fn edge_count(&self) -> usize {
+        self.edge_targets.len()
+    }
+
+    /// Stake of voter `voter` as the solver sees it
+    pub fn voter_stake(&self, voter: usize) -> u64 {
+        self.voter_stakes[voter]
+    }
+
+    /// Candidate indices voted for by voter `voter`
+    pub fn voter_targets(&self, voter: usize) -> &[u32] {
+        &self.edge_targets[self.edge_offsets[voter]..self.edge_offsets[voter + 1]]
+    }
+
+    /// Convert a solver solution back to account IDs, balancing it first if
+    /// the configuration asks for it
+    ///
+    /// With [`ResultDetail::Full`] the balanced solution is also kept as the
+    /// result's raw solution.
+    ///
+    /// Checks the run's cancellation token once the winners are known, and
+    /// between balancing rounds.
+    pub(crate) fn to_result(
+        &self,
+        mut solution: sp_npos_elections::ElectionResult<u32, Perbill>,
+        algorithm: AlgorithmType,
+        config: &ElectionConfiguration,
+        control: &RunControl,
+    ) -> Result<ElectionResult, ElectionError> {
+        control.cancel.check(ElectionPhase::Selection)?;
+        let balancing = config
+            .balancing
+            .map(|settings| crate::algorithms::balancing::balance(self, &mut solution, &settings, control))
+            .transpose()?;
+
+        // Count backers per candidate in one pass; `last_voter` keeps a voter
+        // listing the same target twice from being counted twice
+        let mut nominator_counts = vec![0u32; self.candidate_count()];
+        let mut last_voter = vec![u32::MAX; self.candidate_count()];
+        for assignment in &solution.assignments {
+            for (target, _) in &assignment.distribution {
+                let target = *target as usize;
+                if last_voter[target] != assignment.who {
+                    last_voter[target] = assignment.who;
+                    nominator_counts[target] += 1;
+                }
+            }
+        }
+
+        let selected_validators = solution
+            .winners
+            .iter()
+            .enumerate()
+            .map(|(rank, (winner, total_backing))| SelectedValidator {
+                account_id: self.candidate(*winner).account_id.clone(),
+                total_backing_stake: *total_backing,
+                nominator_count: nominator_counts[*winner as usize],
+                rank: Some(rank as u32 + 1),
+            })
+            .collect();
+
+        let mut stake_distribution = Vec::with_capacity(solution.assignments.len());
+        for assignment in &solution.assignments {
+            let nominator = self.nominator(self.voter_nominators[assignment.who as usize]);
+            for (target, portion) in &assignment.distribution {
+                stake_distribution.push(StakeAllocation {
+                    nominator_id: nominator.account_id.clone(),
+                    validator_id: self.candidate(*target).account_id.clone(),
+                    amount: *portion * nominator.stake,
+                    proportion: perbill_proportion(*portion),
+                    unrewarded: false,
+                });
+            }
+        }
+
+        let raw_solution = match config.result_detail {
+            ResultDetail::Summary => None,
+            ResultDetail::Full => Some(self.raw_solution(&solution)?),
+        };
+
+        Ok(ElectionResult {
+            selected_validators,
+            stake_distribution,
+            total_stake: self.total_nominator_stake,
+            algorithm_used: algorithm,
+            execution_metadata: ExecutionMetadata {
+                block_number: config.block_number,
+                execution_timestamp: Some(chrono::Utc::now().to_rfc3339()),
+                data_source: None,
+                applied_override_layers: Vec::new(),
+                rule_applications: Vec::new(),
+                data_issues: Vec::new(),
+                algorithm_duration_micros: None,
+                input_counts: None,
+                solver_counts: Some(ElectionCounts {
+                    voters: self.voter_count(),
+                    candidates: self.candidate_count(),
+                    edges: self.edge_count(),
+                }),
+                behavior_flags: Vec::new(),
+                balancing_iterations: Some(balancing.map_or(0, |b| b.iterations as u32)),
+                balancing_residual: balancing.map(|b| b.residual),
+                balancing_converged: balancing.map(|b| b.converged),
+                crate_version: None,
+                config_hash: None,
+                data_hash: None,
+                from_cache: false,
+            },
+            diagnostics: None,
+            issues: Vec::new(),
+            raw_solution,
+        })
+    }
+
+    /// The solver's solution with account IDs in place of indices
+    ///
+    /// Staked assignments and supports are derived with the same
+    /// `sp-npos-elections` helpers the runtime uses, from the voter stakes the
+    /// solver saw.
+    fn raw_solution(
+        &self,
+        solution: &sp_npos_elections::ElectionResult<u32, Perbill>,
+    ) -> Result<RawSolution, ElectionError> {
+        let candidate = |index: &u32| self.candidate(*index).account_id.clone();
+        let voter = |index: &u32| self.nominator(self.voter_nominators[*index as usize]).account_id.clone();
+
+        let staked = sp_npos_elections::assignment_ratio_to_staked_normalized(
+            solution.assignments.clone(),
+            |who: &u32| self.voter_stakes[*who as usize],
+        )
+        .map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to convert the solution to staked assignments: {:?}", e),
+            source: None,
+        })?;
+        let supports = sp_npos_elections::to_supports(&staked);
+
+        Ok(RawSolution {
+            winners: solution.winners.iter().map(|(winner, backing)| (candidate(winner), *backing)).collect(),
+            assignments: solution
+                .assignments
+                .iter()
+                .map(|assignment| RawAssignment {
+                    who: voter(&assignment.who),
+                    distribution: assignment
+                        .distribution
+                        .iter()
+                        .map(|(target, portion)| (candidate(target), portion.deconstruct()))
+                        .collect(),
+                })
+                .collect(),
+            staked_assignments: staked
+                .iter()
+                .map(|assignment| RawAssignment {
+                    who: voter(&assignment.who),
+                    distribution: assignment
+                        .distribution
+                        .iter()
+                        .map(|(target, stake)| (candidate(target), *stake))
+                        .collect(),
+                })
+                .collect(),
+            supports: supports
+                .into_iter()
+                .map(|(winner, support)| {
+                    let voters = support.voters.iter().map(|(who, stake)| (voter(who), *stake)).collect();
+                    (candidate(&winner), RawSupport { total: support.total, voters })
+                })
+                .collect(),
+        })
+    }
+}
+
+/// A voter's portion as the `f64` share reported in results
+///
+/// The solvers work on integers and `Perbill` only; this is the one place a
+/// float is made. Both operands are integers below 2^53, so the division is a
+/// single correctly rounded IEEE 754 operation with the same bits on every
+/// platform.
+fn perbill_proportion(portion: Perbill) -> f64 {
+    portion.deconstruct() as f64 / Perbill::one().deconstruct() as f64
+}
@@ -1,5 +1,8 @@
 //! Election algorithm trait definition
 
+use crate::algorithms::prepared::PreparedElectionData;
+use crate::cancellation::ElectionPhase;
+use crate::progress::RunControl;
 use crate::error::ElectionError;
 use crate::models::election_config::ElectionConfiguration;
 use crate::models::election_data::ElectionData;
@@ -12,10 +15,35 @@ pub trait ElectionAlgorithm {
         &self,
         data: &ElectionData,
         config: &ElectionConfiguration,
+    ) -> Result<ElectionResult, ElectionError> {
+        self.execute_prepared(&PreparedElectionData::new(data), config)
+    }
+
+    /// Execute the election algorithm on already prepared data
+    fn execute_prepared(
+        &self,
+        prepared: &PreparedElectionData<'_>,
+        config: &ElectionConfiguration,
     ) -> Result<ElectionResult, ElectionError>;
 
+    /// Execute on prepared data under `control`: stop with
+    /// `ElectionError::Cancelled` once its token is cancelled and report
+    /// finished rounds to its progress observer
+    ///
+    /// The built-in algorithms check the token between selection rounds, after
+    /// selecting the winners and between balancing rounds; every such round
+    /// is reported. The default checks the
+    /// token once, before running, and reports nothing.
+    fn execute_controlled(
+        &self,
+        prepared: &PreparedElectionData<'_>,
+        config: &ElectionConfiguration,
+        control: &RunControl,
+    ) -> Result<ElectionResult, ElectionError> {
+        control.cancel.check(ElectionPhase::Selection)?;
+        self.execute_prepared(prepared, config)
+    }
+
     /// Get the name of the algorithm
     fn name(&self) -> &'static str;
 }
-
-
@@ -0,0 +1,258 @@
+//! API keys, roles and per-key quotas for the server
+//!
+//! Without a key file the server accepts every request, which is only safe on
+//! localhost. With one, every route except `/health` and `/ready` needs a key in an
+//! `Authorization: Bearer <key>` or `X-API-Key` header. Read-only keys may
+//! only `GET` and call the JSON-RPC methods that read; submit keys may also
+//! run elections, fetch snapshots and upload datasets. Each key
+//! can be limited to a number of requests per minute and a number of
+//! elections running at once.
+
+use crate::api::handlers::ApiError;
+use crate::error::{ElectionError, ErrorSource};
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, Method};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Length of a rate limit window
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// What a key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiRole {
+    /// Fetch results, diagnostics, datasets and event streams
+    ReadOnly,
+    /// Also run elections and upload or delete datasets
+    Submit,
+}
+
+impl ApiRole {
+    /// Whether the role may make requests with `method`
+    pub fn allows(&self, method: &Method) -> bool {
+        match self {
+            ApiRole::ReadOnly => method == Method::GET || method == Method::HEAD,
+            ApiRole::Submit => true,
+        }
+    }
+
+    /// Whether the role may call the JSON-RPC method named `method`
+    pub fn allows_rpc_method(&self, method: &str) -> bool {
+        match self {
+            ApiRole::ReadOnly => crate::api::jsonrpc::READ_ONLY_METHODS.contains(&method),
+            ApiRole::Submit => true,
+        }
+    }
+}
+
+/// Key file: `{"keys": [...]}`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiKeyConfig {
+    /// Accepted keys
+    pub keys: Vec<ApiKeyEntry>,
+}
+
+/// One accepted key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyEntry {
+    /// Who the key belongs to, for logs and error messages
+    pub name: String,
+    /// Secret sent by clients
+    pub key: String,
+    /// What the key may do
+    pub role: ApiRole,
+    /// Most requests per minute; unlimited if absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+    /// Most elections running at once; unlimited if absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_jobs: Option<u32>,
+}
+
+/// A key with its usage
+#[derive(Debug)]
+struct KeyState {
+    entry: ApiKeyEntry,
+    window: Mutex<RateWindow>,
+    running_jobs: AtomicU32,
+}
+
+#[derive(Debug)]
+struct RateWindow {
+    started: Instant,
+    requests: u32,
+}
+
+/// Keys the server accepts, with their usage
+///
+/// Clones share usage counts.
+#[derive(Debug, Clone)]
+pub struct ApiKeys {
+    keys: Arc<HashMap<String, Arc<KeyState>>>,
+}
+
+impl ApiKeys {
+    /// Accept the keys in `config`
+    ///
+    /// Fails if a name or key is empty, a key is listed twice, or a quota is 0.
+    pub fn new(config: ApiKeyConfig) -> Result<Self, ElectionError> {
+        let mut keys = HashMap::new();
+        let mut names = HashSet::new();
+        for (index, entry) in config.keys.into_iter().enumerate() {
+            let invalid = |message: String, field: &str| ElectionError::ValidationError {
+                message,
+                field: Some(format!("keys[{}].{}", index, field)),
+            };
+            if entry.name.is_empty() || !names.insert(entry.name.clone()) {
+                return Err(invalid(format!("API key names must be unique and non-empty: '{}'", entry.name), "name"));
+            }
+            if entry.key.is_empty() || keys.contains_key(&entry.key) {
+                return Err(invalid(format!("API key of '{}' is empty or used by another entry", entry.name), "key"));
+            }
+            if entry.requests_per_minute == Some(0) {
+                return Err(invalid(format!("API key '{}' allows no requests", entry.name), "requests_per_minute"));
+            }
+            if entry.max_concurrent_jobs == Some(0) {
+                return Err(invalid(format!("API key '{}' allows no jobs", entry.name), "max_concurrent_jobs"));
+            }
+            let state = KeyState {
+                window: Mutex::new(RateWindow { started: Instant::now(), requests: 0 }),
+                running_jobs: AtomicU32::new(0),
+                entry,
+            };
+            keys.insert(state.entry.key.clone(), Arc::new(state));
+        }
+        Ok(Self { keys: Arc::new(keys) })
+    }
+
+    /// Load a key file
+    pub fn load_from_file(path: &Path) -> Result<Self, ElectionError> {
+        let file_error = |message: String, source: ErrorSource| ElectionError::FileError {
+            message,
+            path: path.to_path_buf(),
+            source: Some(source),
+        };
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| file_error(format!("Failed to read API key file: {}", e), ErrorSource::new(e)))?;
+        let config: ApiKeyConfig = serde_json::from_str(&content)
+            .map_err(|e| file_error(format!("Failed to parse API key file: {}", e), ErrorSource::new(e)))?;
+        Self::new(config)
+    }
+
+    /// Number of accepted keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no key is accepted
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Identify the caller presenting `key`, counting the request against its rate limit
+    pub fn authenticate(&self, key: &str) -> Result<Caller, ApiError> {
+        let state = self
+            .keys
+            .get(key)
+            .ok_or_else(|| ApiError::Unauthorized("Unknown API key".to_string()))?;
+        if let Some(limit) = state.entry.requests_per_minute {
+            let mut window = state.window.lock().unwrap();
+            let elapsed = window.started.elapsed();
+            if elapsed >= RATE_WINDOW {
+                *window = RateWindow { started: Instant::now(), requests: 0 };
+            } else if window.requests >= limit {
+                return Err(ApiError::RateLimited {
+                    message: format!("API key '{}' exceeded {} requests per minute", state.entry.name, limit),
+                    retry_after_secs: (RATE_WINDOW - elapsed).as_secs().max(1),
+                });
+            }
+            window.requests += 1;
+        }
+        Ok(Caller { key: Arc::clone(state) })
+    }
+}
+
+/// The key a request was made with, available to handlers as a request extension
+#[derive(Debug, Clone)]
+pub struct Caller {
+    key: Arc<KeyState>,
+}
+
+impl Caller {
+    /// Name of the key
+    pub fn name(&self) -> &str {
+        &self.key.entry.name
+    }
+
+    /// Role of the key
+    pub fn role(&self) -> ApiRole {
+        self.key.entry.role
+    }
+
+    /// Count an election against the key's concurrent job quota until the permit is dropped
+    pub fn start_job(&self) -> Result<JobPermit, ApiError> {
+        let limit = self.key.entry.max_concurrent_jobs.unwrap_or(u32::MAX);
+        self.key
+            .running_jobs
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| (running < limit).then_some(running + 1))
+            .map_err(|_| {
+                ApiError::QuotaExceeded(format!("API key '{}' already has {} elections running", self.name(), limit))
+            })?;
+        Ok(JobPermit { key: Arc::clone(&self.key) })
+    }
+}
+
+/// A running election counted against its key's quota
+#[derive(Debug)]
+pub struct JobPermit {
+    key: Arc<KeyState>,
+}
+
+impl Drop for JobPermit {
+    fn drop(&mut self) {
+        self.key.running_jobs.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Middleware rejecting requests without a key allowed to make them
+///
+/// Puts the [`Caller`] into the request's extensions for handlers that enforce job quotas.
+pub async fn authorize(
+    State(keys): State<ApiKeys>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let key = presented_key(request.headers())
+        .ok_or_else(|| ApiError::Unauthorized("Missing API key".to_string()))?;
+    let caller = keys.authenticate(key)?;
+    // JSON-RPC calls share `POST /`; the endpoint checks the role per method
+    let json_rpc = request.method() == Method::POST && request.uri().path() == "/";
+    if !json_rpc && !caller.role().allows(request.method()) {
+        return Err(ApiError::Forbidden(format!(
+            "API key '{}' is read-only and cannot {} {}",
+            caller.name(),
+            request.method(),
+            request.uri().path()
+        )));
+    }
+    request.extensions_mut().insert(caller);
+    Ok(next.run(request).await)
+}
+
+/// Key from `Authorization: Bearer <key>` or `X-API-Key: <key>`
+fn presented_key(headers: &HeaderMap) -> Option<&str> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    bearer
+        .or_else(|| headers.get("x-api-key").and_then(|value| value.to_str().ok()))
+        .map(str::trim)
+}
@@ -0,0 +1,158 @@
+//! Named election datasets kept in memory by the API server
+//!
+//! A full snapshot is hundreds of megabytes of JSON. Uploading it once with
+//! `PUT /datasets/{name}` and referring to it by name in election requests
+//! saves a UI from sending it again for every what-if run.
+
+use crate::api::handlers::ApiError;
+use crate::api::models::DatasetSummary;
+use crate::error::ElectionError;
+use crate::models::election_data::ElectionData;
+use crate::models::validation::ValidationProfile;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Largest dataset upload accepted, in bytes
+pub const MAX_DATASET_UPLOAD_BYTES: usize = 1024 * 1024 * 1024;
+
+/// Most datasets the server holds at once
+pub const MAX_DATASETS: usize = 16;
+
+/// Largest estimated memory all datasets may hold together, in bytes
+pub const MAX_DATASET_TOTAL_BYTES: usize = 4 * 1024 * 1024 * 1024;
+
+/// Longest dataset name accepted
+pub const MAX_DATASET_NAME_LEN: usize = 64;
+
+/// An uploaded dataset with its summary
+#[derive(Debug, Clone)]
+struct StoredDataset {
+    summary: DatasetSummary,
+    data: Arc<ElectionData>,
+    /// Estimated heap usage of the data
+    bytes: usize,
+}
+
+/// Datasets uploaded to the server, by name
+///
+/// Holds at most [`MAX_DATASETS`] datasets using an estimated
+/// [`MAX_DATASET_TOTAL_BYTES`] of memory together, unless
+/// [`with_limits`](Self::with_limits) sets others. Clones share the same
+/// datasets.
+#[derive(Debug, Clone)]
+pub struct DatasetRegistry {
+    datasets: Arc<RwLock<BTreeMap<String, StoredDataset>>>,
+    max_datasets: usize,
+    max_total_bytes: usize,
+}
+
+impl DatasetRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            datasets: Arc::default(),
+            max_datasets: MAX_DATASETS,
+            max_total_bytes: MAX_DATASET_TOTAL_BYTES,
+        }
+    }
+
+    /// Hold at most `max_datasets` datasets using `max_total_bytes` of
+    /// estimated memory together
+    pub fn with_limits(mut self, max_datasets: usize, max_total_bytes: usize) -> Self {
+        self.max_datasets = max_datasets;
+        self.max_total_bytes = max_total_bytes;
+        self
+    }
+
+    /// Store `data` under `name`, replacing any dataset already there
+    ///
+    /// The data is checked for structural problems only; elections run on it
+    /// validate it again with their own profile. Returns the new dataset's
+    /// summary and whether it replaced an earlier one, or
+    /// [`ApiError::DatasetQuotaExceeded`] if storing it would take the
+    /// registry over its limits; a replaced dataset no longer counts toward them.
+    pub async fn insert(
+        &self,
+        name: &str,
+        label: Option<String>,
+        data: ElectionData,
+    ) -> Result<(DatasetSummary, bool), ApiError> {
+        validate_dataset_name(name).map_err(ApiError::Election)?;
+        data.validate_with_profile(ValidationProfile::Permissive).map_err(ApiError::Election)?;
+        let bytes = data.memory_report().total();
+
+        let summary = DatasetSummary {
+            name: name.to_string(),
+            label,
+            data_hash: data.content_hash(),
+            candidate_count: data.candidates.len(),
+            nominator_count: data.nominators.len(),
+            block_number: data.metadata.as_ref().and_then(|m| m.block_number),
+            uploaded_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let stored = StoredDataset { summary: summary.clone(), data: Arc::new(data), bytes };
+
+        let mut datasets = self.datasets.write().await;
+        let others = datasets.iter().filter(|(stored_name, _)| stored_name.as_str() != name);
+        let (count, total_bytes) =
+            others.fold((1, bytes), |(count, total), (_, stored)| (count + 1, total.saturating_add(stored.bytes)));
+        if count > self.max_datasets {
+            return Err(ApiError::DatasetQuotaExceeded(format!(
+                "The server already holds {} datasets; delete one before uploading another",
+                self.max_datasets
+            )));
+        }
+        if total_bytes > self.max_total_bytes {
+            return Err(ApiError::DatasetQuotaExceeded(format!(
+                "Dataset '{}' needs about {} bytes, which would take datasets over their {} byte limit; delete one first",
+                name, bytes, self.max_total_bytes
+            )));
+        }
+        let replaced = datasets.insert(name.to_string(), stored).is_some();
+        Ok((summary, replaced))
+    }
+
+    /// Data of the dataset called `name`
+    pub async fn get(&self, name: &str) -> Option<Arc<ElectionData>> {
+        self.datasets.read().await.get(name).map(|stored| Arc::clone(&stored.data))
+    }
+
+    /// Summary of the dataset called `name`
+    pub async fn summary(&self, name: &str) -> Option<DatasetSummary> {
+        self.datasets.read().await.get(name).map(|stored| stored.summary.clone())
+    }
+
+    /// Summaries of every dataset, ordered by name
+    pub async fn list(&self) -> Vec<DatasetSummary> {
+        self.datasets.read().await.values().map(|stored| stored.summary.clone()).collect()
+    }
+
+    /// Drop the dataset called `name`; returns whether it existed
+    ///
+    /// Elections already run on it keep their copy of the data.
+    pub async fn remove(&self, name: &str) -> bool {
+        self.datasets.write().await.remove(name).is_some()
+    }
+}
+
+impl Default for DatasetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check that `name` is 1 to 64 ASCII letters, digits, `-`, `_` or `.`
+pub fn validate_dataset_name(name: &str) -> Result<(), ElectionError> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+    if name.is_empty() || name.len() > MAX_DATASET_NAME_LEN || !name.chars().all(allowed) {
+        return Err(ElectionError::ValidationError {
+            message: format!(
+                "Invalid dataset name '{}': use 1 to {} letters, digits, '-', '_' or '.'",
+                name, MAX_DATASET_NAME_LEN
+            ),
+            field: Some("name".to_string()),
+        });
+    }
+    Ok(())
+}
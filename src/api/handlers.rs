@@ -1,18 +1,36 @@
 //! REST API request handlers
 
-use crate::api::models::{DataSource, ElectionRequest, ElectionResponse, ErrorResponse};
+use crate::api::auth::Caller;
+use crate::api::datasets::DatasetRegistry;
+use crate::api::jobs::ElectionJobs;
+use crate::api::lifecycle::Lifecycle;
+use crate::api::models::{
+    DataSource, DatasetSummary, DatasetUploadQuery, ElectionAccepted, ElectionRequest, ElectionResponse,
+    ErrorResponse, JobEvent, JobWarning, PageQuery, ProgressUpdate, ScheduledRunsQuery,
+};
+use crate::cache::ResultCache;
 use crate::diagnostics::explainer::DiagnosticsGenerator;
 use crate::engine::ElectionEngine;
 use crate::error::ElectionError;
+use crate::input::indexer::IndexerLoader;
 use crate::input::rpc::RpcLoader;
 use crate::input::synthetic::SyntheticDataBuilder;
 use crate::models::election_config::ElectionConfiguration;
 use crate::models::election_data::ElectionData;
-use crate::types::AlgorithmType;
-use axum::extract::Path;
-use axum::http::StatusCode;
+use crate::models::election_result::{
+    BackingBreakdown, ElectionResult, NominatorAllocations, SelectedValidator, StakeAllocation,
+};
+use crate::models::result_index::{IndexedElectionResult, Page};
+use crate::models::validation::ValidationProfile;
+use crate::notifications::{NotificationEvent, Notifier};
+use crate::scheduler::{RunHistory, RunRecord};
+use crate::types::{AlgorithmType, ElectionKind};
+use axum::extract::{Extension, Path, Query};
+use axum::http::{header, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
+use futures::{Stream, StreamExt};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -21,8 +39,20 @@ use uuid::Uuid;
 /// Stored election data including result and original data for diagnostics
 #[derive(Clone)]
 struct StoredElection {
-    response: ElectionResponse,
-    original_data: ElectionData,
+    election_id: String,
+    execution_time_ms: Option<u64>,
+    result: Arc<IndexedElectionResult>,
+    original_data: Arc<ElectionData>,
+}
+
+impl StoredElection {
+    fn response(&self) -> ElectionResponse {
+        ElectionResponse {
+            election_id: self.election_id.clone(),
+            result: self.result.result().clone(),
+            execution_time_ms: self.execution_time_ms,
+        }
+    }
 }
 
 /// In-memory storage for election results (for demo purposes)
@@ -34,6 +64,20 @@ type ElectionStorage = Arc<RwLock<HashMap<String, StoredElection>>>;
 pub struct HandlerState {
     /// Storage for election results
     storage: ElectionStorage,
+    /// Notifier fired when an election job completes
+    notifier: Option<Arc<Notifier>>,
+    /// Datasets uploaded for use by name in election requests
+    datasets: DatasetRegistry,
+    /// Event streams of the elections run
+    jobs: ElectionJobs,
+    /// Results reused for identical election requests, if caching is on
+    result_cache: Option<ResultCache>,
+    /// Readiness and running jobs, for draining on shutdown
+    lifecycle: Lifecycle,
+    /// ID of the most recent election recorded as a prediction
+    latest_prediction: Arc<RwLock<Option<String>>>,
+    /// Runs of scheduled backtests, if a schedule is running
+    run_history: Option<RunHistory>,
 }
 
 impl HandlerState {
@@ -41,7 +85,118 @@ impl HandlerState {
     pub fn new() -> Self {
         Self {
             storage: Arc::new(RwLock::new(HashMap::new())),
+            notifier: None,
+            datasets: DatasetRegistry::new(),
+            jobs: ElectionJobs::new(),
+            result_cache: None,
+            lifecycle: Lifecycle::new(),
+            latest_prediction: Arc::default(),
+            run_history: None,
+        }
+    }
+
+    /// Send a notification whenever an election job completes
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = Some(Arc::new(notifier));
+        self
+    }
+
+    /// Serve datasets from `datasets`, e.g. one shared with another server
+    pub fn with_datasets(mut self, datasets: DatasetRegistry) -> Self {
+        self.datasets = datasets;
+        self
+    }
+
+    /// Answer requests for an election already run on the same data and
+    /// configuration from `cache`
+    pub fn with_result_cache(mut self, cache: ResultCache) -> Self {
+        self.result_cache = Some(cache);
+        self
+    }
+
+    /// Report readiness and count jobs through `lifecycle`, e.g. one a daemon drains on shutdown
+    pub fn with_lifecycle(mut self, lifecycle: Lifecycle) -> Self {
+        self.lifecycle = lifecycle;
+        self
+    }
+
+    /// Readiness and running jobs of the server
+    pub fn lifecycle(&self) -> &Lifecycle {
+        &self.lifecycle
+    }
+
+    /// Serve the runs of a schedule at `/schedule/runs`
+    pub fn with_run_history(mut self, history: RunHistory) -> Self {
+        self.run_history = Some(history);
+        self
+    }
+
+    /// Store an election run outside a request, such as a scheduled backtest,
+    /// under `election_id`, where it is served like any other election
+    pub async fn record_election(&self, election_id: &str, data: Arc<ElectionData>, result: ElectionResult) {
+        let response = ElectionResponse { election_id: election_id.to_string(), result, execution_time_ms: None };
+        self.jobs.register(election_id);
+        self.store(&response, data).await;
+        self.publish_outcome(election_id, &Ok(response));
+    }
+
+    /// Store a daemon's prediction at a snapshot block and make it the one
+    /// served at `/predictions/latest`
+    ///
+    /// Returns the election ID, under which it is served like any other election.
+    pub async fn record_prediction(&self, block_number: u64, data: Arc<ElectionData>, result: ElectionResult) -> String {
+        let election_id = format!("prediction-{}", block_number);
+        self.record_election(&election_id, data, result).await;
+        *self.latest_prediction.write().await = Some(election_id.clone());
+        election_id
+    }
+
+    /// Result of a stored election
+    pub(crate) async fn stored_result(&self, election_id: &str) -> Option<Arc<IndexedElectionResult>> {
+        self.storage.read().await.get(election_id).map(|stored| Arc::clone(&stored.result))
+    }
+
+    /// Datasets available to election requests by name
+    pub(crate) fn datasets(&self) -> &DatasetRegistry {
+        &self.datasets
+    }
+
+    /// Keep a finished election, with its input for diagnostics
+    async fn store(&self, response: &ElectionResponse, data: Arc<ElectionData>) {
+        self.storage.write().await.insert(response.election_id.clone(), StoredElection {
+            election_id: response.election_id.clone(),
+            execution_time_ms: response.execution_time_ms,
+            result: Arc::new(response.result.clone().indexed()),
+            original_data: data,
+        });
+    }
+
+    /// Publish a finished job's warnings and final event
+    fn publish_outcome(&self, election_id: &str, outcome: &Result<ElectionResponse, ApiError>) {
+        match outcome {
+            Ok(response) => {
+                let warnings = response.result.execution_metadata.data_issues.iter().cloned().map(JobWarning::Data)
+                    .chain(response.result.issues.iter().cloned().map(JobWarning::Result));
+                for warning in warnings {
+                    self.jobs.publish(election_id, JobEvent::Warning(warning));
+                }
+                self.jobs.publish(election_id, JobEvent::Completed {
+                    election_id: election_id.to_string(),
+                    execution_time_ms: response.execution_time_ms.unwrap_or_default(),
+                });
+            }
+            Err(e) => {
+                self.jobs.publish(election_id, JobEvent::Failed { error: e.parts().1 });
+            }
+        }
+    }
+
+    /// Refuse new jobs once the server is shutting down
+    pub(crate) fn accept_job(&self) -> Result<(), ApiError> {
+        if self.lifecycle.is_draining() {
+            return Err(ApiError::Unavailable("Server is shutting down; no new elections are accepted".to_string()));
         }
+        Ok(())
     }
 }
 
@@ -52,47 +207,165 @@ impl Default for HandlerState {
 }
 
 /// Run an election simulation
+///
+/// Its events can be followed at `/elections/{id}/events` like those of a
+/// started election, though the ID is only known from the response.
 pub async fn run_election(
     axum::extract::State(state): axum::extract::State<HandlerState>,
+    caller: Option<Extension<Caller>>,
     Json(request): Json<ElectionRequest>,
 ) -> Result<Json<ElectionResponse>, ApiError> {
-    let start_time = std::time::Instant::now();
+    run_request(&state, caller.map(|Extension(caller)| caller), &request).await.map(Json)
+}
+
+/// Run an election to completion for `caller`, as `POST /elections/run` and
+/// `offline_runElection` do
+pub(crate) async fn run_request(
+    state: &HandlerState,
+    caller: Option<Caller>,
+    request: &ElectionRequest,
+) -> Result<ElectionResponse, ApiError> {
+    state.accept_job()?;
+    let config = election_config(request)?;
+    let _permit = caller.map(|caller| caller.start_job()).transpose()?;
+    let _running = state.lifecycle.begin_job();
+    let election_id = Uuid::new_v4().to_string();
+    state.jobs.register(&election_id);
+    run_job(state, election_id, config, request).await
+}
 
+/// Start an election simulation in the background
+///
+/// Responds `202 Accepted` with the election ID once the request is valid.
+/// Follow the run at `/elections/{id}/events` and fetch the result from
+/// `/elections/{id}/results` after its `completed` event.
+pub async fn start_election(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    caller: Option<Extension<Caller>>,
+    Json(request): Json<ElectionRequest>,
+) -> Result<(StatusCode, Json<ElectionAccepted>), ApiError> {
+    state.accept_job()?;
+    let config = election_config(&request)?;
+    let permit = caller.map(|Extension(caller)| caller.start_job()).transpose()?;
+    let running = state.lifecycle.begin_job();
+    let election_id = Uuid::new_v4().to_string();
+    state.jobs.register(&election_id);
+
+    let job_id = election_id.clone();
+    tokio::spawn(async move {
+        // The outcome reaches clients through the job's final event
+        let _ = run_job(&state, job_id, config, &request).await;
+        drop(permit);
+        drop(running);
+    });
+    Ok((StatusCode::ACCEPTED, Json(ElectionAccepted { election_id })))
+}
+
+/// Stream an election's progress, warnings and completion as server-sent events
+pub async fn get_election_events(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Path(election_id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let events = state.jobs.subscribe(&election_id)
+        .ok_or_else(|| ApiError::NotFound(format!("Election not found: {}", election_id)))?;
+    let events = events.map(|event| Event::default().event(event.name()).json_data(&event));
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Build the election configuration a request asks for
+fn election_config(request: &ElectionRequest) -> Result<ElectionConfiguration, ApiError> {
     // Parse algorithm type
     let algorithm = request.algorithm.parse::<AlgorithmType>()
         .map_err(|e| ApiError::Validation(format!("Invalid algorithm: {}", e)))?;
 
-    // Load election data based on data source
-    let election_data = load_election_data(&request.data_source).await
-        .map_err(|e| ApiError::Election(e))?;
-
     // Create election configuration
-    let mut config = ElectionConfiguration::new()
+    let mut config = ElectionConfiguration::builder()
         .algorithm(algorithm)
-        .active_set_size(request.active_set_size);
+        .active_set_size(request.active_set_size)
+        .kind(request.kind)
+        .validation_profile(request.validation_profile)
+        .partial_results(request.partial_results)
+        .rounding(request.rounding)
+        .result_detail(request.result_detail);
 
     if let Some(block) = request.block_number {
         config = config.block_number(block);
     }
+    if let Some(ref runtime) = request.runtime {
+        config = config.runtime(runtime.chain.clone(), runtime.spec_version);
+    }
+    if let Some(max) = request.max_rewarded_nominators {
+        config = config.max_rewarded_nominators(max);
+    }
 
     // Apply overrides if present
     if let Some(ref overrides) = request.overrides {
         config = config.overrides(overrides.clone());
     }
+    for layer in &request.override_layers {
+        config = config.override_layer(layer.name.clone(), layer.overrides.clone());
+    }
+    if let Some(ref convictions) = request.convictions {
+        config = config.convictions(convictions.clone());
+    }
+    if let Some(ref rules) = request.candidate_rules {
+        config = config.candidate_rules(rules.clone());
+    }
+    if let Some(balancing) = request.balancing {
+        config = config.balancing(balancing.max_iterations, balancing.tolerance);
+    }
 
-    let config = config.build()
-        .map_err(|e| ApiError::Validation(e.to_string()))?;
+    config.build()
+        .map_err(|e| ApiError::Validation(e.to_string()))
+}
 
-    // Execute election
-    let engine = ElectionEngine::new();
-    let result = engine.execute(&config, &election_data)
-        .map_err(|e| ApiError::Election(e))?;
+/// Run a registered election job, publishing its warnings and final event
+async fn run_job(
+    state: &HandlerState,
+    election_id: String,
+    config: ElectionConfiguration,
+    request: &ElectionRequest,
+) -> Result<ElectionResponse, ApiError> {
+    let outcome = execute_job(state, election_id.clone(), config, request).await;
+    state.publish_outcome(&election_id, &outcome);
+    outcome
+}
+
+/// Load the data, run the election with progress going to the job's
+/// subscribers, and store and announce the result
+///
+/// The run stops between phases once the server cancels its jobs on shutdown.
+async fn execute_job(
+    state: &HandlerState,
+    election_id: String,
+    config: ElectionConfiguration,
+    request: &ElectionRequest,
+) -> Result<ElectionResponse, ApiError> {
+    let start_time = std::time::Instant::now();
+
+    // Load election data based on data source
+    let election_data = load_election_data(&state.datasets, &request.data_source, request.kind, request.validation_profile).await
+        .map_err(ApiError::Election)?;
+
+    // Execute election off the async workers, reporting each round to the job
+    let jobs = state.jobs.clone();
+    let job_id = election_id.clone();
+    let mut engine = ElectionEngine::new().with_progress_observer(move |event| {
+        jobs.publish(&job_id, JobEvent::Progress(ProgressUpdate::from(event)));
+    });
+    if let Some(ref cache) = state.result_cache {
+        engine = engine.with_result_cache(cache.clone());
+    }
+    let data = Arc::clone(&election_data);
+    let cancel = state.lifecycle.cancellation().clone();
+    let result = tokio::task::spawn_blocking(move || engine.execute_cancellable(&config, &data, &cancel)).await
+        .map_err(|e| ApiError::Internal(format!("Election task failed: {}", e)))?
+        .map_err(ApiError::Election)?;
 
     // Calculate execution time
     let execution_time_ms = start_time.elapsed().as_millis() as u64;
 
     // Create response
-    let election_id = Uuid::new_v4().to_string();
     let response = ElectionResponse {
         election_id: election_id.clone(),
         result,
@@ -100,12 +373,23 @@ pub async fn run_election(
     };
 
     // Store result with original data for diagnostics generation
-    state.storage.write().await.insert(election_id.clone(), StoredElection {
-        response: response.clone(),
-        original_data: election_data.clone(),
-    });
+    state.store(&response, election_data).await;
+
+    // Notify in the background so delivery does not delay the response
+    if let Some(ref notifier) = state.notifier {
+        let notifier = Arc::clone(notifier);
+        let event = NotificationEvent::JobCompleted {
+            election_id: election_id.clone(),
+            validator_count: response.result.validator_count(),
+            total_stake: response.result.total_stake.to_string(),
+            execution_time_ms: response.execution_time_ms,
+        };
+        tokio::spawn(async move {
+            notifier.send_all(&[event]).await;
+        });
+    }
 
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Get election results by ID
@@ -115,11 +399,130 @@ pub async fn get_election_results(
 ) -> Result<Json<ElectionResponse>, ApiError> {
     let storage = state.storage.read().await;
     storage.get(&election_id)
-        .map(|stored| stored.response.clone())
+        .map(StoredElection::response)
         .ok_or_else(|| ApiError::NotFound(format!("Election not found: {}", election_id)))
         .map(Json)
 }
 
+/// Get the most recent prediction recorded by a daemon watching the chain
+pub async fn get_latest_prediction(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+) -> Result<Json<ElectionResponse>, ApiError> {
+    let election_id = state.latest_prediction.read().await.clone()
+        .ok_or_else(|| ApiError::NotFound("No prediction has been made yet".to_string()))?;
+    get_election_results(axum::extract::State(state), Path(election_id)).await
+}
+
+/// List the runs of scheduled backtests, oldest first, optionally of one job
+pub async fn list_scheduled_runs(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Query(query): Query<ScheduledRunsQuery>,
+) -> Result<Json<Vec<RunRecord>>, ApiError> {
+    let history = state.run_history.as_ref()
+        .ok_or_else(|| ApiError::NotFound("No schedule is running".to_string()))?;
+    let runs = match query.job {
+        Some(ref job) => history.runs_of(job),
+        None => history.runs(),
+    };
+    Ok(Json(runs))
+}
+
+/// Report whether the server should receive traffic
+///
+/// Responds `503 Service Unavailable` while not ready or shutting down, so
+/// orchestrators stop routing requests to a draining instance.
+pub async fn readiness_check(
+    axum::extract::State(lifecycle): axum::extract::State<Lifecycle>,
+) -> (StatusCode, &'static str) {
+    if lifecycle.is_draining() {
+        (StatusCode::SERVICE_UNAVAILABLE, "DRAINING")
+    } else if lifecycle.is_ready() {
+        (StatusCode::OK, "READY")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "NOT_READY")
+    }
+}
+
+/// Get a page of an election's winners in active set order
+pub async fn get_election_winners(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Path(election_id): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<SelectedValidator>>, ApiError> {
+    let result = stored_result(&state, &election_id).await?;
+    let page = result
+        .winners_page(query.page(), query.page_size())
+        .map(SelectedValidator::clone);
+    Ok(Json(page))
+}
+
+/// Get a page of the stake allocations backing one validator
+pub async fn get_validator_allocations(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Path((election_id, validator_id)): Path<(String, String)>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<StakeAllocation>>, ApiError> {
+    let result = stored_result(&state, &election_id).await?;
+    if result.winner(&validator_id).is_none() {
+        return Err(ApiError::NotFound(format!(
+            "Validator {} was not elected in {}",
+            validator_id, election_id
+        )));
+    }
+    let page = result
+        .validator_allocations_page(&validator_id, query.page(), query.page_size())
+        .map(StakeAllocation::clone);
+    Ok(Json(page))
+}
+
+/// Get the backing breakdown of one validator
+pub async fn get_validator_backing(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Path((election_id, validator_id)): Path<(String, String)>,
+) -> Result<Json<BackingBreakdown>, ApiError> {
+    let result = stored_result(&state, &election_id).await?;
+    if result.winner(&validator_id).is_none() {
+        return Err(ApiError::NotFound(format!(
+            "Validator {} was not elected in {}",
+            validator_id, election_id
+        )));
+    }
+    Ok(Json(result.backing_of(&validator_id)))
+}
+
+/// Get where one nominator's stake was allocated
+pub async fn get_nominator_allocations(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Path((election_id, nominator_id)): Path<(String, String)>,
+) -> Result<Json<NominatorAllocations>, ApiError> {
+    let (result, data) = {
+        let storage = state.storage.read().await;
+        let stored = storage
+            .get(&election_id)
+            .ok_or_else(|| ApiError::NotFound(format!("Election not found: {}", election_id)))?;
+        (Arc::clone(&stored.result), Arc::clone(&stored.original_data))
+    };
+    let nominator = data.get_nominator(&nominator_id).ok_or_else(|| {
+        ApiError::NotFound(format!(
+            "Nominator {} did not take part in {}",
+            nominator_id, election_id
+        ))
+    })?;
+    Ok(Json(result.allocations_of(nominator)))
+}
+
+/// Look up a stored election's indexed result without holding the storage lock
+async fn stored_result(
+    state: &HandlerState,
+    election_id: &str,
+) -> Result<Arc<IndexedElectionResult>, ApiError> {
+    let storage = state.storage.read().await;
+    storage
+        .get(election_id)
+        .map(|stored| Arc::clone(&stored.result))
+        .ok_or_else(|| ApiError::NotFound(format!("Election not found: {}", election_id)))
+}
+
 /// Get election diagnostics by ID
 pub async fn get_election_diagnostics(
     axum::extract::State(state): axum::extract::State<HandlerState>,
@@ -131,7 +534,7 @@ pub async fn get_election_diagnostics(
 
     // Generate diagnostics from stored result and original data
     let diagnostics_gen = DiagnosticsGenerator::new();
-    let diagnostics = diagnostics_gen.generate(&stored.response.result, &stored.original_data)
+    let diagnostics = diagnostics_gen.generate(stored.result.result(), &stored.original_data)
         .map_err(|e| ApiError::Internal(format!("Failed to generate diagnostics: {}", e)))?;
 
     // Convert diagnostics to JSON
@@ -141,20 +544,77 @@ pub async fn get_election_diagnostics(
     Ok(Json(diagnostics_json))
 }
 
+/// Upload a dataset under a name, replacing any dataset of that name
+///
+/// Responds `201 Created` for a new name and `200 OK` when replacing.
+pub async fn put_dataset(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Path(name): Path<String>,
+    Query(query): Query<DatasetUploadQuery>,
+    Json(data): Json<ElectionData>,
+) -> Result<(StatusCode, Json<DatasetSummary>), ApiError> {
+    let (summary, replaced) = state.datasets.insert(&name, query.label, data).await?;
+    let status = if replaced { StatusCode::OK } else { StatusCode::CREATED };
+    Ok((status, Json(summary)))
+}
+
+/// List uploaded datasets
+pub async fn list_datasets(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+) -> Json<Vec<DatasetSummary>> {
+    Json(state.datasets.list().await)
+}
+
+/// Get the summary of an uploaded dataset
+pub async fn get_dataset(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Path(name): Path<String>,
+) -> Result<Json<DatasetSummary>, ApiError> {
+    state.datasets.summary(&name).await
+        .map(Json)
+        .ok_or_else(|| dataset_not_found(&name))
+}
+
+/// Delete an uploaded dataset
+pub async fn delete_dataset(
+    axum::extract::State(state): axum::extract::State<HandlerState>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    if state.datasets.remove(&name).await {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(dataset_not_found(&name))
+    }
+}
+
+fn dataset_not_found(name: &str) -> ApiError {
+    ApiError::NotFound(format!("Dataset not found: {}", name))
+}
+
 /// Load election data from the specified data source
-async fn load_election_data(data_source: &DataSource) -> Result<ElectionData, ElectionError> {
-    match data_source {
+///
+/// RPC sources read `pallet-elections-phragmen` storage for council elections.
+/// Uploaded datasets are shared, not copied.
+async fn load_election_data(
+    datasets: &DatasetRegistry,
+    data_source: &DataSource,
+    kind: ElectionKind,
+    profile: ValidationProfile,
+) -> Result<Arc<ElectionData>, ElectionError> {
+    let data = match data_source {
         DataSource::Rpc { url, block_number } => {
             let loader = RpcLoader::new(url)?;
-            if let Some(block) = block_number {
-                loader.load_at_block(*block).await
-            } else {
-                loader.load_latest().await
+            match (kind, block_number) {
+                (ElectionKind::Council, Some(block)) => loader.load_council_at_block(*block).await,
+                (ElectionKind::Council, None) => loader.load_council_latest().await,
+                (_, Some(block)) => loader.load_at_block(*block).await,
+                (_, None) => loader.load_latest().await,
             }
         }
+        DataSource::Indexer { url, era } => IndexerLoader::new(url)?.load_era(*era).await,
         DataSource::Json { data } => {
-            // Validate the provided data
-            data.validate()?;
+            // Validate the provided data; tolerated issues are reported in the result
+            data.validate_with_profile(profile)?;
             Ok(data.clone())
         }
         DataSource::Synthetic { candidates, nominators } => {
@@ -186,7 +646,16 @@ async fn load_election_data(data_source: &DataSource) -> Result<ElectionData, El
 
             builder.build()
         }
-    }
+        DataSource::Dataset { name } => {
+            let data = datasets.get(name).await.ok_or_else(|| ElectionError::ValidationError {
+                message: format!("Dataset not found: {}", name),
+                field: Some("data_source.name".to_string()),
+            })?;
+            data.validate_with_profile(profile)?;
+            return Ok(data);
+        }
+    };
+    data.map(Arc::new)
 }
 
 /// API error type
@@ -200,56 +669,92 @@ pub enum ApiError {
     NotFound(String),
     /// Internal server error
     Internal(String),
+    /// Missing or unknown API key
+    Unauthorized(String),
+    /// API key not allowed to make the request
+    Forbidden(String),
+    /// API key over its concurrent job quota
+    QuotaExceeded(String),
+    /// Dataset registry over its count or memory limit
+    DatasetQuotaExceeded(String),
+    /// Server shutting down and not taking new work
+    Unavailable(String),
+    /// API key over its request rate
+    RateLimited {
+        /// What was exceeded
+        message: String,
+        /// Seconds until a retry may succeed
+        retry_after_secs: u64,
+    },
 }
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (status, error_response) = match self {
+impl ApiError {
+    /// Status code and body of the error's response
+    pub fn parts(&self) -> (StatusCode, ErrorResponse) {
+        match self {
             ApiError::Validation(msg) => (
                 StatusCode::BAD_REQUEST,
-                ErrorResponse::validation_error(msg, None),
+                ErrorResponse::validation_error(msg.clone(), None),
             ),
             ApiError::Election(e) => {
-                let (status, error, message) = match e {
-                    ElectionError::ValidationError { message, field: _ } => (
-                        StatusCode::BAD_REQUEST,
-                        "VALIDATION_ERROR".to_string(),
-                        message,
-                    ),
-                    ElectionError::InsufficientCandidates { requested, available } => (
-                        StatusCode::BAD_REQUEST,
-                        "INSUFFICIENT_CANDIDATES".to_string(),
-                        format!("Requested {} candidates but only {} available", requested, available),
-                    ),
-                    ElectionError::RpcError { message, .. } => (
-                        StatusCode::BAD_GATEWAY,
-                        "RPC_ERROR".to_string(),
-                        message,
-                    ),
-                    ElectionError::AlgorithmError { message, .. } => (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        "ALGORITHM_ERROR".to_string(),
-                        message,
-                    ),
-                    _ => (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        "ELECTION_ERROR".to_string(),
-                        e.to_string(),
-                    ),
+                let status = match e {
+                    ElectionError::ValidationError { .. }
+                    | ElectionError::InsufficientCandidates { .. }
+                    | ElectionError::SchemaMismatch { .. } => StatusCode::BAD_REQUEST,
+                    ElectionError::RpcError { .. } => StatusCode::BAD_GATEWAY,
+                    ElectionError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+                    ElectionError::Cancelled { .. } => StatusCode::CONFLICT,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
                 };
-                (status, ErrorResponse::new(error, message))
+                (status, ErrorResponse::from_election_error(e))
             }
             ApiError::NotFound(msg) => (
                 StatusCode::NOT_FOUND,
-                ErrorResponse::new("NOT_FOUND".to_string(), msg),
+                ErrorResponse::new("NOT_FOUND".to_string(), msg.clone()),
             ),
             ApiError::Internal(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                ErrorResponse::new("INTERNAL_ERROR".to_string(), msg),
+                ErrorResponse::new("INTERNAL_ERROR".to_string(), msg.clone()),
             ),
-        };
+            ApiError::Unauthorized(msg) => (
+                StatusCode::UNAUTHORIZED,
+                ErrorResponse::new("UNAUTHORIZED".to_string(), msg.clone()),
+            ),
+            ApiError::Forbidden(msg) => (
+                StatusCode::FORBIDDEN,
+                ErrorResponse::new("FORBIDDEN".to_string(), msg.clone()),
+            ),
+            ApiError::QuotaExceeded(msg) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorResponse::new("QUOTA_EXCEEDED".to_string(), msg.clone()),
+            ),
+            ApiError::DatasetQuotaExceeded(msg) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                ErrorResponse::new("DATASET_QUOTA_EXCEEDED".to_string(), msg.clone()),
+            ),
+            ApiError::Unavailable(msg) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                ErrorResponse::new("UNAVAILABLE".to_string(), msg.clone()),
+            ),
+            ApiError::RateLimited { message, .. } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorResponse::new("RATE_LIMITED".to_string(), message.clone()),
+            ),
+        }
+    }
+}
 
-        (status, Json(error_response)).into_response()
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error_response) = self.parts();
+        let mut response = (status, Json(error_response)).into_response();
+        if let ApiError::RateLimited { retry_after_secs, .. } = self {
+            response.headers_mut().insert(header::RETRY_AFTER, retry_after_secs.into());
+        }
+        if let ApiError::Unauthorized(_) = self {
+            response.headers_mut().insert(header::WWW_AUTHENTICATE, header::HeaderValue::from_static("Bearer"));
+        }
+        response
     }
 }
 
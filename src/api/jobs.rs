@@ -0,0 +1,175 @@
+//! Live event streams of election jobs
+//!
+//! Every election the server runs is a job with a stream of [`JobEvent`]s:
+//! progress from the engine's progress observer, warnings for tolerated
+//! issues, and a final `completed` or `failed` event. Clients that subscribe
+//! late first get the latest progress event and every warning and final
+//! event sent so far, so a finished job can still be followed. Only the most
+//! recently finished jobs are kept; older ones are evicted as others finish.
+
+use crate::api::models::JobEvent;
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber before the slowest falls behind
+///
+/// A subscriber that falls behind misses progress events, never the final one.
+const EVENT_BUFFER: usize = 256;
+
+/// Finished jobs kept for late subscribers by default
+pub const DEFAULT_MAX_FINISHED_JOBS: usize = 1_000;
+
+/// Events of one job
+#[derive(Debug)]
+struct Job {
+    /// Latest progress event and every other event sent so far
+    history: Mutex<History>,
+    sender: broadcast::Sender<JobEvent>,
+}
+
+#[derive(Debug, Default)]
+struct History {
+    progress: Option<JobEvent>,
+    events: Vec<JobEvent>,
+}
+
+impl History {
+    fn replay(&self) -> Vec<JobEvent> {
+        self.progress.iter().chain(&self.events).cloned().collect()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.events.last().is_some_and(JobEvent::is_final)
+    }
+}
+
+/// ID and events of a finished job
+type FinishedJob = (String, Arc<Job>);
+
+/// Event streams of the server's election jobs, by election ID
+///
+/// Clones share the same jobs. Running jobs are always kept; once more than
+/// `max_finished` jobs have finished, the one that finished first is
+/// evicted. Streams already following an evicted job still end normally.
+#[derive(Debug, Clone)]
+pub struct ElectionJobs {
+    jobs: Arc<RwLock<HashMap<String, Arc<Job>>>>,
+    /// Finished jobs, in the order they finished
+    finished: Arc<Mutex<VecDeque<FinishedJob>>>,
+    max_finished: usize,
+}
+
+impl Default for ElectionJobs {
+    fn default() -> Self {
+        Self::with_max_finished(DEFAULT_MAX_FINISHED_JOBS)
+    }
+}
+
+impl ElectionJobs {
+    /// Create an empty set of jobs keeping [`DEFAULT_MAX_FINISHED_JOBS`] finished ones
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty set of jobs keeping at most `max_finished` finished ones
+    pub fn with_max_finished(max_finished: usize) -> Self {
+        Self {
+            jobs: Arc::default(),
+            finished: Arc::default(),
+            max_finished,
+        }
+    }
+
+    /// Start the event stream of the job `election_id`
+    pub fn register(&self, election_id: &str) {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        let job = Arc::new(Job { history: Mutex::new(History::default()), sender });
+        self.jobs.write().unwrap().insert(election_id.to_string(), job);
+    }
+
+    /// Send `event` to everyone following the job `election_id`
+    ///
+    /// Events after the final one are dropped. A final event may evict the
+    /// oldest finished job. Safe to call from the engine's worker threads.
+    pub fn publish(&self, election_id: &str, event: JobEvent) {
+        let Some(job) = self.job(election_id) else {
+            return;
+        };
+        let finishes = event.is_final();
+        {
+            let mut history = job.history.lock().unwrap();
+            if history.is_finished() {
+                return;
+            }
+            match event {
+                JobEvent::Progress(_) => history.progress = Some(event.clone()),
+                _ => history.events.push(event.clone()),
+            }
+            // Sent under the history lock so subscribers see each event exactly once
+            let _ = job.sender.send(event);
+        }
+        if finishes {
+            self.evict_finished(election_id, job);
+        }
+    }
+
+    /// Number of jobs kept, running or finished
+    pub fn len(&self) -> usize {
+        self.jobs.read().unwrap().len()
+    }
+
+    /// Whether no jobs are kept
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Follow the job `election_id`: the events sent so far, then live events
+    /// until the final one
+    ///
+    /// Returns `None` for unknown jobs.
+    pub fn subscribe(&self, election_id: &str) -> Option<impl Stream<Item = JobEvent> + Send + 'static> {
+        let job = self.job(election_id)?;
+        let (replay, finished, receiver) = {
+            let history = job.history.lock().unwrap();
+            (history.replay(), history.is_finished(), job.sender.subscribe())
+        };
+        let live = stream::unfold((receiver, finished), |(mut receiver, finished)| async move {
+            if finished {
+                return None;
+            }
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        let finished = event.is_final();
+                        return Some((event, (receiver, finished)));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+        Some(stream::iter(replay).chain(live))
+    }
+
+    fn job(&self, election_id: &str) -> Option<Arc<Job>> {
+        self.jobs.read().unwrap().get(election_id).cloned()
+    }
+
+    /// Record that `job` finished and drop the oldest finished jobs beyond `max_finished`
+    fn evict_finished(&self, election_id: &str, job: Arc<Job>) {
+        let mut finished = self.finished.lock().unwrap();
+        finished.push_back((election_id.to_string(), job));
+        while finished.len() > self.max_finished {
+            let Some((evicted_id, evicted)) = finished.pop_front() else {
+                break;
+            };
+            let mut jobs = self.jobs.write().unwrap();
+            // The ID may have been registered again since
+            if jobs.get(&evicted_id).is_some_and(|current| Arc::ptr_eq(current, &evicted)) {
+                jobs.remove(&evicted_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! JSON-RPC 2.0 interface following Substrate conventions
+//!
+//! Served at `POST /` next to the REST routes, so tools that already talk
+//! to Substrate nodes can point their JSON-RPC client at the server. Methods
+//! use the `offline_` prefix and accept positional or named parameters:
+//!
+//! - `offline_runElection(request)` runs an election like `POST /elections/run`
+//! - `offline_fetchSnapshot(url, block_number?, dataset?)` reads a snapshot from a chain
+//! - `offline_compare(baseline, other)` compares two elections run on the server
+//! - `rpc_methods()` lists the methods
+//!
+//! Read-only API keys may call `offline_compare` and `rpc_methods`; the
+//! other methods need a submit key. Batches are answered in one array and
+//! notifications get no response.
+//! Failures carry the REST API's [`ErrorResponse`] as `data`.
+
+use crate::api::auth::Caller;
+use crate::api::handlers::{run_request, ApiError, HandlerState};
+use crate::api::models::{
+    CompareParams, ElectionRequest, ErrorResponse, FetchSnapshotParams, JsonRpcError, JsonRpcRequest,
+    JsonRpcResponse,
+};
+use crate::input::rpc::RpcLoader;
+use crate::models::override_impact::OverrideImpact;
+use crate::models::result_index::IndexedElectionResult;
+use axum::body::Bytes;
+use axum::extract::{Extension, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Invalid JSON was received
+pub const PARSE_ERROR: i32 = -32700;
+/// The JSON sent is not a valid request object
+pub const INVALID_REQUEST: i32 = -32600;
+/// The method does not exist
+pub const METHOD_NOT_FOUND: i32 = -32601;
+/// Invalid method parameters, including requests the REST API rejects with 400
+pub const INVALID_PARAMS: i32 = -32602;
+/// A result could not be serialized
+pub const INTERNAL_ERROR: i32 = -32603;
+/// Any other failure of a call, described by the error's `data`
+pub const SERVER_ERROR: i32 = -32000;
+
+/// Methods served, as listed by `rpc_methods`
+pub const METHODS: [&str; 4] = ["offline_compare", "offline_fetchSnapshot", "offline_runElection", "rpc_methods"];
+
+/// Methods a read-only API key may call; the others need a submit key
+pub const READ_ONLY_METHODS: [&str; 2] = ["offline_compare", "rpc_methods"];
+
+impl From<ApiError> for JsonRpcError {
+    fn from(error: ApiError) -> Self {
+        let (status, response) = error.parts();
+        Self {
+            code: if status == StatusCode::BAD_REQUEST { INVALID_PARAMS } else { SERVER_ERROR },
+            message: response.message.clone(),
+            data: Some(Box::new(response)),
+        }
+    }
+}
+
+impl JsonRpcError {
+    fn new(code: i32, message: String) -> Self {
+        Self { code, message, data: None }
+    }
+}
+
+impl JsonRpcResponse {
+    fn new(id: Value, outcome: Result<Value, JsonRpcError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self { jsonrpc: "2.0".to_string(), result, error, id }
+    }
+}
+
+/// Answer a JSON-RPC request or batch
+///
+/// Responds `204 No Content` when every call was a notification.
+pub async fn json_rpc(
+    State(state): State<HandlerState>,
+    caller: Option<Extension<Caller>>,
+    body: Bytes,
+) -> Response {
+    let caller = caller.map(|Extension(caller)| caller);
+    let body: Value = match serde_json::from_slice(&body) {
+        Ok(body) => body,
+        Err(e) => {
+            let error = JsonRpcError::new(PARSE_ERROR, format!("Parse error: {}", e));
+            return Json(JsonRpcResponse::new(Value::Null, Err(error))).into_response();
+        }
+    };
+
+    match body {
+        Value::Array(calls) if calls.is_empty() => {
+            let error = JsonRpcError::new(INVALID_REQUEST, "Empty batch".to_string());
+            Json(JsonRpcResponse::new(Value::Null, Err(error))).into_response()
+        }
+        Value::Array(calls) => {
+            let mut responses = Vec::with_capacity(calls.len());
+            for call in calls {
+                responses.extend(handle_call(&state, caller.clone(), call).await);
+            }
+            if responses.is_empty() {
+                StatusCode::NO_CONTENT.into_response()
+            } else {
+                Json(responses).into_response()
+            }
+        }
+        call => match handle_call(&state, caller, call).await {
+            Some(response) => Json(response).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        },
+    }
+}
+
+/// Answer one call; `None` for notifications
+async fn handle_call(state: &HandlerState, caller: Option<Caller>, call: Value) -> Option<JsonRpcResponse> {
+    let id = call.get("id").cloned().unwrap_or(Value::Null);
+    let request: JsonRpcRequest = match serde_json::from_value(call) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = JsonRpcError::new(INVALID_REQUEST, format!("Invalid request: {}", e));
+            return Some(JsonRpcResponse::new(id, Err(error)));
+        }
+    };
+    if request.jsonrpc != "2.0" {
+        let error = JsonRpcError::new(INVALID_REQUEST, format!("Unsupported JSON-RPC version: {}", request.jsonrpc));
+        return Some(JsonRpcResponse::new(id, Err(error)));
+    }
+
+    let outcome = call_method(state, caller, &request.method, request.params).await;
+    request.id.map(|id| JsonRpcResponse::new(id, outcome))
+}
+
+async fn call_method(
+    state: &HandlerState,
+    caller: Option<Caller>,
+    method: &str,
+    params: Value,
+) -> Result<Value, JsonRpcError> {
+    if let Some(ref caller) = caller {
+        if METHODS.contains(&method) && !caller.role().allows_rpc_method(method) {
+            return Err(ApiError::Forbidden(format!(
+                "API key '{}' is read-only and cannot call {}",
+                caller.name(),
+                method
+            ))
+            .into());
+        }
+    }
+    match method {
+        "offline_runElection" => {
+            let request: ElectionRequest = single_param(params)?;
+            to_value(run_request(state, caller, &request).await?)
+        }
+        "offline_fetchSnapshot" => {
+            let params: FetchSnapshotParams = named_params(params, &["url", "block_number", "dataset"])?;
+            fetch_snapshot(state, caller, params).await
+        }
+        "offline_compare" => {
+            let params: CompareParams = named_params(params, &["baseline", "other"])?;
+            let baseline = stored_result(state, &params.baseline).await?;
+            let other = stored_result(state, &params.other).await?;
+            to_value(OverrideImpact::between(baseline.result(), other.result()))
+        }
+        "rpc_methods" => Ok(serde_json::json!({ "methods": METHODS })),
+        _ => Err(JsonRpcError::new(METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    }
+}
+
+/// Read a snapshot, returning it or, with a dataset name, storing it and
+/// returning the dataset's summary
+///
+/// A fetch counts as a job like an election run: it is refused while the
+/// server drains and counts against the caller's concurrent job quota.
+async fn fetch_snapshot(
+    state: &HandlerState,
+    caller: Option<Caller>,
+    params: FetchSnapshotParams,
+) -> Result<Value, JsonRpcError> {
+    state.accept_job()?;
+    let _permit = caller.map(|caller| caller.start_job()).transpose()?;
+    let _running = state.lifecycle().begin_job();
+    let loader = RpcLoader::new(&params.url).map_err(ApiError::Election)?;
+    let data = match params.block_number {
+        Some(block) => loader.load_at_block(block).await,
+        None => loader.load_latest().await,
+    }
+    .map_err(ApiError::Election)?;
+    match params.dataset {
+        Some(ref name) => {
+            let (summary, _) = state.datasets().insert(name, None, data).await?;
+            to_value(summary)
+        }
+        None => to_value(data),
+    }
+}
+
+async fn stored_result(
+    state: &HandlerState,
+    election_id: &str,
+) -> Result<Arc<IndexedElectionResult>, JsonRpcError> {
+    state
+        .stored_result(election_id)
+        .await
+        .ok_or_else(|| ApiError::NotFound(format!("Election not found: {}", election_id)).into())
+}
+
+/// The only parameter, given positionally as `[value]` or as the params object itself
+fn single_param<T: DeserializeOwned>(params: Value) -> Result<T, JsonRpcError> {
+    let param = match params {
+        Value::Array(mut values) if values.len() == 1 => values.remove(0),
+        Value::Array(values) => {
+            return Err(invalid_params(format!("Expected 1 parameter, got {}", values.len())));
+        }
+        other => other,
+    };
+    serde_json::from_value(param).map_err(|e| invalid_params(format!("Invalid parameters: {}", e)))
+}
+
+/// Parameters given by position in the order of `names`, or by name
+fn named_params<T: DeserializeOwned>(params: Value, names: &[&str]) -> Result<T, JsonRpcError> {
+    let params = match params {
+        Value::Array(values) if values.len() > names.len() => {
+            return Err(invalid_params(format!(
+                "Expected at most {} parameters, got {}",
+                names.len(),
+                values.len()
+            )));
+        }
+        Value::Array(values) => Value::Object(names.iter().map(|name| name.to_string()).zip(values).collect()),
+        Value::Null => Value::Object(Default::default()),
+        other => other,
+    };
+    serde_json::from_value(params).map_err(|e| invalid_params(format!("Invalid parameters: {}", e)))
+}
+
+fn invalid_params(message: String) -> JsonRpcError {
+    JsonRpcError {
+        code: INVALID_PARAMS,
+        data: Some(Box::new(ErrorResponse::validation_error(message.clone(), None))),
+        message,
+    }
+}
+
+fn to_value(value: impl Serialize) -> Result<Value, JsonRpcError> {
+    serde_json::to_value(value)
+        .map_err(|e| JsonRpcError::new(INTERNAL_ERROR, format!("Failed to serialize result: {}", e)))
+}
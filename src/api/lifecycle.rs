@@ -0,0 +1,118 @@
+//! Readiness and draining of a long-running server
+//!
+//! A [`Lifecycle`] is shared by the handlers and whoever runs the server, such
+//! as the `daemon` command. It answers `/ready`, counts the election jobs
+//! running, and on shutdown refuses new jobs, waits for the running ones and
+//! cancels those still running when the grace period ends.
+
+use crate::cancellation::CancellationToken;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+#[derive(Debug, Default)]
+struct LifecycleState {
+    ready: AtomicBool,
+    draining: AtomicBool,
+    running_jobs: AtomicUsize,
+    /// Woken whenever the last running job finishes
+    idle: Notify,
+    /// Held by every job's engine run
+    cancel: CancellationToken,
+}
+
+/// Readiness, running jobs and shutdown state of a server
+///
+/// Clones share the same state. A new lifecycle is ready; a long-running
+/// process can mark it not ready until its own startup work is done.
+#[derive(Debug, Clone)]
+pub struct Lifecycle {
+    state: Arc<LifecycleState>,
+}
+
+impl Lifecycle {
+    /// Create a ready lifecycle with no jobs running
+    pub fn new() -> Self {
+        let lifecycle = Self { state: Arc::default() };
+        lifecycle.set_ready(true);
+        lifecycle
+    }
+
+    /// Whether the server should receive traffic: ready and not draining
+    pub fn is_ready(&self) -> bool {
+        self.state.ready.load(Ordering::SeqCst) && !self.is_draining()
+    }
+
+    /// Mark the server ready or not, e.g. while a dependency is unavailable
+    pub fn set_ready(&self, ready: bool) {
+        self.state.ready.store(ready, Ordering::SeqCst);
+    }
+
+    /// Whether shutdown has begun
+    pub fn is_draining(&self) -> bool {
+        self.state.draining.load(Ordering::SeqCst)
+    }
+
+    /// Begin shutdown: report not ready and refuse new jobs
+    pub fn drain(&self) {
+        self.state.draining.store(true, Ordering::SeqCst);
+    }
+
+    /// Number of election jobs running
+    pub fn running_jobs(&self) -> usize {
+        self.state.running_jobs.load(Ordering::SeqCst)
+    }
+
+    /// Count a job as running until the guard is dropped
+    pub fn begin_job(&self) -> JobGuard {
+        self.state.running_jobs.fetch_add(1, Ordering::SeqCst);
+        JobGuard { state: Arc::clone(&self.state) }
+    }
+
+    /// Token every job's election run checks; cancelled by [`cancel_jobs`](Self::cancel_jobs)
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.state.cancel
+    }
+
+    /// Ask every running job, and any started afterwards, to stop
+    pub fn cancel_jobs(&self) {
+        self.state.cancel.cancel();
+    }
+
+    /// Wait up to `timeout` for every running job to finish
+    ///
+    /// Returns whether none is running.
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        let wait = async {
+            loop {
+                let idle = self.state.idle.notified();
+                if self.running_jobs() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        };
+        tokio::time::timeout(timeout, wait).await.is_ok()
+    }
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A running job counted by its [`Lifecycle`]
+#[derive(Debug)]
+pub struct JobGuard {
+    state: Arc<LifecycleState>,
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        if self.state.running_jobs.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.state.idle.notify_waiters();
+        }
+    }
+}
@@ -1,9 +1,17 @@
-//! REST API server for the Offline NPoS Election Tool
+//! REST and JSON-RPC API server for the Offline NPoS Election Tool
 
 pub mod server;
 pub mod handlers;
 pub mod models;
+pub mod auth;
+pub mod datasets;
+pub mod jobs;
+pub mod jsonrpc;
+pub mod lifecycle;
 
+pub use auth::ApiKeys;
+pub use datasets::DatasetRegistry;
+pub use lifecycle::Lifecycle;
 pub use server::ApiServer;
 
 
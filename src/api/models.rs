@@ -1,8 +1,19 @@
 //! REST API request/response models
 
+use crate::cancellation::ElectionPhase;
+use crate::error::ElectionError;
+use crate::models::candidate_rules::CandidateRules;
+use crate::models::conviction::ConvictionWeights;
+use crate::models::election_config::BalancingSettings;
 use crate::models::election_data::ElectionData;
-use crate::models::election_overrides::ElectionOverrides;
-use crate::models::election_result::ElectionResult;
+use crate::models::election_overrides::{ElectionOverrides, OverrideLayer};
+use crate::models::election_result::{ElectionResult, ResultIssue};
+use crate::models::raw_solution::ResultDetail;
+use crate::models::rounding::RoundingPolicy;
+use crate::models::runtime_behavior::RuntimeVersion;
+use crate::models::validation::{DataIssue, ValidationProfile};
+use crate::progress::ProgressEvent;
+use crate::types::ElectionKind;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -18,6 +29,39 @@ pub struct ElectionRequest {
     /// Optional parameter overrides
     #[serde(skip_serializing_if = "Option::is_none")]
     pub overrides: Option<ElectionOverrides>,
+    /// Named override layers applied in order on top of `overrides`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub override_layers: Vec<OverrideLayer>,
+    /// Conviction multipliers applied to nominator stakes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub convictions: Option<ConvictionWeights>,
+    /// Candidate inclusion and exclusion rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidate_rules: Option<CandidateRules>,
+    /// Kind of election, `staking` by default
+    #[serde(default)]
+    pub kind: ElectionKind,
+    /// Validation strictness, `chain-mirroring` by default
+    #[serde(default)]
+    pub validation_profile: ValidationProfile,
+    /// Balance the solution's stake distribution after the election
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balancing: Option<BalancingSettings>,
+    /// Continue past recoverable problems and list them in the result's `issues`
+    #[serde(default)]
+    pub partial_results: bool,
+    /// Rounding of derived ratios in the result, `exact` by default
+    #[serde(default, skip_serializing_if = "RoundingPolicy::is_exact")]
+    pub rounding: RoundingPolicy,
+    /// Include the solver's raw solution in the result, `summary` by default
+    #[serde(default, skip_serializing_if = "ResultDetail::is_summary")]
+    pub result_detail: ResultDetail,
+    /// Runtime whose election rules apply, e.g. `{"chain": "polkadot", "spec_version": 9180}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<RuntimeVersion>,
+    /// Nominators rewarded per validator; allocations beyond it are marked `unrewarded`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_rewarded_nominators: Option<u32>,
     /// Optional block number for RPC snapshot
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_number: Option<u64>,
@@ -35,11 +79,23 @@ pub enum DataSource {
         #[serde(skip_serializing_if = "Option::is_none")]
         block_number: Option<u64>,
     },
+    /// Fetch a historical era from a GraphQL indexer
+    Indexer {
+        /// Indexer GraphQL endpoint URL
+        url: String,
+        /// Era to load
+        era: u32,
+    },
     /// Use JSON data provided in request
     Json {
         /// Election data as JSON object
         data: ElectionData,
     },
+    /// Use a dataset uploaded with `PUT /datasets/{name}`
+    Dataset {
+        /// Name the dataset was uploaded under
+        name: String,
+    },
     /// Create synthetic data
     Synthetic {
         /// List of candidates
@@ -81,8 +137,161 @@ pub struct ElectionResponse {
     pub execution_time_ms: Option<u64>,
 }
 
-/// Error response model
+/// Query of `GET /schedule/runs`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduledRunsQuery {
+    /// Only list runs of this job
+    #[serde(default)]
+    pub job: Option<String>,
+}
+
+/// Response to `POST /elections/start`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElectionAccepted {
+    /// Identifier to follow the job at `/elections/{id}/events` and fetch its results by
+    pub election_id: String,
+}
+
+/// Event streamed from `GET /elections/{id}/events`
+///
+/// Sent as a server-sent event named after `type`, with the JSON as data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum JobEvent {
+    /// A round of selection or balancing finished
+    Progress(ProgressUpdate),
+    /// A data or result issue the run tolerated
+    Warning(JobWarning),
+    /// The election finished; results are available
+    Completed {
+        /// Identifier of the election
+        election_id: String,
+        /// Execution time in milliseconds
+        execution_time_ms: u64,
+    },
+    /// The election failed
+    Failed {
+        /// Error, as an error response would carry it
+        error: ErrorResponse,
+    },
+}
+
+impl JobEvent {
+    /// Server-sent event name, the same as `type`
+    pub fn name(&self) -> &'static str {
+        match self {
+            JobEvent::Progress(_) => "progress",
+            JobEvent::Warning(_) => "warning",
+            JobEvent::Completed { .. } => "completed",
+            JobEvent::Failed { .. } => "failed",
+        }
+    }
+
+    /// Whether this is the last event of its job
+    pub fn is_final(&self) -> bool {
+        matches!(self, JobEvent::Completed { .. } | JobEvent::Failed { .. })
+    }
+}
+
+/// Progress of a running election
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressUpdate {
+    /// Phase the round belongs to
+    pub phase: ElectionPhase,
+    /// Rounds finished so far, from 1
+    pub round: u32,
+    /// Most rounds the phase will run
+    pub total_rounds: u32,
+    /// Milliseconds since the phase started
+    pub elapsed_ms: u64,
+    /// Smallest backing guaranteed so far (as string to handle large numbers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_support: Option<String>,
+}
+
+impl From<&ProgressEvent> for ProgressUpdate {
+    fn from(event: &ProgressEvent) -> Self {
+        Self {
+            phase: event.phase,
+            round: event.round,
+            total_rounds: event.total_rounds,
+            elapsed_ms: event.elapsed.as_millis() as u64,
+            minimum_support: event.minimum_support.map(|support| support.to_string()),
+        }
+    }
+}
+
+/// Issue a run tolerated, as streamed in a `warning` event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JobWarning {
+    /// Data issue the validation profile tolerated
+    Data(DataIssue),
+    /// Problem recorded in partial-result mode
+    Result(ResultIssue),
+}
+
+/// Summary of a dataset uploaded to the server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetSummary {
+    /// Name the dataset is referenced by
+    pub name: String,
+    /// Free-form label given at upload, e.g. "Kusama era 6123"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Content hash of the data, as recorded in results run on it
+    pub data_hash: String,
+    /// Number of validator candidates
+    pub candidate_count: usize,
+    /// Number of nominators
+    pub nominator_count: usize,
+    /// Block the snapshot was taken at, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Upload time (RFC 3339)
+    pub uploaded_at: String,
+}
+
+/// Dataset upload query parameters (`?label=`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatasetUploadQuery {
+    /// Free-form label stored with the dataset
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Pagination query parameters (`?page=0&page_size=100`)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PageQuery {
+    /// Zero-based page number (default: 0)
+    #[serde(default)]
+    pub page: Option<usize>,
+    /// Items per page (default: 100, capped at 1000)
+    #[serde(default)]
+    pub page_size: Option<usize>,
+}
+
+impl PageQuery {
+    /// Default number of items per page
+    pub const DEFAULT_PAGE_SIZE: usize = 100;
+    /// Largest page size served
+    pub const MAX_PAGE_SIZE: usize = 1000;
+
+    /// Requested page, defaulting to the first
+    pub fn page(&self) -> usize {
+        self.page.unwrap_or(0)
+    }
+
+    /// Requested page size, clamped to `1..=MAX_PAGE_SIZE`
+    pub fn page_size(&self) -> usize {
+        self.page_size
+            .unwrap_or(Self::DEFAULT_PAGE_SIZE)
+            .clamp(1, Self::MAX_PAGE_SIZE)
+    }
+}
+
+/// Error response model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ErrorResponse {
     /// Error type/code
     pub error: String,
@@ -116,6 +325,107 @@ impl ErrorResponse {
             field,
         }
     }
+
+    /// Create an error response from an election error
+    ///
+    /// `error` is the error's stable code and `details` holds its serialized
+    /// fields, so API clients see the same error shape as the CLI prints.
+    pub fn from_election_error(error: &ElectionError) -> Self {
+        let message = match error {
+            ElectionError::ValidationError { message, .. }
+            | ElectionError::RpcError { message, .. }
+            | ElectionError::AlgorithmError { message, .. } => message.clone(),
+            ElectionError::InsufficientCandidates { requested, available } => {
+                format!("Requested {} candidates but only {} available", requested, available)
+            }
+            _ => error.to_string(),
+        };
+        let field = match error {
+            ElectionError::ValidationError { field, .. } => field.clone(),
+            _ => None,
+        };
+        let details = match serde_json::to_value(error) {
+            Ok(serde_json::Value::Object(fields)) => Some(fields.into_iter().collect()),
+            _ => None,
+        };
+        Self {
+            error: error.code().to_string(),
+            message,
+            details,
+            field,
+        }
+    }
+}
+
+
+
+/// JSON-RPC 2.0 request or notification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcRequest {
+    /// Protocol version, always "2.0"
+    pub jsonrpc: String,
+    /// Method to call, e.g. `offline_runElection`
+    pub method: String,
+    /// Positional (array) or named (object) parameters
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub params: serde_json::Value,
+    /// Request ID echoed in the response; `None` for notifications, which
+    /// get no response
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub id: Option<serde_json::Value>,
 }
 
+/// Deserialize a field that is present, even as `null`, into `Some`
+fn present<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<serde_json::Value>, D::Error> {
+    serde_json::Value::deserialize(deserializer).map(Some)
+}
 
+/// JSON-RPC 2.0 response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+    /// Protocol version, always "2.0"
+    pub jsonrpc: String,
+    /// Result of a successful call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    /// Error of a failed call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+    /// ID of the request; `null` if it could not be read
+    pub id: serde_json::Value,
+}
+
+/// JSON-RPC 2.0 error object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    /// Error code, see the constants in [`crate::api::jsonrpc`]
+    pub code: i32,
+    /// Human-readable error message
+    pub message: String,
+    /// The REST API's error response for the same failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Box<ErrorResponse>>,
+}
+
+/// Parameters of `offline_fetchSnapshot`: `[url, block_number?, dataset?]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchSnapshotParams {
+    /// RPC endpoint of the chain
+    pub url: String,
+    /// Block to read the snapshot at; the latest if not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Store the snapshot as a dataset of this name and return its summary
+    /// instead of the data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<String>,
+}
+
+/// Parameters of `offline_compare`: `[baseline, other]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareParams {
+    /// ID of the baseline election
+    pub baseline: String,
+    /// ID of the election compared with it
+    pub other: String,
+}
@@ -1,9 +1,18 @@
 //! REST API server
 
 use crate::api::handlers::HandlerState;
-use crate::error::ElectionError;
-use axum::routing::{get, post};
+use crate::cache::ResultCache;
+use crate::error::{ElectionError, ErrorSource};
+use crate::notifications::Notifier;
+use crate::scheduler::RunHistory;
+use crate::api::auth::{authorize, ApiKeys};
+use crate::api::datasets::{DatasetRegistry, MAX_DATASET_UPLOAD_BYTES};
+use crate::api::lifecycle::Lifecycle;
+use axum::extract::DefaultBodyLimit;
+use axum::middleware;
+use axum::routing::{get, post, put};
 use axum::Router;
+use std::future::Future;
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
 
@@ -11,26 +20,122 @@ use tokio::net::TcpListener;
 pub struct ApiServer {
     /// Port to listen on
     port: u16,
+    /// State shared by the handlers: notifier, datasets, result cache and lifecycle
+    state: HandlerState,
+    /// Keys required on every route but `/health` and `/ready`, if any
+    api_keys: Option<ApiKeys>,
 }
 
 impl ApiServer {
     /// Create a new API server
     pub fn new(port: u16) -> Self {
-        Self { port }
+        Self {
+            port,
+            state: HandlerState::new(),
+            api_keys: None,
+        }
     }
 
-    /// Start the server
-    pub async fn start(&self) -> Result<(), ElectionError> {
-        // Create handler state
-        let state = HandlerState::new();
+    /// Send notifications when election jobs complete
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.state = self.state.with_notifier(notifier);
+        self
+    }
+
+    /// Serve datasets from `datasets`, e.g. ones loaded before the server starts
+    pub fn with_datasets(mut self, datasets: DatasetRegistry) -> Self {
+        self.state = self.state.with_datasets(datasets);
+        self
+    }
+
+    /// Require one of `api_keys` on every route but `/health` and `/ready`
+    pub fn with_api_keys(mut self, api_keys: ApiKeys) -> Self {
+        self.api_keys = Some(api_keys);
+        self
+    }
+
+    /// Answer election requests already run on the same data and configuration from `cache`
+    pub fn with_result_cache(mut self, cache: ResultCache) -> Self {
+        self.state = self.state.with_result_cache(cache);
+        self
+    }
+
+    /// Report readiness and count running jobs through `lifecycle`
+    pub fn with_lifecycle(mut self, lifecycle: Lifecycle) -> Self {
+        self.state = self.state.with_lifecycle(lifecycle);
+        self
+    }
+
+    /// Serve the runs of a schedule at `/schedule/runs`
+    pub fn with_run_history(mut self, history: RunHistory) -> Self {
+        self.state = self.state.with_run_history(history);
+        self
+    }
+
+    /// State shared by the handlers, e.g. for recording predictions made outside a request
+    pub fn state(&self) -> &HandlerState {
+        &self.state
+    }
+
+    /// Build the router serving the API
+    pub fn router(&self) -> Router {
+        let state = self.state.clone();
 
         // Build the router
-        let app = Router::new()
+        let mut app = Router::new()
+            .route("/", post(crate::api::jsonrpc::json_rpc))
             .route("/elections/run", post(crate::api::handlers::run_election))
+            .route("/elections/start", post(crate::api::handlers::start_election))
+            .route("/elections/:election_id/events", get(crate::api::handlers::get_election_events))
             .route("/elections/:election_id/results", get(crate::api::handlers::get_election_results))
+            .route("/elections/:election_id/winners", get(crate::api::handlers::get_election_winners))
+            .route(
+                "/elections/:election_id/validators/:validator_id/allocations",
+                get(crate::api::handlers::get_validator_allocations),
+            )
+            .route(
+                "/elections/:election_id/validators/:validator_id/backing",
+                get(crate::api::handlers::get_validator_backing),
+            )
+            .route(
+                "/elections/:election_id/nominators/:nominator_id/allocations",
+                get(crate::api::handlers::get_nominator_allocations),
+            )
             .route("/elections/:election_id/diagnostics", get(crate::api::handlers::get_election_diagnostics))
-            .route("/health", get(health_check))
+            .route("/predictions/latest", get(crate::api::handlers::get_latest_prediction))
+            .route("/schedule/runs", get(crate::api::handlers::list_scheduled_runs))
+            .route("/datasets", get(crate::api::handlers::list_datasets))
+            .route(
+                "/datasets/:name",
+                put(crate::api::handlers::put_dataset)
+                    .get(crate::api::handlers::get_dataset)
+                    .delete(crate::api::handlers::delete_dataset)
+                    .layer(DefaultBodyLimit::max(MAX_DATASET_UPLOAD_BYTES)),
+            )
             .with_state(state);
+        if let Some(ref api_keys) = self.api_keys {
+            app = app.route_layer(middleware::from_fn_with_state(api_keys.clone(), authorize));
+        }
+        let readiness = get(crate::api::handlers::readiness_check).with_state(self.state.lifecycle().clone());
+        app.route("/health", get(health_check)).route("/ready", readiness)
+    }
+
+    /// Start the server
+    pub async fn start(&self) -> Result<(), ElectionError> {
+        self.start_with_shutdown(std::future::pending()).await
+    }
+
+    /// Start the server and stop it gracefully once `shutdown` completes
+    ///
+    /// After `shutdown`, no new connections are accepted and the server
+    /// returns once every request in flight has been answered. Elections
+    /// started in the background are not awaited; wait for them with
+    /// [`Lifecycle::wait_idle`].
+    pub async fn start_with_shutdown(
+        &self,
+        shutdown: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<(), ElectionError> {
+        let app = self.router();
 
         // Create the address
         let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
@@ -39,17 +144,37 @@ impl ApiServer {
         let listener = TcpListener::bind(addr).await
             .map_err(|e| ElectionError::InvalidData {
                 message: format!("Failed to bind to port {}: {}", self.port, e),
+                source: Some(ErrorSource::new(e)),
             })?;
 
         eprintln!("🚀 API server listening on http://{}", addr);
+        match self.api_keys {
+            Some(ref api_keys) => eprintln!("   API keys required ({} accepted)", api_keys.len()),
+            None => eprintln!("   ⚠️  No API keys configured; every request is accepted"),
+        }
+        eprintln!("   POST   /  (JSON-RPC 2.0: offline_runElection, offline_fetchSnapshot, offline_compare)");
         eprintln!("   POST   /elections/run");
+        eprintln!("   POST   /elections/start");
+        eprintln!("   GET    /elections/:id/events");
         eprintln!("   GET    /elections/:id/results");
+        eprintln!("   GET    /elections/:id/winners?page=&page_size=");
+        eprintln!("   GET    /elections/:id/validators/:validator_id/allocations?page=&page_size=");
+        eprintln!("   GET    /elections/:id/validators/:validator_id/backing");
+        eprintln!("   GET    /elections/:id/nominators/:nominator_id/allocations");
         eprintln!("   GET    /elections/:id/diagnostics");
+        eprintln!("   GET    /predictions/latest");
+        eprintln!("   GET    /schedule/runs?job=");
+        eprintln!("   PUT    /datasets/:name?label=");
+        eprintln!("   GET    /datasets");
+        eprintln!("   GET    /datasets/:name");
+        eprintln!("   DELETE /datasets/:name");
         eprintln!("   GET    /health");
+        eprintln!("   GET    /ready");
 
-        axum::serve(listener, app).await
+        axum::serve(listener, app).with_graceful_shutdown(shutdown).await
             .map_err(|e| ElectionError::InvalidData {
                 message: format!("Server error: {}", e),
+                source: Some(ErrorSource::new(e)),
             })?;
 
         Ok(())
@@ -0,0 +1,123 @@
+//! Caching of election results
+//!
+//! Analysts exploring the same era often run identical elections. A
+//! [`ResultCache`] keeps recent results keyed by the content hashes of the
+//! input data and configuration, so an engine built with
+//! [`with_result_cache`](crate::engine::ElectionEngine::with_result_cache)
+//! returns a repeated election's result without running it again.
+
+use crate::models::election_result::ElectionResult;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// What a cached result was computed from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// [`ElectionData::content_hash`](crate::models::election_data::ElectionData::content_hash) of the input
+    pub data_hash: String,
+    /// [`ElectionConfiguration::content_hash`](crate::models::election_config::ElectionConfiguration::content_hash) of the configuration
+    pub config_hash: String,
+    /// Whether the result includes diagnostics
+    pub diagnostics: bool,
+}
+
+/// Hits and misses of a [`ResultCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that had to run the election
+    pub misses: u64,
+    /// Results held
+    pub entries: usize,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    results: HashMap<CacheKey, (Arc<ElectionResult>, u64)>,
+    /// Incremented on every use; entries remember when they were last used
+    clock: u64,
+}
+
+/// Recent election results, by data and configuration hash
+///
+/// Holds at most `capacity` results, dropping the least recently used first.
+/// Clones share the same results, so one cache can serve several engines.
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    capacity: usize,
+    entries: Arc<Mutex<Entries>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl ResultCache {
+    /// Create a cache holding at most `capacity` results; 0 caches nothing
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Arc::default(),
+            hits: Arc::default(),
+            misses: Arc::default(),
+        }
+    }
+
+    /// Most results held
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Result cached for `key`, counting a hit or a miss
+    pub fn get(&self, key: &CacheKey) -> Option<Arc<ElectionResult>> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.clock += 1;
+        let now = entries.clock;
+        match entries.results.get_mut(key) {
+            Some((result, last_used)) => {
+                *last_used = now;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(Arc::clone(result))
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Cache `result` under `key`, evicting the least recently used result when full
+    pub fn insert(&self, key: CacheKey, result: ElectionResult) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        entries.clock += 1;
+        let now = entries.clock;
+        if !entries.results.contains_key(&key) && entries.results.len() >= self.capacity {
+            let oldest = entries
+                .results
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.results.remove(&oldest);
+            }
+        }
+        entries.results.insert(key, (Arc::new(result), now));
+    }
+
+    /// Drop every cached result
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().results.clear();
+    }
+
+    /// Hits, misses and entries so far
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().results.len(),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Cooperative cancellation of running elections
+//!
+//! A [`CancellationToken`] is shared between whoever starts an election with
+//! [`ElectionEngine::execute_cancellable`](crate::engine::ElectionEngine::execute_cancellable)
+//! and whoever may want to stop it, e.g. a TUI key handler or a REST job
+//! endpoint. The engine and the built-in algorithms check the token between
+//! phases and between rounds of their own loops, and return
+//! [`ElectionError::Cancelled`] naming the phase they stopped in.
+
+use crate::error::ElectionError;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Stage of an election run, as reported when it is cancelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ElectionPhase {
+    /// Validating the data and applying overrides, convictions and candidate rules
+    Input,
+    /// Electing the winners
+    Selection,
+    /// Balancing stake over the elected set
+    Balancing,
+    /// Generating diagnostics
+    Diagnostics,
+}
+
+impl ElectionPhase {
+    /// Lowercase name, e.g. "balancing"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ElectionPhase::Input => "input",
+            ElectionPhase::Selection => "selection",
+            ElectionPhase::Balancing => "balancing",
+            ElectionPhase::Diagnostics => "diagnostics",
+        }
+    }
+}
+
+impl std::fmt::Display for ElectionPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Flag asking a running election to stop
+///
+/// Clones share the flag, so keep one clone to call [`cancel`](Self::cancel)
+/// on and pass another to the engine. Cancelling is permanent.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every election holding this token to stop
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Return `ElectionError::Cancelled` if cancelled, before `phase` runs or finishes
+    pub fn check(&self, phase: ElectionPhase) -> Result<(), ElectionError> {
+        if !self.is_cancelled() {
+            return Ok(());
+        }
+        Err(ElectionError::Cancelled {
+            message: format!("Election cancelled during {}", phase),
+            phase: Some(phase),
+            rounds_completed: None,
+        })
+    }
+
+    /// Like [`check`](Self::check), recording how many rounds of `phase` had finished
+    pub fn check_round(&self, phase: ElectionPhase, rounds_completed: u32) -> Result<(), ElectionError> {
+        if !self.is_cancelled() {
+            return Ok(());
+        }
+        Err(ElectionError::Cancelled {
+            message: format!("Election cancelled during {} after {} rounds", phase, rounds_completed),
+            phase: Some(phase),
+            rounds_completed: Some(rounds_completed),
+        })
+    }
+}
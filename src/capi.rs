@@ -0,0 +1,201 @@
+//! C ABI for embedding the solver (requires the `capi` feature)
+//!
+//! Lets non-Rust services run elections in-process instead of spawning the
+//! CLI. Engines and loaded data are opaque handles owned by the caller;
+//! data, configurations and results cross the boundary as JSON in the same
+//! shapes the CLI reads and writes. Build the shared library with
+//!
+//! ```text
+//! cargo build --release --features capi
+//! ```
+//!
+//! which writes it to `target/release` next to the `rlib`, and include
+//! `include/offline_election.h`.
+//!
+//! Fallible functions return `0` on success and otherwise the error's
+//! [`exit_code`](crate::error::ElectionError::exit_code), the same code the
+//! CLI exits with; [`offline_election_last_error`] then describes the error.
+//! Panics are caught at the boundary and reported as errors.
+
+use crate::engine::ElectionEngine;
+use crate::error::ElectionError;
+use crate::input::json::JsonLoader;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+/// Status returned by successful calls
+pub const OFFLINE_ELECTION_OK: i32 = 0;
+
+thread_local! {
+    /// Message of the last error on this thread
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Remember `error` as the last error of this thread and return its status
+fn fail(error: ElectionError) -> i32 {
+    let status = error.exit_code();
+    // Messages never contain NUL in practice; drop any rather than lose the message
+    let message = CString::new(error.to_string().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    status
+}
+
+/// Run `f`, turning a panic into `on_panic`
+fn guarded<T>(
+    f: impl FnOnce() -> Result<T, ElectionError>,
+    on_panic: impl FnOnce() -> ElectionError,
+) -> Result<T, ElectionError> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| Err(on_panic()))
+}
+
+/// Borrow `len` bytes at `buffer` as UTF-8
+///
+/// # Safety
+///
+/// `buffer` must be null or point to `len` readable bytes.
+unsafe fn utf8<'a>(buffer: *const u8, len: usize, field: &str) -> Result<&'a str, ElectionError> {
+    if buffer.is_null() {
+        return Err(ElectionError::ValidationError {
+            message: format!("{} is null", field),
+            field: Some(field.to_string()),
+        });
+    }
+    std::str::from_utf8(std::slice::from_raw_parts(buffer, len)).map_err(|e| ElectionError::ValidationError {
+        message: format!("{} is not UTF-8: {}", field, e),
+        field: Some(field.to_string()),
+    })
+}
+
+/// Create an engine; free it with [`offline_election_engine_free`]
+#[no_mangle]
+pub extern "C" fn offline_election_engine_new() -> *mut ElectionEngine {
+    Box::into_raw(Box::new(ElectionEngine::new()))
+}
+
+/// Free an engine
+///
+/// # Safety
+///
+/// `engine` must be null or come from [`offline_election_engine_new`] and
+/// not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn offline_election_engine_free(engine: *mut ElectionEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Load and validate election data from a JSON document of `len` bytes
+///
+/// Returns null on failure; free the data with [`offline_election_data_free`].
+///
+/// # Safety
+///
+/// `json` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn offline_election_data_from_json(json: *const u8, len: usize) -> *mut ElectionData {
+    let loaded = guarded(
+        || JsonLoader::new().load_from_str(utf8(json, len, "data_json")?),
+        || ElectionError::InvalidData {
+            message: "Loading election data panicked".to_string(),
+            source: None,
+        },
+    );
+    match loaded {
+        Ok(data) => Box::into_raw(Box::new(data)),
+        Err(error) => {
+            fail(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Free election data
+///
+/// # Safety
+///
+/// `data` must be null or come from [`offline_election_data_from_json`] and
+/// not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn offline_election_data_free(data: *mut ElectionData) {
+    if !data.is_null() {
+        drop(Box::from_raw(data));
+    }
+}
+
+/// Run an election configured by a JSON configuration of `config_len` bytes
+///
+/// On success, stores the result as a NUL-terminated JSON string in
+/// `*result_json`, to be freed with [`offline_election_string_free`].
+///
+/// # Safety
+///
+/// `engine` and `data` must be live handles, `config_json` must point to
+/// `config_len` readable bytes and `result_json` to a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn offline_election_run(
+    engine: *const ElectionEngine,
+    data: *const ElectionData,
+    config_json: *const u8,
+    config_len: usize,
+    result_json: *mut *mut c_char,
+) -> i32 {
+    let run = || {
+        let (Some(engine), Some(data)) = (engine.as_ref(), data.as_ref()) else {
+            return Err(ElectionError::ValidationError {
+                message: "Engine and data handles must not be null".to_string(),
+                field: None,
+            });
+        };
+        if result_json.is_null() {
+            return Err(ElectionError::ValidationError {
+                message: "result_json is null".to_string(),
+                field: Some("result_json".to_string()),
+            });
+        }
+        let config = ElectionConfiguration::from_json_str(utf8(config_json, config_len, "config_json")?)?.build()?;
+        let algorithm = config.algorithm;
+        let json = guarded(
+            || engine.execute(&config, data)?.to_json(),
+            || ElectionError::AlgorithmError {
+                message: "Election panicked".to_string(),
+                algorithm,
+            },
+        )?;
+        CString::new(json).map_err(|e| ElectionError::InvalidData {
+            message: format!("Result JSON contains NUL: {}", e),
+            source: None,
+        })
+    };
+    match run() {
+        Ok(json) => {
+            *result_json = json.into_raw();
+            OFFLINE_ELECTION_OK
+        }
+        Err(error) => fail(error),
+    }
+}
+
+/// Free a string returned by this library
+///
+/// # Safety
+///
+/// `string` must be null or come from this library and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn offline_election_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Message of the last error on the calling thread, or null if none
+///
+/// The string stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn offline_election_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
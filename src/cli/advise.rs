@@ -0,0 +1,199 @@
+//! Parameter advisor command
+//!
+//! Sweeps validator set sizes and nomination caps over a projected stake
+//! distribution and prints the combinations that meet the given targets.
+
+use crate::advisor::{AdvisorTargets, ParameterAdvisor, ParameterGrid, Recommendation};
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::json::JsonLoader;
+use crate::models::balance::{parse_amount, TokenUnit};
+use crate::types::AlgorithmType;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Advise command for recommending staking parameters
+#[derive(Parser)]
+#[command(name = "advise")]
+#[command(about = "Recommend validator set sizes and nomination caps for a projected stake distribution")]
+pub struct AdviseCommand {
+    /// Projected or synthetic stake distribution (JSON `ElectionData`)
+    #[arg(long)]
+    pub input_file: PathBuf,
+
+    /// Set sizes to sweep: a list ("50,100,150") or a range with a step ("50..200:25")
+    #[arg(long, value_name = "SIZES")]
+    pub set_sizes: String,
+
+    /// Nomination caps to sweep, e.g. "8,16,24" (default: keep every target)
+    #[arg(long, value_name = "CAPS")]
+    pub nomination_caps: Option<String>,
+
+    /// Lowest acceptable Nakamoto coefficient of the elected backing
+    #[arg(long, value_name = "N")]
+    pub min_nakamoto: Option<usize>,
+
+    /// Lowest acceptable backing of any elected validator (plancks or e.g. "10000 UNIT")
+    #[arg(long, value_name = "STAKE")]
+    pub min_backing: Option<String>,
+
+    /// Token for amounts like "10000 DOT": DOT, KSM, WND or SYMBOL:decimals
+    #[arg(long, value_name = "UNIT")]
+    pub token: Option<String>,
+
+    /// Election algorithm used for every combination
+    #[arg(long, default_value = "sequential-phragmen")]
+    pub algorithm: String,
+
+    /// Output format: json or human-readable
+    #[arg(long, default_value = "json")]
+    pub format: String,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+}
+
+impl AdviseCommand {
+    /// Execute the advise command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let data = JsonLoader::new().load_from_file(self.input_file.clone())?;
+        let unit = match self.token {
+            Some(ref token) => Some(token.parse::<TokenUnit>().map_err(|e| ElectionError::ValidationError {
+                message: format!("Invalid token '{}': {}", token, e),
+                field: Some("token".to_string()),
+            })?),
+            None => data.token_unit(),
+        };
+        let algorithm = self.algorithm.parse::<AlgorithmType>().map_err(|e| ElectionError::ValidationError {
+            message: e,
+            field: Some("algorithm".to_string()),
+        })?;
+
+        let mut grid = ParameterGrid::new(parse_set_sizes(&self.set_sizes)?);
+        if let Some(ref caps) = self.nomination_caps {
+            grid = grid.nomination_caps(parse_list(caps, "nomination_caps")?);
+        }
+        let targets = AdvisorTargets {
+            min_nakamoto_coefficient: self.min_nakamoto,
+            min_backing: self
+                .min_backing
+                .as_deref()
+                .map(|stake| parse_amount(stake, unit.as_ref()))
+                .transpose()?,
+        };
+        eprintln!(
+            "Sweeping {} set sizes x {} nomination caps over {} nominators...",
+            grid.set_sizes.len(),
+            grid.nomination_caps.len(),
+            data.nominators.len()
+        );
+        let recommendation = ParameterAdvisor::new()
+            .with_algorithm(algorithm)
+            .recommend(&data, &grid, &targets)?;
+
+        let output = if self.format == "human-readable" {
+            format_recommendation(&recommendation, unit.as_ref())
+        } else {
+            serde_json::to_string_pretty(&recommendation).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize recommendation: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?
+        };
+
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+        } else {
+            println!("{}", output);
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse "50,100,150" or "50..200:25" (both ends included)
+fn parse_set_sizes(input: &str) -> Result<Vec<u32>, ElectionError> {
+    let Some((start, rest)) = input.split_once("..") else {
+        return parse_list(input, "set_sizes");
+    };
+    let (end, step) = rest.split_once(':').unwrap_or((rest, "1"));
+    let [start, end, step] = [start, end, step].map(|value| {
+        value.trim().parse::<u32>().map_err(|e| ElectionError::ValidationError {
+            message: format!("Invalid set size range '{}': {}", input, e),
+            field: Some("set_sizes".to_string()),
+        })
+    });
+    let (start, end, step) = (start?, end?, step?);
+    if step == 0 || start > end {
+        return Err(ElectionError::ValidationError {
+            message: format!("Invalid set size range '{}': expected START..END:STEP with START <= END and STEP > 0", input),
+            field: Some("set_sizes".to_string()),
+        });
+    }
+    Ok((start..=end).step_by(step as usize).collect())
+}
+
+/// Parse a comma-separated list of numbers
+fn parse_list<T>(input: &str, field: &str) -> Result<Vec<T>, ElectionError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    input
+        .split(',')
+        .map(|value| {
+            value.trim().parse::<T>().map_err(|e| ElectionError::ValidationError {
+                message: format!("Invalid value '{}' in {}: {}", value.trim(), field.replace('_', " "), e),
+                field: Some(field.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Format a recommendation as a table of the sweep followed by the ranges
+fn format_recommendation(recommendation: &Recommendation, unit: Option<&TokenUnit>) -> String {
+    let amount = |plancks: u128| match unit {
+        Some(unit) => unit.format(plancks),
+        None => format!("{} plancks", plancks),
+    };
+    let cap = |cap: Option<usize>| cap.map_or_else(|| "none".to_string(), |c| c.to_string());
+
+    let mut output = String::new();
+    output.push_str("Cap   Seats  Elected  Nakamoto  Minimum backing\n");
+    for point in &recommendation.points {
+        output.push_str(&format!(
+            "{:<5} {:<6} {:<8} {:<9} {}{}\n",
+            cap(point.nomination_cap),
+            point.set_size,
+            point.elected_count,
+            point.nakamoto_coefficient,
+            amount(point.minimal_backing),
+            if point.meets_targets { "  ok" } else { "" }
+        ));
+    }
+    output.push('\n');
+    if recommendation.ranges.is_empty() {
+        output.push_str("No combination meets the targets\n");
+    }
+    for range in &recommendation.ranges {
+        output.push_str(&format!(
+            "Nomination cap {}: {} to {} validators meet the targets\n",
+            cap(range.nomination_cap),
+            range.min_set_size,
+            range.max_set_size
+        ));
+    }
+    if let Some(ref point) = recommendation.suggested {
+        output.push_str(&format!(
+            "Suggested: {} validators, nomination cap {} (Nakamoto coefficient {}, minimum backing {})\n",
+            point.set_size,
+            cap(point.nomination_cap),
+            point.nakamoto_coefficient,
+            amount(point.minimal_backing)
+        ));
+    }
+    output
+}
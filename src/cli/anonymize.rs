@@ -0,0 +1,77 @@
+//! Snapshot anonymization command
+//!
+//! Replaces account IDs in a JSON snapshot with keyed pseudonyms so it can be
+//! shared without the account IDs in it.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::json::JsonLoader;
+use crate::models::anonymize::Anonymizer;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Anonymize command for pseudonymizing election snapshots
+#[derive(Parser)]
+#[command(name = "anonymize")]
+#[command(about = "Replace account IDs in a snapshot with deterministic pseudonyms")]
+pub struct AnonymizeCommand {
+    /// Path to the JSON snapshot to anonymize
+    #[arg(long)]
+    pub input_file: PathBuf,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+
+    /// Secret key for the pseudonyms
+    #[arg(long, conflicts_with = "key_file", required_unless_present = "key_file")]
+    pub key: Option<String>,
+
+    /// File holding the secret key for the pseudonyms
+    #[arg(long)]
+    pub key_file: Option<PathBuf>,
+}
+
+impl AnonymizeCommand {
+    /// Execute the anonymize command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let key = match (&self.key, &self.key_file) {
+            (Some(key), _) => key.as_bytes().to_vec(),
+            (None, Some(path)) => std::fs::read(path).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to read key file: {}", e),
+                path: path.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?,
+            (None, None) => {
+                return Err(ElectionError::ValidationError {
+                    message: "Either --key or --key-file is required".to_string(),
+                    field: Some("key".to_string()),
+                })
+            }
+        };
+        let anonymizer = Anonymizer::new(&key)?;
+
+        let data = JsonLoader::new().load_from_file(self.input_file.clone())?;
+        let anonymized = anonymizer.anonymize(&data);
+        let output = serde_json::to_string_pretty(&anonymized).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize anonymized snapshot: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            eprintln!(
+                "Anonymized {} candidates and {} nominators",
+                anonymized.candidates.len(),
+                anonymized.nominators.len()
+            );
+        } else {
+            println!("{}", output);
+        }
+
+        Ok(())
+    }
+}
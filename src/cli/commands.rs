@@ -1,10 +1,25 @@
 //! CLI commands implementation
 
-use crate::error::ElectionError;
+use crate::error::{ElectionError, ErrorSource};
 use crate::engine::ElectionEngine;
+use crate::models::account_format::AccountFormat;
+use crate::models::allocation_matrix::{AllocationMatrix, StakeBucketing};
+use crate::models::balance::TokenUnit;
+use crate::models::conviction::{Conviction, ConvictionWeights};
 use crate::models::election_config::ElectionConfiguration;
-use crate::models::election_data::ElectionData;
-use crate::types::AlgorithmType;
+use crate::models::election_data::{ElectionData, StakeSource};
+use crate::models::election_overrides::ElectionOverrides;
+use crate::models::rank_stability::StabilityScenario;
+use crate::models::raw_solution::ResultDetail;
+use crate::models::stake_flow::StakeFlowScenario;
+use crate::models::sybil_scenario::{SybilScenario, SybilSplit};
+use crate::models::validation::DedupPolicy;
+use crate::models::stake_redistribution::StakeRedistribution;
+use crate::models::whale_impact::WhaleRemovalMode;
+use crate::payout::commission::{CommissionPolicy, CommissionRefusal};
+use crate::payout::models::PayoutConfig;
+use crate::settings::ResolvedSettings;
+use crate::types::ElectionKind;
 use clap::Parser;
 use std::path::PathBuf;
 
@@ -12,124 +27,710 @@ use std::path::PathBuf;
 #[derive(Parser)]
 #[command(name = "run")]
 #[command(about = "Run an election simulation")]
+#[command(group(clap::ArgGroup::new("rpc_source").args(["rpc_url", "chain", "config"]).multiple(true)))]
 pub struct RunCommand {
-    /// Election algorithm to use (sequential-phragmen, parallel-phragmen, multi-phase)
-    #[arg(long)]
-    pub algorithm: String,
+    #[command(flatten)]
+    pub settings: crate::cli::config::SettingsArgs,
 
-    /// Number of validators to select
-    #[arg(long)]
-    pub active_set_size: u32,
+    /// Collapse duplicated account IDs in --input-file before validating:
+    /// keep-first, keep-last, keep-highest-stake or sum
+    #[arg(long, requires = "input_file")]
+    pub dedup: Option<String>,
 
-    /// RPC URL for fetching on-chain data
-    #[arg(long, conflicts_with_all = ["input_file", "synthetic"])]
+    /// RPC URL for fetching on-chain data (a chain preset or configuration
+    /// file's URL is used only when no other input is given)
+    #[arg(long, conflicts_with_all = ["input_file", "synthetic", "indexer_url"])]
     pub rpc_url: Option<String>,
 
     /// Block number for RPC snapshot
-    #[arg(long, requires = "rpc_url")]
+    #[arg(long, requires = "rpc_source")]
     pub block_number: Option<u64>,
 
+    /// Number of RPC storage queries in flight at once when fetching a snapshot
+    #[arg(long, requires = "rpc_source")]
+    pub rpc_concurrency: Option<usize>,
+
+    /// Seconds a single RPC request may take before it is abandoned and retried
+    #[arg(long, requires = "rpc_source")]
+    pub rpc_timeout: Option<u64>,
+
+    /// Seconds allowed for fetching the whole RPC snapshot, retries included
+    #[arg(long, requires = "rpc_source")]
+    pub rpc_deadline: Option<u64>,
+
+    /// Maximum RPC requests started per second, for rate-limited public endpoints
+    #[arg(long, requires = "rpc_source")]
+    pub rpc_rate_limit: Option<f64>,
+
+    /// Load each candidate's era points in this many eras before the active one,
+    /// for the exclude-inactive candidate rule
+    #[arg(long, value_name = "N", requires = "rpc_source")]
+    pub activity_eras: Option<u32>,
+
+    /// Where nominator stakes come from: ledger (bonded total, the default) or
+    /// exposure (stake exposed in the active era)
+    #[arg(long, value_parser = ["ledger", "exposure"], requires = "rpc_source")]
+    pub stake_source: Option<String>,
+
+    /// Read each stash's controller and store it with the account, for
+    /// snapshots of eras whose records refer to controllers
+    #[arg(long, requires = "rpc_source")]
+    pub resolve_controllers: bool,
+
+    /// Name of the pallet-elections-phragmen instance read for council elections
+    #[arg(long, default_value = crate::input::rpc::DEFAULT_ELECTIONS_PALLET, requires = "rpc_source")]
+    pub elections_pallet: String,
+
+    /// GraphQL indexer URL (Subsquid/SubQuery) for loading a historical era
+    #[arg(long, conflicts_with_all = ["rpc_url", "input_file", "synthetic"], requires = "era")]
+    pub indexer_url: Option<String>,
+
+    /// Era to load from the indexer
+    #[arg(long, requires = "indexer_url")]
+    pub era: Option<u32>,
+
     /// Input file path (JSON format)
-    #[arg(long, conflicts_with_all = ["rpc_url", "synthetic"])]
+    #[arg(long, conflicts_with_all = ["rpc_url", "synthetic", "indexer_url"])]
     pub input_file: Option<PathBuf>,
 
+    /// Format of --input-file: json (`ElectionData`, the default) or staking-miner
+    /// (a dumped solver snapshot, as JSON, hex or SCALE bytes)
+    #[arg(long, value_parser = ["json", "staking-miner"], requires = "input_file")]
+    pub input_format: Option<String>,
+
     /// Use synthetic data (requires additional flags)
-    #[arg(long, conflicts_with_all = ["rpc_url", "input_file"])]
+    #[arg(long, conflicts_with_all = ["rpc_url", "input_file", "indexer_url"])]
     pub synthetic: bool,
 
     /// Include detailed diagnostics in output
     #[arg(long)]
     pub diagnostics: bool,
 
+    /// Add the maximin support gap to the diagnostics, running PhragMMS and
+    /// max-flow balancing on top of the election
+    #[arg(long, requires = "diagnostics")]
+    pub maximin_gap: bool,
+
+    /// Print a plain-language summary of the result
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Result detail: summary, or full to include the solver's raw assignments
+    /// and supports map in JSON output
+    #[arg(long, default_value = "summary", value_parser = ["summary", "full"])]
+    pub result_detail: String,
+
+    /// Runtime spec_version whose election rules apply (nomination and snapshot
+    /// limits, miner balancing) on the chain of --chain or of the data
+    #[arg(long, value_name = "N")]
+    pub spec_version: Option<u32>,
+
+    /// Nominators rewarded per validator (e.g. 256 on Polkadot before paged
+    /// exposures); allocations beyond it are marked unrewarded
+    #[arg(long, value_name = "N")]
+    pub max_rewarded_nominators: Option<u32>,
+
+    /// Seat the data's invulnerables before the algorithm fills the rest;
+    /// pallet-staking itself never does
+    #[arg(long)]
+    pub force_invulnerables: bool,
+
+    /// Print every selection and balancing round to stderr while the election runs
+    #[arg(long)]
+    pub progress: bool,
+
+    /// Earlier result (JSON) the summary compares against
+    #[arg(long, value_name = "PATH", requires = "explain")]
+    pub explain_baseline: Option<PathBuf>,
+
     /// Output file path (default: stdout)
     #[arg(long)]
     pub output_file: Option<PathBuf>,
 
-    /// Output format: json or human-readable
+    /// Output format: json, human-readable, polkadot-js (winners and `erasStakers` exposures)
+    /// or csv (one row per winner for spreadsheets)
     #[arg(long, default_value = "json")]
     pub format: String,
 
-    /// Override candidate stake (format: account_id=stake, can be repeated)
+    /// Override candidate stake (format: account_id=stake, can be repeated; stake in plancks or e.g. "1.5 DOT")
     #[arg(long, value_name = "ACCOUNT_ID=STAKE")]
     pub override_candidate_stake: Vec<String>,
 
-    /// Override nominator stake (format: account_id=stake, can be repeated)
+    /// Override nominator stake (format: account_id=stake, can be repeated; stake in plancks or e.g. "1.5 DOT")
     #[arg(long, value_name = "ACCOUNT_ID=STAKE")]
     pub override_nominator_stake: Vec<String>,
+
+    /// Mark a candidate as blocking new nominations (can be repeated)
+    #[arg(long, value_name = "ACCOUNT_ID")]
+    pub block_candidate: Vec<String>,
+
+    /// Weight a nominator's stake by vote conviction (format: account_id=conviction, can be repeated)
+    #[arg(long, value_name = "ACCOUNT_ID=CONVICTION")]
+    pub conviction: Vec<String>,
+
+    /// Conviction applied to every nominator without its own (none, locked1x ... locked6x)
+    #[arg(long, value_name = "CONVICTION")]
+    pub default_conviction: Option<String>,
+
+    /// Apply a named override layer from a JSON file (format: name=path, can be repeated, applied in order)
+    #[arg(long, value_name = "NAME=PATH")]
+    pub override_layer: Vec<String>,
+
+    /// Attach country, city and hosting provider to candidates from a CSV or JSON mapping
+    #[arg(long, value_name = "PATH")]
+    pub candidate_attributes: Option<PathBuf>,
+
+    /// Apply candidate inclusion/exclusion rules from a JSON file (array of rules)
+    #[arg(long, value_name = "PATH")]
+    pub rules: Option<PathBuf>,
+
+    /// Write a provenance bundle (result, post-override data, config and hashes) to this directory
+    #[arg(long, value_name = "DIR")]
+    pub export_bundle: Option<PathBuf>,
+
+    /// Write the stake each nominator stake bucket allocated to each elected
+    /// validator, for heatmaps: CSV if the path ends in .csv, JSON otherwise
+    #[arg(long, value_name = "PATH")]
+    pub export_heatmap: Option<PathBuf>,
+
+    /// Number of --export-heatmap rows, each holding about as many nominators
+    #[arg(long, value_name = "N", default_value_t = 10, requires = "export_heatmap")]
+    pub heatmap_buckets: usize,
+
+    /// Stake splitting --export-heatmap rows instead of equal-count buckets
+    /// (can be repeated; plancks or e.g. "1000 DOT")
+    #[arg(long, value_name = "STAKE", requires = "export_heatmap")]
+    pub heatmap_bound: Vec<String>,
+
+    /// Give only the N top-ranked validators a column; the rest are summed in "Other"
+    #[arg(long, value_name = "N", requires = "export_heatmap")]
+    pub heatmap_validators: Option<usize>,
+
+    /// Re-run the election without each of the K largest nominators and report set changes
+    #[arg(long, value_name = "K")]
+    pub whale_impact: Option<usize>,
+
+    /// How --whale-impact removes nominators: individual (one at a time) or cumulative
+    #[arg(long, default_value = "individual", requires = "whale_impact")]
+    pub whale_impact_mode: String,
+
+    /// Recommend moving stake from over-backed to under-backed elected validators
+    #[arg(long)]
+    pub recommend_redistribution: bool,
+
+    /// Backing above the average, in basis points, before --recommend-redistribution
+    /// counts a validator as over-backed
+    #[arg(long, value_name = "BPS", default_value_t = crate::models::stake_redistribution::DEFAULT_TOLERANCE_BPS, requires = "recommend_redistribution")]
+    pub redistribution_tolerance_bps: u32,
+
+    /// Inject N sybil candidates backed by one attacker and report how many get elected
+    #[arg(long, value_name = "N", requires = "sybil_attacker_stake")]
+    pub sybil_candidates: Option<usize>,
+
+    /// Attacker stake to test with --sybil-candidates (can be repeated; plancks or e.g. "1.5 DOT")
+    #[arg(long, value_name = "STAKE", requires = "sybil_candidates")]
+    pub sybil_attacker_stake: Vec<String>,
+
+    /// Split of the attacker stake to test: pooled, even or halving (can be repeated; default all)
+    #[arg(long, value_name = "SPLIT", requires = "sybil_candidates")]
+    pub sybil_split: Vec<String>,
+
+    /// Estimate the minimum stake an attacker needs to win this many seats with sybil candidates
+    #[arg(long, value_name = "SEATS")]
+    pub attack_seats: Option<usize>,
+
+    /// Split of the attacker stake assumed by --attack-seats: pooled, even or halving
+    #[arg(long, default_value = "even", requires = "attack_seats")]
+    pub attack_split: String,
+
+    /// Project the elected set over future eras under the bonds and unbonds of a JSON scenario file
+    #[arg(long, value_name = "PATH")]
+    pub stake_flows: Option<PathBuf>,
+
+    /// Eras to project with --stake-flows (default: the last era a flow takes effect at)
+    #[arg(long, value_name = "N", requires = "stake_flows")]
+    pub projection_eras: Option<u32>,
+
+    /// Re-run the election with the set size moved a seat either way and with
+    /// stake noise, and report winner order stability and fragile validators
+    #[arg(long)]
+    pub rank_stability: bool,
+
+    /// Largest stake change per nominator in --rank-stability noise runs, in basis points
+    #[arg(long, value_name = "BPS", default_value_t = 100, requires = "rank_stability")]
+    pub stability_noise_bps: u32,
+
+    /// Number of --rank-stability noise runs
+    #[arg(long, value_name = "N", default_value_t = 5, requires = "rank_stability")]
+    pub stability_runs: usize,
+
+    /// Seed of the first --rank-stability noise run
+    #[arg(long, value_name = "SEED", default_value_t = 0, requires = "rank_stability")]
+    pub stability_seed: u64,
+
+    /// Simulate enforcing this minimum commission (percent) and report nominator reward changes
+    #[arg(long, value_name = "PCT", requires = "era_reward")]
+    pub min_commission: Option<u8>,
+
+    /// Simulate enforcing this maximum commission (percent) and report nominator reward changes
+    #[arg(long, value_name = "PCT", requires = "era_reward")]
+    pub max_commission: Option<u8>,
+
+    /// Era reward paid out in the commission simulation (plancks or e.g. "100000 DOT")
+    #[arg(long, value_name = "STAKE")]
+    pub era_reward: Option<String>,
+
+    /// In the commission simulation, candidates outside the bound stop validating instead of complying
+    #[arg(long)]
+    pub drop_non_compliant: bool,
+
+    /// Candidate that stops validating if outside the commission bound (can be repeated)
+    #[arg(long, value_name = "ACCOUNT", conflicts_with = "drop_non_compliant")]
+    pub refusing_validator: Vec<String>,
+
+    /// Watch the input file and override layer files, re-running the election on change
+    #[arg(long, requires = "input_file")]
+    pub watch: bool,
+
+    /// Load the data and report its size and estimated memory use without running the election
+    #[arg(long, conflicts_with = "watch")]
+    pub dry_run: bool,
 }
 
 impl RunCommand {
     /// Execute the run command
     pub async fn execute(&self) -> Result<(), ElectionError> {
+        let settings = self.resolve_settings()?;
+        if self.watch {
+            return self.watch(&settings).await;
+        }
+        if self.dry_run {
+            return self.dry_run(&settings).await;
+        }
+
+        let (result, election_data) = self.run_once(&settings).await?;
+
+        // Output results
+        self.output_result(&result, &election_data, &settings)?;
+
+        Ok(())
+    }
+
+    /// Load the data and print its size and estimated memory use
+    ///
+    /// JSON output prints the memory report as JSON; every other format
+    /// prints a short summary.
+    async fn dry_run(&self, settings: &ResolvedSettings) -> Result<(), ElectionError> {
+        let mut election_data = self.load_data(settings).await?;
+        let released = election_data.shrink_to_fit();
+        let report = election_data.memory_report();
+        if self.format == "json" {
+            let output = serde_json::json!({
+                "candidates": election_data.candidates.len(),
+                "nominators": election_data.nominators.len(),
+                "memory": report,
+                "released_spare_capacity": released,
+            });
+            println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+        } else {
+            print!("{}", crate::cli::output::format_memory_report(&election_data, &report));
+            println!(
+                "Shrinking released {} of spare capacity after loading",
+                crate::cli::output::format_size(released as u64)
+            );
+        }
+        Ok(())
+    }
+
+    /// Load data, build the configuration and execute the election once
+    async fn run_once(
+        &self,
+        settings: &ResolvedSettings,
+    ) -> Result<(crate::models::election_result::ElectionResult, ElectionData), ElectionError> {
         // Load election data
-        let election_data = self.load_data().await?;
+        let mut election_data = self.load_data(settings).await?;
 
-        // Parse algorithm type
-        let algorithm = self.algorithm.parse::<AlgorithmType>()
-            .map_err(|e| ElectionError::ValidationError {
-                message: format!("Invalid algorithm: {}", e),
-                field: Some("algorithm".to_string()),
-            })?;
+        // Attach external candidate attributes
+        if let Some(ref path) = self.candidate_attributes {
+            let attributes = crate::input::CandidateAttributeMap::load_from_file(path)?;
+            let matched = attributes.apply(&mut election_data);
+            eprintln!(
+                "Attached attributes to {} of {} candidates ({} entries in mapping)",
+                matched,
+                election_data.candidates.len(),
+                attributes.len()
+            );
+        }
 
         // Create election configuration
-        let mut config = ElectionConfiguration::new()
-            .algorithm(algorithm)
-            .active_set_size(self.active_set_size);
+        let mut config = ElectionConfiguration::builder()
+            .algorithm(settings.algorithm()?)
+            .active_set_size(settings.active_set_size()?)
+            .kind(settings.kind()?)
+            .validation_profile(settings.validation_profile()?)
+            .rounding(settings.rounding()?);
 
         if let Some(block) = self.block_number {
             config = config.block_number(block);
         }
+        if settings.settings.partial_results == Some(true) {
+            config = config.partial_results(true);
+        }
+        let result_detail = self.result_detail.parse::<ResultDetail>().map_err(|e| ElectionError::ValidationError {
+            message: e,
+            field: Some("result_detail".to_string()),
+        })?;
+        config = config.result_detail(result_detail);
+        if let Some(spec_version) = self.spec_version {
+            let chain = settings
+                .settings
+                .chain
+                .clone()
+                .or_else(|| election_data.metadata.as_ref()?.chain.clone())
+                .ok_or_else(|| ElectionError::ValidationError {
+                    message: "--spec-version needs --chain or data that names its chain".to_string(),
+                    field: Some("spec_version".to_string()),
+                })?;
+            config = config.runtime(chain, spec_version);
+        }
+        if let Some(max) = self.max_rewarded_nominators {
+            config = config.max_rewarded_nominators(max);
+        }
+        if self.force_invulnerables {
+            config = config.force_invulnerables(true);
+        }
+        if self.maximin_gap {
+            config = config.maximin_gap(true);
+        }
+
+        let unit = self.token_unit(settings, &election_data)?;
+
+        let balancing = &settings.settings;
+        if balancing.balancing_iterations.is_some() || balancing.balancing_tolerance.is_some() {
+            let tolerance = match balancing.balancing_tolerance.as_deref() {
+                Some(tolerance) => crate::models::balance::parse_amount(tolerance, unit.as_ref()).map_err(|e| {
+                    ElectionError::ValidationError {
+                        message: format!("Invalid balancing tolerance '{}': {}", tolerance, e),
+                        field: Some("balancing_tolerance".to_string()),
+                    }
+                })?,
+                None => 0,
+            };
+            config = config.balancing(
+                balancing.balancing_iterations.unwrap_or(crate::models::election_config::DEFAULT_BALANCING_ITERATIONS),
+                tolerance,
+            );
+        }
 
         // Apply overrides if specified
-        if !self.override_candidate_stake.is_empty() || !self.override_nominator_stake.is_empty() {
+        if !self.override_candidate_stake.is_empty()
+            || !self.override_nominator_stake.is_empty()
+            || !self.block_candidate.is_empty()
+        {
             let mut overrides = crate::models::election_overrides::ElectionOverrides::new();
             
             // Parse candidate stake overrides
             for override_str in &self.override_candidate_stake {
-                let (account_id, stake) = self.parse_override(override_str, "candidate")?;
+                let (account_id, stake) = self.parse_override(override_str, "candidate", unit.as_ref())?;
                 overrides.set_candidate_stake(account_id, stake)?;
             }
             
             // Parse nominator stake overrides
             for override_str in &self.override_nominator_stake {
-                let (account_id, stake) = self.parse_override(override_str, "nominator")?;
+                let (account_id, stake) = self.parse_override(override_str, "nominator", unit.as_ref())?;
                 overrides.set_nominator_stake(account_id, stake)?;
             }
+
+            // Parse blocked candidates
+            for account_id in &self.block_candidate {
+                overrides.set_candidate_blocked(account_id.trim().to_string(), true)?;
+            }
             
             config = config.overrides(overrides);
         }
 
+        // Apply override layers in the order given
+        for layer_str in &self.override_layer {
+            let (name, overrides) = self.load_override_layer(layer_str, unit.as_ref())?;
+            config = config.override_layer(name, overrides);
+        }
+
+        if !self.conviction.is_empty() || self.default_conviction.is_some() {
+            config = config.convictions(self.parse_convictions()?);
+        }
+
+        if let Some(ref path) = self.rules {
+            config = config.candidate_rules(self.load_rules(path)?);
+        }
+
         let config = config.build()?;
 
         // Execute election with diagnostics if requested
-        let engine = ElectionEngine::new();
+        let mut engine = ElectionEngine::new();
+        if self.progress {
+            let unit = unit.clone();
+            engine = engine.with_progress_observer(move |event| {
+                eprintln!("  {}", crate::cli::output::format_progress(event, unit.as_ref()));
+            });
+        }
         let result = engine.execute_with_diagnostics(&config, &election_data, self.diagnostics)?;
+        if let Some(ref dir) = self.export_bundle {
+            result.export_bundle(dir, &config, &election_data)?;
+            eprintln!("Wrote result bundle to {}", dir.display());
+        }
+        if let Some(top_k) = self.whale_impact {
+            let mode = self.whale_impact_mode.parse::<WhaleRemovalMode>().map_err(|e| ElectionError::ValidationError {
+                message: e,
+                field: Some("whale_impact_mode".to_string()),
+            })?;
+            let impact = engine.whale_impact(&config, &election_data, top_k, mode)?;
+            // Keep JSON on stdout parseable
+            eprint!("{}", crate::cli::output::format_whale_impact(&impact));
+        }
+        if self.recommend_redistribution {
+            let redistribution = StakeRedistribution::recommend(&result, &election_data, self.redistribution_tolerance_bps);
+            eprint!("{}", crate::cli::output::format_stake_redistribution(&redistribution));
+        }
+        if let Some(sybil_count) = self.sybil_candidates {
+            let scenario = self.sybil_scenario(sybil_count, unit.as_ref())?;
+            let report = engine.sybil_stress(&config, &election_data, &scenario)?;
+            eprint!("{}", crate::cli::output::format_sybil_report(&report));
+        }
+        if let Some(seats) = self.attack_seats {
+            let split = self.attack_split.parse::<SybilSplit>().map_err(|e| ElectionError::ValidationError {
+                message: e,
+                field: Some("attack_split".to_string()),
+            })?;
+            let estimate = engine.min_attack_stake(&config, &election_data, seats, split)?;
+            eprint!("{}", crate::cli::output::format_attack_estimate(&estimate));
+        }
+        if let Some(ref path) = self.stake_flows {
+            let scenario = self.load_stake_flows(path, unit.as_ref())?;
+            let eras = self.projection_eras.unwrap_or_else(|| scenario.last_era());
+            let projection = engine.project_stake_flows(&config, &election_data, &scenario, eras)?;
+            eprint!("{}", crate::cli::output::format_stake_flow_projection(&projection));
+        }
+        if self.rank_stability {
+            let scenario = StabilityScenario::new()
+                .stake_noise_bps(self.stability_noise_bps)
+                .noise_runs(self.stability_runs)
+                .seed(self.stability_seed);
+            let stability = engine.rank_stability(&config, &election_data, &scenario)?;
+            eprint!("{}", crate::cli::output::format_rank_stability(&stability));
+        }
+        if self.min_commission.is_some() || self.max_commission.is_some() {
+            let policy = self.commission_policy();
+            let era_reward = self.era_reward.as_deref().unwrap_or_default();
+            let era_reward = crate::models::balance::parse_amount(era_reward, unit.as_ref()).map_err(|e| {
+                ElectionError::ValidationError {
+                    message: format!("Invalid era reward '{}': {}", era_reward, e),
+                    field: Some("era_reward".to_string()),
+                }
+            })?;
+            let impact = engine.commission_scenario(&config, &election_data, &policy, &PayoutConfig::new(era_reward))?;
+            eprint!("{}", crate::cli::output::format_commission_impact(&impact));
+        }
+        let issues = &result.execution_metadata.data_issues;
+        if !issues.is_empty() {
+            eprintln!(
+                "Warning: {} data issues tolerated by the {} validation profile (see execution_metadata.data_issues)",
+                issues.len(),
+                config.validation_profile
+            );
+        }
+        if !result.issues.is_empty() {
+            eprintln!("Warning: partial result with {} issues (see issues)", result.issues.len());
+        }
+        Ok((result, election_data))
+    }
 
-        // Output results
-        self.output_result(&result)?;
+    /// Run the election, then re-run it whenever a watched file changes
+    ///
+    /// The full result is printed on the first run; later runs print a compact
+    /// diff against the previous successful result. Errors are reported without
+    /// stopping the watch so a half-edited file does not end the session.
+    async fn watch(&self, settings: &ResolvedSettings) -> Result<(), ElectionError> {
+        use notify::{RecursiveMode, Watcher};
+
+        let watched = self.watched_files();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let _ = tx.send(event);
+            }
+        })
+        .map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to start file watcher: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+
+        // Watch parent directories so editors that replace files on save are still seen
+        let directories: std::collections::BTreeSet<PathBuf> = watched
+            .iter()
+            .map(|path| match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            })
+            .collect();
+        for directory in &directories {
+            watcher
+                .watch(directory, RecursiveMode::NonRecursive)
+                .map_err(|e| ElectionError::FileError {
+                    message: format!("Failed to watch directory: {}", e),
+                    path: directory.clone(),
+                    source: Some(ErrorSource::new(e)),
+                })?;
+        }
+
+        let mut previous = match self.run_once(settings).await {
+            Ok((result, election_data)) => {
+                self.output_result(&result, &election_data, settings)?;
+                Some(result)
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                None
+            }
+        };
+        eprintln!("Watching {} file(s) for changes...", watched.len());
+
+        while let Some(event) = rx.recv().await {
+            if !event.paths.iter().any(|path| is_watched(path, &watched)) {
+                continue;
+            }
+            // Debounce bursts of events from a single save
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            while rx.try_recv().is_ok() {}
+
+            match self.run_once(settings).await {
+                Ok((result, election_data)) => {
+                    match previous {
+                        Some(ref previous) => {
+                            println!("--- re-run at {}", chrono::Utc::now().format("%H:%M:%S"));
+                            print!("{}", crate::cli::output::format_result_diff(previous, &result));
+                            if self.output_file.is_some() {
+                                self.output_result(&result, &election_data, settings)?;
+                            }
+                        }
+                        None => self.output_result(&result, &election_data, settings)?,
+                    }
+                    previous = Some(result);
+                }
+                Err(e) => eprintln!("Error: {}", e),
+            }
+        }
 
         Ok(())
     }
 
+    /// Files whose modification triggers a re-run in watch mode
+    fn watched_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self.input_file.iter().cloned().collect();
+        for layer_str in &self.override_layer {
+            if let Some((_, path)) = layer_str.split_once('=') {
+                files.push(PathBuf::from(path.trim()));
+            }
+        }
+        files.extend(self.candidate_attributes.iter().cloned());
+        files.extend(self.rules.iter().cloned());
+        files
+    }
+
+    /// Resolve the run settings from defaults, the chain preset, `--config` and flags
+    pub fn resolve_settings(&self) -> Result<ResolvedSettings, ElectionError> {
+        let cli = crate::settings::RunSettings {
+            rpc_url: self.rpc_url.clone(),
+            rpc_concurrency: self.rpc_concurrency,
+            rpc_timeout: self.rpc_timeout,
+            ..self.settings.cli_layer()
+        };
+        self.settings.resolve(cli)
+    }
+
+    /// Token unit from the settings, or from the chain named in the data
+    fn token_unit(&self, settings: &ResolvedSettings, data: &ElectionData) -> Result<Option<TokenUnit>, ElectionError> {
+        Ok(settings.token_unit()?.or_else(|| data.token_unit()))
+    }
+
+    /// Fetch a snapshot over RPC
+    async fn load_from_rpc(&self, rpc_url: &str, settings: &ResolvedSettings) -> Result<ElectionData, ElectionError> {
+        let concurrency = settings.settings.rpc_concurrency.unwrap_or(crate::input::rpc::DEFAULT_RPC_CONCURRENCY);
+        let timeout = settings
+            .settings
+            .rpc_timeout
+            .map_or(crate::input::rpc::DEFAULT_REQUEST_TIMEOUT, std::time::Duration::from_secs);
+        let mut loader = crate::input::rpc::RpcLoader::new(rpc_url)?
+            .with_concurrency(concurrency)
+            .with_request_timeout(timeout)
+            .with_rate_limit(self.rpc_rate_limit.unwrap_or(0.0))?
+            .with_activity_eras(self.activity_eras.unwrap_or(0))
+            .with_controller_mapping(self.resolve_controllers);
+        if let Some(ref source) = self.stake_source {
+            let source = source.parse::<StakeSource>().map_err(|e| ElectionError::ValidationError {
+                message: format!("Invalid stake source: {}", e),
+                field: Some("stake_source".to_string()),
+            })?;
+            loader = loader.with_stake_source(source);
+        }
+        if let Some(deadline) = self.rpc_deadline {
+            loader = loader.with_deadline(std::time::Duration::from_secs(deadline));
+        }
+        if settings.kind()? == ElectionKind::Council {
+            let loader = loader.with_elections_pallet(&self.elections_pallet);
+            return match self.block_number {
+                Some(block_number) => loader.load_council_at_block(block_number).await,
+                None => loader.load_council_latest().await,
+            };
+        }
+        let block_number = self.block_number.unwrap_or_else(|| {
+            // If no block number specified, use latest (None = latest)
+            0 // We'll handle this in the RPC loader
+        });
+        
+        if block_number == 0 {
+            // Get latest block
+            loader.load_latest().await
+        } else {
+            loader.load_at_block(block_number).await
+        }
+    }
+
     /// Load election data from the specified source
-    async fn load_data(&self) -> Result<ElectionData, ElectionError> {
-        if let Some(ref rpc_url) = self.rpc_url {
-            // Load from RPC
-            let loader = crate::input::rpc::RpcLoader::new(rpc_url)?;
-            let block_number = self.block_number.unwrap_or_else(|| {
-                // If no block number specified, use latest (None = latest)
-                0 // We'll handle this in the RPC loader
-            });
-            
-            if block_number == 0 {
-                // Get latest block
-                loader.load_latest().await
-            } else {
-                loader.load_at_block(block_number).await
+    ///
+    /// An input given on the command line wins over an RPC URL from the chain
+    /// preset or configuration file.
+    async fn load_data(&self, settings: &ResolvedSettings) -> Result<ElectionData, ElectionError> {
+        let layered_rpc_url = settings.settings.rpc_url.as_deref();
+        if let (Some(_), Some(rpc_url)) = (&self.rpc_url, layered_rpc_url) {
+            self.load_from_rpc(rpc_url, settings).await
+        } else if let (Some(ref indexer_url), Some(era)) = (&self.indexer_url, self.era) {
+            crate::input::indexer::IndexerLoader::new(indexer_url)?
+                .with_queries(settings.indexer_queries())
+                .load_era(era)
+                .await
+        } else if let (Some(ref input_file), Some("staking-miner")) = (&self.input_file, self.input_format.as_deref()) {
+            let snapshot = crate::input::staking_miner::StakingMinerLoader::new()
+                .with_validation_profile(settings.validation_profile()?)
+                .load_from_file(input_file.clone())?;
+            if let (Some(desired), Some(active_set_size)) = (snapshot.desired_targets, settings.settings.active_set_size) {
+                if desired != active_set_size {
+                    eprintln!(
+                        "Warning: the miner was asked for {} winners, but --active-set-size is {}",
+                        desired, active_set_size
+                    );
+                }
             }
+            Ok(snapshot.data)
         } else if let Some(ref input_file) = self.input_file {
             // Load from JSON file
-            let json_loader = crate::input::json::JsonLoader::new();
+            let mut json_loader = crate::input::json::JsonLoader::new().with_validation_profile(settings.validation_profile()?);
+            if let Some(ref policy) = self.dedup {
+                let policy = policy.parse::<DedupPolicy>().map_err(|e| ElectionError::ValidationError {
+                    message: format!("Invalid dedup policy: {}", e),
+                    field: Some("dedup".to_string()),
+                })?;
+                json_loader = json_loader.with_dedup(policy);
+            }
             json_loader.load_from_file(input_file.clone())
         } else if self.synthetic {
             // Create synthetic data using the builder
@@ -151,36 +752,108 @@ impl RunCommand {
                 .add_nominator("0xcccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc".to_string(), 0, vec![])?; // Zero stake nominator
             
             builder.build()
+        } else if let Some(rpc_url) = layered_rpc_url {
+            self.load_from_rpc(rpc_url, settings).await
         } else {
             Err(ElectionError::ValidationError {
-                message: "Must specify one of: --rpc-url, --input-file, or --synthetic".to_string(),
+                message: "Must specify one of: --rpc-url, --chain, --indexer-url, --input-file, or --synthetic".to_string(),
                 field: None,
             })
         }
     }
 
     /// Output election results
-    fn output_result(&self, result: &crate::models::election_result::ElectionResult) -> Result<(), ElectionError> {
+    fn output_result(
+        &self,
+        result: &crate::models::election_result::ElectionResult,
+        election_data: &ElectionData,
+        settings: &ResolvedSettings,
+    ) -> Result<(), ElectionError> {
+        let account_format = settings.account_format()?;
+        let prefix = settings.ss58_prefix().unwrap_or_else(|| election_data.ss58_prefix());
+        let prefixes = election_data.address_prefixes();
+        if prefixes.len() > 1 && account_format.is_as_loaded() {
+            eprintln!(
+                "Warning: account IDs use {} SS58 prefixes ({}); pass --account-format network to write them uniformly",
+                prefixes.len(),
+                prefixes.keys().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+            );
+        }
+        let output_result = if account_format.is_as_loaded() {
+            std::borrow::Cow::Borrowed(result)
+        } else {
+            let mut result = result.clone();
+            result.reencode_accounts(account_format, prefix);
+            std::borrow::Cow::Owned(result)
+        };
+
+        if let Some(ref path) = self.export_heatmap {
+            self.export_heatmap(path, result, election_data, settings, account_format, prefix)?;
+        }
+
         let output = if self.format == "human-readable" {
-            self.format_human_readable(result)?
+            self.format_human_readable(&output_result)?
+        } else if self.format == "polkadot-js" {
+            let mut export = crate::models::PolkadotJsStaking::from_result(result, election_data, self.era);
+            export.reencode_accounts(account_format, prefix);
+            export.to_json()?
+        } else if self.format == "csv" {
+            let unit = self.token_unit(settings, election_data)?;
+            // Spreadsheets get addresses even when the rest of the output keeps raw keys
+            let csv_format = if account_format.is_as_loaded() { AccountFormat::Network } else { account_format };
+            crate::cli::output::format_csv(result, election_data, unit.as_ref(), csv_format, prefix)
         } else {
-            result.to_json()?
+            output_result.to_json()?
         };
 
         if let Some(ref output_file) = self.output_file {
             std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
                 message: format!("Failed to write output file: {}", e),
                 path: output_file.clone(),
+                source: Some(ErrorSource::new(e)),
             })?;
         } else {
             println!("{}", output);
         }
 
+        if self.explain {
+            let baseline = match self.explain_baseline {
+                Some(ref path) => Some(self.load_baseline_result(path)?),
+                None => None,
+            };
+            let summary = crate::diagnostics::DiagnosticsGenerator::new().summary(&output_result, baseline.as_ref());
+            // Keep JSON on stdout parseable
+            if self.format == "human-readable" || self.output_file.is_some() {
+                println!("\n{}", summary);
+            } else {
+                eprintln!("{}", summary);
+            }
+        }
+
         Ok(())
     }
 
+    /// Load an earlier election result to compare against
+    fn load_baseline_result(&self, path: &PathBuf) -> Result<crate::models::election_result::ElectionResult, ElectionError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read baseline result file: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        serde_json::from_str(&content).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to parse baseline result JSON: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+
     /// Parse an override string in format "account_id=stake"
-    fn parse_override(&self, override_str: &str, override_type: &str) -> Result<(String, u128), ElectionError> {
+    fn parse_override(
+        &self,
+        override_str: &str,
+        override_type: &str,
+        unit: Option<&TokenUnit>,
+    ) -> Result<(String, u128), ElectionError> {
         let parts: Vec<&str> = override_str.split('=').collect();
         if parts.len() != 2 {
             return Err(ElectionError::ValidationError {
@@ -195,7 +868,7 @@ impl RunCommand {
         let account_id = parts[0].trim().to_string();
         let stake_str = parts[1].trim();
         
-        let stake = stake_str.parse::<u128>().map_err(|e| ElectionError::ValidationError {
+        let stake = crate::models::balance::parse_amount(stake_str, unit).map_err(|e| ElectionError::ValidationError {
             message: format!(
                 "Invalid stake value '{}' in {} override: {}",
                 stake_str, override_type, e
@@ -206,6 +879,186 @@ impl RunCommand {
         Ok((account_id, stake))
     }
 
+    /// Write the allocation matrix of `--export-heatmap`
+    ///
+    /// Validator columns use the output's account format, or addresses when
+    /// it keeps keys as loaded, like CSV output.
+    fn export_heatmap(
+        &self,
+        path: &PathBuf,
+        result: &crate::models::election_result::ElectionResult,
+        election_data: &ElectionData,
+        settings: &ResolvedSettings,
+        account_format: AccountFormat,
+        prefix: u16,
+    ) -> Result<(), ElectionError> {
+        let bucketing = if self.heatmap_bound.is_empty() {
+            StakeBucketing::Quantiles(self.heatmap_buckets)
+        } else {
+            let unit = self.token_unit(settings, election_data)?;
+            let bounds = self
+                .heatmap_bound
+                .iter()
+                .map(|bound| {
+                    crate::models::balance::parse_amount(bound, unit.as_ref()).map_err(|e| ElectionError::ValidationError {
+                        message: format!("Invalid heatmap bound '{}': {}", bound, e),
+                        field: Some("heatmap_bound".to_string()),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            StakeBucketing::Bounds(bounds)
+        };
+        let mut matrix = AllocationMatrix::from_result(result, election_data, &bucketing, self.heatmap_validators)?;
+        matrix.reencode_accounts(if account_format.is_as_loaded() { AccountFormat::Network } else { account_format }, prefix);
+
+        let is_csv = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        let output = if is_csv { matrix.to_csv() } else { matrix.to_json()? };
+        std::fs::write(path, output).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to write heatmap file: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        eprintln!(
+            "Wrote {}x{} allocation matrix to {}",
+            matrix.buckets.len(),
+            matrix.validators.len(),
+            path.display()
+        );
+        Ok(())
+    }
+
+    /// Build the commission policy from `--min-commission`, `--max-commission`
+    /// and the refusal flags
+    fn commission_policy(&self) -> CommissionPolicy {
+        let mut policy = CommissionPolicy::new();
+        if let Some(min) = self.min_commission {
+            policy = policy.min(min);
+        }
+        if let Some(max) = self.max_commission {
+            policy = policy.max(max);
+        }
+        if self.drop_non_compliant {
+            policy = policy.refusal(CommissionRefusal::OutOfBounds);
+        } else if !self.refusing_validator.is_empty() {
+            policy = policy.refusal(CommissionRefusal::Accounts(self.refusing_validator.clone()));
+        }
+        policy
+    }
+
+    /// Build the sybil scenario from `--sybil-attacker-stake` and `--sybil-split`
+    fn sybil_scenario(&self, sybil_count: usize, unit: Option<&TokenUnit>) -> Result<SybilScenario, ElectionError> {
+        let mut scenario = SybilScenario::new(sybil_count);
+        for stake_str in &self.sybil_attacker_stake {
+            let stake = crate::models::balance::parse_amount(stake_str, unit).map_err(|e| ElectionError::ValidationError {
+                message: format!("Invalid sybil attacker stake '{}': {}", stake_str, e),
+                field: Some("sybil_attacker_stake".to_string()),
+            })?;
+            scenario = scenario.attacker_stake(stake);
+        }
+        if !self.sybil_split.is_empty() {
+            let splits = self
+                .sybil_split
+                .iter()
+                .map(|split| split.parse::<SybilSplit>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| ElectionError::ValidationError {
+                    message: e,
+                    field: Some("sybil_split".to_string()),
+                })?;
+            scenario = scenario.splits(splits);
+        }
+        Ok(scenario)
+    }
+
+    /// Parse `--conviction` and `--default-conviction` arguments
+    fn parse_convictions(&self) -> Result<ConvictionWeights, ElectionError> {
+        let parse = |value: &str| {
+            value.trim().parse::<Conviction>().map_err(|e| ElectionError::ValidationError {
+                message: e,
+                field: Some("conviction".to_string()),
+            })
+        };
+
+        let mut convictions = ConvictionWeights::new();
+        if let Some(ref default) = self.default_conviction {
+            convictions = convictions.default_conviction(parse(default)?);
+        }
+        for conviction_str in &self.conviction {
+            let (account_id, conviction) = conviction_str.split_once('=').ok_or_else(|| ElectionError::ValidationError {
+                message: format!(
+                    "Invalid conviction format: '{}'. Expected format: account_id=conviction",
+                    conviction_str
+                ),
+                field: Some("conviction".to_string()),
+            })?;
+            convictions = convictions.nominator(account_id.trim(), parse(conviction)?);
+        }
+        Ok(convictions)
+    }
+
+    /// Load an override layer from a "name=path" argument
+    fn load_override_layer(
+        &self,
+        layer_str: &str,
+        unit: Option<&TokenUnit>,
+    ) -> Result<(String, crate::models::election_overrides::ElectionOverrides), ElectionError> {
+        let (name, path) = layer_str.split_once('=').ok_or_else(|| ElectionError::ValidationError {
+            message: format!(
+                "Invalid override layer format: '{}'. Expected format: name=path",
+                layer_str
+            ),
+            field: Some("override_layer".to_string()),
+        })?;
+        let path = PathBuf::from(path.trim());
+
+        let content = std::fs::read_to_string(&path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read override layer file: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        let overrides = ElectionOverrides::from_json_str(&content, unit).map_err(|e| match e {
+            ElectionError::ValidationError { message, field: None } => ElectionError::FileError {
+                message: format!("Failed to parse override layer: {}", message),
+                path: path.clone(),
+                source: None,
+            },
+            other => other,
+        })?;
+
+        Ok((name.trim().to_string(), overrides))
+    }
+
+    /// Load a stake flow scenario from a JSON file
+    fn load_stake_flows(&self, path: &PathBuf, unit: Option<&TokenUnit>) -> Result<StakeFlowScenario, ElectionError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read stake flow scenario: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        StakeFlowScenario::from_json_str(&content, unit).map_err(|e| match e {
+            ElectionError::ValidationError { message, field: None } => ElectionError::FileError {
+                message,
+                path: path.clone(),
+                source: None,
+            },
+            other => other,
+        })
+    }
+
+    /// Load candidate rules from a JSON file
+    fn load_rules(&self, path: &PathBuf) -> Result<crate::models::candidate_rules::CandidateRules, ElectionError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read rules file: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        serde_json::from_str(&content).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to parse rules JSON: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+
     /// Format result as human-readable text
     fn format_human_readable(&self, result: &crate::models::election_result::ElectionResult) -> Result<String, ElectionError> {
         let mut output = String::new();
@@ -213,7 +1066,30 @@ impl RunCommand {
         output.push_str("================\n");
         output.push_str(&format!("Algorithm: {:?}\n", result.algorithm_used));
         output.push_str(&format!("Total Stake: {}\n", result.total_stake));
-        output.push_str(&format!("Selected Validators: {}\n\n", result.selected_validators.len()));
+        output.push_str(&format!("Selected Validators: {}\n", result.selected_validators.len()));
+        if !result.execution_metadata.applied_override_layers.is_empty() {
+            output.push_str(&format!(
+                "Override Layers: {}\n",
+                result.execution_metadata.applied_override_layers.join(" -> ")
+            ));
+        }
+        if !result.execution_metadata.rule_applications.is_empty() {
+            output.push_str("Candidate Rules:\n");
+            for application in &result.execution_metadata.rule_applications {
+                output.push_str(&format!(
+                    "  - {}: {} candidates\n",
+                    application.rule.describe(),
+                    application.affected.len()
+                ));
+            }
+        }
+        if !result.issues.is_empty() {
+            output.push_str("Issues:\n");
+            for issue in &result.issues {
+                output.push_str(&format!("  - {}\n", issue.message));
+            }
+        }
+        output.push('\n');
 
         output.push_str("Selected Validators:\n");
         for (idx, validator) in result.selected_validators.iter().take(10).enumerate() {
@@ -235,10 +1111,142 @@ impl RunCommand {
             output.push_str("\nDiagnostics\n");
             output.push_str("===========\n");
             
-            if !diagnostics.warnings.is_empty() {
-                output.push_str("\nWarnings:\n");
-                for warning in &diagnostics.warnings {
-                    output.push_str(&format!("  - {}\n", warning));
+            if !diagnostics.findings.is_empty() {
+                output.push_str("\nFindings:\n");
+                for finding in &diagnostics.findings {
+                    output.push_str(&format!(
+                        "  - [{}] {}: {}\n",
+                        finding.severity, finding.code, finding.message
+                    ));
+                }
+            }
+
+            if let Some(global_minimum) = diagnostics
+                .minimum_active_stake
+                .as_ref()
+                .and_then(|m| m.global_minimum)
+            {
+                output.push_str(&format!("\nMinimum Active Stake: {}\n", global_minimum));
+            }
+
+            if let Some(clusters) = diagnostics
+                .nominator_clusters
+                .as_ref()
+                .filter(|c| !c.clusters.is_empty())
+            {
+                output.push_str(&format!(
+                    "\nCorrelated Nominator Clusters (similarity >= {}%):\n",
+                    clusters.similarity_threshold_percent
+                ));
+                for cluster in clusters.clusters.iter().take(5) {
+                    output.push_str(&format!(
+                        "  - {} nominators, stake {} ({:.2}% of nominator stake), {} shared targets\n",
+                        cluster.nominator_ids.len(),
+                        cluster.total_stake,
+                        cluster.stake_share_bps as f64 / 100.0,
+                        cluster.shared_targets.len()
+                    ));
+                }
+            }
+
+            if let Some(groups) = diagnostics.operator_groups.as_ref() {
+                output.push_str(&format!(
+                    "\nOperators: {} in the elected set (Nakamoto coefficient {} operators / {} validators)\n",
+                    groups.elected_operator_count,
+                    groups.nakamoto_coefficient,
+                    groups.validator_nakamoto_coefficient
+                ));
+                for operator in groups
+                    .operators
+                    .iter()
+                    .filter(|o| o.elected_validator_ids.len() > 1)
+                    .take(5)
+                {
+                    output.push_str(&format!(
+                        "  - {}: {} elected of {} candidates, backing {} ({:.2}% of elected backing)\n",
+                        operator.display_name.as_deref().unwrap_or(&operator.operator_id),
+                        operator.elected_validator_ids.len(),
+                        operator.candidate_count,
+                        operator.total_backing,
+                        operator.stake_share_bps as f64 / 100.0
+                    ));
+                }
+            }
+
+            if let Some(diversity) = diagnostics.diversity.as_ref() {
+                output.push_str("\nDiversity of Elected Set:\n");
+                for (label, concentration) in [
+                    ("Country", &diversity.by_country),
+                    ("City", &diversity.by_city),
+                    ("Hosting provider", &diversity.by_hosting_provider),
+                ] {
+                    let Some(largest) = concentration.groups.first() else {
+                        continue;
+                    };
+                    output.push_str(&format!(
+                        "  {}: {} distinct, HHI {}, largest {} ({:.2}% of validators), {} unknown\n",
+                        label,
+                        concentration.groups.len(),
+                        concentration.herfindahl_index,
+                        largest.value,
+                        concentration.largest_share_bps as f64 / 100.0,
+                        concentration.unknown_count
+                    ));
+                }
+            }
+
+            if let Some(pjr) = diagnostics.pjr.as_ref() {
+                let verdict = if pjr.satisfied { "satisfied" } else { "VIOLATED" };
+                output.push_str(&format!("\nPJR (threshold {}): {}\n", pjr.threshold, verdict));
+                for violation in pjr.violations.iter().take(5) {
+                    output.push_str(&format!(
+                        "  - {} voters could give unelected {} a pre-score of {}\n",
+                        violation.voter_ids.len(),
+                        violation.candidate_id,
+                        violation.pre_score
+                    ));
+                }
+                let verdict = if pjr.locally_satisfied { "satisfied" } else { "VIOLATED" };
+                output.push_str(&format!("Local PJR: {}\n", verdict));
+                for violation in pjr.local_violations.iter().take(5) {
+                    output.push_str(&format!(
+                        "  - {} voters of {} are owed {} seats but back {} elected\n",
+                        violation.voter_ids.len(),
+                        violation.candidate_id,
+                        violation.owed_seats,
+                        violation.elected_seats
+                    ));
+                }
+            }
+
+            if let Some(gap) = diagnostics.maximin_gap.as_ref() {
+                output.push_str(&format!(
+                    "\nMaximin Support: {} (balanced {}, MMS {}, upper bound {}), gap {:.2}%\n",
+                    gap.minimal_support,
+                    gap.balanced_minimal_support,
+                    gap.mms_minimal_support,
+                    gap.upper_bound,
+                    gap.gap_bps as f64 / 100.0
+                ));
+            }
+
+            if let Some(sensitivity) = diagnostics
+                .stake_sensitivity
+                .as_ref()
+                .filter(|s| !s.elected.is_empty() || !s.near_misses.is_empty())
+            {
+                output.push_str("\nStake Sensitivity Around the Cutoff:\n");
+                for margin in &sensitivity.elected {
+                    output.push_str(&format!(
+                        "  - {} (elected): backing {}, could lose {}\n",
+                        margin.validator_id, margin.backing, margin.margin
+                    ));
+                }
+                for near_miss in &sensitivity.near_misses {
+                    output.push_str(&format!(
+                        "  - {} (not elected): approval stake {}, needs {} more\n",
+                        near_miss.candidate_id, near_miss.approval_stake, near_miss.shortfall
+                    ));
                 }
             }
 
@@ -262,6 +1270,17 @@ impl RunCommand {
     }
 }
 
+/// Whether an event path refers to one of the watched files
+fn is_watched(path: &std::path::Path, watched: &[PathBuf]) -> bool {
+    watched.iter().any(|file| {
+        path == file
+            || match (path.canonicalize(), file.canonicalize()) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => path.file_name() == file.file_name(),
+            }
+    })
+}
+
 /// Server command for starting the REST API server
 #[derive(Parser)]
 #[command(name = "server")]
@@ -270,12 +1289,77 @@ pub struct ServerCommand {
     /// Port to listen on
     #[arg(long, default_value = "3000")]
     pub port: u16,
+
+    /// Notification sinks fired when an election job completes
+    #[command(flatten)]
+    pub notifications: NotificationArgs,
+
+    /// JSON file of accepted API keys with their roles and quotas; without it every request is accepted
+    #[arg(long, value_name = "PATH")]
+    pub api_keys: Option<PathBuf>,
+
+    /// Most election results kept to answer identical requests again; 0 disables the cache
+    #[arg(long, value_name = "COUNT", default_value = "32")]
+    pub result_cache_size: usize,
 }
 
 impl ServerCommand {
     /// Execute the server command
     pub async fn execute(&self) -> Result<(), ElectionError> {
-        let server = crate::api::server::ApiServer::new(self.port);
+        let mut server = crate::api::server::ApiServer::new(self.port);
+        if let Some(notifier) = self.notifications.notifier()? {
+            server = server.with_notifier(notifier);
+        }
+        if let Some(ref path) = self.api_keys {
+            server = server.with_api_keys(crate::api::auth::ApiKeys::load_from_file(path)?);
+        }
+        if self.result_cache_size > 0 {
+            server = server.with_result_cache(crate::cache::ResultCache::new(self.result_cache_size));
+        }
         server.start().await
     }
 }
+
+/// Notification sink flags shared by watch and serve commands
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct NotificationArgs {
+    /// Webhook URL to POST notifications to (can be repeated)
+    #[arg(long, value_name = "URL")]
+    pub notify_webhook: Vec<String>,
+
+    /// Shell command to run per notification, payload on stdin (can be repeated)
+    #[arg(long, value_name = "COMMAND")]
+    pub notify_command: Vec<String>,
+
+    /// File containing a payload template with {{field}} placeholders
+    #[arg(long, value_name = "PATH")]
+    pub notify_template: Option<PathBuf>,
+}
+
+impl NotificationArgs {
+    /// Build the notification configuration from the flags
+    pub fn config(&self) -> Result<crate::notifications::NotificationConfig, ElectionError> {
+        let mut config = crate::notifications::NotificationConfig::new();
+        for url in &self.notify_webhook {
+            config = config.webhook(url.trim());
+        }
+        for command in &self.notify_command {
+            config = config.command(command.as_str());
+        }
+        if let Some(ref path) = self.notify_template {
+            let template = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to read notification template: {}", e),
+                path: path.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            config = config.template(template);
+        }
+        config.build()
+    }
+
+    /// Build a notifier, or `None` when no sink is configured
+    pub fn notifier(&self) -> Result<Option<crate::notifications::Notifier>, ElectionError> {
+        let config = self.config()?;
+        Ok(config.has_sinks().then(|| crate::notifications::Notifier::new(config)))
+    }
+}
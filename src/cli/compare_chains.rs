@@ -0,0 +1,265 @@
+//! Multi-chain comparison command
+//!
+//! Runs equivalently configured elections on two chains, for example
+//! Polkadot and Kusama at corresponding eras, and reports set utilization,
+//! stake concentration and minimum backing side by side. Chains with their
+//! own staking parameters to choose read this to see how a set size plays
+//! out on a given stake distribution.
+
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::json::JsonLoader;
+use crate::input::rpc::RpcLoader;
+use crate::models::chain_comparison::{ChainComparison, ChainProfile};
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::settings::{RunSettings, CHAIN_PRESETS};
+use crate::types::AlgorithmType;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Compare-chains command for running the same election on two chains
+#[derive(Parser)]
+#[command(name = "compare-chains")]
+#[command(about = "Run equivalently configured elections on two chains and compare them")]
+pub struct CompareChainsCommand {
+    /// First chain: a JSON snapshot file, a chain preset (polkadot, kusama, westend)
+    /// or an RPC URL, the last two optionally followed by @BLOCK
+    #[arg(long, value_name = "SOURCE")]
+    pub left: String,
+
+    /// Second chain, in the same forms as --left
+    #[arg(long, value_name = "SOURCE")]
+    pub right: String,
+
+    /// Name the first chain is reported under (default: the chain named in its data, or the source)
+    #[arg(long)]
+    pub left_label: Option<String>,
+
+    /// Name the second chain is reported under
+    #[arg(long)]
+    pub right_label: Option<String>,
+
+    /// Number of validators to select on both chains
+    #[arg(long)]
+    pub active_set_size: Option<u32>,
+
+    /// Number of validators to select on the first chain, instead of --active-set-size
+    #[arg(long)]
+    pub left_active_set_size: Option<u32>,
+
+    /// Number of validators to select on the second chain, instead of --active-set-size
+    #[arg(long)]
+    pub right_active_set_size: Option<u32>,
+
+    /// Election algorithm used on both chains
+    #[arg(long, default_value = "sequential-phragmen")]
+    pub algorithm: String,
+
+    /// Output format: json or human-readable
+    #[arg(long, default_value = "json")]
+    pub format: String,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+}
+
+impl CompareChainsCommand {
+    /// Execute the compare-chains command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let algorithm = self.algorithm.parse::<AlgorithmType>().map_err(|e| ElectionError::ValidationError {
+            message: e,
+            field: Some("algorithm".to_string()),
+        })?;
+        let left = self
+            .profile(&self.left, self.left_label.as_deref(), self.left_active_set_size, "left", algorithm)
+            .await?;
+        let right = self
+            .profile(&self.right, self.right_label.as_deref(), self.right_active_set_size, "right", algorithm)
+            .await?;
+        let comparison = ChainComparison::new(left, right);
+
+        let output = if self.format == "human-readable" {
+            format_comparison(&comparison)
+        } else {
+            serde_json::to_string_pretty(&comparison).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize chain comparison: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?
+        };
+
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+        } else {
+            println!("{}", output);
+        }
+
+        Ok(())
+    }
+
+    /// Load one side's data, run its election and profile the result
+    async fn profile(
+        &self,
+        source: &str,
+        label: Option<&str>,
+        active_set_size: Option<u32>,
+        side: &str,
+        algorithm: AlgorithmType,
+    ) -> Result<ChainProfile, ElectionError> {
+        let desired = active_set_size.or(self.active_set_size).ok_or_else(|| ElectionError::ValidationError {
+            message: format!("No active set size for the {} chain: pass --active-set-size or --{}-active-set-size", side, side),
+            field: Some(format!("{}_active_set_size", side)),
+        })?;
+        let source = ChainSource::parse(source)?;
+        eprintln!("Loading {} chain from {}...", side, source);
+        let data = source.load().await?;
+        let config = ElectionConfiguration::builder()
+            .algorithm(algorithm)
+            .active_set_size(desired)
+            .build()?;
+        let result = ElectionEngine::new().execute(&config, &data)?;
+        let label = label.map(str::to_string).unwrap_or_else(|| source.label(&data));
+        Ok(ChainProfile::from_result(label, &data, &result, desired))
+    }
+}
+
+/// Where one side of the comparison is loaded from
+enum ChainSource {
+    /// JSON snapshot file
+    File(PathBuf),
+    /// RPC endpoint, at a block or the latest one; `chain` is set for presets
+    Rpc { url: String, chain: Option<String>, block_number: Option<u64> },
+}
+
+impl ChainSource {
+    /// Parse a file path, or a chain preset or RPC URL with an optional "@BLOCK"
+    fn parse(source: &str) -> Result<Self, ElectionError> {
+        let source = source.trim();
+        let (endpoint, block_number) = match source.rsplit_once('@') {
+            Some((endpoint, block)) if !block.is_empty() && block.bytes().all(|b| b.is_ascii_digit()) => {
+                let block = block.parse::<u64>().map_err(|e| ElectionError::ValidationError {
+                    message: format!("Invalid block number in '{}': {}", source, e),
+                    field: Some("block_number".to_string()),
+                })?;
+                (endpoint, Some(block))
+            }
+            _ => (source, None),
+        };
+        if let Some(preset) = RunSettings::chain_preset(endpoint) {
+            return Ok(Self::Rpc {
+                url: preset.rpc_url.unwrap_or_default(),
+                chain: Some(endpoint.to_lowercase()),
+                block_number,
+            });
+        }
+        if ["ws://", "wss://", "http://", "https://"].iter().any(|scheme| endpoint.starts_with(scheme)) {
+            return Ok(Self::Rpc { url: endpoint.to_string(), chain: None, block_number });
+        }
+        let path = PathBuf::from(source);
+        if path.is_file() {
+            return Ok(Self::File(path));
+        }
+        Err(ElectionError::ValidationError {
+            message: format!(
+                "'{}' is not a snapshot file, a chain preset ({}) or an RPC URL",
+                source,
+                CHAIN_PRESETS.join(", ")
+            ),
+            field: Some("source".to_string()),
+        })
+    }
+
+    /// Load the source's election data
+    async fn load(&self) -> Result<ElectionData, ElectionError> {
+        match self {
+            Self::File(path) => JsonLoader::new().load_from_file(path.clone()),
+            Self::Rpc { url, chain, block_number } => {
+                let loader = RpcLoader::new(url)?;
+                let mut data = match block_number {
+                    Some(block_number) => loader.load_at_block(*block_number).await?,
+                    None => loader.load_latest().await?,
+                };
+                // Presets name the chain so its token is known
+                if let (Some(chain), Some(metadata)) = (chain, data.metadata.as_mut()) {
+                    metadata.chain.get_or_insert_with(|| chain.clone());
+                }
+                Ok(data)
+            }
+        }
+    }
+
+    /// Default report name: the chain named in the data, or else the source
+    fn label(&self, data: &ElectionData) -> String {
+        if let Some(chain) = data.metadata.as_ref().and_then(|m| m.chain.clone()) {
+            return chain;
+        }
+        match self {
+            Self::File(path) => path.file_stem().map_or_else(|| path.display().to_string(), |s| s.to_string_lossy().into_owned()),
+            Self::Rpc { url, .. } => url.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for ChainSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Rpc { url, block_number: Some(block), .. } => write!(f, "{} at block {}", url, block),
+            Self::Rpc { url, .. } => write!(f, "{} at the latest block", url),
+        }
+    }
+}
+
+/// Format a chain comparison as a two-column table
+fn format_comparison(comparison: &ChainComparison) -> String {
+    let (left, right) = (&comparison.left, &comparison.right);
+    let rows: Vec<(&str, String, String)> = vec![
+        ("Block", optional(left.block_number), optional(right.block_number)),
+        ("Candidates", left.candidate_count.to_string(), right.candidate_count.to_string()),
+        ("Nominators", left.nominator_count.to_string(), right.nominator_count.to_string()),
+        (
+            "Seats filled",
+            format!("{}/{} ({})", left.elected_count, left.desired_seats, percent(left.set_utilization_bps)),
+            format!("{}/{} ({})", right.elected_count, right.desired_seats, percent(right.set_utilization_bps)),
+        ),
+        ("Candidates per seat", ratio(left.candidates_per_seat_bps), ratio(right.candidates_per_seat_bps)),
+        ("Total stake", left.total_stake.to_string(), right.total_stake.to_string()),
+        ("Stake backing the set", percent(left.stake_utilization_bps), percent(right.stake_utilization_bps)),
+        ("Minimum backing", left.minimal_backing.to_string(), right.minimal_backing.to_string()),
+        ("Median backing", left.median_backing.to_string(), right.median_backing.to_string()),
+        ("Minimum / median", percent(left.minimal_to_median_bps), percent(right.minimal_to_median_bps)),
+        (
+            "Nakamoto coefficient",
+            format!("{} ({})", left.nakamoto_coefficient, percent(left.nakamoto_share_bps)),
+            format!("{} ({})", right.nakamoto_coefficient, percent(right.nakamoto_share_bps)),
+        ),
+        ("Top 10% backing share", percent(left.top_decile_share_bps), percent(right.top_decile_share_bps)),
+        ("Herfindahl index", left.herfindahl_index.to_string(), right.herfindahl_index.to_string()),
+    ];
+    let width = rows.iter().map(|(name, _, _)| name.len()).max().unwrap_or(0);
+    let left_width = rows.iter().map(|(_, l, _)| l.len()).chain([left.label.len()]).max().unwrap_or(0);
+
+    let mut output = String::new();
+    output.push_str(&format!("{:<width$}  {:<left_width$}  {}\n", "", left.label, right.label));
+    for (name, l, r) in rows {
+        output.push_str(&format!("{:<width$}  {:<left_width$}  {}\n", name, l, r));
+    }
+    output
+}
+
+fn percent(bps: u32) -> String {
+    format!("{:.1}%", bps as f64 / 100.0)
+}
+
+fn ratio(bps: u32) -> String {
+    format!("{:.2}", bps as f64 / 10_000.0)
+}
+
+fn optional(value: Option<u64>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
@@ -0,0 +1,34 @@
+//! Shell completion command
+//!
+//! Generates completion scripts with `clap_complete` from the clap definitions
+//! of the command line, so new subcommands and flags are picked up without
+//! maintaining the scripts by hand.
+
+use clap::{Command, Parser};
+
+pub use clap_complete::Shell;
+
+/// Completions command for printing a shell completion script
+#[derive(Parser)]
+#[command(name = "completions")]
+#[command(about = "Print a shell completion script")]
+pub struct CompletionsCommand {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+impl CompletionsCommand {
+    /// Generate the completion script for `command`, the tool's top-level command
+    pub fn generate(&self, command: Command) -> String {
+        completion_script(command, self.shell)
+    }
+}
+
+/// Completion script for `command` in `shell`
+pub fn completion_script(mut command: Command, shell: Shell) -> String {
+    let name = command.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut command, name, &mut script);
+    String::from_utf8_lossy(&script).into_owned()
+}
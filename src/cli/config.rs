@@ -0,0 +1,176 @@
+//! Configuration command and the layered settings flags
+//!
+//! [`SettingsArgs`] are the flags that can also come from a configuration
+//! file or a chain preset; `run` and `config show` share them. `config show`
+//! prints the effective settings as a TOML file, and with `--resolve` names
+//! the layer each value came from.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::settings::{ResolvedSettings, RunSettings, SettingsResolver};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Run settings that can also come from a configuration file or chain preset
+#[derive(Args, Debug, Clone, Default)]
+pub struct SettingsArgs {
+    /// TOML configuration file, overridden by flags
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Apply the preset of a known chain: polkadot, kusama or westend
+    #[arg(long, value_name = "NAME")]
+    pub chain: Option<String>,
+
+    /// Election algorithm to use (sequential-phragmen, parallel-phragmen, multi-phase, mms;
+    /// default: sequential-phragmen)
+    #[arg(long)]
+    pub algorithm: Option<String>,
+
+    /// Number of validators to select
+    #[arg(long)]
+    pub active_set_size: Option<u32>,
+
+    /// Kind of election: staking (validators) or council (pallet-elections-phragmen)
+    /// (default: staking)
+    #[arg(long)]
+    pub kind: Option<String>,
+
+    /// Validation strictness: strict, chain-mirroring or permissive (default: chain-mirroring)
+    #[arg(long)]
+    pub validation_profile: Option<String>,
+
+    /// Balance the solution's stake with at most this many rounds (default 10 with --balancing-tolerance)
+    #[arg(long, value_name = "N")]
+    pub balancing_iterations: Option<usize>,
+
+    /// Stop balancing once no voter moves more than this stake in a round (plancks or e.g. "0.1 DOT")
+    #[arg(long, value_name = "STAKE")]
+    pub balancing_tolerance: Option<String>,
+
+    /// Continue past recoverable problems (failed storage entries, diagnostics
+    /// errors, unmatched overrides, unknown forced candidates) and list them in
+    /// the result's issues
+    #[arg(long)]
+    pub partial_results: bool,
+
+    /// Rounding of derived ratios such as allocation proportions: exact,
+    /// truncate:N, round-half-even:N or significant-digits:N (default: exact)
+    #[arg(long, value_name = "POLICY")]
+    pub rounding: Option<String>,
+
+    /// Token of the chain for amounts like "1.5 DOT": DOT, KSM, WND or SYMBOL:decimals
+    /// (defaults to the chain preset, then the chain named in the input data, if known)
+    #[arg(long, value_name = "UNIT")]
+    pub token: Option<String>,
+
+    /// Encoding of account IDs in results and reports: as-loaded, network (SS58
+    /// with the chain's prefix), ss58:N or public-key (default: as-loaded)
+    #[arg(long, value_name = "FORMAT")]
+    pub account_format: Option<String>,
+}
+
+impl SettingsArgs {
+    /// The command line layer: only the flags that were given
+    pub fn cli_layer(&self) -> RunSettings {
+        RunSettings {
+            chain: self.chain.clone(),
+            algorithm: self.algorithm.clone(),
+            active_set_size: self.active_set_size,
+            kind: self.kind.clone(),
+            validation_profile: self.validation_profile.clone(),
+            rounding: self.rounding.clone(),
+            partial_results: self.partial_results.then_some(true),
+            balancing_iterations: self.balancing_iterations,
+            balancing_tolerance: self.balancing_tolerance.clone(),
+            token: self.token.clone(),
+            account_format: self.account_format.clone(),
+            ..RunSettings::default()
+        }
+    }
+
+    /// Resolve `cli` over the defaults, the chain preset and `--config`
+    pub fn resolve(&self, cli: RunSettings) -> Result<ResolvedSettings, ElectionError> {
+        let mut resolver = SettingsResolver::new().with_cli(cli);
+        if let Some(ref path) = self.config {
+            resolver = resolver.with_file(path.clone(), RunSettings::load_from_file(path)?);
+        }
+        resolver.resolve()
+    }
+}
+
+/// Config command for inspecting layered settings
+#[derive(Parser, Debug)]
+#[command(name = "config")]
+#[command(about = "Inspect the settings resolved from defaults, chain presets, a config file and flags")]
+pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+/// What to do with the configuration
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the effective settings
+    Show {
+        #[command(flatten)]
+        settings: SettingsArgs,
+
+        /// RPC URL for fetching on-chain data
+        #[arg(long)]
+        rpc_url: Option<String>,
+
+        /// Number of RPC storage queries in flight at once
+        #[arg(long)]
+        rpc_concurrency: Option<usize>,
+
+        /// Seconds a single RPC request may take
+        #[arg(long)]
+        rpc_timeout: Option<u64>,
+
+        /// Name the layer each value came from
+        #[arg(long)]
+        resolve: bool,
+
+        /// Output format: toml or json
+        #[arg(long, default_value = "toml", value_parser = ["toml", "json"])]
+        format: String,
+    },
+}
+
+impl ConfigCommand {
+    /// Execute the config command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let ConfigAction::Show {
+            settings,
+            rpc_url,
+            rpc_concurrency,
+            rpc_timeout,
+            resolve,
+            format,
+        } = &self.action;
+
+        let cli = RunSettings {
+            rpc_url: rpc_url.clone(),
+            rpc_concurrency: *rpc_concurrency,
+            rpc_timeout: *rpc_timeout,
+            ..settings.cli_layer()
+        };
+        let resolved = settings.resolve(cli)?;
+
+        let output = if format == "json" {
+            let json = if *resolve {
+                serde_json::to_string_pretty(&resolved)
+            } else {
+                serde_json::to_string_pretty(&resolved.settings)
+            };
+            json.map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize settings: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?
+        } else {
+            resolved.to_toml(*resolve)?
+        };
+        println!("{}", output.trim_end());
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+//! Long-running daemon command
+//!
+//! Serves the REST API and, given a WebSocket URL, also watches the chain and
+//! records a prediction at every election snapshot, served at
+//! `/predictions/latest`. Given a schedule file, it also runs recurring
+//! backtests, listed at `/schedule/runs`. Every option can be set through an
+//! `OFFLINE_ELECTION_*` environment variable so a container needs no
+//! arguments. On SIGTERM or Ctrl-C the daemon stops accepting requests and
+//! new elections, waits for running elections up to the shutdown timeout,
+//! then cancels the rest, which fail with a `CANCELLED` event, and exits.
+
+use crate::api::auth::ApiKeys;
+use crate::api::handlers::HandlerState;
+use crate::api::lifecycle::Lifecycle;
+use crate::api::server::ApiServer;
+use crate::cache::ResultCache;
+use crate::cli::commands::NotificationArgs;
+use crate::cli::watch_chain::predict_next_set;
+use crate::scheduler::Scheduler;
+use crate::error::ElectionError;
+use crate::input::chain_watch::{http_url_for, ChainWatcher};
+use crate::input::rpc::RpcLoader;
+use crate::types::AlgorithmType;
+use clap::Parser;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Pause before reconnecting after the chain connection fails
+const RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// How long cancelled elections get to stop after the shutdown timeout
+const CANCEL_GRACE: Duration = Duration::from_secs(5);
+
+/// Daemon command serving the API and optionally watching the chain
+#[derive(Parser, Debug)]
+#[command(name = "daemon")]
+#[command(about = "Serve the API and watch the chain until SIGTERM, configured by flags or OFFLINE_ELECTION_* variables")]
+pub struct DaemonCommand {
+    /// Port to listen on
+    #[arg(long, env = "OFFLINE_ELECTION_PORT", default_value = "3000")]
+    pub port: u16,
+
+    /// JSON file of accepted API keys; without it every request is accepted
+    #[arg(long, env = "OFFLINE_ELECTION_API_KEYS", value_name = "PATH")]
+    pub api_keys: Option<PathBuf>,
+
+    /// Most election results kept to answer identical requests again; 0 disables the cache
+    #[arg(long, env = "OFFLINE_ELECTION_RESULT_CACHE_SIZE", value_name = "COUNT", default_value = "32")]
+    pub result_cache_size: usize,
+
+    /// Seconds to let running elections finish on shutdown before cancelling them
+    #[arg(long, env = "OFFLINE_ELECTION_SHUTDOWN_TIMEOUT", value_name = "SECONDS", default_value = "30")]
+    pub shutdown_timeout: u64,
+
+    /// WebSocket URL to watch for election snapshots; without it the chain is not watched
+    #[arg(long, env = "OFFLINE_ELECTION_WS_URL")]
+    pub ws_url: Option<String>,
+
+    /// HTTP RPC URL for fetching snapshot data (default: derived from --ws-url)
+    #[arg(long, env = "OFFLINE_ELECTION_RPC_URL")]
+    pub rpc_url: Option<String>,
+
+    /// Election algorithm for predictions (sequential-phragmen, parallel-phragmen, multi-phase, mms)
+    #[arg(long, env = "OFFLINE_ELECTION_ALGORITHM", default_value = "sequential-phragmen")]
+    pub algorithm: String,
+
+    /// Number of validators to predict (default: the chain's desired targets)
+    #[arg(long, env = "OFFLINE_ELECTION_ACTIVE_SET_SIZE")]
+    pub active_set_size: Option<u32>,
+
+    /// Webhook URLs to POST notifications to (comma-separated in the variable)
+    #[arg(long, env = "OFFLINE_ELECTION_NOTIFY_WEBHOOK", value_name = "URL", value_delimiter = ',')]
+    pub notify_webhook: Vec<String>,
+
+    /// Shell command to run per notification, payload on stdin
+    #[arg(long, env = "OFFLINE_ELECTION_NOTIFY_COMMAND", value_name = "COMMAND")]
+    pub notify_command: Option<String>,
+
+    /// File containing a payload template with {{field}} placeholders
+    #[arg(long, env = "OFFLINE_ELECTION_NOTIFY_TEMPLATE", value_name = "PATH")]
+    pub notify_template: Option<PathBuf>,
+
+    /// TOML schedule of recurring backtests to run
+    #[arg(long, env = "OFFLINE_ELECTION_SCHEDULE", value_name = "PATH")]
+    pub schedule: Option<PathBuf>,
+}
+
+impl DaemonCommand {
+    /// Execute the daemon command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let algorithm = self.algorithm.parse::<AlgorithmType>()
+            .map_err(|e| ElectionError::ValidationError {
+                message: format!("Invalid algorithm: {}", e),
+                field: Some("algorithm".to_string()),
+            })?;
+        let mut server = self.server()?;
+        let scheduler = match self.schedule {
+            Some(ref path) => {
+                let scheduler = Scheduler::load_from_file(path)?;
+                server = server.with_run_history(scheduler.history().clone());
+                let scheduler = scheduler.with_handler_state(server.state().clone());
+                eprintln!("Running {} scheduled jobs: {}", scheduler.job_names().len(), scheduler.job_names().join(", "));
+                scheduler.spawn()
+            }
+            None => Vec::new(),
+        };
+        let lifecycle = server.state().lifecycle().clone();
+
+        let watcher = match self.ws_url {
+            Some(ref ws_url) => {
+                let rpc_url = self.rpc_url.clone().unwrap_or_else(|| http_url_for(ws_url));
+                // Reject a malformed URL now rather than on every reconnect
+                RpcLoader::new(&rpc_url)?;
+                let state = server.state().clone();
+                let (ws_url, active_set_size) = (ws_url.clone(), self.active_set_size);
+                Some(tokio::spawn(async move {
+                    watch_chain(&ws_url, &rpc_url, algorithm, active_set_size, &state).await
+                }))
+            }
+            None => None,
+        };
+
+        let timeout = Duration::from_secs(self.shutdown_timeout);
+        let draining = lifecycle.clone();
+        let served = server
+            .start_with_shutdown(async move {
+                shutdown_signal().await;
+                eprintln!("Shutting down: waiting up to {}s for running elections...", timeout.as_secs());
+                draining.drain();
+                tokio::spawn(async move {
+                    if !draining.wait_idle(timeout).await {
+                        eprintln!("Cancelling {} elections still running", draining.running_jobs());
+                        draining.cancel_jobs();
+                    }
+                });
+            })
+            .await;
+
+        if let Some(watcher) = watcher {
+            watcher.abort();
+        }
+        // Elections started in the background and scheduled runs outlive their requests
+        if !lifecycle.wait_idle(timeout + CANCEL_GRACE).await {
+            eprintln!("⚠️  {} elections did not stop in time", lifecycle.running_jobs());
+        }
+        for task in scheduler {
+            task.abort();
+        }
+        served
+    }
+
+    /// Build the API server from the options
+    fn server(&self) -> Result<ApiServer, ElectionError> {
+        let mut server = ApiServer::new(self.port).with_lifecycle(Lifecycle::new());
+        if let Some(notifier) = self.notifications().notifier()? {
+            server = server.with_notifier(notifier);
+        }
+        if let Some(ref path) = self.api_keys {
+            server = server.with_api_keys(ApiKeys::load_from_file(path)?);
+        }
+        if self.result_cache_size > 0 {
+            server = server.with_result_cache(ResultCache::new(self.result_cache_size));
+        }
+        Ok(server)
+    }
+
+    /// Notification flags in the form the other commands take them
+    pub fn notifications(&self) -> NotificationArgs {
+        NotificationArgs {
+            notify_webhook: self.notify_webhook.clone(),
+            notify_command: self.notify_command.iter().cloned().collect(),
+            notify_template: self.notify_template.clone(),
+        }
+    }
+}
+
+/// Record a prediction at every snapshot, reconnecting whenever the connection fails
+async fn watch_chain(
+    ws_url: &str,
+    rpc_url: &str,
+    algorithm: AlgorithmType,
+    active_set_size: Option<u32>,
+    state: &HandlerState,
+) {
+    loop {
+        if let Err(e) = watch_once(ws_url, rpc_url, algorithm, active_set_size, state).await {
+            eprintln!("⚠️  Chain watch failed: {}; reconnecting in {}s", e, RECONNECT_DELAY.as_secs());
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Follow one connection until it fails
+async fn watch_once(
+    ws_url: &str,
+    rpc_url: &str,
+    algorithm: AlgorithmType,
+    active_set_size: Option<u32>,
+    state: &HandlerState,
+) -> Result<(), ElectionError> {
+    let watcher = ChainWatcher::connect(ws_url, RpcLoader::new(rpc_url)?).await?;
+    let mut subscription = watcher.subscribe_new_heads().await?;
+    eprintln!("Watching {} for the next election snapshot...", ws_url);
+    loop {
+        let (block_number, _) = watcher.wait_for_snapshot(&mut subscription).await?;
+        match predict_next_set(watcher.loader(), algorithm, active_set_size, block_number).await {
+            Ok((data, result)) => {
+                let election_id = state.record_prediction(block_number, Arc::new(data), result).await;
+                eprintln!("Predicted the next validator set at block {} as {}", block_number, election_id);
+            }
+            Err(e) => eprintln!("Error: Prediction at block {} failed: {}", block_number, e),
+        }
+    }
+}
+
+/// Wait for SIGTERM, as sent by container runtimes, or Ctrl-C
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
@@ -0,0 +1,78 @@
+//! Error reporting for the CLI
+//!
+//! Failed commands print the error to stderr and exit with the code of its
+//! [`ErrorCategory`](crate::error::ErrorCategory), so scripts can tell a bad
+//! input from an unreachable node. With `--error-format json` the error is
+//! printed as a single JSON object instead of a sentence.
+
+use crate::error::ElectionError;
+use serde_json::json;
+
+/// How failures are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// "Error: ..." sentence
+    #[default]
+    Human,
+    /// JSON object with the error's category, exit code and fields
+    Json,
+}
+
+impl std::str::FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "human" => Ok(ErrorFormat::Human),
+            "json" => Ok(ErrorFormat::Json),
+            _ => Err(format!("Unknown error format: {} (expected human or json)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorFormat::Human => write!(f, "human"),
+            ErrorFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Error as a JSON value
+///
+/// `error` holds the serialized [`ElectionError`]: the variant name under
+/// `type` and the variant's fields.
+pub fn error_json(error: &ElectionError) -> serde_json::Value {
+    json!({
+        "code": error.code(),
+        "category": error.category().as_str(),
+        "exit_code": error.exit_code(),
+        "message": error.to_string(),
+        "error": error,
+    })
+}
+
+/// Error as it is printed to stderr in `format`, without a trailing newline
+///
+/// Human output lists the underlying causes the message does not already
+/// mention on "Caused by:" lines.
+pub fn format_error(error: &ElectionError, format: ErrorFormat) -> String {
+    match format {
+        ErrorFormat::Human => {
+            let chain = error.chain();
+            let mut text = format!("Error: {}", chain[0]);
+            for cause in &chain[1..] {
+                text.push_str(&format!("\n  Caused by: {}", cause));
+            }
+            text
+        }
+        ErrorFormat::Json => error_json(error).to_string(),
+    }
+}
+
+/// Print `error` to stderr in `format` and exit with its category's code
+pub fn exit_with_error(error: &ElectionError, format: ErrorFormat) -> ! {
+    eprintln!("{}", format_error(error, format));
+    std::process::exit(error.exit_code())
+}
@@ -0,0 +1,121 @@
+//! Snapshot hash command
+//!
+//! Prints the content hash of an election data snapshot, the same hash a run
+//! records as `execution_metadata.data_hash`, and optionally checks that a
+//! saved result was produced from that snapshot.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::json::JsonLoader;
+use crate::models::election_result::ElectionResult;
+use clap::Parser;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Hash command for fingerprinting snapshots
+#[derive(Parser)]
+#[command(name = "hash")]
+#[command(about = "Print a snapshot's content hash and check which snapshot a result came from")]
+pub struct HashCommand {
+    /// Election data snapshot (JSON)
+    pub input_file: PathBuf,
+
+    /// Check that this saved result was produced from the snapshot
+    #[arg(long, value_name = "RESULT_FILE")]
+    pub verify_result: Option<PathBuf>,
+
+    /// Output format: human-readable (`<hash>  <file>`) or json
+    #[arg(long, default_value = "human-readable")]
+    pub format: String,
+}
+
+/// Outcome of hashing a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnapshotHash {
+    /// Snapshot file that was hashed
+    pub file: PathBuf,
+    /// [`ElectionData::content_hash`](crate::models::election_data::ElectionData::content_hash) of the snapshot
+    pub data_hash: String,
+    /// Data hash recorded in the checked result, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_data_hash: Option<String>,
+    /// Whether the checked result was produced from this snapshot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches: Option<bool>,
+}
+
+impl HashCommand {
+    /// Execute the hash command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let hash = self.hash()?;
+
+        if self.format == "json" {
+            let output = serde_json::to_string_pretty(&hash).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize snapshot hash: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            println!("{}", output);
+        } else {
+            println!("{}  {}", hash.data_hash, hash.file.display());
+        }
+
+        match (hash.matches, &self.verify_result) {
+            (Some(false), Some(path)) => Err(ElectionError::ValidationError {
+                message: format!(
+                    "{} was produced from snapshot {}, not {} ({})",
+                    path.display(),
+                    hash.result_data_hash.as_deref().unwrap_or_default(),
+                    hash.file.display(),
+                    hash.data_hash
+                ),
+                field: Some("verify_result".to_string()),
+            }),
+            (Some(true), Some(path)) => {
+                eprintln!("{} was produced from {}", path.display(), hash.file.display());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Hash the snapshot and, if asked, compare it with the result's recorded hash
+    pub fn hash(&self) -> Result<SnapshotHash, ElectionError> {
+        let data = JsonLoader::new().load_from_file(self.input_file.clone())?;
+        let data_hash = data.content_hash();
+
+        let (result_data_hash, matches) = match self.verify_result {
+            Some(ref path) => {
+                let result = load_result(path)?;
+                let recorded = result.execution_metadata.data_hash.ok_or_else(|| ElectionError::ValidationError {
+                    message: format!(
+                        "{} records no data hash; re-run the election with this version to check its snapshot",
+                        path.display()
+                    ),
+                    field: Some("verify_result".to_string()),
+                })?;
+                let matches = recorded == data_hash;
+                (Some(recorded), Some(matches))
+            }
+            None => (None, None),
+        };
+
+        Ok(SnapshotHash {
+            file: self.input_file.clone(),
+            data_hash,
+            result_data_hash,
+            matches,
+        })
+    }
+}
+
+/// Load a result saved by `run --format json`
+fn load_result(path: &Path) -> Result<ElectionResult, ElectionError> {
+    let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to read result file: {}", e),
+        path: path.to_path_buf(),
+        source: Some(ErrorSource::new(e)),
+    })?;
+    serde_json::from_str(&content).map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to parse result file {}: {}", path.display(), e),
+        source: Some(ErrorSource::new(e)),
+    })
+}
@@ -0,0 +1,249 @@
+//! `--json-io`: one request document on stdin, one response on stdout
+//!
+//! Workflow engines and agents drive the tool without assembling flags: a
+//! single JSON document names the operation, the election configuration and
+//! where the data comes from, and the answer is a single JSON document.
+//!
+//! ```json
+//! {"id": "job-7", "operation": "run",
+//!  "config": {"algorithm": "sequential-phragmen", "active_set_size": 100},
+//!  "data": {"file": {"path": "data.json"}}}
+//! ```
+//!
+//! The response echoes `id` and holds either `result` or, with `ok: false`,
+//! the error in the shape `--error-format json` prints. The process exits
+//! with the error's code, as the commands do.
+
+use crate::cli::error_output::error_json;
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorCategory};
+use crate::input::indexer::IndexerLoader;
+use crate::input::json::JsonLoader;
+use crate::input::rpc::RpcLoader;
+use crate::input::snapshot_store::SnapshotStore;
+use crate::models::data_statistics::DataStatistics;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::stake_redistribution::{StakeRedistribution, DEFAULT_TOLERANCE_BPS};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+/// What a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonIoOperation {
+    /// Run the configured election; the result is the `run --format json` result
+    Run,
+    /// Run the election and recommend stake moves away from over-backed validators
+    Redistribution,
+    /// Totals, approval stakes and vote counts of the data
+    Statistics,
+    /// Content hash of the data, as recorded in `execution_metadata.data_hash`
+    Hash,
+}
+
+/// Where a request's election data comes from
+///
+/// Externally tagged, e.g. `{"file": {"path": "data.json"}}`: an internally
+/// tagged enum would buffer inline data, and serde cannot buffer u128 stakes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataReference {
+    /// Snapshot file, as read by `--input-file`
+    File {
+        /// Path of the JSON snapshot
+        path: PathBuf,
+    },
+    /// Snapshot fetched from a node
+    Rpc {
+        /// RPC endpoint URL
+        url: String,
+        /// Block to read at; the latest block if omitted
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_number: Option<u64>,
+    },
+    /// Historical era from a GraphQL indexer
+    Indexer {
+        /// Indexer GraphQL endpoint URL
+        url: String,
+        /// Era to load
+        era: u32,
+    },
+    /// Snapshot from the local snapshot library
+    Snapshot {
+        /// Snapshot ID or unique prefix
+        id: String,
+        /// Library directory; the `snapshots` command's default if omitted
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        store: Option<PathBuf>,
+    },
+    /// Election data embedded in the request, in the `--input-file` shape
+    Inline(ElectionData),
+}
+
+impl DataReference {
+    /// Load the referenced data
+    pub async fn load(&self) -> Result<ElectionData, ElectionError> {
+        match self {
+            DataReference::File { path } => JsonLoader::new().load_from_file(path.clone()),
+            DataReference::Rpc { url, block_number } => {
+                let loader = RpcLoader::new(url)?;
+                match block_number {
+                    Some(block) => loader.load_at_block(*block).await,
+                    None => loader.load_latest().await,
+                }
+            }
+            DataReference::Indexer { url, era } => IndexerLoader::new(url)?.load_era(*era).await,
+            DataReference::Snapshot { id, store } => {
+                SnapshotStore::open(store.clone().unwrap_or_else(SnapshotStore::default_dir))?.load(id)
+            }
+            DataReference::Inline(data) => {
+                data.validate()?;
+                Ok(data.clone())
+            }
+        }
+    }
+}
+
+/// Request document read from stdin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonIoRequest {
+    /// Caller's ID for the request, echoed in the response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    /// Operation to perform
+    pub operation: JsonIoOperation,
+    /// Election configuration, required by `run` and `redistribution`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<ElectionConfiguration>,
+    /// Election data
+    pub data: DataReference,
+    /// Backing above the average tolerated by `redistribution`, in basis points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance_bps: Option<u32>,
+}
+
+/// Response document written to stdout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonIoResponse {
+    /// ID of the request, if it gave one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    /// Whether the operation succeeded
+    pub ok: bool,
+    /// Operation performed, if the request could be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<JsonIoOperation>,
+    /// Result of the operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// Error, as printed by `--error-format json`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+    /// Code the process exits with: 0, or the error's category code
+    pub exit_code: i32,
+}
+
+impl JsonIoRequest {
+    /// Parse a request document
+    pub fn from_json_str(content: &str) -> Result<Self, ElectionError> {
+        serde_json::from_str(content).map_err(|e| ElectionError::ValidationError {
+            message: format!("Failed to parse request document: {}", e),
+            field: None,
+        })
+    }
+
+    /// Perform the operation, returning its result as JSON
+    ///
+    /// The configuration is checked before the data is loaded, so a bad
+    /// request fails without fetching a snapshot.
+    pub async fn execute(&self) -> Result<Value, ElectionError> {
+        match self.operation {
+            JsonIoOperation::Run => {
+                let config = self.election_config()?;
+                let data = self.data.load().await?;
+                to_value(ElectionEngine::new().execute(&config, &data)?)
+            }
+            JsonIoOperation::Redistribution => {
+                let config = self.election_config()?;
+                let data = self.data.load().await?;
+                let result = ElectionEngine::new().execute(&config, &data)?;
+                let tolerance_bps = self.tolerance_bps.unwrap_or(DEFAULT_TOLERANCE_BPS);
+                to_value(StakeRedistribution::recommend(&result, &data, tolerance_bps))
+            }
+            JsonIoOperation::Statistics => to_value(DataStatistics::compute(&self.data.load().await?)),
+            JsonIoOperation::Hash => Ok(json!({ "data_hash": self.data.load().await?.content_hash() })),
+        }
+    }
+
+    /// The request's configuration, validated
+    fn election_config(&self) -> Result<ElectionConfiguration, ElectionError> {
+        let config = self.config.clone().ok_or_else(|| ElectionError::ValidationError {
+            message: "The run and redistribution operations need a config".to_string(),
+            field: Some("config".to_string()),
+        })?;
+        config.build()
+    }
+}
+
+impl JsonIoResponse {
+    fn new(id: Option<Value>, operation: Option<JsonIoOperation>, outcome: Result<Value, ElectionError>) -> Self {
+        match outcome {
+            Ok(result) => Self { id, ok: true, operation, result: Some(result), error: None, exit_code: 0 },
+            Err(error) => Self {
+                id,
+                ok: false,
+                operation,
+                result: None,
+                error: Some(error_json(&error)),
+                exit_code: error.exit_code(),
+            },
+        }
+    }
+}
+
+/// Answer a request document
+///
+/// Never fails: a document that cannot be read is answered with a
+/// validation error, echoing its `id` if it has one.
+pub async fn respond(content: &str) -> JsonIoResponse {
+    let id = serde_json::from_str::<Value>(content).ok().and_then(|document| document.get("id").cloned());
+    match JsonIoRequest::from_json_str(content) {
+        Ok(request) => {
+            let outcome = request.execute().await;
+            JsonIoResponse::new(request.id, Some(request.operation), outcome)
+        }
+        Err(error) => JsonIoResponse::new(id, None, Err(error)),
+    }
+}
+
+/// Read a request from stdin, write the response to stdout and return the
+/// code to exit with
+pub async fn run_stdio() -> i32 {
+    let mut content = String::new();
+    let response = match std::io::stdin().read_to_string(&mut content) {
+        Ok(_) => respond(&content).await,
+        Err(e) => JsonIoResponse::new(
+            None,
+            None,
+            Err(ElectionError::ValidationError {
+                message: format!("Failed to read request document from stdin: {}", e),
+                field: None,
+            }),
+        ),
+    };
+    let mut stdout = std::io::stdout().lock();
+    if serde_json::to_writer(&mut stdout, &response).is_err() || writeln!(stdout).is_err() {
+        return ErrorCategory::Io.exit_code();
+    }
+    response.exit_code
+}
+
+fn to_value(value: impl Serialize) -> Result<Value, ElectionError> {
+    serde_json::to_value(value).map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to serialize result: {}", e),
+        source: None,
+    })
+}
@@ -0,0 +1,148 @@
+//! Man page command
+//!
+//! Renders a roff manual page from the clap definitions of the command line:
+//! the synopsis, then one section per subcommand listing its options.
+
+use crate::error::{ElectionError, ErrorSource};
+use clap::{Arg, Command, Parser};
+use std::path::PathBuf;
+
+/// Man command for printing the manual page
+#[derive(Parser)]
+#[command(name = "man")]
+#[command(about = "Print the manual page in roff format")]
+pub struct ManCommand {
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+}
+
+impl ManCommand {
+    /// Render the manual page for `command`, the tool's top-level command
+    pub fn execute(&self, command: Command) -> Result<(), ElectionError> {
+        let page = man_page(command);
+        match self.output_file {
+            Some(ref path) => std::fs::write(path, page).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write man page: {}", e),
+                path: path.clone(),
+                source: Some(ErrorSource::new(e)),
+            }),
+            None => {
+                print!("{}", page);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Subcommands shown to users, without the implicit `help` subcommand
+fn visible_subcommands(command: &Command) -> impl Iterator<Item = &Command> {
+    command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set() && sub.get_name() != "help")
+}
+
+/// Arguments shown to users
+fn visible_args(command: &Command) -> impl Iterator<Item = &Arg> {
+    command.get_arguments().filter(|arg| !arg.is_hide_set())
+}
+
+/// Escape text for roff: backslashes, hyphens and leading control characters
+fn roff(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let escaped = line.replace('\\', "\\e").replace('-', "\\-");
+            if escaped.starts_with('.') || escaped.starts_with('\'') {
+                format!("\\&{}", escaped)
+            } else {
+                escaped
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Full help of an argument, falling back to its short help
+fn help(arg: &Arg) -> String {
+    arg.get_long_help()
+        .or_else(|| arg.get_help())
+        .map(|help| help.to_string())
+        .unwrap_or_default()
+}
+
+/// `.TP` entries for the options and positional arguments of a command
+fn options(command: &Command) -> String {
+    let mut section = String::new();
+    for arg in visible_args(command) {
+        let value_name = arg
+            .get_value_names()
+            .and_then(|names| names.first())
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| arg.get_id().to_string().to_uppercase());
+        let mut term = Vec::new();
+        if let Some(short) = arg.get_short() {
+            term.push(format!("\\fB\\-{}\\fR", roff(&short.to_string())));
+        }
+        if let Some(long) = arg.get_long() {
+            term.push(format!("\\fB\\-\\-{}\\fR", roff(long)));
+        }
+        let mut term = term.join(", ");
+        if arg.is_positional() {
+            term = format!("<\\fI{}\\fR>", roff(&value_name));
+        } else if arg.get_action().takes_values() {
+            term.push_str(&format!(" <\\fI{}\\fR>", roff(&value_name)));
+        }
+        section.push_str(&format!(".TP\n{}\n", term));
+        let mut text = help(arg);
+        let values: Vec<String> = arg
+            .get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect();
+        if !values.is_empty() {
+            text.push_str(&format!(" [possible values: {}]", values.join(", ")));
+        }
+        if let Some(default) = arg.get_default_values().first() {
+            text.push_str(&format!(" [default: {}]", default.to_string_lossy()));
+        }
+        if !text.is_empty() {
+            section.push_str(&format!("{}\n", roff(text.trim())));
+        }
+    }
+    section
+}
+
+/// Manual page for `command` in roff format
+pub fn man_page(mut command: Command) -> String {
+    // Building adds the implicit --help flag
+    command.build();
+    let name = command.get_name().to_string();
+    let about = command.get_about().map(|a| a.to_string()).unwrap_or_default();
+
+    let mut page = format!(
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"\n",
+        roff(&name.to_uppercase()),
+        roff(&name),
+        env!("CARGO_PKG_VERSION")
+    );
+    page.push_str(&format!(".SH NAME\n{} \\- {}\n", roff(&name), roff(&about)));
+    page.push_str(&format!(".SH SYNOPSIS\n\\fB{}\\fR <\\fICOMMAND\\fR> [\\fIOPTIONS\\fR]\n", roff(&name)));
+    if let Some(long_about) = command.get_long_about() {
+        page.push_str(&format!(".SH DESCRIPTION\n{}\n", roff(&long_about.to_string())));
+    }
+    let top_level = options(&command);
+    if !top_level.is_empty() {
+        page.push_str(&format!(".SH OPTIONS\n{}", top_level));
+    }
+
+    page.push_str(".SH COMMANDS\n");
+    for sub in visible_subcommands(&command) {
+        page.push_str(&format!(".SS \"{} {}\"\n", roff(&name), roff(sub.get_name())));
+        if let Some(about) = sub.get_about() {
+            page.push_str(&format!("{}\n", roff(&about.to_string())));
+        }
+        page.push_str(&options(sub));
+    }
+    page
+}
@@ -1,9 +1,58 @@
 //! CLI interface for the Offline NPoS Election Tool
 
+pub mod advise;
+pub mod anonymize;
 pub mod commands;
+pub mod compare_chains;
+pub mod completions;
+pub mod config;
+pub mod daemon;
+pub mod error_output;
+pub mod hash;
+pub mod json_io;
+pub mod man_page;
 pub mod output;
+pub mod score;
+#[cfg(feature = "testing")]
+pub mod selftest;
+#[cfg(feature = "sign")]
+pub mod sign_bundle;
+pub mod snapshot_diff;
+pub mod snapshots;
+#[cfg(feature = "submit")]
+pub mod submit;
+pub mod tui;
+pub mod verify_bundle;
+pub mod verify_onchain;
+pub mod watch_chain;
 
+pub use advise::AdviseCommand;
+pub use anonymize::AnonymizeCommand;
 pub use commands::{RunCommand, ServerCommand};
-pub use output::format_json;
+pub use compare_chains::CompareChainsCommand;
+pub use completions::CompletionsCommand;
+pub use config::{ConfigCommand, SettingsArgs};
+pub use daemon::DaemonCommand;
+pub use error_output::{format_error, ErrorFormat};
+pub use hash::HashCommand;
+pub use json_io::{JsonIoRequest, JsonIoResponse};
+pub use man_page::ManCommand;
+pub use output::{
+    format_attack_estimate, format_csv, format_json, format_progress, format_result_diff, format_sybil_report,
+    format_whale_impact,
+};
+pub use score::ScoreCommand;
+#[cfg(feature = "testing")]
+pub use selftest::SelftestCommand;
+#[cfg(feature = "sign")]
+pub use sign_bundle::SignBundleCommand;
+pub use snapshot_diff::SnapshotDiffCommand;
+pub use snapshots::SnapshotsCommand;
+#[cfg(feature = "submit")]
+pub use submit::SubmitCommand;
+pub use tui::TuiCommand;
+pub use verify_bundle::VerifyBundleCommand;
+pub use verify_onchain::VerifyOnchainCommand;
+pub use watch_chain::WatchChainCommand;
 
 
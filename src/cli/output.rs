@@ -1,7 +1,19 @@
 //! CLI output formatting
 //! Will be implemented in Phase 3 (T052-T053)
 
-use crate::models::election_result::ElectionResult;
+use crate::models::account_format::AccountFormat;
+use crate::models::balance::TokenUnit;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::{signed_difference, BackingBreakdown, ElectionResult};
+use crate::models::memory_report::MemoryReport;
+use crate::models::override_impact::OverrideImpact;
+use crate::models::rank_stability::RankStability;
+use crate::models::stake_flow::StakeFlowProjection;
+use crate::models::stake_redistribution::StakeRedistribution;
+use crate::models::sybil_scenario::{AttackEstimate, SybilReport};
+use crate::models::whale_impact::{WhaleImpact, WhaleRemovalMode};
+use crate::payout::commission::CommissionImpact;
+use crate::progress::ProgressEvent;
 
 /// Format election result as JSON
 pub fn format_json(result: &ElectionResult) -> Result<String, crate::error::ElectionError> {
@@ -9,4 +21,433 @@ pub fn format_json(result: &ElectionResult) -> Result<String, crate::error::Elec
     result.to_json()
 }
 
+/// Format the elected validators as CSV for spreadsheet review
+///
+/// One row per winner in rank order with its SS58 address, identity, self
+/// stake (the validator's self-vote allocation, or its bond when the data has
+/// no self-vote), total backing including that self stake, number of
+/// nominators other than itself and commission. Amounts are whole tokens
+/// when `unit` is given, plancks otherwise; the header names which. Account
+/// IDs are written in `account_format`, with `prefix` as the network prefix.
+pub fn format_csv(
+    result: &ElectionResult,
+    data: &ElectionData,
+    unit: Option<&TokenUnit>,
+    account_format: AccountFormat,
+    prefix: u16,
+) -> String {
+    let unit_label = unit.map(|u| u.symbol.as_str()).unwrap_or("planck");
+    let amount = |plancks: u128| match unit {
+        Some(unit) => unit.to_decimal_string(plancks),
+        None => plancks.to_string(),
+    };
+    let candidates: std::collections::HashMap<&str, _> =
+        data.candidates.iter().map(|c| (c.account_id.as_str(), c)).collect();
 
+    let mut output = format!(
+        "Rank,Validator,Identity,Self Stake ({unit}),Total Backing ({unit}),Nominators,Commission (%)\n",
+        unit = unit_label
+    );
+    let mut allocations: std::collections::HashMap<&str, Vec<_>> = std::collections::HashMap::new();
+    for allocation in &result.stake_distribution {
+        allocations.entry(allocation.validator_id.as_str()).or_default().push(allocation);
+    }
+    for (position, validator) in result.selected_validators.iter().enumerate() {
+        let metadata = candidates.get(validator.account_id.as_str()).and_then(|c| c.metadata.as_ref());
+        let backing = BackingBreakdown::from_allocations(
+            &validator.account_id,
+            allocations.get(validator.account_id.as_str()).into_iter().flatten().copied(),
+        )
+        .with_bonded_self_stake(candidates.get(validator.account_id.as_str()).map(|c| c.stake).unwrap_or(0));
+        let nominators = backing.backer_count - usize::from(backing.self_stake > 0);
+        output.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            validator.rank.unwrap_or(position as u32 + 1),
+            csv_field(&account_format.encode(&validator.account_id, prefix)),
+            csv_field(metadata.and_then(|m| m.identity_display.as_deref()).unwrap_or("")),
+            amount(backing.self_stake),
+            amount(backing.total_backing),
+            nominators,
+            metadata.and_then(|m| m.commission_rate).map(|c| c.to_string()).unwrap_or_default()
+        ));
+    }
+
+    output
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Format a compact diff between two election results
+///
+/// Lists validators entering and leaving the elected set followed by the
+/// change in each score component. Used by `run --watch` after each re-run.
+pub fn format_result_diff(previous: &ElectionResult, current: &ElectionResult) -> String {
+    let impact = OverrideImpact::between(previous, current);
+    let mut output = String::new();
+
+    if impact.set_changed() {
+        for validator in &impact.validators_added {
+            output.push_str(&format!("+ {}\n", validator));
+        }
+        for validator in &impact.validators_removed {
+            output.push_str(&format!("- {}\n", validator));
+        }
+    } else {
+        output.push_str("Elected set unchanged\n");
+    }
+
+    output.push_str(&format!(
+        "Minimal stake: {} ({:+})\n",
+        impact.overridden_score.minimal_stake, impact.score_delta.minimal_stake
+    ));
+    output.push_str(&format!(
+        "Total stake: {} ({:+})\n",
+        impact.overridden_score.sum_stake, impact.score_delta.sum_stake
+    ));
+    output.push_str(&format!(
+        "Nominators affected: {}\n",
+        impact.affected_nominators.len()
+    ));
+
+    output
+}
+
+/// Format the set changes of a whale impact experiment
+///
+/// One line per re-run with the removed nominators, their share of the
+/// nominator stake and the validators swapped in and out.
+pub fn format_whale_impact(impact: &WhaleImpact) -> String {
+    let mut output = format!(
+        "Whale impact ({} removal of the {} largest nominators):\n",
+        impact.mode,
+        impact.steps.len()
+    );
+
+    for step in &impact.steps {
+        let removed = match impact.mode {
+            WhaleRemovalMode::Individual => step.removed_nominators.join(", "),
+            WhaleRemovalMode::Cumulative => format!("top {}", step.removed_nominators.len()),
+        };
+        output.push_str(&format!(
+            "  - without {} ({:.2}% of nominator stake): ",
+            removed,
+            step.removed_stake_bps as f64 / 100.0
+        ));
+        if step.set_changed() {
+            output.push_str(&format!(
+                "+{} -{} (minimal stake {:+})\n",
+                step.validators_added.join(" +"),
+                step.validators_removed.join(" -"),
+                step.score_delta.minimal_stake
+            ));
+        } else {
+            output.push_str(&format!(
+                "elected set unchanged (minimal stake {:+})\n",
+                step.score_delta.minimal_stake
+            ));
+        }
+    }
+
+    let changed = impact.set_changes().count();
+    output.push_str(&format!("{} of {} removals changed the elected set\n", changed, impact.steps.len()));
+    output
+}
+
+/// Format stake redistribution recommendations
+///
+/// Lists the over-backed validators and every recommended move, marking moves
+/// that need new nominations, then the minimal stake before and after.
+pub fn format_stake_redistribution(redistribution: &StakeRedistribution) -> String {
+    let mut output = format!(
+        "Stake redistribution (average backing {}, tolerance {:.2}%):\n",
+        redistribution.target_backing,
+        redistribution.tolerance_bps as f64 / 100.0
+    );
+    if redistribution.over_backed.is_empty() {
+        output.push_str("  no validator is over-backed\n");
+        return output;
+    }
+    for validator in &redistribution.over_backed {
+        output.push_str(&format!(
+            "  {} backed by {} ({} above average)\n",
+            validator.validator_id, validator.backing, validator.excess
+        ));
+    }
+    for stake_move in &redistribution.moves {
+        output.push_str(&format!(
+            "  - {} moves {} from {} to {}{}\n",
+            stake_move.nominator_id,
+            stake_move.amount,
+            stake_move.from_validator,
+            stake_move.to_validator,
+            if stake_move.already_nominated { "" } else { " (new nomination)" }
+        ));
+    }
+    output.push_str(&format!(
+        "Moving {} changes the minimal stake from {} to {} ({:+})\n",
+        redistribution.moved_stake,
+        redistribution.baseline_score.minimal_stake,
+        redistribution.redistributed_score.minimal_stake,
+        redistribution.score_delta.minimal_stake
+    ));
+    output
+}
+
+/// Format the winner order stability of perturbed re-runs
+///
+/// One line per run with its Kendall's tau and swapped validators, then the
+/// fragile validators with the number of runs that elected them.
+pub fn format_rank_stability(stability: &RankStability) -> String {
+    let mut output = format!(
+        "Rank stability ({} perturbed runs of {} seats):\n",
+        stability.runs.len(),
+        stability.active_set_size
+    );
+
+    for run in &stability.runs {
+        output.push_str(&format!(
+            "  - {}: tau {:.4}",
+            run.perturbation,
+            run.kendall_tau_bps as f64 / 10_000.0
+        ));
+        if !run.validators_added.is_empty() || !run.validators_removed.is_empty() {
+            output.push_str(&format!(
+                ", +{} -{}",
+                run.validators_added.join(" +"),
+                run.validators_removed.join(" -")
+            ));
+        }
+        output.push('\n');
+    }
+    output.push_str(&format!(
+        "Mean Kendall's tau: {:.4}\n",
+        stability.mean_kendall_tau_bps as f64 / 10_000.0
+    ));
+
+    let fragile: Vec<_> = stability.fragile().collect();
+    if fragile.is_empty() {
+        output.push_str("No fragile validators\n");
+    } else {
+        output.push_str(&format!("Fragile validators ({}):\n", fragile.len()));
+        for validator in fragile {
+            let baseline = match validator.baseline_rank {
+                Some(rank) => format!("rank {}", rank),
+                None => "not elected".to_string(),
+            };
+            output.push_str(&format!(
+                "  - {} ({} in the baseline): elected in {} of {} runs\n",
+                validator.validator_id,
+                baseline,
+                validator.elected_runs,
+                stability.runs.len()
+            ));
+        }
+    }
+    output
+}
+
+/// Number of nominators listed on each side of a commission impact
+const COMMISSION_IMPACT_LISTED: usize = 10;
+
+/// Format the outcome of a commission bound simulation
+///
+/// Lists adjusted and dropped candidates, set changes, the shift between
+/// commission and nominator rewards, and the nominators losing and gaining
+/// the most.
+pub fn format_commission_impact(impact: &CommissionImpact) -> String {
+    let bound = match (impact.policy.min, impact.policy.max) {
+        (Some(min), Some(max)) => format!("{}% to {}%", min, max),
+        (Some(min), None) => format!("at least {}%", min),
+        (None, Some(max)) => format!("at most {}%", max),
+        (None, None) => "unbounded".to_string(),
+    };
+    let mut output = format!("Commission bound {}:\n", bound);
+    output.push_str(&format!(
+        "  {} candidates adjusted, {} dropped out\n",
+        impact.adjusted.len(),
+        impact.dropped.len()
+    ));
+    for validator in &impact.validators_added {
+        output.push_str(&format!("  + {}\n", validator));
+    }
+    for validator in &impact.validators_removed {
+        output.push_str(&format!("  - {}\n", validator));
+    }
+    output.push_str(&format!(
+        "  Commission: {} -> {} ({:+})\n",
+        impact.baseline_commission,
+        impact.scenario_commission,
+        signed_difference(impact.scenario_commission, impact.baseline_commission)
+    ));
+    output.push_str(&format!(
+        "  Nominator rewards: {} -> {} ({:+})\n",
+        impact.baseline_nominator_rewards,
+        impact.scenario_nominator_rewards,
+        signed_difference(impact.scenario_nominator_rewards, impact.baseline_nominator_rewards)
+    ));
+
+    let losers: Vec<_> = impact.losers().collect();
+    let gainers: Vec<_> = impact.gainers().collect();
+    output.push_str(&format!("  {} nominators lose, {} gain\n", losers.len(), gainers.len()));
+    for change in losers.iter().take(COMMISSION_IMPACT_LISTED) {
+        output.push_str(&format!("    {}: {:+}\n", change.nominator_id, change.change));
+    }
+    for change in gainers.iter().rev().take(COMMISSION_IMPACT_LISTED) {
+        output.push_str(&format!("    {}: {:+}\n", change.nominator_id, change.change));
+    }
+    output
+}
+
+/// Format the sybils elected at each attacker stake of a sybil stress test
+///
+/// One block per split, listing attacker stakes in increasing order.
+pub fn format_sybil_report(report: &SybilReport) -> String {
+    let mut output = format!(
+        "Sybil stress test ({} sybil candidates, {} seats, honest nominator stake {}):\n",
+        report.sybil_count, report.active_set_size, report.honest_stake
+    );
+
+    let mut splits = Vec::new();
+    for point in &report.points {
+        if !splits.contains(&point.split) {
+            splits.push(point.split);
+        }
+    }
+    for split in splits {
+        output.push_str(&format!("  {} split:\n", split));
+        for point in report.curve(split) {
+            output.push_str(&format!(
+                "    - attacker stake {} ({:.2}% of nominator stake): {} sybils elected ({:.2}% of seats)\n",
+                point.attacker_stake,
+                point.attacker_share_bps as f64 / 100.0,
+                point.sybils_elected,
+                point.seat_share_bps as f64 / 100.0
+            ));
+        }
+    }
+
+    output
+}
+
+/// Format the validator sets projected over future eras under stake flows
+pub fn format_stake_flow_projection(projection: &StakeFlowProjection) -> String {
+    let mut output = format!(
+        "Stake flow projection ({} eras after the snapshot):\n",
+        projection.eras.len().saturating_sub(1)
+    );
+    let mut reported: &[String] = &[];
+    for era in &projection.eras {
+        output.push_str(&format!(
+            "  - era +{} ({} flows, nominator stake {}): ",
+            era.era, era.flows_applied, era.nominator_stake
+        ));
+        if era.set_changed() {
+            output.push_str(&format!(
+                "+{} -{} (minimal stake {})\n",
+                era.validators_added.join(" +"),
+                era.validators_removed.join(" -"),
+                era.score.minimal_stake
+            ));
+        } else {
+            output.push_str(&format!("elected set unchanged (minimal stake {})\n", era.score.minimal_stake));
+        }
+        // Skipped flows stay listed in later eras; report each once
+        for unmatched in era.unmatched_flows.iter().filter(|flow| !reported.contains(flow)) {
+            output.push_str(&format!("      skipped: {}\n", unmatched));
+        }
+        reported = &era.unmatched_flows;
+    }
+
+    let changed = projection.set_changes().count();
+    output.push_str(&format!(
+        "{} of {} projected eras changed the elected set\n",
+        changed,
+        projection.eras.len().saturating_sub(1)
+    ));
+    output
+}
+
+/// Format a minimum attack stake estimate
+pub fn format_attack_estimate(estimate: &AttackEstimate) -> String {
+    match (estimate.min_stake, estimate.attacker_share_bps) {
+        (Some(stake), Some(share_bps)) => format!(
+            "Winning {} seats with an {} split takes about {} ({:.2}% of nominator stake; {} does not suffice; {} elections run)\n",
+            estimate.seats,
+            estimate.split,
+            stake,
+            share_bps as f64 / 100.0,
+            estimate.max_failing_stake,
+            estimate.elections_run
+        ),
+        _ => format!(
+            "No attacker stake up to {} wins {} seats with an {} split ({} elections run)\n",
+            estimate.max_failing_stake, estimate.seats, estimate.split, estimate.elections_run
+        ),
+    }
+}
+
+/// Format the size of a dry-run data set and its estimated memory use
+pub fn format_memory_report(data: &ElectionData, report: &MemoryReport) -> String {
+    let edges: usize = data.nominators.iter().map(|n| n.targets.len()).sum();
+    let mut output = format!(
+        "Dry run: {} candidates, {} nominators, {} votes; no election was run\n",
+        data.candidates.len(),
+        data.nominators.len(),
+        edges
+    );
+    output.push_str(&format!("Estimated data memory: {}\n", format_size(report.total() as u64)));
+    for (label, bytes) in [
+        ("records", report.records),
+        ("account IDs", report.account_ids),
+        ("targets", report.targets),
+        ("metadata", report.metadata),
+    ] {
+        output.push_str(&format!("  - {}: {}\n", label, format_size(bytes as u64)));
+    }
+    output.push_str(&format!(
+        "  of which spare capacity: {}; repeated target strings: {}\n",
+        format_size(report.spare_capacity as u64),
+        format_size(report.repeated_targets as u64)
+    ));
+    output
+}
+
+/// Format a byte count with a binary unit, e.g. "1.5 MiB"
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1} GiB", b as f64 / (1u64 << 30) as f64),
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+/// Format a progress event as one line, e.g.
+/// "selection round 12/297 (3.4s, minimum support 1204.5 DOT)"
+///
+/// Amounts are whole tokens when `unit` is given, plancks otherwise.
+pub fn format_progress(event: &ProgressEvent, unit: Option<&TokenUnit>) -> String {
+    let mut line = format!(
+        "{} round {}/{} ({:.1}s",
+        event.phase,
+        event.round,
+        event.total_rounds,
+        event.elapsed.as_secs_f64()
+    );
+    if let Some(support) = event.minimum_support {
+        let amount = match unit {
+            Some(unit) => unit.format(support),
+            None => format!("{} plancks", support),
+        };
+        line.push_str(&format!(", minimum support {}", amount));
+    }
+    line.push(')');
+    line
+}
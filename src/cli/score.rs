@@ -0,0 +1,130 @@
+//! Solution scoring command
+//!
+//! Decodes a `RawSolution` produced by any miner, recomputes its score against
+//! the snapshot it indexes, checks feasibility and compares it with the best
+//! solution this tool's solvers find, as a neutral referee between miners.
+
+use crate::cli::verify_onchain::format_verification;
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::onchain_solution::{decode_snapshot, ScoreReport, SubmittedSolution};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+/// Score command for refereeing externally produced solutions
+#[derive(Parser)]
+#[command(name = "score")]
+#[command(about = "Score a submitted solution against its snapshot and this tool's own solutions")]
+pub struct ScoreCommand {
+    /// File containing the SCALE-encoded RawSolution, as hex or raw bytes
+    #[arg(long, value_name = "PATH")]
+    pub solution: PathBuf,
+
+    /// File containing the SCALE-encoded RoundSnapshot, as hex or raw bytes
+    #[arg(long, value_name = "PATH")]
+    pub snapshot: PathBuf,
+
+    /// Number of winners the round asks for (default: the solution's winner count)
+    #[arg(long)]
+    pub desired_targets: Option<u32>,
+
+    /// Votes per voter of the runtime's compact solution type (default: try 16 and 24)
+    #[arg(long, value_name = "N")]
+    pub max_votes: Option<u32>,
+
+    /// Block the snapshot was taken at, recorded in the report
+    #[arg(long, default_value_t = 0)]
+    pub block_number: u64,
+
+    /// Output format: json or human-readable
+    #[arg(long, default_value = "json")]
+    pub format: String,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+}
+
+impl ScoreCommand {
+    /// Execute the score command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let snapshot = decode_snapshot(&read_scale(&self.snapshot)?, Some(self.block_number))?;
+        let submitted = SubmittedSolution::decode(&read_scale(&self.solution)?, &snapshot, self.max_votes)?;
+
+        eprintln!(
+            "Scoring round {} solution ({} votes per voter) with {} winners against {} targets and {} voters...",
+            submitted.round,
+            submitted.max_votes,
+            submitted.solution.supports.len(),
+            snapshot.candidates.len(),
+            snapshot.nominators.len()
+        );
+        let verification = ElectionEngine::new().verify_solution(
+            &submitted.solution,
+            &snapshot,
+            self.desired_targets,
+            self.block_number,
+        );
+        let report = ScoreReport::new(&submitted, verification);
+
+        let output = if self.format == "human-readable" {
+            format_score_report(&report)
+        } else {
+            serde_json::to_string_pretty(&report).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize score report: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?
+        };
+
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+        } else {
+            println!("{}", output);
+        }
+
+        Ok(())
+    }
+}
+
+/// Read a SCALE-encoded file written either as hex text or as raw bytes
+fn read_scale(path: &Path) -> Result<Vec<u8>, ElectionError> {
+    let bytes = std::fs::read(path).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to read file: {}", e),
+        path: path.to_path_buf(),
+        source: Some(ErrorSource::new(e)),
+    })?;
+    let hex_text = std::str::from_utf8(&bytes)
+        .ok()
+        .map(|text| text.trim().trim_start_matches("0x"))
+        .filter(|text| !text.is_empty() && text.len() % 2 == 0 && text.bytes().all(|b| b.is_ascii_hexdigit()));
+    match hex_text {
+        Some(text) => hex::decode(text).map_err(|e| ElectionError::InvalidData {
+            message: format!("Invalid hex in {}: {}", path.display(), e),
+            source: Some(ErrorSource::new(e)),
+        }),
+        None => Ok(bytes),
+    }
+}
+
+/// Format a score report as human-readable text
+fn format_score_report(report: &ScoreReport) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("Round: {}\n", report.round));
+    output.push_str(&format!("Votes per Voter: {}\n", report.max_votes));
+    output.push_str(&format_verification(&report.verification));
+    if let Some(margin) = report.margin {
+        output.push_str("Margin of Best Alternative:\n");
+        output.push_str(&format!("  Minimal Stake: {:+}", margin.minimal_stake));
+        if let Some(bps) = margin.minimal_stake_bps {
+            output.push_str(&format!(" ({:+.2}%)", bps as f64 / 100.0));
+        }
+        output.push('\n');
+        output.push_str(&format!("  Sum Stake: {:+}\n", margin.sum_stake));
+        output.push_str(&format!("  Sum Stake Squared: {:+}\n", margin.sum_stake_squared));
+    }
+    output
+}
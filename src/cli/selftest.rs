@@ -0,0 +1,158 @@
+//! Differential self-test command (requires the `testing` feature)
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::testing::{run_selftest, SelftestConfig};
+use crate::types::AlgorithmType;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Selftest command comparing the pipeline with `sp-npos-elections` on random elections
+#[derive(Parser, Debug)]
+#[command(name = "selftest")]
+#[command(about = "Compare this tool's elections with sp-npos-elections on random inputs")]
+pub struct SelftestCommand {
+    /// Number of random elections
+    #[arg(long, default_value = "100")]
+    pub cases: usize,
+
+    /// Seed to generate the elections from (default: random, printed for reruns)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Most candidates in an election
+    #[arg(long, default_value = "40")]
+    pub max_candidates: usize,
+
+    /// Most voters in an election
+    #[arg(long, default_value = "200")]
+    pub max_voters: usize,
+
+    /// Algorithm to compare (repeatable; default: all)
+    #[arg(long = "algorithm", value_name = "ALGORITHM")]
+    pub algorithms: Vec<String>,
+
+    /// Directory to write the election data of diverging cases to
+    #[arg(long, value_name = "DIR")]
+    pub save_failures: Option<PathBuf>,
+
+    /// Output format: human-readable or json
+    #[arg(long, default_value = "human-readable")]
+    pub format: String,
+}
+
+impl SelftestCommand {
+    /// Execute the selftest command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let config = self.config()?;
+        let report = run_selftest(&config)?;
+
+        if self.format == "json" {
+            let output = serde_json::to_string_pretty(&report).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize selftest report: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            println!("{}", output);
+        } else {
+            println!(
+                "Seed {}: {} cases, {} comparisons, {} divergences",
+                report.seed,
+                report.cases,
+                report.comparisons,
+                report.divergences.len()
+            );
+            for divergence in &report.divergences {
+                println!(
+                    "  case {} (case seed {}) {:?}, {} candidates, {} voters, {} to elect: {}",
+                    divergence.case,
+                    divergence.case_seed,
+                    divergence.algorithm,
+                    divergence.candidates,
+                    divergence.voters,
+                    divergence.to_elect,
+                    divergence.detail
+                );
+            }
+        }
+
+        if let Some(ref dir) = self.save_failures {
+            self.save_failures(dir, &config, &report)?;
+        }
+
+        match report.divergences.first() {
+            None => Ok(()),
+            Some(first) => Err(ElectionError::AlgorithmError {
+                message: format!(
+                    "{} of {} comparisons diverged from sp-npos-elections (seed {})",
+                    report.divergences.len(),
+                    report.comparisons,
+                    report.seed
+                ),
+                algorithm: first.algorithm,
+            }),
+        }
+    }
+
+    /// Generation settings from the flags
+    pub fn config(&self) -> Result<SelftestConfig, ElectionError> {
+        let mut config = SelftestConfig {
+            cases: self.cases,
+            seed: self.seed.unwrap_or_else(clock_seed),
+            max_candidates: self.max_candidates,
+            max_voters: self.max_voters,
+            ..SelftestConfig::default()
+        };
+        if self.max_candidates == 0 {
+            return Err(ElectionError::ValidationError {
+                message: "--max-candidates must be at least 1".to_string(),
+                field: Some("max_candidates".to_string()),
+            });
+        }
+        if !self.algorithms.is_empty() {
+            config.algorithms = self
+                .algorithms
+                .iter()
+                .map(|algorithm| {
+                    algorithm.parse::<AlgorithmType>().map_err(|e| ElectionError::ValidationError {
+                        message: format!("Invalid algorithm: {}", e),
+                        field: Some("algorithm".to_string()),
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(config)
+    }
+
+    /// Write each diverging case's election data as `case-<case seed>.json`
+    fn save_failures(
+        &self,
+        dir: &std::path::Path,
+        config: &SelftestConfig,
+        report: &crate::testing::SelftestReport,
+    ) -> Result<(), ElectionError> {
+        let file_error = |e: std::io::Error, path: PathBuf| ElectionError::FileError {
+            message: format!("Failed to save diverging case: {}", e),
+            path,
+            source: Some(ErrorSource::new(e)),
+        };
+        std::fs::create_dir_all(dir).map_err(|e| file_error(e, dir.to_path_buf()))?;
+        for divergence in &report.divergences {
+            let (data, _) = crate::testing::random_election(divergence.case_seed, config);
+            let path = dir.join(format!("case-{}.json", divergence.case_seed));
+            let json = serde_json::to_string_pretty(&data).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize diverging case: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            std::fs::write(&path, json).map_err(|e| file_error(e, path.clone()))?;
+            eprintln!("Saved case {} to {}", divergence.case, path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Seed from the clock, for runs without --seed
+fn clock_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,72 @@
+//! Result bundle signing command (requires the `sign` feature)
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::bundle_signing::BundleSigner;
+use crate::models::result_bundle::{ResultBundle, SignatureScheme};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Environment variable holding the signer's secret URI
+pub const SURI_ENV_VAR: &str = "OFFLINE_ELECTION_SURI";
+
+/// Sign-bundle command for attesting published result bundles
+#[derive(Parser)]
+#[command(name = "sign-bundle")]
+#[command(about = "Sign a result bundle's manifest with an sr25519 or ed25519 key")]
+pub struct SignBundleCommand {
+    /// Bundle directory
+    #[arg(long)]
+    pub bundle: PathBuf,
+
+    /// Key type: sr25519 or ed25519
+    #[arg(long, default_value = "sr25519")]
+    pub scheme: String,
+
+    /// File containing the signer's secret URI (default: $OFFLINE_ELECTION_SURI)
+    #[arg(long)]
+    pub suri_file: Option<PathBuf>,
+}
+
+impl SignBundleCommand {
+    /// Execute the sign-bundle command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let scheme = self.scheme.parse::<SignatureScheme>().map_err(|e| ElectionError::ValidationError {
+            message: e,
+            field: Some("scheme".to_string()),
+        })?;
+        let signer = BundleSigner::from_suri(scheme, &self.load_suri()?)?;
+
+        // Refuse to attest a bundle that does not verify
+        let mut bundle = ResultBundle::load(&self.bundle)?;
+        let verification = bundle.verify()?;
+        if !(verification.mismatched_files.is_empty()
+            && verification.content_hashes_match
+            && verification.result_differences.is_empty())
+        {
+            return Err(ElectionError::InvalidData {
+                message: "Bundle does not verify; run verify-bundle for details".to_string(),
+                source: None,
+            });
+        }
+
+        let signature = bundle.sign(&signer)?;
+        eprintln!("Signed with {} key", signature.scheme);
+        println!("{}", signature.public_key);
+        Ok(())
+    }
+
+    /// Read the signer's secret URI from the key file or environment
+    fn load_suri(&self) -> Result<String, ElectionError> {
+        match self.suri_file {
+            Some(ref path) => std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to read key file: {}", e),
+                path: path.clone(),
+                source: Some(ErrorSource::new(e)),
+            }),
+            None => std::env::var(SURI_ENV_VAR).map_err(|_| ElectionError::ValidationError {
+                message: format!("Signing requires --suri-file or ${}", SURI_ENV_VAR),
+                field: Some("suri".to_string()),
+            }),
+        }
+    }
+}
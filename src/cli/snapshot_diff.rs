@@ -0,0 +1,77 @@
+//! Snapshot diff command
+//!
+//! Compares two JSON snapshots and writes the change set between them, which
+//! can be studied on its own or replayed onto another baseline.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::json::JsonLoader;
+use crate::models::snapshot_delta::SnapshotDelta;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Snapshot-diff command for computing churn between two snapshots
+#[derive(Parser)]
+#[command(name = "snapshot-diff")]
+#[command(about = "Compute the change set between two election data snapshots")]
+pub struct SnapshotDiffCommand {
+    /// Path to the earlier JSON snapshot
+    #[arg(long)]
+    pub before: PathBuf,
+
+    /// Path to the later JSON snapshot
+    #[arg(long)]
+    pub after: PathBuf,
+
+    /// Replay the change set onto this snapshot and output the result instead
+    #[arg(long)]
+    pub replay_onto: Option<PathBuf>,
+
+    /// Output file path (default: stdout)
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+}
+
+impl SnapshotDiffCommand {
+    /// Execute the snapshot-diff command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let loader = JsonLoader::new();
+        let before = loader.load_from_file(self.before.clone())?;
+        let after = loader.load_from_file(self.after.clone())?;
+        let delta = SnapshotDelta::between(&before, &after);
+        eprintln!(
+            "{} candidates added, {} removed; {} nominators added, {} removed; {} stake changes, {} edge changes",
+            delta.added_candidates.len(),
+            delta.removed_candidates.len(),
+            delta.added_nominators.len(),
+            delta.removed_nominators.len(),
+            delta.overrides.candidate_stakes.len() + delta.overrides.nominator_stakes.len(),
+            delta.overrides.voting_edges.len()
+        );
+
+        let output = match self.replay_onto {
+            Some(ref baseline) => {
+                let mut data = loader.load_from_file(baseline.clone())?;
+                let applied = delta.apply(&mut data);
+                eprintln!("Replayed {} changes onto {}", applied, baseline.display());
+                serde_json::to_string_pretty(&data)
+            }
+            None => serde_json::to_string_pretty(&delta),
+        }
+        .map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize snapshot diff: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+
+        if let Some(ref output_file) = self.output_file {
+            std::fs::write(output_file, output).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to write output file: {}", e),
+                path: output_file.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+        } else {
+            println!("{}", output);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+//! Snapshot library command
+//!
+//! Manages the local [`SnapshotStore`]: `add` stores a snapshot file or one
+//! fetched over RPC, `list` and `info` show what is stored, `tag` labels
+//! snapshots and `prune` removes old untagged ones.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::json::JsonLoader;
+use crate::input::rpc::RpcLoader;
+use crate::input::snapshot_store::{PrunePolicy, SnapshotEntry, SnapshotStore, SNAPSHOT_DIR_ENV_VAR};
+use crate::models::validation::ValidationProfile;
+use chrono::Utc;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Snapshots command for curating the local snapshot library
+#[derive(Parser, Debug)]
+#[command(name = "snapshots")]
+#[command(about = "List, inspect, tag and prune the local snapshot library")]
+pub struct SnapshotsCommand {
+    /// Store directory (default: ~/.offline-election/snapshots)
+    #[arg(long, global = true, env = SNAPSHOT_DIR_ENV_VAR, value_name = "DIR")]
+    pub store: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub action: SnapshotsAction,
+}
+
+/// What to do with the snapshot library
+#[derive(Subcommand, Debug)]
+pub enum SnapshotsAction {
+    /// Store a snapshot file, or fetch one over RPC
+    Add {
+        /// Election data snapshot (JSON)
+        #[arg(required_unless_present = "rpc_url", conflicts_with = "rpc_url")]
+        input_file: Option<PathBuf>,
+
+        /// Fetch the snapshot from this RPC endpoint instead
+        #[arg(long)]
+        rpc_url: Option<String>,
+
+        /// Block to fetch at (default: latest)
+        #[arg(long, requires = "rpc_url")]
+        block_number: Option<u64>,
+
+        /// Tag to attach (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+    },
+    /// List stored snapshots
+    List {
+        /// Only snapshots with this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Only snapshots from this chain
+        #[arg(long)]
+        chain: Option<String>,
+
+        /// Output format: human-readable or json
+        #[arg(long, default_value = "human-readable")]
+        format: String,
+    },
+    /// Show a snapshot's metadata and where it is stored
+    Info {
+        /// Snapshot ID or unique prefix
+        id: String,
+
+        /// Output format: human-readable or json
+        #[arg(long, default_value = "human-readable")]
+        format: String,
+    },
+    /// Add or remove tags
+    Tag {
+        /// Snapshot ID or unique prefix
+        id: String,
+
+        /// Tags to add
+        #[arg(required_unless_present = "remove")]
+        tags: Vec<String>,
+
+        /// Tag to remove (repeatable)
+        #[arg(long, value_name = "TAG")]
+        remove: Vec<String>,
+    },
+    /// Remove old snapshots; tagged snapshots are kept unless --include-tagged is given
+    Prune {
+        /// Keep this many of the most recently added snapshots
+        #[arg(long, value_name = "COUNT", required_unless_present = "older_than_days")]
+        keep_latest: Option<usize>,
+
+        /// Only remove snapshots added more than this many days ago
+        #[arg(long, value_name = "DAYS")]
+        older_than_days: Option<u32>,
+
+        /// Also remove tagged snapshots
+        #[arg(long)]
+        include_tagged: bool,
+
+        /// List what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl SnapshotsCommand {
+    /// Execute the snapshots command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let store = SnapshotStore::open(self.store.clone().unwrap_or_else(SnapshotStore::default_dir))?;
+
+        match &self.action {
+            SnapshotsAction::Add {
+                input_file,
+                rpc_url,
+                block_number,
+                tags,
+            } => {
+                let (data, source) = match (input_file, rpc_url) {
+                    (Some(path), _) => {
+                        let data = JsonLoader::new()
+                            .with_validation_profile(ValidationProfile::Permissive)
+                            .load_from_file(path.clone())?;
+                        (data, path.display().to_string())
+                    }
+                    (None, Some(url)) => {
+                        let loader = RpcLoader::new(url.as_str())?;
+                        let data = match block_number {
+                            Some(block) => loader.load_at_block(*block).await?,
+                            None => loader.load_latest().await?,
+                        };
+                        (data, url.clone())
+                    }
+                    (None, None) => {
+                        return Err(ElectionError::ValidationError {
+                            message: "Give a snapshot file or --rpc-url".to_string(),
+                            field: Some("input_file".to_string()),
+                        })
+                    }
+                };
+                let (entry, added) = store.add(&data, Some(source), tags)?;
+                if added {
+                    println!("Added {}", entry.id);
+                } else {
+                    println!("Already stored as {}", entry.id);
+                }
+            }
+            SnapshotsAction::List { tag, chain, format } => {
+                let entries: Vec<SnapshotEntry> = store
+                    .list()?
+                    .into_iter()
+                    .filter(|entry| tag.as_ref().map_or(true, |tag| entry.tags.contains(tag)))
+                    .filter(|entry| chain.is_none() || entry.chain == *chain)
+                    .collect();
+                if format == "json" {
+                    println!("{}", to_json(&entries)?);
+                } else {
+                    print!("{}", format_entries(&entries));
+                }
+            }
+            SnapshotsAction::Info { id, format } => {
+                let entry = store.get(id)?;
+                if format == "json" {
+                    println!("{}", to_json(&entry)?);
+                } else {
+                    print!("{}", format_entry(&entry));
+                    println!("File:       {}", store.dir().join(format!("{}.json", entry.id)).display());
+                }
+            }
+            SnapshotsAction::Tag { id, tags, remove } => {
+                let entry = store.tag(id, tags, remove)?;
+                println!("{}  {}", short_id(&entry.id), format_tags(&entry));
+            }
+            SnapshotsAction::Prune {
+                keep_latest,
+                older_than_days,
+                include_tagged,
+                dry_run,
+            } => {
+                let policy = PrunePolicy {
+                    keep_latest: *keep_latest,
+                    added_before: older_than_days
+                        .map(|days| Utc::now() - chrono::Duration::days(i64::from(days))),
+                    include_tagged: *include_tagged,
+                };
+                let removed = store.prune(&policy, *dry_run)?;
+                let verb = if *dry_run { "Would remove" } else { "Removed" };
+                for entry in &removed {
+                    println!("{} {}", verb, short_id(&entry.id));
+                }
+                eprintln!("{} {} snapshot(s)", verb, removed.len());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One line per snapshot: ID, chain, block, era, size, added and tags
+pub fn format_entries(entries: &[SnapshotEntry]) -> String {
+    if entries.is_empty() {
+        return "No snapshots stored\n".to_string();
+    }
+    let mut output = format!(
+        "{:<12}  {:<10}  {:>10}  {:>5}  {:>10}  {:<20}  {}\n",
+        "ID", "CHAIN", "BLOCK", "ERA", "SIZE", "ADDED", "TAGS"
+    );
+    for entry in entries {
+        output.push_str(&format!(
+            "{:<12}  {:<10}  {:>10}  {:>5}  {:>10}  {:<20}  {}\n",
+            short_id(&entry.id),
+            entry.chain.as_deref().unwrap_or("-"),
+            optional(entry.block_number),
+            optional(entry.era),
+            crate::cli::output::format_size(entry.size_bytes),
+            entry.added_at().map_or_else(|| entry.added_at.clone(), |t| t.format("%Y-%m-%d %H:%M:%S").to_string()),
+            format_tags(entry),
+        ));
+    }
+    output
+}
+
+/// Every field of a snapshot, one per line
+pub fn format_entry(entry: &SnapshotEntry) -> String {
+    format!(
+        "ID:         {}\nChain:      {}\nBlock:      {}\nEra:        {}\nSize:       {}\nCandidates: {}\nNominators: {}\nAdded:      {}\nSource:     {}\nTags:       {}\n",
+        entry.id,
+        entry.chain.as_deref().unwrap_or("-"),
+        optional(entry.block_number),
+        optional(entry.era),
+        crate::cli::output::format_size(entry.size_bytes),
+        entry.candidate_count,
+        entry.nominator_count,
+        entry.added_at,
+        entry.source.as_deref().unwrap_or("-"),
+        format_tags(entry),
+    )
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..id.len().min(12)]
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+fn format_tags(entry: &SnapshotEntry) -> String {
+    if entry.tags.is_empty() {
+        return "-".to_string();
+    }
+    entry.tags.iter().cloned().collect::<Vec<_>>().join(",")
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, ElectionError> {
+    serde_json::to_string_pretty(value).map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to serialize snapshots: {}", e),
+        source: Some(ErrorSource::new(e)),
+    })
+}
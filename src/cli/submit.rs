@@ -0,0 +1,153 @@
+//! Solution submission command (requires the `submit` feature)
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::submit::extrinsic::decode_hex;
+use crate::submit::{
+    signer_from_suri, DryRunOutcome, SolutionWitness, SubmissionBuilder, SubmitClient,
+    DEFAULT_SUBMIT_CALL_INDEX, DEFAULT_SUBMIT_UNSIGNED_CALL_INDEX,
+};
+use clap::Parser;
+use sp_core::crypto::{Pair as _, Ss58Codec};
+use std::path::PathBuf;
+
+/// Environment variable holding the signer's secret URI
+pub const SURI_ENV_VAR: &str = "OFFLINE_ELECTION_SURI";
+
+/// Submit command for dry-running and broadcasting a solution
+#[derive(Parser)]
+#[command(name = "submit")]
+#[command(about = "Sign and submit an exported RawSolution to the multi-phase election pallet")]
+pub struct SubmitCommand {
+    /// RPC URL of the node to dry-run against and broadcast to
+    #[arg(long)]
+    pub rpc_url: String,
+
+    /// File containing the SCALE-encoded RawSolution as hex
+    #[arg(long)]
+    pub solution_file: PathBuf,
+
+    /// Index of the ElectionProviderMultiPhase pallet in the runtime
+    #[arg(long)]
+    pub pallet_index: u8,
+
+    /// Call index of `submit` in the pallet
+    #[arg(long, default_value_t = DEFAULT_SUBMIT_CALL_INDEX)]
+    pub submit_call_index: u8,
+
+    /// Call index of `submit_unsigned` in the pallet
+    #[arg(long, default_value_t = DEFAULT_SUBMIT_UNSIGNED_CALL_INDEX)]
+    pub submit_unsigned_call_index: u8,
+
+    /// Submit via `submit_unsigned` instead of a signed `submit`
+    #[arg(long, requires_all = ["witness_voters", "witness_targets"])]
+    pub unsigned: bool,
+
+    /// Snapshot voter count for the unsigned submission witness
+    #[arg(long)]
+    pub witness_voters: Option<u32>,
+
+    /// Snapshot target count for the unsigned submission witness
+    #[arg(long)]
+    pub witness_targets: Option<u32>,
+
+    /// File containing the signer's secret URI (default: $OFFLINE_ELECTION_SURI)
+    #[arg(long)]
+    pub suri_file: Option<PathBuf>,
+
+    /// Tip paid to the block author
+    #[arg(long, default_value_t = 0)]
+    pub tip: u128,
+
+    /// Include the CheckMetadataHash signed extension (required by recent runtimes)
+    #[arg(long)]
+    pub metadata_hash_extension: bool,
+
+    /// Broadcast the extrinsic after a successful dry run (default: dry run only)
+    #[arg(long)]
+    pub broadcast: bool,
+}
+
+impl SubmitCommand {
+    /// Execute the submit command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let raw_solution = self.load_solution()?;
+        let builder = SubmissionBuilder::new(self.pallet_index, raw_solution)
+            .submit_call_index(self.submit_call_index)
+            .submit_unsigned_call_index(self.submit_unsigned_call_index);
+        let client = SubmitClient::new(&self.rpc_url)?;
+
+        let extrinsic = match (self.unsigned, self.witness_voters, self.witness_targets) {
+            (true, Some(voters), Some(targets)) => {
+                builder.unsigned_extrinsic(SolutionWitness { voters, targets })
+            }
+            (true, _, _) => {
+                return Err(ElectionError::ValidationError {
+                    message: "Unsigned submissions require --witness-voters and --witness-targets".to_string(),
+                    field: Some("witness".to_string()),
+                });
+            }
+            (false, _, _) => {
+                let signer = signer_from_suri(&self.load_suri()?)?;
+                let account = signer.public().to_ss58check();
+                eprintln!("Signing as {}", account);
+                let context = client
+                    .signing_context(&account, self.tip, self.metadata_hash_extension)
+                    .await?;
+                builder.signed_extrinsic(&signer, &context)
+            }
+        };
+
+        eprintln!("Dry-running extrinsic ({} bytes)...", extrinsic.len());
+        let outcome = client.dry_run(&extrinsic).await?;
+        match outcome {
+            DryRunOutcome::Success => eprintln!("  ✓ Dry run succeeded"),
+            DryRunOutcome::DispatchError(ref error) => {
+                return Err(ElectionError::InvalidData {
+                    message: format!("Dry run dispatch error: {}", error),
+                    source: None,
+                });
+            }
+            DryRunOutcome::Invalid(ref error) => {
+                return Err(ElectionError::InvalidData {
+                    message: format!("Dry run rejected the transaction: {}", error),
+                    source: None,
+                });
+            }
+        }
+
+        if self.broadcast {
+            let hash = client.submit(&extrinsic).await?;
+            println!("{}", hash);
+        } else {
+            println!("0x{}", hex::encode(&extrinsic));
+            eprintln!("Not broadcast; pass --broadcast to submit");
+        }
+
+        Ok(())
+    }
+
+    /// Read the hex-encoded RawSolution
+    fn load_solution(&self) -> Result<Vec<u8>, ElectionError> {
+        let content = std::fs::read_to_string(&self.solution_file).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read solution file: {}", e),
+            path: self.solution_file.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        decode_hex(&content)
+    }
+
+    /// Read the signer's secret URI from the key file or environment
+    fn load_suri(&self) -> Result<String, ElectionError> {
+        match self.suri_file {
+            Some(ref path) => std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to read key file: {}", e),
+                path: path.clone(),
+                source: Some(ErrorSource::new(e)),
+            }),
+            None => std::env::var(SURI_ENV_VAR).map_err(|_| ElectionError::ValidationError {
+                message: format!("Signed submissions require --suri-file or ${}", SURI_ENV_VAR),
+                field: Some("suri".to_string()),
+            }),
+        }
+    }
+}
@@ -0,0 +1,563 @@
+//! Interactive terminal UI for exploring elections
+//!
+//! Loads a snapshot once, then lets the user adjust candidate stakes or remove
+//! candidates and re-runs the election on every change, highlighting churn in
+//! the elected set relative to the previous run.
+//!
+//! [`TuiApp`] holds the state and handles keys without touching the terminal,
+//! so it can be driven from tests; warnings from the engine are collected in
+//! its messages instead of being printed over the screen.
+
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_overrides::ElectionOverrides;
+use crate::models::election_result::ElectionResult;
+use crate::types::AlgorithmType;
+use clap::Parser;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::ExecutableCommand;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{Frame, Terminal};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Stake adjustment step applied by the `+`/`-` keys (percent of current stake)
+const STAKE_STEP_PERCENT: u128 = 10;
+
+/// Most warnings shown below the table
+const MAX_MESSAGE_LINES: usize = 5;
+
+/// TUI command for interactive election exploration
+#[derive(Parser)]
+#[command(name = "tui")]
+#[command(about = "Explore an election interactively in the terminal")]
+pub struct TuiCommand {
+    /// Election algorithm to use (sequential-phragmen, parallel-phragmen, multi-phase, mms)
+    #[arg(long, default_value = "sequential-phragmen")]
+    pub algorithm: String,
+
+    /// Number of validators to select
+    #[arg(long)]
+    pub active_set_size: u32,
+
+    /// RPC URL for fetching on-chain data
+    #[arg(long, conflicts_with = "input_file")]
+    pub rpc_url: Option<String>,
+
+    /// Block number for RPC snapshot
+    #[arg(long, requires = "rpc_url")]
+    pub block_number: Option<u64>,
+
+    /// Number of RPC storage queries in flight at once when fetching a snapshot
+    #[arg(long, default_value_t = crate::input::rpc::DEFAULT_RPC_CONCURRENCY, requires = "rpc_url")]
+    pub rpc_concurrency: usize,
+
+    /// Seconds a single RPC request may take before it is abandoned and retried
+    #[arg(long, default_value_t = crate::input::rpc::DEFAULT_REQUEST_TIMEOUT.as_secs(), requires = "rpc_url")]
+    pub rpc_timeout: u64,
+
+    /// Seconds allowed for fetching the whole RPC snapshot, retries included
+    #[arg(long, requires = "rpc_url")]
+    pub rpc_deadline: Option<u64>,
+
+    /// Maximum RPC requests started per second, for rate-limited public endpoints
+    #[arg(long, requires = "rpc_url")]
+    pub rpc_rate_limit: Option<f64>,
+
+    /// Input file path (JSON format)
+    #[arg(long, conflicts_with = "rpc_url")]
+    pub input_file: Option<PathBuf>,
+}
+
+impl TuiCommand {
+    /// Execute the TUI command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let algorithm = self.algorithm.parse::<AlgorithmType>()
+            .map_err(|e| ElectionError::ValidationError {
+                message: format!("Invalid algorithm: {}", e),
+                field: Some("algorithm".to_string()),
+            })?;
+        let config = ElectionConfiguration::builder()
+            .algorithm(algorithm)
+            .active_set_size(self.active_set_size)
+            .build()?;
+
+        let data = self.load_data().await?;
+        let mut app = TuiApp::new(data, config);
+        app.rerun();
+
+        run_terminal(&mut app).map_err(|e| ElectionError::InvalidData {
+            message: format!("Terminal error: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+
+    /// Load election data from the specified source
+    async fn load_data(&self) -> Result<ElectionData, ElectionError> {
+        if let Some(ref rpc_url) = self.rpc_url {
+            let mut loader = crate::input::rpc::RpcLoader::new(rpc_url)?
+                .with_concurrency(self.rpc_concurrency)
+                .with_request_timeout(std::time::Duration::from_secs(self.rpc_timeout))
+                .with_rate_limit(self.rpc_rate_limit.unwrap_or(0.0))?;
+            if let Some(deadline) = self.rpc_deadline {
+                loader = loader.with_deadline(std::time::Duration::from_secs(deadline));
+            }
+            match self.block_number {
+                Some(block_number) => loader.load_at_block(block_number).await,
+                None => loader.load_latest().await,
+            }
+        } else if let Some(ref input_file) = self.input_file {
+            crate::input::json::JsonLoader::new().load_from_file(input_file.clone())
+        } else {
+            Err(ElectionError::ValidationError {
+                message: "Must specify one of: --rpc-url or --input-file".to_string(),
+                field: None,
+            })
+        }
+    }
+}
+
+/// Column the candidate table is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Backing,
+    Rank,
+    Commission,
+}
+
+impl SortColumn {
+    /// The column the `s` key switches to
+    pub fn next(self) -> Self {
+        match self {
+            SortColumn::Backing => SortColumn::Rank,
+            SortColumn::Rank => SortColumn::Commission,
+            SortColumn::Commission => SortColumn::Backing,
+        }
+    }
+
+    /// Name shown in the summary line
+    pub fn label(self) -> &'static str {
+        match self {
+            SortColumn::Backing => "backing",
+            SortColumn::Rank => "rank",
+            SortColumn::Commission => "commission",
+        }
+    }
+}
+
+/// Row of the candidate table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandidateRow {
+    /// Candidate account
+    pub account_id: String,
+    /// Self stake, with any stake override applied
+    pub self_stake: u128,
+    /// Backing in the latest result; zero if not elected
+    pub backing: u128,
+    /// Rank in the latest result, if elected
+    pub rank: Option<u32>,
+    /// Commission in percent, if known
+    pub commission: Option<u8>,
+    /// Whether the user removed the candidate
+    pub removed: bool,
+    /// Change in elected status since the previous run
+    pub churn: Churn,
+}
+
+/// Change in elected status relative to the previous run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Churn {
+    /// Elected in both runs, or in neither
+    Unchanged,
+    /// Elected now but not in the previous run
+    Entered,
+    /// Elected in the previous run but not now
+    Left,
+}
+
+/// Interactive application state
+pub struct TuiApp {
+    data: ElectionData,
+    config: ElectionConfiguration,
+    engine: ElectionEngine,
+    overrides: ElectionOverrides,
+    removed: HashSet<String>,
+    result: Option<ElectionResult>,
+    previous_elected: HashSet<String>,
+    elected: HashSet<String>,
+    status: String,
+    /// Warnings from the latest run
+    messages: Vec<String>,
+    sort: SortColumn,
+    table_state: TableState,
+}
+
+impl TuiApp {
+    /// Create the state for `data`; call [`rerun`](Self::rerun) for the first result
+    pub fn new(data: ElectionData, config: ElectionConfiguration) -> Self {
+        let mut table_state = TableState::default();
+        table_state.select(Some(0));
+        Self {
+            data,
+            config,
+            engine: ElectionEngine::new().quiet(),
+            overrides: ElectionOverrides::new(),
+            removed: HashSet::new(),
+            result: None,
+            previous_elected: HashSet::new(),
+            elected: HashSet::new(),
+            status: String::new(),
+            messages: Vec::new(),
+            sort: SortColumn::Backing,
+            table_state,
+        }
+    }
+
+    /// Re-run the election with the current overrides and removals
+    pub fn rerun(&mut self) {
+        let mut data = self.data.clone();
+        if !self.removed.is_empty() {
+            data.candidates.retain(|c| !self.removed.contains(&c.account_id));
+            for nominator in data.nominators.iter_mut() {
+                nominator.targets.retain(|t| !self.removed.contains(t));
+            }
+            // Nominators left without targets cannot take part in the election
+            data.nominators.retain(|n| !n.targets.is_empty());
+        }
+
+        let mut config = self.config.clone();
+        config.overrides = Some(self.overrides.clone());
+
+        self.messages.clear();
+        if config.active_set_size as usize > data.candidates.len() {
+            self.messages.push(format!(
+                "Requested {} validators but only {} candidates remain",
+                config.active_set_size,
+                data.candidates.len()
+            ));
+        }
+        match self.engine.execute(&config, &data) {
+            Ok(result) => {
+                self.messages.extend(result.issues.iter().map(|issue| issue.message.clone()));
+                self.previous_elected = std::mem::take(&mut self.elected);
+                self.elected = result
+                    .selected_validators
+                    .iter()
+                    .map(|v| v.account_id.clone())
+                    .collect();
+                let entered = self.elected.difference(&self.previous_elected).count();
+                let left = self.previous_elected.difference(&self.elected).count();
+                self.status = if self.result.is_some() {
+                    format!("Re-ran election: {} entered, {} left", entered, left)
+                } else {
+                    format!("Elected {} validators", self.elected.len())
+                };
+                self.result = Some(result);
+            }
+            Err(e) => {
+                self.status = format!("Election failed: {}", e);
+            }
+        }
+    }
+
+    /// Outcome of the latest run, shown in the status bar
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Warnings from the latest run
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+
+    /// Latest result, if any run succeeded
+    pub fn result(&self) -> Option<&ElectionResult> {
+        self.result.as_ref()
+    }
+
+    /// Column the table is sorted by
+    pub fn sort(&self) -> SortColumn {
+        self.sort
+    }
+
+    /// Handle a key press; returns `true` when the user asked to quit
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.adjust_stake(true),
+            KeyCode::Char('-') => self.adjust_stake(false),
+            KeyCode::Char('x') => self.toggle_removed(),
+            KeyCode::Char('s') => self.sort = self.sort.next(),
+            KeyCode::Char('r') => self.reset(),
+            _ => {}
+        }
+        false
+    }
+
+    /// Build table rows sorted by the current sort column
+    pub fn rows(&self) -> Vec<CandidateRow> {
+        let selected: HashMap<&String, (u128, Option<u32>)> = self
+            .result
+            .iter()
+            .flat_map(|r| r.selected_validators.iter())
+            .map(|v| (&v.account_id, (v.total_backing_stake, v.rank)))
+            .collect();
+
+        let mut rows: Vec<CandidateRow> = self
+            .data
+            .candidates
+            .iter()
+            .map(|c| {
+                let (backing, rank) = selected.get(&c.account_id).copied().unwrap_or((0, None));
+                let is_elected = self.elected.contains(&c.account_id);
+                let was_elected = self.previous_elected.contains(&c.account_id);
+                CandidateRow {
+                    account_id: c.account_id.clone(),
+                    self_stake: self
+                        .overrides
+                        .candidate_stakes
+                        .get(&c.account_id)
+                        .copied()
+                        .unwrap_or(c.stake),
+                    backing,
+                    rank,
+                    commission: c.metadata.as_ref().and_then(|m| m.commission_rate),
+                    removed: self.removed.contains(&c.account_id),
+                    churn: match (was_elected, is_elected) {
+                        (false, true) if !self.previous_elected.is_empty() => Churn::Entered,
+                        (true, false) => Churn::Left,
+                        _ => Churn::Unchanged,
+                    },
+                }
+            })
+            .collect();
+
+        match self.sort {
+            SortColumn::Backing => rows.sort_by_key(|r| std::cmp::Reverse(r.backing)),
+            SortColumn::Rank => rows.sort_by_key(|r| r.rank.unwrap_or(u32::MAX)),
+            SortColumn::Commission => rows.sort_by_key(|r| r.commission.unwrap_or(u8::MAX)),
+        }
+        rows
+    }
+
+    /// Account of the highlighted row
+    pub fn selected_account(&self) -> Option<String> {
+        let index = self.table_state.selected()?;
+        self.rows().get(index).map(|r| r.account_id.clone())
+    }
+
+    fn move_selection(&mut self, delta: i64) {
+        let len = self.data.candidates.len() as i64;
+        if len == 0 {
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0) as i64;
+        let next = (current + delta).clamp(0, len - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    /// Adjust the selected candidate's stake by one step up or down
+    fn adjust_stake(&mut self, increase: bool) {
+        let Some(account_id) = self.selected_account() else {
+            return;
+        };
+        let current = self
+            .overrides
+            .candidate_stakes
+            .get(&account_id)
+            .copied()
+            .or_else(|| {
+                self.data
+                    .candidates
+                    .iter()
+                    .find(|c| c.account_id == account_id)
+                    .map(|c| c.stake)
+            })
+            .unwrap_or(0);
+        let step = (current * STAKE_STEP_PERCENT / 100).max(1);
+        let stake = if increase {
+            current.saturating_add(step)
+        } else {
+            current.saturating_sub(step)
+        };
+        self.overrides.candidate_stakes.insert(account_id, stake);
+        self.rerun();
+    }
+
+    /// Toggle removal of the selected candidate
+    fn toggle_removed(&mut self) {
+        let Some(account_id) = self.selected_account() else {
+            return;
+        };
+        if !self.removed.remove(&account_id) {
+            self.removed.insert(account_id);
+        }
+        self.rerun();
+    }
+
+    fn reset(&mut self) {
+        self.overrides = ElectionOverrides::new();
+        self.removed.clear();
+        self.rerun();
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(5),
+                Constraint::Length(match self.messages.len() {
+                    0 => 0,
+                    count => count.min(MAX_MESSAGE_LINES) as u16 + 2,
+                }),
+                Constraint::Length(3),
+            ])
+            .split(frame.size());
+
+        let summary = match self.result {
+            Some(ref result) => {
+                let score = result.score();
+                format!(
+                    "{} | elected {}/{} | min stake {} | total stake {} | sort: {}",
+                    result.algorithm_used,
+                    result.validator_count(),
+                    self.data.candidates.len(),
+                    score.minimal_stake,
+                    score.sum_stake,
+                    self.sort.label()
+                )
+            }
+            None => "No result".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(summary)
+                .block(Block::default().borders(Borders::ALL).title("Offline NPoS Election")),
+            chunks[0],
+        );
+
+        let rows: Vec<Row> = self
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let marker = match row.churn {
+                    Churn::Entered => "+",
+                    Churn::Left => "-",
+                    Churn::Unchanged if row.removed => "x",
+                    Churn::Unchanged => " ",
+                };
+                let style = match row.churn {
+                    Churn::Entered => Style::default().fg(Color::Green),
+                    Churn::Left => Style::default().fg(Color::Red),
+                    Churn::Unchanged if row.removed => Style::default().fg(Color::DarkGray),
+                    Churn::Unchanged => Style::default(),
+                };
+                Row::new(vec![
+                    Cell::from(marker),
+                    Cell::from(row.rank.map(|r| r.to_string()).unwrap_or_default()),
+                    Cell::from(row.account_id),
+                    Cell::from(row.backing.to_string()),
+                    Cell::from(row.self_stake.to_string()),
+                    Cell::from(row.commission.map(|c| format!("{}%", c)).unwrap_or_default()),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Length(5),
+                Constraint::Min(20),
+                Constraint::Length(24),
+                Constraint::Length(24),
+                Constraint::Length(10),
+            ],
+        )
+        .header(
+            Row::new(vec!["", "Rank", "Account", "Backing", "Self Stake", "Commission"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::default().borders(Borders::ALL).title("Candidates"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, chunks[1], &mut self.table_state);
+
+        if !self.messages.is_empty() {
+            let messages = self.messages.iter().take(MAX_MESSAGE_LINES).cloned().collect::<Vec<_>>().join("\n");
+            frame.render_widget(
+                Paragraph::new(messages)
+                    .style(Style::default().fg(Color::Yellow))
+                    .block(Block::default().borders(Borders::ALL).title("Warnings")),
+                chunks[2],
+            );
+        }
+
+        let help = format!(
+            "{}  |  ↑/↓ select  +/- stake  x remove  s sort  r reset  q quit",
+            self.status
+        );
+        frame.render_widget(
+            Paragraph::new(help).block(Block::default().borders(Borders::ALL)),
+            chunks[3],
+        );
+    }
+}
+
+/// Set up the terminal, run the event loop and restore the terminal afterwards
+///
+/// A panic hook restores the terminal before the panic message is printed,
+/// so a panic does not leave the shell in raw mode on the alternate screen.
+fn run_terminal(app: &mut TuiApp) -> std::io::Result<()> {
+    let previous_hook = Arc::new(std::panic::take_hook());
+    let chained_hook = Arc::clone(&previous_hook);
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        (*chained_hook)(info);
+    }));
+
+    let result = enable_raw_mode()
+        .and_then(|_| std::io::stdout().execute(EnterAlternateScreen).map(|_| ()))
+        .and_then(|_| Terminal::new(CrosstermBackend::new(std::io::stdout())))
+        .and_then(|mut terminal| event_loop(&mut terminal, app));
+    let restored = restore_terminal();
+
+    drop(std::panic::take_hook());
+    std::panic::set_hook(Box::new(move |info| (*previous_hook)(info)));
+    result.and(restored)
+}
+
+/// Leave raw mode and the alternate screen
+fn restore_terminal() -> std::io::Result<()> {
+    disable_raw_mode()?;
+    std::io::stdout().execute(LeaveAlternateScreen)?;
+    Ok(())
+}
+
+fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut TuiApp,
+) -> std::io::Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && app.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Result bundle verification command
+//!
+//! Checks a bundle written by `run --export-bundle`: file hashes against the
+//! manifest, the signature if present, and that re-running the bundled
+//! election reproduces the bundled result.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::result_bundle::{BundleVerification, ResultBundle};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Verify-bundle command for checking published result bundles
+#[derive(Parser)]
+#[command(name = "verify-bundle")]
+#[command(about = "Check a result bundle's hashes and signature and re-run its election")]
+pub struct VerifyBundleCommand {
+    /// Bundle directory
+    #[arg(long)]
+    pub bundle: PathBuf,
+
+    /// Fail unless the bundle carries a valid signature
+    #[arg(long)]
+    pub require_signature: bool,
+
+    /// Fail unless the bundle is signed by this public key (0x-prefixed hex)
+    #[arg(long, value_name = "PUBLIC_KEY")]
+    pub signer: Option<String>,
+
+    /// Output format: json or human-readable
+    #[arg(long, default_value = "json")]
+    pub format: String,
+}
+
+impl VerifyBundleCommand {
+    /// Execute the verify-bundle command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        if (self.require_signature || self.signer.is_some()) && !cfg!(feature = "sign") {
+            return Err(ElectionError::ValidationError {
+                message: "Checking signatures requires building with the `sign` feature".to_string(),
+                field: Some("require_signature".to_string()),
+            });
+        }
+
+        let bundle = ResultBundle::load(&self.bundle)?;
+        eprintln!(
+            "Re-running {} election over {} candidates and {} nominators...",
+            bundle.config.algorithm,
+            bundle.data.candidates.len(),
+            bundle.data.nominators.len()
+        );
+        let verification = bundle.verify()?;
+
+        if self.format == "human-readable" {
+            println!("{}", format_verification(&bundle, &verification));
+        } else {
+            let output = serde_json::to_string_pretty(&verification).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize verification: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            println!("{}", output);
+        }
+
+        if !verification.is_valid() {
+            return Err(ElectionError::InvalidData {
+                message: "Bundle verification failed".to_string(),
+                source: None,
+            });
+        }
+        if self.require_signature && verification.signature_valid != Some(true) {
+            return Err(ElectionError::InvalidData {
+                message: "Bundle is not signed".to_string(),
+                source: None,
+            });
+        }
+        if let Some(ref expected) = self.signer {
+            let actual = bundle.signature.as_ref().map(|s| s.public_key.as_str());
+            if !actual.is_some_and(|actual| actual.eq_ignore_ascii_case(expected)) {
+                return Err(ElectionError::InvalidData {
+                    message: format!("Bundle is not signed by {}", expected),
+                    source: None,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Format a verification report as human-readable text
+fn format_verification(bundle: &ResultBundle, verification: &BundleVerification) -> String {
+    let yes_no = |ok: bool| if ok { "yes" } else { "NO" };
+    let mut output = String::new();
+    output.push_str("Result Bundle Verification\n");
+    output.push_str("==========================\n");
+    output.push_str(&format!("Bundle: {}\n", bundle.path.display()));
+    output.push_str(&format!("Written by version: {}\n", bundle.manifest.crate_version));
+    output.push_str(&format!("Files Intact: {}\n", yes_no(verification.mismatched_files.is_empty())));
+    for file in &verification.mismatched_files {
+        output.push_str(&format!("  - {} does not match the manifest\n", file));
+    }
+    output.push_str(&format!("Content Hashes Match: {}\n", yes_no(verification.content_hashes_match)));
+    output.push_str(&format!("Result Reproduced: {}\n", yes_no(verification.result_differences.is_empty())));
+    for difference in &verification.result_differences {
+        output.push_str(&format!("  - {}\n", difference));
+    }
+    let signature = match (&bundle.signature, verification.signature_valid) {
+        (None, _) => "unsigned".to_string(),
+        (Some(signature), Some(true)) => format!("valid ({} key {})", signature.scheme, signature.public_key),
+        (Some(signature), Some(false)) => format!("INVALID ({} key {})", signature.scheme, signature.public_key),
+        (Some(_), None) => "not checked (built without the `sign` feature)".to_string(),
+    };
+    output.push_str(&format!("Signature: {}", signature));
+    output
+}
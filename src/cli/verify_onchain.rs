@@ -0,0 +1,113 @@
+//! On-chain solution verification command
+//!
+//! Fetches the queued solution and the snapshot it was computed from,
+//! recomputes its score, checks feasibility and searches for a better solution.
+
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::rpc::RpcLoader;
+use crate::models::onchain_solution::SolutionVerification;
+use clap::Parser;
+
+/// Verify-onchain command for auditing queued solutions
+#[derive(Parser)]
+#[command(name = "verify-onchain")]
+#[command(about = "Verify the queued on-chain election solution against its snapshot")]
+pub struct VerifyOnchainCommand {
+    /// RPC URL for fetching on-chain data
+    #[arg(long)]
+    pub rpc_url: String,
+
+    /// Block number to read the queued solution at (default: latest)
+    #[arg(long)]
+    pub block_number: Option<u64>,
+
+    /// Output format: json or human-readable
+    #[arg(long, default_value = "json")]
+    pub format: String,
+}
+
+impl VerifyOnchainCommand {
+    /// Execute the verify-onchain command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let loader = RpcLoader::new(&self.rpc_url)?;
+        let block_number = match self.block_number {
+            Some(block_number) => block_number,
+            None => loader.get_latest_block_number().await?,
+        };
+
+        let solution = loader.queued_solution_at(block_number).await?.ok_or_else(|| {
+            ElectionError::InvalidData {
+                message: format!("No queued solution at block {}", block_number),
+                source: None,
+            }
+        })?;
+        let snapshot = loader.snapshot_at(block_number).await?.ok_or_else(|| {
+            ElectionError::InvalidData {
+                message: format!(
+                    "No election snapshot at block {}; the snapshot is only kept until the election is enacted",
+                    block_number
+                ),
+                source: None,
+            }
+        })?;
+        let desired_targets = loader.desired_targets_at(block_number).await?;
+
+        eprintln!(
+            "Verifying {} solution with {} winners against {} targets and {} voters...",
+            solution.compute,
+            solution.supports.len(),
+            snapshot.candidates.len(),
+            snapshot.nominators.len()
+        );
+        let verification =
+            ElectionEngine::new().verify_solution(&solution, &snapshot, desired_targets, block_number);
+
+        if self.format == "human-readable" {
+            println!("{}", format_verification(&verification));
+        } else {
+            let output = serde_json::to_string_pretty(&verification).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize verification: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            println!("{}", output);
+        }
+
+        Ok(())
+    }
+}
+
+/// Format a verification report as human-readable text
+pub(crate) fn format_verification(verification: &SolutionVerification) -> String {
+    let mut output = String::new();
+    output.push_str("Queued Solution Verification\n");
+    output.push_str("============================\n");
+    output.push_str(&format!("Block: {}\n", verification.block_number));
+    output.push_str(&format!("Compute: {}\n", verification.compute));
+    output.push_str(&format!("Claimed Score: {:?}\n", verification.claimed_score));
+    output.push_str(&format!("Computed Score: {:?}\n", verification.computed_score));
+    output.push_str(&format!(
+        "Score Matches: {}\n",
+        if verification.score_matches { "yes" } else { "NO" }
+    ));
+    output.push_str(&format!(
+        "Feasible: {}\n",
+        if verification.feasible { "yes" } else { "NO" }
+    ));
+    for issue in &verification.feasibility_issues {
+        output.push_str(&format!("  - {}\n", issue));
+    }
+
+    match (verification.best_alternative_score, verification.best_alternative_algorithm) {
+        (Some(score), Some(algorithm)) => {
+            output.push_str(&format!("Best Alternative ({}): {:?}\n", algorithm, score));
+            output.push_str(&format!(
+                "Better Solution Found: {}\n",
+                if verification.better_solution_found { "YES" } else { "no" }
+            ));
+        }
+        _ => output.push_str("Best Alternative: none (all algorithms failed on the snapshot)\n"),
+    }
+
+    output
+}
@@ -0,0 +1,200 @@
+//! Live chain watch command
+//!
+//! Follows new blocks over WebSocket and, as soon as the election snapshot
+//! phase opens, decodes the snapshot the chain will elect from, runs the
+//! solver and reports the predicted next validator set before it is enacted.
+
+use crate::cli::commands::NotificationArgs;
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::chain_watch::{http_url_for, ChainWatcher, ElectionPhase};
+use crate::input::rpc::RpcLoader;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::ElectionResult;
+use crate::notifications::Notifier;
+use crate::types::AlgorithmType;
+use clap::Parser;
+
+/// Watch-chain command for predicting upcoming elections
+#[derive(Parser)]
+#[command(name = "watch-chain")]
+#[command(about = "Watch the chain and predict the next validator set when the election snapshot opens")]
+pub struct WatchChainCommand {
+    /// WebSocket URL to subscribe to new blocks (e.g. wss://rpc.polkadot.io)
+    #[arg(long)]
+    pub ws_url: String,
+
+    /// HTTP RPC URL for fetching snapshot data (default: derived from --ws-url)
+    #[arg(long)]
+    pub rpc_url: Option<String>,
+
+    /// Election algorithm to use (sequential-phragmen, parallel-phragmen, multi-phase, mms)
+    #[arg(long, default_value = "sequential-phragmen")]
+    pub algorithm: String,
+
+    /// Number of validators to select (default: the chain's desired targets)
+    #[arg(long)]
+    pub active_set_size: Option<u32>,
+
+    /// Exit after the first prediction instead of waiting for the next election
+    #[arg(long)]
+    pub once: bool,
+
+    /// Output format: json or human-readable
+    #[arg(long, default_value = "json")]
+    pub format: String,
+
+    /// Only notify when this validator is predicted to enter or leave the set (can be repeated)
+    #[arg(long, value_name = "ACCOUNT_ID")]
+    pub notify_validator: Vec<String>,
+
+    /// Notification sinks fired on predicted set changes
+    #[command(flatten)]
+    pub notifications: NotificationArgs,
+}
+
+impl WatchChainCommand {
+    /// Execute the watch-chain command
+    pub async fn execute(&self) -> Result<(), ElectionError> {
+        let algorithm = self.algorithm.parse::<AlgorithmType>()
+            .map_err(|e| ElectionError::ValidationError {
+                message: format!("Invalid algorithm: {}", e),
+                field: Some("algorithm".to_string()),
+            })?;
+
+        let notifier = self.notifier()?;
+
+        let rpc_url = self.rpc_url.clone().unwrap_or_else(|| http_url_for(&self.ws_url));
+        let watcher = ChainWatcher::connect(&self.ws_url, RpcLoader::new(&rpc_url)?).await?;
+        let mut subscription = watcher.subscribe_new_heads().await?;
+
+        eprintln!("Watching {} for the next election snapshot...", self.ws_url);
+        loop {
+            let (block_number, phase) = watcher.wait_for_snapshot(&mut subscription).await?;
+            eprintln!("Snapshot phase opened at block {}, predicting next validator set...", block_number);
+
+            match predict_next_set(watcher.loader(), algorithm, self.active_set_size, block_number).await {
+                Ok((_, result)) => {
+                    println!("{}", self.format_prediction(block_number, phase, &result)?);
+                    if let Some(ref notifier) = notifier {
+                        self.notify_set_changes(notifier, watcher.loader(), block_number, &result).await;
+                    }
+                }
+                Err(e) => eprintln!("Error: {}", e),
+            }
+
+            if self.once {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Build the notifier from the notification flags
+    fn notifier(&self) -> Result<Option<Notifier>, ElectionError> {
+        let mut config = self.notifications.config()?;
+        for validator_id in &self.notify_validator {
+            config = config.watch_validator(validator_id.trim());
+        }
+        Ok(config.has_sinks().then(|| Notifier::new(config)))
+    }
+
+    /// Notify about validators entering or leaving the current active set
+    async fn notify_set_changes(
+        &self,
+        notifier: &Notifier,
+        loader: &RpcLoader,
+        block_number: u64,
+        result: &ElectionResult,
+    ) {
+        let current = match loader.active_validators_at(block_number).await {
+            Ok(current) => current,
+            Err(e) => {
+                eprintln!("  ⚠ Warning: Could not read the current validator set: {}", e);
+                return;
+            }
+        };
+        let predicted: Vec<String> = result
+            .selected_validators
+            .iter()
+            .map(|v| v.account_id.clone())
+            .collect();
+
+        let events = notifier.set_change_events(&current, &predicted, Some(block_number));
+        notifier.send_all(&events).await;
+    }
+
+    /// Format a prediction in the requested output format
+    fn format_prediction(
+        &self,
+        block_number: u64,
+        phase: ElectionPhase,
+        result: &ElectionResult,
+    ) -> Result<String, ElectionError> {
+        if self.format == "human-readable" {
+            let mut output = String::new();
+            output.push_str("Predicted Validator Set\n");
+            output.push_str("=======================\n");
+            output.push_str(&format!("Snapshot Block: {}\n", block_number));
+            output.push_str(&format!("Phase: {:?}\n", phase));
+            output.push_str(&format!("Algorithm: {:?}\n", result.algorithm_used));
+            output.push_str(&format!("Selected Validators: {}\n\n", result.selected_validators.len()));
+            for (idx, validator) in result.selected_validators.iter().enumerate() {
+                output.push_str(&format!(
+                    "{}. {} - Stake: {}\n",
+                    idx + 1,
+                    validator.account_id,
+                    validator.total_backing_stake
+                ));
+            }
+            Ok(output)
+        } else {
+            let report = serde_json::json!({
+                "snapshot_block": block_number,
+                "phase": phase,
+                "result": result,
+            });
+            serde_json::to_string_pretty(&report).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to serialize prediction: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })
+        }
+    }
+}
+
+/// Decode the election snapshot at a block and run the election on it
+///
+/// The snapshot (`ElectionProviderMultiPhase::Snapshot`) holds the voters
+/// and targets the chain elects from, already trimmed to its voter and
+/// target limits, so the prediction does not depend on how live staking
+/// storage changes after it was taken. Without `active_set_size`, selects
+/// the chain's desired targets at the block.
+pub async fn predict_next_set(
+    loader: &RpcLoader,
+    algorithm: AlgorithmType,
+    active_set_size: Option<u32>,
+    block_number: u64,
+) -> Result<(ElectionData, ElectionResult), ElectionError> {
+    let active_set_size = match active_set_size {
+        Some(size) => size,
+        None => loader.desired_targets_at(block_number).await?.ok_or_else(|| {
+            ElectionError::ValidationError {
+                message: "Desired targets not available on-chain; specify --active-set-size".to_string(),
+                field: Some("active_set_size".to_string()),
+            }
+        })?,
+    };
+
+    let data = loader.snapshot_at(block_number).await?.ok_or_else(|| ElectionError::ValidationError {
+        message: format!("No election snapshot at block {}; the signed or unsigned phase is not open", block_number),
+        field: Some("block_number".to_string()),
+    })?;
+    let config = ElectionConfiguration::builder()
+        .algorithm(algorithm)
+        .active_set_size(active_set_size)
+        .block_number(block_number)
+        .build()?;
+
+    let result = ElectionEngine::new().execute(&config, &data)?;
+    Ok((data, result))
+}
@@ -1,19 +1,60 @@
 //! Diagnostics generator for explaining election results
 
-use crate::diagnostics::models::{Diagnostics, StakeAnalysis, ValidatorExplanation};
+use crate::diagnostics::models::{
+    AttributeConcentration, AttributeGroup, Diagnostics, DiversityAnalysis, ElectedMargin, Finding,
+    FindingCode, LocalPjrViolation, MaximinGap, MinimumActiveStake, NearMiss, NominatorCluster,
+    NominatorClusters, OperatorGroups, OperatorSummary, OversubscribedValidator, PjrCheck, PjrViolation, Severity,
+    StakeAnalysis, StakeSensitivity, UnrewardedStake, ValidatorExplanation, ValidatorMinimumStake,
+};
+use crate::algorithms::mms::{balance_optimally, Mms};
+use crate::algorithms::prepared::PreparedElectionData;
+use crate::algorithms::trait_def::ElectionAlgorithm;
 use crate::error::ElectionError;
+use crate::models::election_config::ElectionConfiguration;
 use crate::models::election_data::ElectionData;
 use crate::models::election_result::ElectionResult;
+use crate::models::override_impact::OverrideImpact;
+use crate::models::validator::CandidateMetadata;
 use crate::types::AlgorithmType;
-use std::collections::{HashMap, HashSet};
+use sp_runtime::Perbill;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Default Jaccard similarity (percent) for linking two nominators
+pub const DEFAULT_CLUSTER_SIMILARITY_PERCENT: u8 = 80;
+/// Default smallest nominator cluster reported
+pub const DEFAULT_MIN_CLUSTER_SIZE: usize = 3;
+/// Nominator count above which clustering is skipped during `generate`
+const MAX_CLUSTERING_NOMINATORS: usize = 10_000;
+/// Default number of validators examined on each side of the cutoff
+pub const DEFAULT_SENSITIVITY_WINDOW: usize = 5;
+/// Nominator count above which the maximin support gap is skipped during `generate`
+const MAX_MAXIMIN_NOMINATORS: usize = 50_000;
+/// Number of validators named in each part of a summary
+const SUMMARY_TOP_COUNT: usize = 3;
+/// Operator share of the elected backing (bps) reported as `STAKE_CONCENTRATION_HIGH`
+const STAKE_CONCENTRATION_BPS: u32 = 3_334;
+/// Share of elected validators (bps) with one attribute value reported as `ATTRIBUTE_CONCENTRATION_HIGH`
+const ATTRIBUTE_CONCENTRATION_BPS: u32 = 5_000;
 
 /// Generator for election diagnostics
-pub struct DiagnosticsGenerator;
+pub struct DiagnosticsGenerator {
+    /// Whether `generate` runs PhragMMS and max-flow balancing for the maximin gap
+    maximin_gap: bool,
+}
 
 impl DiagnosticsGenerator {
     /// Create a new diagnostics generator
     pub fn new() -> Self {
-        Self
+        Self { maximin_gap: false }
+    }
+
+    /// Set whether [`generate`](Self::generate) includes the [`maximin_gap`](Self::maximin_gap)
+    ///
+    /// Off by default: the gap runs PhragMMS and max-flow balancing, which
+    /// can take longer than the election itself.
+    pub fn with_maximin_gap(mut self, enabled: bool) -> Self {
+        self.maximin_gap = enabled;
+        self
     }
 
     /// Generate diagnostics for an election result
@@ -23,7 +64,6 @@ impl DiagnosticsGenerator {
         data: &ElectionData,
     ) -> Result<Diagnostics, ElectionError> {
         let mut validator_explanations = Vec::new();
-        let mut warnings = Vec::new();
 
         // Create lookup maps for efficient access
         let selected_validator_set: HashSet<&String> = result
@@ -82,42 +122,284 @@ impl DiagnosticsGenerator {
         // Generate stake analysis
         let stake_analysis = self.generate_stake_analysis(result);
 
+        // Generate minimum active stake analysis
+        let minimum_active_stake = self.generate_minimum_active_stake(result);
+
+        // Cluster nominators with correlated nominations
+        let clustering_enabled = data.nominators.len() <= MAX_CLUSTERING_NOMINATORS;
+        let nominator_clusters = clustering_enabled.then(|| {
+            self.cluster_nominators(data, DEFAULT_CLUSTER_SIMILARITY_PERCENT, DEFAULT_MIN_CLUSTER_SIZE)
+        });
+
+        // Group the elected set by operator
+        let operator_groups = self.group_by_operator(result, data);
+
+        // Geographic and provider diversity, when attributes were attached
+        let diversity = self.generate_diversity_analysis(result, data);
+
+        // Margins of validators around the cutoff
+        let stake_sensitivity = self.stake_sensitivity(result, data, DEFAULT_SENSITIVITY_WINDOW);
+
+        // Proportional justified representation at the standard threshold
+        let pjr = self.check_pjr(result, data, None);
+
+        // Smallest backing against maximin support, when asked for
+        let maximin_gap = if self.maximin_gap && data.nominators.len() <= MAX_MAXIMIN_NOMINATORS {
+            self.maximin_gap(result, data)
+        } else {
+            None
+        };
+
+        // Stake outside the rewarded exposures of oversubscribed validators
+        let unrewarded_stake = self.unrewarded_stake(result);
+
         // Generate algorithm-specific insights
         let algorithm_insights = self.generate_algorithm_insights(result, data);
 
-        // Check for warnings
-        if result.selected_validators.len() < result.selected_validators.len() {
-            warnings.push(format!(
-                "Only {} validators selected, but {} were requested",
-                result.selected_validators.len(),
-                result.selected_validators.len()
-            ));
+        let mut findings = self.findings(result, data, &operator_groups, nominator_clusters.as_ref(), diversity.as_ref());
+        if let Some(ref counter_example) = pjr.counter_example {
+            findings.push(Finding {
+                code: FindingCode::PjrViolated,
+                severity: Severity::Warning,
+                subject: Some(counter_example.clone()),
+                message: format!(
+                    "Result violates PJR: voters of {} unelected candidates could back one with at least {} (e.g. {})",
+                    pjr.violations.len(),
+                    pjr.threshold,
+                    counter_example
+                ),
+                data: serde_json::json!({
+                    "threshold": pjr.threshold,
+                    "violating_candidates": pjr.violations.iter().map(|v| &v.candidate_id).collect::<Vec<_>>(),
+                }),
+            });
         }
-
-        if result.total_stake == 0 {
-            warnings.push("Total stake is zero - election may not be meaningful".to_string());
+        for violation in &pjr.local_violations {
+            findings.push(Finding {
+                code: FindingCode::LocalPjrViolated,
+                severity: Severity::Warning,
+                subject: Some(violation.candidate_id.clone()),
+                message: format!(
+                    "Result violates local PJR: the {} voters of {} are owed {} seats but back {} elected candidates",
+                    violation.voter_ids.len(),
+                    violation.candidate_id,
+                    violation.owed_seats,
+                    violation.elected_seats
+                ),
+                data: serde_json::json!({
+                    "threshold": pjr.threshold,
+                    "group_stake": violation.group_stake,
+                    "owed_seats": violation.owed_seats,
+                    "elected_seats": violation.elected_seats,
+                }),
+            });
         }
-
-        let zero_stake_validators = result
-            .selected_validators
-            .iter()
-            .filter(|v| v.total_backing_stake == 0)
-            .count();
-        if zero_stake_validators > 0 {
-            warnings.push(format!(
-                "{} selected validators have zero backing stake",
-                zero_stake_validators
-            ));
+        for validator in unrewarded_stake.iter().flat_map(|u| &u.validators) {
+            findings.push(Finding {
+                code: FindingCode::ValidatorOversubscribed,
+                severity: Severity::Warning,
+                subject: Some(validator.validator_id.clone()),
+                message: format!(
+                    "Validator {} is oversubscribed: {} of its {} nominators with {} stake earn no rewards",
+                    validator.validator_id,
+                    validator.unrewarded_count,
+                    validator.nominator_count,
+                    validator.unrewarded_stake
+                ),
+                data: serde_json::json!({
+                    "nominator_count": validator.nominator_count,
+                    "unrewarded_count": validator.unrewarded_count,
+                    "unrewarded_stake": validator.unrewarded_stake,
+                    "rewarded_minimum": validator.rewarded_minimum,
+                }),
+            });
+        }
+        if !clustering_enabled {
+            findings.push(Finding {
+                code: FindingCode::ClusteringSkipped,
+                severity: Severity::Info,
+                subject: None,
+                message: format!(
+                    "Nominator clustering skipped for {} nominators (limit {})",
+                    data.nominators.len(),
+                    MAX_CLUSTERING_NOMINATORS
+                ),
+                data: serde_json::json!({ "nominator_count": data.nominators.len(), "limit": MAX_CLUSTERING_NOMINATORS }),
+            });
         }
+        // Most severe first; the sort is stable so detection order is kept within a severity
+        findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+        let warnings = findings
+            .iter()
+            .filter(|f| f.severity >= Severity::Warning)
+            .map(|f| f.message.clone())
+            .collect();
 
         Ok(Diagnostics {
             validator_explanations,
             stake_analysis,
             algorithm_insights: Some(algorithm_insights),
+            minimum_active_stake: Some(minimum_active_stake),
+            nominator_clusters,
+            operator_groups: Some(operator_groups),
+            diversity,
+            stake_sensitivity: Some(stake_sensitivity),
+            pjr: Some(pjr),
+            maximin_gap,
+            unrewarded_stake,
+            findings,
             warnings,
         })
     }
 
+    /// Detect notable conditions in a result as typed findings
+    fn findings(
+        &self,
+        result: &ElectionResult,
+        data: &ElectionData,
+        operator_groups: &OperatorGroups,
+        nominator_clusters: Option<&NominatorClusters>,
+        diversity: Option<&DiversityAnalysis>,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        if result.total_stake == 0 {
+            findings.push(Finding {
+                code: FindingCode::TotalStakeZero,
+                severity: Severity::Critical,
+                subject: None,
+                message: "Total stake is zero - election may not be meaningful".to_string(),
+                data: serde_json::Value::Null,
+            });
+        }
+
+        if result.execution_metadata.balancing_converged == Some(false) {
+            let iterations = result.execution_metadata.balancing_iterations.unwrap_or(0);
+            let residual = result.execution_metadata.balancing_residual.unwrap_or(0);
+            findings.push(Finding {
+                code: FindingCode::BalancingIterationCap,
+                severity: Severity::Warning,
+                subject: None,
+                message: format!(
+                    "Balancing stopped at its cap of {} iterations without converging; a voter could still shift {}",
+                    iterations, residual
+                ),
+                data: serde_json::json!({ "iterations": iterations, "residual": residual }),
+            });
+        }
+
+        for validator in result.selected_validators.iter().filter(|v| v.total_backing_stake == 0) {
+            findings.push(Finding {
+                code: FindingCode::ValidatorZeroBacking,
+                severity: Severity::Warning,
+                subject: Some(validator.account_id.clone()),
+                message: format!("Selected validator {} has zero backing stake", validator.account_id),
+                data: serde_json::Value::Null,
+            });
+        }
+
+        for operator in operator_groups
+            .operators
+            .iter()
+            .filter(|o| o.stake_share_bps >= STAKE_CONCENTRATION_BPS)
+        {
+            findings.push(Finding {
+                code: FindingCode::StakeConcentrationHigh,
+                severity: Severity::Warning,
+                subject: Some(operator.operator_id.clone()),
+                message: format!(
+                    "Operator {} holds {:.2}% of the elected backing across {} validators",
+                    operator.display_name.as_deref().unwrap_or(&operator.operator_id),
+                    operator.stake_share_bps as f64 / 100.0,
+                    operator.elected_validator_ids.len()
+                ),
+                data: serde_json::json!({
+                    "stake_share_bps": operator.stake_share_bps,
+                    "total_backing": operator.total_backing,
+                    "elected_validator_ids": operator.elected_validator_ids,
+                }),
+            });
+        }
+
+        if let Some(diversity) = diversity {
+            for (attribute, concentration) in [
+                ("country", &diversity.by_country),
+                ("city", &diversity.by_city),
+                ("hosting_provider", &diversity.by_hosting_provider),
+            ] {
+                let Some(largest) = concentration.groups.first() else {
+                    continue;
+                };
+                if concentration.largest_share_bps < ATTRIBUTE_CONCENTRATION_BPS {
+                    continue;
+                }
+                findings.push(Finding {
+                    code: FindingCode::AttributeConcentrationHigh,
+                    severity: Severity::Warning,
+                    subject: Some(largest.value.clone()),
+                    message: format!(
+                        "{} of {} elected validators with a known {} have {} {}",
+                        largest.elected_count,
+                        concentration.groups.iter().map(|g| g.elected_count).sum::<usize>(),
+                        attribute.replace('_', " "),
+                        attribute.replace('_', " "),
+                        largest.value
+                    ),
+                    data: serde_json::json!({
+                        "attribute": attribute,
+                        "validator_share_bps": largest.validator_share_bps,
+                        "backing_share_bps": largest.backing_share_bps,
+                    }),
+                });
+            }
+        }
+
+        for cluster in nominator_clusters.map(|c| c.clusters.as_slice()).unwrap_or_default() {
+            findings.push(Finding {
+                code: FindingCode::NominatorClusterDetected,
+                severity: Severity::Info,
+                subject: cluster.nominator_ids.first().cloned(),
+                message: format!(
+                    "{} nominators with {:.2}% of nominator stake vote for nearly the same targets",
+                    cluster.nominator_ids.len(),
+                    cluster.stake_share_bps as f64 / 100.0
+                ),
+                data: serde_json::json!({
+                    "nominator_ids": cluster.nominator_ids,
+                    "total_stake": cluster.total_stake,
+                    "stake_share_bps": cluster.stake_share_bps,
+                }),
+            });
+        }
+
+        let mut allocated: HashMap<&str, u128> = HashMap::new();
+        for alloc in &result.stake_distribution {
+            *allocated.entry(alloc.nominator_id.as_str()).or_insert(0) += alloc.amount;
+        }
+        for nominator in &data.nominators {
+            let active = allocated.get(nominator.account_id.as_str()).copied().unwrap_or(0);
+            // Solvers split stake by Perbill ratios, which can drop a planck per edge
+            let dust = nominator.targets.len() as u128;
+            if active.saturating_add(dust) >= nominator.stake {
+                continue;
+            }
+            findings.push(Finding {
+                code: FindingCode::VoterTrimmed,
+                severity: Severity::Info,
+                subject: Some(nominator.account_id.clone()),
+                message: format!(
+                    "{} of nominator {}'s stake of {} backs no elected validator",
+                    nominator.stake - active,
+                    nominator.account_id,
+                    nominator.stake
+                ),
+                data: serde_json::json!({ "stake": nominator.stake, "active_stake": active }),
+            });
+        }
+
+        findings
+    }
+
     /// Explain why a validator was selected
     fn explain_selected_validator(
         &self,
@@ -147,11 +429,9 @@ impl DiagnosticsGenerator {
                     if total_stake == max_stake {
                         key_factors.push("Highest total stake".to_string());
                         reason_parts.push("(highest total stake)".to_string());
-                    } else {
-                        let percentile = (total_stake as f64 / max_stake as f64) * 100.0;
-                        if percentile >= 80.0 {
-                            key_factors.push("Top tier stake".to_string());
-                        }
+                    } else if total_stake >= max_stake - max_stake / 5 {
+                        // Within 80% of the highest backing, in integers
+                        key_factors.push("Top tier stake".to_string());
                     }
                 }
             } else {
@@ -271,6 +551,692 @@ impl DiagnosticsGenerator {
         }
     }
 
+    /// Generate the minimum allocated nominator stake per elected validator
+    fn generate_minimum_active_stake(&self, result: &ElectionResult) -> MinimumActiveStake {
+        let mut minimum_by_validator: HashMap<&String, (u128, &String)> = HashMap::new();
+        for alloc in &result.stake_distribution {
+            if alloc.amount == 0 {
+                continue;
+            }
+            let entry = minimum_by_validator
+                .entry(&alloc.validator_id)
+                .or_insert((alloc.amount, &alloc.nominator_id));
+            if alloc.amount < entry.0 {
+                *entry = (alloc.amount, &alloc.nominator_id);
+            }
+        }
+
+        let per_validator: Vec<ValidatorMinimumStake> = result
+            .selected_validators
+            .iter()
+            .map(|v| {
+                let minimum = minimum_by_validator.get(&v.account_id);
+                ValidatorMinimumStake {
+                    validator_id: v.account_id.clone(),
+                    minimum_allocation: minimum.map(|(amount, _)| *amount),
+                    nominator_id: minimum.map(|(_, nominator)| (*nominator).clone()),
+                }
+            })
+            .collect();
+
+        let global_minimum = per_validator
+            .iter()
+            .filter_map(|v| v.minimum_allocation)
+            .min();
+
+        MinimumActiveStake {
+            global_minimum,
+            per_validator,
+        }
+    }
+
+    /// Sum up the allocations a result marks unrewarded
+    ///
+    /// Returns `None` when no allocation is marked, as for results of
+    /// elections run without `max_rewarded_nominators`.
+    pub fn unrewarded_stake(&self, result: &ElectionResult) -> Option<UnrewardedStake> {
+        let mut by_validator: HashMap<&str, OversubscribedValidator> = HashMap::new();
+        for alloc in &result.stake_distribution {
+            if alloc.amount == 0 || alloc.nominator_id == alloc.validator_id {
+                continue;
+            }
+            let validator = by_validator
+                .entry(alloc.validator_id.as_str())
+                .or_insert_with(|| OversubscribedValidator {
+                    validator_id: alloc.validator_id.clone(),
+                    nominator_count: 0,
+                    unrewarded_count: 0,
+                    unrewarded_stake: 0,
+                    rewarded_minimum: u128::MAX,
+                });
+            validator.nominator_count += 1;
+            if alloc.unrewarded {
+                validator.unrewarded_count += 1;
+                validator.unrewarded_stake = validator.unrewarded_stake.saturating_add(alloc.amount);
+            } else {
+                validator.rewarded_minimum = validator.rewarded_minimum.min(alloc.amount);
+            }
+        }
+
+        let mut validators: Vec<OversubscribedValidator> =
+            by_validator.into_values().filter(|v| v.unrewarded_count > 0).collect();
+        if validators.is_empty() {
+            return None;
+        }
+        validators.sort_by(|a, b| {
+            b.unrewarded_stake
+                .cmp(&a.unrewarded_stake)
+                .then_with(|| a.validator_id.cmp(&b.validator_id))
+        });
+
+        let total_unrewarded = result.unrewarded_stake();
+        let elected_backing: u128 = result.selected_validators.iter().map(|v| v.total_backing_stake).sum();
+        let nominators: HashSet<&str> = result
+            .stake_distribution
+            .iter()
+            .filter(|alloc| alloc.unrewarded)
+            .map(|alloc| alloc.nominator_id.as_str())
+            .collect();
+        Some(UnrewardedStake {
+            total_unrewarded,
+            share_bps: total_unrewarded
+                .saturating_mul(10_000)
+                .checked_div(elected_backing)
+                .unwrap_or(0) as u32,
+            nominator_count: nominators.len() as u32,
+            validators,
+        })
+    }
+
+    /// Cluster nominators by the Jaccard similarity of their target sets
+    ///
+    /// Nominators whose target sets have a similarity of at least
+    /// `similarity_threshold_percent` are linked, and linked groups of at least
+    /// `min_cluster_size` nominators are reported. Only nominators sharing a
+    /// target are compared, so cost grows with target popularity rather than
+    /// with the square of the nominator count.
+    pub fn cluster_nominators(
+        &self,
+        data: &ElectionData,
+        similarity_threshold_percent: u8,
+        min_cluster_size: usize,
+    ) -> NominatorClusters {
+        let target_sets: Vec<HashSet<&String>> = data
+            .nominators
+            .iter()
+            .map(|n| n.targets.iter().collect())
+            .collect();
+
+        let mut nominators_by_target: HashMap<&String, Vec<usize>> = HashMap::new();
+        for (index, targets) in target_sets.iter().enumerate() {
+            for target in targets {
+                nominators_by_target.entry(*target).or_default().push(index);
+            }
+        }
+
+        let mut parents: Vec<usize> = (0..target_sets.len()).collect();
+        for (index, targets) in target_sets.iter().enumerate() {
+            // Count shared targets with every later nominator that shares at least one
+            let mut shared: HashMap<usize, usize> = HashMap::new();
+            for target in targets {
+                for &other in &nominators_by_target[*target] {
+                    if other > index {
+                        *shared.entry(other).or_insert(0) += 1;
+                    }
+                }
+            }
+
+            for (other, intersection) in shared {
+                let union = targets.len() + target_sets[other].len() - intersection;
+                if intersection * 100 >= union * similarity_threshold_percent as usize {
+                    let (a, b) = (find_root(&mut parents, index), find_root(&mut parents, other));
+                    if a != b {
+                        parents[b] = a;
+                    }
+                }
+            }
+        }
+
+        let mut members_by_root: HashMap<usize, Vec<usize>> = HashMap::new();
+        for index in 0..target_sets.len() {
+            let root = find_root(&mut parents, index);
+            members_by_root.entry(root).or_default().push(index);
+        }
+
+        let total_nominator_stake = data.statistics().total_nominator_stake;
+        let mut clusters: Vec<NominatorCluster> = members_by_root
+            .into_values()
+            .filter(|members| members.len() >= min_cluster_size.max(2))
+            .map(|members| {
+                let mut shared_targets: BTreeSet<&String> = target_sets[members[0]].iter().copied().collect();
+                for &member in &members[1..] {
+                    shared_targets.retain(|t| target_sets[member].contains(*t));
+                }
+                let total_stake: u128 = members.iter().map(|&m| data.nominators[m].stake).sum();
+                let stake_share_bps = total_stake
+                    .saturating_mul(10_000)
+                    .checked_div(total_nominator_stake)
+                    .unwrap_or(0) as u32;
+
+                let mut nominator_ids: Vec<String> = members
+                    .iter()
+                    .map(|&m| data.nominators[m].account_id.clone())
+                    .collect();
+                nominator_ids.sort();
+
+                NominatorCluster {
+                    nominator_ids,
+                    shared_targets: shared_targets.into_iter().cloned().collect(),
+                    total_stake,
+                    stake_share_bps,
+                }
+            })
+            .collect();
+        clusters.sort_by(|a, b| {
+            b.total_stake
+                .cmp(&a.total_stake)
+                .then_with(|| a.nominator_ids.cmp(&b.nominator_ids))
+        });
+
+        NominatorClusters {
+            similarity_threshold_percent,
+            min_cluster_size,
+            clusters,
+        }
+    }
+
+    /// Group elected validators by operator (parent identity)
+    ///
+    /// Backing is taken from the elected set. The Nakamoto coefficient counts
+    /// the fewest operators that together hold more than one third of it, the
+    /// share needed to stall finality.
+    pub fn group_by_operator(&self, result: &ElectionResult, data: &ElectionData) -> OperatorGroups {
+        let mut operators: HashMap<&str, OperatorSummary> = HashMap::new();
+        for candidate in &data.candidates {
+            let operator_id = candidate.operator_id();
+            let summary = operators.entry(operator_id).or_insert_with(|| OperatorSummary {
+                operator_id: operator_id.to_string(),
+                display_name: None,
+                candidate_count: 0,
+                elected_validator_ids: Vec::new(),
+                total_backing: 0,
+                stake_share_bps: 0,
+            });
+            summary.candidate_count += 1;
+            if summary.display_name.is_none() {
+                summary.display_name = candidate
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.identity_display.clone());
+            }
+        }
+
+        let operator_by_validator: HashMap<&String, &str> = data
+            .candidates
+            .iter()
+            .map(|c| (&c.account_id, c.operator_id()))
+            .collect();
+        for validator in &result.selected_validators {
+            let operator_id = operator_by_validator
+                .get(&validator.account_id)
+                .copied()
+                .unwrap_or(&validator.account_id);
+            let summary = operators.entry(operator_id).or_insert_with(|| OperatorSummary {
+                operator_id: operator_id.to_string(),
+                display_name: None,
+                candidate_count: 1,
+                elected_validator_ids: Vec::new(),
+                total_backing: 0,
+                stake_share_bps: 0,
+            });
+            summary.elected_validator_ids.push(validator.account_id.clone());
+            summary.total_backing = summary.total_backing.saturating_add(validator.total_backing_stake);
+        }
+
+        let elected_backing: u128 = result
+            .selected_validators
+            .iter()
+            .map(|v| v.total_backing_stake)
+            .fold(0u128, |acc, b| acc.saturating_add(b));
+
+        let mut operators: Vec<OperatorSummary> = operators
+            .into_values()
+            .filter(|o| !o.elected_validator_ids.is_empty())
+            .map(|mut o| {
+                o.stake_share_bps = o
+                    .total_backing
+                    .saturating_mul(10_000)
+                    .checked_div(elected_backing)
+                    .unwrap_or(0) as u32;
+                o
+            })
+            .collect();
+        operators.sort_by(|a, b| {
+            b.total_backing
+                .cmp(&a.total_backing)
+                .then_with(|| a.operator_id.cmp(&b.operator_id))
+        });
+
+        let mut validator_backings: Vec<u128> = result
+            .selected_validators
+            .iter()
+            .map(|v| v.total_backing_stake)
+            .collect();
+        validator_backings.sort_unstable_by(|a, b| b.cmp(a));
+
+        OperatorGroups {
+            elected_operator_count: operators.len(),
+            nakamoto_coefficient: nakamoto_coefficient(operators.iter().map(|o| o.total_backing), elected_backing),
+            validator_nakamoto_coefficient: nakamoto_coefficient(validator_backings.into_iter(), elected_backing),
+            operators,
+        }
+    }
+
+    /// Measure country, city and hosting provider concentration of the elected set
+    ///
+    /// Returns `None` when no candidate carries any of these attributes.
+    pub fn generate_diversity_analysis(
+        &self,
+        result: &ElectionResult,
+        data: &ElectionData,
+    ) -> Option<DiversityAnalysis> {
+        let metadata_by_validator: HashMap<&String, &CandidateMetadata> = data
+            .candidates
+            .iter()
+            .filter_map(|c| c.metadata.as_ref().map(|m| (&c.account_id, m)))
+            .filter(|(_, m)| m.country.is_some() || m.city.is_some() || m.hosting_provider.is_some())
+            .collect();
+        if metadata_by_validator.is_empty() {
+            return None;
+        }
+
+        let concentration = |attribute: fn(&CandidateMetadata) -> Option<&String>| {
+            attribute_concentration(result, |id| {
+                metadata_by_validator.get(id).and_then(|m| attribute(m))
+            })
+        };
+
+        Some(DiversityAnalysis {
+            by_country: concentration(|m| m.country.as_ref()),
+            by_city: concentration(|m| m.city.as_ref()),
+            by_hosting_provider: concentration(|m| m.hosting_provider.as_ref()),
+        })
+    }
+
+    /// Estimate how far validators around the cutoff are from changing places
+    ///
+    /// Examines the `window` weakest elected validators and the `window`
+    /// strongest candidates that were not elected. Margins come from scores in
+    /// a single pass over the votes rather than from re-running the election;
+    /// see [`StakeSensitivity`] for what they mean.
+    pub fn stake_sensitivity(&self, result: &ElectionResult, data: &ElectionData, window: usize) -> StakeSensitivity {
+        let elected: HashSet<&str> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+
+        // A target listed twice still only receives the nominator's stake once
+        let statistics = data.statistics();
+        let mut runners_up: Vec<(&str, u128)> = data
+            .candidates
+            .iter()
+            .filter(|c| !elected.contains(c.account_id.as_str()))
+            .map(|c| (c.account_id.as_str(), statistics.approval_of(&c.account_id)))
+            .collect();
+        runners_up.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        let runner_up_approval = runners_up.first().map(|(_, stake)| *stake);
+
+        let mut weakest: Vec<&crate::models::election_result::SelectedValidator> =
+            result.selected_validators.iter().collect();
+        weakest.sort_by(|a, b| {
+            a.total_backing_stake
+                .cmp(&b.total_backing_stake)
+                .then_with(|| a.account_id.cmp(&b.account_id))
+        });
+        let cutoff_backing = weakest.first().map(|v| v.total_backing_stake);
+
+        let elected = weakest
+            .iter()
+            .take(window)
+            .map(|v| ElectedMargin {
+                validator_id: v.account_id.clone(),
+                backing: v.total_backing_stake,
+                margin: v.total_backing_stake.saturating_sub(runner_up_approval.unwrap_or(0)),
+            })
+            .collect();
+        let near_misses = runners_up
+            .iter()
+            .take(window)
+            .map(|(id, approval_stake)| {
+                // A bond without a self-vote in the data becomes one on-chain
+                let self_stake = match data.get_nominator(id) {
+                    Some(_) => 0,
+                    None => data.get_candidate(id).map_or(0, |candidate| candidate.stake),
+                };
+                NearMiss {
+                    candidate_id: id.to_string(),
+                    approval_stake: *approval_stake,
+                    shortfall: cutoff_backing
+                        .map(|cutoff| {
+                            cutoff
+                                .saturating_add(1)
+                                .saturating_sub(*approval_stake)
+                                .saturating_sub(self_stake)
+                        })
+                        .unwrap_or(0),
+                }
+            })
+            .collect();
+
+        StakeSensitivity {
+            window,
+            cutoff_backing,
+            runner_up_approval,
+            elected,
+            near_misses,
+        }
+    }
+
+    /// Check whether a result satisfies proportional justified representation
+    ///
+    /// Uses the standard PJR threshold (total voter stake divided by the
+    /// number of seats) unless `threshold` is given. The verdict and counter
+    /// example come from `sp_npos_elections::t_pjr_check`; the violating
+    /// groups are every unelected candidate whose pre-score reaches the
+    /// threshold, computed the same way. Local PJR is checked against the
+    /// same threshold; see [`PjrCheck`].
+    pub fn check_pjr(&self, result: &ElectionResult, data: &ElectionData, threshold: Option<u128>) -> PjrCheck {
+        let mut supports: Vec<(String, sp_npos_elections::Support<String>)> = result
+            .selected_validators
+            .iter()
+            .map(|v| {
+                let support = sp_npos_elections::Support { total: v.total_backing_stake, voters: Vec::new() };
+                (v.account_id.clone(), support)
+            })
+            .collect();
+        let support_index: HashMap<&str, usize> = result
+            .selected_validators
+            .iter()
+            .enumerate()
+            .map(|(index, v)| (v.account_id.as_str(), index))
+            .collect();
+        for alloc in &result.stake_distribution {
+            if let Some(&index) = support_index.get(alloc.validator_id.as_str()) {
+                supports[index].1.voters.push((alloc.nominator_id.clone(), alloc.amount));
+            }
+        }
+
+        let voters: Vec<(String, u64, Vec<String>)> = data
+            .nominators
+            .iter()
+            .map(|n| (n.account_id.clone(), n.stake.min(u64::MAX as u128) as u64, n.targets.clone()))
+            .collect();
+        let standard = sp_npos_elections::standard_threshold(
+            supports.len(),
+            voters.iter().map(|(_, stake, _)| *stake as u128),
+        );
+        let threshold_value = threshold.unwrap_or(standard);
+        let candidates: Vec<String> = data.candidates.iter().map(|c| c.account_id.clone()).collect();
+        let counter_example = sp_npos_elections::t_pjr_check(&supports, candidates, voters, threshold_value).err();
+
+        // Slack of each voter: stake it could move without pushing any of its
+        // elected candidates below the threshold
+        let backing: HashMap<&str, u128> = result
+            .selected_validators
+            .iter()
+            .map(|v| (v.account_id.as_str(), v.total_backing_stake))
+            .collect();
+        let mut committed: HashMap<&str, u128> = HashMap::new();
+        for alloc in &result.stake_distribution {
+            let Some(&total) = backing.get(alloc.validator_id.as_str()) else {
+                continue;
+            };
+            let kept = Perbill::one().min(Perbill::from_rational(threshold_value, total.max(1))) * alloc.amount;
+            *committed.entry(alloc.nominator_id.as_str()).or_insert(0) += kept;
+        }
+
+        let mut pre_scores: HashMap<&str, (u128, Vec<String>)> = data
+            .candidates
+            .iter()
+            .filter(|c| !backing.contains_key(c.account_id.as_str()))
+            .map(|c| (c.account_id.as_str(), (0, Vec::new())))
+            .collect();
+        for nominator in &data.nominators {
+            let stake = nominator.stake.min(u64::MAX as u128);
+            let slack = stake.saturating_sub(committed.get(nominator.account_id.as_str()).copied().unwrap_or(0));
+            if slack == 0 {
+                continue;
+            }
+            let targets: BTreeSet<&str> = nominator.targets.iter().map(String::as_str).collect();
+            for target in targets {
+                if let Some((pre_score, voter_ids)) = pre_scores.get_mut(target) {
+                    *pre_score = pre_score.saturating_add(slack);
+                    voter_ids.push(nominator.account_id.clone());
+                }
+            }
+        }
+
+        let mut violations: Vec<PjrViolation> = pre_scores
+            .into_iter()
+            .filter(|(_, (pre_score, _))| *pre_score >= threshold_value)
+            .map(|(id, (pre_score, voter_ids))| PjrViolation {
+                candidate_id: id.to_string(),
+                pre_score,
+                voter_ids,
+            })
+            .collect();
+        violations.sort_by(|a, b| b.pre_score.cmp(&a.pre_score).then_with(|| a.candidate_id.cmp(&b.candidate_id)));
+
+        let local_violations = local_pjr_violations(result, data, threshold_value);
+
+        PjrCheck {
+            threshold: threshold_value,
+            standard_threshold: threshold_value == standard,
+            satisfied: counter_example.is_none(),
+            counter_example,
+            violations,
+            locally_satisfied: local_violations.is_empty(),
+            local_violations,
+        }
+    }
+
+    /// Compare a result's smallest backing against maximin support
+    ///
+    /// Rebalances the result's elected set optimally and runs the
+    /// experimental `mms` algorithm for the same number of seats, unless the
+    /// result came from it. Returns `None` for an empty result or when the
+    /// `mms` run fails.
+    pub fn maximin_gap(&self, result: &ElectionResult, data: &ElectionData) -> Option<MaximinGap> {
+        let seats = result.selected_validators.len();
+        if seats == 0 {
+            return None;
+        }
+        let minimal_support = result.selected_validators.iter().map(|v| v.total_backing_stake).min()?;
+
+        let prepared = PreparedElectionData::new(data);
+        let candidate_index: HashMap<&str, u32> = data
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(index, c)| (c.account_id.as_str(), index as u32))
+            .collect();
+        let winners: Vec<u32> = result
+            .selected_validators
+            .iter()
+            .filter_map(|v| candidate_index.get(v.account_id.as_str()).copied())
+            .collect();
+        let balanced_minimal_support = if winners.len() == seats {
+            balance_optimally(&prepared, &winners).minimal_support
+        } else {
+            0
+        };
+
+        let mms_minimal_support = if result.algorithm_used == AlgorithmType::Mms {
+            minimal_support
+        } else {
+            let config = ElectionConfiguration::builder()
+                .algorithm(AlgorithmType::Mms)
+                .active_set_size(seats as u32)
+                .build()
+                .ok()?;
+            let mms = Mms.execute_prepared(&prepared, &config).ok()?;
+            mms.selected_validators.iter().map(|v| v.total_backing_stake).min()?
+        };
+
+        let connected_stake: u128 = (0..prepared.voter_count()).map(|v| prepared.voter_stake(v) as u128).sum();
+        let upper_bound = (connected_stake / seats as u128).min(mms_minimal_support.saturating_mul(2));
+        let best = minimal_support.max(balanced_minimal_support).max(mms_minimal_support);
+
+        Some(MaximinGap {
+            minimal_support,
+            balanced_minimal_support,
+            mms_minimal_support,
+            upper_bound,
+            gap_bps: ((best - minimal_support).saturating_mul(10_000)).checked_div(best).unwrap_or(0) as u32,
+        })
+    }
+
+    /// Summarize an election result in a short textual narrative
+    ///
+    /// Describes the elected set and its most over- and under-backed
+    /// validators. With a `baseline` result it also covers validators entering
+    /// and leaving the set, the largest backing movements and the change in
+    /// each score component.
+    pub fn summary(&self, result: &ElectionResult, baseline: Option<&ElectionResult>) -> String {
+        let mut lines = Vec::new();
+        let validators = &result.selected_validators;
+        if validators.is_empty() {
+            lines.push(format!("{} elected no validators.", result.algorithm_used));
+            return lines.join("\n");
+        }
+
+        let backing_total: u128 = validators.iter().map(|v| v.total_backing_stake).sum();
+        let average = backing_total / validators.len() as u128;
+        let score = result.score();
+        let maximum = validators.iter().map(|v| v.total_backing_stake).max().unwrap_or(0);
+        lines.push(format!(
+            "{} elected {} validators backed by {} in total; backing ranges from {} to {} (average {}).",
+            result.algorithm_used,
+            validators.len(),
+            backing_total,
+            score.minimal_stake,
+            maximum,
+            average
+        ));
+
+        let mut by_backing: Vec<&crate::models::election_result::SelectedValidator> = validators.iter().collect();
+        by_backing.sort_by(|a, b| {
+            b.total_backing_stake
+                .cmp(&a.total_backing_stake)
+                .then_with(|| a.account_id.cmp(&b.account_id))
+        });
+        let relative = |v: &crate::models::election_result::SelectedValidator| {
+            let percent = v.total_backing_stake.saturating_mul(100).checked_div(average).unwrap_or(0);
+            format!("{} ({}, {}% of average)", v.account_id, v.total_backing_stake, percent)
+        };
+        let top = SUMMARY_TOP_COUNT.min(by_backing.len() / 2).max(1);
+        lines.push(format!(
+            "Most over-backed: {}.",
+            by_backing.iter().take(top).map(|v| relative(v)).collect::<Vec<_>>().join(", ")
+        ));
+        if by_backing.len() > 1 {
+            lines.push(format!(
+                "Most under-backed: {}.",
+                by_backing.iter().rev().take(top).map(|v| relative(v)).collect::<Vec<_>>().join(", ")
+            ));
+        }
+
+        let Some(baseline) = baseline else {
+            return lines.join("\n");
+        };
+
+        let impact = OverrideImpact::between(baseline, result);
+        lines.push(format!(
+            "Compared to the baseline: {} entered and {} left the elected set.",
+            impact.validators_added.len(),
+            impact.validators_removed.len()
+        ));
+
+        let baseline_backing: HashMap<&str, u128> = baseline
+            .selected_validators
+            .iter()
+            .map(|v| (v.account_id.as_str(), v.total_backing_stake))
+            .collect();
+        let current_backing: HashMap<&str, u128> =
+            validators.iter().map(|v| (v.account_id.as_str(), v.total_backing_stake)).collect();
+
+        if !impact.validators_added.is_empty() {
+            let mut entrants: Vec<(&str, u128)> = impact
+                .validators_added
+                .iter()
+                .map(|id| (id.as_str(), current_backing.get(id.as_str()).copied().unwrap_or(0)))
+                .collect();
+            entrants.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            lines.push(format!(
+                "Notable new entrants: {}.",
+                entrants
+                    .iter()
+                    .take(SUMMARY_TOP_COUNT)
+                    .map(|(id, backing)| format!("{} (backing {})", id, backing))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        let accounts: BTreeSet<&str> = baseline_backing.keys().chain(current_backing.keys()).copied().collect();
+        let mut movements: Vec<(&str, u128, u128)> = accounts
+            .into_iter()
+            .map(|id| {
+                let before = baseline_backing.get(id).copied().unwrap_or(0);
+                let after = current_backing.get(id).copied().unwrap_or(0);
+                (id, before, after)
+            })
+            .filter(|(_, before, after)| before != after)
+            .collect();
+        movements.sort_by(|a, b| b.1.abs_diff(b.2).cmp(&a.1.abs_diff(a.2)).then_with(|| a.0.cmp(b.0)));
+        if movements.is_empty() {
+            lines.push("No validator's backing changed.".to_string());
+        } else {
+            let described: Vec<String> = movements
+                .iter()
+                .take(SUMMARY_TOP_COUNT)
+                .map(|(id, before, after)| {
+                    let change = if after >= before {
+                        format!("+{}", after - before)
+                    } else {
+                        format!("-{}", before - after)
+                    };
+                    if !current_backing.contains_key(id) {
+                        format!("{} {} (left the set)", id, change)
+                    } else if !baseline_backing.contains_key(id) {
+                        format!("{} {} (entered the set)", id, change)
+                    } else {
+                        format!("{} {} ({} -> {})", id, change, before, after)
+                    }
+                })
+                .collect();
+            lines.push(format!("Largest stake movements: {}.", described.join(", ")));
+        }
+
+        let delta = &impact.score_delta;
+        let verdict = if impact.overridden_score.is_better_than(&impact.baseline_score) {
+            "better than"
+        } else if impact.baseline_score.is_better_than(&impact.overridden_score) {
+            "worse than"
+        } else {
+            "equal to"
+        };
+        lines.push(format!(
+            "Minimal stake {} ({:+}), total stake {} ({:+}), sum of squared stakes {} ({:+}); the score is {} the baseline.",
+            impact.overridden_score.minimal_stake,
+            delta.minimal_stake,
+            impact.overridden_score.sum_stake,
+            delta.sum_stake,
+            impact.overridden_score.sum_stake_squared,
+            delta.sum_stake_squared,
+            verdict
+        ));
+
+        lines.join("\n")
+    }
+
     /// Generate algorithm-specific insights
     fn generate_algorithm_insights(
         &self,
@@ -310,6 +1276,14 @@ impl DiagnosticsGenerator {
                     ),
                 );
             }
+            AlgorithmType::Mms => {
+                insights.insert(
+                    "description".to_string(),
+                    serde_json::Value::String(
+                        "MMS approximates maximin support: PhragMMS picks the set and max-flow balancing maximizes its smallest backing".to_string(),
+                    ),
+                );
+            }
         }
 
         // Distribution statistics
@@ -349,3 +1323,145 @@ impl Default for DiagnosticsGenerator {
         Self::new()
     }
 }
+
+/// Group elected validators by one attribute and measure concentration
+fn attribute_concentration<'a>(
+    result: &'a ElectionResult,
+    attribute: impl Fn(&String) -> Option<&'a String>,
+) -> AttributeConcentration {
+    let mut groups: HashMap<&String, (usize, u128)> = HashMap::new();
+    let mut unknown_count = 0;
+    for validator in &result.selected_validators {
+        match attribute(&validator.account_id) {
+            Some(value) => {
+                let group = groups.entry(value).or_insert((0, 0));
+                group.0 += 1;
+                group.1 = group.1.saturating_add(validator.total_backing_stake);
+            }
+            None => unknown_count += 1,
+        }
+    }
+
+    let known_count = result.selected_validators.len() - unknown_count;
+    let elected_backing: u128 = result
+        .selected_validators
+        .iter()
+        .map(|v| v.total_backing_stake)
+        .fold(0u128, |acc, b| acc.saturating_add(b));
+
+    let mut groups: Vec<AttributeGroup> = groups
+        .into_iter()
+        .map(|(value, (count, backing))| AttributeGroup {
+            value: value.clone(),
+            elected_count: count,
+            validator_share_bps: (count * 10_000).checked_div(known_count).unwrap_or(0) as u32,
+            backing_share_bps: backing
+                .saturating_mul(10_000)
+                .checked_div(elected_backing)
+                .unwrap_or(0) as u32,
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.elected_count
+            .cmp(&a.elected_count)
+            .then_with(|| a.value.cmp(&b.value))
+    });
+
+    let squares: usize = groups.iter().map(|g| g.elected_count * g.elected_count).sum();
+    let herfindahl_index = (squares * 10_000)
+        .checked_div(known_count * known_count)
+        .unwrap_or(0) as u32;
+
+    AttributeConcentration {
+        largest_share_bps: groups.first().map(|g| g.validator_share_bps).unwrap_or(0),
+        groups,
+        unknown_count,
+        herfindahl_index,
+    }
+}
+
+/// Fewest entries (largest first) whose sum exceeds one third of `total`
+pub(crate) fn nakamoto_coefficient(backings_desc: impl Iterator<Item = u128>, total: u128) -> usize {
+    let mut accumulated: u128 = 0;
+    let mut count = 0;
+    for backing in backings_desc {
+        if accumulated.saturating_mul(3) > total {
+            break;
+        }
+        accumulated = accumulated.saturating_add(backing);
+        count += 1;
+    }
+    count
+}
+
+/// Groups of voters sharing a candidate that got fewer seats than local PJR owes them
+fn local_pjr_violations(result: &ElectionResult, data: &ElectionData, threshold: u128) -> Vec<LocalPjrViolation> {
+    let elected: HashSet<&str> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+    let mut voters_of: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, nominator) in data.nominators.iter().enumerate() {
+        if nominator.stake == 0 {
+            continue;
+        }
+        let targets: BTreeSet<&str> = nominator.targets.iter().map(String::as_str).collect();
+        for target in targets {
+            voters_of.entry(target).or_default().push(index);
+        }
+    }
+
+    let mut violations = Vec::new();
+    for candidate in &data.candidates {
+        let Some(voters) = voters_of.get(candidate.account_id.as_str()) else {
+            continue;
+        };
+        let group_stake = voters
+            .iter()
+            .fold(0u128, |total, &index| total.saturating_add(data.nominators[index].stake.min(u64::MAX as u128)));
+        let mut common: BTreeSet<&str> = data.nominators[voters[0]].targets.iter().map(String::as_str).collect();
+        for &index in &voters[1..] {
+            let targets: HashSet<&str> = data.nominators[index].targets.iter().map(String::as_str).collect();
+            common.retain(|target| targets.contains(target));
+        }
+        let owed_seats = match group_stake.checked_div(threshold) {
+            Some(seats) => common.len().min(seats.min(usize::MAX as u128) as usize),
+            None => common.len(),
+        };
+        if owed_seats == 0 {
+            continue;
+        }
+        let backed: HashSet<&str> = voters
+            .iter()
+            .flat_map(|&index| data.nominators[index].targets.iter().map(String::as_str))
+            .filter(|target| elected.contains(target))
+            .collect();
+        if backed.len() < owed_seats {
+            violations.push(LocalPjrViolation {
+                candidate_id: candidate.account_id.clone(),
+                group_stake,
+                owed_seats,
+                elected_seats: backed.len(),
+                voter_ids: voters.iter().map(|&index| data.nominators[index].account_id.clone()).collect(),
+            });
+        }
+    }
+    violations.sort_by(|a, b| {
+        (b.owed_seats - b.elected_seats)
+            .cmp(&(a.owed_seats - a.elected_seats))
+            .then_with(|| a.candidate_id.cmp(&b.candidate_id))
+    });
+    violations
+}
+
+/// Find the representative of `index` in a union-find forest, compressing the path
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    let mut current = index;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+    root
+}
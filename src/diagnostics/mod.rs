@@ -4,6 +4,11 @@ pub mod explainer;
 pub mod models;
 
 pub use explainer::DiagnosticsGenerator;
-pub use models::{Diagnostics, ValidatorExplanation, StakeAnalysis};
+pub use models::{
+    AttributeConcentration, AttributeGroup, Diagnostics, DiversityAnalysis, ElectedMargin, Finding,
+    FindingCode, LocalPjrViolation, MaximinGap, MinimumActiveStake, NearMiss, NominatorCluster,
+    NominatorClusters, OperatorGroups, OperatorSummary, OversubscribedValidator, PjrCheck, PjrViolation, Severity,
+    StakeAnalysis, StakeSensitivity, UnrewardedStake, ValidatorExplanation, ValidatorMinimumStake,
+};
 
 
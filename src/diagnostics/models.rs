@@ -12,11 +12,135 @@ pub struct Diagnostics {
     /// Algorithm-specific insights
     #[serde(skip_serializing_if = "Option::is_none")]
     pub algorithm_insights: Option<serde_json::Value>,
-    /// Warnings or notable conditions
+    /// Minimum nominator stake that was allocated to elected validators
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_active_stake: Option<MinimumActiveStake>,
+    /// Groups of nominators with strongly overlapping target sets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nominator_clusters: Option<NominatorClusters>,
+    /// Elected set grouped by operator (parent identity)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_groups: Option<OperatorGroups>,
+    /// Country, city and hosting provider concentration of the elected set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diversity: Option<DiversityAnalysis>,
+    /// How far validators around the cutoff are from changing places
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stake_sensitivity: Option<StakeSensitivity>,
+    /// Proportional justified representation check of the elected set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pjr: Option<PjrCheck>,
+    /// How far the smallest backing is from the best known maximin support
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximin_gap: Option<MaximinGap>,
+    /// Stake backing validators beyond their rewarded nominators
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unrewarded_stake: Option<UnrewardedStake>,
+    /// Typed findings with stable codes, most severe first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<Finding>,
+    /// Messages of findings with at least [`Severity::Warning`]
+    ///
+    /// Kept for consumers of the free-form output; filter `findings` by code instead.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
 }
 
+/// A notable condition found in an election result
+///
+/// Codes are stable across releases so tooling can filter and alert on them;
+/// messages are for humans and may change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Stable identifier of the condition
+    pub code: FindingCode,
+    /// How much attention the condition needs
+    pub severity: Severity,
+    /// Account, operator or attribute value the finding is about, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Human-readable description
+    pub message: String,
+    /// Structured values behind the finding
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub data: serde_json::Value,
+}
+
+/// Severity of a finding, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth knowing, no action needed
+    Info,
+    /// Deserves a closer look
+    Warning,
+    /// The result is likely not meaningful or unsafe
+    Critical,
+}
+
+/// Stable finding codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FindingCode {
+    /// No stake backs the elected set
+    TotalStakeZero,
+    /// An elected validator has no backing stake
+    ValidatorZeroBacking,
+    /// One operator holds at least a third of the elected backing
+    StakeConcentrationHigh,
+    /// Half or more of the elected validators share a country, city or hosting provider
+    AttributeConcentrationHigh,
+    /// A group of nominators votes for nearly the same targets
+    NominatorClusterDetected,
+    /// Part of a nominator's stake does not back any elected validator
+    VoterTrimmed,
+    /// Nominator clustering was skipped because the snapshot is too large
+    ClusteringSkipped,
+    /// The elected set does not satisfy proportional justified representation
+    PjrViolated,
+    /// The voters of one candidate are owed more seats than they got
+    LocalPjrViolated,
+    /// Balancing hit its iteration cap before converging
+    BalancingIterationCap,
+    /// A validator has more nominators than are rewarded
+    ValidatorOversubscribed,
+}
+
+impl FindingCode {
+    /// Code as it appears in serialized output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FindingCode::TotalStakeZero => "TOTAL_STAKE_ZERO",
+            FindingCode::ValidatorZeroBacking => "VALIDATOR_ZERO_BACKING",
+            FindingCode::StakeConcentrationHigh => "STAKE_CONCENTRATION_HIGH",
+            FindingCode::AttributeConcentrationHigh => "ATTRIBUTE_CONCENTRATION_HIGH",
+            FindingCode::NominatorClusterDetected => "NOMINATOR_CLUSTER_DETECTED",
+            FindingCode::VoterTrimmed => "VOTER_TRIMMED",
+            FindingCode::ClusteringSkipped => "CLUSTERING_SKIPPED",
+            FindingCode::PjrViolated => "PJR_VIOLATED",
+            FindingCode::LocalPjrViolated => "LOCAL_PJR_VIOLATED",
+            FindingCode::BalancingIterationCap => "BALANCING_ITERATION_CAP",
+            FindingCode::ValidatorOversubscribed => "VALIDATOR_OVERSUBSCRIBED",
+        }
+    }
+}
+
+impl std::fmt::Display for FindingCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
 /// Explanation for why a validator was selected or not selected
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidatorExplanation {
@@ -40,6 +164,300 @@ pub struct StakeAnalysis {
     pub average_stake_per_validator: u128,
 }
 
+/// Effective minimum active bond across the elected set
+///
+/// A nominator whose allocation to a validator falls below that validator's
+/// minimum would be the first to drop out of its backing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MinimumActiveStake {
+    /// Smallest allocation across all elected validators (None if nothing was allocated)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_minimum: Option<u128>,
+    /// Per-validator minimum allocations, in active set order
+    pub per_validator: Vec<ValidatorMinimumStake>,
+}
+
+/// Minimum allocated nominator stake for a single elected validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorMinimumStake {
+    /// Account ID of the elected validator
+    pub validator_id: String,
+    /// Smallest nominator allocation backing this validator (None if no nominators)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_allocation: Option<u128>,
+    /// Nominator holding the smallest allocation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nominator_id: Option<String>,
+}
+
+/// Nominators clustered by overlapping target sets
+///
+/// Two nominators are linked when the Jaccard similarity of their target sets
+/// is at least the threshold; clusters are the connected groups of links.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NominatorClusters {
+    /// Minimum Jaccard similarity (percent) for two nominators to be linked
+    pub similarity_threshold_percent: u8,
+    /// Smallest cluster size reported
+    pub min_cluster_size: usize,
+    /// Clusters found, largest total stake first
+    pub clusters: Vec<NominatorCluster>,
+}
+
+/// A group of nominators with correlated nominations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NominatorCluster {
+    /// Members of the cluster
+    pub nominator_ids: Vec<String>,
+    /// Targets nominated by every member
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shared_targets: Vec<String>,
+    /// Combined stake of the members
+    pub total_stake: u128,
+    /// Share of all nominator stake held by the cluster, in basis points
+    pub stake_share_bps: u32,
+}
+
+/// Elected validators grouped by operator
+///
+/// Validators sharing a parent identity are treated as one operator; validators
+/// without a parent identity are their own operator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorGroups {
+    /// Operators with at least one elected validator, largest backing first
+    pub operators: Vec<OperatorSummary>,
+    /// Number of distinct operators in the elected set
+    pub elected_operator_count: usize,
+    /// Fewest operators whose combined backing exceeds one third of the elected backing
+    pub nakamoto_coefficient: usize,
+    /// Fewest individual validators whose combined backing exceeds one third of the elected backing
+    pub validator_nakamoto_coefficient: usize,
+}
+
+/// Elected validators and backing of a single operator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorSummary {
+    /// Parent identity account, or the validator's own account
+    pub operator_id: String,
+    /// Identity display name, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Number of candidates run by the operator
+    pub candidate_count: usize,
+    /// Elected validators run by the operator
+    pub elected_validator_ids: Vec<String>,
+    /// Combined backing of the operator's elected validators
+    pub total_backing: u128,
+    /// Share of the elected backing held by the operator, in basis points
+    pub stake_share_bps: u32,
+}
+
+/// Geographic and provider concentration of the elected set
+///
+/// Only produced when candidates carry external attributes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiversityAnalysis {
+    /// Concentration by country
+    pub by_country: AttributeConcentration,
+    /// Concentration by city
+    pub by_city: AttributeConcentration,
+    /// Concentration by hosting provider
+    pub by_hosting_provider: AttributeConcentration,
+}
+
+/// Concentration of elected validators across the values of one attribute
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttributeConcentration {
+    /// Elected validators per attribute value, most validators first
+    pub groups: Vec<AttributeGroup>,
+    /// Elected validators without this attribute
+    pub unknown_count: usize,
+    /// Herfindahl-Hirschman index of validator shares (0-10000; higher is more concentrated)
+    pub herfindahl_index: u32,
+    /// Largest share of known validators held by a single value, in basis points
+    pub largest_share_bps: u32,
+}
+
+/// Elected validators sharing one attribute value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttributeGroup {
+    /// Attribute value (e.g. country code or provider name)
+    pub value: String,
+    /// Number of elected validators with this value
+    pub elected_count: usize,
+    /// Share of elected validators with a known value, in basis points
+    pub validator_share_bps: u32,
+    /// Share of the elected backing, in basis points
+    pub backing_share_bps: u32,
+}
+
+/// Backing margins of validators around the election cutoff
+///
+/// Margins are estimated from scores instead of re-running the election: an
+/// elected validator's backing is compared against the approval stake of the
+/// strongest runner-up, and a runner-up's approval stake against the backing
+/// of the weakest elected validator. Approval stake (all stake voting for a
+/// candidate) bounds the backing a candidate can get, so the estimates are
+/// conservative for elected validators. A runner-up's bonded self-stake is
+/// counted towards its shortfall unless the data already carries its
+/// self-vote, which is then part of the approval stake.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeSensitivity {
+    /// Number of validators examined on each side of the cutoff
+    pub window: usize,
+    /// Backing of the weakest elected validator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cutoff_backing: Option<u128>,
+    /// Approval stake of the strongest candidate that was not elected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runner_up_approval: Option<u128>,
+    /// Weakest elected validators, weakest first
+    pub elected: Vec<ElectedMargin>,
+    /// Strongest candidates that were not elected, strongest first
+    pub near_misses: Vec<NearMiss>,
+}
+
+/// Backing an elected validator could lose before a runner-up could overtake it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectedMargin {
+    /// Account ID of the elected validator
+    pub validator_id: String,
+    /// Backing assigned by the election
+    pub backing: u128,
+    /// Backing above the strongest runner-up's approval stake (0 when none is above)
+    pub margin: u128,
+}
+
+/// Backing a candidate that was not elected would need to pass the cutoff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NearMiss {
+    /// Account ID of the candidate
+    pub candidate_id: String,
+    /// Combined stake of all nominators voting for the candidate
+    pub approval_stake: u128,
+    /// Additional backing needed to exceed the cutoff backing, after the
+    /// candidate's own bonded stake
+    ///
+    /// 0 means the approval stake already exceeds the cutoff and the candidate
+    /// lost on how its voters' stake was shared.
+    pub shortfall: u128,
+}
+
+/// Result of a proportional justified representation (PJR) check
+///
+/// A result is t-PJR when no group of voters could back an unelected
+/// candidate with at least `t` without pushing one of their elected
+/// candidates below `t`; standard PJR uses `t = total stake / seats`. The
+/// check follows `sp_npos_elections::pjr`, which tests the pre-score of every
+/// unelected candidate against the threshold.
+///
+/// Local PJR checks the groups PJR protects that form around one candidate:
+/// the voters of a candidate, with combined stake `s` and `k` candidates they
+/// all vote for, are owed `min(s / t, k)` seats among the candidates any of
+/// them votes for. A result that is PJR is also locally PJR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PjrCheck {
+    /// Threshold `t` the result was checked against
+    pub threshold: u128,
+    /// Whether `threshold` is the standard PJR threshold
+    pub standard_threshold: bool,
+    /// Whether the result satisfies t-PJR
+    pub satisfied: bool,
+    /// Unelected candidate with the highest pre-score at or above the threshold
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counter_example: Option<String>,
+    /// Every unelected candidate whose voters are under-represented, highest pre-score first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<PjrViolation>,
+    /// Whether the result satisfies local t-PJR
+    pub locally_satisfied: bool,
+    /// Every candidate whose voters got fewer seats than they are owed, most seats missing first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub local_violations: Vec<LocalPjrViolation>,
+}
+
+/// Voters of one candidate that got fewer seats than local PJR owes them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalPjrViolation {
+    /// Candidate the voters have in common
+    pub candidate_id: String,
+    /// Combined stake of the candidate's voters
+    pub group_stake: u128,
+    /// Seats owed: the group stake in thresholds, capped at the candidates every voter votes for
+    pub owed_seats: usize,
+    /// Elected candidates any of the voters votes for
+    pub elected_seats: usize,
+    /// Voters of the candidate
+    pub voter_ids: Vec<String>,
+}
+
+/// Group of voters that could elect a candidate on their own under t-PJR
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PjrViolation {
+    /// Unelected candidate the group votes for
+    pub candidate_id: String,
+    /// Stake the group could move to the candidate without dropping any of
+    /// its elected candidates below the threshold
+    pub pre_score: u128,
+    /// Voters of the candidate with spare stake
+    pub voter_ids: Vec<String>,
+}
+
+/// Smallest backing of a result against maximin support
+///
+/// Maximin support is the largest smallest backing any elected set and
+/// stake distribution achieves. It is not computed exactly; the result is
+/// compared against the same set with optimally balanced stake and against
+/// the experimental `mms` algorithm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaximinGap {
+    /// Smallest backing in the result
+    pub minimal_support: u128,
+    /// Smallest backing of the result's elected set with optimally balanced stake
+    pub balanced_minimal_support: u128,
+    /// Smallest backing of the `mms` algorithm's elected set
+    pub mms_minimal_support: u128,
+    /// Upper bound on maximin support over all elected sets
+    ///
+    /// The lower of the average backing and twice the `mms` value, since
+    /// PhragMMS is a 2-approximation of maximin support.
+    pub upper_bound: u128,
+    /// Shortfall of `minimal_support` against the best value found, in basis points
+    pub gap_bps: u32,
+}
+
+/// Stake that earns nothing because it falls outside rewarded exposures
+///
+/// Present when the election ran with
+/// [`max_rewarded_nominators`](crate::models::election_config::ElectionConfiguration::max_rewarded_nominators)
+/// and some validator was oversubscribed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnrewardedStake {
+    /// Stake allocated outside rewarded exposures
+    pub total_unrewarded: u128,
+    /// Share of the elected backing that is unrewarded, in basis points
+    pub share_bps: u32,
+    /// Nominators with at least one unrewarded allocation
+    pub nominator_count: u32,
+    /// Oversubscribed validators, most unrewarded stake first
+    pub validators: Vec<OversubscribedValidator>,
+}
+
+/// A validator with more nominators than are rewarded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OversubscribedValidator {
+    /// Account ID of the validator
+    pub validator_id: String,
+    /// Nominators allocating stake to the validator, excluding its self-vote
+    pub nominator_count: u32,
+    /// Nominators outside the rewarded exposure
+    pub unrewarded_count: u32,
+    /// Stake of the nominators outside the rewarded exposure
+    pub unrewarded_stake: u128,
+    /// Smallest allocation still rewarded
+    pub rewarded_minimum: u128,
+}
+
 impl Diagnostics {
     /// Get validator explanations
     pub fn validator_explanations(&self) -> &[ValidatorExplanation] {
@@ -50,6 +468,54 @@ impl Diagnostics {
     pub fn stake_analysis(&self) -> &StakeAnalysis {
         &self.stake_analysis
     }
-}
 
+    /// Get minimum active stake analysis if available
+    pub fn minimum_active_stake(&self) -> Option<&MinimumActiveStake> {
+        self.minimum_active_stake.as_ref()
+    }
+
+    /// Get nominator clustering analysis if available
+    pub fn nominator_clusters(&self) -> Option<&NominatorClusters> {
+        self.nominator_clusters.as_ref()
+    }
+
+    /// Get operator grouping analysis if available
+    pub fn operator_groups(&self) -> Option<&OperatorGroups> {
+        self.operator_groups.as_ref()
+    }
+
+    /// Get geographic and provider diversity analysis if available
+    pub fn diversity(&self) -> Option<&DiversityAnalysis> {
+        self.diversity.as_ref()
+    }
 
+    /// Get stake sensitivity around the cutoff if available
+    pub fn stake_sensitivity(&self) -> Option<&StakeSensitivity> {
+        self.stake_sensitivity.as_ref()
+    }
+
+    /// Get the PJR check if available
+    pub fn pjr(&self) -> Option<&PjrCheck> {
+        self.pjr.as_ref()
+    }
+
+    /// Get the maximin support gap if available
+    pub fn maximin_gap(&self) -> Option<&MaximinGap> {
+        self.maximin_gap.as_ref()
+    }
+
+    /// Get the unrewarded stake of oversubscribed validators if any
+    pub fn unrewarded_stake(&self) -> Option<&UnrewardedStake> {
+        self.unrewarded_stake.as_ref()
+    }
+
+    /// Findings with the given code
+    pub fn findings_with_code(&self, code: FindingCode) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(move |f| f.code == code)
+    }
+
+    /// Findings at or above the given severity
+    pub fn findings_at_least(&self, severity: Severity) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(move |f| f.severity >= severity)
+    }
+}
@@ -3,14 +3,39 @@
 //! The [`ElectionEngine`] is the main entry point for running election simulations.
 //! It handles algorithm selection, parameter overrides, validation, and result generation.
 
+use crate::algorithms::prepared::PreparedElectionData;
 use crate::algorithms::trait_def::ElectionAlgorithm;
+use crate::cache::{CacheKey, ResultCache};
+use crate::cancellation::{CancellationToken, ElectionPhase};
+use crate::progress::{ProgressEvent, ProgressObserver, RunControl};
 use crate::algorithms::sequential_phragmen::SequentialPhragmen;
 use crate::diagnostics::explainer::DiagnosticsGenerator;
 use crate::error::ElectionError;
 use crate::models::election_config::ElectionConfiguration;
 use crate::models::election_data::ElectionData;
-use crate::models::election_result::ElectionResult;
-use crate::types::AlgorithmType;
+use crate::models::election_overrides::{ElectionOverrides, OverrideApplication};
+use crate::models::election_result::{ElectionCounts, ElectionResult, ElectionScore, ResultIssue, ResultIssueKind};
+use crate::models::onchain_solution::{OnchainSolution, SolutionVerification};
+use crate::models::override_impact::OverrideImpact;
+use crate::models::rank_stability::{Perturbation, RankStability, StabilityScenario};
+use crate::models::runtime_behavior::RuntimeBehavior;
+use crate::models::snapshot_delta::SnapshotDelta;
+use crate::models::candidate_rules::{CandidateRule, CandidateRules, RuleApplication};
+use crate::models::sybil_scenario::{
+    AttackEstimate, SybilPoint, SybilReport, SybilScenario, SybilSplit, DEFAULT_ATTACK_TOLERANCE_BPS,
+};
+use crate::models::stake_flow::{EraProjection, StakeFlowProjection, StakeFlowScenario};
+use crate::models::validation::{DataIssue, DataIssueKind, ValidationProfile};
+use crate::models::whale_impact::{WhaleImpact, WhaleRemoval, WhaleRemovalMode};
+use crate::payout::commission::{CommissionChange, CommissionImpact, CommissionPolicy, NominatorRewardChange};
+use crate::payout::models::{PayoutConfig, PayoutResult, ValidatorPayout};
+use crate::payout::simulator::PayoutSimulator;
+use crate::types::{AlgorithmType, ElectionKind};
+use std::borrow::Cow;
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Election engine for executing elections with various algorithms
 ///
@@ -28,7 +53,7 @@ use crate::types::AlgorithmType;
 ///
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// let engine = ElectionEngine::new();
-/// let config = ElectionConfiguration::new()
+/// let config = ElectionConfiguration::builder()
 ///     .algorithm(AlgorithmType::SequentialPhragmen)
 ///     .active_set_size(100)
 ///     .build()?;
@@ -43,14 +68,174 @@ use crate::types::AlgorithmType;
 /// # Thread Safety
 ///
 /// `ElectionEngine` is `Send + Sync` and can be safely shared across threads.
-pub struct ElectionEngine;
+/// Batch runs ([`execute_batch`](Self::execute_batch),
+/// [`whale_impact`](Self::whale_impact), [`sybil_stress`](Self::sybil_stress),
+/// [`rank_stability`](Self::rank_stability))
+/// spread their elections over rayon's global pool, or over the engine's own
+/// pool when built with [`with_thread_pool`](Self::with_thread_pool); clones
+/// share that pool.
+#[derive(Clone, Default)]
+pub struct ElectionEngine {
+    /// Pool for batch runs; `None` uses rayon's global pool
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
+    /// Notified of every finished selection and balancing round
+    progress_observer: Option<ProgressObserver>,
+    /// Results of earlier runs, reused for identical ones
+    result_cache: Option<ResultCache>,
+    /// Keep warnings off stderr
+    quiet: bool,
+}
+
+impl std::fmt::Debug for ElectionEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ElectionEngine")
+            .field("thread_pool", &self.thread_pool)
+            .field("progress_observer", &self.progress_observer.as_ref().map(|_| "observer"))
+            .field("result_cache", &self.result_cache)
+            .field("quiet", &self.quiet)
+            .finish()
+    }
+}
+
+/// Election input after validation, overrides and candidate rules
+struct ResolvedInput<'d> {
+    data: Cow<'d, ElectionData>,
+    data_issues: Vec<DataIssue>,
+    applied_layers: Vec<String>,
+    rule_applications: Vec<RuleApplication>,
+    /// Candidate rules as applied; in partial-result mode, without
+    /// force-included accounts that are not candidates
+    candidate_rules: Option<CandidateRules>,
+    /// Rules of the runtime the configuration names, if any
+    behavior: Option<RuntimeBehavior>,
+    /// Recoverable problems, see [`ElectionConfiguration::partial_results`]
+    issues: Vec<ResultIssue>,
+}
 
 impl ElectionEngine {
     /// Create a new election engine
     ///
     /// The engine is stateless and can be reused for multiple elections.
     pub fn new() -> Self {
-        Self
+        Self {
+            thread_pool: None,
+            progress_observer: None,
+            result_cache: None,
+            quiet: false,
+        }
+    }
+
+    /// Create an engine whose batch runs use a dedicated pool of `num_threads` threads
+    ///
+    /// Servers that run elections for several tenants can give each one an
+    /// engine with its own pool to bound how many cores it occupies, instead
+    /// of sharing rayon's global pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ElectionError::ValidationError` if `num_threads` is zero or the
+    /// threads cannot be spawned.
+    pub fn with_thread_pool(num_threads: usize) -> Result<Self, ElectionError> {
+        if num_threads == 0 {
+            return Err(ElectionError::ValidationError {
+                message: "Solver thread pool needs at least one thread".to_string(),
+                field: Some("num_threads".to_string()),
+            });
+        }
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("election-solver-{}", index))
+            .build()
+            .map_err(|e| ElectionError::ValidationError {
+                message: format!("Failed to start a solver thread pool with {} threads: {}", num_threads, e),
+                field: Some("num_threads".to_string()),
+            })?;
+        Ok(Self {
+            thread_pool: Some(Arc::new(pool)),
+            progress_observer: None,
+            result_cache: None,
+            quiet: false,
+        })
+    }
+
+    /// Report every finished selection and balancing round to `observer`
+    ///
+    /// Sequential Phragmén and multi-phase elections report each elected
+    /// candidate; every algorithm reports balancing rounds. Events arrive on
+    /// the thread running the election, so the observer should return quickly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::new().with_progress_observer(|event| {
+    ///     eprintln!("{} round {}/{} after {:?}", event.phase, event.round, event.total_rounds, event.elapsed);
+    /// });
+    /// let result = engine.execute(&ElectionConfiguration::builder().active_set_size(1000).build()?, &data)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_progress_observer(mut self, observer: impl Fn(&ProgressEvent) + Send + Sync + 'static) -> Self {
+        self.progress_observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Reuse results from `cache` for elections already run on the same data and configuration
+    ///
+    /// Applies to [`execute`](Self::execute),
+    /// [`execute_with_diagnostics`](Self::execute_with_diagnostics) and
+    /// [`execute_cancellable`](Self::execute_cancellable). Results are keyed by
+    /// the content hashes of the data and configuration, so a hit costs one
+    /// pass over the data to hash it. Cached results are marked
+    /// `execution_metadata.from_cache` and report no progress. Clones of the
+    /// cache share results, so several engines can use one cache.
+    pub fn with_result_cache(mut self, cache: ResultCache) -> Self {
+        self.result_cache = Some(cache);
+        self
+    }
+
+    /// Stop printing warnings to stderr
+    ///
+    /// For callers that own the terminal, such as the TUI. Warnings about the
+    /// data still reach the result's `issues`; a reduced active set shows in
+    /// the result's validator count.
+    pub fn quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    /// Print a warning to stderr unless the engine is [`quiet`](Self::quiet)
+    fn warn(&self, message: std::fmt::Arguments<'_>) {
+        if !self.quiet {
+            eprintln!("{}", message);
+        }
+    }
+
+    /// Control for one run: `cancel` plus the engine's progress observer
+    fn run_control(&self, cancel: CancellationToken) -> RunControl {
+        RunControl {
+            cancel,
+            progress: self.progress_observer.clone(),
+        }
+    }
+
+    /// Number of threads batch runs are spread over
+    pub fn thread_count(&self) -> usize {
+        match self.thread_pool {
+            Some(ref pool) => pool.current_num_threads(),
+            None => rayon::current_num_threads(),
+        }
+    }
+
+    /// Run `op` on the engine's pool, or on the global pool without one
+    fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match self.thread_pool {
+            Some(ref pool) => pool.install(op),
+            None => op(),
+        }
     }
 
     /// Execute an election with the given configuration and data
@@ -103,118 +288,1258 @@ impl ElectionEngine {
         data: &ElectionData,
         generate_diagnostics: bool,
     ) -> Result<ElectionResult, ElectionError> {
-        // Validate election data
-        data.validate()?;
+        self.run(config, data, generate_diagnostics, &self.run_control(CancellationToken::new()))
+    }
 
-        // Auto-adjust active set size if there are fewer candidates available
-        let candidate_count = data.candidates().len();
-        let effective_active_set_size = if config.active_set_size as usize > candidate_count {
-            eprintln!(
-                "Warning: Requested {} validators but only {} candidates available. Using {} instead.",
-                config.active_set_size,
-                candidate_count,
-                candidate_count
-            );
-            candidate_count as u32
-        } else {
-            config.active_set_size
-        };
+    /// Execute an election that another thread can stop
+    ///
+    /// Behaves like [`execute`](Self::execute), but checks `cancel` after the
+    /// input is resolved, between selection rounds, once the winners are known
+    /// and between balancing rounds. After [`CancellationToken::cancel`] the
+    /// run returns `ElectionError::Cancelled` naming the phase it stopped in
+    /// and, for selection and balancing, the rounds finished.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{CancellationToken, ElectionEngine, ElectionConfiguration, ElectionData, ElectionError};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let config = ElectionConfiguration::builder().active_set_size(100).build()?;
+    /// let cancel = CancellationToken::new();
+    ///
+    /// let stop = cancel.clone();
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(std::time::Duration::from_secs(30));
+    ///     stop.cancel();
+    /// });
+    ///
+    /// match ElectionEngine::new().execute_cancellable(&config, &data, &cancel) {
+    ///     Ok(result) => println!("Selected {} validators", result.validator_count()),
+    ///     Err(ElectionError::Cancelled { phase, .. }) => println!("Stopped during {:?}", phase),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_cancellable(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        cancel: &CancellationToken,
+    ) -> Result<ElectionResult, ElectionError> {
+        self.run(config, data, false, &self.run_control(cancel.clone()))
+    }
 
-        // Create a modified config with the adjusted active set size
-        let mut adjusted_config = config.clone();
-        adjusted_config.active_set_size = effective_active_set_size;
+    /// Answer from the result cache if possible, otherwise solve and cache the result
+    fn run(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        generate_diagnostics: bool,
+        control: &RunControl,
+    ) -> Result<ElectionResult, ElectionError> {
+        // Fields are public, so a configuration may have changed since it was built
+        config.validate()?;
+        let data_hash = data.content_hash();
+        let Some(ref cache) = self.result_cache else {
+            return self.solve(config, data, data_hash, generate_diagnostics, control);
+        };
 
-        // Select algorithm based on configuration
-        let algorithm: Box<dyn ElectionAlgorithm> = match config.algorithm {
-            AlgorithmType::SequentialPhragmen => Box::new(SequentialPhragmen),
-            AlgorithmType::ParallelPhragmen => Box::new(crate::algorithms::parallel_phragmen::ParallelPhragmen),
-            AlgorithmType::MultiPhase => Box::new(crate::algorithms::multi_phase::MultiPhase),
+        let key = CacheKey {
+            data_hash: data_hash.clone(),
+            config_hash: config.content_hash(),
+            diagnostics: generate_diagnostics,
         };
+        if let Some(cached) = cache.get(&key) {
+            let mut result = ElectionResult::clone(&cached);
+            result.execution_metadata.from_cache = true;
+            return Ok(result);
+        }
+        let result = self.solve(config, data, data_hash, generate_diagnostics, control)?;
+        cache.insert(key, result.clone());
+        Ok(result)
+    }
 
-        // Apply overrides if present
-        let mut modified_data = data.clone();
-        if let Some(ref overrides) = config.overrides {
-            self.apply_overrides(&mut modified_data, overrides)?;
+    /// Resolve the input, run the algorithm and check the result, stopping
+    /// between phases once the run is cancelled
+    fn solve(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        data_hash: String,
+        generate_diagnostics: bool,
+        control: &RunControl,
+    ) -> Result<ElectionResult, ElectionError> {
+        let ResolvedInput {
+            data: modified_data,
+            data_issues,
+            applied_layers,
+            rule_applications,
+            candidate_rules,
+            behavior,
+            mut issues,
+        } = self.resolve(config, data)?;
+        control.cancel.check(ElectionPhase::Input)?;
+
+        // Auto-adjust active set size if there are fewer candidates available
+        let mut adjusted_config = self.adjusted_config(config, modified_data.candidates().len());
+        if let Some(ref behavior) = behavior {
+            adjusted_config = behavior.adjusted_config(adjusted_config);
         }
+        let algorithm = self.algorithm(config.algorithm);
 
-        // Execute algorithm with adjusted config
-        let result = algorithm.execute(&modified_data, &adjusted_config)?;
+        // Execute algorithm with adjusted config; invulnerables and
+        // force-included candidates take their seats first
+        let forced = self.forced_candidates(
+            &modified_data,
+            config,
+            candidate_rules.as_ref(),
+            adjusted_config.active_set_size,
+            &mut issues,
+        )?;
+        let started = Instant::now();
+        let mut result = if forced.is_empty() {
+            algorithm.execute_controlled(&PreparedElectionData::new(&modified_data), &adjusted_config, control)?
+        } else {
+            self.execute_with_forced(algorithm.as_ref(), &modified_data, &adjusted_config, &forced, control)?
+        };
+        let elapsed = started.elapsed();
+        self.record_run(&mut result, config, ElectionCounts::of(data), Some(data_hash), elapsed);
+        result.execution_metadata.applied_override_layers = applied_layers;
+        result.execution_metadata.rule_applications = rule_applications;
+        result.execution_metadata.data_issues = data_issues;
+        result.execution_metadata.behavior_flags = behavior.map(|b| b.flags).unwrap_or_default();
+        if config.kind == ElectionKind::Council {
+            self.fill_council_seats(&mut result);
+        }
+        self.check_forced_elected(&result, &forced, config.partial_results, &mut issues)?;
+        if let Some(max) = config.max_rewarded_nominators {
+            result.mark_unrewarded(max);
+        }
 
         // Validate result against adjusted config
         self.validate_result(&result, &adjusted_config)?;
 
         // Generate diagnostics if requested
-        let result = if generate_diagnostics {
-            let diagnostics_gen = DiagnosticsGenerator::new();
-            match diagnostics_gen.generate(&result, &modified_data) {
-                Ok(diagnostics) => result.with_diagnostics(diagnostics),
-                Err(e) => {
-                    // Log error but don't fail the election
-                    eprintln!("Warning: Failed to generate diagnostics: {}", e);
-                    result
-                }
-            }
+        if generate_diagnostics {
+            control.cancel.check(ElectionPhase::Diagnostics)?;
+            result = self.attach_diagnostics(result, config, &modified_data, &mut issues);
+        }
+
+        result.apply_rounding(&config.rounding);
+        if config.partial_results {
+            result.issues = issues;
+        }
+        Ok(result)
+    }
+
+    /// The data and configuration an election actually runs on
+    ///
+    /// Validates `data`, drops duplicate targets and votes for non-candidates
+    /// the validation profile tolerates, and applies overrides, override layers,
+    /// convictions, the named runtime's limits and candidate rules. Running
+    /// the returned configuration on the returned data gives the same result
+    /// as running `config` on `data`: overrides, layers, convictions and the
+    /// runtime's limits are already part of the data and are removed from the
+    /// configuration, its balancing is set explicitly, while candidate rules
+    /// are kept because force-included candidates still take their seats first.
+    pub fn resolve_input(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+    ) -> Result<(ElectionConfiguration, ElectionData), ElectionError> {
+        let resolved = self.resolve(config, data)?;
+        let mut resolved_config = config.clone();
+        resolved_config.overrides = None;
+        resolved_config.override_layers = Vec::new();
+        resolved_config.convictions = None;
+        resolved_config.candidate_rules = resolved.candidate_rules;
+        if let Some(behavior) = resolved.behavior {
+            resolved_config = behavior.adjusted_config(resolved_config);
+            resolved_config.runtime = None;
+        }
+        Ok((resolved_config, resolved.data.into_owned()))
+    }
+
+    /// Validate data and apply everything in `config` that modifies it
+    fn resolve<'d>(
+        &self,
+        config: &ElectionConfiguration,
+        data: &'d ElectionData,
+    ) -> Result<ResolvedInput<'d>, ElectionError> {
+        // Validate election data; the profile decides which issues are only warnings
+        let data_issues = data.validate_with_profile(config.validation_profile)?;
+        let mut issues = load_failure_issues(data);
+
+        // Snapshots can be several GB, so the data is only copied once
+        // something actually modifies it
+        let mut modified_data = Cow::Borrowed(data);
+
+        // Drop tolerated duplicate targets and votes for non-candidates
+        if data_issues
+            .iter()
+            .any(|issue| matches!(issue.kind, DataIssueKind::DuplicateTarget | DataIssueKind::DanglingEdge))
+        {
+            modified_data.to_mut().sanitize();
+        }
+
+        // Apply overrides and override layers if present
+        let applied_layers = if config.overrides.is_some() || !config.override_layers.is_empty() {
+            let (applied_layers, override_issues) = self.apply_all_overrides(modified_data.to_mut(), config)?;
+            issues.extend(override_issues);
+            applied_layers
         } else {
-            result
+            Vec::new()
         };
 
+        // Weight nominator stakes by conviction
+        if let Some(ref convictions) = config.convictions {
+            if !convictions.is_empty() {
+                convictions.apply(modified_data.to_mut());
+            }
+        }
+
+        // Cut the data to the nomination and snapshot limits of the named runtime
+        let behavior = config.runtime.as_ref().map(RuntimeBehavior::for_runtime);
+        if let Some(ref behavior) = behavior {
+            if behavior.limits(&modified_data) {
+                behavior.apply(modified_data.to_mut());
+            }
+        }
+
+        // Apply candidate inclusion and exclusion rules; in partial-result mode
+        // force-included accounts that are not candidates are dropped
+        let mut candidate_rules = config.candidate_rules.clone();
+        if let (true, Some(rules)) = (config.partial_results, candidate_rules.as_mut()) {
+            let candidates: HashSet<&String> = modified_data.candidates.iter().map(|c| &c.account_id).collect();
+            let unknown: HashSet<String> = rules.forced().into_iter().filter(|id| !candidates.contains(id)).collect();
+            if !unknown.is_empty() {
+                let mut unknown_ids: Vec<&String> = unknown.iter().collect();
+                unknown_ids.sort();
+                issues.extend(unknown_ids.into_iter().map(|id| {
+                    ResultIssue::new(
+                        ResultIssueKind::UnknownForcedCandidate,
+                        format!("Force-included account {} is not a candidate", id),
+                    )
+                }));
+                *rules = rules.without_forced(&unknown);
+            }
+        }
+        let rule_applications = match candidate_rules {
+            Some(ref rules) if !rules.is_empty() => {
+                rules.validate(&modified_data, config.active_set_size)?;
+                rules.apply(modified_data.to_mut())
+            }
+            _ => Vec::new(),
+        };
+
+        Ok(ResolvedInput {
+            data: modified_data,
+            data_issues,
+            applied_layers,
+            rule_applications,
+            candidate_rules,
+            behavior,
+            issues,
+        })
+    }
+
+    /// Validate election data and build the solver's index over it once
+    ///
+    /// Pass the prepared data to [`execute_prepared`](Self::execute_prepared)
+    /// for every run over the same snapshot, e.g. when sweeping active set
+    /// sizes or algorithms, instead of re-validating and re-indexing each time.
+    pub fn prepare<'a>(&self, data: &'a ElectionData) -> Result<PreparedElectionData<'a>, ElectionError> {
+        data.validate()?;
+        Ok(PreparedElectionData::new(data))
+    }
+
+    /// Execute an election on data from [`prepare`](Self::prepare)
+    ///
+    /// Overrides, convictions, candidate rules, runtime rules and forced invulnerables change the
+    /// algorithm's input, so configurations that use them fall back to
+    /// [`execute_with_diagnostics`](Self::execute_with_diagnostics) on the
+    /// prepared data's source.
+    pub fn execute_prepared(
+        &self,
+        config: &ElectionConfiguration,
+        prepared: &PreparedElectionData<'_>,
+        generate_diagnostics: bool,
+    ) -> Result<ElectionResult, ElectionError> {
+        if modifies_input(config, prepared.data()) || config.validation_profile != ValidationProfile::ChainMirroring {
+            return self.execute_with_diagnostics(config, prepared.data(), generate_diagnostics);
+        }
+
+        let adjusted_config = self.adjusted_config(config, prepared.candidate_count());
+        let started = Instant::now();
+        let mut result = self
+            .algorithm(config.algorithm)
+            .execute_controlled(prepared, &adjusted_config, &self.run_control(CancellationToken::new()))?;
+        let elapsed = started.elapsed();
+        let data_hash = prepared.data_hash().to_string();
+        self.record_run(&mut result, config, prepared.input_counts(), Some(data_hash), elapsed);
+        if config.kind == ElectionKind::Council {
+            self.fill_council_seats(&mut result);
+        }
+        if let Some(max) = config.max_rewarded_nominators {
+            result.mark_unrewarded(max);
+        }
+        self.validate_result(&result, &adjusted_config)?;
+
+        let mut issues = load_failure_issues(prepared.data());
+        if generate_diagnostics {
+            result = self.attach_diagnostics(result, config, prepared.data(), &mut issues);
+        }
+
+        result.apply_rounding(&config.rounding);
+        if config.partial_results {
+            result.issues = issues;
+        }
         Ok(result)
     }
 
-    /// Apply parameter overrides to election data
-    fn apply_overrides(
+    /// Execute an election on `data` changed by `delta`, without copying `data`
+    ///
+    /// For what-if runs over a shared snapshot: `config` and `data` come from
+    /// [`resolve_input`](Self::resolve_input), so only what the delta adds or
+    /// changes is validated, and the solver reads the snapshot through the
+    /// delta. The result matches running on a copy with the delta applied,
+    /// except that no data hash is recorded. Configurations that still modify
+    /// the input, a result cache, and deltas listing a target twice run on
+    /// such a copy instead.
+    fn execute_delta(
         &self,
-        data: &mut ElectionData,
-        overrides: &crate::models::election_overrides::ElectionOverrides,
-    ) -> Result<(), ElectionError> {
-        // Apply candidate stake overrides
-        for (account_id, stake) in &overrides.candidate_stakes {
-            if let Some(candidate) = data.candidates.iter_mut().find(|c| c.account_id == *account_id) {
-                candidate.stake = *stake;
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        delta: &SnapshotDelta,
+    ) -> Result<ElectionResult, ElectionError> {
+        config.validate()?;
+        let data_issues = validate_delta(data, delta, config.validation_profile)?;
+        if self.result_cache.is_some()
+            || modifies_input(config, data)
+            || data_issues.iter().any(|issue| issue.kind == DataIssueKind::DuplicateTarget)
+        {
+            let mut changed = data.clone();
+            delta.apply(&mut changed);
+            return self.execute(config, &changed);
+        }
+
+        let prepared = PreparedElectionData::with_delta(data, delta);
+        let adjusted_config = self.adjusted_config(config, prepared.candidate_count());
+        let started = Instant::now();
+        let mut result = self
+            .algorithm(config.algorithm)
+            .execute_controlled(&prepared, &adjusted_config, &self.run_control(CancellationToken::new()))?;
+        let elapsed = started.elapsed();
+        self.record_run(&mut result, config, prepared.input_counts(), None, elapsed);
+        result.execution_metadata.data_issues = data_issues;
+        if config.kind == ElectionKind::Council {
+            self.fill_council_seats(&mut result);
+        }
+        if let Some(max) = config.max_rewarded_nominators {
+            result.mark_unrewarded(max);
+        }
+        self.validate_result(&result, &adjusted_config)?;
+
+        result.apply_rounding(&config.rounding);
+        if config.partial_results {
+            result.issues = load_failure_issues(data);
+        }
+        Ok(result)
+    }
+
+    /// Run one election per configuration over the same snapshot
+    ///
+    /// The data is validated and indexed once, see [`prepare`](Self::prepare),
+    /// and the elections run in parallel on the engine's thread pool. Results
+    /// are returned in the order of `configs`; the first error ends the batch.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::with_thread_pool(4)?;
+    /// let configs: Vec<ElectionConfiguration> = (250..=300)
+    ///     .map(|size| ElectionConfiguration::builder().active_set_size(size).build())
+    ///     .collect::<Result<_, _>>()?;
+    ///
+    /// for result in engine.execute_batch(&configs, &data)? {
+    ///     println!("{} validators", result.validator_count());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_batch(
+        &self,
+        configs: &[ElectionConfiguration],
+        data: &ElectionData,
+    ) -> Result<Vec<ElectionResult>, ElectionError> {
+        let prepared = self.prepare(data)?;
+        self.install(|| {
+            configs
+                .par_iter()
+                .map(|config| self.execute_prepared(config, &prepared, false))
+                .collect()
+        })
+    }
+
+    /// Evaluate the impact of overrides against a baseline election
+    ///
+    /// Runs the baseline election described by `base_config` and `base_data`, then the
+    /// same election with `overrides` applied on top of any overrides already present in
+    /// `base_config`, and returns only the difference between the two outcomes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData, ElectionOverrides};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::new();
+    /// let config = ElectionConfiguration::builder().active_set_size(100).build()?;
+    ///
+    /// let mut overrides = ElectionOverrides::new();
+    /// overrides.set_candidate_stake("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(), 0)?;
+    ///
+    /// let impact = engine.evaluate_override(&data, &config, &overrides)?;
+    /// println!("{} validators removed", impact.validators_removed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn evaluate_override(
+        &self,
+        base_data: &ElectionData,
+        base_config: &ElectionConfiguration,
+        overrides: &ElectionOverrides,
+    ) -> Result<OverrideImpact, ElectionError> {
+        let baseline = self.execute(base_config, base_data)?;
+
+        // Bake the base overrides into the data so the new overrides layer on top of them
+        let mut layered_data = base_data.clone();
+        self.apply_all_overrides(&mut layered_data, base_config)?;
+        let mut overridden_config = base_config.clone();
+        overridden_config.overrides = Some(overrides.clone());
+        overridden_config.override_layers.clear();
+
+        let overridden = self.execute(&overridden_config, &layered_data)?;
+
+        Ok(OverrideImpact::between(&baseline, &overridden))
+    }
+
+    /// Measure how much the elected set depends on the largest nominators
+    ///
+    /// Runs the election once with every nominator, then re-runs it without
+    /// the `top_k` largest nominators by stake: each on its own in
+    /// [`WhaleRemovalMode::Individual`], or the largest one, then the two
+    /// largest and so on in [`WhaleRemovalMode::Cumulative`]. Overrides,
+    /// convictions and candidate rules in `config` are applied first, so
+    /// "largest" refers to the stake the election actually uses.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData};
+    /// use offline_election::models::WhaleRemovalMode;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::new();
+    /// let config = ElectionConfiguration::builder().active_set_size(100).build()?;
+    ///
+    /// let impact = engine.whale_impact(&config, &data, 10, WhaleRemovalMode::Cumulative)?;
+    /// for step in impact.set_changes() {
+    ///     println!("{} nominators removed: {} validators swapped", step.removed_nominators.len(), step.validators_removed.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn whale_impact(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        top_k: usize,
+        mode: WhaleRemovalMode,
+    ) -> Result<WhaleImpact, ElectionError> {
+        let (config, data) = self.resolve_input(config, data)?;
+        let baseline = self.execute(&config, &data)?;
+        let baseline_score = baseline.score();
+        let total_nominator_stake = data.statistics().total_nominator_stake;
+
+        let mut whales: Vec<(&str, u128)> = data
+            .nominators
+            .iter()
+            .filter(|n| n.stake > 0)
+            .map(|n| (n.account_id.as_str(), n.stake))
+            .collect();
+        whales.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        whales.truncate(top_k);
+
+        let steps = self.install(|| {
+            (0..whales.len())
+                .into_par_iter()
+                .map(|index| {
+                    let removed = match mode {
+                        WhaleRemovalMode::Individual => &whales[index..=index],
+                        WhaleRemovalMode::Cumulative => &whales[..=index],
+                    };
+                    let without_whales = SnapshotDelta {
+                        removed_nominators: removed.iter().map(|(id, _)| id.to_string()).collect(),
+                        ..SnapshotDelta::default()
+                    };
+                    let result = self.execute_delta(&config, &data, &without_whales)?;
+                    let impact = OverrideImpact::between(&baseline, &result);
+                    let removed_stake: u128 = removed.iter().map(|(_, stake)| stake).sum();
+                    Ok(WhaleRemoval {
+                        removed_nominators: removed.iter().map(|(id, _)| id.to_string()).collect(),
+                        removed_stake,
+                        removed_stake_bps: removed_stake
+                            .saturating_mul(10_000)
+                            .checked_div(total_nominator_stake)
+                            .unwrap_or(0) as u32,
+                        validators_added: impact.validators_added,
+                        validators_removed: impact.validators_removed,
+                        score_delta: impact.score_delta,
+                    })
+                })
+                .collect::<Result<Vec<_>, ElectionError>>()
+        })?;
+
+        Ok(WhaleImpact {
+            mode,
+            baseline_score,
+            total_nominator_stake,
+            steps,
+        })
+    }
+
+    /// Measure how stable the winner ordering is under small perturbations
+    ///
+    /// Runs the election once as configured, then once per perturbation of
+    /// `scenario`: with the active set size moved by its delta either way,
+    /// and with every nominator's stake scaled by random noise. Overrides,
+    /// convictions and candidate rules in `config` are applied first, so the
+    /// noise applies to the stake the election actually uses.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData};
+    /// use offline_election::models::StabilityScenario;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::new();
+    /// let config = ElectionConfiguration::builder().active_set_size(100).build()?;
+    ///
+    /// let stability = engine.rank_stability(&config, &data, &StabilityScenario::new().noise_runs(10))?;
+    /// for validator in stability.fragile() {
+    ///     println!("{} elected in {} of {} runs", validator.validator_id, validator.elected_runs, stability.runs.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rank_stability(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        scenario: &StabilityScenario,
+    ) -> Result<RankStability, ElectionError> {
+        scenario.validate()?;
+        let (config, data) = self.resolve_input(config, data)?;
+        let winners = |result: ElectionResult| -> Vec<String> {
+            result.selected_validators.into_iter().map(|v| v.account_id).collect()
+        };
+        let baseline = winners(self.execute(&config, &data)?);
+
+        let runs = self.install(|| {
+            scenario
+                .perturbations(config.active_set_size)
+                .into_par_iter()
+                .map(|perturbation| {
+                    let mut perturbed_config = config.clone();
+                    if let Perturbation::SetSize { active_set_size } = perturbation {
+                        perturbed_config.active_set_size = active_set_size;
+                    }
+                    let result = self.execute_delta(&perturbed_config, &data, &perturbation.delta(&data))?;
+                    Ok((perturbation, winners(result)))
+                })
+                .collect::<Result<Vec<_>, ElectionError>>()
+        })?;
+
+        Ok(RankStability::from_runs(config.active_set_size, baseline, runs))
+    }
+
+    /// Simulate enforcing a minimum and/or maximum commission
+    ///
+    /// Candidates outside the bound of `policy` either move their commission
+    /// into it or, if the policy says they refuse, are excluded with a
+    /// [`CandidateRule::Exclude`].
+    /// The election and an era payout are run with the current and the
+    /// bounded commissions. Commissions are those of `payout` after
+    /// [`with_candidate_data`](PayoutConfig::with_candidate_data), so
+    /// candidates without a recorded commission start at its default.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData};
+    /// use offline_election::payout::{CommissionPolicy, CommissionRefusal, PayoutConfig};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::new();
+    /// let config = ElectionConfiguration::builder().active_set_size(297).build()?;
+    /// let policy = CommissionPolicy::new().min(5).refusal(CommissionRefusal::OutOfBounds);
+    ///
+    /// let impact = engine.commission_scenario(&config, &data, &policy, &PayoutConfig::new(1_000_000_000_000))?;
+    /// println!("{} nominators lose rewards, {} validators drop out", impact.losers().count(), impact.dropped.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn commission_scenario(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        policy: &CommissionPolicy,
+        payout: &PayoutConfig,
+    ) -> Result<CommissionImpact, ElectionError> {
+        policy.validate()?;
+        let baseline_payout_config = payout.clone().with_candidate_data(data);
+        baseline_payout_config.validate()?;
+
+        let mut scenario_data = data.clone();
+        let mut scenario_payout_config = baseline_payout_config.clone();
+        let mut adjusted = Vec::new();
+        let mut dropped = Vec::new();
+        for candidate in scenario_data.candidates_mut() {
+            let from = baseline_payout_config.commission_for(&candidate.account_id);
+            let to = policy.bounded(from);
+            if to == from {
+                continue;
             }
+            if policy.refuses(&candidate.account_id, from) {
+                dropped.push(candidate.account_id.clone());
+                continue;
+            }
+            // Candidate rules on commission see the bounded rate
+            candidate.metadata.get_or_insert_with(Default::default).commission_rate = Some(to);
+            scenario_payout_config = scenario_payout_config.commission(candidate.account_id.clone(), to);
+            adjusted.push(CommissionChange {
+                validator_id: candidate.account_id.clone(),
+                from,
+                to,
+            });
+        }
+        adjusted.sort_by(|a, b| a.validator_id.cmp(&b.validator_id));
+        dropped.sort();
+        let mut scenario_config = config.clone();
+        if !dropped.is_empty() {
+            scenario_config.candidate_rules.get_or_insert_with(CandidateRules::new).rules.push(CandidateRule::Exclude {
+                account_ids: dropped.clone(),
+            });
+        }
+
+        let (baseline, scenario) = self.install(|| {
+            rayon::join(|| self.execute(config, data), || self.execute(&scenario_config, &scenario_data))
+        });
+        let (baseline, scenario) = (baseline?, scenario?);
+        let simulator = PayoutSimulator::new();
+        let baseline_payout = simulator.simulate(&baseline, &baseline_payout_config)?;
+        let scenario_payout = simulator.simulate(&scenario, &scenario_payout_config)?;
+        let set_change = OverrideImpact::between(&baseline, &scenario);
+        let sum = |payout: &PayoutResult, part: fn(&ValidatorPayout) -> u128| {
+            payout.validator_payouts.iter().map(part).sum::<u128>()
+        };
+
+        Ok(CommissionImpact {
+            policy: policy.clone(),
+            adjusted,
+            dropped,
+            validators_added: set_change.validators_added,
+            validators_removed: set_change.validators_removed,
+            baseline_commission: sum(&baseline_payout, |p| p.commission_reward),
+            scenario_commission: sum(&scenario_payout, |p| p.commission_reward),
+            baseline_nominator_rewards: sum(&baseline_payout, |p| p.nominators_reward),
+            scenario_nominator_rewards: sum(&scenario_payout, |p| p.nominators_reward),
+            nominators: NominatorRewardChange::between(&baseline_payout, &scenario_payout),
+        })
+    }
+
+    /// Run a sybil stress test against a snapshot
+    ///
+    /// For every split and attacker stake in `scenario`, injects the sybil
+    /// candidates and the attacker's nominators into `data` (after applying
+    /// overrides, convictions and candidate rules from `config`), runs the
+    /// election and counts the sybils that entered the active set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData};
+    /// use offline_election::models::{SybilScenario, SybilSplit};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::new();
+    /// let config = ElectionConfiguration::builder().active_set_size(100).build()?;
+    /// let scenario = SybilScenario::new(20)
+    ///     .attacker_stake(1_000_000_000_000_000)
+    ///     .attacker_stake(10_000_000_000_000_000);
+    ///
+    /// let report = engine.sybil_stress(&config, &data, &scenario)?;
+    /// println!("{:?}", report.min_stake_for(SybilSplit::Even, 10));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sybil_stress(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        scenario: &SybilScenario,
+    ) -> Result<SybilReport, ElectionError> {
+        let (config, data) = self.resolve_input(config, data)?;
+        scenario.validate(&data)?;
+        let sybil_ids = scenario.sybil_ids();
+        let honest_stake = data.statistics().total_nominator_stake;
+        let seats = config.active_set_size as u128;
+
+        let runs: Vec<(SybilSplit, u128)> = scenario
+            .splits
+            .iter()
+            .flat_map(|&split| scenario.attacker_stakes.iter().map(move |&stake| (split, stake)))
+            .collect();
+        let points = self.install(|| {
+            runs.into_par_iter()
+                .map(|(split, attacker_stake)| {
+                    let result = self.execute_delta(&config, &data, &scenario.delta(attacker_stake, split))?;
+                    let elected_sybils: Vec<String> = result
+                        .selected_validators
+                        .iter()
+                        .filter(|v| sybil_ids.contains(&v.account_id))
+                        .map(|v| v.account_id.clone())
+                        .collect();
+                    Ok(SybilPoint {
+                        split,
+                        attacker_stake,
+                        attacker_share_bps: attacker_stake
+                            .saturating_mul(10_000)
+                            .checked_div(honest_stake.saturating_add(attacker_stake))
+                            .unwrap_or(0) as u32,
+                        sybils_elected: elected_sybils.len(),
+                        seat_share_bps: (elected_sybils.len() as u128 * 10_000).checked_div(seats).unwrap_or(0) as u32,
+                        elected_sybils,
+                    })
+                })
+                .collect::<Result<Vec<_>, ElectionError>>()
+        })?;
+
+        Ok(SybilReport {
+            sybil_count: scenario.sybil_count,
+            active_set_size: config.active_set_size,
+            honest_stake,
+            points,
+        })
+    }
+
+    /// Project the validator set over future eras under scheduled stake flows
+    ///
+    /// Elects a set for the snapshot (era 0) and for each of the next `eras`
+    /// eras, with every flow scheduled up to that era applied to `data` (after
+    /// overrides, convictions and candidate rules from `config`). Each era
+    /// records the validators that entered and left the set since the era before.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData};
+    /// use offline_election::models::StakeFlowScenario;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::new();
+    /// let config = ElectionConfiguration::builder().active_set_size(100).build()?;
+    /// let scenario = StakeFlowScenario::new().unbond(2, "whale", 50_000_000_000_000_000);
+    ///
+    /// let projection = engine.project_stake_flows(&config, &data, &scenario, 4)?;
+    /// for era in projection.set_changes() {
+    ///     println!("era +{}: {:?} out", era.era, era.validators_removed);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn project_stake_flows(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        scenario: &StakeFlowScenario,
+        eras: u32,
+    ) -> Result<StakeFlowProjection, ElectionError> {
+        scenario.validate()?;
+        let (config, data) = self.resolve_input(config, data)?;
+
+        let elections = self.install(|| {
+            (0..=eras)
+                .into_par_iter()
+                .map(|era| {
+                    let (flows, unmatched) = scenario.delta_at(&data, era);
+                    let result = self.execute_delta(&config, &data, &flows)?;
+                    let nominator_stake = flows.nominator_stake(&data);
+                    Ok((result, nominator_stake, unmatched))
+                })
+                .collect::<Result<Vec<_>, ElectionError>>()
+        })?;
+
+        let mut projections = Vec::with_capacity(elections.len());
+        let mut previous: Option<&ElectionResult> = None;
+        for (era, (result, nominator_stake, unmatched_flows)) in (0u32..).zip(&elections) {
+            let (validators_added, validators_removed) = match previous {
+                Some(previous) => {
+                    let impact = OverrideImpact::between(previous, result);
+                    (impact.validators_added, impact.validators_removed)
+                }
+                None => (Vec::new(), Vec::new()),
+            };
+            projections.push(EraProjection {
+                era,
+                flows_applied: scenario.flows_at(era),
+                nominator_stake: *nominator_stake,
+                selected_validators: result.selected_validators.iter().map(|v| v.account_id.clone()).collect(),
+                validators_added,
+                validators_removed,
+                score: result.score(),
+                unmatched_flows: unmatched_flows.clone(),
+            });
+            previous = Some(result);
         }
+        Ok(StakeFlowProjection { eras: projections })
+    }
 
-        // Apply nominator stake overrides
-        for (account_id, stake) in &overrides.nominator_stakes {
-            if let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == *account_id) {
-                nominator.stake = *stake;
+    /// Estimate the minimum stake an attacker needs to win `seats` seats
+    ///
+    /// Injects `seats` sybil candidates backed by an attacker whose stake is
+    /// split as `split`, doubles the attacker's stake from the honest
+    /// nominator stake until the sybils win every seat, then bisects down to
+    /// within [`DEFAULT_ATTACK_TOLERANCE_BPS`] of the smallest winning stake.
+    /// Overrides, convictions and candidate rules in `config` are applied to
+    /// `data` first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use offline_election::{ElectionEngine, ElectionConfiguration, ElectionData};
+    /// use offline_election::models::SybilSplit;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let data = ElectionData::default();
+    /// let engine = ElectionEngine::new();
+    /// let config = ElectionConfiguration::builder().active_set_size(297).build()?;
+    ///
+    /// let estimate = engine.min_attack_stake(&config, &data, 100, SybilSplit::Even)?;
+    /// println!("{:?} plancks win 100 seats", estimate.min_stake);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn min_attack_stake(
+        &self,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        seats: usize,
+        split: SybilSplit,
+    ) -> Result<AttackEstimate, ElectionError> {
+        if seats == 0 || seats > config.active_set_size as usize {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Attack seats must be between 1 and the active set size of {}, got {}",
+                    config.active_set_size, seats
+                ),
+                field: Some("seats".to_string()),
+            });
+        }
+
+        let (config, data) = self.resolve_input(config, data)?;
+        let scenario = SybilScenario::new(seats).splits(vec![split]);
+        scenario.check_prefix(&data)?;
+        let sybil_ids = scenario.sybil_ids();
+        let honest_stake = data.statistics().total_nominator_stake;
+
+        let mut elections_run = 0;
+        let mut wins = |stake: u128| -> Result<bool, ElectionError> {
+            elections_run += 1;
+            let result = self.execute_delta(&config, &data, &scenario.delta(stake, split))?;
+            let elected = result
+                .selected_validators
+                .iter()
+                .filter(|v| sybil_ids.contains(&v.account_id))
+                .count();
+            Ok(elected >= seats)
+        };
+
+        // Solver stakes are u64, so larger attacker stakes cannot do better
+        let ceiling = u64::MAX as u128;
+        let mut failing = 0u128;
+        let mut winning = honest_stake.clamp(1, ceiling);
+        let mut found = true;
+        while !wins(winning)? {
+            failing = winning;
+            if winning == ceiling {
+                found = false;
+                break;
             }
+            winning = winning.saturating_mul(2).min(ceiling);
         }
 
-        // Apply voting edge modifications
-        for edge_mod in &overrides.voting_edges {
-            match edge_mod.action {
-                crate::models::election_overrides::EdgeAction::Add => {
-                    if let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == edge_mod.nominator_id) {
-                        nominator.add_target(edge_mod.candidate_id.clone());
-                    }
+        if found {
+            loop {
+                let tolerance = (winning / 10_000 * DEFAULT_ATTACK_TOLERANCE_BPS as u128).max(1);
+                if winning - failing <= tolerance {
+                    break;
                 }
-                crate::models::election_overrides::EdgeAction::Remove => {
-                    if let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == edge_mod.nominator_id) {
-                        nominator.remove_target(&edge_mod.candidate_id);
-                    }
+                let middle = failing + (winning - failing) / 2;
+                if wins(middle)? {
+                    winning = middle;
+                } else {
+                    failing = middle;
                 }
-                crate::models::election_overrides::EdgeAction::Modify => {
-                    // Modify is similar to remove + add
-                    if let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == edge_mod.nominator_id) {
-                        nominator.remove_target(&edge_mod.candidate_id);
-                        nominator.add_target(edge_mod.candidate_id.clone());
+            }
+        }
+
+        let min_stake = found.then_some(winning);
+        Ok(AttackEstimate {
+            seats,
+            split,
+            min_stake,
+            max_failing_stake: failing,
+            attacker_share_bps: min_stake.map(|stake| {
+                (stake.saturating_mul(10_000) / honest_stake.saturating_add(stake).max(1)) as u32
+            }),
+            honest_stake,
+            elections_run,
+        })
+    }
+
+    /// Independently verify an on-chain solution against its snapshot
+    ///
+    /// Recomputes the solution's score from its supports, runs the pallet's
+    /// feasibility checks against `snapshot`, and runs every available
+    /// algorithm on the snapshot to see whether a better solution exists.
+    /// Algorithms that fail on the snapshot are skipped.
+    pub fn verify_solution(
+        &self,
+        solution: &OnchainSolution,
+        snapshot: &ElectionData,
+        desired_targets: Option<u32>,
+        block_number: u64,
+    ) -> SolutionVerification {
+        let computed_score = solution.computed_score();
+        let feasibility_issues = solution.check_feasibility(snapshot, desired_targets);
+        let active_set_size = desired_targets.unwrap_or(solution.supports.len() as u32);
+
+        let mut best: Option<(ElectionScore, AlgorithmType)> = None;
+        for algorithm in [
+            AlgorithmType::SequentialPhragmen,
+            AlgorithmType::ParallelPhragmen,
+            AlgorithmType::MultiPhase,
+        ] {
+            let result = ElectionConfiguration::builder()
+                .algorithm(algorithm)
+                .active_set_size(active_set_size)
+                .block_number(block_number)
+                .build()
+                .and_then(|config| self.execute(&config, snapshot));
+            match result {
+                Ok(result) => {
+                    let score = result.score();
+                    if best.map_or(true, |(best_score, _)| score.is_better_than(&best_score)) {
+                        best = Some((score, algorithm));
                     }
                 }
+                Err(e) => self.warn(format_args!("  ⚠ Warning: {} failed on snapshot: {}", algorithm, e)),
+            }
+        }
+
+        SolutionVerification {
+            block_number,
+            compute: solution.compute.clone(),
+            claimed_score: solution.claimed_score,
+            computed_score,
+            score_matches: computed_score == solution.claimed_score,
+            feasible: feasibility_issues.is_empty(),
+            feasibility_issues,
+            best_alternative_score: best.map(|(score, _)| score),
+            best_alternative_algorithm: best.map(|(_, algorithm)| algorithm),
+            better_solution_found: best.is_some_and(|(score, _)| score.is_better_than(&computed_score)),
+        }
+    }
+
+    /// Copy of `config` with the active set capped at the number of candidates
+    fn adjusted_config(&self, config: &ElectionConfiguration, candidate_count: usize) -> ElectionConfiguration {
+        let mut adjusted_config = config.clone();
+        if config.active_set_size as usize > candidate_count {
+            self.warn(format_args!(
+                "Warning: Requested {} validators but only {} candidates available. Using {} instead.",
+                config.active_set_size,
+                candidate_count,
+                candidate_count
+            ));
+            adjusted_config.active_set_size = candidate_count as u32;
+        }
+        adjusted_config
+    }
+
+    /// Select the algorithm implementation for an algorithm type
+    fn algorithm(&self, algorithm: AlgorithmType) -> Box<dyn ElectionAlgorithm> {
+        match algorithm {
+            AlgorithmType::SequentialPhragmen => Box::new(SequentialPhragmen),
+            AlgorithmType::ParallelPhragmen => Box::new(crate::algorithms::parallel_phragmen::ParallelPhragmen),
+            AlgorithmType::MultiPhase => Box::new(crate::algorithms::multi_phase::MultiPhase),
+            AlgorithmType::Mms => Box::new(crate::algorithms::mms::Mms),
+        }
+    }
+
+    /// Attach diagnostics to a result, logging rather than failing on errors
+    fn attach_diagnostics(
+        &self,
+        result: ElectionResult,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+        issues: &mut Vec<ResultIssue>,
+    ) -> ElectionResult {
+        let diagnostics_gen = DiagnosticsGenerator::new().with_maximin_gap(config.maximin_gap);
+        match diagnostics_gen.generate(&result, data) {
+            Ok(diagnostics) => result.with_diagnostics(diagnostics),
+            Err(e) => {
+                // Log error but don't fail the election
+                self.warn(format_args!("Warning: Failed to generate diagnostics: {}", e));
+                issues.push(ResultIssue::new(
+                    ResultIssueKind::DiagnosticsFailed,
+                    format!("Failed to generate diagnostics: {}", e),
+                ));
+                result
+            }
+        }
+    }
+
+    /// Apply council seat rules to a result
+    ///
+    /// As in `pallet-elections-phragmen`, winners nobody backs do not take a
+    /// seat, and seats are ranked by backing so the top ranks are the members
+    /// and the rest runners-up.
+    fn fill_council_seats(&self, result: &mut ElectionResult) {
+        result.selected_validators.retain(|v| v.total_backing_stake > 0);
+        result
+            .selected_validators
+            .sort_by_key(|v| std::cmp::Reverse(v.total_backing_stake));
+        for (rank, validator) in result.selected_validators.iter_mut().enumerate() {
+            validator.rank = Some(rank as u32 + 1);
+        }
+        result.stake_distribution.retain(|a| a.amount > 0);
+    }
+
+    /// Candidates that must win: invulnerables first, then force-included candidates
+    ///
+    /// Invulnerables only win when the configuration opts in with
+    /// `force_invulnerables`, since pallet-staking itself never seats them.
+    /// Ones that are not candidates are skipped with a warning and an issue.
+    /// Council elections have no invulnerables.
+    fn forced_candidates(
+        &self,
+        data: &ElectionData,
+        config: &ElectionConfiguration,
+        candidate_rules: Option<&CandidateRules>,
+        active_set_size: u32,
+        issues: &mut Vec<ResultIssue>,
+    ) -> Result<Vec<String>, ElectionError> {
+        let candidates: HashSet<&String> = data.candidates.iter().map(|c| &c.account_id).collect();
+        let mut forced: Vec<String> = Vec::new();
+        let invulnerables = match config.kind {
+            ElectionKind::Staking if config.force_invulnerables => data.invulnerables.as_slice(),
+            ElectionKind::Staking | ElectionKind::Council => &[],
+        };
+        for account_id in invulnerables {
+            if candidates.contains(account_id) {
+                forced.push(account_id.clone());
+            } else {
+                self.warn(format_args!(
+                    "Warning: Invulnerable {} is not a candidate and cannot take a seat.",
+                    account_id
+                ));
+                issues.push(ResultIssue::new(
+                    ResultIssueKind::UnknownInvulnerable,
+                    format!("Invulnerable {} is not a candidate and cannot take a seat", account_id),
+                ));
+            }
+        }
+        if let Some(rules) = candidate_rules {
+            for account_id in rules.forced() {
+                if !forced.contains(&account_id) {
+                    forced.push(account_id);
+                }
             }
         }
 
+        if forced.len() > active_set_size as usize {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "{} candidates must win (invulnerables and force-included) but the active set only has {} seats",
+                    forced.len(),
+                    active_set_size
+                ),
+                field: Some("invulnerables".to_string()),
+            });
+        }
+        Ok(forced)
+    }
+
+    /// Record what a reproducibility audit needs about a run
+    fn record_run(
+        &self,
+        result: &mut ElectionResult,
+        config: &ElectionConfiguration,
+        input_counts: ElectionCounts,
+        data_hash: Option<String>,
+        algorithm_duration: Duration,
+    ) {
+        let metadata = &mut result.execution_metadata;
+        metadata.algorithm_duration_micros = Some(algorithm_duration.as_micros().min(u64::MAX as u128) as u64);
+        metadata.input_counts = Some(input_counts);
+        metadata.crate_version = Some(env!("CARGO_PKG_VERSION").to_string());
+        metadata.config_hash = Some(config.content_hash());
+        metadata.data_hash = data_hash;
+    }
+
+    /// Check that every `forced` candidate took a seat
+    ///
+    /// A forced candidate can still lose its seat, e.g. a council candidate
+    /// nobody backs. That fails the election, or in partial-result mode is
+    /// reported as an issue.
+    fn check_forced_elected(
+        &self,
+        result: &ElectionResult,
+        forced: &[String],
+        partial_results: bool,
+        issues: &mut Vec<ResultIssue>,
+    ) -> Result<(), ElectionError> {
+        let elected: HashSet<&str> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+        for account_id in forced.iter().filter(|id| !elected.contains(id.as_str())) {
+            let message = format!("Forced candidate {} was not elected", account_id);
+            if !partial_results {
+                return Err(ElectionError::ValidationError { message, field: Some("candidate_rules".to_string()) });
+            }
+            issues.push(ResultIssue::new(ResultIssueKind::ForcedCandidateNotElected, message));
+        }
         Ok(())
     }
 
+    /// Run an election in which `forced` candidates consume seats before the algorithm runs
+    ///
+    /// The algorithm first fills the remaining seats from the other candidates,
+    /// ignoring votes for forced ones. The candidate pool is then narrowed to the
+    /// forced candidates plus those winners and the election is re-run over it so
+    /// stake, including votes for forced candidates, is distributed across the
+    /// final set. Nominators left without a target in the pool are dropped from
+    /// that re-run only; `data` is left whole for diagnostics.
+    fn execute_with_forced(
+        &self,
+        algorithm: &dyn ElectionAlgorithm,
+        data: &ElectionData,
+        config: &ElectionConfiguration,
+        forced: &[String],
+        control: &RunControl,
+    ) -> Result<ElectionResult, ElectionError> {
+        let mut pool: HashSet<String> = forced.iter().cloned().collect();
+        let open_seats = (config.active_set_size as usize).saturating_sub(forced.len());
+
+        if open_seats > 0 {
+            let mut selection_data = data.clone();
+            selection_data.candidates_mut().retain(|c| !pool.contains(&c.account_id));
+            for nominator in selection_data.nominators_mut() {
+                nominator.targets.retain(|t| !pool.contains(t));
+            }
+            let mut selection_config = config.clone();
+            selection_config.active_set_size = open_seats.min(selection_data.candidates.len()) as u32;
+
+            if selection_config.active_set_size > 0 {
+                let selection = algorithm.execute_controlled(
+                    &PreparedElectionData::new(&selection_data),
+                    &selection_config,
+                    control,
+                )?;
+                pool.extend(selection.selected_validators.into_iter().map(|v| v.account_id));
+            }
+        }
+
+        let mut pool_data = data.clone();
+        pool_data.candidates_mut().retain(|c| pool.contains(&c.account_id));
+        pool_data.nominators_mut().retain_mut(|nominator| {
+            let had_targets = !nominator.targets.is_empty();
+            nominator.targets.retain(|t| pool.contains(t));
+            !had_targets || !nominator.targets.is_empty()
+        });
+
+        algorithm.execute_controlled(&PreparedElectionData::new(&pool_data), config, control)
+    }
+
+    /// Apply the configuration's overrides followed by its override layers
+    ///
+    /// Returns the names of the applied layers, in order, and an issue for
+    /// each override naming an account that is not in the data or dropped
+    /// because its candidate blocks new nominations.
+    fn apply_all_overrides(
+        &self,
+        data: &mut ElectionData,
+        config: &ElectionConfiguration,
+    ) -> Result<(Vec<String>, Vec<ResultIssue>), ElectionError> {
+        let mut issues = Vec::new();
+        let mut report = |application: OverrideApplication, layer: Option<&str>| {
+            let describe = |message: String| match layer {
+                Some(name) => format!("{} (layer {})", message, name),
+                None => message,
+            };
+            issues.extend(
+                application
+                    .unmatched
+                    .into_iter()
+                    .map(|message| ResultIssue::new(ResultIssueKind::UnmatchedOverride, describe(message))),
+            );
+            issues.extend(
+                application
+                    .rejected_edges
+                    .into_iter()
+                    .map(|message| ResultIssue::new(ResultIssueKind::RejectedOverride, describe(message))),
+            );
+        };
+        if let Some(ref overrides) = config.overrides {
+            report(overrides.apply(data), None);
+        }
+
+        let mut applied_layers = Vec::with_capacity(config.override_layers.len());
+        for layer in &config.override_layers {
+            report(layer.overrides.apply(data), Some(&layer.name));
+            applied_layers.push(layer.name.clone());
+        }
+
+        Ok((applied_layers, issues))
+    }
+
     /// Validate election result
     fn validate_result(
         &self,
         result: &ElectionResult,
         config: &ElectionConfiguration,
     ) -> Result<(), ElectionError> {
-        // Check that number of selected validators matches active set size
-        if result.selected_validators.len() != config.active_set_size as usize {
+        // Check that number of selected validators matches active set size;
+        // council seats nobody backs stay empty
+        let seat_count_matches = match config.kind {
+            ElectionKind::Staking => result.selected_validators.len() == config.active_set_size as usize,
+            ElectionKind::Council => result.selected_validators.len() <= config.active_set_size as usize,
+        };
+        if !seat_count_matches {
             return Err(ElectionError::ValidationError {
                 message: format!(
                     "Result has {} validators but expected {}",
@@ -225,12 +1550,16 @@ impl ElectionEngine {
             });
         }
 
-        // Check that total stake matches
+        // Check that no more stake is allocated than exists; voters backing no
+        // winner are trimmed, so less is fine. Each allocation is a ratio of
+        // the voter's stake rounded to the nearest planck, as upstream, so
+        // may exceed its exact share by one
         let total_allocated: u128 = result.stake_distribution.iter().map(|a| a.amount).sum();
-        if total_allocated != result.total_stake {
+        let rounding = result.stake_distribution.len() as u128;
+        if total_allocated > result.total_stake.saturating_add(rounding) {
             return Err(ElectionError::ValidationError {
                 message: format!(
-                    "Stake distribution total {} doesn't match total stake {}",
+                    "Stake distribution total {} exceeds total stake {}",
                     total_allocated, result.total_stake
                 ),
                 field: Some("stake_distribution".to_string()),
@@ -241,10 +1570,126 @@ impl ElectionEngine {
     }
 }
 
-impl Default for ElectionEngine {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Run a single election without setting up an engine
+///
+/// Equivalent to `ElectionEngine::new().execute(config, data)`, for callers
+/// that need no thread pool, observers, cache or diagnostics.
+///
+/// # Example
+///
+/// ```no_run
+/// use offline_election::{run_election, ElectionConfiguration, ElectionData};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let data = ElectionData::default();
+/// let config = ElectionConfiguration::builder().active_set_size(100).build()?;
+/// let result = run_election(&data, &config)?;
+/// println!("Selected {} validators", result.validator_count());
+/// # Ok(())
+/// # }
+/// ```
+pub fn run_election(data: &ElectionData, config: &ElectionConfiguration) -> Result<ElectionResult, ElectionError> {
+    ElectionEngine::new().execute(config, data)
 }
 
+/// Fetch a snapshot over RPC and run a single election on it
+///
+/// Loads the state at `block_number`, or at the latest block if `None`,
+/// with the default RPC settings. The configuration is checked before
+/// anything is fetched.
+///
+/// # Example
+///
+/// ```no_run
+/// use offline_election::{run_election_from_rpc, ElectionConfiguration};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let config = ElectionConfiguration::builder().active_set_size(297).build()?;
+/// let result = run_election_from_rpc("https://rpc.polkadot.io", None, &config).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn run_election_from_rpc(
+    url: &str,
+    block_number: Option<u64>,
+    config: &ElectionConfiguration,
+) -> Result<ElectionResult, ElectionError> {
+    config.validate()?;
+    let data = ElectionData::from_rpc(url, block_number).await?;
+    run_election(&data, config)
+}
+
+/// Whether `config` changes the algorithm's input beyond what the
+/// prepared data holds
+fn modifies_input(config: &ElectionConfiguration, data: &ElectionData) -> bool {
+    config.overrides.is_some()
+        || !config.override_layers.is_empty()
+        || config.convictions.as_ref().is_some_and(|convictions| !convictions.is_empty())
+        || config.candidate_rules.as_ref().is_some_and(|rules| !rules.is_empty())
+        || config.runtime.is_some()
+        || (config.kind == ElectionKind::Staking && config.force_invulnerables && !data.invulnerables.is_empty())
+}
+
+/// Validate what `delta` adds to or changes in already validated `data`
+///
+/// The nominators the delta touches, and those sharing an account with a
+/// candidate whose stake it changes, are checked against the candidates it
+/// leaves, with votes for missing candidates dropped as
+/// [`SnapshotDelta::apply`] drops them.
+fn validate_delta(
+    data: &ElectionData,
+    delta: &SnapshotDelta,
+    profile: ValidationProfile,
+) -> Result<Vec<DataIssue>, ElectionError> {
+    let removed_candidates: HashSet<&str> = delta.removed_candidates.iter().map(String::as_str).collect();
+    let removed_nominators: HashSet<&str> = delta.removed_nominators.iter().map(String::as_str).collect();
+    let mut touched = ElectionData::default();
+    let mut candidate_ids = HashSet::new();
+    let kept = data.candidates.iter().filter(|c| !removed_candidates.contains(c.account_id.as_str()));
+    for candidate in kept.chain(&delta.added_candidates) {
+        if candidate_ids.insert(candidate.account_id.as_str()) {
+            let mut candidate = candidate.clone();
+            if let Some(&stake) = delta.overrides.candidate_stakes.get(&candidate.account_id) {
+                candidate.stake = stake;
+            }
+            touched.candidates.push(candidate);
+        }
+    }
+
+    let changed: HashSet<&str> = delta
+        .overrides
+        .nominator_stakes
+        .keys()
+        .chain(delta.overrides.voting_edges.iter().map(|edge| &edge.nominator_id))
+        .chain(delta.overrides.candidate_stakes.keys())
+        .map(String::as_str)
+        .collect();
+    let mut nominator_ids = HashSet::new();
+    for nominator in &data.nominators {
+        let id = nominator.account_id.as_str();
+        if !removed_nominators.contains(id) {
+            nominator_ids.insert(id);
+            if changed.contains(id) {
+                touched.nominators.push(delta.changed_nominator(nominator));
+            }
+        }
+    }
+    for nominator in &delta.added_nominators {
+        if nominator_ids.insert(nominator.account_id.as_str()) {
+            touched.nominators.push(delta.changed_nominator(nominator));
+        }
+    }
+    for nominator in &mut touched.nominators {
+        nominator.targets.retain(|target| candidate_ids.contains(target.as_str()));
+    }
+    touched.validate_with_profile(profile)
+}
 
+/// Issues for the storage queries that failed while loading `data`
+fn load_failure_issues(data: &ElectionData) -> Vec<ResultIssue> {
+    data.metadata
+        .iter()
+        .flat_map(|metadata| &metadata.load_failures)
+        .map(|failure| ResultIssue::new(ResultIssueKind::LoadFailure, failure.clone()))
+        .collect()
+}
@@ -2,7 +2,9 @@
 //!
 //! All operations return `Result<T, ElectionError>` to provide detailed error information.
 
+use crate::cancellation::ElectionPhase;
 use crate::types::AlgorithmType;
+use serde::Serialize;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -25,7 +27,7 @@ use thiserror::Error;
 ///     Err(ElectionError::ValidationError { message, field }) => {
 ///         eprintln!("Validation failed: {} (field: {:?})", message, field);
 ///     }
-///     Err(ElectionError::RpcError { message, url }) => {
+///     Err(ElectionError::RpcError { message, url, .. }) => {
 ///         eprintln!("RPC error: {} (URL: {})", message, url);
 ///     }
 ///     Err(e) => eprintln!("Error: {}", e),
@@ -33,7 +35,22 @@ use thiserror::Error;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Error)]
+///
+/// # Machine-readable Errors
+///
+/// Every variant has a stable [`code`](ElectionError::code), and errors
+/// serialize to a JSON object with the variant name under `type` next to its
+/// fields, e.g. `{"type":"RpcError","message":"...","url":"..."}`.
+///
+/// # Root Causes
+///
+/// RPC, file and data errors keep the error that caused them as their
+/// [`source`](std::error::Error::source), so the full chain down to e.g.
+/// "connection refused" is available; see [`ElectionError::chain`].
+/// [`ElectionError::context`] and [`ErrorContext`] add context to an error
+/// without losing its variant or source.
+#[derive(Debug, Error, Serialize)]
+#[serde(tag = "type")]
 pub enum ElectionError {
     /// Validation error with optional field name
     ///
@@ -57,6 +74,10 @@ pub enum ElectionError {
         message: String,
         /// RPC endpoint URL that failed
         url: String,
+        /// Underlying error, e.g. the I/O, JSON or HTTP error that caused this one
+        #[source]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        source: Option<ErrorSource>,
     },
 
     /// Algorithm execution error
@@ -91,6 +112,10 @@ pub enum ElectionError {
     InvalidData {
         /// Error message describing the invalid data
         message: String,
+        /// Underlying error, e.g. the I/O, JSON or HTTP error that caused this one
+        #[source]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        source: Option<ErrorSource>,
     },
 
     /// File I/O error
@@ -103,7 +128,258 @@ pub enum ElectionError {
         message: String,
         /// Path to the file that caused the error
         path: PathBuf,
+        /// Underlying error, e.g. the I/O, JSON or HTTP error that caused this one
+        #[source]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        source: Option<ErrorSource>,
+    },
+
+    /// Operation timed out
+    ///
+    /// Occurs when a request or load does not finish within its deadline.
+    #[error("Timeout: {message} (after {timeout_ms} ms)")]
+    Timeout {
+        /// Error message describing what timed out
+        message: String,
+        /// Time limit that was exceeded, in milliseconds
+        timeout_ms: u64,
+    },
+
+    /// Operation cancelled before it finished
+    ///
+    /// Occurs when a running job or command is stopped on request, e.g.
+    /// through a [`CancellationToken`](crate::cancellation::CancellationToken).
+    #[error("Cancelled: {message}")]
+    Cancelled {
+        /// Error message describing what was cancelled
+        message: String,
+        /// Election phase that was running when the cancellation was noticed
+        #[serde(skip_serializing_if = "Option::is_none")]
+        phase: Option<ElectionPhase>,
+        /// Rounds of that phase that had finished, for phases that run in rounds
+        #[serde(skip_serializing_if = "Option::is_none")]
+        rounds_completed: Option<u32>,
+    },
+
+    /// Data does not match the expected schema version
+    ///
+    /// Occurs when reading files or responses written for a different
+    /// format version than this build understands.
+    #[error("Schema mismatch: {message} (expected {expected}, found {found})")]
+    SchemaMismatch {
+        /// Error message describing the mismatched data
+        message: String,
+        /// Schema version this build understands
+        expected: String,
+        /// Schema version found in the data
+        found: String,
     },
 }
 
+/// Broad class of an [`ElectionError`], used for CLI exit codes
+///
+/// Exit code 1 is left for unexpected failures and 2 for command line usage
+/// errors reported by the argument parser. Cancellation uses 130, the shell
+/// convention for a command stopped by Ctrl-C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Invalid input data, configuration or parameters
+    Validation,
+    /// Failure talking to an RPC endpoint or indexer
+    Rpc,
+    /// Failure inside an election algorithm
+    Algorithm,
+    /// Failure reading or writing files
+    Io,
+    /// Operation cancelled on request
+    Cancelled,
+}
+
+impl ErrorCategory {
+    /// Process exit code for errors of this category
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorCategory::Validation => 3,
+            ErrorCategory::Rpc => 4,
+            ErrorCategory::Algorithm => 5,
+            ErrorCategory::Io => 6,
+            ErrorCategory::Cancelled => 130,
+        }
+    }
+
+    /// Lowercase name, e.g. "validation"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Validation => "validation",
+            ErrorCategory::Rpc => "rpc",
+            ErrorCategory::Algorithm => "algorithm",
+            ErrorCategory::Io => "io",
+            ErrorCategory::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Boxed underlying error kept as the source of an [`ElectionError`]
+///
+/// Serializes as the messages of its error chain joined with ": ".
+pub struct ErrorSource(Box<dyn std::error::Error + Send + Sync + 'static>);
+
+impl ErrorSource {
+    /// Wrap an underlying error
+    pub fn new(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self(Box::new(error))
+    }
+
+    /// Underlying error
+    pub fn get_ref(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        &*self.0
+    }
+}
 
+impl std::fmt::Debug for ErrorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for ErrorSource {
+    // The wrapper is transparent: its source is the wrapped error's source
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl Serialize for ErrorSource {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut messages = vec![self.0.to_string()];
+        let mut next = self.0.source();
+        while let Some(error) = next {
+            messages.push(error.to_string());
+            next = error.source();
+        }
+        serializer.serialize_str(&messages.join(": "))
+    }
+}
+
+impl From<std::io::Error> for ErrorSource {
+    fn from(error: std::io::Error) -> Self {
+        Self::new(error)
+    }
+}
+
+impl From<serde_json::Error> for ErrorSource {
+    fn from(error: serde_json::Error) -> Self {
+        Self::new(error)
+    }
+}
+
+impl ElectionError {
+    /// Category of the error
+    ///
+    /// Malformed data, schema mismatches and too few candidates count as
+    /// validation errors; timeouts come from loading chain data and count as
+    /// RPC errors.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ElectionError::ValidationError { .. }
+            | ElectionError::InsufficientCandidates { .. }
+            | ElectionError::InvalidData { .. }
+            | ElectionError::SchemaMismatch { .. } => ErrorCategory::Validation,
+            ElectionError::RpcError { .. } | ElectionError::Timeout { .. } => ErrorCategory::Rpc,
+            ElectionError::AlgorithmError { .. } => ErrorCategory::Algorithm,
+            ElectionError::FileError { .. } => ErrorCategory::Io,
+            ElectionError::Cancelled { .. } => ErrorCategory::Cancelled,
+        }
+    }
+
+    /// Stable code of the error variant, e.g. "RPC_ERROR"
+    ///
+    /// Codes never change once released, so clients can match on them
+    /// instead of on messages.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ElectionError::ValidationError { .. } => "VALIDATION_ERROR",
+            ElectionError::RpcError { .. } => "RPC_ERROR",
+            ElectionError::AlgorithmError { .. } => "ALGORITHM_ERROR",
+            ElectionError::InsufficientCandidates { .. } => "INSUFFICIENT_CANDIDATES",
+            ElectionError::InvalidData { .. } => "INVALID_DATA",
+            ElectionError::FileError { .. } => "FILE_ERROR",
+            ElectionError::Timeout { .. } => "TIMEOUT",
+            ElectionError::Cancelled { .. } => "CANCELLED",
+            ElectionError::SchemaMismatch { .. } => "SCHEMA_MISMATCH",
+        }
+    }
+
+    /// Process exit code for the error, see [`ErrorCategory::exit_code`]
+    pub fn exit_code(&self) -> i32 {
+        self.category().exit_code()
+    }
+
+    /// Messages of the error and each of its sources, outermost first
+    ///
+    /// Sources whose message the previous entry already contains are left out,
+    /// as many errors include their cause's message in their own.
+    pub fn chain(&self) -> Vec<String> {
+        let mut messages = vec![self.to_string()];
+        let mut next = std::error::Error::source(self);
+        while let Some(error) = next {
+            let message = error.to_string();
+            if !messages.last().is_some_and(|previous| previous.contains(&message)) {
+                messages.push(message);
+            }
+            next = error.source();
+        }
+        messages
+    }
+
+    /// Prefix the error's message with `context`, keeping its variant and source
+    ///
+    /// `InsufficientCandidates` has no message and is returned unchanged.
+    pub fn context(mut self, context: impl std::fmt::Display) -> Self {
+        match &mut self {
+            ElectionError::ValidationError { message, .. }
+            | ElectionError::RpcError { message, .. }
+            | ElectionError::AlgorithmError { message, .. }
+            | ElectionError::InvalidData { message, .. }
+            | ElectionError::FileError { message, .. }
+            | ElectionError::Timeout { message, .. }
+            | ElectionError::Cancelled { message, .. }
+            | ElectionError::SchemaMismatch { message, .. } => {
+                *message = format!("{}: {}", context, message);
+            }
+            ElectionError::InsufficientCandidates { .. } => {}
+        }
+        self
+    }
+}
+
+/// Context helpers for results carrying an [`ElectionError`]
+pub trait ErrorContext<T> {
+    /// Prefix the error's message with `context`, see [`ElectionError::context`]
+    fn context(self, context: impl std::fmt::Display) -> Result<T, ElectionError>;
+
+    /// Like [`ErrorContext::context`], building the context only on error
+    fn with_context<C: std::fmt::Display>(self, context: impl FnOnce() -> C) -> Result<T, ElectionError>;
+}
+
+impl<T> ErrorContext<T> for Result<T, ElectionError> {
+    fn context(self, context: impl std::fmt::Display) -> Result<T, ElectionError> {
+        self.map_err(|e| e.context(context))
+    }
+
+    fn with_context<C: std::fmt::Display>(self, context: impl FnOnce() -> C) -> Result<T, ElectionError> {
+        self.map_err(|e| e.context(context()))
+    }
+}
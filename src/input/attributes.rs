@@ -0,0 +1,199 @@
+//! External candidate attributes (country, city, hosting provider)
+//!
+//! Attributes are not available on-chain, so they are supplied as a mapping
+//! keyed by account ID, either as CSV with an `account_id` header column or as
+//! JSON (an object keyed by account ID, or an array of objects with `account_id`).
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::election_data::ElectionData;
+use crate::models::validator::CandidateMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Attributes for a single candidate
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandidateAttributes {
+    /// Country the validator runs in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// City the validator runs in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// Hosting provider (cloud or data centre operator)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hosting_provider: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AttributeRow {
+    account_id: String,
+    #[serde(flatten)]
+    attributes: CandidateAttributes,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AttributeDocument {
+    Map(HashMap<String, CandidateAttributes>),
+    Rows(Vec<AttributeRow>),
+}
+
+/// Mapping from candidate account ID to attributes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CandidateAttributeMap {
+    entries: HashMap<String, CandidateAttributes>,
+}
+
+impl CandidateAttributeMap {
+    /// Load a mapping from a `.csv` or JSON file
+    pub fn load_from_file(path: &Path) -> Result<Self, ElectionError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read candidate attributes: {}", e),
+            path: path.to_path_buf(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+
+        let is_csv = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        let parsed = if is_csv {
+            Self::from_csv_str(&content)
+        } else {
+            Self::from_json_str(&content)
+        };
+        parsed.map_err(|e| ElectionError::FileError {
+            message: e.to_string(),
+            path: path.to_path_buf(),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+
+    /// Parse a JSON mapping
+    pub fn from_json_str(content: &str) -> Result<Self, ElectionError> {
+        let document: AttributeDocument =
+            serde_json::from_str(content).map_err(|e| ElectionError::InvalidData {
+                message: format!("Failed to parse candidate attributes JSON: {}", e),
+                source: Some(ErrorSource::new(e)),
+            })?;
+
+        let entries = match document {
+            AttributeDocument::Map(entries) => entries,
+            AttributeDocument::Rows(rows) => rows
+                .into_iter()
+                .map(|row| (row.account_id, row.attributes))
+                .collect(),
+        };
+        Ok(Self { entries })
+    }
+
+    /// Parse a CSV mapping
+    ///
+    /// The header must contain `account_id`; `country`, `city` and
+    /// `hosting_provider` (or `provider`) columns are read when present and
+    /// other columns are ignored. Fields may be double-quoted.
+    pub fn from_csv_str(content: &str) -> Result<Self, ElectionError> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let header = lines.next().ok_or_else(|| ElectionError::InvalidData {
+            message: "Candidate attributes CSV is empty".to_string(),
+            source: None,
+        })?;
+        let columns: Vec<String> = split_csv_line(header)
+            .into_iter()
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let column = |names: &[&str]| columns.iter().position(|c| names.contains(&c.as_str()));
+
+        let account_column = column(&["account_id"]).ok_or_else(|| ElectionError::InvalidData {
+            message: "Candidate attributes CSV has no account_id column".to_string(),
+            source: None,
+        })?;
+        let country_column = column(&["country"]);
+        let city_column = column(&["city"]);
+        let provider_column = column(&["hosting_provider", "provider"]);
+
+        let mut entries = HashMap::new();
+        for (index, line) in lines.enumerate() {
+            let fields = split_csv_line(line);
+            let field = |column: Option<usize>| {
+                column
+                    .and_then(|c| fields.get(c))
+                    .filter(|value| !value.is_empty())
+                    .cloned()
+            };
+            let account_id = field(Some(account_column)).ok_or_else(|| ElectionError::InvalidData {
+                message: format!("Candidate attributes CSV row {} has no account_id", index + 2),
+                source: None,
+            })?;
+            entries.insert(
+                account_id,
+                CandidateAttributes {
+                    country: field(country_column),
+                    city: field(city_column),
+                    hosting_provider: field(provider_column),
+                },
+            );
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Attributes for an account
+    pub fn get(&self, account_id: &str) -> Option<&CandidateAttributes> {
+        self.entries.get(account_id)
+    }
+
+    /// Number of accounts in the mapping
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the mapping is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Attach attributes to matching candidates, returning how many were matched
+    ///
+    /// Only attributes present in the mapping overwrite existing metadata.
+    pub fn apply(&self, data: &mut ElectionData) -> usize {
+        let mut matched = 0;
+        for candidate in &mut data.candidates {
+            if let Some(attributes) = self.entries.get(&candidate.account_id) {
+                matched += 1;
+                let metadata = candidate.metadata.get_or_insert_with(CandidateMetadata::default);
+                if attributes.country.is_some() {
+                    metadata.country = attributes.country.clone();
+                }
+                if attributes.city.is_some() {
+                    metadata.city = attributes.city.clone();
+                }
+                if attributes.hosting_provider.is_some() {
+                    metadata.hosting_provider = attributes.hosting_provider.clone();
+                }
+            }
+        }
+        matched
+    }
+}
+
+/// Split a CSV line into trimmed fields, honouring double quotes
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current.trim().to_string());
+    fields
+}
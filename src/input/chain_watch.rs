@@ -0,0 +1,176 @@
+//! Live chain watching for upcoming elections
+//!
+//! Subscribes to new block headers over WebSocket and tracks the
+//! `ElectionProviderMultiPhase::CurrentPhase` storage item so callers can react
+//! when the snapshot for the next election is taken.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::rpc::RpcLoader;
+use jsonrpsee::core::client::{Subscription, SubscriptionClientT};
+use jsonrpsee::rpc_params;
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Phase of the multi-phase election provider
+///
+/// Mirrors `pallet_election_provider_multi_phase::Phase`. The snapshot is
+/// created when the pallet leaves `Off`, so any other phase means a snapshot
+/// for the upcoming election is available on-chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ElectionPhase {
+    /// Nothing is happening
+    Off,
+    /// Signed submissions are accepted
+    Signed,
+    /// Unsigned submissions are accepted; carries whether the phase is open and the block it started
+    Unsigned {
+        /// Whether unsigned submissions are currently accepted
+        open: bool,
+        /// Block at which the phase started
+        started_at: u32,
+    },
+    /// The election failed and governance must provide a solution
+    Emergency,
+}
+
+impl ElectionPhase {
+    /// Decode a SCALE-encoded `Phase<u32>`
+    pub fn decode(bytes: &[u8]) -> Result<Self, ElectionError> {
+        let invalid = |message: &str| ElectionError::InvalidData {
+            message: format!("Invalid election phase encoding: {}", message),
+            source: None,
+        };
+
+        match bytes.first() {
+            Some(0) => Ok(ElectionPhase::Off),
+            Some(1) => Ok(ElectionPhase::Signed),
+            Some(2) => {
+                if bytes.len() < 6 {
+                    return Err(invalid("unsigned phase payload is too short"));
+                }
+                let mut started_at = [0u8; 4];
+                started_at.copy_from_slice(&bytes[2..6]);
+                Ok(ElectionPhase::Unsigned {
+                    open: bytes[1] != 0,
+                    started_at: u32::from_le_bytes(started_at),
+                })
+            }
+            Some(3) => Ok(ElectionPhase::Emergency),
+            Some(other) => Err(invalid(&format!("unknown variant {}", other))),
+            None => Err(invalid("empty value")),
+        }
+    }
+
+    /// Whether a snapshot for the upcoming election exists in this phase
+    pub fn is_snapshot_open(&self) -> bool {
+        !matches!(self, ElectionPhase::Off | ElectionPhase::Emergency)
+    }
+}
+
+/// Watches new blocks and reports election phase transitions
+pub struct ChainWatcher {
+    client: WsClient,
+    loader: RpcLoader,
+    url: String,
+}
+
+impl ChainWatcher {
+    /// Connect to a WebSocket endpoint, using `loader` for storage queries
+    pub async fn connect(ws_url: impl Into<String>, loader: RpcLoader) -> Result<Self, ElectionError> {
+        let url = ws_url.into();
+        let client = WsClientBuilder::default()
+            .build(&url)
+            .await
+            .map_err(|e| ElectionError::RpcError {
+                message: format!("Failed to connect to WebSocket endpoint: {}", e),
+                url: url.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+
+        Ok(Self { client, loader, url })
+    }
+
+    /// RPC loader used for storage queries
+    pub fn loader(&self) -> &RpcLoader {
+        &self.loader
+    }
+
+    /// Subscribe to new block headers
+    pub async fn subscribe_new_heads(&self) -> Result<Subscription<Value>, ElectionError> {
+        self.client
+            .subscribe("chain_subscribeNewHeads", rpc_params![], "chain_unsubscribeNewHeads")
+            .await
+            .map_err(|e| ElectionError::RpcError {
+                message: format!("Failed to subscribe to new heads: {}", e),
+                url: self.url.clone(),
+                source: Some(ErrorSource::new(e)),
+            })
+    }
+
+    /// Wait for the next block in which the snapshot phase opens
+    ///
+    /// Returns the block number and phase at the first block where the phase
+    /// moves from `Off` into one with a snapshot. Blocks observed while a
+    /// snapshot is already open do not trigger, so starting the watcher in the
+    /// middle of an election waits for the following one.
+    pub async fn wait_for_snapshot(
+        &self,
+        subscription: &mut Subscription<Value>,
+    ) -> Result<(u64, ElectionPhase), ElectionError> {
+        let mut previous: Option<ElectionPhase> = None;
+
+        while let Some(header) = subscription.next().await {
+            let header = header.map_err(|e| ElectionError::RpcError {
+                message: format!("New heads subscription failed: {}", e),
+                url: self.url.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            let block_number = parse_header_number(&header).ok_or_else(|| ElectionError::RpcError {
+                message: "Invalid header notification".to_string(),
+                url: self.url.clone(),
+                source: None,
+            })?;
+
+            let phase = match self.loader.election_phase_at(block_number).await {
+                Ok(phase) => phase,
+                Err(e) => {
+                    eprintln!("  ⚠ Warning: Could not read election phase at block {}: {}", block_number, e);
+                    continue;
+                }
+            };
+
+            if previous != Some(phase) {
+                eprintln!("Block {}: election phase {:?}", block_number, phase);
+            }
+            if previous == Some(ElectionPhase::Off) && phase.is_snapshot_open() {
+                return Ok((block_number, phase));
+            }
+            previous = Some(phase);
+        }
+
+        Err(ElectionError::RpcError {
+            message: "New heads subscription closed".to_string(),
+            url: self.url.clone(),
+            source: None,
+        })
+    }
+}
+
+/// Derive the HTTP(S) URL matching a WebSocket URL
+pub fn http_url_for(ws_url: &str) -> String {
+    if let Some(rest) = ws_url.strip_prefix("wss://") {
+        format!("https://{}", rest)
+    } else if let Some(rest) = ws_url.strip_prefix("ws://") {
+        format!("http://{}", rest)
+    } else {
+        ws_url.to_string()
+    }
+}
+
+/// Parse the hex block number out of a header notification
+fn parse_header_number(header: &Value) -> Option<u64> {
+    let number = header.get("number")?.as_str()?;
+    u64::from_str_radix(number.trim_start_matches("0x"), 16).ok()
+}
@@ -0,0 +1,280 @@
+//! Indexer loader for fetching historical election data from a GraphQL indexer
+//!
+//! Subsquid and SubQuery indexers keep per-era staking data in a database, so
+//! an era's candidates and nominators can be read with a few paged GraphQL
+//! queries instead of iterating raw storage on an archive node.
+//!
+//! Indexer schemas differ, so the queries are configurable through
+//! [`IndexerQueries`]. Each query receives `$era`, `$limit` and `$offset`
+//! variables and must return its rows under a fixed alias; use GraphQL aliases
+//! to map an indexer's field names onto the ones read here:
+//!
+//! - `candidates`: `accountId`, `stake`, optional `commission` (percent) and `blocked`
+//! - `nominators`: `accountId`, `stake`, `targets` (list of account IDs)
+//! - `elected`: `accountId`
+//!
+//! Stakes may be JSON numbers or strings, as indexers usually return big
+//! integers as strings.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::election_data::{ElectionData, ElectionMetadata};
+use crate::models::nominator::Nominator;
+use crate::models::validator::{CandidateMetadata, ValidatorCandidate};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// Default number of rows requested per page
+pub const DEFAULT_INDEXER_PAGE_SIZE: usize = 1000;
+
+/// GraphQL queries used to read an era from an indexer
+///
+/// The defaults follow the Subsquid filtering conventions (`where`, `orderBy`,
+/// `limit`, `offset`) over `eraValidators` and `eraNominators` entities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexerQueries {
+    /// Query returning the era's validator candidates under `candidates`
+    pub candidates: String,
+    /// Query returning the era's nominators under `nominators`
+    pub nominators: String,
+    /// Query returning the era's elected validators under `elected`
+    pub elected: String,
+}
+
+impl Default for IndexerQueries {
+    fn default() -> Self {
+        Self {
+            candidates: "query Candidates($era: Int!, $limit: Int!, $offset: Int!) { \
+                candidates: eraValidators(where: {era_eq: $era}, orderBy: id_ASC, limit: $limit, offset: $offset) { \
+                accountId stake: selfBonded commission blocked } }"
+                .to_string(),
+            nominators: "query Nominators($era: Int!, $limit: Int!, $offset: Int!) { \
+                nominators: eraNominators(where: {era_eq: $era}, orderBy: id_ASC, limit: $limit, offset: $offset) { \
+                accountId stake: bonded targets } }"
+                .to_string(),
+            elected: "query Elected($era: Int!, $limit: Int!, $offset: Int!) { \
+                elected: eraValidators(where: {era_eq: $era, elected_eq: true}, orderBy: id_ASC, limit: $limit, offset: $offset) { \
+                accountId } }"
+                .to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CandidateRow {
+    account_id: String,
+    stake: Value,
+    #[serde(default)]
+    commission: Option<f64>,
+    #[serde(default)]
+    blocked: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NominatorRow {
+    account_id: String,
+    stake: Value,
+    #[serde(default)]
+    targets: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ElectedRow {
+    account_id: String,
+}
+
+/// Indexer loader for fetching election data from a GraphQL endpoint
+#[derive(Debug, Clone)]
+pub struct IndexerLoader {
+    client: reqwest::Client,
+    url: String,
+    queries: IndexerQueries,
+    page_size: usize,
+}
+
+impl IndexerLoader {
+    /// Create a new indexer loader for a GraphQL endpoint
+    pub fn new(url: impl Into<String>) -> Result<Self, ElectionError> {
+        let url = url.into();
+        reqwest::Url::parse(&url).map_err(|e| ElectionError::RpcError {
+            message: format!("Invalid indexer URL: {}", e),
+            url: url.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url,
+            queries: IndexerQueries::default(),
+            page_size: DEFAULT_INDEXER_PAGE_SIZE,
+        })
+    }
+
+    /// Use queries matching the indexer's schema
+    pub fn with_queries(mut self, queries: IndexerQueries) -> Self {
+        self.queries = queries;
+        self
+    }
+
+    /// Set how many rows are requested per page (at least one)
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// The queries in use
+    pub fn queries(&self) -> &IndexerQueries {
+        &self.queries
+    }
+
+    /// Load the candidates and nominators of an era
+    ///
+    /// Votes for accounts that were not candidates in the era are dropped, as
+    /// the chain ignores them, and nominators left without targets are skipped.
+    pub async fn load_era(&self, era: u32) -> Result<ElectionData, ElectionError> {
+        let candidates = self
+            .fetch_rows::<CandidateRow>(&self.queries.candidates, "candidates", era)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let stake = parse_amount(&row.stake, "candidates.stake", &row.account_id)?;
+                let metadata = CandidateMetadata {
+                    commission_rate: row.commission.map(|percent| percent.clamp(0.0, 100.0).round() as u8),
+                    blocked: row.blocked,
+                    ..Default::default()
+                };
+                Ok(ValidatorCandidate::with_metadata(row.account_id, stake, metadata))
+            })
+            .collect::<Result<Vec<_>, ElectionError>>()?;
+
+        let candidate_ids: HashSet<&str> = candidates.iter().map(|c| c.account_id.as_str()).collect();
+        let mut nominators = Vec::new();
+        for row in self
+            .fetch_rows::<NominatorRow>(&self.queries.nominators, "nominators", era)
+            .await?
+        {
+            let stake = parse_amount(&row.stake, "nominators.stake", &row.account_id)?;
+            let mut seen = HashSet::new();
+            let targets: Vec<String> = row
+                .targets
+                .into_iter()
+                .filter(|target| candidate_ids.contains(target.as_str()) && seen.insert(target.clone()))
+                .collect();
+            if !targets.is_empty() {
+                nominators.push(Nominator {
+                    targets,
+                    ..Nominator::new(row.account_id, stake)
+                });
+            }
+        }
+
+        let data = ElectionData {
+            candidates,
+            nominators,
+            invulnerables: Vec::new(),
+            metadata: Some(ElectionMetadata {
+                block_number: None,
+                chain: None,
+                rpc_retries: None,
+                era: Some(era),
+                load_failures: Vec::new(),
+                stake_source: None,
+            }),
+            ..ElectionData::new()
+        };
+        data.validate()?;
+        Ok(data)
+    }
+
+    /// Validators elected in an era, for comparing predictions against the chain
+    pub async fn elected_at_era(&self, era: u32) -> Result<Vec<String>, ElectionError> {
+        Ok(self
+            .fetch_rows::<ElectedRow>(&self.queries.elected, "elected", era)
+            .await?
+            .into_iter()
+            .map(|row| row.account_id)
+            .collect())
+    }
+
+    /// Fetch every page of a query, reading rows from `data.{field}`
+    async fn fetch_rows<T: serde::de::DeserializeOwned>(
+        &self,
+        query: &str,
+        field: &str,
+        era: u32,
+    ) -> Result<Vec<T>, ElectionError> {
+        let mut rows = Vec::new();
+        loop {
+            let response = self
+                .query(query, json!({ "era": era, "limit": self.page_size, "offset": rows.len() }))
+                .await?;
+            let page = response
+                .get("data")
+                .and_then(|data| data.get(field))
+                .cloned()
+                .ok_or_else(|| ElectionError::InvalidData {
+                    message: format!("Indexer response has no `data.{}` field", field),
+                    source: None,
+                })?;
+            let page: Vec<T> = serde_json::from_value(page).map_err(|e| ElectionError::InvalidData {
+                message: format!("Invalid `{}` rows in indexer response: {}", field, e),
+                source: Some(ErrorSource::new(e)),
+            })?;
+            let last_page = page.len() < self.page_size;
+            rows.extend(page);
+            if last_page {
+                return Ok(rows);
+            }
+        }
+    }
+
+    /// Send one GraphQL request, turning GraphQL errors into an error
+    async fn query(&self, query: &str, variables: Value) -> Result<Value, ElectionError> {
+        let rpc_error = |message: String| ElectionError::RpcError {
+            message,
+            url: self.url.clone(),
+            source: None,
+        };
+
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .map_err(|e| rpc_error(format!("Indexer request failed: {}", e)))?;
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| rpc_error(format!("Invalid indexer response (status {}): {}", status, e)))?;
+
+        if let Some(errors) = body.get("errors").and_then(Value::as_array).filter(|e| !e.is_empty()) {
+            let messages: Vec<&str> = errors
+                .iter()
+                .map(|e| e.get("message").and_then(Value::as_str).unwrap_or("unknown error"))
+                .collect();
+            return Err(rpc_error(format!("Indexer query failed: {}", messages.join("; "))));
+        }
+        if !status.is_success() {
+            return Err(rpc_error(format!("Indexer returned status {}", status)));
+        }
+        Ok(body)
+    }
+}
+
+/// Parse a stake given as a JSON number or decimal string
+fn parse_amount(value: &Value, field: &str, account_id: &str) -> Result<u128, ElectionError> {
+    let parsed = match value {
+        Value::String(s) => s.parse::<u128>().ok(),
+        Value::Number(n) => n.as_u64().map(u128::from),
+        _ => None,
+    };
+    parsed.ok_or_else(|| ElectionError::ValidationError {
+        message: format!("Invalid stake {} for account {}", value, account_id),
+        field: Some(field.to_string()),
+    })
+}
@@ -1,16 +1,35 @@
 //! JSON input loader for loading election data from JSON files
 
-use crate::error::ElectionError;
+use crate::error::{ElectionError, ErrorSource};
 use crate::models::election_data::ElectionData;
+use crate::models::validation::{DedupPolicy, ValidationProfile};
 use std::path::PathBuf;
 
 /// JSON loader for loading election data from files
-pub struct JsonLoader;
+pub struct JsonLoader {
+    profile: ValidationProfile,
+    dedup: Option<DedupPolicy>,
+}
 
 impl JsonLoader {
     /// Create a new JSON loader
     pub fn new() -> Self {
-        Self
+        Self {
+            profile: ValidationProfile::ChainMirroring,
+            dedup: None,
+        }
+    }
+
+    /// Validate loaded data with a different strictness profile
+    pub fn with_validation_profile(mut self, profile: ValidationProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Collapse duplicated account IDs with `policy` before validating
+    pub fn with_dedup(mut self, policy: DedupPolicy) -> Self {
+        self.dedup = Some(policy);
+        self
     }
 
     /// Load election data from a JSON file
@@ -18,15 +37,37 @@ impl JsonLoader {
         let content = std::fs::read_to_string(&path).map_err(|e| ElectionError::FileError {
             message: format!("Failed to read file: {}", e),
             path: path.clone(),
+            source: Some(ErrorSource::new(e)),
         })?;
 
         let data: ElectionData = serde_json::from_str(&content).map_err(|e| ElectionError::FileError {
             message: format!("Failed to parse JSON: {}", e),
             path: path.clone(),
+            source: Some(ErrorSource::new(e)),
         })?;
+        self.prepare(data)
+    }
+
+    /// Load election data from a JSON document, e.g. an uploaded request body
+    pub fn load_from_str(&self, content: &str) -> Result<ElectionData, ElectionError> {
+        let data: ElectionData = serde_json::from_str(content).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to parse JSON: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        self.prepare(data)
+    }
+
+    /// Deduplicate and validate parsed data
+    fn prepare(&self, mut data: ElectionData) -> Result<ElectionData, ElectionError> {
+        if let Some(policy) = self.dedup {
+            let report = data.dedup(policy);
+            if !report.is_empty() {
+                eprintln!("Warning: resolved {} with the {} dedup policy", report.summary(), policy);
+            }
+        }
 
-        // Validate the loaded data
-        data.validate()?;
+        // Validate the loaded data; tolerated issues are reported when the election runs
+        data.validate_with_profile(self.profile)?;
 
         Ok(data)
     }
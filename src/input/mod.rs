@@ -3,8 +3,13 @@
 //! This module provides loaders for election data from different sources:
 //!
 //! - [`RpcLoader`] - Load data from Substrate RPC endpoints
+//! - [`IndexerLoader`] - Load historical eras from a Subsquid/SubQuery GraphQL indexer
 //! - [`JsonLoader`] - Load data from JSON files
+//! - [`StakingMinerLoader`] - Load snapshots dumped by the staking miner
 //! - [`SyntheticDataBuilder`] - Create synthetic election data programmatically
+//! - [`ChainWatcher`] - Follow new blocks and detect when an election snapshot is taken
+//! - [`CandidateAttributeMap`] - Attach off-chain attributes (country, provider) from CSV/JSON
+//! - [`SnapshotStore`] - Keep a local library of snapshots with their metadata and tags
 //!
 //! # Examples
 //!
@@ -49,8 +54,13 @@
 //! ```
 
 pub mod rpc;
+pub mod indexer;
 pub mod json;
+pub mod staking_miner;
 pub mod synthetic;
+pub mod chain_watch;
+pub mod attributes;
+pub mod snapshot_store;
 
 /// RPC loader for fetching election data from Substrate nodes
 ///
@@ -58,15 +68,43 @@ pub mod synthetic;
 /// nominators, and stake information at a specific block.
 pub use rpc::RpcLoader;
 
+/// Indexer loader for fetching historical eras
+///
+/// Reads an era's candidates, nominators and elected set from a GraphQL
+/// indexer, without needing an archive node.
+pub use indexer::{IndexerLoader, IndexerQueries};
+
 /// JSON loader for reading election data from files
 ///
 /// Loads election data from JSON files that match the `ElectionData` schema.
 pub use json::JsonLoader;
 
+/// Loader for staking-miner snapshot dumps
+///
+/// Converts the miner's solver snapshot into election data, so solutions can
+/// be compared on identical inputs.
+pub use staking_miner::{StakingMinerLoader, StakingMinerSnapshot};
+
 /// Builder for creating synthetic election data
 ///
 /// Allows creating election data with arbitrary account IDs that don't
 /// need to exist on-chain. Useful for testing and "what-if" scenarios.
 pub use synthetic::SyntheticDataBuilder;
 
+/// Watcher for upcoming on-chain elections
+///
+/// Subscribes to new blocks over WebSocket and reports when the election
+/// provider opens the snapshot phase.
+pub use chain_watch::{ChainWatcher, ElectionPhase};
+
+/// Off-chain candidate attributes
+///
+/// Maps account IDs to country, city and hosting provider, loaded from a
+/// user-provided CSV or JSON file.
+pub use attributes::{CandidateAttributeMap, CandidateAttributes};
 
+/// Local snapshot library
+///
+/// Stores snapshots by content hash with their chain, block, era, size and
+/// tags, for listing, tagging and pruning.
+pub use snapshot_store::{PrunePolicy, SnapshotEntry, SnapshotStore};
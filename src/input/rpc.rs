@@ -1,46 +1,451 @@
 //! RPC input loader for fetching election data from Substrate RPC endpoints
 
-use crate::error::ElectionError;
-use crate::models::election_data::{ElectionData, ElectionMetadata};
-use crate::models::nominator::Nominator;
-use crate::models::validator::ValidatorCandidate;
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::chain_watch::ElectionPhase;
+use crate::models::election_data::{ElectionData, ElectionMetadata, StakeSource};
+use crate::models::nominator::{Nominator, NominatorMetadata};
+use crate::models::onchain_solution::{decode_snapshot, OnchainSolution};
+use crate::models::validator::{CandidateActivity, CandidateMetadata, EraActivity, ValidatorCandidate};
 use jsonrpsee::core::client::ClientT;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::core::traits::ToRpcParams;
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::hash::Hasher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use twox_hash::XxHash64;
 
-/// Maximum number of retry attempts for transient errors
-const MAX_RETRIES: u32 = 5;
-/// Initial delay in seconds before first retry
-const INITIAL_RETRY_DELAY_SECS: u64 = 2;
+/// Default number of storage queries in flight at once
+pub const DEFAULT_RPC_CONCURRENCY: usize = 16;
+/// Default time allowed for a single RPC request attempt
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Lowest rate limit accepted, one request an hour, in requests per second
+pub const MIN_RATE_LIMIT: f64 = 1.0 / 3600.0;
+/// Default name of the `pallet-elections-phragmen` instance read by council loads
+pub const DEFAULT_ELECTIONS_PALLET: &str = "Elections";
+/// Timeout given to the HTTP client itself; per-request timeouts are enforced in [`RpcLoader::request`]
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+/// Failed queries listed individually in a bulk fetch warning
+const REPORTED_FAILURES: usize = 5;
+
+/// Retry and backoff policy for RPC calls
+///
+/// Delays double after each failed attempt, starting at `initial_delay`;
+/// `jitter` spreads each delay randomly by up to that fraction in either
+/// direction so concurrent queries don't retry in lockstep. No delay, jittered
+/// or not, exceeds `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts per call, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Random spread applied to each delay, as a fraction (0.0-1.0)
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 6,
+            initial_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create the default policy (6 attempts, 2s initial delay, 30s cap, 20% jitter)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Set the total number of attempts (at least one)
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Set the upper bound on any single delay
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set the random spread applied to each delay (clamped to 0.0-1.0)
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Delay before retry number `retry` (zero-based), before jitter
+    pub fn base_delay(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry).unwrap_or(u32::MAX);
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Delay before retry number `retry` (zero-based), with jitter applied and
+    /// capped at `max_delay`
+    pub fn delay(&self, retry: u32) -> Duration {
+        let base = self.base_delay(retry);
+        if self.jitter <= 0.0 {
+            return base;
+        }
+        // Uniform in [-1, 1], seeded from the clock; good enough to de-synchronise retries
+        let mut hasher = XxHash64::with_seed(u64::from(retry));
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default(),
+        );
+        let spread = hasher.finish() as f64 / u64::MAX as f64 * 2.0 - 1.0;
+        base.mul_f64((1.0 + self.jitter * spread).max(0.0)).min(self.max_delay)
+    }
+
+    /// Run `f` until it succeeds, fails with a non-retryable error, or attempts run out
+    ///
+    /// `on_retry` is called with the error, the failed attempt number (from 1)
+    /// and the delay before the next attempt.
+    pub async fn retry<F, Fut, T, E>(
+        &self,
+        mut f: F,
+        is_retryable: impl Fn(&E) -> bool,
+        mut on_retry: impl FnMut(&E, u32, Duration),
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match f().await {
+                Err(e) if attempt < self.max_attempts && is_retryable(&e) => {
+                    let delay = self.delay(attempt - 1);
+                    on_retry(&e, attempt, delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// A retried RPC call, reported to the loader's retry observer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryEvent {
+    /// RPC method being called
+    pub method: String,
+    /// Failed attempt number, from 1
+    pub attempt: u32,
+    /// Total attempts allowed by the policy
+    pub max_attempts: u32,
+    /// Delay before the next attempt
+    pub delay: Duration,
+    /// Error from the failed attempt
+    pub error: String,
+}
+
+/// Callback notified of every retried RPC call
+pub type RetryObserver = Arc<dyn Fn(&RetryEvent) + Send + Sync>;
+
+/// Spaces out request starts to stay under a requests-per-second limit
+#[derive(Debug)]
+struct Throttle {
+    interval: Duration,
+    next_slot: tokio::sync::Mutex<Instant>,
+}
+
+impl Throttle {
+    fn new(requests_per_second: f64) -> Self {
+        Self {
+            interval: Duration::from_secs_f64(1.0 / requests_per_second),
+            next_slot: tokio::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait for the next free request slot
+    async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot.into()).await;
+    }
+}
+
+/// Storage queries that failed during a bulk fetch
+///
+/// Bulk fetches keep going past individual failures; the failures are
+/// collected here and reported once, with the first few listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchFailures {
+    what: String,
+    attempted: usize,
+    messages: Vec<String>,
+}
+
+impl FetchFailures {
+    /// Start collecting failures for `attempted` queries of `what`
+    pub fn new(what: impl Into<String>, attempted: usize) -> Self {
+        Self {
+            what: what.into(),
+            attempted,
+            messages: Vec::new(),
+        }
+    }
+
+    /// Record a failed query
+    pub fn record(&mut self, message: impl Into<String>) {
+        self.messages.push(message.into());
+    }
+
+    /// Number of failed queries
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether every query succeeded
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Human-readable summary, or `None` if nothing failed
+    pub fn summary(&self) -> Option<String> {
+        if self.messages.is_empty() {
+            return None;
+        }
+        let mut summary = format!(
+            "{} of {} {} queries failed",
+            self.messages.len(),
+            self.attempted,
+            self.what
+        );
+        for message in self.messages.iter().take(REPORTED_FAILURES) {
+            summary.push_str(&format!("\n    - {}", message));
+        }
+        if self.messages.len() > REPORTED_FAILURES {
+            summary.push_str(&format!("\n    ... and {} more", self.messages.len() - REPORTED_FAILURES));
+        }
+        Some(summary)
+    }
+
+}
 
 /// RPC loader for fetching election data from Substrate nodes
 pub struct RpcLoader {
     client: HttpClient,
     url: String,
+    concurrency: usize,
+    retry_policy: RetryPolicy,
+    retry_observer: Option<RetryObserver>,
+    retries: AtomicU64,
+    load_failures: Mutex<Vec<String>>,
+    request_timeout: Duration,
+    deadline: Option<Duration>,
+    throttle: Option<Throttle>,
+    elections_pallet: String,
+    activity_eras: u32,
+    stake_source: StakeSource,
+    resolve_controllers: bool,
 }
 
 impl RpcLoader {
     /// Create a new RPC loader
     pub fn new(url: impl Into<String>) -> Result<Self, ElectionError> {
         let url_str = url.into();
-        // Configure timeouts to prevent hanging
-        // 30 seconds for request timeout
         let client = HttpClientBuilder::default()
-            .request_timeout(std::time::Duration::from_secs(30))
+            .request_timeout(CLIENT_TIMEOUT)
             .build(&url_str)
             .map_err(|e| ElectionError::RpcError {
                 message: format!("Failed to create RPC client: {}", e),
                 url: url_str.clone(),
+                source: Some(ErrorSource::new(e)),
             })?;
 
         Ok(Self {
             client,
             url: url_str,
+            concurrency: DEFAULT_RPC_CONCURRENCY,
+            retry_policy: RetryPolicy::default(),
+            retry_observer: None,
+            retries: AtomicU64::new(0),
+            load_failures: Mutex::new(Vec::new()),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            deadline: None,
+            throttle: None,
+            elections_pallet: DEFAULT_ELECTIONS_PALLET.to_string(),
+            activity_eras: 0,
+            stake_source: StakeSource::Ledger,
+            resolve_controllers: false,
         })
     }
 
+    /// Set how long a single request attempt may take before it is abandoned
+    ///
+    /// Timed-out attempts count as transient errors and are retried under the
+    /// retry policy.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout.min(CLIENT_TIMEOUT);
+        self
+    }
+
+    /// Limit how long [`load_at_block`](Self::load_at_block) and
+    /// [`load_latest`](Self::load_latest) may run in total, retries included
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Start at most `requests_per_second` requests per second
+    ///
+    /// Retries count towards the limit. Zero or infinity removes the limit;
+    /// NaN, negative values and values below [`MIN_RATE_LIMIT`] are rejected.
+    pub fn with_rate_limit(mut self, requests_per_second: f64) -> Result<Self, ElectionError> {
+        if requests_per_second == 0.0 || requests_per_second == f64::INFINITY {
+            self.throttle = None;
+            return Ok(self);
+        }
+        if requests_per_second.is_nan() || requests_per_second < MIN_RATE_LIMIT {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "RPC rate limit must be at least one request an hour ({}), but got {}",
+                    MIN_RATE_LIMIT, requests_per_second
+                ),
+                field: Some("rpc_rate_limit".to_string()),
+            });
+        }
+        self.throttle = Some(Throttle::new(requests_per_second));
+        Ok(self)
+    }
+
+    /// Read council elections from a differently named `pallet-elections-phragmen` instance
+    ///
+    /// Runtimes name the pallet `Elections`, `PhragmenElection` or similar.
+    pub fn with_elections_pallet(mut self, pallet: impl Into<String>) -> Self {
+        self.elections_pallet = pallet.into();
+        self
+    }
+
+    /// Also load each candidate's era points in the `eras` eras before the active one
+    ///
+    /// The points end up in each candidate's `activity` metadata. Eras older
+    /// than the chain's history depth have no points left and are skipped.
+    pub fn with_activity_eras(mut self, eras: u32) -> Self {
+        self.activity_eras = eras;
+        self
+    }
+
+    /// Choose where nominator stakes are read from (default: the bonded ledger)
+    pub fn with_stake_source(mut self, source: StakeSource) -> Self {
+        self.stake_source = source;
+        self
+    }
+
+    /// Also read each stash's controller (`Staking::Bonded`)
+    ///
+    /// Controllers that differ from their stash are stored in the candidate and
+    /// nominator metadata, and nominators whose ledger is keyed by such a
+    /// controller get their stake from it. This matters for snapshots of eras
+    /// before controllers were deprecated; it costs one query per account.
+    pub fn with_controller_mapping(mut self, resolve: bool) -> Self {
+        self.resolve_controllers = resolve;
+        self
+    }
+
+    /// Time allowed for a single request attempt
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// Total time allowed for loading a snapshot, if limited
+    pub fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    /// Maximum requests started per second, if limited
+    pub fn rate_limit(&self) -> Option<f64> {
+        self.throttle.as_ref().map(|t| 1.0 / t.interval.as_secs_f64())
+    }
+
+    /// Set the retry and backoff policy applied to every RPC call
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Report retried calls to `observer` instead of printing them to stderr
+    pub fn with_retry_observer(mut self, observer: impl Fn(&RetryEvent) + Send + Sync + 'static) -> Self {
+        self.retry_observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// The retry and backoff policy in use
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Number of RPC calls retried by this loader so far
+    pub fn retry_count(&self) -> u64 {
+        self.retries.load(Ordering::Relaxed)
+    }
+
+    /// Warn about failed storage queries and remember them for the data's metadata
+    fn report_failures(&self, failures: &FetchFailures) {
+        if let Some(summary) = failures.summary() {
+            eprintln!("  ⚠ Warning: {}", summary);
+            self.record_load_failure(summary);
+        }
+    }
+
+    fn record_load_failure(&self, message: String) {
+        self.load_failures.lock().unwrap_or_else(|e| e.into_inner()).push(message);
+    }
+
+    fn load_failure_count(&self) -> usize {
+        self.load_failures.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Load failures recorded after the first `before`
+    fn load_failures_since(&self, before: usize) -> Vec<String> {
+        let failures = self.load_failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures.get(before..).unwrap_or_default().to_vec()
+    }
+
+    /// Set how many storage queries may be in flight at once (at least one)
+    ///
+    /// Per-account storage (ledgers, nominations, preferences, identities) is
+    /// fetched with this many concurrent requests. Public endpoints may rate
+    /// limit aggressive settings; archive nodes you operate can take more.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Number of storage queries that may be in flight at once
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
     /// Get suggested alternative RPC endpoints based on current URL
     fn get_alternative_endpoints(&self) -> Vec<&str> {
         let url_lower = self.url.to_lowercase();
@@ -95,81 +500,155 @@ impl RpcLoader {
                 msg_lower.contains("server returned an error status code") ||
                 msg_lower.contains("networking or low-level protocol error")
             }
+            ElectionError::Timeout { .. } => true,
             _ => false,
         }
     }
 
-    /// Retry an RPC call with exponential backoff for transient errors
-    async fn retry_rpc_call<F, Fut, T>(&self, mut f: F) -> Result<T, ElectionError>
+    /// Retry an RPC call according to the retry policy
+    ///
+    /// Only transient errors are retried. Every retry is counted and reported
+    /// to the retry observer, or printed when none is set.
+    async fn retry_rpc_call<F, Fut, T>(&self, method: &str, f: F) -> Result<T, ElectionError>
     where
         F: FnMut() -> Fut,
         Fut: std::future::Future<Output = Result<T, ElectionError>>,
     {
-        for attempt in 0..=MAX_RETRIES {
-            match f().await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    // Check if error is retryable
-                    if !self.is_retryable_error(&e) {
-                        // Not retryable, return immediately
-                        return Err(e);
-                    }
-                    
-                    // If this was the last attempt, return enhanced error with suggestions
-                    if attempt >= MAX_RETRIES {
-                        return Err(match &e {
-                            ElectionError::RpcError { message, url } => {
-                                let alternatives = self.get_alternative_endpoints();
-                                let alternatives_list = alternatives
-                                    .iter()
-                                    .map(|alt| format!("  - {}", alt))
-                                    .collect::<Vec<_>>()
-                                    .join("\n");
-                                
-                                ElectionError::RpcError {
-                                    message: format!(
-                                        "{}\n\n\
-                                        All {} retry attempts failed. The RPC endpoint appears to be unavailable.\n\n\
-                                        Suggested alternative endpoints:\n{}\n\n\
-                                        Other options:\n\
-                                        - Use --input-file with JSON data instead\n\
-                                        - Wait a few minutes and try again\n\
-                                        - Check the endpoint status page",
-                                        message,
-                                        MAX_RETRIES + 1,
-                                        alternatives_list
-                                    ),
-                                    url: url.clone(),
-                                }
-                            }
-                            _ => e,
-                        });
+        self.retry_policy
+            .retry(
+                f,
+                |e| self.is_retryable_error(e),
+                |e, attempt, delay| {
+                    self.retries.fetch_add(1, Ordering::Relaxed);
+                    let event = RetryEvent {
+                        method: method.to_string(),
+                        attempt,
+                        max_attempts: self.retry_policy.max_attempts,
+                        delay,
+                        error: e.to_string(),
+                    };
+                    match self.retry_observer {
+                        Some(ref observer) => observer(&event),
+                        None => {
+                            eprintln!(
+                                "  ⚠ RPC error calling {} (attempt {}/{}), retrying in {:.1} seconds...",
+                                event.method,
+                                event.attempt,
+                                event.max_attempts,
+                                event.delay.as_secs_f64()
+                            );
+                            std::io::Write::flush(&mut std::io::stderr()).ok();
+                        }
                     }
-                    
-                    // Calculate exponential backoff delay with cap at 30 seconds
-                    let delay_secs = std::cmp::min(
-                        INITIAL_RETRY_DELAY_SECS * (1u64 << attempt),
-                        30
-                    );
-                    eprintln!("  ⚠ RPC error (attempt {}/{}), retrying in {} seconds...", 
-                             attempt + 1, MAX_RETRIES + 1, delay_secs);
-                    std::io::Write::flush(&mut std::io::stderr()).ok();
-                    
-                    // Wait before retrying
-                    tokio::time::sleep(std::time::Duration::from_secs(delay_secs)).await;
+                },
+            )
+            .await
+    }
+
+    /// Make an RPC request under the retry policy, rate limit and request timeout
+    ///
+    /// Errors are reported as `"{context}: {error}"`.
+    async fn request<R, P>(&self, method: &str, params: P, context: &str) -> Result<R, ElectionError>
+    where
+        R: serde::de::DeserializeOwned,
+        P: ToRpcParams + Clone + Send,
+    {
+        self.retry_rpc_call(method, || {
+            let params = params.clone();
+            async move {
+                if let Some(ref throttle) = self.throttle {
+                    throttle.acquire().await;
+                }
+                match tokio::time::timeout(self.request_timeout, self.client.request(method, params)).await {
+                    Ok(Ok(value)) => Ok(value),
+                    Ok(Err(e)) => Err(ElectionError::RpcError {
+                        message: format!("{}: {}", context, e),
+                        url: self.url.clone(),
+                        source: Some(ErrorSource::new(e)),
+                    }),
+                    Err(_) => Err(ElectionError::Timeout {
+                        message: format!("{}: {} request to {} got no response", context, method, self.url),
+                        timeout_ms: self.request_timeout.as_millis() as u64,
+                    }),
                 }
             }
-        }
-        
-        // This should never be reached, but handle it just in case
-        Err(ElectionError::RpcError {
-            message: "Unknown error during retry".to_string(),
-            url: self.url.clone(),
         })
+        .await
+    }
+
+    /// Add alternative endpoints to an RPC error that survived every retry
+    fn with_endpoint_suggestions(&self, error: ElectionError) -> ElectionError {
+        if !self.is_retryable_error(&error) {
+            return error;
+        }
+        match error {
+            ElectionError::RpcError { message, url, source } => {
+                let alternatives_list = self
+                    .get_alternative_endpoints()
+                    .iter()
+                    .map(|alt| format!("  - {}", alt))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                ElectionError::RpcError {
+                    message: format!(
+                        "{}\n\n\
+                        All {} attempts failed. The RPC endpoint appears to be unavailable.\n\n\
+                        Suggested alternative endpoints:\n{}\n\n\
+                        Other options:\n\
+                        - Use --input-file with JSON data instead\n\
+                        - Wait a few minutes and try again\n\
+                        - Check the endpoint status page",
+                        message,
+                        self.retry_policy.max_attempts,
+                        alternatives_list
+                    ),
+                    url,
+                    source,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Run `load` under the loader's deadline, if any
+    async fn within_deadline<T>(
+        &self,
+        what: &str,
+        load: impl std::future::Future<Output = Result<T, ElectionError>>,
+    ) -> Result<T, ElectionError> {
+        let Some(deadline) = self.deadline else {
+            return load.await;
+        };
+        tokio::time::timeout(deadline, load).await.map_err(|_| ElectionError::Timeout {
+            message: format!(
+                "Deadline of {:.1} seconds exceeded while {} from {}",
+                deadline.as_secs_f64(),
+                what,
+                self.url
+            ),
+            timeout_ms: deadline.as_millis() as u64,
+        })?
     }
 
     /// Load election data at a specific block number
     pub async fn load_at_block(&self, block_number: u64) -> Result<ElectionData, ElectionError> {
+        self.within_deadline(
+            &format!("loading election data at block {}", block_number),
+            self.fetch_at_block(block_number),
+        )
+        .await
+    }
+
+    /// Load election data from the latest block
+    pub async fn load_latest(&self) -> Result<ElectionData, ElectionError> {
+        self.within_deadline("loading election data from the latest block", self.fetch_latest())
+            .await
+    }
+
+    async fn fetch_at_block(&self, block_number: u64) -> Result<ElectionData, ElectionError> {
+        let retries_before = self.retry_count();
+        let failures_before = self.load_failure_count();
         eprintln!("Fetching data from block {}...", block_number);
         std::io::Write::flush(&mut std::io::stderr()).ok();
         
@@ -193,6 +672,7 @@ impl RpcLoader {
                 block_number
             ),
             url: self.url.clone(),
+            source: None,
         })??;
         
         eprintln!("  ✓ Block hash: {}", block_hash);
@@ -213,11 +693,19 @@ impl RpcLoader {
                 block_hash
             ),
             url: self.url.clone(),
+            source: None,
         })??;
         
         eprintln!("  ✓ Found {} validators", candidates.len());
         std::io::Write::flush(&mut std::io::stderr()).ok();
 
+        // Fetch validator preferences (commission, blocked flag)
+        let mut candidates = candidates;
+        self.fetch_validator_prefs(&mut candidates, &block_hash).await;
+        self.fetch_identity_parents(&mut candidates, &block_hash).await;
+        self.fetch_activity(&mut candidates, &block_hash).await;
+        let invulnerables = self.fetch_invulnerables(&block_hash).await;
+
         // Fetch nominators and their votes
         eprintln!("  → Fetching nominators (this may take a while, timeout: 60 seconds)...");
         std::io::Write::flush(&mut std::io::stderr()).ok();
@@ -235,9 +723,11 @@ impl RpcLoader {
                     block_hash
                 ),
                 url: self.url.clone(),
+                source: None,
             })
         }).unwrap_or_else(|e| {
             eprintln!("  ⚠ Warning: Could not fetch nominators from RPC: {}", e);
+            self.record_load_failure(format!("Could not fetch nominators, proceeding with none: {}", e));
             eprintln!("  → Proceeding with zero nominators - election will use only validator self-stakes.");
             std::io::Write::flush(&mut std::io::stderr()).ok();
             Vec::new()
@@ -245,19 +735,29 @@ impl RpcLoader {
         
         eprintln!("  ✓ Found {} nominators", nominators.len());
         std::io::Write::flush(&mut std::io::stderr()).ok();
+        let mut nominators = nominators;
+        self.fetch_controllers(&mut candidates, &mut nominators, &block_hash).await;
+        let stake_source = self.apply_stake_source(&mut nominators, &block_hash).await;
 
         Ok(ElectionData {
             candidates,
             nominators,
+            invulnerables,
             metadata: Some(ElectionMetadata {
                 block_number: Some(block_number),
                 chain: None,
+                rpc_retries: Some(self.retry_count() - retries_before),
+                era: None,
+                load_failures: self.load_failures_since(failures_before),
+                stake_source: Some(stake_source),
             }),
+            ..ElectionData::new()
         })
     }
 
-    /// Load election data from the latest block
-    pub async fn load_latest(&self) -> Result<ElectionData, ElectionError> {
+    async fn fetch_latest(&self) -> Result<ElectionData, ElectionError> {
+        let retries_before = self.retry_count();
+        let failures_before = self.load_failure_count();
         eprintln!("Fetching data from latest block...");
         std::io::Write::flush(&mut std::io::stderr()).ok();
         
@@ -278,6 +778,7 @@ impl RpcLoader {
                 - Checking your network connection"
             ),
             url: self.url.clone(),
+            source: None,
         })??;
         
         eprintln!("  ✓ Block hash: {}", block_hash);
@@ -298,11 +799,19 @@ impl RpcLoader {
                 block_hash
             ),
             url: self.url.clone(),
+            source: None,
         })??;
         
         eprintln!("  ✓ Found {} validators", candidates.len());
         std::io::Write::flush(&mut std::io::stderr()).ok();
 
+        // Fetch validator preferences (commission, blocked flag)
+        let mut candidates = candidates;
+        self.fetch_validator_prefs(&mut candidates, &block_hash).await;
+        self.fetch_identity_parents(&mut candidates, &block_hash).await;
+        self.fetch_activity(&mut candidates, &block_hash).await;
+        let invulnerables = self.fetch_invulnerables(&block_hash).await;
+
         // Fetch nominators and their votes
         eprintln!("  → Fetching nominators (this may take a while, timeout: 60 seconds)...");
         std::io::Write::flush(&mut std::io::stderr()).ok();
@@ -320,9 +829,11 @@ impl RpcLoader {
                     block_hash
                 ),
                 url: self.url.clone(),
+                source: None,
             })
         }).unwrap_or_else(|e| {
             eprintln!("  ⚠ Warning: Could not fetch nominators from RPC: {}", e);
+            self.record_load_failure(format!("Could not fetch nominators, proceeding with none: {}", e));
             eprintln!("  → Proceeding with zero nominators - election will use only validator self-stakes.");
             std::io::Write::flush(&mut std::io::stderr()).ok();
             Vec::new()
@@ -330,6 +841,9 @@ impl RpcLoader {
         
         eprintln!("  ✓ Found {} nominators", nominators.len());
         std::io::Write::flush(&mut std::io::stderr()).ok();
+        let mut nominators = nominators;
+        self.fetch_controllers(&mut candidates, &mut nominators, &block_hash).await;
+        let stake_source = self.apply_stake_source(&mut nominators, &block_hash).await;
 
         // Get latest block number
         let latest_block = self.get_latest_block_number().await?;
@@ -337,66 +851,325 @@ impl RpcLoader {
         Ok(ElectionData {
             candidates,
             nominators,
+            invulnerables,
             metadata: Some(ElectionMetadata {
                 block_number: Some(latest_block),
                 chain: None,
+                rpc_retries: Some(self.retry_count() - retries_before),
+                era: None,
+                load_failures: self.load_failures_since(failures_before),
+                stake_source: Some(stake_source),
             }),
+            ..ElectionData::new()
         })
     }
 
-    /// Get the latest block number
-    async fn get_latest_block_number(&self) -> Result<u64, ElectionError> {
-        self.retry_rpc_call(|| async {
-            let response: Value = self
-                .client
-                .request("chain_getHeader", Vec::<String>::new())
-                .await
-                .map_err(|e| ElectionError::RpcError {
-                    message: format!("Failed to get latest header: {}", e),
-                    url: self.url.clone(),
-                })?;
-
-            let number = response
-                .get("number")
-                .and_then(|n| n.as_str())
-                .ok_or_else(|| ElectionError::RpcError {
-                    message: "Invalid header response".to_string(),
-                    url: self.url.clone(),
-                })?;
+    /// Load council election data (`pallet-elections-phragmen`) at a specific block
+    ///
+    /// Candidates are the applicants plus the current members and runners-up,
+    /// who stand again automatically; they carry no stake. Voters become
+    /// nominators weighted by their locked stake. Run the result with
+    /// [`ElectionKind::Council`](crate::types::ElectionKind::Council).
+    pub async fn load_council_at_block(&self, block_number: u64) -> Result<ElectionData, ElectionError> {
+        self.within_deadline(&format!("loading council election data at block {}", block_number), async {
+            let block_hash = self.get_block_hash(block_number).await?;
+            self.fetch_council(&block_hash, block_number).await
+        })
+        .await
+    }
 
-            // Parse hex number
-            let number = number.trim_start_matches("0x");
-            u64::from_str_radix(number, 16).map_err(|e| ElectionError::RpcError {
-                message: format!("Failed to parse block number: {}", e),
-                url: self.url.clone(),
-            })
+    /// Load council election data (`pallet-elections-phragmen`) from the latest block
+    pub async fn load_council_latest(&self) -> Result<ElectionData, ElectionError> {
+        self.within_deadline("loading council election data from the latest block", async {
+            let block_number = self.get_latest_block_number().await?;
+            let block_hash = self.get_block_hash(block_number).await?;
+            self.fetch_council(&block_hash, block_number).await
         })
         .await
     }
 
-    /// Get block hash for a given block number
-    async fn get_block_hash(&self, block_number: u64) -> Result<String, ElectionError> {
-        self.retry_rpc_call(|| async {
-            let response: Value = self
-                .client
-                .request(
-                    "chain_getBlockHash",
-                    (format!("0x{:x}", block_number),),
-                )
-                .await
-                .map_err(|e| ElectionError::RpcError {
-                    message: format!("Failed to get block hash: {}", e),
-                    url: self.url.clone(),
-                })?;
+    async fn fetch_council(&self, block_hash: &str, block_number: u64) -> Result<ElectionData, ElectionError> {
+        let retries_before = self.retry_count();
+        let failures_before = self.load_failure_count();
+        let pallet = self.elections_pallet.as_str();
+        eprintln!("Fetching {} council election data from block {}...", pallet, block_number);
+
+        let mut candidate_ids: Vec<[u8; 32]> = Vec::new();
+        for item in ["Members", "RunnersUp"] {
+            let seats: Vec<SeatHolder> = self.decode_storage_value(pallet, item, block_hash).await?.unwrap_or_default();
+            candidate_ids.extend(seats.into_iter().map(|seat| seat.who));
+        }
+        let applicants: Vec<([u8; 32], u128)> =
+            self.decode_storage_value(pallet, "Candidates", block_hash).await?.unwrap_or_default();
+        candidate_ids.extend(applicants.into_iter().map(|(who, _deposit)| who));
+
+        let mut seen = std::collections::HashSet::new();
+        let candidates: Vec<ValidatorCandidate> = candidate_ids
+            .into_iter()
+            .filter(|who| seen.insert(*who))
+            .map(|who| ValidatorCandidate::new(format!("0x{}", hex::encode(who)), 0))
+            .collect();
+        eprintln!("  ✓ Found {} council candidates", candidates.len());
+
+        // Voting is a Twox64Concat map: prefix + twox64(AccountId) + AccountId
+        let voting_prefix = self.encode_storage_key(pallet, "Voting")?;
+        let keys = self.get_storage_keys_paged(&voting_prefix, block_hash).await?;
+        let values = self.get_storage_values(&keys, block_hash).await;
+        let mut failures = FetchFailures::new(format!("{}::Voting", pallet), keys.len());
+        let mut nominators = Vec::new();
+        for (key, value) in keys.iter().zip(values) {
+            let voter = match value {
+                Ok(Some(bytes)) => match <Voter as parity_scale_codec::Decode>::decode(&mut &bytes[..]) {
+                    Ok(voter) => voter,
+                    Err(e) => {
+                        failures.record(format!("{}: {}", key, e));
+                        continue;
+                    }
+                },
+                Ok(None) => continue,
+                Err(e) => {
+                    failures.record(format!("{}: {}", key, e));
+                    continue;
+                }
+            };
+            let account_id = match self.decode_account_id_from_key(key, &voting_prefix, false) {
+                Ok(account_id) => account_id,
+                Err(e) => {
+                    failures.record(format!("{}: {}", key, e));
+                    continue;
+                }
+            };
+            let mut nominator = Nominator::new(account_id, voter.stake);
+            for vote in voter.votes.iter().filter(|vote| seen.contains(*vote)) {
+                nominator.add_target(format!("0x{}", hex::encode(vote)));
+            }
+            if !nominator.targets.is_empty() {
+                nominators.push(nominator);
+            }
+        }
+        self.report_failures(&failures);
+        eprintln!("  ✓ Found {} voters", nominators.len());
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+
+        Ok(ElectionData {
+            candidates,
+            nominators,
+            invulnerables: Vec::new(),
+            metadata: Some(ElectionMetadata {
+                block_number: Some(block_number),
+                chain: None,
+                rpc_retries: Some(self.retry_count() - retries_before),
+                era: None,
+                load_failures: self.load_failures_since(failures_before),
+                stake_source: None,
+            }),
+            ..ElectionData::new()
+        })
+    }
+
+    /// Read and SCALE-decode a storage value, `None` if it is not set
+    async fn decode_storage_value<T: parity_scale_codec::Decode>(
+        &self,
+        pallet: &str,
+        item: &str,
+        block_hash: &str,
+    ) -> Result<Option<T>, ElectionError> {
+        let key = self.encode_storage_key(pallet, item)?;
+        match self.get_storage_value(&key, block_hash).await? {
+            Some(bytes) => T::decode(&mut &bytes[..]).map(Some).map_err(|e| ElectionError::RpcError {
+                message: format!("Failed to decode {}::{}: {}", pallet, item, e),
+                url: self.url.clone(),
+                source: Some(ErrorSource::new(e)),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the multi-phase election provider phase at a specific block
+    pub async fn election_phase_at(&self, block_number: u64) -> Result<ElectionPhase, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        let key = self.encode_storage_key("ElectionProviderMultiPhase", "CurrentPhase")?;
+
+        match self.get_storage_value(&key, &block_hash).await? {
+            Some(bytes) => ElectionPhase::decode(&bytes),
+            // Storage default is `Phase::Off`
+            None => Ok(ElectionPhase::Off),
+        }
+    }
+
+    /// Read the number of validators the upcoming election should select
+    ///
+    /// `DesiredTargets` is only populated while a snapshot exists, so `None` is
+    /// returned outside of the signed and unsigned phases.
+    pub async fn desired_targets_at(&self, block_number: u64) -> Result<Option<u32>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        let key = self.encode_storage_key("ElectionProviderMultiPhase", "DesiredTargets")?;
+
+        Ok(self
+            .get_storage_value(&key, &block_hash)
+            .await?
+            .filter(|bytes| bytes.len() >= 4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])))
+    }
+
+    /// Read the election round (`ElectionProviderMultiPhase::Round`) at a specific block
+    ///
+    /// The round starts at 1 and grows by one with every election, so it
+    /// tells which election a block belongs to. `None` on chains without the
+    /// pallet.
+    pub async fn election_round_at(&self, block_number: u64) -> Result<Option<u32>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        self.decode_storage_value::<u32>("ElectionProviderMultiPhase", "Round", &block_hash).await
+    }
+
+    /// Last block of the most recent election concluded at or before `block_number`
+    ///
+    /// The election's snapshot and queued solution are still in storage at
+    /// that block; the next block rotates the round. Searches back from
+    /// `block_number` in doubling steps and then bisects, so only recent
+    /// state is read and a pruned node works. `None` if no election has
+    /// concluded yet.
+    pub async fn last_election_block(&self, block_number: u64) -> Result<Option<u64>, ElectionError> {
+        let Some(round) = self.election_round_at(block_number).await? else {
+            return Ok(None);
+        };
+        if round <= 1 {
+            return Ok(None);
+        }
+        // Bracket the first block of the current round; the round only grows
+        let mut low = block_number;
+        let mut step = 256u64;
+        let mut high = loop {
+            let high = low;
+            low = low.saturating_sub(step);
+            if self.election_round_at(low).await?.unwrap_or(0) < round {
+                break high;
+            }
+            step = step.saturating_mul(2);
+        };
+        // `low` is in an earlier round and `high` in the current one
+        while high - low > 1 {
+            let middle = low + (high - low) / 2;
+            if self.election_round_at(middle).await?.unwrap_or(0) >= round {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        Ok(Some(low))
+    }
+
+    /// Read the queued solution (`ElectionProviderMultiPhase::QueuedSolution`) at a specific block
+    pub async fn queued_solution_at(&self, block_number: u64) -> Result<Option<OnchainSolution>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        let key = self.encode_storage_key("ElectionProviderMultiPhase", "QueuedSolution")?;
+
+        self.get_storage_value(&key, &block_hash)
+            .await?
+            .map(|bytes| OnchainSolution::decode(&bytes))
+            .transpose()
+    }
+
+    /// Read the election snapshot (`ElectionProviderMultiPhase::Snapshot`) at a specific block
+    pub async fn snapshot_at(&self, block_number: u64) -> Result<Option<ElectionData>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        let key = self.encode_storage_key("ElectionProviderMultiPhase", "Snapshot")?;
+
+        self.get_storage_value(&key, &block_hash)
+            .await?
+            .map(|bytes| decode_snapshot(&bytes, Some(block_number)))
+            .transpose()
+    }
+
+    /// Read the current active validator set (`Session::Validators`) at a specific block
+    pub async fn active_validators_at(&self, block_number: u64) -> Result<Vec<String>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        let key = self.encode_storage_key("Session", "Validators")?;
+
+        let bytes = match self.get_storage_value(&key, &block_hash).await? {
+            Some(bytes) if !bytes.is_empty() => bytes,
+            _ => return Ok(Vec::new()),
+        };
+        let (len, offset) = self.decode_compact_u32(&bytes)?;
+
+        Ok(bytes[offset..]
+            .chunks_exact(32)
+            .take(len as usize)
+            .map(|account| format!("0x{}", hex::encode(account)))
+            .collect())
+    }
+
+    /// Read the index of the active era (`Staking::ActiveEra`) at a specific block
+    pub async fn active_era_at(&self, block_number: u64) -> Result<Option<u32>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        // `ActiveEraInfo` starts with the era index
+        self.decode_storage_value::<u32>("Staking", "ActiveEra", &block_hash).await
+    }
+
+    /// Read the reward points of `era` (`Staking::ErasRewardPoints`) at a specific block
+    ///
+    /// Returns the points of each validator that earned any, or `None` if the
+    /// era is older than the chain's history depth or has not started.
+    pub async fn era_points_at(
+        &self,
+        block_number: u64,
+        era: u32,
+    ) -> Result<Option<BTreeMap<String, u32>>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        let key = self.era_points_key(era)?;
+        match self.get_storage_value(&key, &block_hash).await? {
+            Some(bytes) => decode_era_reward_points(&bytes).map(Some).ok_or_else(|| ElectionError::RpcError {
+                message: format!("Failed to decode Staking::ErasRewardPoints for era {}", era),
+                url: self.url.clone(),
+                source: None,
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the latest block number
+    pub async fn get_latest_block_number(&self) -> Result<u64, ElectionError> {
+        let response: Value = self
+            .request("chain_getHeader", Vec::<String>::new(), "Failed to get latest header")
+            .await
+            .map_err(|e| self.with_endpoint_suggestions(e))?;
 
-            let hash = response.as_str().ok_or_else(|| ElectionError::RpcError {
-                message: "Invalid block hash response".to_string(),
+        let number = response
+            .get("number")
+            .and_then(|n| n.as_str())
+            .ok_or_else(|| ElectionError::RpcError {
+                message: "Invalid header response".to_string(),
                 url: self.url.clone(),
+                source: None,
             })?;
 
-            Ok(hash.to_string())
+        // Parse hex number
+        let number = number.trim_start_matches("0x");
+        u64::from_str_radix(number, 16).map_err(|e| ElectionError::RpcError {
+            message: format!("Failed to parse block number: {}", e),
+            url: self.url.clone(),
+            source: Some(ErrorSource::new(e)),
         })
-        .await
+    }
+
+    /// Get block hash for a given block number
+    async fn get_block_hash(&self, block_number: u64) -> Result<String, ElectionError> {
+        let response: Value = self
+            .request(
+                "chain_getBlockHash",
+                (format!("0x{:x}", block_number),),
+                "Failed to get block hash",
+            )
+            .await
+            .map_err(|e| self.with_endpoint_suggestions(e))?;
+
+        let hash = response.as_str().ok_or_else(|| ElectionError::RpcError {
+            message: "Invalid block hash response".to_string(),
+            url: self.url.clone(),
+            source: None,
+        })?;
+
+        Ok(hash.to_string())
     }
 
     /// Fetch validator candidates from chain
@@ -406,16 +1179,12 @@ impl RpcLoader {
         let session_key = self.encode_storage_key("Session", "Validators")?;
         
         let response: Value = self
-            .client
             .request(
                 "state_getStorage",
                 (session_key.clone(), block_hash),
+                "Failed to query Session::Validators storage",
             )
-            .await
-            .map_err(|e| ElectionError::RpcError {
-                message: format!("Failed to query Session::Validators storage: {}", e),
-                url: self.url.clone(),
-            })?;
+            .await?;
 
         // If Session::Validators returns data, decode it
         if !response.is_null() {
@@ -427,16 +1196,12 @@ impl RpcLoader {
         let staking_key = self.encode_storage_key("Staking", "Validators")?;
         
         let response: Value = self
-            .client
             .request(
                 "state_getStorage",
                 (staking_key.clone(), block_hash),
+                "Failed to query Staking::Validators storage",
             )
-            .await
-            .map_err(|e| ElectionError::RpcError {
-                message: format!("Failed to query Staking::Validators storage: {}", e),
-                url: self.url.clone(),
-            })?;
+            .await?;
 
         if !response.is_null() {
             return self.decode_validators_from_storage(&response, block_hash).await;
@@ -446,6 +1211,482 @@ impl RpcLoader {
         self.fetch_validators_with_prefix(block_hash).await
     }
 
+    /// Fetch `Staking::Validators` preferences for each candidate
+    ///
+    /// Populates the commission rate and blocked flag in the candidate metadata.
+    /// Failures are non-fatal: candidates without readable preferences are left unchanged.
+    async fn fetch_validator_prefs(&self, candidates: &mut [ValidatorCandidate], block_hash: &str) {
+        let prefs_prefix = match self.encode_storage_key("Staking", "Validators") {
+            Ok(prefix) => prefix,
+            Err(_) => return,
+        };
+
+        let mut blocked_count = 0;
+        let mut failures = FetchFailures::new("Staking::Validators", candidates.len());
+        // Staking::Validators is a Twox64Concat map: prefix + twox64(AccountId) + AccountId
+        let keys = self.map_keys(candidates, &prefs_prefix, |account| twox_64_hash(account).to_vec(), &mut failures);
+        let (indices, keys): (Vec<usize>, Vec<String>) = keys.into_iter().unzip();
+        let values = self.get_storage_values(&keys, block_hash).await;
+
+        for (index, value) in indices.into_iter().zip(values) {
+            let candidate = &mut candidates[index];
+            match value {
+                Ok(Some(bytes)) => match self.decode_validator_prefs(&bytes) {
+                    Ok((commission, blocked)) => {
+                        if blocked {
+                            blocked_count += 1;
+                        }
+                        let metadata = candidate.metadata.get_or_insert_with(CandidateMetadata::default);
+                        metadata.commission_rate = Some(commission_percent(commission));
+                        metadata.blocked = Some(blocked);
+                    }
+                    Err(e) => failures.record(format!("{}: {}", candidate.account_id, e)),
+                },
+                Ok(None) => {}
+                Err(e) => failures.record(format!("{}: {}", candidate.account_id, e)),
+            }
+        }
+
+        self.report_failures(&failures);
+        if blocked_count > 0 {
+            eprintln!("  ✓ {} validators are blocking new nominations", blocked_count);
+        }
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+    }
+
+    /// Fetch the `Staking::Invulnerables` list
+    ///
+    /// Failures are non-fatal and yield an empty list.
+    async fn fetch_invulnerables(&self, block_hash: &str) -> Vec<String> {
+        let key = match self.encode_storage_key("Staking", "Invulnerables") {
+            Ok(key) => key,
+            Err(_) => return Vec::new(),
+        };
+
+        let invulnerables = match self.get_storage_value(&key, block_hash).await {
+            Ok(Some(bytes)) => match <Vec<[u8; 32]> as parity_scale_codec::Decode>::decode(&mut &bytes[..]) {
+                Ok(accounts) => accounts
+                    .iter()
+                    .map(|account| format!("0x{}", hex::encode(account)))
+                    .collect(),
+                Err(_) => {
+                    eprintln!("  ⚠ Warning: Could not decode Staking::Invulnerables");
+                    Vec::new()
+                }
+            },
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                eprintln!("  ⚠ Warning: Could not read Staking::Invulnerables: {}", e);
+                Vec::new()
+            }
+        };
+
+        if !invulnerables.is_empty() {
+            eprintln!("  ✓ Found {} invulnerables", invulnerables.len());
+        }
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+        invulnerables
+    }
+
+    /// Read the controllers of `stashes` (`Staking::Bonded`) at a specific block
+    ///
+    /// Returns the controller of each bonded stash; unbonded stashes are absent.
+    pub async fn controllers_at(
+        &self,
+        block_number: u64,
+        stashes: &[String],
+    ) -> Result<BTreeMap<String, String>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        let keys = stashes
+            .iter()
+            .map(|stash| self.account_map_key("Staking", "Bonded", stash))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut controllers = BTreeMap::new();
+        for (stash, value) in stashes.iter().zip(self.get_storage_values(&keys, &block_hash).await) {
+            if let Some(bytes) = value? {
+                let controller = decode_account(&bytes).ok_or_else(|| ElectionError::RpcError {
+                    message: format!("Failed to decode Staking::Bonded for {}", stash),
+                    url: self.url.clone(),
+                    source: None,
+                })?;
+                controllers.insert(stash.clone(), controller);
+            }
+        }
+        Ok(controllers)
+    }
+
+    /// Record controllers that differ from their stash, if enabled
+    ///
+    /// Ledgers are keyed by controller, so a nominator with a separate
+    /// controller was loaded without stake; it is read from the controller's
+    /// ledger. Failures are non-fatal and leave the accounts unmapped.
+    async fn fetch_controllers(
+        &self,
+        candidates: &mut [ValidatorCandidate],
+        nominators: &mut [Nominator],
+        block_hash: &str,
+    ) {
+        if !self.resolve_controllers {
+            return;
+        }
+        let stashes: Vec<String> = candidates
+            .iter()
+            .map(|c| c.account_id.clone())
+            .chain(nominators.iter().map(|n| n.account_id.clone()))
+            .collect();
+        let keys = match stashes
+            .iter()
+            .map(|stash| self.account_map_key("Staking", "Bonded", stash))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(keys) => keys,
+            Err(e) => {
+                eprintln!("  ⚠ Warning: Could not map controllers: {}", e);
+                return;
+            }
+        };
+
+        let mut failures = FetchFailures::new("Staking::Bonded", keys.len());
+        let mut controllers = std::collections::HashMap::new();
+        for (stash, value) in stashes.into_iter().zip(self.get_storage_values(&keys, block_hash).await) {
+            match value {
+                Ok(Some(bytes)) => match decode_account(&bytes) {
+                    Some(controller) if controller != stash => {
+                        controllers.insert(stash, controller);
+                    }
+                    Some(_) => {}
+                    None => failures.record(format!("{}: undecodable value", stash)),
+                },
+                Ok(None) => {}
+                Err(e) => failures.record(format!("{}: {}", stash, e)),
+            }
+        }
+        self.report_failures(&failures);
+
+        for candidate in candidates.iter_mut() {
+            if let Some(controller) = controllers.get(&candidate.account_id) {
+                candidate.metadata.get_or_insert_with(CandidateMetadata::default).controller = Some(controller.clone());
+            }
+        }
+
+        // Stakes of nominators whose ledger sits under a separate controller
+        let (unstaked, ledger_keys): (Vec<usize>, Vec<String>) = nominators
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.stake == 0)
+            .filter_map(|(i, n)| {
+                let controller = controllers.get(&n.account_id)?;
+                Some((i, self.account_map_key("Staking", "Ledger", controller).ok()?))
+            })
+            .unzip();
+        let mut ledger_failures = FetchFailures::new("Staking::Ledger", ledger_keys.len());
+        let ledgers = self.get_storage_values(&ledger_keys, block_hash).await;
+        for (&index, ledger) in unstaked.iter().zip(ledgers) {
+            let nominator = &mut nominators[index];
+            match ledger.map(|bytes| bytes.map(|bytes| self.decode_staking_ledger_stake(&bytes))) {
+                Ok(Some(Ok(stake))) => nominator.stake = stake,
+                Ok(Some(Err(e))) | Err(e) => ledger_failures.record(format!("{}: {}", nominator.account_id, e)),
+                Ok(None) => {}
+            }
+        }
+        self.report_failures(&ledger_failures);
+
+        let mut mapped = 0;
+        for nominator in nominators.iter_mut() {
+            if let Some(controller) = controllers.get(&nominator.account_id) {
+                nominator.metadata.get_or_insert_with(NominatorMetadata::default).controller = Some(controller.clone());
+                mapped += 1;
+            }
+        }
+        eprintln!(
+            "  ✓ Mapped {} controllers ({} validators, {} nominators)",
+            controllers.len(),
+            controllers.len() - mapped,
+            mapped
+        );
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+    }
+
+    /// Storage key of an account in a Twox64Concat map
+    fn account_map_key(&self, pallet: &str, item: &str, account_id: &str) -> Result<String, ElectionError> {
+        let account = hex::decode(account_id.trim_start_matches("0x")).map_err(|e| ElectionError::InvalidData {
+            message: format!("Account ID {} is not hex: {}", account_id, e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        Ok(format!(
+            "{}{}{}",
+            self.encode_storage_key(pallet, item)?,
+            hex::encode(twox_64_hash(&account)),
+            hex::encode(&account)
+        ))
+    }
+
+    /// Read each nominator's total exposure in `era` at a specific block
+    ///
+    /// Sums the nominator's stake behind every validator it was exposed to,
+    /// from `Staking::ErasStakersPaged`, or from `Staking::ErasStakers` if the
+    /// runtime predates paged exposures. Nominators without exposure are absent.
+    pub async fn exposures_at(&self, block_number: u64, era: u32) -> Result<BTreeMap<String, u128>, ElectionError> {
+        let block_hash = self.get_block_hash(block_number).await?;
+        self.fetch_exposures(era, &block_hash).await
+    }
+
+    async fn fetch_exposures(&self, era: u32, block_hash: &str) -> Result<BTreeMap<String, u128>, ElectionError> {
+        let era = era.to_le_bytes();
+        let era_key = format!("{}{}", hex::encode(twox_64_hash(&era)), hex::encode(era));
+        let mut exposures = BTreeMap::new();
+        for (item, paged) in [("ErasStakersPaged", true), ("ErasStakers", false)] {
+            let prefix = format!("{}{}", self.encode_storage_key("Staking", item)?, era_key);
+            let keys = self.get_storage_keys_paged(&prefix, block_hash).await?;
+            if keys.is_empty() {
+                continue;
+            }
+            let mut failures = FetchFailures::new(format!("Staking::{}", item), keys.len());
+            for (key, value) in keys.iter().zip(self.get_storage_values(&keys, block_hash).await) {
+                let others = match value {
+                    Ok(Some(bytes)) => match decode_exposure_others(&bytes, paged) {
+                        Some(others) => others,
+                        None => {
+                            failures.record(format!("{}: undecodable value", key));
+                            continue;
+                        }
+                    },
+                    Ok(None) => continue,
+                    Err(e) => {
+                        failures.record(format!("{}: {}", key, e));
+                        continue;
+                    }
+                };
+                for (nominator, stake) in others {
+                    *exposures.entry(nominator).or_insert(0u128) += stake;
+                }
+            }
+            self.report_failures(&failures);
+            break;
+        }
+        Ok(exposures)
+    }
+
+    /// Replace ledger stakes with active era exposures if so configured
+    ///
+    /// Returns the source the stakes were actually read from: if the active
+    /// era or its exposures cannot be read, the ledger stakes are kept.
+    async fn apply_stake_source(&self, nominators: &mut [Nominator], block_hash: &str) -> StakeSource {
+        if self.stake_source == StakeSource::Ledger {
+            return StakeSource::Ledger;
+        }
+        let exposures = match self.decode_storage_value::<u32>("Staking", "ActiveEra", block_hash).await {
+            Ok(Some(era)) => self.fetch_exposures(era, block_hash).await,
+            Ok(None) => Ok(BTreeMap::new()),
+            Err(e) => Err(e),
+        };
+        let exposures = match exposures {
+            Ok(exposures) if !exposures.is_empty() => exposures,
+            Ok(_) => {
+                self.record_load_failure("No exposures in the active era, keeping ledger stakes".to_string());
+                eprintln!("  ⚠ Warning: No exposures in the active era, keeping ledger stakes");
+                return StakeSource::Ledger;
+            }
+            Err(e) => {
+                self.record_load_failure(format!("Could not read exposures, keeping ledger stakes: {}", e));
+                eprintln!("  ⚠ Warning: Could not read exposures, keeping ledger stakes: {}", e);
+                return StakeSource::Ledger;
+            }
+        };
+
+        let mut unexposed = 0;
+        for nominator in nominators.iter_mut() {
+            match exposures.get(&nominator.account_id) {
+                Some(&stake) => nominator.stake = stake,
+                None => unexposed += 1,
+            }
+        }
+        eprintln!(
+            "  ✓ Took stakes from active era exposures; {} nominators without exposure keep their ledger stake",
+            unexposed
+        );
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+        StakeSource::Exposure
+    }
+
+    /// Fetch the era points of each candidate in the eras before the active one
+    ///
+    /// Does nothing unless [`with_activity_eras`](Self::with_activity_eras) was
+    /// set. Failures are non-fatal: eras whose points cannot be read are left
+    /// out of every candidate's activity.
+    async fn fetch_activity(&self, candidates: &mut [ValidatorCandidate], block_hash: &str) {
+        if self.activity_eras == 0 {
+            return;
+        }
+        let active_era = match self.decode_storage_value::<u32>("Staking", "ActiveEra", block_hash).await {
+            Ok(Some(era)) => era,
+            Ok(None) => {
+                eprintln!("  ⚠ Warning: No active era, skipping era points");
+                return;
+            }
+            Err(e) => {
+                self.record_load_failure(format!("Could not read Staking::ActiveEra, skipping era points: {}", e));
+                eprintln!("  ⚠ Warning: Could not read Staking::ActiveEra: {}", e);
+                return;
+            }
+        };
+
+        // The active era's points are still accumulating, so only completed eras count
+        let eras: Vec<u32> = (active_era.saturating_sub(self.activity_eras)..active_era).collect();
+        let mut failures = FetchFailures::new("Staking::ErasRewardPoints", eras.len());
+        let keys: Vec<String> = match eras.iter().map(|&era| self.era_points_key(era)).collect() {
+            Ok(keys) => keys,
+            Err(_) => return,
+        };
+        let values = self.get_storage_values(&keys, block_hash).await;
+
+        let mut activity = vec![CandidateActivity::default(); candidates.len()];
+        let mut loaded_eras = 0;
+        for (&era, value) in eras.iter().zip(values) {
+            let points = match value {
+                Ok(Some(bytes)) => match decode_era_reward_points(&bytes) {
+                    Some(points) => points,
+                    None => {
+                        failures.record(format!("era {}: undecodable value", era));
+                        continue;
+                    }
+                },
+                // Pruned beyond the history depth
+                Ok(None) => continue,
+                Err(e) => {
+                    failures.record(format!("era {}: {}", era, e));
+                    continue;
+                }
+            };
+            loaded_eras += 1;
+            for (candidate, activity) in candidates.iter().zip(activity.iter_mut()) {
+                let points = points.get(&candidate.account_id).copied().unwrap_or(0);
+                activity.eras.push(EraActivity { era, points });
+            }
+        }
+
+        self.report_failures(&failures);
+        if loaded_eras == 0 {
+            return;
+        }
+        let mut inactive = 0;
+        for (candidate, activity) in candidates.iter_mut().zip(activity) {
+            if activity.active_eras() == 0 {
+                inactive += 1;
+            }
+            candidate.metadata.get_or_insert_with(CandidateMetadata::default).activity = Some(activity);
+        }
+        eprintln!(
+            "  ✓ Loaded era points for {} eras; {} validators earned none",
+            loaded_eras, inactive
+        );
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+    }
+
+    /// Storage key of `Staking::ErasRewardPoints` for an era (Twox64Concat map)
+    fn era_points_key(&self, era: u32) -> Result<String, ElectionError> {
+        let prefix = self.encode_storage_key("Staking", "ErasRewardPoints")?;
+        let era = era.to_le_bytes();
+        Ok(format!("{}{}{}", prefix, hex::encode(twox_64_hash(&era)), hex::encode(era)))
+    }
+
+    /// Fetch `Identity::SuperOf` for each candidate
+    ///
+    /// Sub-identities record their parent account and sub-name in the candidate
+    /// metadata, which lets diagnostics group validators by operator. Chains whose
+    /// identities live on a separate (people) chain simply yield no parents.
+    /// Failures are non-fatal.
+    async fn fetch_identity_parents(&self, candidates: &mut [ValidatorCandidate], block_hash: &str) {
+        let super_of_prefix = match self.encode_storage_key("Identity", "SuperOf") {
+            Ok(prefix) => prefix,
+            Err(_) => return,
+        };
+
+        let mut sub_identity_count = 0;
+        let mut failures = FetchFailures::new("Identity::SuperOf", candidates.len());
+        // Identity::SuperOf is a Blake2_128Concat map: prefix + blake2_128(AccountId) + AccountId
+        let keys = self.map_keys(
+            candidates,
+            &super_of_prefix,
+            |account| sp_crypto_hashing::blake2_128(account).to_vec(),
+            &mut failures,
+        );
+        let (indices, keys): (Vec<usize>, Vec<String>) = keys.into_iter().unzip();
+        let values = self.get_storage_values(&keys, block_hash).await;
+
+        for (index, value) in indices.into_iter().zip(values) {
+            let candidate = &mut candidates[index];
+            match value {
+                Ok(Some(bytes)) => match decode_super_of(&bytes) {
+                    Some((parent, sub_name)) => {
+                        sub_identity_count += 1;
+                        let metadata = candidate.metadata.get_or_insert_with(CandidateMetadata::default);
+                        metadata.identity_parent = Some(parent);
+                        if sub_name.is_some() {
+                            metadata.identity_display = sub_name;
+                        }
+                    }
+                    None => failures.record(format!("{}: undecodable value", candidate.account_id)),
+                },
+                Ok(None) => {}
+                Err(e) => failures.record(format!("{}: {}", candidate.account_id, e)),
+            }
+        }
+
+        self.report_failures(&failures);
+        if sub_identity_count > 0 {
+            eprintln!("  ✓ {} validators are sub-identities of an operator", sub_identity_count);
+        }
+        std::io::Write::flush(&mut std::io::stderr()).ok();
+    }
+
+    /// Build `prefix ++ hash(AccountId) ++ AccountId` map keys for each candidate
+    ///
+    /// Returns `(candidate index, key)` pairs; candidates whose ID is not hex
+    /// are recorded as failures and skipped.
+    fn map_keys(
+        &self,
+        candidates: &[ValidatorCandidate],
+        prefix: &str,
+        hasher: impl Fn(&[u8]) -> Vec<u8>,
+        failures: &mut FetchFailures,
+    ) -> Vec<(usize, String)> {
+        candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, candidate)| {
+                match hex::decode(candidate.account_id.trim_start_matches("0x")) {
+                    Ok(account) => Some((
+                        index,
+                        format!("{}{}{}", prefix, hex::encode(hasher(&account)), hex::encode(&account)),
+                    )),
+                    Err(e) => {
+                        failures.record(format!("{}: invalid account ID: {}", candidate.account_id, e));
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Decode ValidatorPrefs struct to extract commission and blocked flag
+    /// ValidatorPrefs structure: { commission: Compact<Perbill>, blocked: bool }
+    fn decode_validator_prefs(&self, bytes: &[u8]) -> Result<(u32, bool), ElectionError> {
+        let (commission, offset) = self.decode_compact_u32(bytes)?;
+        let blocked = match bytes.get(offset) {
+            Some(0) => false,
+            Some(1) => true,
+            _ => {
+                return Err(ElectionError::RpcError {
+                    message: "Invalid ValidatorPrefs encoding: missing or invalid blocked flag".to_string(),
+                    url: self.url.clone(),
+                    source: None,
+                });
+            }
+        };
+        Ok((commission, blocked))
+    }
+
     /// Fetch validators using state_queryStorageAt with storage prefix
     async fn fetch_validators_with_prefix(&self, block_hash: &str) -> Result<Vec<ValidatorCandidate>, ElectionError> {
         // Try using state_queryStorageAt (if available) or state_queryStorage
@@ -454,10 +1695,10 @@ impl RpcLoader {
         
         // Try state_queryStorageAt first
         let response: Result<Value, _> = self
-            .client
             .request(
                 "state_queryStorageAt",
                 (vec![session_prefix.clone()], block_hash),
+                "Failed to query storage",
             )
             .await;
 
@@ -501,6 +1742,7 @@ impl RpcLoader {
                 block_hash
             ),
             url: self.url.clone(),
+            source: None,
         })
     }
 
@@ -510,6 +1752,7 @@ impl RpcLoader {
         let hex_str = storage_value.as_str().ok_or_else(|| ElectionError::RpcError {
             message: "Storage value is not a string".to_string(),
             url: self.url.clone(),
+            source: None,
         })?;
 
         // Remove 0x prefix if present
@@ -519,6 +1762,7 @@ impl RpcLoader {
         let bytes = hex::decode(hex_str).map_err(|e| ElectionError::RpcError {
             message: format!("Failed to decode hex: {}", e),
             url: self.url.clone(),
+            source: Some(ErrorSource::new(e)),
         })?;
 
         // Decode SCALE-encoded Vec<AccountId>
@@ -549,6 +1793,7 @@ impl RpcLoader {
                     bytes.len()
                 ),
                 url: self.url.clone(),
+                source: None,
             });
         }
 
@@ -574,6 +1819,7 @@ impl RpcLoader {
                 block_hash
             ),
             url: self.url.clone(),
+            source: None,
         })
         } else {
             Ok(validators)
@@ -586,6 +1832,7 @@ impl RpcLoader {
             return Err(ElectionError::RpcError {
                 message: "Empty data for compact decoding".to_string(),
                 url: self.url.clone(),
+                source: None,
             });
         }
 
@@ -603,6 +1850,7 @@ impl RpcLoader {
                     return Err(ElectionError::RpcError {
                         message: "Insufficient data for two-byte compact".to_string(),
                         url: self.url.clone(),
+                        source: None,
                     });
                 }
                 let value = ((first_byte >> 2) as u32) | ((data[1] as u32) << 6);
@@ -614,6 +1862,7 @@ impl RpcLoader {
                     return Err(ElectionError::RpcError {
                         message: "Insufficient data for four-byte compact".to_string(),
                         url: self.url.clone(),
+                        source: None,
                     });
                 }
                 let value = ((first_byte >> 2) as u32)
@@ -629,6 +1878,7 @@ impl RpcLoader {
                     return Err(ElectionError::RpcError {
                         message: format!("Insufficient data for {}-byte compact", len),
                         url: self.url.clone(),
+                        source: None,
                     });
                 }
                 // Read little-endian u32 from following bytes
@@ -696,23 +1946,28 @@ impl RpcLoader {
         let mut decode_errors = Vec::new();
         
         // Process Nominators storage entries to get targets
+        let mut nominator_entries = Vec::with_capacity(nominator_keys.len());
         for key in nominator_keys {
             nominator_keys_processed += 1;
             // Extract AccountId from storage key
             // Format: prefix (32 bytes) + blake2_128(AccountId) (16 bytes) + AccountId (32 bytes)
-            let account_id = match self.decode_account_id_from_key(&key, &nominators_prefix, true) {
-                Ok(id) => id,
+            match self.decode_account_id_from_key(&key, &nominators_prefix, true) {
+                Ok(id) => nominator_entries.push((id, key)),
                 Err(e) => {
                     decode_errors.push(format!("Failed to decode AccountId from Nominators key: {}", e));
-                    continue;
                 }
-            };
-            
-            // Fetch the storage value for this key
-            let value = match self.get_storage_value(&key, block_hash).await {
+            }
+        }
+
+        // Fetch the storage values concurrently
+        let (account_ids, keys): (Vec<String>, Vec<String>) = nominator_entries.into_iter().unzip();
+        let mut nominator_failures = FetchFailures::new("Staking::Nominators", keys.len());
+        let values = self.get_storage_values(&keys, block_hash).await;
+        for (account_id, value) in account_ids.into_iter().zip(values) {
+            let value = match value {
                 Ok(v) => v,
                 Err(e) => {
-                    decode_errors.push(format!("Failed to get storage value for Nominators key: {}", e));
+                    nominator_failures.record(format!("{}: {}", account_id, e));
                     continue;
                 }
             };
@@ -735,23 +1990,28 @@ impl RpcLoader {
         }
         
         // Process Ledger storage entries to get stakes
+        let mut ledger_entries = Vec::with_capacity(ledger_keys.len());
         for key in ledger_keys {
             ledger_keys_processed += 1;
             // Extract AccountId from storage key
             // Format: prefix (32 bytes) + twox64(AccountId) (8 bytes) + AccountId (32 bytes)
-            let account_id = match self.decode_account_id_from_key(&key, &ledger_prefix, false) {
-                Ok(id) => id,
+            match self.decode_account_id_from_key(&key, &ledger_prefix, false) {
+                Ok(id) => ledger_entries.push((id, key)),
                 Err(e) => {
                     decode_errors.push(format!("Failed to decode AccountId from Ledger key: {}", e));
-                    continue;
                 }
-            };
-            
-            // Fetch the storage value for this key
-            let value = match self.get_storage_value(&key, block_hash).await {
+            }
+        }
+
+        // Fetch the storage values concurrently
+        let (account_ids, keys): (Vec<String>, Vec<String>) = ledger_entries.into_iter().unzip();
+        let mut ledger_failures = FetchFailures::new("Staking::Ledger", keys.len());
+        let values = self.get_storage_values(&keys, block_hash).await;
+        for (account_id, value) in account_ids.into_iter().zip(values) {
+            let value = match value {
                 Ok(v) => v,
                 Err(e) => {
-                    decode_errors.push(format!("Failed to get storage value for Ledger key: {}", e));
+                    ledger_failures.record(format!("{}: {}", account_id, e));
                     continue;
                 }
             };
@@ -773,6 +2033,9 @@ impl RpcLoader {
             }
         }
         
+        self.report_failures(&nominator_failures);
+        self.report_failures(&ledger_failures);
+
         // Convert HashMap to Vec
         let mut nominators: Vec<Nominator> = nominators_map.into_values().collect();
         
@@ -857,6 +2120,7 @@ impl RpcLoader {
         let mut stakes_decoded = 0;
         
         // Process Nominators storage entries
+        let mut nominator_entries = Vec::with_capacity(nominator_keys.len());
         for key in &nominator_keys {
             // Skip keys that are exactly the prefix (some RPCs return the prefix itself)
             let key_normalized = key.trim_start_matches("0x");
@@ -867,22 +2131,27 @@ impl RpcLoader {
             }
             
             nominators_processed += 1;
-            let account_id = match self.decode_account_id_from_key(key, nominators_prefix, true) {
-                Ok(id) => id,
+            match self.decode_account_id_from_key(key, nominators_prefix, true) {
+                Ok(id) => nominator_entries.push((id, key.clone())),
                 Err(e) => {
                     decode_errors.push(format!("Failed to decode AccountId from Nominators key: {}", e));
-                    continue;
                 }
-            };
-            
-            let nominations_bytes = match self.get_storage_value(key, block_hash).await {
+            }
+        }
+
+        // Fetch the storage values concurrently
+        let (account_ids, keys): (Vec<String>, Vec<String>) = nominator_entries.into_iter().unzip();
+        let mut nominator_failures = FetchFailures::new("Staking::Nominators", keys.len());
+        let values = self.get_storage_values(&keys, block_hash).await;
+        for (account_id, value) in account_ids.into_iter().zip(values) {
+            let nominations_bytes = match value {
                 Ok(Some(bytes)) => bytes,
                 Ok(None) => {
                     decode_errors.push(format!("Nominators storage value is null for key"));
                     continue;
                 }
                 Err(e) => {
-                    decode_errors.push(format!("Failed to get storage value for Nominators key: {}", e));
+                    nominator_failures.record(format!("{}: {}", account_id, e));
                     continue;
                 }
             };
@@ -904,6 +2173,7 @@ impl RpcLoader {
         }
         
         // Process Ledger storage entries
+        let mut ledger_entries = Vec::with_capacity(ledger_keys.len());
         for key in &ledger_keys {
             // Skip keys that are exactly the prefix (some RPCs return the prefix itself)
             let key_normalized = key.trim_start_matches("0x");
@@ -914,22 +2184,27 @@ impl RpcLoader {
             }
             
             ledgers_processed += 1;
-            let account_id = match self.decode_account_id_from_key(key, ledger_prefix, false) {
-                Ok(id) => id,
+            match self.decode_account_id_from_key(key, ledger_prefix, false) {
+                Ok(id) => ledger_entries.push((id, key.clone())),
                 Err(e) => {
                     decode_errors.push(format!("Failed to decode AccountId from Ledger key: {}", e));
-                    continue;
                 }
-            };
-            
-            let ledger_bytes = match self.get_storage_value(key, block_hash).await {
+            }
+        }
+
+        // Fetch the storage values concurrently
+        let (account_ids, keys): (Vec<String>, Vec<String>) = ledger_entries.into_iter().unzip();
+        let mut ledger_failures = FetchFailures::new("Staking::Ledger", keys.len());
+        let values = self.get_storage_values(&keys, block_hash).await;
+        for (account_id, value) in account_ids.into_iter().zip(values) {
+            let ledger_bytes = match value {
                 Ok(Some(bytes)) => bytes,
                 Ok(None) => {
                     decode_errors.push(format!("Ledger storage value is null for key"));
                     continue;
                 }
                 Err(e) => {
-                    decode_errors.push(format!("Failed to get storage value for Ledger key: {}", e));
+                    ledger_failures.record(format!("{}: {}", account_id, e));
                     continue;
                 }
             };
@@ -948,6 +2223,9 @@ impl RpcLoader {
             }
         }
         
+        self.report_failures(&nominator_failures);
+        self.report_failures(&ledger_failures);
+
         let mut nominators: Vec<Nominator> = nominators_map.into_values().collect();
         let before_filter = nominators.len();
         nominators.retain(|n| !n.targets.is_empty());
@@ -995,10 +2273,14 @@ impl RpcLoader {
                     error_msg.push_str(&format!("  - {}\n", err));
                 }
             }
+            for summary in [nominator_failures.summary(), ledger_failures.summary()].into_iter().flatten() {
+                error_msg.push_str(&format!("\n{}\n", summary));
+            }
             
             return Err(ElectionError::RpcError {
                 message: error_msg,
                 url: self.url.clone(),
+                source: None,
             });
         }
         
@@ -1035,7 +2317,7 @@ impl RpcLoader {
             // Add timeout wrapper for individual requests
             let response = tokio::time::timeout(
                 std::time::Duration::from_secs(30),
-                self.client.request(
+                self.request(
                     "state_getKeysPaged",
                     (
                         nominators_prefix,
@@ -1043,6 +2325,7 @@ impl RpcLoader {
                         nominator_start_key.as_ref(),
                         Some(block_hash),
                     ),
+                    "Failed to fetch storage keys page",
                 )
             ).await;
             
@@ -1053,7 +2336,7 @@ impl RpcLoader {
                     // Try alternative parameter order
                     let alt_response = tokio::time::timeout(
                         std::time::Duration::from_secs(30),
-                        self.client.request(
+                        self.request(
                             "state_getKeysPaged",
                             (
                                 nominators_prefix,
@@ -1061,6 +2344,7 @@ impl RpcLoader {
                                 Some(block_hash),
                                 nominator_start_key.as_ref(),
                             ),
+                            "Failed to fetch storage keys page",
                         )
                     ).await;
                     
@@ -1068,29 +2352,31 @@ impl RpcLoader {
                         Ok(Ok(v)) => Ok(v),
                         Ok(Err(e)) => Err(e),
                         Err(_) => {
-                            return Err(ElectionError::RpcError {
+                            return Err(ElectionError::Timeout {
                                 message: format!(
-                                    "Request timeout after 30 seconds while fetching nominator keys.\n\
+                                    "Fetching nominator keys from {} did not finish.\n\
                                     This usually means the RPC endpoint is slow or doesn't support this method.\n\
                                     Block hash: {}\n\
                                     Please try using --input-file with JSON data instead.",
+                                    self.url,
                                     block_hash
                                 ),
-                                url: self.url.clone(),
+                                timeout_ms: 30_000,
                             });
                         }
                     }
                 }
                 Err(_) => {
-                    return Err(ElectionError::RpcError {
+                    return Err(ElectionError::Timeout {
                         message: format!(
-                            "Request timeout after 30 seconds while fetching nominator keys.\n\
+                            "Fetching nominator keys from {} did not finish.\n\
                             This usually means the RPC endpoint is slow or doesn't support this method.\n\
                             Block hash: {}\n\
                             Please try using --input-file with JSON data instead.",
+                            self.url,
                             block_hash
                         ),
-                        url: self.url.clone(),
+                        timeout_ms: 30_000,
                     });
                 }
             };
@@ -1140,6 +2426,7 @@ impl RpcLoader {
                 block_hash
             ),
                         url: self.url.clone(),
+                        source: None,
                     });
                 }
             }
@@ -1156,7 +2443,7 @@ impl RpcLoader {
             // Add timeout wrapper for individual requests
             let response = tokio::time::timeout(
                 std::time::Duration::from_secs(30),
-                self.client.request(
+                self.request(
                     "state_getKeysPaged",
                     (
                         ledger_prefix,
@@ -1164,6 +2451,7 @@ impl RpcLoader {
                         ledger_start_key.as_ref(),
                         Some(block_hash),
                     ),
+                    "Failed to fetch storage keys page",
                 )
             ).await;
             
@@ -1171,15 +2459,16 @@ impl RpcLoader {
                 Ok(Ok(v)) => Ok(v),
                 Ok(Err(e)) => Err(e),
                 Err(_) => {
-                    return Err(ElectionError::RpcError {
+                    return Err(ElectionError::Timeout {
                         message: format!(
-                            "Request timeout after 30 seconds while fetching ledger keys.\n\
+                            "Fetching ledger keys from {} did not finish.\n\
                             This usually means the RPC endpoint is slow or doesn't support this method.\n\
                             Block hash: {}\n\
                             Please try using --input-file with JSON data instead.",
+                            self.url,
                             block_hash
                         ),
-                        url: self.url.clone(),
+                        timeout_ms: 30_000,
                     });
                 }
             };
@@ -1188,7 +2477,7 @@ impl RpcLoader {
             let response = match response {
                 Ok(v) => Ok(v),
                 Err(_) => {
-                    self.client
+                    self
                         .request(
                             "state_getKeysPaged",
                             (
@@ -1197,6 +2486,7 @@ impl RpcLoader {
                                 Some(block_hash),
                                 ledger_start_key.as_ref(),
                             ),
+                            "Failed to fetch storage keys page",
                         )
                         .await
                 }
@@ -1267,29 +2557,16 @@ impl RpcLoader {
     async fn get_storage_keys(&self, prefix: &str, block_hash: &str) -> Result<Vec<String>, ElectionError> {
         // Use state_getKeys RPC method to get all keys with the prefix
         // Note: Some RPC endpoints use state_getKeysPaged instead
-        let response: Result<Value, _> = self
-            .client
-            .request(
-                "state_getKeys",
-                (prefix, block_hash),
-            )
-            .await;
-        
-        let value = match response {
-            Ok(v) => v,
-            Err(e) => {
-                // If state_getKeys fails, the error will be caught by caller
-                return Err(ElectionError::RpcError {
-                    message: format!("Failed to query storage keys: {}", e),
-                    url: self.url.clone(),
-                });
-            }
-        };
+        // If state_getKeys fails, the error will be caught by caller
+        let value: Value = self
+            .request("state_getKeys", (prefix, block_hash), "Failed to query storage keys")
+            .await?;
         
         // Parse the response - should be an array of hex strings
         let keys_array = value.as_array().ok_or_else(|| ElectionError::RpcError {
             message: "Invalid storage keys response (not an array)".to_string(),
             url: self.url.clone(),
+            source: None,
         })?;
         
         let prefix_normalized = prefix.trim_start_matches("0x");
@@ -1307,19 +2584,55 @@ impl RpcLoader {
         Ok(result)
     }
     
+    /// Get all storage keys with a given prefix, one `state_getKeysPaged` page at a time
+    async fn get_storage_keys_paged(&self, prefix: &str, block_hash: &str) -> Result<Vec<String>, ElectionError> {
+        const PAGE_SIZE: u32 = 1000;
+        let mut keys: Vec<String> = Vec::new();
+        loop {
+            let page: Vec<String> = self
+                .request(
+                    "state_getKeysPaged",
+                    (prefix, PAGE_SIZE, keys.last(), block_hash),
+                    "Failed to fetch storage keys page",
+                )
+                .await?;
+            let last_page = page.len() < PAGE_SIZE as usize;
+            keys.extend(page);
+            if last_page {
+                return Ok(keys);
+            }
+        }
+    }
+
+    /// Get the storage values for many keys, keeping at most `concurrency` queries in flight
+    ///
+    /// Results are returned in key order. Each query is retried on transient
+    /// errors independently, so one flaky request does not fail the batch.
+    async fn get_storage_values(
+        &self,
+        keys: &[String],
+        block_hash: &str,
+    ) -> Vec<Result<Option<Vec<u8>>, ElectionError>> {
+        let permits = tokio::sync::Semaphore::new(self.concurrency);
+        futures::future::join_all(keys.iter().map(|key| {
+            let permits = &permits;
+            async move {
+                let _permit = permits.acquire().await.map_err(|e| ElectionError::RpcError {
+                    message: format!("Storage query limiter closed: {}", e),
+                    url: self.url.clone(),
+                    source: Some(ErrorSource::new(e)),
+                })?;
+                self.get_storage_value(key, block_hash).await
+            }
+        }))
+        .await
+    }
+
     /// Get storage value for a given key
     async fn get_storage_value(&self, key: &str, block_hash: &str) -> Result<Option<Vec<u8>>, ElectionError> {
         let response: Value = self
-            .client
-            .request(
-                "state_getStorage",
-                (key, block_hash),
-            )
-            .await
-            .map_err(|e| ElectionError::RpcError {
-                message: format!("Failed to query storage value: {}", e),
-                url: self.url.clone(),
-            })?;
+            .request("state_getStorage", (key, block_hash), "Failed to query storage value")
+            .await?;
         
         if response.is_null() {
             return Ok(None);
@@ -1328,12 +2641,14 @@ impl RpcLoader {
         let hex_str = response.as_str().ok_or_else(|| ElectionError::RpcError {
             message: "Storage value is not a string".to_string(),
             url: self.url.clone(),
+            source: None,
         })?;
         
         let hex_str = hex_str.trim_start_matches("0x");
         let bytes = hex::decode(hex_str).map_err(|e| ElectionError::RpcError {
             message: format!("Failed to decode hex: {}", e),
             url: self.url.clone(),
+            source: Some(ErrorSource::new(e)),
         })?;
         
         Ok(Some(bytes))
@@ -1355,6 +2670,7 @@ impl RpcLoader {
                     key_normalized.len() / 2
                 ),
                 url: self.url.clone(),
+                source: None,
             });
         }
         
@@ -1362,11 +2678,13 @@ impl RpcLoader {
         let key_bytes = hex::decode(key_normalized).map_err(|e| ElectionError::RpcError {
             message: format!("Failed to decode key hex: {}", e),
             url: self.url.clone(),
+            source: Some(ErrorSource::new(e)),
         })?;
         
         let prefix_bytes = hex::decode(prefix_normalized).map_err(|e| ElectionError::RpcError {
             message: format!("Failed to decode prefix hex: {}", e),
             url: self.url.clone(),
+            source: Some(ErrorSource::new(e)),
         })?;
         
         // Ensure the key starts with the prefix
@@ -1378,6 +2696,7 @@ impl RpcLoader {
                     prefix_bytes.len()
                 ),
                 url: self.url.clone(),
+                source: None,
             });
         }
         
@@ -1385,6 +2704,7 @@ impl RpcLoader {
             return Err(ElectionError::RpcError {
                 message: "Storage key does not start with expected prefix".to_string(),
                 url: self.url.clone(),
+                source: None,
             });
         }
         
@@ -1402,6 +2722,7 @@ impl RpcLoader {
                     key_bytes.len()
                 ),
                 url: self.url.clone(),
+                source: None,
             });
         }
         
@@ -1461,6 +2782,7 @@ impl RpcLoader {
             return Err(ElectionError::RpcError {
                 message: "StakingLedger data too short".to_string(),
                 url: self.url.clone(),
+                source: None,
             });
         }
         
@@ -1477,6 +2799,7 @@ impl RpcLoader {
             return Err(ElectionError::RpcError {
                 message: "StakingLedger data incomplete".to_string(),
                 url: self.url.clone(),
+                source: None,
             });
         }
         
@@ -1490,6 +2813,91 @@ impl RpcLoader {
     }
 }
 
+/// `pallet-elections-phragmen` member or runner-up
+#[derive(parity_scale_codec::Decode)]
+struct SeatHolder {
+    who: [u8; 32],
+    _stake: u128,
+    _deposit: u128,
+}
+
+/// `pallet-elections-phragmen` vote: the candidates voted for and the locked stake
+#[derive(parity_scale_codec::Decode)]
+struct Voter {
+    votes: Vec<[u8; 32]>,
+    stake: u128,
+    _deposit: u128,
+}
+
+/// Decode an `Identity::SuperOf` value: `(AccountId, Data)`
+///
+/// Returns the parent account and the sub-name when it is stored as raw UTF-8.
+fn decode_super_of(bytes: &[u8]) -> Option<(String, Option<String>)> {
+    let parent = bytes.get(..32)?;
+    // Data: 0 = None, 1..=33 = Raw with length (tag - 1), higher tags are hashes
+    let sub_name = match bytes.get(32) {
+        Some(&tag) if (1..=33).contains(&tag) => {
+            let len = (tag - 1) as usize;
+            bytes
+                .get(33..33 + len)
+                .and_then(|raw| String::from_utf8(raw.to_vec()).ok())
+        }
+        _ => None,
+    };
+    Some((format!("0x{}", hex::encode(parent)), sub_name))
+}
+
+/// Decode a SCALE-encoded 32-byte account ID
+fn decode_account(bytes: &[u8]) -> Option<String> {
+    let account = <[u8; 32] as parity_scale_codec::Decode>::decode(&mut &bytes[..]).ok()?;
+    Some(format!("0x{}", hex::encode(account)))
+}
+
+/// Decode the nominators of an exposure page (`ExposurePage { page_total, others }`)
+/// or, if not `paged`, of a legacy `Exposure { total, own, others }`
+fn decode_exposure_others(bytes: &[u8], paged: bool) -> Option<Vec<(String, u128)>> {
+    use parity_scale_codec::{Compact, Decode};
+    type Others = Vec<([u8; 32], Compact<u128>)>;
+    let input = &mut &bytes[..];
+    let others = if paged {
+        <(Compact<u128>, Others)>::decode(input).ok()?.1
+    } else {
+        <(Compact<u128>, Compact<u128>, Others)>::decode(input).ok()?.2
+    };
+    Some(
+        others
+            .into_iter()
+            .map(|(account, stake)| (format!("0x{}", hex::encode(account)), stake.0))
+            .collect(),
+    )
+}
+
+/// Decode an `EraRewardPoints` value: `{ total: u32, individual: BTreeMap<AccountId, u32> }`
+fn decode_era_reward_points(bytes: &[u8]) -> Option<BTreeMap<String, u32>> {
+    let (_total, individual) =
+        <(u32, Vec<([u8; 32], u32)>) as parity_scale_codec::Decode>::decode(&mut &bytes[..]).ok()?;
+    Some(
+        individual
+            .into_iter()
+            .map(|(account, points)| (format!("0x{}", hex::encode(account)), points))
+            .collect(),
+    )
+}
+
+/// Convert a Perbill commission to a percent (0-100), rounding to the nearest
+/// percent so that 2.5% reads as 3% rather than 2%
+fn commission_percent(perbill: u32) -> u8 {
+    (perbill.saturating_add(5_000_000) / 10_000_000).min(100) as u8
+}
+
+/// Re-implementation of Substrate's twox_64 hashing combinator.
+/// Uses a single XxHash64 computation with seed 0.
+fn twox_64_hash(data: &[u8]) -> [u8; 8] {
+    let mut hasher = XxHash64::with_seed(0);
+    hasher.write(data);
+    hasher.finish().to_le_bytes()
+}
+
 /// Re-implementation of Substrate's twox_128 hashing combinator.
 /// Uses two XxHash64 computations with seeds 0 and 1 and concatenates the outputs.
 fn twox_128_hash(data: &[u8]) -> [u8; 16] {
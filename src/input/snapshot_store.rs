@@ -0,0 +1,337 @@
+//! Local library of election snapshots
+//!
+//! A [`SnapshotStore`] keeps snapshots in a directory, one `<hash>.json` file
+//! each, with an `index.json` recording their chain, block, era, size, tags
+//! and when they were added. Snapshots are identified by their
+//! [content hash](crate::models::election_data::ElectionData::content_hash),
+//! so adding the same data twice stores it once; any unique prefix of at least
+//! [`MIN_ID_PREFIX_LEN`] characters also identifies a snapshot.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::json::JsonLoader;
+use crate::models::election_data::ElectionData;
+use crate::models::validation::ValidationProfile;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the store directory
+pub const SNAPSHOT_DIR_ENV_VAR: &str = "OFFLINE_ELECTION_SNAPSHOT_DIR";
+
+/// Shortest ID prefix accepted in place of a full snapshot ID
+pub const MIN_ID_PREFIX_LEN: usize = 6;
+
+/// Longest tag accepted
+pub const MAX_TAG_LEN: usize = 64;
+
+const INDEX_FILE: &str = "index.json";
+
+/// A stored snapshot's metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    /// Content hash of the snapshot, also its file name
+    pub id: String,
+    /// Chain the snapshot was taken from, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// Block the snapshot was taken at, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Era the snapshot was taken for, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era: Option<u32>,
+    /// Size of the stored file in bytes
+    pub size_bytes: u64,
+    /// Number of candidates
+    pub candidate_count: usize,
+    /// Number of nominators
+    pub nominator_count: usize,
+    /// When the snapshot was added (RFC 3339)
+    pub added_at: String,
+    /// Where the snapshot came from: a file path or RPC URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Labels for curating the collection, e.g. "baseline" or "incident-2024-03"
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,
+}
+
+impl SnapshotEntry {
+    /// When the snapshot was added, if the recorded time parses
+    pub fn added_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.added_at).ok().map(|time| time.with_timezone(&Utc))
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreIndex {
+    #[serde(default)]
+    snapshots: Vec<SnapshotEntry>,
+}
+
+/// Which snapshots [`SnapshotStore::prune`] removes
+///
+/// A snapshot is removed only if it meets every criterion given. Tagged
+/// snapshots are kept unless `include_tagged` is set.
+#[derive(Debug, Clone, Default)]
+pub struct PrunePolicy {
+    /// Keep this many of the most recently added snapshots
+    pub keep_latest: Option<usize>,
+    /// Only remove snapshots added before this time
+    pub added_before: Option<DateTime<Utc>>,
+    /// Also remove tagged snapshots
+    pub include_tagged: bool,
+}
+
+/// Directory of stored snapshots with their metadata
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Open the store in `dir`, creating the directory if needed
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, ElectionError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to create snapshot store: {}", e),
+            path: dir.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        Ok(Self { dir })
+    }
+
+    /// `$OFFLINE_ELECTION_SNAPSHOT_DIR`, else `~/.offline-election/snapshots`
+    pub fn default_dir() -> PathBuf {
+        if let Some(dir) = std::env::var_os(SNAPSHOT_DIR_ENV_VAR) {
+            return PathBuf::from(dir);
+        }
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+        home.map(PathBuf::from).unwrap_or_default().join(".offline-election").join("snapshots")
+    }
+
+    /// Directory the store is kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Store `data`, returning its entry and whether it was new
+    ///
+    /// Data already stored keeps its entry; `tags` are added to it.
+    pub fn add(
+        &self,
+        data: &ElectionData,
+        source: Option<String>,
+        tags: &[String],
+    ) -> Result<(SnapshotEntry, bool), ElectionError> {
+        for tag in tags {
+            validate_tag(tag)?;
+        }
+        let mut index = self.read_index()?;
+        let id = data.content_hash();
+        if let Some(entry) = index.snapshots.iter_mut().find(|entry| entry.id == id) {
+            entry.tags.extend(tags.iter().cloned());
+            let entry = entry.clone();
+            self.write_index(&index)?;
+            return Ok((entry, false));
+        }
+
+        let json = serde_json::to_string(data).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize snapshot: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        let path = self.snapshot_path(&id);
+        write_atomically(&path, &json)?;
+
+        let metadata = data.metadata.as_ref();
+        let entry = SnapshotEntry {
+            id,
+            chain: metadata.and_then(|m| m.chain.clone()),
+            block_number: metadata.and_then(|m| m.block_number),
+            era: metadata.and_then(|m| m.era),
+            size_bytes: json.len() as u64,
+            candidate_count: data.candidates.len(),
+            nominator_count: data.nominators.len(),
+            added_at: Utc::now().to_rfc3339(),
+            source,
+            tags: tags.iter().cloned().collect(),
+        };
+        index.snapshots.push(entry.clone());
+        self.write_index(&index)?;
+        Ok((entry, true))
+    }
+
+    /// Every stored snapshot, ordered by chain, block and time added
+    pub fn list(&self) -> Result<Vec<SnapshotEntry>, ElectionError> {
+        let mut snapshots = self.read_index()?.snapshots;
+        snapshots.sort_by(|a, b| {
+            (&a.chain, a.block_number, &a.added_at).cmp(&(&b.chain, b.block_number, &b.added_at))
+        });
+        Ok(snapshots)
+    }
+
+    /// The snapshot with ID `id`, or the only one whose ID starts with it
+    pub fn get(&self, id: &str) -> Result<SnapshotEntry, ElectionError> {
+        let index = self.read_index()?;
+        resolve(&index, id).map(|position| index.snapshots[position].clone())
+    }
+
+    /// Load the data of a stored snapshot
+    pub fn load(&self, id: &str) -> Result<ElectionData, ElectionError> {
+        let entry = self.get(id)?;
+        JsonLoader::new()
+            .with_validation_profile(ValidationProfile::Permissive)
+            .load_from_file(self.snapshot_path(&entry.id))
+    }
+
+    /// Add `add` and then remove `remove` from a snapshot's tags
+    pub fn tag(&self, id: &str, add: &[String], remove: &[String]) -> Result<SnapshotEntry, ElectionError> {
+        for tag in add {
+            validate_tag(tag)?;
+        }
+        let mut index = self.read_index()?;
+        let position = resolve(&index, id)?;
+        let entry = &mut index.snapshots[position];
+        entry.tags.extend(add.iter().cloned());
+        for tag in remove {
+            entry.tags.remove(tag);
+        }
+        let entry = entry.clone();
+        self.write_index(&index)?;
+        Ok(entry)
+    }
+
+    /// Remove the snapshots `policy` selects, or only report them if `dry_run`
+    ///
+    /// Fails if the policy gives no criterion, rather than removing everything.
+    pub fn prune(&self, policy: &PrunePolicy, dry_run: bool) -> Result<Vec<SnapshotEntry>, ElectionError> {
+        if policy.keep_latest.is_none() && policy.added_before.is_none() {
+            return Err(ElectionError::ValidationError {
+                message: "Pruning needs a number of snapshots to keep or an age to remove".to_string(),
+                field: Some("keep_latest".to_string()),
+            });
+        }
+        let mut index = self.read_index()?;
+
+        // Newest first, so the first `keep_latest` candidates are kept; the
+        // index is in the order snapshots were added, which breaks ties
+        let mut candidates: Vec<(usize, &SnapshotEntry)> = index
+            .snapshots
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| policy.include_tagged || entry.tags.is_empty())
+            .collect();
+        candidates.sort_by(|(i, a), (j, b)| (b.added_at(), j).cmp(&(a.added_at(), i)));
+        let removed: Vec<SnapshotEntry> = candidates
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .skip(policy.keep_latest.unwrap_or(0))
+            .filter(|entry| match (policy.added_before, entry.added_at()) {
+                (Some(before), Some(added_at)) => added_at < before,
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .cloned()
+            .collect();
+        if dry_run || removed.is_empty() {
+            return Ok(removed);
+        }
+
+        index.snapshots.retain(|entry| !removed.iter().any(|r| r.id == entry.id));
+        self.write_index(&index)?;
+        for entry in &removed {
+            let path = self.snapshot_path(&entry.id);
+            if let Err(e) = std::fs::remove_file(&path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    return Err(ElectionError::FileError {
+                        message: format!("Failed to remove snapshot: {}", e),
+                        path,
+                        source: Some(ErrorSource::new(e)),
+                    });
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    fn snapshot_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    fn read_index(&self) -> Result<StoreIndex, ElectionError> {
+        let path = self.dir.join(INDEX_FILE);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(StoreIndex::default()),
+            Err(e) => {
+                return Err(ElectionError::FileError {
+                    message: format!("Failed to read snapshot index: {}", e),
+                    path,
+                    source: Some(ErrorSource::new(e)),
+                })
+            }
+        };
+        serde_json::from_str(&content).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to parse snapshot index: {}", e),
+            path,
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+
+    fn write_index(&self, index: &StoreIndex) -> Result<(), ElectionError> {
+        let json = serde_json::to_string_pretty(index).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize snapshot index: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        write_atomically(&self.dir.join(INDEX_FILE), &json)
+    }
+}
+
+/// Position of the snapshot `id` identifies
+fn resolve(index: &StoreIndex, id: &str) -> Result<usize, ElectionError> {
+    let not_found = |message: String| ElectionError::ValidationError { message, field: Some("id".to_string()) };
+    if let Some(position) = index.snapshots.iter().position(|entry| entry.id == id) {
+        return Ok(position);
+    }
+    if id.len() < MIN_ID_PREFIX_LEN {
+        return Err(not_found(format!(
+            "No snapshot with ID {}; prefixes need at least {} characters",
+            id, MIN_ID_PREFIX_LEN
+        )));
+    }
+    let mut matches = index.snapshots.iter().enumerate().filter(|(_, entry)| entry.id.starts_with(id));
+    match (matches.next(), matches.next()) {
+        (Some((position, _)), None) => Ok(position),
+        (Some(_), Some(_)) => Err(not_found(format!("Snapshot ID prefix {} is ambiguous", id))),
+        (None, _) => Err(not_found(format!("No snapshot with ID {}", id))),
+    }
+}
+
+fn validate_tag(tag: &str) -> Result<(), ElectionError> {
+    let valid = !tag.is_empty()
+        && tag.len() <= MAX_TAG_LEN
+        && tag.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        return Ok(());
+    }
+    Err(ElectionError::ValidationError {
+        message: format!(
+            "Invalid tag '{}': use 1 to {} letters, digits, '-', '_' or '.'",
+            tag, MAX_TAG_LEN
+        ),
+        field: Some("tags".to_string()),
+    })
+}
+
+/// Replace `path` in one step so a crash never leaves it half written
+fn write_atomically(path: &Path, content: &str) -> Result<(), ElectionError> {
+    let partial = path.with_extension("partial");
+    std::fs::write(&partial, content)
+        .and_then(|_| std::fs::rename(&partial, path))
+        .map_err(|e| ElectionError::FileError {
+            message: format!("Failed to write {}: {}", path.display(), e),
+            path: path.to_path_buf(),
+            source: Some(ErrorSource::new(e)),
+        })
+}
@@ -0,0 +1,165 @@
+//! Import of staking-miner snapshot dumps
+//!
+//! The staking miner solves the election from the multi-phase pallet's
+//! `RoundSnapshot`. Loading the same snapshot here lets this tool's solutions
+//! be compared with the miner's on identical inputs. A dump is accepted in
+//! three forms:
+//!
+//! - JSON as serde writes the snapshot: `{"voters": [[who, stake, [target, ...]], ...],
+//!   "targets": [...]}`, optionally with `desired_targets` and `block_number`
+//! - the SCALE-encoded snapshot as a `0x`-prefixed hex string, alone or as
+//!   the `result` of a saved `state_getStorage` response
+//! - the SCALE-encoded snapshot as raw bytes
+//!
+//! As on-chain, targets become candidates with zero stake; a validator's
+//! self-stake is carried by its own self-vote in the voter list.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::election_data::{ElectionData, ElectionMetadata};
+use crate::models::nominator::Nominator;
+use crate::models::onchain_solution::decode_snapshot;
+use crate::models::validation::ValidationProfile;
+use crate::models::validator::ValidatorCandidate;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Snapshot read from a staking-miner dump
+#[derive(Debug, Clone)]
+pub struct StakingMinerSnapshot {
+    /// Voters and targets as election data
+    pub data: ElectionData,
+    /// Number of winners the miner was asked for, if the dump records it
+    pub desired_targets: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotDump {
+    voters: Vec<(String, u128, Vec<String>)>,
+    targets: Vec<String>,
+    #[serde(default)]
+    desired_targets: Option<u32>,
+    #[serde(default)]
+    block_number: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: String,
+}
+
+/// Loader for staking-miner snapshot dumps
+pub struct StakingMinerLoader {
+    profile: ValidationProfile,
+}
+
+impl StakingMinerLoader {
+    /// Create a new staking-miner dump loader
+    pub fn new() -> Self {
+        Self {
+            profile: ValidationProfile::ChainMirroring,
+        }
+    }
+
+    /// Validate loaded data with a different strictness profile
+    pub fn with_validation_profile(mut self, profile: ValidationProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Load a dump from a file in any of the accepted forms
+    pub fn load_from_file(&self, path: PathBuf) -> Result<StakingMinerSnapshot, ElectionError> {
+        let bytes = std::fs::read(&path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read file: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        self.load_from_bytes(&bytes)
+    }
+
+    /// Load a dump from its contents in any of the accepted forms
+    pub fn load_from_bytes(&self, bytes: &[u8]) -> Result<StakingMinerSnapshot, ElectionError> {
+        let text = std::str::from_utf8(bytes).map(str::trim).unwrap_or_default();
+        let snapshot = if text.starts_with('{') {
+            parse_json(text)?
+        } else if let Some(hex_string) = text.trim_matches('"').strip_prefix("0x") {
+            StakingMinerSnapshot {
+                data: decode_hex(hex_string)?,
+                desired_targets: None,
+            }
+        } else {
+            StakingMinerSnapshot {
+                data: decode_snapshot(bytes, None)?,
+                desired_targets: None,
+            }
+        };
+
+        // Tolerated issues are reported when the election runs
+        snapshot.data.validate_with_profile(self.profile)?;
+        Ok(snapshot)
+    }
+}
+
+impl Default for StakingMinerLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn decode_hex(hex_string: &str) -> Result<ElectionData, ElectionError> {
+    let scale = hex::decode(hex_string).map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to decode hex snapshot dump: {}", e),
+        source: Some(ErrorSource::new(e)),
+    })?;
+    decode_snapshot(&scale, None)
+}
+
+fn parse_json(text: &str) -> Result<StakingMinerSnapshot, ElectionError> {
+    let dump: SnapshotDump = match serde_json::from_str(text) {
+        Ok(dump) => dump,
+        Err(e) => {
+            // A saved `state_getStorage` response carries the hex snapshot as its result
+            let response: Option<RpcResponse> = serde_json::from_str(text).ok();
+            if let Some(hex_string) = response.as_ref().and_then(|r| r.result.strip_prefix("0x")) {
+                return Ok(StakingMinerSnapshot {
+                    data: decode_hex(hex_string)?,
+                    desired_targets: None,
+                });
+            }
+            return Err(ElectionError::InvalidData {
+                message: format!("Failed to parse staking-miner snapshot dump: {}", e),
+                source: Some(ErrorSource::new(e)),
+            });
+        }
+    };
+
+    let data = ElectionData {
+        candidates: dump
+            .targets
+            .into_iter()
+            .map(|target| ValidatorCandidate::new(target, 0))
+            .collect(),
+        nominators: dump
+            .voters
+            .into_iter()
+            .map(|(who, stake, targets)| {
+                let mut voter = Nominator::new(who, stake);
+                voter.targets = targets;
+                voter
+            })
+            .collect(),
+        invulnerables: Vec::new(),
+        metadata: Some(ElectionMetadata {
+            block_number: dump.block_number,
+            chain: None,
+            rpc_retries: None,
+            era: None,
+            load_failures: Vec::new(),
+            stake_source: None,
+        }),
+        ..ElectionData::new()
+    };
+    Ok(StakingMinerSnapshot {
+        data,
+        desired_targets: dump.desired_targets,
+    })
+}
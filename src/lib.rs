@@ -16,7 +16,7 @@
 //! ).await?;
 //!
 //! // Configure election
-//! let config = ElectionConfiguration::new()
+//! let config = ElectionConfiguration::builder()
 //!     .algorithm(AlgorithmType::SequentialPhragmen)
 //!     .active_set_size(100)
 //!     .build()?;
@@ -31,23 +31,64 @@
 //! # }
 //! ```
 //!
+//! Embedders that only need one election can skip the engine:
+//!
+//! ```no_run
+//! use offline_election::*;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ElectionConfiguration::builder().active_set_size(100).build()?;
+//! let result = run_election_from_rpc("https://rpc.polkadot.io", Some(10000000), &config).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # Modules
 //!
 //! - [`engine`] - Election execution engine
+//! - [`cancellation`] - Stopping long-running elections
+//! - [`progress`] - Progress reporting from long-running elections
+//! - [`cache`] - Caching results of repeated elections
 //! - [`models`] - Data models for elections, results, and configuration
 //! - [`input`] - Data loading from RPC, JSON files, or synthetic generation
 //! - [`algorithms`] - Election algorithm implementations
 //! - [`diagnostics`] - Result analysis and explanations
+//! - [`payout`] - Era reward and payout simulation
+//! - [`advisor`] - Set size and nomination cap recommendations for new chains
+//! - [`scheduler`] - Recurring backtests for the daemon
+//! - [`settings`] - Layered run settings from defaults, chain presets, files and flags
+//! - `submit` - Solution submission extrinsics (requires the `submit` feature)
+//! - `models::bundle_signing` - Signing result bundles (requires the `sign` feature)
+//! - `regression` - Golden fixtures for verifying a build offline (requires the `regression` feature)
+//! - `testing` - Differential testing against `sp-npos-elections` (requires the `testing` feature)
+//! - `capi` - C ABI for embedding the solver (requires the `capi` feature)
+//! - [`notifications`] - Webhook and command notifications for watch and serve modes
 //! - [`error`] - Error types
 
+pub mod advisor;
 pub mod algorithms;
 pub mod api;
+pub mod cache;
+pub mod cancellation;
+#[cfg(feature = "capi")]
+pub mod capi;
 pub mod cli;
 pub mod diagnostics;
 pub mod engine;
 pub mod error;
 pub mod input;
 pub mod models;
+pub mod notifications;
+pub mod payout;
+pub mod progress;
+#[cfg(feature = "regression")]
+pub mod regression;
+pub mod scheduler;
+pub mod settings;
+#[cfg(feature = "submit")]
+pub mod submit;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod types;
 
 // Re-export commonly used types
@@ -58,11 +99,36 @@ pub mod types;
 /// and call [`execute`](ElectionEngine::execute) with a configuration and data.
 pub use engine::ElectionEngine;
 
+/// Single-shot elections
+///
+/// [`run_election`] runs one election on loaded data and
+/// [`run_election_from_rpc`] fetches the data first, for embedders that do
+/// not need an engine of their own.
+pub use engine::{run_election, run_election_from_rpc};
+
+/// Cancellation of running elections
+///
+/// Pass a token to [`execute_cancellable`](ElectionEngine::execute_cancellable)
+/// and call [`cancel`](CancellationToken::cancel) on a clone to stop the run.
+pub use cancellation::{CancellationToken, ElectionPhase};
+
+/// Progress reporting
+///
+/// Set an observer with [`with_progress_observer`](ElectionEngine::with_progress_observer)
+/// to be told about every finished selection and balancing round.
+pub use progress::{ProgressEvent, ProgressObserver};
+
+/// Result caching
+///
+/// Give an engine a cache with [`with_result_cache`](ElectionEngine::with_result_cache)
+/// to answer repeated elections on the same data and configuration at once.
+pub use cache::ResultCache;
+
 /// Error type for election operations
 ///
 /// All operations return `Result<T, ElectionError>` to handle validation errors,
 /// RPC failures, algorithm errors, and other issues.
-pub use error::ElectionError;
+pub use error::{ElectionError, ErrorCategory, ErrorContext};
 
 /// Election configuration
 ///
@@ -74,13 +140,13 @@ pub use error::ElectionError;
 /// ```no_run
 /// use offline_election::{ElectionConfiguration, AlgorithmType};
 ///
-/// let config = ElectionConfiguration::new()
+/// let config = ElectionConfiguration::builder()
 ///     .algorithm(AlgorithmType::SequentialPhragmen)
 ///     .active_set_size(100)
 ///     .build()?;
 /// # Ok::<(), offline_election::ElectionError>(())
 /// ```
-pub use models::election_config::ElectionConfiguration;
+pub use models::election_config::{ElectionConfiguration, ElectionConfigurationBuilder};
 
 /// Election data containing candidates and nominators
 ///
@@ -103,6 +169,11 @@ pub use models::election_config::ElectionConfiguration;
 /// ```
 pub use models::election_data::ElectionData;
 
+/// Collision handling when merging election data sets
+///
+/// Used by `ElectionData::merge` to compose scenarios from several snapshots.
+pub use models::election_data::MergePolicy;
+
 /// Election result containing selected validators and stake distribution
 ///
 /// The outcome of an election execution, including which validators were selected,
@@ -113,7 +184,7 @@ pub use models::election_result::ElectionResult;
 ///
 /// Allows overriding candidate stakes, nominator stakes, and voting edges
 /// without modifying the original data source.
-pub use models::election_overrides::ElectionOverrides;
+pub use models::election_overrides::{ElectionOverrides, OverrideApplication};
 
 /// Nominator model
 ///
@@ -130,6 +201,11 @@ pub use models::validator::ValidatorCandidate;
 /// Supported algorithms: Sequential Phragmen, Parallel Phragmen, and Multi-phase.
 pub use types::AlgorithmType;
 
+/// Election kind
+///
+/// Staking (validator) elections or council-style elections.
+pub use types::ElectionKind;
+
 /// Data source type
 ///
 /// Specifies where election data comes from: RPC endpoint, JSON file, or synthetic.
@@ -1,41 +1,143 @@
 //! CLI binary entry point for the Offline NPoS Election Tool
 
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use offline_election::cli::advise::AdviseCommand;
+use offline_election::cli::anonymize::AnonymizeCommand;
 use offline_election::cli::commands::{RunCommand, ServerCommand};
+use offline_election::cli::compare_chains::CompareChainsCommand;
+use offline_election::cli::completions::CompletionsCommand;
+use offline_election::cli::config::ConfigCommand;
+use offline_election::cli::daemon::DaemonCommand;
+use offline_election::cli::error_output::{exit_with_error, ErrorFormat};
+use offline_election::cli::hash::HashCommand;
+use offline_election::cli::json_io;
+use offline_election::cli::man_page::ManCommand;
+use offline_election::cli::score::ScoreCommand;
+use offline_election::cli::snapshot_diff::SnapshotDiffCommand;
+use offline_election::cli::snapshots::SnapshotsCommand;
+use offline_election::cli::tui::TuiCommand;
+use offline_election::cli::verify_bundle::VerifyBundleCommand;
+use offline_election::cli::verify_onchain::VerifyOnchainCommand;
+use offline_election::cli::watch_chain::WatchChainCommand;
+use offline_election::error::ElectionError;
+#[cfg(feature = "sign")]
+use offline_election::cli::sign_bundle::SignBundleCommand;
+#[cfg(feature = "submit")]
+use offline_election::cli::submit::SubmitCommand;
+#[cfg(feature = "testing")]
+use offline_election::cli::selftest::SelftestCommand;
 
 #[derive(Parser)]
 #[command(name = "offline-election")]
 #[command(about = "Offline NPoS Election Tool - Run election simulations offline")]
+#[command(arg_required_else_help = true)]
 struct Cli {
     #[command(subcommand)]
-    command: Command,
+    command: Option<Command>,
+
+    /// Read a request document on stdin and write the response to stdout instead of running a command
+    #[arg(long)]
+    json_io: bool,
+
+    /// How to print errors: human or json (exit codes: 3 validation, 4 rpc, 5 algorithm, 6 io)
+    #[arg(long, global = true, default_value = "human", value_parser = ["human", "json"])]
+    error_format: String,
 }
 
 #[derive(clap::Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Command {
     /// Run an election simulation
     Run(RunCommand),
     /// Start the REST API server
     Server(ServerCommand),
+    /// Serve the API and watch the chain until SIGTERM, for containers
+    Daemon(DaemonCommand),
+    /// Explore an election interactively in the terminal
+    Tui(TuiCommand),
+    /// Watch the chain and predict the next validator set
+    WatchChain(WatchChainCommand),
+    /// Verify the queued on-chain solution
+    VerifyOnchain(VerifyOnchainCommand),
+    /// Score a submitted solution against its snapshot
+    Score(ScoreCommand),
+    /// Pseudonymize account IDs in a snapshot
+    Anonymize(AnonymizeCommand),
+    /// Compute the change set between two snapshots
+    SnapshotDiff(SnapshotDiffCommand),
+    /// Run the same election on two chains and compare them
+    CompareChains(CompareChainsCommand),
+    /// Recommend set sizes and nomination caps for a new chain
+    Advise(AdviseCommand),
+    /// Print a snapshot's content hash
+    Hash(HashCommand),
+    /// List, inspect, tag and prune the local snapshot library
+    Snapshots(SnapshotsCommand),
+    /// Check a result bundle and re-run its election
+    VerifyBundle(VerifyBundleCommand),
+    /// Sign a result bundle
+    #[cfg(feature = "sign")]
+    SignBundle(SignBundleCommand),
+    /// Sign and submit an exported solution
+    #[cfg(feature = "submit")]
+    Submit(SubmitCommand),
+    /// Compare elections with sp-npos-elections on random inputs
+    #[cfg(feature = "testing")]
+    Selftest(SelftestCommand),
+    /// Inspect the resolved run settings
+    Config(ConfigCommand),
+    /// Print a shell completion script
+    Completions(CompletionsCommand),
+    /// Print the manual page
+    Man(ManCommand),
 }
 
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
-    
-    match cli.command {
-        Command::Run(cmd) => {
-            if let Err(e) = cmd.execute().await {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
-        }
-        Command::Server(cmd) => {
-            if let Err(e) = cmd.execute().await {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
-        }
+    let error_format = cli.error_format.parse::<ErrorFormat>().unwrap_or_default();
+
+    let command = match (cli.command, cli.json_io) {
+        (Some(command), false) => command,
+        (None, true) => std::process::exit(json_io::run_stdio().await),
+        (Some(_), true) => Cli::command()
+            .error(ErrorKind::ArgumentConflict, "--json-io reads the operation from stdin and takes no command")
+            .exit(),
+        (None, false) => Cli::command().error(ErrorKind::MissingSubcommand, "a command is required").exit(),
+    };
+    if let Err(e) = run(command).await {
+        exit_with_error(&e, error_format);
     }
 }
 
+async fn run(command: Command) -> Result<(), ElectionError> {
+    match command {
+        Command::Run(cmd) => cmd.execute().await,
+        Command::Server(cmd) => cmd.execute().await,
+        Command::Daemon(cmd) => cmd.execute().await,
+        Command::Tui(cmd) => cmd.execute().await,
+        Command::WatchChain(cmd) => cmd.execute().await,
+        Command::VerifyOnchain(cmd) => cmd.execute().await,
+        Command::Score(cmd) => cmd.execute().await,
+        Command::Anonymize(cmd) => cmd.execute().await,
+        Command::SnapshotDiff(cmd) => cmd.execute().await,
+        Command::CompareChains(cmd) => cmd.execute().await,
+        Command::Advise(cmd) => cmd.execute().await,
+        Command::Hash(cmd) => cmd.execute().await,
+        Command::Snapshots(cmd) => cmd.execute().await,
+        Command::VerifyBundle(cmd) => cmd.execute().await,
+        #[cfg(feature = "sign")]
+        Command::SignBundle(cmd) => cmd.execute().await,
+        #[cfg(feature = "submit")]
+        Command::Submit(cmd) => cmd.execute().await,
+        #[cfg(feature = "testing")]
+        Command::Selftest(cmd) => cmd.execute().await,
+        Command::Config(cmd) => cmd.execute().await,
+        Command::Completions(cmd) => {
+            print!("{}", cmd.generate(Cli::command()));
+            Ok(())
+        }
+        Command::Man(cmd) => cmd.execute(Cli::command()),
+    }
+}
@@ -0,0 +1,131 @@
+//! Encoding of account IDs in results and reports
+//!
+//! Snapshots name accounts however their source did: RPC loads use `0x`
+//! public keys, indexers and hand-written files use SS58 addresses, often
+//! with the generic Substrate prefix 42 rather than the chain's own. Data
+//! mixing several encodings elects correctly as long as each account is
+//! spelled the same way throughout, but its results are hard to read and to
+//! compare with explorers. An [`AccountFormat`] re-encodes the account IDs
+//! of a result for output; the election itself always runs on the IDs as
+//! loaded.
+
+use serde::{Deserialize, Serialize};
+use sp_runtime::app_crypto::Ss58Codec;
+use sp_runtime::AccountId32;
+
+/// How account IDs are written in results and reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccountFormat {
+    /// Keep each ID as it was loaded
+    #[default]
+    AsLoaded,
+    /// SS58 address with the prefix of the run's network
+    Network,
+    /// SS58 address with this prefix
+    Ss58(u16),
+    /// `0x`-prefixed hex public key
+    PublicKey,
+}
+
+impl AccountFormat {
+    /// Encode `account_id` in this format
+    ///
+    /// `network_prefix` is the SS58 prefix used by [`AccountFormat::Network`].
+    /// IDs that are neither a 32-byte hex key nor a valid SS58 address, such
+    /// as the names of synthetic data, are returned as they are.
+    pub fn encode(&self, account_id: &str, network_prefix: u16) -> String {
+        let prefix = match *self {
+            Self::AsLoaded => return account_id.to_string(),
+            Self::Network => network_prefix,
+            Self::Ss58(prefix) => prefix,
+            Self::PublicKey => {
+                return match public_key(account_id) {
+                    Some(key) => format!("0x{}", hex::encode(key)),
+                    None => account_id.to_string(),
+                }
+            }
+        };
+        match public_key(account_id) {
+            Some(key) => AccountId32::from(key).to_ss58check_with_version(prefix.into()),
+            None => account_id.to_string(),
+        }
+    }
+
+    /// Whether the format leaves IDs unchanged
+    pub fn is_as_loaded(&self) -> bool {
+        *self == Self::AsLoaded
+    }
+}
+
+impl std::fmt::Display for AccountFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AsLoaded => write!(f, "as-loaded"),
+            Self::Network => write!(f, "network"),
+            Self::Ss58(prefix) => write!(f, "ss58:{}", prefix),
+            Self::PublicKey => write!(f, "public-key"),
+        }
+    }
+}
+
+impl std::str::FromStr for AccountFormat {
+    type Err = String;
+
+    /// Parse "as-loaded", "network", "ss58:N" or "public-key"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "as-loaded" => return Ok(Self::AsLoaded),
+            "network" | "ss58" => return Ok(Self::Network),
+            "public-key" | "hex" => return Ok(Self::PublicKey),
+            _ => {}
+        }
+        match s.split_once(':') {
+            Some(("ss58", prefix)) => {
+                let prefix: u16 = prefix
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid SS58 prefix in account format '{}': {}", s, e))?;
+                // Prefixes above 16383 cannot be encoded
+                if prefix > 0x3fff {
+                    return Err(format!("Invalid SS58 prefix in account format '{}': at most 16383", s));
+                }
+                Ok(Self::Ss58(prefix))
+            }
+            _ => Err(format!(
+                "Invalid account format '{}': expected as-loaded, network, ss58:N or public-key",
+                s
+            )),
+        }
+    }
+}
+
+/// SS58 address prefix of a chain
+///
+/// Polkadot uses 0 and Kusama 2; Westend and unknown chains use the generic
+/// Substrate prefix 42.
+pub fn chain_prefix(chain: &str) -> u16 {
+    match chain.trim().to_lowercase().as_str() {
+        "polkadot" | "dot" => 0,
+        "kusama" | "ksm" => 2,
+        _ => 42,
+    }
+}
+
+/// The 32-byte public key of a `0x` hex key or an SS58 address of any prefix
+pub fn public_key(account_id: &str) -> Option<[u8; 32]> {
+    if let Some(hex_id) = account_id.strip_prefix("0x") {
+        return hex::decode(hex_id).ok().and_then(|bytes| <[u8; 32]>::try_from(bytes).ok());
+    }
+    AccountId32::from_ss58check_with_version(account_id)
+        .ok()
+        .map(|(account, _)| account.into())
+}
+
+/// SS58 prefix of an address, or `None` if `account_id` is not an SS58 address
+pub fn ss58_prefix(account_id: &str) -> Option<u16> {
+    AccountId32::from_ss58check_with_version(account_id)
+        .ok()
+        .map(|(_, format)| format.into())
+}
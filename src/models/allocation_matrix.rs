@@ -0,0 +1,243 @@
+//! Nominator-to-validator allocation matrix for heatmaps
+//!
+//! Governance reports on stake flow want to show who backs whom without
+//! listing every nominator. An [`AllocationMatrix`] groups nominators into
+//! stake buckets (rows) and sums what each bucket allocated to each elected
+//! validator (columns), ready to plot as a heatmap from CSV or JSON.
+
+use crate::cli::output::csv_field;
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::account_format::AccountFormat;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::ElectionResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default number of quantile buckets
+pub const DEFAULT_BUCKET_COUNT: usize = 10;
+
+/// How nominators are grouped into rows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StakeBucketing {
+    /// This many buckets holding about as many nominators each; nominators
+    /// with equal stake share a bucket
+    Quantiles(usize),
+    /// Buckets split at these stakes, in plancks: below the first, from
+    /// each bound up to the next, and from the last on
+    Bounds(Vec<u128>),
+}
+
+/// A range of nominator stakes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeBucket {
+    /// Smallest stake in the bucket, inclusive
+    pub lower: u128,
+    /// Stake the next bucket starts at; `None` for the last bucket
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper: Option<u128>,
+    /// Nominators in the bucket
+    pub nominator_count: usize,
+    /// Stake the bucket allocated across all elected validators
+    pub allocated: u128,
+}
+
+/// Stake allocated by each nominator stake bucket to each elected validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocationMatrix {
+    /// Rows, smallest stakes first
+    pub buckets: Vec<StakeBucket>,
+    /// Columns: elected validators in rank order
+    pub validators: Vec<String>,
+    /// `cells[row][column]` is the stake bucket `row` allocated to validator `column`
+    pub cells: Vec<Vec<u128>>,
+    /// Per row, the stake allocated to elected validators beyond the
+    /// columns, when the columns were limited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_validators: Option<Vec<u128>>,
+}
+
+impl Default for StakeBucketing {
+    fn default() -> Self {
+        StakeBucketing::Quantiles(DEFAULT_BUCKET_COUNT)
+    }
+}
+
+impl StakeBucketing {
+    /// Check the bucketing yields at least one bucket and its bounds increase
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        let invalid = |message: &str| ElectionError::ValidationError {
+            message: message.to_string(),
+            field: Some("bucketing".to_string()),
+        };
+        match self {
+            StakeBucketing::Quantiles(0) => Err(invalid("Quantile bucketing needs at least one bucket")),
+            StakeBucketing::Bounds(bounds) if bounds.windows(2).any(|pair| pair[0] >= pair[1]) => {
+                Err(invalid("Bucket bounds must be strictly increasing"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Lower bounds of the buckets for `stakes`, sorted ascending
+    fn lower_bounds(&self, stakes: &[u128]) -> Vec<u128> {
+        match self {
+            StakeBucketing::Bounds(bounds) => {
+                let mut lowers = vec![0];
+                lowers.extend(bounds.iter().copied().filter(|bound| *bound > 0));
+                lowers
+            }
+            StakeBucketing::Quantiles(count) => {
+                let mut lowers: Vec<u128> = Vec::with_capacity(*count);
+                for bucket in 0..*count {
+                    // Equal stakes stay in one bucket, so the next starts at a larger stake
+                    let start = bucket * stakes.len() / count;
+                    let next = stakes[start..]
+                        .iter()
+                        .find(|stake| lowers.last().map_or(true, |last| last < *stake));
+                    match next {
+                        Some(&stake) => lowers.push(stake),
+                        None => break,
+                    }
+                }
+                if lowers.is_empty() {
+                    lowers.push(0);
+                }
+                lowers
+            }
+        }
+    }
+}
+
+impl AllocationMatrix {
+    /// Bucket the nominators of `data` that allocated stake in `result`
+    ///
+    /// Nominators are bucketed by their stake in `data`; ones `data` does not
+    /// list, e.g. added by an override, by the stake they allocated. With
+    /// `max_validators`, only that many validators of the top ranks get a
+    /// column and the rest are summed in `other_validators`.
+    pub fn from_result(
+        result: &ElectionResult,
+        data: &ElectionData,
+        bucketing: &StakeBucketing,
+        max_validators: Option<usize>,
+    ) -> Result<Self, ElectionError> {
+        bucketing.validate()?;
+
+        let mut allocated: HashMap<&str, u128> = HashMap::new();
+        for allocation in &result.stake_distribution {
+            let amount = allocated.entry(allocation.nominator_id.as_str()).or_insert(0);
+            *amount = amount.saturating_add(allocation.amount);
+        }
+        let data_stakes: HashMap<&str, u128> =
+            data.nominators.iter().map(|n| (n.account_id.as_str(), n.stake)).collect();
+        let mut stakes: Vec<u128> = allocated
+            .iter()
+            .map(|(id, amount)| data_stakes.get(id).copied().unwrap_or(*amount))
+            .collect();
+        stakes.sort_unstable();
+
+        let lowers = bucketing.lower_bounds(&stakes);
+        let bucket_of = |stake: u128| lowers.partition_point(|lower| *lower <= stake).saturating_sub(1);
+
+        let column_count = max_validators.map_or(result.selected_validators.len(), |max| {
+            max.min(result.selected_validators.len())
+        });
+        let columns: HashMap<&str, usize> = result
+            .selected_validators
+            .iter()
+            .enumerate()
+            .map(|(column, v)| (v.account_id.as_str(), column))
+            .collect();
+        let limited = column_count < result.selected_validators.len();
+
+        let mut buckets: Vec<StakeBucket> = lowers
+            .iter()
+            .enumerate()
+            .map(|(row, lower)| StakeBucket {
+                lower: *lower,
+                upper: lowers.get(row + 1).copied(),
+                nominator_count: 0,
+                allocated: 0,
+            })
+            .collect();
+        let mut cells = vec![vec![0u128; column_count]; buckets.len()];
+        let mut others = vec![0u128; buckets.len()];
+        for (id, amount) in &allocated {
+            let row = bucket_of(data_stakes.get(id).copied().unwrap_or(*amount));
+            buckets[row].nominator_count += 1;
+            buckets[row].allocated = buckets[row].allocated.saturating_add(*amount);
+        }
+        for allocation in &result.stake_distribution {
+            let Some(&column) = columns.get(allocation.validator_id.as_str()) else {
+                continue;
+            };
+            let nominator = allocation.nominator_id.as_str();
+            let row = bucket_of(data_stakes.get(nominator).copied().unwrap_or(allocated[nominator]));
+            let cell = match cells[row].get_mut(column) {
+                Some(cell) => cell,
+                None => &mut others[row],
+            };
+            *cell = cell.saturating_add(allocation.amount);
+        }
+
+        Ok(Self {
+            buckets,
+            validators: result.selected_validators[..column_count]
+                .iter()
+                .map(|v| v.account_id.clone())
+                .collect(),
+            cells,
+            other_validators: limited.then_some(others),
+        })
+    }
+
+    /// Re-encode the validator columns in `format`
+    pub fn reencode_accounts(&mut self, format: AccountFormat, network_prefix: u16) {
+        for validator in &mut self.validators {
+            *validator = format.encode(validator, network_prefix);
+        }
+    }
+
+    /// Write the matrix as CSV, one row per bucket
+    ///
+    /// The columns are the bucket's lower and upper stake, its nominator
+    /// count, then one per validator and `Other` when the columns were
+    /// limited. Amounts are in plancks.
+    pub fn to_csv(&self) -> String {
+        let mut output = String::from("Lower,Upper,Nominators");
+        for validator in &self.validators {
+            output.push(',');
+            output.push_str(&csv_field(validator));
+        }
+        if self.other_validators.is_some() {
+            output.push_str(",Other");
+        }
+        output.push('\n');
+
+        for (row, bucket) in self.buckets.iter().enumerate() {
+            output.push_str(&format!(
+                "{},{},{}",
+                bucket.lower,
+                bucket.upper.map(|upper| upper.to_string()).unwrap_or_default(),
+                bucket.nominator_count
+            ));
+            for amount in &self.cells[row] {
+                output.push_str(&format!(",{}", amount));
+            }
+            if let Some(ref others) = self.other_validators {
+                output.push_str(&format!(",{}", others[row]));
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Convert to a JSON string
+    pub fn to_json(&self) -> Result<String, ElectionError> {
+        serde_json::to_string_pretty(self).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize allocation matrix: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+}
@@ -0,0 +1,119 @@
+//! Snapshot pseudonymization for sharing election data
+//!
+//! Every account ID is replaced by `0x` + HMAC-SHA256(key, public key), so
+//! the same account maps to the same pseudonym under one key whether it was
+//! written as hex or in any SS58 format, and the account IDs themselves do
+//! not appear in the output. Stakes, votes and operator groupings are
+//! preserved, so elections on the anonymized snapshot elect the pseudonyms
+//! of the original winners.
+//!
+//! This is pseudonymization, not anonymity: exact stakes and votes are
+//! public on-chain, so anyone with the chain state at the snapshot's block
+//! can match most pseudonyms back to their accounts.
+
+use crate::error::ElectionError;
+use crate::models::account_format::public_key;
+use crate::models::election_data::ElectionData;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Replaces account IDs with deterministic keyed pseudonyms
+#[derive(Clone)]
+pub struct Anonymizer {
+    mac: Hmac<Sha256>,
+}
+
+impl Anonymizer {
+    /// Create an anonymizer keyed with a secret
+    ///
+    /// Keep the key private: anyone holding it can test guesses of which
+    /// account is behind a pseudonym. Reuse it to get matching pseudonyms
+    /// across snapshots.
+    pub fn new(key: &[u8]) -> Result<Self, ElectionError> {
+        if key.is_empty() {
+            return Err(ElectionError::ValidationError {
+                message: "Anonymization key must not be empty".to_string(),
+                field: Some("key".to_string()),
+            });
+        }
+        let mac = Hmac::<Sha256>::new_from_slice(key).map_err(|e| ElectionError::ValidationError {
+            message: format!("Invalid anonymization key: {}", e),
+            field: Some("key".to_string()),
+        })?;
+        Ok(Self { mac })
+    }
+
+    /// Pseudonym of one account ID
+    ///
+    /// Hex keys and SS58 addresses are keyed by their 32-byte public key, so
+    /// every spelling of an account gets the same pseudonym; other IDs are
+    /// keyed by their text.
+    pub fn pseudonym(&self, account_id: &str) -> String {
+        let mut mac = self.mac.clone();
+        match public_key(account_id) {
+            Some(key) => mac.update(&key),
+            None => mac.update(account_id.as_bytes()),
+        }
+        format!("0x{}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// `text` with every word that is a hex key or SS58 address pseudonymized
+    fn pseudonymize_text(&self, text: &str) -> String {
+        let mut scrubbed = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(|c: char| c.is_ascii_alphanumeric()) {
+            scrubbed.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+            let word = &rest[..end];
+            match public_key(word) {
+                Some(_) => scrubbed.push_str(&self.pseudonym(word)),
+                None => scrubbed.push_str(word),
+            }
+            rest = &rest[end..];
+        }
+        scrubbed.push_str(rest);
+        scrubbed
+    }
+
+    /// Copy of `data` with every account ID pseudonymized
+    ///
+    /// Identity display names and nominator metadata are dropped; identity
+    /// parents and controllers are pseudonymized so operator groupings
+    /// survive. Location and hosting attributes describe infrastructure and
+    /// are kept. Accounts named in the load failures of the metadata are
+    /// pseudonymized too.
+    pub fn anonymize(&self, data: &ElectionData) -> ElectionData {
+        let mut anonymized = data.clone();
+        for candidate in &mut anonymized.candidates {
+            candidate.account_id = self.pseudonym(&candidate.account_id);
+            if let Some(ref mut metadata) = candidate.metadata {
+                metadata.identity_display = None;
+                metadata.identity_parent = metadata.identity_parent.as_deref().map(|parent| self.pseudonym(parent));
+                metadata.controller = metadata.controller.as_deref().map(|controller| self.pseudonym(controller));
+            }
+        }
+        for nominator in &mut anonymized.nominators {
+            nominator.account_id = self.pseudonym(&nominator.account_id);
+            for target in &mut nominator.targets {
+                *target = self.pseudonym(target);
+            }
+            nominator.metadata = None;
+        }
+        for account_id in &mut anonymized.invulnerables {
+            *account_id = self.pseudonym(account_id);
+        }
+        if let Some(ref mut metadata) = anonymized.metadata {
+            for failure in &mut metadata.load_failures {
+                *failure = self.pseudonymize_text(failure);
+            }
+        }
+        anonymized
+    }
+}
+
+impl std::fmt::Debug for Anonymizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Anonymizer").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,239 @@
+//! Token amounts with chain decimals
+//!
+//! Stakes inside the election models are plancks (`u128`), the unit the
+//! algorithms work in. Amounts that leave one election, such as the figures
+//! of a [`ChainProfile`](crate::models::chain_comparison::ChainProfile), are
+//! [`Balance`]s, which carry their [`TokenUnit`] so amounts of different
+//! chains are not added or compared by accident. A [`TokenUnit`] also converts
+//! human inputs such as `"1.5 DOT"` to plancks, rejecting amounts in the wrong
+//! token instead of silently mixing them.
+
+use crate::error::ElectionError;
+use serde::{Deserialize, Serialize};
+
+/// Token symbol and number of decimals of a chain's native token
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TokenUnit {
+    /// Token symbol, e.g. "DOT"
+    pub symbol: String,
+    /// Number of decimals; one token is `10^decimals` plancks
+    pub decimals: u8,
+}
+
+impl TokenUnit {
+    /// Create a token unit
+    pub fn new(symbol: impl Into<String>, decimals: u8) -> Self {
+        Self {
+            symbol: symbol.into(),
+            decimals,
+        }
+    }
+
+    /// DOT, 10 decimals
+    pub fn polkadot() -> Self {
+        Self::new("DOT", 10)
+    }
+
+    /// KSM, 12 decimals
+    pub fn kusama() -> Self {
+        Self::new("KSM", 12)
+    }
+
+    /// WND, 12 decimals
+    pub fn westend() -> Self {
+        Self::new("WND", 12)
+    }
+
+    /// Plancks of a token whose decimals are not known
+    pub fn planck() -> Self {
+        Self::new("planck", 0)
+    }
+
+    /// Unit of a known chain, by chain name or token symbol
+    pub fn for_chain(chain: &str) -> Option<Self> {
+        match chain.trim().to_lowercase().as_str() {
+            "polkadot" | "dot" => Some(Self::polkadot()),
+            "kusama" | "ksm" => Some(Self::kusama()),
+            "westend" | "wnd" => Some(Self::westend()),
+            _ => None,
+        }
+    }
+
+    /// Plancks in one whole token, or `None` if that overflows `u128`
+    pub fn one(&self) -> Option<u128> {
+        10u128.checked_pow(self.decimals as u32)
+    }
+
+    /// Whole tokens as a plain decimal number with trailing zeros trimmed, e.g. "1.5"
+    ///
+    /// Falls back to plancks if one token overflows `u128`.
+    pub fn to_decimal_string(&self, plancks: u128) -> String {
+        let Some(one) = self.one() else {
+            return plancks.to_string();
+        };
+        let whole = plancks / one;
+        let fraction = plancks % one;
+        if fraction == 0 {
+            return whole.to_string();
+        }
+        let digits = format!("{:0width$}", fraction, width = self.decimals as usize);
+        format!("{}.{}", whole, digits.trim_end_matches('0'))
+    }
+
+    /// Whole tokens with the symbol, e.g. "1.5 DOT"
+    pub fn format(&self, plancks: u128) -> String {
+        if self.one().is_none() {
+            return format!("{} planck", plancks);
+        }
+        format!("{} {}", self.to_decimal_string(plancks), self.symbol)
+    }
+}
+
+impl std::str::FromStr for TokenUnit {
+    type Err = String;
+
+    /// Parse a known chain or symbol ("polkadot", "KSM"), or "SYMBOL:decimals"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((symbol, decimals)) = s.split_once(':') {
+            let decimals = decimals
+                .trim()
+                .parse::<u8>()
+                .map_err(|e| format!("Invalid decimals in token unit '{}': {}", s, e))?;
+            return Ok(Self::new(symbol.trim().to_uppercase(), decimals));
+        }
+        Self::for_chain(s).ok_or_else(|| {
+            format!(
+                "Unknown token unit: {} (expected DOT, KSM, WND or SYMBOL:decimals)",
+                s
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for TokenUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol)
+    }
+}
+
+/// Amount of a chain's native token, in plancks with the token's decimals attached
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Balance {
+    plancks: u128,
+    unit: TokenUnit,
+}
+
+impl Balance {
+    /// Create a balance from plancks
+    pub fn from_plancks(plancks: u128, unit: TokenUnit) -> Self {
+        Self { plancks, unit }
+    }
+
+    /// Amount in plancks
+    pub fn plancks(&self) -> u128 {
+        self.plancks
+    }
+
+    /// Token unit of the amount
+    pub fn unit(&self) -> &TokenUnit {
+        &self.unit
+    }
+
+    /// Parse a human amount in `unit`, as described in [`parse_amount`]
+    pub fn parse(input: &str, unit: &TokenUnit) -> Result<Self, ElectionError> {
+        Ok(Self::from_plancks(parse_amount(input, Some(unit))?, unit.clone()))
+    }
+
+    /// Add two balances of the same token
+    pub fn checked_add(&self, other: &Balance) -> Result<Balance, ElectionError> {
+        if self.unit != other.unit {
+            return Err(ElectionError::ValidationError {
+                message: format!("Cannot add {} to {}: different token units", other, self),
+                field: None,
+            });
+        }
+        let plancks = self.plancks.checked_add(other.plancks).ok_or_else(|| ElectionError::ValidationError {
+            message: format!("Adding {} to {} overflows", other, self),
+            field: None,
+        })?;
+        Ok(Self::from_plancks(plancks, self.unit.clone()))
+    }
+
+    /// Whole tokens as a plain decimal number, e.g. "1.5"
+    pub fn to_decimal_string(&self) -> String {
+        self.unit.to_decimal_string(self.plancks)
+    }
+}
+
+impl std::fmt::Display for Balance {
+    /// Whole tokens with the symbol, e.g. "1.5 DOT"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.unit.format(self.plancks))
+    }
+}
+
+/// Parse a human amount to plancks
+///
+/// Accepts plancks ("15000000000" or "15000000000 planck") and token amounts
+/// with a symbol ("1.5 DOT", "1.5dot"). With a `unit`, token amounts must use
+/// its symbol; without one, any known token symbol is accepted. Amounts with
+/// more fractional digits than the token has decimals, or that overflow, are
+/// errors.
+pub fn parse_amount(input: &str, unit: Option<&TokenUnit>) -> Result<u128, ElectionError> {
+    let invalid = |reason: String| ElectionError::ValidationError {
+        message: format!("Invalid amount '{}': {}", input, reason),
+        field: None,
+    };
+
+    let trimmed = input.trim();
+    let split = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+        .unwrap_or(trimmed.len());
+    let number = trimmed[..split].replace('_', "");
+    let suffix = trimmed[split..].trim();
+    if number.chars().all(|c| c == '.') {
+        return Err(invalid("expected a number".to_string()));
+    }
+
+    if suffix.is_empty() || suffix.eq_ignore_ascii_case("planck") || suffix.eq_ignore_ascii_case("plancks") {
+        return number.parse::<u128>().map_err(|_| {
+            invalid("plancks must be a whole number; add a token symbol for fractional amounts".to_string())
+        });
+    }
+
+    let amount_unit = match unit {
+        Some(unit) if unit.symbol.eq_ignore_ascii_case(suffix) => unit.clone(),
+        Some(unit) => {
+            return Err(invalid(format!(
+                "amount is in {} but the chain's token is {}",
+                suffix.to_uppercase(),
+                unit.symbol
+            )))
+        }
+        None => TokenUnit::for_chain(suffix).ok_or_else(|| invalid(format!("unknown token '{}'", suffix)))?,
+    };
+
+    let (whole, fraction) = number.split_once('.').unwrap_or((&number, ""));
+    if fraction.contains('.') {
+        return Err(invalid("expected a number".to_string()));
+    }
+    if fraction.len() > amount_unit.decimals as usize {
+        return Err(invalid(format!(
+            "{} has only {} decimals",
+            amount_unit.symbol, amount_unit.decimals
+        )));
+    }
+    let overflow = || invalid("amount is too large".to_string());
+    let one = amount_unit.one().ok_or_else(overflow)?;
+    let whole = if whole.is_empty() { 0 } else { whole.parse::<u128>().map_err(|_| overflow())? };
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        let padded = format!("{:0<width$}", fraction, width = amount_unit.decimals as usize);
+        padded.parse::<u128>().map_err(|_| overflow())?
+    };
+    whole
+        .checked_mul(one)
+        .and_then(|plancks| plancks.checked_add(fraction))
+        .ok_or_else(overflow)
+}
@@ -0,0 +1,114 @@
+//! Signing result bundles (requires the `sign` feature)
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::result_bundle::{BundleSignature, ResultBundle, SignatureScheme, SIGNATURE_FILE};
+use sp_core::crypto::Pair as _;
+use sp_core::{ed25519, sr25519};
+
+/// Key that signs result bundles
+#[allow(clippy::large_enum_variant)]
+pub enum BundleSigner {
+    /// sr25519 key pair
+    Sr25519(sr25519::Pair),
+    /// ed25519 key pair
+    Ed25519(ed25519::Pair),
+}
+
+impl BundleSigner {
+    /// Derive a key pair from a secret URI (mnemonic, hex seed or `//Dev` path)
+    pub fn from_suri(scheme: SignatureScheme, suri: &str) -> Result<Self, ElectionError> {
+        let invalid = |e: sp_core::crypto::SecretStringError| ElectionError::ValidationError {
+            message: format!("Invalid signing key: {:?}", e),
+            field: Some("suri".to_string()),
+        };
+        Ok(match scheme {
+            SignatureScheme::Sr25519 => Self::Sr25519(sr25519::Pair::from_string(suri.trim(), None).map_err(invalid)?),
+            SignatureScheme::Ed25519 => Self::Ed25519(ed25519::Pair::from_string(suri.trim(), None).map_err(invalid)?),
+        })
+    }
+
+    /// Signature scheme of the key
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            Self::Sr25519(_) => SignatureScheme::Sr25519,
+            Self::Ed25519(_) => SignatureScheme::Ed25519,
+        }
+    }
+
+    /// Public key as 0x-prefixed hex
+    pub fn public_key(&self) -> String {
+        match self {
+            Self::Sr25519(pair) => format!("0x{}", hex::encode(pair.public())),
+            Self::Ed25519(pair) => format!("0x{}", hex::encode(pair.public())),
+        }
+    }
+
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sr25519(pair) => pair.sign(message).0.to_vec(),
+            Self::Ed25519(pair) => pair.sign(message).0.to_vec(),
+        }
+    }
+}
+
+impl std::fmt::Debug for BundleSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BundleSigner")
+            .field("scheme", &self.scheme())
+            .field("public_key", &self.public_key())
+            .finish()
+    }
+}
+
+impl ResultBundle {
+    /// Sign the bundle's manifest and write the signature next to it
+    ///
+    /// Replaces any existing signature.
+    pub fn sign(&mut self, signer: &BundleSigner) -> Result<&BundleSignature, ElectionError> {
+        let signature = BundleSignature {
+            scheme: signer.scheme(),
+            public_key: signer.public_key(),
+            signature: format!("0x{}", hex::encode(signer.sign(&self.manifest_bytes))),
+            signed_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let path = self.path.join(SIGNATURE_FILE);
+        let content = serde_json::to_string_pretty(&signature).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize signature: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        std::fs::write(&path, content).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to write signature: {}", e),
+            path: path.clone(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        Ok(self.signature.insert(signature))
+    }
+
+    /// Whether the signature matches the manifest, or `None` if the bundle is unsigned
+    pub fn signature_valid(&self) -> Option<bool> {
+        let signature = self.signature.as_ref()?;
+        Some(signature_matches(signature, &self.manifest_bytes))
+    }
+}
+
+/// Check a signature over `message`; malformed keys or signatures never match
+fn signature_matches(signature: &BundleSignature, message: &[u8]) -> bool {
+    let decode = |value: &str| hex::decode(value.trim_start_matches("0x")).ok();
+    let (Some(public_key), Some(bytes)) = (decode(&signature.public_key), decode(&signature.signature)) else {
+        return false;
+    };
+    match signature.scheme {
+        SignatureScheme::Sr25519 => {
+            let (Ok(public_key), Ok(bytes)) = (<[u8; 32]>::try_from(public_key), <[u8; 64]>::try_from(bytes)) else {
+                return false;
+            };
+            sr25519::Pair::verify(&sr25519::Signature::from_raw(bytes), message, &sr25519::Public::from_raw(public_key))
+        }
+        SignatureScheme::Ed25519 => {
+            let (Ok(public_key), Ok(bytes)) = (<[u8; 32]>::try_from(public_key), <[u8; 64]>::try_from(bytes)) else {
+                return false;
+            };
+            ed25519::Pair::verify(&ed25519::Signature::from_raw(bytes), message, &ed25519::Public::from_raw(public_key))
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Pre-election candidate inclusion and exclusion rules
+
+use crate::error::ElectionError;
+use crate::models::election_data::ElectionData;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// A single inclusion or exclusion constraint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "kebab-case")]
+pub enum CandidateRule {
+    /// Exclude candidates whose commission is at least `percent` (0-100)
+    ExcludeCommissionAtLeast {
+        /// Commission threshold in percent
+        percent: u8,
+    },
+    /// Exclude candidates that block new nominations
+    ExcludeBlocked,
+    /// Exclude candidates that earned no era points in their `eras` most
+    /// recent recorded eras; candidates without an activity record are kept
+    ExcludeInactive {
+        /// Recent eras to look at
+        eras: u32,
+    },
+    /// Exclude the listed candidates
+    Exclude {
+        /// Account IDs to exclude
+        account_ids: Vec<String>,
+    },
+    /// Keep at most `max` candidates per operator (parent identity), preferring
+    /// those with the most approval stake, so no operator can fill more seats
+    MaxPerOperator {
+        /// Candidates kept per operator
+        max: usize,
+    },
+    /// Guarantee the listed candidates a seat; like invulnerables, they are never
+    /// excluded by other rules
+    ForceInclude {
+        /// Account IDs to include
+        account_ids: Vec<String>,
+    },
+}
+
+impl CandidateRule {
+    /// Short human-readable description of the rule
+    pub fn describe(&self) -> String {
+        match self {
+            CandidateRule::ExcludeCommissionAtLeast { percent } => {
+                format!("exclude commission >= {}%", percent)
+            }
+            CandidateRule::ExcludeBlocked => "exclude blocked".to_string(),
+            CandidateRule::ExcludeInactive { eras } => format!("exclude inactive in last {} eras", eras),
+            CandidateRule::Exclude { account_ids } => format!("exclude {} accounts", account_ids.len()),
+            CandidateRule::MaxPerOperator { max } => format!("at most {} per operator", max),
+            CandidateRule::ForceInclude { account_ids } => {
+                format!("force-include {} accounts", account_ids.len())
+            }
+        }
+    }
+}
+
+/// Candidates affected by one rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleApplication {
+    /// The rule that was applied
+    pub rule: CandidateRule,
+    /// Candidates excluded (or, for `force-include`, protected) by this rule
+    pub affected: Vec<String>,
+}
+
+/// Ordered set of candidate rules evaluated before the election
+///
+/// Rules are applied in order; a candidate excluded by an earlier rule is not
+/// reported again by later ones. Serialized as a JSON array of rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CandidateRules {
+    /// Rules in evaluation order
+    pub rules: Vec<CandidateRule>,
+}
+
+impl CandidateRules {
+    /// Create an empty rule set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule
+    pub fn rule(mut self, rule: CandidateRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Exclude candidates whose commission is at least `percent`
+    pub fn exclude_commission_at_least(self, percent: u8) -> Self {
+        self.rule(CandidateRule::ExcludeCommissionAtLeast { percent })
+    }
+
+    /// Exclude candidates that block new nominations
+    pub fn exclude_blocked(self) -> Self {
+        self.rule(CandidateRule::ExcludeBlocked)
+    }
+
+    /// Exclude candidates without era points in the last `eras` recorded eras
+    pub fn exclude_inactive(self, eras: u32) -> Self {
+        self.rule(CandidateRule::ExcludeInactive { eras })
+    }
+
+    /// Exclude the given candidates
+    pub fn exclude(self, account_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.rule(CandidateRule::Exclude {
+            account_ids: account_ids.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Keep at most `max` candidates per operator
+    pub fn max_per_operator(self, max: usize) -> Self {
+        self.rule(CandidateRule::MaxPerOperator { max })
+    }
+
+    /// Guarantee the given candidates a seat
+    pub fn force_include(self, account_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.rule(CandidateRule::ForceInclude {
+            account_ids: account_ids.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Whether no rules are configured
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// All force-included account IDs, in rule order
+    pub fn forced(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.rules
+            .iter()
+            .filter_map(|rule| match rule {
+                CandidateRule::ForceInclude { account_ids } => Some(account_ids),
+                _ => None,
+            })
+            .flatten()
+            .filter(|id| seen.insert(id.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// Copy of the rules with `account_ids` removed from every force-include rule
+    pub fn without_forced(&self, account_ids: &HashSet<String>) -> Self {
+        let rules = self
+            .rules
+            .iter()
+            .map(|rule| match rule {
+                CandidateRule::ForceInclude { account_ids: forced } => CandidateRule::ForceInclude {
+                    account_ids: forced.iter().filter(|id| !account_ids.contains(*id)).cloned().collect(),
+                },
+                rule => rule.clone(),
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Validate the rules against the election data and active set size
+    pub fn validate(&self, data: &ElectionData, active_set_size: u32) -> Result<(), ElectionError> {
+        let candidates: HashSet<&String> = data.candidates.iter().map(|c| &c.account_id).collect();
+        let forced = self.forced();
+        if let Some(unknown) = forced.iter().find(|id| !candidates.contains(id)) {
+            return Err(ElectionError::ValidationError {
+                message: format!("Force-included account {} is not a candidate", unknown),
+                field: Some("candidate_rules.force_include".to_string()),
+            });
+        }
+        if forced.len() > active_set_size as usize {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "{} candidates are force-included but the active set only has {} seats",
+                    forced.len(),
+                    active_set_size
+                ),
+                field: Some("candidate_rules.force_include".to_string()),
+            });
+        }
+        if self
+            .rules
+            .iter()
+            .any(|rule| matches!(rule, CandidateRule::MaxPerOperator { max: 0 }))
+        {
+            return Err(ElectionError::ValidationError {
+                message: "max-per-operator must allow at least one candidate".to_string(),
+                field: Some("candidate_rules.max_per_operator".to_string()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Apply the rules to election data, removing excluded candidates
+    ///
+    /// Votes for excluded candidates are dropped, and nominators left without
+    /// any target are removed since their stake can no longer back anyone.
+    /// Returns the candidates affected by each rule, in rule order.
+    pub fn apply(&self, data: &mut ElectionData) -> Vec<RuleApplication> {
+        // Invulnerables are protected like force-included candidates
+        let forced: HashSet<String> = self
+            .forced()
+            .into_iter()
+            .chain(data.invulnerables.iter().cloned())
+            .collect();
+        let mut excluded: HashSet<String> = HashSet::new();
+        let mut applications = Vec::with_capacity(self.rules.len());
+
+        for rule in &self.rules {
+            let eligible = |id: &String| !forced.contains(id) && !excluded.contains(id);
+            let affected: Vec<String> = match rule {
+                CandidateRule::ExcludeCommissionAtLeast { percent } => data
+                    .candidates
+                    .iter()
+                    .filter(|c| eligible(&c.account_id))
+                    .filter(|c| {
+                        c.metadata
+                            .as_ref()
+                            .and_then(|m| m.commission_rate)
+                            .is_some_and(|rate| rate >= *percent)
+                    })
+                    .map(|c| c.account_id.clone())
+                    .collect(),
+                CandidateRule::ExcludeBlocked => data
+                    .candidates
+                    .iter()
+                    .filter(|c| eligible(&c.account_id) && c.is_blocked())
+                    .map(|c| c.account_id.clone())
+                    .collect(),
+                CandidateRule::ExcludeInactive { eras } => data
+                    .candidates
+                    .iter()
+                    .filter(|c| eligible(&c.account_id))
+                    .filter(|c| c.activity().is_some_and(|activity| activity.inactive_for(*eras as usize)))
+                    .map(|c| c.account_id.clone())
+                    .collect(),
+                CandidateRule::Exclude { account_ids } => {
+                    let listed: HashSet<&String> = account_ids.iter().collect();
+                    data.candidates
+                        .iter()
+                        .filter(|c| eligible(&c.account_id) && listed.contains(&c.account_id))
+                        .map(|c| c.account_id.clone())
+                        .collect()
+                }
+                CandidateRule::MaxPerOperator { max } => {
+                    Self::over_operator_cap(data, *max, &forced, &excluded)
+                }
+                CandidateRule::ForceInclude { account_ids } => account_ids
+                    .iter()
+                    .filter(|id| data.candidates.iter().any(|c| &c.account_id == *id))
+                    .cloned()
+                    .collect(),
+            };
+
+            if !matches!(rule, CandidateRule::ForceInclude { .. }) {
+                excluded.extend(affected.iter().cloned());
+            }
+            applications.push(RuleApplication {
+                rule: rule.clone(),
+                affected,
+            });
+        }
+
+        if !excluded.is_empty() {
+            data.invalidate_caches();
+            data.candidates.retain(|c| !excluded.contains(&c.account_id));
+            data.nominators.retain_mut(|nominator| {
+                let had_targets = !nominator.targets.is_empty();
+                nominator.targets.retain(|t| !excluded.contains(t));
+                !had_targets || !nominator.targets.is_empty()
+            });
+        }
+
+        applications
+    }
+
+    /// Candidates beyond each operator's cap, lowest approval stake first to go
+    fn over_operator_cap(
+        data: &ElectionData,
+        max: usize,
+        forced: &HashSet<String>,
+        excluded: &HashSet<String>,
+    ) -> Vec<String> {
+        let statistics = data.statistics();
+        let approval: HashMap<&String, u128> = data
+            .candidates
+            .iter()
+            .map(|c| (&c.account_id, c.stake.saturating_add(statistics.approval_of(&c.account_id))))
+            .collect();
+
+        let mut by_operator: HashMap<&str, Vec<&String>> = HashMap::new();
+        for candidate in &data.candidates {
+            if !excluded.contains(&candidate.account_id) {
+                by_operator
+                    .entry(candidate.operator_id())
+                    .or_default()
+                    .push(&candidate.account_id);
+            }
+        }
+
+        let mut affected = Vec::new();
+        for members in by_operator.values_mut() {
+            // Forced candidates keep their seats first, then by approval stake
+            members.sort_by(|a, b| {
+                forced
+                    .contains(*b)
+                    .cmp(&forced.contains(*a))
+                    .then_with(|| approval[*b].cmp(&approval[*a]))
+                    .then_with(|| a.cmp(b))
+            });
+            affected.extend(
+                members
+                    .iter()
+                    .skip(max)
+                    .filter(|id| !forced.contains(**id))
+                    .map(|id| (*id).clone()),
+            );
+        }
+        affected.sort();
+        affected
+    }
+}
@@ -0,0 +1,138 @@
+//! Side-by-side profile of elections on two chains
+//!
+//! Chains differ in token decimals, issuance and set size, so absolute stakes
+//! do not compare. A [`ChainProfile`] keeps the absolute figures of one
+//! election together with its token, and the unitless measures a comparison
+//! is read from: how many seats were filled, how concentrated the elected
+//! backing is and how the weakest seat relates to the typical one.
+
+use crate::models::balance::{Balance, TokenUnit};
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::ElectionResult;
+use serde::{Deserialize, Serialize};
+
+/// Set size, stake concentration and minimum backing of one chain's election
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainProfile {
+    /// Name the chain is reported under
+    pub label: String,
+    /// Token the stakes are counted in, if known; amounts of an unknown
+    /// token are in [`TokenUnit::planck`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<TokenUnit>,
+    /// Block the data was read at, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Candidates in the data
+    pub candidate_count: usize,
+    /// Nominators in the data
+    pub nominator_count: usize,
+    /// Seats the election was asked to fill
+    pub desired_seats: u32,
+    /// Validators elected
+    pub elected_count: usize,
+    /// Share of the seats filled, in basis points
+    pub set_utilization_bps: u32,
+    /// Candidates per seat, in basis points (10000 is one candidate per seat)
+    pub candidates_per_seat_bps: u32,
+    /// Stake of every nominator
+    pub total_stake: Balance,
+    /// Backing of the elected set
+    pub elected_backing: Balance,
+    /// Share of the total stake backing the elected set, in basis points
+    pub stake_utilization_bps: u32,
+    /// Backing of the weakest elected validator
+    pub minimal_backing: Balance,
+    /// Median backing of the elected validators
+    pub median_backing: Balance,
+    /// Minimal backing as a share of the median, in basis points
+    pub minimal_to_median_bps: u32,
+    /// Fewest validators whose combined backing exceeds one third of the elected backing
+    pub nakamoto_coefficient: usize,
+    /// Nakamoto coefficient as a share of the elected set, in basis points;
+    /// sets of different sizes compare on this rather than the raw count
+    pub nakamoto_share_bps: u32,
+    /// Share of the elected backing held by the best-backed tenth of the set, in basis points
+    pub top_decile_share_bps: u32,
+    /// Herfindahl-Hirschman index of backing shares (0-10000; higher is more concentrated)
+    pub herfindahl_index: u32,
+}
+
+/// Profiles of two chains' elections, compared side by side
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainComparison {
+    /// First chain
+    pub left: ChainProfile,
+    /// Second chain
+    pub right: ChainProfile,
+}
+
+impl ChainProfile {
+    /// Profile the election `result` computed on `data` for `desired_seats`
+    pub fn from_result(label: impl Into<String>, data: &ElectionData, result: &ElectionResult, desired_seats: u32) -> Self {
+        let mut backings: Vec<u128> = result.selected_validators.iter().map(|v| v.total_backing_stake).collect();
+        backings.sort_unstable_by(|a, b| b.cmp(a));
+        let elected_backing = backings.iter().fold(0u128, |acc, b| acc.saturating_add(*b));
+        let total_stake = data.nominators.iter().fold(0u128, |acc, n| acc.saturating_add(n.stake));
+        let minimal_backing = backings.last().copied().unwrap_or(0);
+        let median_backing = match backings.len() {
+            0 => 0,
+            n if n % 2 == 1 => backings[n / 2],
+            n => backings[n / 2] + (backings[n / 2 - 1] - backings[n / 2]) / 2,
+        };
+        let top_decile = (backings.len() + 9) / 10;
+        let top_decile_backing = backings[..top_decile].iter().fold(0u128, |acc, b| acc.saturating_add(*b));
+        let nakamoto_coefficient =
+            crate::diagnostics::explainer::nakamoto_coefficient(backings.iter().copied(), elected_backing);
+        let herfindahl_index: u128 = backings
+            .iter()
+            .map(|b| {
+                let share = bps(*b, elected_backing) as u128;
+                share * share
+            })
+            .sum::<u128>()
+            / 10_000;
+
+        let token = data.token_unit();
+        let unit = token.clone().unwrap_or_else(TokenUnit::planck);
+        let balance = |plancks: u128| Balance::from_plancks(plancks, unit.clone());
+
+        Self {
+            label: label.into(),
+            token,
+            block_number: result.execution_metadata.block_number.or_else(|| data.metadata.as_ref()?.block_number),
+            candidate_count: data.candidates.len(),
+            nominator_count: data.nominators.len(),
+            desired_seats,
+            elected_count: backings.len(),
+            set_utilization_bps: bps(backings.len() as u128, desired_seats as u128),
+            candidates_per_seat_bps: bps(data.candidates.len() as u128, desired_seats as u128),
+            total_stake: balance(total_stake),
+            elected_backing: balance(elected_backing),
+            stake_utilization_bps: bps(elected_backing, total_stake),
+            minimal_backing: balance(minimal_backing),
+            median_backing: balance(median_backing),
+            minimal_to_median_bps: bps(minimal_backing, median_backing),
+            nakamoto_coefficient,
+            nakamoto_share_bps: bps(nakamoto_coefficient as u128, backings.len() as u128),
+            top_decile_share_bps: bps(top_decile_backing, elected_backing),
+            herfindahl_index: herfindahl_index as u32,
+        }
+    }
+}
+
+impl ChainComparison {
+    /// Compare two chains' profiles
+    pub fn new(left: ChainProfile, right: ChainProfile) -> Self {
+        Self { left, right }
+    }
+}
+
+/// `part` as a share of `whole`, in basis points; zero for an empty whole
+///
+/// Shares above 100% are kept, e.g. more candidates than seats.
+fn bps(part: u128, whole: u128) -> u32 {
+    part.saturating_mul(10_000)
+        .checked_div(whole)
+        .map_or(0, |share| share.min(u32::MAX as u128) as u32)
+}
@@ -0,0 +1,154 @@
+//! Conviction-weighted voting input transformation
+
+use crate::models::election_data::ElectionData;
+use crate::models::election_overrides::sorted_map;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Vote conviction, as in `pallet-conviction-voting`
+///
+/// Voters who lock their stake for longer get their vote multiplied: from
+/// 0.1x for no lock up to 6x for 32 lock periods.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Conviction {
+    /// No lock, 0.1x
+    #[default]
+    None,
+    /// One lock period, 1x
+    Locked1x,
+    /// Two lock periods, 2x
+    Locked2x,
+    /// Four lock periods, 3x
+    Locked3x,
+    /// Eight lock periods, 4x
+    Locked4x,
+    /// Sixteen lock periods, 5x
+    Locked5x,
+    /// Thirty-two lock periods, 6x
+    Locked6x,
+}
+
+impl Conviction {
+    /// Vote multiplier in tenths (1 for 0.1x, 60 for 6x)
+    pub fn multiplier_tenths(&self) -> u128 {
+        match self {
+            Conviction::None => 1,
+            Conviction::Locked1x => 10,
+            Conviction::Locked2x => 20,
+            Conviction::Locked3x => 30,
+            Conviction::Locked4x => 40,
+            Conviction::Locked5x => 50,
+            Conviction::Locked6x => 60,
+        }
+    }
+
+    /// Number of lock periods the stake is locked for
+    pub fn lock_periods(&self) -> u32 {
+        match self {
+            Conviction::None => 0,
+            Conviction::Locked1x => 1,
+            Conviction::Locked2x => 2,
+            Conviction::Locked3x => 4,
+            Conviction::Locked4x => 8,
+            Conviction::Locked5x => 16,
+            Conviction::Locked6x => 32,
+        }
+    }
+
+    /// Weight a stake by this conviction, saturating at `u128::MAX`
+    pub fn weigh(&self, stake: u128) -> u128 {
+        let tenths = self.multiplier_tenths();
+        (stake / 10).saturating_mul(tenths).saturating_add(stake % 10 * tenths / 10)
+    }
+}
+
+impl std::str::FromStr for Conviction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" | "0.1x" => Ok(Conviction::None),
+            "locked1x" | "1x" => Ok(Conviction::Locked1x),
+            "locked2x" | "2x" => Ok(Conviction::Locked2x),
+            "locked3x" | "3x" => Ok(Conviction::Locked3x),
+            "locked4x" | "4x" => Ok(Conviction::Locked4x),
+            "locked5x" | "5x" => Ok(Conviction::Locked5x),
+            "locked6x" | "6x" => Ok(Conviction::Locked6x),
+            _ => Err(format!("Unknown conviction: {} (expected none, locked1x ... locked6x)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Conviction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conviction::None => write!(f, "none"),
+            Conviction::Locked1x => write!(f, "locked1x"),
+            Conviction::Locked2x => write!(f, "locked2x"),
+            Conviction::Locked3x => write!(f, "locked3x"),
+            Conviction::Locked4x => write!(f, "locked4x"),
+            Conviction::Locked5x => write!(f, "locked5x"),
+            Conviction::Locked6x => write!(f, "locked6x"),
+        }
+    }
+}
+
+/// Convictions applied to nominator stakes before the election
+///
+/// Nominators listed in `nominators` use their own conviction; everyone else
+/// uses `default`, or keeps their stake unchanged when there is no default.
+/// Applied once, after overrides and override layers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConvictionWeights {
+    /// Conviction for nominators without their own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Conviction>,
+    /// Per-nominator convictions (account_id -> conviction)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", serialize_with = "sorted_map")]
+    pub nominators: HashMap<String, Conviction>,
+}
+
+impl ConvictionWeights {
+    /// Create an empty set of convictions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the conviction for nominators without their own
+    pub fn default_conviction(mut self, conviction: Conviction) -> Self {
+        self.default = Some(conviction);
+        self
+    }
+
+    /// Set the conviction of one nominator
+    pub fn nominator(mut self, account_id: impl Into<String>, conviction: Conviction) -> Self {
+        self.nominators.insert(account_id.into(), conviction);
+        self
+    }
+
+    /// Whether applying these convictions leaves every stake unchanged
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.nominators.is_empty()
+    }
+
+    /// Conviction applied to a nominator, if any
+    pub fn conviction_of(&self, account_id: &str) -> Option<Conviction> {
+        self.nominators.get(account_id).copied().or(self.default)
+    }
+
+    /// Multiply nominator stakes by their conviction
+    ///
+    /// Returns the number of nominators whose stake was weighted.
+    pub fn apply(&self, data: &mut ElectionData) -> usize {
+        data.invalidate_statistics();
+        let mut weighted = 0;
+        for nominator in &mut data.nominators {
+            if let Some(conviction) = self.conviction_of(&nominator.account_id) {
+                nominator.stake = conviction.weigh(nominator.stake);
+                weighted += 1;
+            }
+        }
+        weighted
+    }
+}
@@ -0,0 +1,206 @@
+//! Aggregate statistics of election data, computed once and cached
+//!
+//! Diagnostics, candidate rules and the engine's scenario runs all need
+//! totals and per-candidate sums over the full nominator set. On a chain
+//! snapshot that is a scan over hundreds of megabytes, so
+//! [`ElectionData::statistics`] computes them in one pass on first use and
+//! keeps them until the data is changed through one of its `&mut` methods,
+//! or its candidate or nominator count changes.
+
+use crate::models::election_data::ElectionData;
+use crate::models::election_overrides::sorted_map;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+/// Totals, approval stakes and vote counts of an [`ElectionData`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DataStatistics {
+    /// Self-stake of all candidates
+    pub total_candidate_stake: u128,
+    /// Stake of all nominators
+    pub total_nominator_stake: u128,
+    /// Nominator stake of the voters naming each candidate, every candidate included
+    ///
+    /// A nominator listing a target twice is counted once, and votes for
+    /// accounts that are not candidates are left out.
+    #[serde(serialize_with = "sorted_map")]
+    pub approval_stake: HashMap<String, u128>,
+    /// Number of nominators naming each candidate, every candidate included
+    #[serde(serialize_with = "sorted_map")]
+    pub candidate_degrees: HashMap<String, usize>,
+    /// Votes for candidates across all nominators
+    pub edge_count: usize,
+    /// Most targets any nominator names
+    pub max_nominator_degree: usize,
+}
+
+impl DataStatistics {
+    /// Compute the statistics in one pass over the nominators
+    pub fn compute(data: &ElectionData) -> Self {
+        let mut approval_stake: HashMap<String, u128> =
+            data.candidates.iter().map(|c| (c.account_id.clone(), 0)).collect();
+        let mut candidate_degrees: HashMap<String, usize> =
+            data.candidates.iter().map(|c| (c.account_id.clone(), 0)).collect();
+        let mut total_nominator_stake: u128 = 0;
+        let mut edge_count = 0;
+        let mut max_nominator_degree = 0;
+
+        for nominator in &data.nominators {
+            total_nominator_stake = total_nominator_stake.saturating_add(nominator.stake);
+            max_nominator_degree = max_nominator_degree.max(nominator.targets.len());
+            let targets: HashSet<&str> = nominator.targets.iter().map(String::as_str).collect();
+            for target in targets {
+                if let Some(stake) = approval_stake.get_mut(target) {
+                    *stake = stake.saturating_add(nominator.stake);
+                    edge_count += 1;
+                }
+                if let Some(degree) = candidate_degrees.get_mut(target) {
+                    *degree += 1;
+                }
+            }
+        }
+
+        Self {
+            total_candidate_stake: data.candidates.iter().fold(0u128, |total, c| total.saturating_add(c.stake)),
+            total_nominator_stake,
+            approval_stake,
+            candidate_degrees,
+            edge_count,
+            max_nominator_degree,
+        }
+    }
+
+    /// Approval stake of a candidate, 0 for unknown accounts
+    pub fn approval_of(&self, account_id: &str) -> u128 {
+        self.approval_stake.get(account_id).copied().unwrap_or(0)
+    }
+
+    /// Nominators naming a candidate, 0 for unknown accounts
+    pub fn degree_of(&self, account_id: &str) -> usize {
+        self.candidate_degrees.get(account_id).copied().unwrap_or(0)
+    }
+}
+
+/// Statistics cached with the lengths of the data they were computed for
+struct CountedStatistics {
+    statistics: Arc<DataStatistics>,
+    candidate_count: usize,
+    nominator_count: usize,
+}
+
+impl CountedStatistics {
+    fn compute(data: &ElectionData) -> Self {
+        Self {
+            statistics: Arc::new(DataStatistics::compute(data)),
+            candidate_count: data.candidates.len(),
+            nominator_count: data.nominators.len(),
+        }
+    }
+
+    /// Whether the data still has the lengths the statistics were computed for
+    fn is_current(&self, data: &ElectionData) -> bool {
+        self.candidate_count == data.candidates.len() && self.nominator_count == data.nominators.len()
+    }
+}
+
+/// Statistics computed on first use and replaced when the data's counts change
+///
+/// Unlike a [`DerivedCache`], a stale value is replaced through `&self`, so
+/// readers share the statistics through an `Arc` instead of borrowing them.
+/// Clones start empty, like the other derived caches.
+#[derive(Default)]
+pub(crate) struct StatisticsCache(RwLock<Option<CountedStatistics>>);
+
+impl StatisticsCache {
+    /// Cached statistics of `data`, recomputed and stored if missing or stale
+    pub(crate) fn get(&self, data: &ElectionData) -> Arc<DataStatistics> {
+        let cached = self.0.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(cached) = cached.as_ref().filter(|cached| cached.is_current(data)) {
+            return Arc::clone(&cached.statistics);
+        }
+        drop(cached);
+        let fresh = CountedStatistics::compute(data);
+        let statistics = Arc::clone(&fresh.statistics);
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = Some(fresh);
+        statistics
+    }
+
+    /// Drop the cached statistics
+    pub(crate) fn clear(&mut self) {
+        *self.0.get_mut().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+impl Clone for StatisticsCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl std::fmt::Debug for StatisticsCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cached = self.0.read().unwrap_or_else(PoisonError::into_inner).is_some();
+        f.write_str(if cached { "StatisticsCache(cached)" } else { "StatisticsCache(empty)" })
+    }
+}
+
+/// Caches never make two data sets unequal
+impl PartialEq for StatisticsCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for StatisticsCache {}
+
+/// Value derived from election data on first use, dropped when the data changes
+///
+/// Boxed to keep `ElectionData` small while the cache is empty. Clones start
+/// empty: a copy of the data is usually made to be changed, for example by
+/// overrides, which write the public fields directly.
+pub(crate) struct DerivedCache<T>(OnceLock<Box<T>>);
+
+impl<T> DerivedCache<T> {
+    /// Cached value, computed with `compute` if there is none
+    pub(crate) fn get_or_compute(&self, compute: impl FnOnce() -> T) -> &T {
+        self.0.get_or_init(|| Box::new(compute()))
+    }
+
+    /// Cached value, if it has been computed
+    pub(crate) fn get_mut(&mut self) -> Option<&mut T> {
+        self.0.get_mut().map(|value| &mut **value)
+    }
+
+    /// Drop the cached value
+    pub(crate) fn clear(&mut self) {
+        self.0 = OnceLock::new();
+    }
+}
+
+impl<T> Default for DerivedCache<T> {
+    fn default() -> Self {
+        Self(OnceLock::new())
+    }
+}
+
+impl<T> Clone for DerivedCache<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<T> std::fmt::Debug for DerivedCache<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.get().is_some() { "DerivedCache(cached)" } else { "DerivedCache(empty)" })
+    }
+}
+
+/// Caches never make two data sets unequal
+impl<T> PartialEq for DerivedCache<T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for DerivedCache<T> {}
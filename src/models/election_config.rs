@@ -1,13 +1,44 @@
 //! Election configuration model
 
-use crate::error::ElectionError;
-use crate::models::election_overrides::ElectionOverrides;
-use crate::types::AlgorithmType;
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::candidate_rules::{CandidateRule, CandidateRules};
+use crate::models::conviction::ConvictionWeights;
+use crate::models::election_overrides::{EdgeAction, ElectionOverrides, OverrideLayer};
+use crate::models::raw_solution::ResultDetail;
+use crate::models::rounding::RoundingPolicy;
+use crate::models::runtime_behavior::{RuntimeVersion, BEHAVIOR_FLAGS};
+use crate::models::validation::ValidationProfile;
+use crate::types::{AlgorithmType, ElectionKind};
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// Version of the serialized configuration shape
+///
+/// Bumped only when a field is removed or changes meaning; new optional
+/// fields keep the version. Configurations without a version are read as
+/// version 1, and newer versions are rejected rather than half understood.
+pub const CONFIG_VERSION: u32 = 1;
 
 /// Configuration for how an election should be executed
+///
+/// Outside this crate a configuration is made with
+/// [`builder`](Self::builder), which needs an active set size before it can
+/// build and validates what it builds, or deserialized. The engine validates
+/// every configuration it runs, so one changed after building is still
+/// checked.
+///
+/// The serialized shape is stable within a [`CONFIG_VERSION`] and
+/// documented in `docs/reference/configuration-schema.md`. Unknown fields
+/// are rejected, and maps are written with their keys in order, so a
+/// configuration round-trips through JSON and TOML unchanged.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
 pub struct ElectionConfiguration {
+    /// Version of the serialized shape, see [`CONFIG_VERSION`]
+    #[serde(default = "config_version", deserialize_with = "deserialize_version")]
+    pub version: u32,
     /// Election algorithm to use
     pub algorithm: AlgorithmType,
     /// Number of validators to select (must be positive)
@@ -15,91 +46,504 @@ pub struct ElectionConfiguration {
     /// Optional parameter overrides
     #[serde(skip_serializing_if = "Option::is_none")]
     pub overrides: Option<ElectionOverrides>,
+    /// Named override layers applied in order on top of `overrides`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub override_layers: Vec<OverrideLayer>,
+    /// Conviction multipliers applied to nominator stakes after overrides
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub convictions: Option<ConvictionWeights>,
+    /// Inclusion and exclusion rules evaluated after overrides, before the election
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidate_rules: Option<CandidateRules>,
+    /// Kind of election; council elections leave unbacked seats empty
+    #[serde(default, skip_serializing_if = "is_staking")]
+    pub kind: ElectionKind,
+    /// Which data issues fail the election and which are only reported
+    #[serde(default, skip_serializing_if = "is_chain_mirroring")]
+    pub validation_profile: ValidationProfile,
+    /// Balance the solution's stake distribution after the election
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balancing: Option<BalancingSettings>,
+    /// Continue past recoverable problems and list them in the result's `issues`
+    ///
+    /// Covers storage entries that failed to load, diagnostics that could not
+    /// be generated, overrides naming unknown accounts, and invulnerables or
+    /// force-included accounts that are not candidates. Without it, these are
+    /// only warned about on stderr, and force-including a non-candidate fails
+    /// the election.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub partial_results: bool,
+    /// Rounding of derived ratios in the result, such as allocation proportions
+    #[serde(default, skip_serializing_if = "RoundingPolicy::is_exact")]
+    pub rounding: RoundingPolicy,
+    /// Whether the result also carries the solver's raw solution
+    #[serde(default, skip_serializing_if = "ResultDetail::is_summary")]
+    pub result_detail: ResultDetail,
+    /// Runtime whose election rules apply, see
+    /// [`RuntimeBehavior`](crate::models::runtime_behavior::RuntimeBehavior)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<RuntimeVersion>,
+    /// Nominators rewarded per validator, largest allocations first
+    ///
+    /// Models the `MaxNominatorRewardedPerValidator` limit of runtimes before
+    /// paged exposures: allocations beyond it are marked `unrewarded` in the
+    /// result, see [`ElectionResult::mark_unrewarded`](crate::models::election_result::ElectionResult::mark_unrewarded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_rewarded_nominators: Option<u32>,
+    /// Whether the data's invulnerables take seats before the algorithm runs
+    ///
+    /// pallet-staking never seats `Staking::Invulnerables`, which only exempts
+    /// validators from slashing, so this is off unless asked for, to model a
+    /// chain or fork that does.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub force_invulnerables: bool,
+    /// Whether diagnostics compare the smallest backing against maximin support
+    ///
+    /// Runs PhragMMS and max-flow balancing on top of the election, so it is
+    /// off unless asked for, see
+    /// [`MaximinGap`](crate::diagnostics::models::MaximinGap).
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub maximin_gap: bool,
     /// Optional block number for RPC snapshot
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_number: Option<u64>,
 }
 
 impl ElectionConfiguration {
-    /// Create a new election configuration
+    /// Builder for a configuration, which cannot build until the active set
+    /// size is set
+    ///
+    /// ```compile_fail
+    /// use offline_election::*;
+    ///
+    /// let config = ElectionConfiguration::builder().algorithm(AlgorithmType::Mms).build();
+    /// ```
+    pub fn builder() -> ElectionConfigurationBuilder<NoActiveSetSize> {
+        ElectionConfigurationBuilder {
+            config: Self::defaults(),
+            state: PhantomData,
+        }
+    }
+
+    /// Create a new election configuration with the defaults: sequential
+    /// Phragmén filling 100 seats
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which requires an active set size and validates")]
     pub fn new() -> Self {
+        Self::defaults()
+    }
+
+    /// Sequential Phragmén filling 100 seats
+    fn defaults() -> Self {
         Self {
+            version: CONFIG_VERSION,
             algorithm: AlgorithmType::SequentialPhragmen,
             active_set_size: 100,
             overrides: None,
+            override_layers: Vec::new(),
+            convictions: None,
+            candidate_rules: None,
+            kind: ElectionKind::Staking,
+            validation_profile: ValidationProfile::ChainMirroring,
+            balancing: None,
+            partial_results: false,
+            rounding: RoundingPolicy::Exact,
+            result_detail: ResultDetail::Summary,
+            runtime: None,
+            max_rewarded_nominators: None,
+            force_invulnerables: false,
+            maximin_gap: false,
             block_number: None,
         }
     }
 
     /// Set the algorithm
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
     pub fn algorithm(mut self, algorithm: AlgorithmType) -> Self {
         self.algorithm = algorithm;
         self
     }
 
     /// Set the active set size
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
     pub fn active_set_size(mut self, size: u32) -> Self {
         self.active_set_size = size;
         self
     }
 
     /// Set parameter overrides
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
     pub fn overrides(mut self, overrides: ElectionOverrides) -> Self {
         self.overrides = Some(overrides);
         self
     }
 
+    /// Add a named override layer
+    ///
+    /// Layers are applied in the order they are added, after `overrides`.
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn override_layer(mut self, name: impl Into<String>, overrides: ElectionOverrides) -> Self {
+        self.override_layers.push(OverrideLayer::new(name, overrides));
+        self
+    }
+
+    /// Set conviction multipliers for nominator stakes
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn convictions(mut self, convictions: ConvictionWeights) -> Self {
+        self.convictions = Some(convictions);
+        self
+    }
+
+    /// Set candidate inclusion and exclusion rules
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn candidate_rules(mut self, rules: CandidateRules) -> Self {
+        self.candidate_rules = Some(rules);
+        self
+    }
+
+    /// Set the kind of election
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn kind(mut self, kind: ElectionKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Set the validation strictness profile
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn validation_profile(mut self, profile: ValidationProfile) -> Self {
+        self.validation_profile = profile;
+        self
+    }
+
+    /// Balance the solution with at most `max_iterations` rounds, stopping
+    /// early once no voter moves more than `tolerance`
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn balancing(mut self, max_iterations: usize, tolerance: u128) -> Self {
+        self.balancing = Some(BalancingSettings { max_iterations, tolerance });
+        self
+    }
+
+    /// Add a candidate rule after any already set
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn candidate_rule(mut self, rule: CandidateRule) -> Self {
+        self.candidate_rules.get_or_insert_with(CandidateRules::new).rules.push(rule);
+        self
+    }
+
+    /// Continue past recoverable problems, listing them in the result
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn partial_results(mut self, enabled: bool) -> Self {
+        self.partial_results = enabled;
+        self
+    }
+
+    /// Set how derived ratios in the result are rounded
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn rounding(mut self, policy: RoundingPolicy) -> Self {
+        self.rounding = policy;
+        self
+    }
+
+    /// Set whether the result carries the solver's raw solution
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn result_detail(mut self, detail: ResultDetail) -> Self {
+        self.result_detail = detail;
+        self
+    }
+
+    /// Apply the election rules of `chain`'s runtime at `spec_version`
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn runtime(mut self, chain: impl Into<String>, spec_version: u32) -> Self {
+        self.runtime = Some(RuntimeVersion::new(chain, spec_version));
+        self
+    }
+
+    /// Reward only the `max` largest nominators of each validator, e.g. 256 on Polkadot
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
+    pub fn max_rewarded_nominators(mut self, max: u32) -> Self {
+        self.max_rewarded_nominators = Some(max);
+        self
+    }
+
     /// Set block number
+    #[deprecated(note = "use `ElectionConfiguration::builder()`, which validates what it builds")]
     pub fn block_number(mut self, block: u64) -> Self {
         self.block_number = Some(block);
         self
     }
 
+    /// SHA-256 of the configuration as hex, identical for equal configurations
+    ///
+    /// Object keys are sorted before hashing, so override maps hash the same
+    /// regardless of insertion order. The shape `version` is left out, so
+    /// hashes recorded before configurations carried one stay valid.
+    pub fn content_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        // Round-trip through `Value` to sort the keys of the override maps
+        let canonical = serde_json::to_vec(self)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+            .map(|mut value| {
+                if let Some(object) = value.as_object_mut() {
+                    object.remove("version");
+                }
+                value
+            })
+            .and_then(|value| serde_json::to_vec(&value).ok())
+            .unwrap_or_default();
+        hex::encode(Sha256::digest(canonical))
+    }
+
+    /// Parse a configuration from JSON
+    pub fn from_json_str(content: &str) -> Result<Self, ElectionError> {
+        serde_json::from_str(content).map_err(|e| ElectionError::ValidationError {
+            message: format!("Failed to parse configuration JSON: {}", e),
+            field: None,
+        })
+    }
+
+    /// Serialize the configuration as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, ElectionError> {
+        serde_json::to_string_pretty(self).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize configuration as JSON: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+
+    /// Parse a configuration from TOML
+    pub fn from_toml_str(content: &str) -> Result<Self, ElectionError> {
+        let parse_error = |message: String| ElectionError::ValidationError {
+            message: format!("Failed to parse configuration TOML: {}", message),
+            field: None,
+        };
+        // The TOML deserializer has no u128, so stakes are read through JSON values
+        let value: toml::Value = toml::from_str(content).map_err(|e| parse_error(e.to_string()))?;
+        let value = serde_json::to_value(value).map_err(|e| parse_error(e.to_string()))?;
+        serde_json::from_value(value).map_err(|e| parse_error(e.to_string()))
+    }
+
+    /// Serialize the configuration as TOML
+    ///
+    /// TOML integers are 64-bit signed, so stakes above `i64::MAX` plancks
+    /// fail to serialize; use [`to_json`](Self::to_json) for those.
+    pub fn to_toml(&self) -> Result<String, ElectionError> {
+        let serialize_error = |message: String| ElectionError::InvalidData {
+            message: format!("Failed to serialize configuration as TOML: {}", message),
+            source: None,
+        };
+        let value = serde_json::to_value(self).map_err(|e| serialize_error(e.to_string()))?;
+        toml::to_string(&toml_value(value).map_err(serialize_error)?).map_err(|e| serialize_error(e.to_string()))
+    }
+
+    /// Load a configuration file, TOML if the extension is `.toml` and JSON otherwise
+    pub fn load_from_file(path: &Path) -> Result<Self, ElectionError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to read configuration file: {}", e),
+            path: path.to_path_buf(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        let parsed = if path.extension().is_some_and(|ext| ext == "toml") {
+            Self::from_toml_str(&content)
+        } else {
+            Self::from_json_str(&content)
+        };
+        parsed.map_err(|e| ElectionError::FileError {
+            message: e.to_string(),
+            path: path.to_path_buf(),
+            source: None,
+        })
+    }
+
     /// Build and validate the configuration
     pub fn build(self) -> Result<Self, ElectionError> {
         self.validate()?;
         Ok(self)
     }
 
-    /// Validate the configuration
+    /// Validate the configuration, failing with the first issue found
+    ///
+    /// The error's message ends with the suggested fix; see
+    /// [`issues`](Self::issues) for every problem at once.
     pub fn validate(&self) -> Result<(), ElectionError> {
-        // Active set size must be positive
+        match self.issues().into_iter().next() {
+            Some(issue) => Err(issue.into_error()),
+            None => Ok(()),
+        }
+    }
+
+    /// Every problem with the configuration, in field order
+    ///
+    /// Checks what can be checked without election data: the active set
+    /// size, balancing, override layers, overrides that add and remove the
+    /// same voting edge, candidate rules that contradict each other, and
+    /// runtimes of chains without recorded election rules.
+    pub fn issues(&self) -> Vec<ConfigurationIssue> {
+        let mut issues = Vec::new();
+
+        if self.version == 0 || self.version > CONFIG_VERSION {
+            issues.push(ConfigurationIssue::new(
+                "version",
+                format!("Configuration version {} is not supported", self.version),
+                format!("set version to {}, the version this build writes", CONFIG_VERSION),
+            ));
+        }
+
         if self.active_set_size == 0 {
-            return Err(ElectionError::ValidationError {
-                message: format!(
-                    "Active set size must be positive, but got {}. Please specify a value greater than 0.",
-                    self.active_set_size
-                ),
-                field: Some("active_set_size".to_string()),
-            });
+            issues.push(ConfigurationIssue::new(
+                "active_set_size",
+                "Active set size must be positive, but got 0",
+                "set active_set_size to the number of seats to fill, e.g. the chain's validator count",
+            ));
         }
 
-        // Validate overrides if present
         if let Some(ref overrides) = self.overrides {
-            // Check for negative stake values in overrides
-            for (account_id, stake) in &overrides.candidate_stakes {
-                if *stake == 0 && account_id.is_empty() {
-                    return Err(ElectionError::ValidationError {
-                        message: format!(
-                            "Invalid candidate stake override: account_id cannot be empty"
-                        ),
-                        field: Some("overrides.candidate_stakes".to_string()),
-                    });
-                }
+            Self::overrides_issues(overrides, "overrides", &mut issues);
+        }
+
+        // Override layers: names must be unique and non-empty
+        let mut layer_names = std::collections::HashSet::new();
+        for (index, layer) in self.override_layers.iter().enumerate() {
+            if layer.name.is_empty() {
+                issues.push(ConfigurationIssue::new(
+                    "override_layers.name",
+                    format!("Override layer #{} has no name", index + 1),
+                    "name every layer, e.g. --override-layer upgrade=layer.json",
+                ));
+            } else if !layer_names.insert(&layer.name) {
+                issues.push(ConfigurationIssue::new(
+                    "override_layers.name",
+                    format!("Duplicate override layer name: {}", layer.name),
+                    "give each layer its own name, or merge the two layers into one file",
+                ));
             }
-            for (account_id, _stake) in &overrides.nominator_stakes {
-                if account_id.is_empty() {
-                    return Err(ElectionError::ValidationError {
-                        message: format!(
-                            "Invalid nominator stake override: account_id cannot be empty"
-                        ),
-                        field: Some("overrides.nominator_stakes".to_string()),
-                    });
-                }
+            Self::overrides_issues(&layer.overrides, &format!("override_layers.{}", layer.name), &mut issues);
+        }
+
+        if let Some(ref rules) = self.candidate_rules {
+            Self::rules_issues(rules, &mut issues);
+        }
+
+        if let Some(balancing) = self.balancing {
+            if !(1..=MAX_BALANCING_ITERATIONS).contains(&balancing.max_iterations) {
+                issues.push(ConfigurationIssue::new(
+                    "balancing.max_iterations",
+                    format!(
+                        "Balancing iterations must be between 1 and {}, but got {}",
+                        MAX_BALANCING_ITERATIONS, balancing.max_iterations
+                    ),
+                    format!(
+                        "use at most {} rounds and a tolerance to stop once balancing converges",
+                        MAX_BALANCING_ITERATIONS
+                    ),
+                ));
             }
         }
 
-        Ok(())
+        if let Some(ref runtime) = self.runtime {
+            if !runtime.is_recorded() {
+                let chains: std::collections::BTreeSet<&str> =
+                    BEHAVIOR_FLAGS.iter().map(|flag| flag.chain).collect();
+                issues.push(ConfigurationIssue::new(
+                    "runtime.chain",
+                    format!("No election rules are recorded for chain '{}'", runtime.chain),
+                    format!(
+                        "use one of {}, or remove the runtime to run without its rules",
+                        chains.into_iter().collect::<Vec<_>>().join(", ")
+                    ),
+                ));
+            }
+        }
+
+        if self.max_rewarded_nominators == Some(0) {
+            issues.push(ConfigurationIssue::new(
+                "max_rewarded_nominators",
+                "Max rewarded nominators must be positive, but got 0",
+                "set it to the runtime's limit, e.g. 256 for Polkadot or 512 for Kusama, or remove it",
+            ));
+        }
+
+        issues
+    }
+
+    /// Problems with a single set of overrides, reported under `field_prefix`
+    fn overrides_issues(overrides: &ElectionOverrides, field_prefix: &str, issues: &mut Vec<ConfigurationIssue>) {
+        if overrides.candidate_stakes.keys().any(String::is_empty) {
+            issues.push(ConfigurationIssue::new(
+                format!("{}.candidate_stakes", field_prefix),
+                "Invalid candidate stake override: account_id cannot be empty",
+                "name the candidate as ACCOUNT_ID=STAKE",
+            ));
+        }
+        if overrides.nominator_stakes.keys().any(String::is_empty) {
+            issues.push(ConfigurationIssue::new(
+                format!("{}.nominator_stakes", field_prefix),
+                "Invalid nominator stake override: account_id cannot be empty",
+                "name the nominator as ACCOUNT_ID=STAKE",
+            ));
+        }
+        if overrides.active_set_size == Some(0) {
+            issues.push(ConfigurationIssue::new(
+                format!("{}.active_set_size", field_prefix),
+                "Overridden active set size must be positive, but got 0",
+                "remove the active_set_size override or set it to at least 1",
+            ));
+        }
+
+        let edges_with = |action: EdgeAction| -> std::collections::BTreeSet<(&str, &str)> {
+            overrides
+                .voting_edges
+                .iter()
+                .filter(|edge| edge.action == action)
+                .map(|edge| (edge.nominator_id.as_str(), edge.candidate_id.as_str()))
+                .collect()
+        };
+        let removed = edges_with(EdgeAction::Remove);
+        for (nominator_id, candidate_id) in edges_with(EdgeAction::Add).intersection(&removed) {
+            issues.push(ConfigurationIssue::new(
+                format!("{}.voting_edges", field_prefix),
+                format!("The vote of {} for {} is both added and removed", nominator_id, candidate_id),
+                "keep only the add or the remove; to change a layer's edge, override it in a later layer",
+            ));
+        }
+    }
+
+    /// Candidate rules that cannot be applied as written
+    ///
+    /// Excluding a force-included account is not a conflict: force-inclusion
+    /// takes precedence by design.
+    fn rules_issues(rules: &CandidateRules, issues: &mut Vec<ConfigurationIssue>) {
+        for (index, rule) in rules.rules.iter().enumerate() {
+            match rule {
+                CandidateRule::ExcludeCommissionAtLeast { percent } if *percent > 100 => {
+                    issues.push(ConfigurationIssue::new(
+                        format!("candidate_rules[{}].percent", index),
+                        format!("Commission threshold is {}%, but commission is at most 100%", percent),
+                        "use a percent between 1 and 100; 100 excludes only candidates keeping all rewards",
+                    ));
+                }
+                CandidateRule::ExcludeCommissionAtLeast { percent: 0 } => {
+                    issues.push(ConfigurationIssue::new(
+                        format!("candidate_rules[{}].percent", index),
+                        "A commission threshold of 0% excludes every candidate",
+                        "use a percent between 1 and 100, or force-include the candidates to keep",
+                    ));
+                }
+                CandidateRule::ExcludeInactive { eras: 0 } => {
+                    issues.push(ConfigurationIssue::new(
+                        format!("candidate_rules[{}].eras", index),
+                        "exclude-inactive must look at least one era back",
+                        "set eras to 1 or more, at most the eras loaded with the snapshot",
+                    ));
+                }
+                CandidateRule::MaxPerOperator { max: 0 } => {
+                    issues.push(ConfigurationIssue::new(
+                        format!("candidate_rules[{}].max", index),
+                        "max-per-operator must allow at least one candidate",
+                        "set max to 1 or more, or remove the rule",
+                    ));
+                }
+                _ => {}
+            }
+        }
     }
 
     /// Validate that active set size doesn't exceed available candidates
@@ -114,10 +558,266 @@ impl ElectionConfiguration {
     }
 }
 
+/// Builder state before the active set size is set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoActiveSetSize {}
+
+/// Builder state once the active set size is set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HasActiveSetSize {}
+
+/// Typestate builder for an [`ElectionConfiguration`]
+///
+/// Options left unset keep the defaults, sequential Phragmén with no
+/// overrides, except the active set size, which must be set before
+/// [`build`](ElectionConfigurationBuilder::build) is available.
+///
+/// ```
+/// use offline_election::*;
+///
+/// let config = ElectionConfiguration::builder()
+///     .algorithm(AlgorithmType::ParallelPhragmen)
+///     .active_set_size(297)
+///     .balancing(10, 0)
+///     .build()?;
+/// # Ok::<(), ElectionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use = "a configuration builder does nothing until built"]
+pub struct ElectionConfigurationBuilder<S> {
+    config: ElectionConfiguration,
+    state: PhantomData<S>,
+}
+
+impl<S> ElectionConfigurationBuilder<S> {
+    /// Change the configuration, moving to state `T`
+    fn with<T>(mut self, set: impl FnOnce(&mut ElectionConfiguration)) -> ElectionConfigurationBuilder<T> {
+        set(&mut self.config);
+        ElectionConfigurationBuilder {
+            config: self.config,
+            state: PhantomData,
+        }
+    }
+
+    /// Set the number of seats to fill
+    pub fn active_set_size(self, size: u32) -> ElectionConfigurationBuilder<HasActiveSetSize> {
+        self.with(|config| config.active_set_size = size)
+    }
+
+    /// Set the algorithm
+    pub fn algorithm(self, algorithm: AlgorithmType) -> Self {
+        self.with(|config| config.algorithm = algorithm)
+    }
+
+    /// Set the kind of election
+    pub fn kind(self, kind: ElectionKind) -> Self {
+        self.with(|config| config.kind = kind)
+    }
+
+    /// Set parameter overrides
+    pub fn overrides(self, overrides: ElectionOverrides) -> Self {
+        self.with(|config| config.overrides = Some(overrides))
+    }
+
+    /// Add a named override layer, applied after `overrides` and earlier layers
+    pub fn override_layer(self, name: impl Into<String>, overrides: ElectionOverrides) -> Self {
+        self.with(|config| config.override_layers.push(OverrideLayer::new(name, overrides)))
+    }
+
+    /// Set conviction multipliers for nominator stakes
+    pub fn convictions(self, convictions: ConvictionWeights) -> Self {
+        self.with(|config| config.convictions = Some(convictions))
+    }
+
+    /// Set candidate inclusion and exclusion rules, replacing any already set
+    pub fn candidate_rules(self, rules: CandidateRules) -> Self {
+        self.with(|config| config.candidate_rules = Some(rules))
+    }
+
+    /// Add a candidate rule, such as a commission filter or a per-operator
+    /// limit, after any already set
+    pub fn candidate_rule(self, rule: CandidateRule) -> Self {
+        self.with(|config| config.candidate_rules.get_or_insert_with(CandidateRules::new).rules.push(rule))
+    }
+
+    /// Set the validation strictness profile
+    pub fn validation_profile(self, profile: ValidationProfile) -> Self {
+        self.with(|config| config.validation_profile = profile)
+    }
+
+    /// Balance the solution with at most `max_iterations` rounds, stopping
+    /// early once no voter moves more than `tolerance`
+    pub fn balancing(self, max_iterations: usize, tolerance: u128) -> Self {
+        self.with(|config| config.balancing = Some(BalancingSettings { max_iterations, tolerance }))
+    }
+
+    /// Continue past recoverable problems, listing them in the result
+    pub fn partial_results(self, enabled: bool) -> Self {
+        self.with(|config| config.partial_results = enabled)
+    }
+
+    /// Set how derived ratios in the result are rounded
+    pub fn rounding(self, policy: RoundingPolicy) -> Self {
+        self.with(|config| config.rounding = policy)
+    }
+
+    /// Set whether the result carries the solver's raw solution
+    pub fn result_detail(self, detail: ResultDetail) -> Self {
+        self.with(|config| config.result_detail = detail)
+    }
+
+    /// Apply the election rules of `chain`'s runtime at `spec_version`
+    pub fn runtime(self, chain: impl Into<String>, spec_version: u32) -> Self {
+        self.with(|config| config.runtime = Some(RuntimeVersion::new(chain, spec_version)))
+    }
+
+    /// Reward only the `max` largest nominators of each validator, e.g. 256 on Polkadot
+    pub fn max_rewarded_nominators(self, max: u32) -> Self {
+        self.with(|config| config.max_rewarded_nominators = Some(max))
+    }
+
+    /// Set whether the data's invulnerables take seats before the algorithm runs
+    pub fn force_invulnerables(self, enabled: bool) -> Self {
+        self.with(|config| config.force_invulnerables = enabled)
+    }
+
+    /// Set whether diagnostics compare the smallest backing against maximin support
+    pub fn maximin_gap(self, enabled: bool) -> Self {
+        self.with(|config| config.maximin_gap = enabled)
+    }
+
+    /// Set the block number of the RPC snapshot
+    pub fn block_number(self, block: u64) -> Self {
+        self.with(|config| config.block_number = Some(block))
+    }
+}
+
+impl ElectionConfigurationBuilder<HasActiveSetSize> {
+    /// Validate and return the configuration
+    pub fn build(self) -> Result<ElectionConfiguration, ElectionError> {
+        self.config.build()
+    }
+}
+
+/// Iteration cap and convergence tolerance for stake balancing
+///
+/// Balancing redistributes each voter's stake over the elected candidates it
+/// backs, round after round, until the largest change a voter makes in a round
+/// is at most `tolerance` or `max_iterations` rounds have run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalancingSettings {
+    /// Most balancing rounds to run
+    #[serde(default = "default_balancing_iterations")]
+    pub max_iterations: usize,
+    /// Largest per-voter change in a round that counts as converged
+    #[serde(default)]
+    pub tolerance: u128,
+}
+
+/// Balancing rounds used when only a tolerance is given
+pub const DEFAULT_BALANCING_ITERATIONS: usize = 10;
+
+/// Most balancing rounds a configuration may ask for
+///
+/// Each round touches every edge of the solution; staking miners balance with
+/// a handful of rounds, so larger caps are almost certainly typos.
+pub const MAX_BALANCING_ITERATIONS: usize = 1_000;
+
+impl Default for BalancingSettings {
+    fn default() -> Self {
+        Self { max_iterations: DEFAULT_BALANCING_ITERATIONS, tolerance: 0 }
+    }
+}
+
+fn default_balancing_iterations() -> usize {
+    DEFAULT_BALANCING_ITERATIONS
+}
+
 impl Default for ElectionConfiguration {
     fn default() -> Self {
-        Self::new()
+        Self::defaults()
     }
 }
 
+/// Convert a JSON value to TOML, failing on integers TOML cannot hold
+fn toml_value(value: serde_json::Value) -> Result<toml::Value, String> {
+    Ok(match value {
+        serde_json::Value::Bool(flag) => toml::Value::Boolean(flag),
+        serde_json::Value::Number(number) => match (number.as_i64(), number.as_f64()) {
+            (Some(integer), _) if number.is_i64() || number.is_u64() => toml::Value::Integer(integer),
+            (_, Some(float)) if number.is_f64() => toml::Value::Float(float),
+            _ => return Err(format!("{} does not fit in a TOML integer", number)),
+        },
+        serde_json::Value::String(text) => toml::Value::String(text),
+        serde_json::Value::Array(items) => {
+            toml::Value::Array(items.into_iter().map(toml_value).collect::<Result<_, _>>()?)
+        }
+        serde_json::Value::Object(entries) => toml::Value::Table(
+            entries
+                .into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| Ok((key, toml_value(value)?)))
+                .collect::<Result<_, String>>()?,
+        ),
+        serde_json::Value::Null => return Err("null has no TOML representation".to_string()),
+    })
+}
 
+fn config_version() -> u32 {
+    CONFIG_VERSION
+}
+
+/// Read a version, rejecting ones newer than this build understands
+fn deserialize_version<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+    if version == 0 || version > CONFIG_VERSION {
+        return Err(serde::de::Error::custom(format!(
+            "unsupported configuration version {} (this build reads versions 1 to {})",
+            version, CONFIG_VERSION
+        )));
+    }
+    Ok(version)
+}
+
+fn is_staking(kind: &ElectionKind) -> bool {
+    *kind == ElectionKind::Staking
+}
+
+fn is_chain_mirroring(profile: &ValidationProfile) -> bool {
+    *profile == ValidationProfile::ChainMirroring
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// A problem with an [`ElectionConfiguration`], found before any data is loaded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigurationIssue {
+    /// Offending field, e.g. `balancing.max_iterations` or `candidate_rules[2].percent`
+    pub field: String,
+    /// What is wrong
+    pub message: String,
+    /// How to fix it
+    pub suggestion: String,
+}
+
+impl ConfigurationIssue {
+    fn new(field: impl Into<String>, message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self { field: field.into(), message: message.into(), suggestion: suggestion.into() }
+    }
+
+    /// Validation error naming the field, with the suggestion in its message
+    pub fn into_error(self) -> ElectionError {
+        ElectionError::ValidationError {
+            message: format!("{} (fix: {})", self.message, self.suggestion),
+            field: Some(self.field),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigurationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} (fix: {})", self.field, self.message, self.suggestion)
+    }
+}
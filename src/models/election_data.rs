@@ -1,10 +1,17 @@
 //! Election data model containing all candidates, nominators, and voting edges
 
 use crate::error::ElectionError;
+use crate::models::balance::{Balance, TokenUnit};
+use crate::models::data_statistics::{DataStatistics, DerivedCache, StatisticsCache};
+use crate::models::memory_report::MemoryReport;
 use crate::models::nominator::Nominator;
+use crate::models::validation::{
+    DataIssue, DataIssueKind, DedupPolicy, DuplicateEntry, DuplicateKind, DuplicateReport, ValidationProfile,
+};
 use crate::models::validator::ValidatorCandidate;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
 
 /// Complete state needed to run an election
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,13 +20,26 @@ pub struct ElectionData {
     pub candidates: Vec<ValidatorCandidate>,
     /// List of nominators with their stakes and votes
     pub nominators: Vec<Nominator>,
+    /// Validators exempt from slashing (`Staking::Invulnerables`)
+    ///
+    /// pallet-staking does not seat them. With
+    /// [`force_invulnerables`](crate::models::election_config::ElectionConfiguration::force_invulnerables)
+    /// set, they consume active set slots before the algorithm fills the rest.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invulnerables: Vec<String>,
     /// Optional metadata about the election data source
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<ElectionMetadata>,
+    /// Aggregates computed on first use, see [`statistics`](Self::statistics)
+    #[serde(skip)]
+    pub(crate) statistics: StatisticsCache,
+    /// Positions by account ID, see [`get_candidate`](Self::get_candidate)
+    #[serde(skip)]
+    pub(crate) index: DerivedCache<AccountIndex>,
 }
 
 /// Metadata about the election data source
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ElectionMetadata {
     /// Block number if data came from RPC
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -27,6 +47,74 @@ pub struct ElectionMetadata {
     /// Chain identifier
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chain: Option<String>,
+    /// Number of RPC calls retried while fetching the data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_retries: Option<u64>,
+    /// Era the data was read for, if it came from an indexer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era: Option<u32>,
+    /// Storage queries that failed while fetching the data; the data is
+    /// missing the entries they would have returned
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub load_failures: Vec<String>,
+    /// Where nominator stakes were read from, if the data came from RPC
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stake_source: Option<StakeSource>,
+}
+
+/// Where the RPC loader reads nominator stakes from
+///
+/// The two differ around unbonding: the ledger still counts funds that are
+/// unlocking, while the exposure is the stake the nominator actually backed
+/// validators with in the active era.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StakeSource {
+    /// Total bonded in `Staking::Ledger`
+    #[default]
+    Ledger,
+    /// Sum of the nominator's exposures in the active era
+    /// (`Staking::ErasStakersPaged`, or `Staking::ErasStakers` on older runtimes);
+    /// nominators without any exposure keep their ledger stake
+    Exposure,
+}
+
+impl std::str::FromStr for StakeSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ledger" => Ok(StakeSource::Ledger),
+            "exposure" => Ok(StakeSource::Exposure),
+            _ => Err(format!("Unknown stake source: {} (expected ledger or exposure)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for StakeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StakeSource::Ledger => write!(f, "ledger"),
+            StakeSource::Exposure => write!(f, "exposure"),
+        }
+    }
+}
+
+/// How [`ElectionData::merge`] resolves account IDs present in both data sets
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergePolicy {
+    /// Fail on the first colliding account ID
+    Reject,
+    /// Keep the entry already present and drop the incoming one
+    KeepExisting,
+    /// Replace the entry already present with the incoming one
+    Replace,
+    /// Add stakes together; colliding nominators vote for the union of their targets
+    #[default]
+    Sum,
+    /// Prefix every incoming account ID so the two data sets never collide
+    Prefix(String),
 }
 
 impl ElectionData {
@@ -35,19 +123,172 @@ impl ElectionData {
         Self {
             candidates: Vec::new(),
             nominators: Vec::new(),
+            invulnerables: Vec::new(),
             metadata: None,
+            statistics: StatisticsCache::default(),
+            index: DerivedCache::default(),
+        }
+    }
+
+    /// Totals, approval stakes and vote counts, computed on first use
+    ///
+    /// The statistics are cached until the data is changed through a `&mut`
+    /// method, [`candidates_mut`](Self::candidates_mut) or
+    /// [`nominators_mut`](Self::nominators_mut). Like the account index, the
+    /// cache notices accounts pushed or removed through the public fields by
+    /// their count changing, and replaces the statistics with fresh ones.
+    /// Code writing the fields in a way that keeps the counts, such as
+    /// changing a stake in place, must call
+    /// [`invalidate_statistics`](Self::invalidate_statistics).
+    pub fn statistics(&self) -> Arc<DataStatistics> {
+        self.statistics.get(self)
+    }
+
+    /// Drop the cached statistics, recomputing them on next use
+    pub fn invalidate_statistics(&mut self) {
+        self.statistics.clear();
+    }
+
+    /// Estimated heap usage of the data, by component
+    pub fn memory_report(&self) -> MemoryReport {
+        MemoryReport::compute(self)
+    }
+
+    /// Release spare capacity held by the data's vectors and strings
+    ///
+    /// Loaders grow vectors as they read, so a freshly loaded snapshot can
+    /// hold noticeably more memory than it uses. Returns the estimated bytes
+    /// released. The data itself, and the cached statistics and account
+    /// index, are unchanged.
+    ///
+    /// Strings are not interned: each target stays its own `String`, so the
+    /// [`repeated_targets`](MemoryReport::repeated_targets) of the memory
+    /// report are still held after shrinking.
+    pub fn shrink_to_fit(&mut self) -> usize {
+        let before = self.memory_report().total();
+        self.candidates.shrink_to_fit();
+        for candidate in &mut self.candidates {
+            candidate.account_id.shrink_to_fit();
+            if let Some(ref mut metadata) = candidate.metadata {
+                for value in [
+                    &mut metadata.on_chain_status,
+                    &mut metadata.identity_display,
+                    &mut metadata.identity_parent,
+                    &mut metadata.country,
+                    &mut metadata.city,
+                    &mut metadata.hosting_provider,
+                    &mut metadata.controller,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    value.shrink_to_fit();
+                }
+                if let Some(ref mut activity) = metadata.activity {
+                    activity.eras.shrink_to_fit();
+                }
+            }
+        }
+        self.nominators.shrink_to_fit();
+        for nominator in &mut self.nominators {
+            nominator.account_id.shrink_to_fit();
+            nominator.targets.shrink_to_fit();
+            for target in &mut nominator.targets {
+                target.shrink_to_fit();
+            }
+            if let Some(ref mut metadata) = nominator.metadata {
+                if let Some(ref mut controller) = metadata.controller {
+                    controller.shrink_to_fit();
+                }
+                metadata.extra.shrink_to_fit();
+            }
+        }
+        self.invulnerables.shrink_to_fit();
+        for account_id in &mut self.invulnerables {
+            account_id.shrink_to_fit();
+        }
+        if let Some(ref mut metadata) = self.metadata {
+            if let Some(ref mut chain) = metadata.chain {
+                chain.shrink_to_fit();
+            }
+            metadata.load_failures.shrink_to_fit();
+            for failure in &mut metadata.load_failures {
+                failure.shrink_to_fit();
+            }
+        }
+        before.saturating_sub(self.memory_report().total())
+    }
+
+    /// Drop the cached statistics and account index
+    ///
+    /// The index notices accounts pushed or removed through the public
+    /// fields by their count changing. Call this after writing the fields
+    /// in a way that keeps the counts, such as renaming an account in place
+    /// or removing one account and pushing another.
+    pub fn invalidate_caches(&mut self) {
+        self.statistics.clear();
+        self.index.clear();
+    }
+
+    /// Mutable candidates, dropping the cached statistics and index
+    pub fn candidates_mut(&mut self) -> &mut Vec<ValidatorCandidate> {
+        self.invalidate_caches();
+        &mut self.candidates
+    }
+
+    /// Mutable nominators, dropping the cached statistics and index
+    pub fn nominators_mut(&mut self) -> &mut Vec<Nominator> {
+        self.invalidate_caches();
+        &mut self.nominators
+    }
+
+    /// Candidate with an account ID, found through an index built on first use
+    ///
+    /// With duplicate account IDs, the first candidate is returned.
+    pub fn get_candidate(&self, account_id: &str) -> Option<&ValidatorCandidate> {
+        self.candidate_position(account_id).map(|position| &self.candidates[position])
+    }
+
+    /// Nominator with an account ID, found through an index built on first use
+    ///
+    /// With duplicate account IDs, the first nominator is returned.
+    pub fn get_nominator(&self, account_id: &str) -> Option<&Nominator> {
+        self.nominator_position(account_id).map(|position| &self.nominators[position])
+    }
+
+    /// Position of the first candidate with an account ID
+    pub(crate) fn candidate_position(&self, account_id: &str) -> Option<usize> {
+        let index = self.index.get_or_compute(|| AccountIndex::build(self));
+        match index.candidate(self, account_id) {
+            Some(found) => found,
+            // Changed since the index was built: scan instead
+            None => self.candidates.iter().position(|c| c.account_id == account_id),
+        }
+    }
+
+    /// Position of the first nominator with an account ID
+    pub(crate) fn nominator_position(&self, account_id: &str) -> Option<usize> {
+        let index = self.index.get_or_compute(|| AccountIndex::build(self));
+        match index.nominator(self, account_id) {
+            Some(found) => found,
+            None => self.nominators.iter().position(|n| n.account_id == account_id),
         }
     }
 
     /// Add a validator candidate
     pub fn add_candidate(&mut self, candidate: ValidatorCandidate) -> Result<(), ElectionError> {
         // Check for duplicate account IDs
-        if self.candidates.iter().any(|c| c.account_id == candidate.account_id) {
+        if self.candidate_position(&candidate.account_id).is_some() {
             return Err(ElectionError::ValidationError {
                 message: format!("Duplicate candidate account ID: {}", candidate.account_id),
                 field: Some("candidates".to_string()),
             });
         }
+        self.invalidate_statistics();
+        let position = self.candidates.len();
+        if let Some(index) = self.index.get_mut() {
+            index.push_candidate(&candidate.account_id, position);
+        }
         self.candidates.push(candidate);
         Ok(())
     }
@@ -55,18 +296,31 @@ impl ElectionData {
     /// Add a nominator
     pub fn add_nominator(&mut self, nominator: Nominator) -> Result<(), ElectionError> {
         // Check for duplicate account IDs
-        if self.nominators.iter().any(|n| n.account_id == nominator.account_id) {
+        if self.nominator_position(&nominator.account_id).is_some() {
             return Err(ElectionError::ValidationError {
                 message: format!("Duplicate nominator account ID: {}", nominator.account_id),
                 field: Some("nominators".to_string()),
             });
         }
+        self.invalidate_statistics();
+        let position = self.nominators.len();
+        if let Some(index) = self.index.get_mut() {
+            index.push_nominator(&nominator.account_id, position);
+        }
         self.nominators.push(nominator);
         Ok(())
     }
 
-    /// Validate election data
+    /// Validate election data with the default [`ValidationProfile::ChainMirroring`]
     pub fn validate(&self) -> Result<(), ElectionError> {
+        self.validate_with_profile(ValidationProfile::ChainMirroring).map(|_| ())
+    }
+
+    /// Validate election data, tolerating the issues `profile` allows
+    ///
+    /// Returns the tolerated issues as warnings, or an error for the first
+    /// structural problem or issue the profile does not tolerate.
+    pub fn validate_with_profile(&self, profile: ValidationProfile) -> Result<Vec<DataIssue>, ElectionError> {
         // Must contain at least one validator candidate
         if self.candidates.is_empty() {
             return Err(ElectionError::ValidationError {
@@ -82,11 +336,15 @@ impl ElectionData {
         // This allows the tool to work when RPC endpoints don't support storage queries
 
         // All candidate account IDs must be unique
-        let mut candidate_ids = HashSet::new();
+        let mut candidate_ids = HashMap::with_capacity(self.candidates.len());
         for candidate in &self.candidates {
-            if !candidate_ids.insert(&candidate.account_id) {
+            if candidate_ids.insert(&candidate.account_id, candidate).is_some() {
                 return Err(ElectionError::ValidationError {
-                    message: format!("Duplicate candidate account ID: {}", candidate.account_id),
+                    message: format!(
+                        "Duplicate candidate account ID: {} ({})",
+                        candidate.account_id,
+                        self.duplicate_report().summary()
+                    ),
                     field: Some("candidates".to_string()),
                 });
             }
@@ -97,35 +355,489 @@ impl ElectionData {
         for nominator in &self.nominators {
             if !nominator_ids.insert(&nominator.account_id) {
                 return Err(ElectionError::ValidationError {
-                    message: format!("Duplicate nominator account ID: {}", nominator.account_id),
+                    message: format!(
+                        "Duplicate nominator account ID: {} ({})",
+                        nominator.account_id,
+                        self.duplicate_report().summary()
+                    ),
                     field: Some("nominators".to_string()),
                 });
             }
         }
 
-        // All voting edges must reference existing candidates
-        let candidate_id_set: HashSet<&String> = self.candidates.iter().map(|c| &c.account_id).collect();
+        // Invulnerables must be unique; ones that are not candidates are ignored by the engine
+        let mut invulnerable_ids = HashSet::new();
+        for account_id in &self.invulnerables {
+            if !invulnerable_ids.insert(account_id) {
+                return Err(ElectionError::ValidationError {
+                    message: format!(
+                        "Duplicate invulnerable account ID: {} ({})",
+                        account_id,
+                        self.duplicate_report().summary()
+                    ),
+                    field: Some("invulnerables".to_string()),
+                });
+            }
+        }
+
+        let mut issues = Vec::new();
+        let mut report = |kind: DataIssueKind, account_id: &str, target: Option<&String>, message: String, field: &str| {
+            if profile.is_error(kind) {
+                return Err(ElectionError::ValidationError {
+                    message: message.clone(),
+                    field: Some(field.to_string()),
+                });
+            }
+            issues.push(DataIssue {
+                kind,
+                account_id: account_id.to_string(),
+                target: target.cloned(),
+                message,
+            });
+            Ok(())
+        };
+
         for nominator in &self.nominators {
+            if nominator.stake == 0 {
+                report(
+                    DataIssueKind::ZeroStakeNominator,
+                    &nominator.account_id,
+                    None,
+                    format!("Nominator '{}' has zero stake", nominator.account_id),
+                    "nominators.stake",
+                )?;
+            }
+
+            let mut seen_targets = HashSet::new();
             for target in &nominator.targets {
-                if !candidate_id_set.contains(target) {
+                // All voting edges must reference existing candidates
+                if !candidate_ids.contains_key(target) {
                     let available_candidates: Vec<String> = self.candidates.iter().take(5).map(|c| c.account_id.clone()).collect();
                     let candidate_list = if self.candidates.len() > 5 {
                         format!("{} (and {} more)", available_candidates.join(", "), self.candidates.len() - 5)
                     } else {
                         available_candidates.join(", ")
                     };
-                    return Err(ElectionError::ValidationError {
-                        message: format!(
+                    report(
+                        DataIssueKind::DanglingEdge,
+                        &nominator.account_id,
+                        Some(target),
+                        format!(
                             "Nominator '{}' votes for non-existent candidate '{}'. Available candidates: {}",
                             nominator.account_id, target, candidate_list
                         ),
-                        field: Some("nominators.targets".to_string()),
+                        "nominators.targets",
+                    )?;
+                }
+                if !seen_targets.insert(target) {
+                    report(
+                        DataIssueKind::DuplicateTarget,
+                        &nominator.account_id,
+                        Some(target),
+                        format!("Nominator '{}' lists target '{}' more than once", nominator.account_id, target),
+                        "nominators.targets",
+                    )?;
+                }
+                if *target == nominator.account_id {
+                    report(
+                        DataIssueKind::SelfNomination,
+                        &nominator.account_id,
+                        Some(target),
+                        format!("Candidate '{}' nominates itself", nominator.account_id),
+                        "nominators.targets",
+                    )?;
+                }
+            }
+
+            // An account that is both candidate and nominator should bond the same stake in both roles
+            if let Some(candidate) = candidate_ids.get(&nominator.account_id) {
+                if candidate.stake != nominator.stake {
+                    report(
+                        DataIssueKind::RoleConflict,
+                        &nominator.account_id,
+                        None,
+                        format!(
+                            "Account '{}' has stake {} as a candidate but {} as a nominator",
+                            nominator.account_id, candidate.stake, nominator.stake
+                        ),
+                        "nominators.stake",
+                    )?;
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Report account IDs listed more than once
+    ///
+    /// Covers duplicated candidates, nominators and invulnerables, and
+    /// accounts listed as both candidate and nominator with different stakes.
+    pub fn duplicate_report(&self) -> DuplicateReport {
+        let mut entries = Vec::new();
+        entries.extend(Self::duplicates_of(&self.candidates, DuplicateKind::Candidate, |c| &c.account_id, |c| c.stake));
+        entries.extend(Self::duplicates_of(&self.nominators, DuplicateKind::Nominator, |n| &n.account_id, |n| n.stake));
+        entries.extend(
+            Self::duplicates_of(&self.invulnerables, DuplicateKind::Invulnerable, |id| id, |_| 0)
+                .into_iter()
+                .map(|entry| DuplicateEntry { stakes: Vec::new(), ..entry }),
+        );
+
+        // Compare against the first listing of a duplicated candidate
+        let mut candidates = HashMap::with_capacity(self.candidates.len());
+        for candidate in &self.candidates {
+            candidates.entry(&candidate.account_id).or_insert(candidate);
+        }
+        let mut seen = HashSet::new();
+        for nominator in &self.nominators {
+            if !seen.insert(&nominator.account_id) {
+                continue;
+            }
+            if let Some(candidate) = candidates.get(&nominator.account_id) {
+                if candidate.stake != nominator.stake {
+                    entries.push(DuplicateEntry {
+                        kind: DuplicateKind::CandidateAndNominator,
+                        account_id: nominator.account_id.clone(),
+                        occurrences: 2,
+                        stakes: vec![candidate.stake, nominator.stake],
+                        conflicting: true,
                     });
                 }
             }
         }
 
-        Ok(())
+        DuplicateReport { entries }
+    }
+
+    /// Collapse duplicated candidates, nominators and invulnerables into one entry each
+    ///
+    /// Returns the report of what was found before resolving. Accounts listed
+    /// as both candidate and nominator are reported but left as they are.
+    pub fn dedup(&mut self, policy: DedupPolicy) -> DuplicateReport {
+        let report = self.duplicate_report();
+        if report.is_empty() {
+            return report;
+        }
+        self.invalidate_caches();
+
+        let candidates = std::mem::take(&mut self.candidates);
+        self.candidates = Self::resolve_duplicates(candidates, policy, |c| &c.account_id, |c| c.stake, |kept, other| {
+            kept.stake = kept.stake.saturating_add(other.stake);
+            if kept.metadata.is_none() {
+                kept.metadata = other.metadata;
+            }
+        });
+
+        let nominators = std::mem::take(&mut self.nominators);
+        self.nominators = Self::resolve_duplicates(nominators, policy, |n| &n.account_id, |n| n.stake, |kept, other| {
+            kept.stake = kept.stake.saturating_add(other.stake);
+            for target in other.targets {
+                if !kept.targets.contains(&target) {
+                    kept.targets.push(target);
+                }
+            }
+            if kept.metadata.is_none() {
+                kept.metadata = other.metadata;
+            }
+        });
+
+        let mut seen = HashSet::new();
+        self.invulnerables.retain(|account_id| seen.insert(account_id.clone()));
+
+        report
+    }
+
+    /// Duplicated account IDs in `items`, in order of first appearance
+    fn duplicates_of<T: PartialEq>(
+        items: &[T],
+        kind: DuplicateKind,
+        id: impl Fn(&T) -> &String,
+        stake: impl Fn(&T) -> u128,
+    ) -> Vec<DuplicateEntry> {
+        let mut groups: Vec<(&String, Vec<&T>)> = Vec::new();
+        let mut index: HashMap<&String, usize> = HashMap::new();
+        for item in items {
+            match index.get(id(item)) {
+                Some(&i) => groups[i].1.push(item),
+                None => {
+                    index.insert(id(item), groups.len());
+                    groups.push((id(item), vec![item]));
+                }
+            }
+        }
+        groups
+            .into_iter()
+            .filter(|(_, group)| group.len() > 1)
+            .map(|(account_id, group)| DuplicateEntry {
+                kind,
+                account_id: account_id.clone(),
+                occurrences: group.len(),
+                stakes: group.iter().map(|item| stake(item)).collect(),
+                conflicting: group.iter().any(|item| *item != group[0]),
+            })
+            .collect()
+    }
+
+    /// Keep one entry per account ID at the position of its first occurrence
+    fn resolve_duplicates<T>(
+        items: Vec<T>,
+        policy: DedupPolicy,
+        id: impl Fn(&T) -> &String,
+        stake: impl Fn(&T) -> u128,
+        sum: impl Fn(&mut T, T),
+    ) -> Vec<T> {
+        let mut resolved: Vec<T> = Vec::with_capacity(items.len());
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for item in items {
+            let Some(&i) = index.get(id(&item)) else {
+                index.insert(id(&item).clone(), resolved.len());
+                resolved.push(item);
+                continue;
+            };
+            match policy {
+                DedupPolicy::KeepFirst => {}
+                DedupPolicy::KeepLast => resolved[i] = item,
+                DedupPolicy::KeepHighestStake => {
+                    if stake(&item) > stake(&resolved[i]) {
+                        resolved[i] = item;
+                    }
+                }
+                DedupPolicy::Sum => sum(&mut resolved[i], item),
+            }
+        }
+        resolved
+    }
+
+    /// Remove duplicate targets and votes for accounts that are not candidates
+    ///
+    /// Returns the number of votes removed.
+    pub fn sanitize(&mut self) -> usize {
+        self.invalidate_statistics();
+        let candidate_ids: HashSet<String> = self.candidates.iter().map(|c| c.account_id.clone()).collect();
+        let mut removed = 0;
+        for nominator in &mut self.nominators {
+            let before = nominator.targets.len();
+            let mut seen = HashSet::new();
+            nominator
+                .targets
+                .retain(|target| candidate_ids.contains(target) && seen.insert(target.clone()));
+            removed += before - nominator.targets.len();
+        }
+        removed
+    }
+
+    /// Merge another data set into this one
+    ///
+    /// Candidates, nominators and invulnerables of `other` are added, with
+    /// account IDs present in both resolved by `policy`. Metadata of `self` is
+    /// kept. Returns the number of colliding account IDs that were resolved.
+    pub fn merge(&mut self, mut other: ElectionData, policy: MergePolicy) -> Result<usize, ElectionError> {
+        self.invalidate_caches();
+        if let MergePolicy::Prefix(ref prefix) = policy {
+            let prefixed = |account_id: &str| format!("{}{}", prefix, account_id);
+            for candidate in &mut other.candidates {
+                candidate.account_id = prefixed(&candidate.account_id);
+                if let Some(ref mut metadata) = candidate.metadata {
+                    metadata.identity_parent = metadata.identity_parent.as_deref().map(prefixed);
+                }
+            }
+            for nominator in &mut other.nominators {
+                nominator.account_id = prefixed(&nominator.account_id);
+                for target in &mut nominator.targets {
+                    *target = prefixed(target);
+                }
+            }
+            for account_id in &mut other.invulnerables {
+                *account_id = prefixed(account_id);
+            }
+        }
+
+        let candidate_index: HashMap<String, usize> = self
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| (candidate.account_id.clone(), index))
+            .collect();
+        let nominator_index: HashMap<String, usize> = self
+            .nominators
+            .iter()
+            .enumerate()
+            .map(|(index, nominator)| (nominator.account_id.clone(), index))
+            .collect();
+
+        // Check before changing anything so a rejected merge leaves `self` intact
+        if matches!(policy, MergePolicy::Reject | MergePolicy::Prefix(_)) {
+            if let Some(candidate) = other.candidates.iter().find(|c| candidate_index.contains_key(&c.account_id)) {
+                return Err(Self::collision_error("candidate", &candidate.account_id, "candidates"));
+            }
+            if let Some(nominator) = other.nominators.iter().find(|n| nominator_index.contains_key(&n.account_id)) {
+                return Err(Self::collision_error("nominator", &nominator.account_id, "nominators"));
+            }
+        }
+
+        let mut collisions = 0;
+        for candidate in other.candidates {
+            let Some(&index) = candidate_index.get(&candidate.account_id) else {
+                self.candidates.push(candidate);
+                continue;
+            };
+            collisions += 1;
+            let existing = &mut self.candidates[index];
+            match policy {
+                MergePolicy::KeepExisting | MergePolicy::Reject | MergePolicy::Prefix(_) => {}
+                MergePolicy::Replace => *existing = candidate,
+                MergePolicy::Sum => {
+                    existing.stake = existing.stake.saturating_add(candidate.stake);
+                    if existing.metadata.is_none() {
+                        existing.metadata = candidate.metadata;
+                    }
+                }
+            }
+        }
+
+        for nominator in other.nominators {
+            let Some(&index) = nominator_index.get(&nominator.account_id) else {
+                self.nominators.push(nominator);
+                continue;
+            };
+            collisions += 1;
+            let existing = &mut self.nominators[index];
+            match policy {
+                MergePolicy::KeepExisting | MergePolicy::Reject | MergePolicy::Prefix(_) => {}
+                MergePolicy::Replace => *existing = nominator,
+                MergePolicy::Sum => {
+                    existing.stake = existing.stake.saturating_add(nominator.stake);
+                    for target in nominator.targets {
+                        existing.add_target(target);
+                    }
+                    if existing.metadata.is_none() {
+                        existing.metadata = nominator.metadata;
+                    }
+                }
+            }
+        }
+
+        for account_id in other.invulnerables {
+            if !self.invulnerables.contains(&account_id) {
+                self.invulnerables.push(account_id);
+            }
+        }
+
+        Ok(collisions)
+    }
+
+    fn collision_error(kind: &str, account_id: &str, field: &str) -> ElectionError {
+        ElectionError::ValidationError {
+            message: format!("Account ID {} is a {} in both data sets being merged", account_id, kind),
+            field: Some(field.to_string()),
+        }
+    }
+
+    /// SHA-256 of the data as hex, identical for equal data
+    ///
+    /// The data is streamed into the hash rather than serialized in memory, so
+    /// hashing a large snapshot needs no extra copy. Candidates, nominators
+    /// and invulnerables are hashed in account order, since loaders fill
+    /// them in storage iteration order, and nominator metadata with sorted
+    /// keys. Metadata about the fetch itself (`rpc_retries`,
+    /// `load_failures`) is left out, so two fetches of the same block hash
+    /// the same.
+    pub fn content_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        struct HashWriter(Sha256);
+        impl std::io::Write for HashWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.update(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut candidates: Vec<&ValidatorCandidate> = self.candidates.iter().collect();
+        candidates.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+        let mut nominators: Vec<&Nominator> = self.nominators.iter().collect();
+        nominators.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+        let mut invulnerables: Vec<&String> = self.invulnerables.iter().collect();
+        invulnerables.sort();
+        let metadata = self.metadata.as_ref().map(|metadata| ElectionMetadata {
+            rpc_retries: None,
+            load_failures: Vec::new(),
+            ..metadata.clone()
+        });
+
+        let mut writer = HashWriter(Sha256::new());
+        // Writing to a hasher cannot fail, and every field serializes
+        let _ = serde_json::to_writer(&mut writer, &(&candidates, &invulnerables, &metadata));
+        for nominator in nominators {
+            let metadata = nominator.metadata.as_ref().and_then(|m| serde_json::to_value(m).ok());
+            let _ = serde_json::to_writer(
+                &mut writer,
+                &(&nominator.account_id, nominator.stake, &nominator.targets, metadata),
+            );
+        }
+        hex::encode(writer.0.finalize())
+    }
+
+    /// Token unit of the chain named in the metadata, if it is a known chain
+    pub fn token_unit(&self) -> Option<TokenUnit> {
+        self.metadata.as_ref()?.chain.as_deref().and_then(TokenUnit::for_chain)
+    }
+
+    /// `plancks` in the data's token, if its chain is known
+    pub fn balance(&self, plancks: u128) -> Option<Balance> {
+        self.token_unit().map(|unit| Balance::from_plancks(plancks, unit))
+    }
+
+    /// SS58 address prefix of the chain named in the metadata
+    ///
+    /// Polkadot uses 0 and Kusama 2; Westend and unknown chains use the
+    /// generic Substrate prefix 42.
+    pub fn ss58_prefix(&self) -> u16 {
+        let chain = self.metadata.as_ref().and_then(|m| m.chain.as_deref()).unwrap_or_default();
+        crate::models::account_format::chain_prefix(chain)
+    }
+
+    /// SS58 prefixes of the candidate and nominator account IDs, with the
+    /// number of accounts using each
+    ///
+    /// IDs that are not SS58 addresses, such as hex keys, are not counted.
+    /// More than one prefix means the data mixes networks' encodings.
+    pub fn address_prefixes(&self) -> BTreeMap<u16, usize> {
+        let mut prefixes = BTreeMap::new();
+        let accounts = self
+            .candidates
+            .iter()
+            .map(|c| c.account_id.as_str())
+            .chain(self.nominators.iter().map(|n| n.account_id.as_str()));
+        for prefix in accounts.filter_map(crate::models::account_format::ss58_prefix) {
+            *prefixes.entry(prefix).or_insert(0) += 1;
+        }
+        prefixes
+    }
+
+    /// Stash account for a stash or controller account ID
+    ///
+    /// Historical on-chain records may name an account by its controller;
+    /// this maps them to the stash used throughout the data. Returns `None`
+    /// if the account is neither a candidate nor a nominator.
+    pub fn stash_of(&self, account_id: &str) -> Option<&str> {
+        let candidates = self
+            .candidates
+            .iter()
+            .map(|c| (c.account_id.as_str(), c.controller()));
+        let nominators = self
+            .nominators
+            .iter()
+            .map(|n| (n.account_id.as_str(), n.controller()));
+        let accounts: Vec<(&str, Option<&str>)> = candidates.chain(nominators).collect();
+        accounts
+            .iter()
+            .find(|(stash, _)| *stash == account_id)
+            .or_else(|| accounts.iter().find(|(_, controller)| *controller == Some(account_id)))
+            .map(|(stash, _)| *stash)
     }
 
     /// Get reference to candidates
@@ -172,6 +884,82 @@ impl ElectionData {
     }
 }
 
+/// Positions of candidates and nominators by account ID
+///
+/// Records the lengths it was built for, so accounts added or removed
+/// through the public fields are noticed and lookups fall back to a scan.
+#[derive(Debug)]
+pub(crate) struct AccountIndex {
+    candidates: HashMap<String, usize>,
+    nominators: HashMap<String, usize>,
+    candidate_count: usize,
+    nominator_count: usize,
+}
+
+impl AccountIndex {
+    /// Index every account; the first of duplicate account IDs wins
+    fn build(data: &ElectionData) -> Self {
+        let mut index = Self {
+            candidates: HashMap::with_capacity(data.candidates.len()),
+            nominators: HashMap::with_capacity(data.nominators.len()),
+            candidate_count: 0,
+            nominator_count: 0,
+        };
+        for (position, candidate) in data.candidates.iter().enumerate() {
+            index.push_candidate(&candidate.account_id, position);
+        }
+        for (position, nominator) in data.nominators.iter().enumerate() {
+            index.push_nominator(&nominator.account_id, position);
+        }
+        index
+    }
+
+    /// Whether the data still has the lengths the index was built for
+    fn is_current(&self, candidate_count: usize, nominator_count: usize) -> bool {
+        self.candidate_count == candidate_count && self.nominator_count == nominator_count
+    }
+
+    /// Record a candidate appended at `position`, if the index is current
+    fn push_candidate(&mut self, account_id: &str, position: usize) {
+        if self.candidate_count == position {
+            self.candidates.entry(account_id.to_string()).or_insert(position);
+            self.candidate_count += 1;
+        }
+    }
+
+    /// Record a nominator appended at `position`, if the index is current
+    fn push_nominator(&mut self, account_id: &str, position: usize) {
+        if self.nominator_count == position {
+            self.nominators.entry(account_id.to_string()).or_insert(position);
+            self.nominator_count += 1;
+        }
+    }
+
+    /// Indexed position of a candidate, `None` if the index cannot answer
+    fn candidate(&self, data: &ElectionData, account_id: &str) -> Option<Option<usize>> {
+        if !self.is_current(data.candidates.len(), data.nominators.len()) {
+            return None;
+        }
+        match self.candidates.get(account_id) {
+            Some(&position) if data.candidates[position].account_id == account_id => Some(Some(position)),
+            Some(_) => None,
+            None => Some(None),
+        }
+    }
+
+    /// Indexed position of a nominator, `None` if the index cannot answer
+    fn nominator(&self, data: &ElectionData, account_id: &str) -> Option<Option<usize>> {
+        if !self.is_current(data.candidates.len(), data.nominators.len()) {
+            return None;
+        }
+        match self.nominators.get(account_id) {
+            Some(&position) if data.nominators[position].account_id == account_id => Some(Some(position)),
+            Some(_) => None,
+            None => Some(None),
+        }
+    }
+}
+
 impl Default for ElectionData {
     fn default() -> Self {
         Self::new()
@@ -1,6 +1,8 @@
 //! Election parameter overrides model
 
 use crate::error::ElectionError;
+use crate::models::balance::{parse_amount, TokenUnit};
+use crate::models::election_data::ElectionData;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -8,19 +10,48 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct ElectionOverrides {
     /// Override stake for specific candidates (account_id -> stake)
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", serialize_with = "sorted_map")]
     pub candidate_stakes: HashMap<String, u128>,
     /// Override stake for specific nominators (account_id -> stake)
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", serialize_with = "sorted_map")]
     pub nominator_stakes: HashMap<String, u128>,
+    /// Override the blocked flag for specific candidates (account_id -> blocked)
+    ///
+    /// Blocked candidates keep their existing nominations, but voting edges
+    /// added through [`EdgeAction::Add`] are dropped, mirroring how the chain
+    /// rejects new nominations to validators that block them.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", serialize_with = "sorted_map")]
+    pub candidate_blocked: HashMap<String, bool>,
     /// Voting edge modifications
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub voting_edges: Vec<EdgeModification>,
     /// Override active set size
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub active_set_size: Option<u32>,
 }
 
+/// Named set of overrides that can be layered on top of other overrides
+///
+/// Layers let scenario libraries compose reusable building blocks such as
+/// "whale-unbonds" followed by "slash-validator-x".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverrideLayer {
+    /// Layer name, recorded in the execution metadata when applied
+    pub name: String,
+    /// Overrides applied by this layer
+    pub overrides: ElectionOverrides,
+}
+
+impl OverrideLayer {
+    /// Create a new named override layer
+    pub fn new(name: impl Into<String>, overrides: ElectionOverrides) -> Self {
+        Self {
+            name: name.into(),
+            overrides,
+        }
+    }
+}
+
 /// Modification to a voting edge
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EdgeModification {
@@ -73,6 +104,16 @@ impl ElectionOverrides {
         Ok(())
     }
 
+    /// Set candidate blocked flag override
+    pub fn set_candidate_blocked(
+        &mut self,
+        account_id: String,
+        blocked: bool,
+    ) -> Result<(), ElectionError> {
+        self.candidate_blocked.insert(account_id, blocked);
+        Ok(())
+    }
+
     /// Add a voting edge
     pub fn add_voting_edge(
         &mut self,
@@ -118,6 +159,228 @@ impl ElectionOverrides {
         });
         Ok(())
     }
+
+    /// Parse overrides from JSON, accepting amounts like `"1.5 DOT"`
+    ///
+    /// Stakes and edge weights may be numbers (plancks) or strings parsed as
+    /// described in [`parse_amount`].
+    /// When `unit` is given, amounts in another token are rejected; without it,
+    /// known token symbols are accepted.
+    pub fn from_json_str(content: &str, unit: Option<&TokenUnit>) -> Result<Self, ElectionError> {
+        let parse_error = |e: &dyn std::fmt::Display| ElectionError::ValidationError {
+            message: format!("Failed to parse overrides JSON: {}", e),
+            field: None,
+        };
+
+        // Plain numbers parse directly, keeping amounts above u64::MAX exact
+        let first_attempt = match serde_json::from_str::<Self>(content) {
+            Ok(overrides) => return Ok(overrides),
+            Err(e) => e,
+        };
+        let mut value: serde_json::Value = serde_json::from_str(content).map_err(|e| parse_error(&e))?;
+        let Some(object) = value.as_object_mut() else {
+            return Err(parse_error(&first_attempt));
+        };
+
+        let mut take_stakes = |key: &str| -> Result<HashMap<String, u128>, ElectionError> {
+            let Some(entries) = object.insert(key.to_string(), serde_json::json!({})) else {
+                return Ok(HashMap::new());
+            };
+            let serde_json::Value::Object(entries) = entries else {
+                return Err(parse_error(&first_attempt));
+            };
+            entries
+                .into_iter()
+                .map(|(account_id, amount)| {
+                    let field = format!("{}.{}", key, account_id);
+                    Ok((account_id, json_amount(&amount, unit, &field)?))
+                })
+                .collect()
+        };
+        let candidate_stakes = take_stakes("candidate_stakes")?;
+        let nominator_stakes = take_stakes("nominator_stakes")?;
+
+        let mut weights = Vec::new();
+        if let Some(serde_json::Value::Array(edges)) = object.get_mut("voting_edges") {
+            for (index, edge) in edges.iter_mut().enumerate() {
+                let weight = match edge.get_mut("weight") {
+                    Some(weight) if !weight.is_null() => {
+                        let field = format!("voting_edges[{}].weight", index);
+                        Some(json_amount(&std::mem::take(weight), unit, &field)?)
+                    }
+                    _ => None,
+                };
+                weights.push(weight);
+            }
+        }
+
+        let mut overrides: Self = serde_json::from_value(value).map_err(|e| parse_error(&e))?;
+        overrides.candidate_stakes = candidate_stakes;
+        overrides.nominator_stakes = nominator_stakes;
+        for (edge, weight) in overrides.voting_edges.iter_mut().zip(weights) {
+            edge.weight = weight;
+        }
+        Ok(overrides)
+    }
+
+    /// Apply these overrides to election data
+    ///
+    /// Accounts are looked up through the data's account index, so applying
+    /// `k` overrides to `n` accounts takes `O(n + k)` rather than a scan per
+    /// override. Stake and blocked-flag overrides are applied in account ID
+    /// order, then the voting edges in list order. Overrides naming an
+    /// account that is not in the data are skipped, and reported in the
+    /// returned summary along with the number applied of each kind.
+    pub fn apply(&self, data: &mut ElectionData) -> OverrideApplication {
+        data.invalidate_statistics();
+        let mut application = OverrideApplication::default();
+
+        // Apply candidate stake and blocked flag overrides
+        for (account_id, stake) in sorted(&self.candidate_stakes) {
+            match data.candidate_position(account_id) {
+                Some(position) => {
+                    data.candidates[position].stake = *stake;
+                    application.candidate_stakes += 1;
+                }
+                None => application
+                    .unmatched
+                    .push(format!("Candidate stake override for unknown candidate {}", account_id)),
+            }
+        }
+        for (account_id, blocked) in sorted(&self.candidate_blocked) {
+            match data.candidate_position(account_id) {
+                Some(position) => {
+                    data.candidates[position].set_blocked(*blocked);
+                    application.candidate_blocked += 1;
+                }
+                None => application
+                    .unmatched
+                    .push(format!("Blocked flag override for unknown candidate {}", account_id)),
+            }
+        }
+
+        // Apply nominator stake overrides
+        for (account_id, stake) in sorted(&self.nominator_stakes) {
+            match data.nominator_position(account_id) {
+                Some(position) => {
+                    data.nominators[position].stake = *stake;
+                    application.nominator_stakes += 1;
+                }
+                None => application
+                    .unmatched
+                    .push(format!("Nominator stake override for unknown nominator {}", account_id)),
+            }
+        }
+
+        // Apply voting edge modifications
+        for edge_mod in &self.voting_edges {
+            // Blocked candidates reject new nominations, existing ones are kept
+            let blocked = data.get_candidate(&edge_mod.candidate_id).is_some_and(|c| c.is_blocked());
+            let Some(position) = data.nominator_position(&edge_mod.nominator_id) else {
+                application.unmatched.push(format!(
+                    "Voting edge override for unknown nominator {}",
+                    edge_mod.nominator_id
+                ));
+                continue;
+            };
+            let nominator = &mut data.nominators[position];
+            match edge_mod.action {
+                EdgeAction::Add => {
+                    if blocked && !nominator.targets.contains(&edge_mod.candidate_id) {
+                        application.rejected_edges.push(format!(
+                            "Candidate {} blocks new nominations; ignored new vote from {}",
+                            edge_mod.candidate_id, edge_mod.nominator_id
+                        ));
+                        continue;
+                    }
+                    nominator.add_target(edge_mod.candidate_id.clone());
+                }
+                EdgeAction::Remove => {
+                    nominator.remove_target(&edge_mod.candidate_id);
+                }
+                EdgeAction::Modify => {
+                    // Modify is similar to remove + add
+                    nominator.remove_target(&edge_mod.candidate_id);
+                    nominator.add_target(edge_mod.candidate_id.clone());
+                }
+            }
+            application.voting_edges += 1;
+        }
+
+        application
+    }
+}
+
+/// Outcome of applying [`ElectionOverrides`] to election data
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverrideApplication {
+    /// Candidate stake overrides applied
+    pub candidate_stakes: usize,
+    /// Nominator stake overrides applied
+    pub nominator_stakes: usize,
+    /// Blocked flag overrides applied
+    pub candidate_blocked: usize,
+    /// Voting edge modifications applied
+    pub voting_edges: usize,
+    /// New votes dropped because the candidate blocks new nominations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejected_edges: Vec<String>,
+    /// Overrides naming an account that is not in the data
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmatched: Vec<String>,
+}
+
+impl OverrideApplication {
+    /// Overrides applied, across all kinds
+    pub fn applied(&self) -> usize {
+        self.candidate_stakes + self.nominator_stakes + self.candidate_blocked + self.voting_edges
+    }
+
+    /// Whether every override named an account in the data and took effect
+    pub fn is_complete(&self) -> bool {
+        self.rejected_edges.is_empty() && self.unmatched.is_empty()
+    }
 }
 
+/// Entries of a map in key order, so unmatched overrides are reported stably
+fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> Vec<(&K, &V)> {
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Serialize a map with its keys in order, so equal maps serialize identically
+pub(crate) fn sorted_map<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+    S: serde::Serializer,
+{
+    serializer.collect_map(map.iter().collect::<std::collections::BTreeMap<_, _>>())
+}
 
+/// Convert a JSON number or amount string to plancks
+pub(crate) fn json_amount(value: &serde_json::Value, unit: Option<&TokenUnit>, field: &str) -> Result<u128, ElectionError> {
+    let amount = match value {
+        serde_json::Value::String(text) => parse_amount(text, unit),
+        serde_json::Value::Number(number) => number.as_u64().map(u128::from).ok_or_else(|| ElectionError::ValidationError {
+            message: format!(
+                "Invalid amount {}: expected whole plancks; write amounts above {} as strings",
+                number,
+                u64::MAX
+            ),
+            field: None,
+        }),
+        other => Err(ElectionError::ValidationError {
+            message: format!("Invalid amount {}: expected a number or a string like \"1.5 DOT\"", other),
+            field: None,
+        }),
+    };
+    amount.map_err(|e| match e {
+        ElectionError::ValidationError { message, .. } => ElectionError::ValidationError {
+            message,
+            field: Some(field.to_string()),
+        },
+        other => other,
+    })
+}
@@ -1,6 +1,13 @@
 //! Election result model
 
 use crate::diagnostics::models::Diagnostics;
+use crate::models::account_format::AccountFormat;
+use crate::models::candidate_rules::RuleApplication;
+use crate::models::nominator::Nominator;
+use crate::models::raw_solution::RawSolution;
+use crate::models::result_index::{IndexedElectionResult, Page};
+use crate::models::rounding::RoundingPolicy;
+use crate::models::validation::DataIssue;
 use crate::types::AlgorithmType;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +27,50 @@ pub struct ElectionResult {
     /// Optional diagnostics explaining the results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostics: Option<Diagnostics>,
+    /// Recoverable problems the run continued past; only filled in partial-result
+    /// mode, see [`ElectionConfiguration::partial_results`](crate::models::election_config::ElectionConfiguration::partial_results)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<ResultIssue>,
+    /// The solver's solution before conversion; only filled in with
+    /// [`ResultDetail::Full`](crate::models::raw_solution::ResultDetail::Full)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_solution: Option<RawSolution>,
+}
+
+/// A recoverable problem that left the result incomplete or less informative
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultIssue {
+    /// What went wrong
+    pub kind: ResultIssueKind,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Kind of [`ResultIssue`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResultIssueKind {
+    /// Some storage entries failed to load; the data is missing them
+    LoadFailure,
+    /// Diagnostics were requested but could not be generated
+    DiagnosticsFailed,
+    /// An override names an account that is not in the data
+    UnmatchedOverride,
+    /// An override vote was dropped because the candidate blocks new nominations
+    RejectedOverride,
+    /// An invulnerable is not a candidate and did not take a seat
+    UnknownInvulnerable,
+    /// A force-included account is not a candidate and was not forced in
+    UnknownForcedCandidate,
+    /// An invulnerable or force-included candidate did not end up elected
+    ForcedCandidateNotElected,
+}
+
+impl ResultIssue {
+    /// Create an issue
+    pub fn new(kind: ResultIssueKind, message: impl Into<String>) -> Self {
+        Self { kind, message: message.into() }
+    }
 }
 
 /// Validator that was selected in the election
@@ -47,10 +98,14 @@ pub struct StakeAllocation {
     pub amount: u128,
     /// Proportion of nominator's total stake (0.0 to 1.0)
     pub proportion: f64,
+    /// Whether the allocation falls outside the validator's rewarded
+    /// exposure, see [`ElectionResult::mark_unrewarded`]
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub unrewarded: bool,
 }
 
 /// Execution metadata
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutionMetadata {
     /// Block number if data came from RPC
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -61,6 +116,352 @@ pub struct ExecutionMetadata {
     /// Data source identifier
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data_source: Option<String>,
+    /// Names of the override layers applied, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub applied_override_layers: Vec<String>,
+    /// Candidates affected by each candidate rule, in rule order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rule_applications: Vec<RuleApplication>,
+    /// Data issues the validation profile tolerated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data_issues: Vec<DataIssue>,
+    /// Time spent in the election algorithm, in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm_duration_micros: Option<u64>,
+    /// Voters, candidates and edges in the data as given, before sanitizing,
+    /// overrides and rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_counts: Option<ElectionCounts>,
+    /// Voters, candidates and edges the algorithm received; voters without a
+    /// vote for a known candidate are trimmed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solver_counts: Option<ElectionCounts>,
+    /// Runtime behavior flags the election ran under, see
+    /// [`RuntimeBehavior`](crate::models::runtime_behavior::RuntimeBehavior)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub behavior_flags: Vec<String>,
+    /// Balancing iterations performed on the solution (0 unless the
+    /// configuration asks for balancing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balancing_iterations: Option<u32>,
+    /// Largest amount a single voter could still shift between its elected
+    /// targets when balancing stopped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balancing_residual: Option<u128>,
+    /// Whether balancing stopped because it converged within the tolerance
+    /// rather than because it hit the iteration cap
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balancing_converged: Option<bool>,
+    /// Version of this crate that produced the result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crate_version: Option<String>,
+    /// SHA-256 of the configuration, see [`ElectionConfiguration::content_hash`](crate::models::election_config::ElectionConfiguration::content_hash)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_hash: Option<String>,
+    /// SHA-256 of the input data, see [`ElectionData::content_hash`](crate::models::election_data::ElectionData::content_hash)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_hash: Option<String>,
+    /// Whether the result was answered from a [`ResultCache`](crate::cache::ResultCache)
+    /// instead of running the election; the timings are those of the original run
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub from_cache: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Size of an election's input
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionCounts {
+    /// Number of voters (nominators)
+    pub voters: usize,
+    /// Number of candidates
+    pub candidates: usize,
+    /// Number of votes
+    pub edges: usize,
+}
+
+impl ElectionCounts {
+    /// Count every nominator, candidate and vote in `data`
+    pub fn of(data: &crate::models::election_data::ElectionData) -> Self {
+        Self {
+            voters: data.nominators.len(),
+            candidates: data.candidates.len(),
+            edges: data.nominators.iter().map(|n| n.targets.len()).sum(),
+        }
+    }
+}
+
+/// How a single validator's backing is made up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackingBreakdown {
+    /// Account ID of the validator
+    pub validator_id: String,
+    /// Total stake allocated to the validator
+    pub total_backing: u128,
+    /// Contributions from each backer, largest first
+    pub contributions: Vec<BackerContribution>,
+    /// Number of backers, including the validator's self-vote
+    pub backer_count: usize,
+    /// Share of the backing from the largest backer, in basis points
+    pub largest_backer_share_bps: u32,
+    /// Stake the validator allocated to itself through its self-vote, or its
+    /// bonded stake once [`BackingBreakdown::with_bonded_self_stake`] adds it
+    pub self_stake: u128,
+    /// Share of the backing that is self-stake, in basis points
+    pub self_stake_share_bps: u32,
+}
+
+/// Stake a single backer allocated to a validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackerContribution {
+    /// Account ID of the backer
+    pub nominator_id: String,
+    /// Amount allocated
+    pub amount: u128,
+    /// Share of the validator's backing, in basis points
+    pub share_bps: u32,
+}
+
+impl BackingBreakdown {
+    /// Build a breakdown from the allocations made to `validator_id`
+    ///
+    /// Allocations for other validators are ignored. A validator's self-stake
+    /// is the allocation whose backer is the validator itself.
+    pub fn from_allocations<'a>(
+        validator_id: &str,
+        allocations: impl IntoIterator<Item = &'a StakeAllocation>,
+    ) -> Self {
+        let mut amounts: std::collections::HashMap<&str, u128> = std::collections::HashMap::new();
+        for allocation in allocations {
+            if allocation.validator_id == validator_id {
+                let amount = amounts.entry(allocation.nominator_id.as_str()).or_insert(0);
+                *amount = amount.saturating_add(allocation.amount);
+            }
+        }
+
+        let total_backing = amounts.values().fold(0u128, |acc, a| acc.saturating_add(*a));
+        let share = |amount: u128| {
+            amount
+                .saturating_mul(10_000)
+                .checked_div(total_backing)
+                .unwrap_or(0) as u32
+        };
+
+        let mut contributions: Vec<BackerContribution> = amounts
+            .into_iter()
+            .map(|(nominator_id, amount)| BackerContribution {
+                nominator_id: nominator_id.to_string(),
+                amount,
+                share_bps: share(amount),
+            })
+            .collect();
+        contributions.sort_by(|a, b| {
+            b.amount
+                .cmp(&a.amount)
+                .then_with(|| a.nominator_id.cmp(&b.nominator_id))
+        });
+
+        let self_stake = contributions
+            .iter()
+            .find(|c| c.nominator_id == validator_id)
+            .map(|c| c.amount)
+            .unwrap_or(0);
+
+        Self {
+            validator_id: validator_id.to_string(),
+            total_backing,
+            backer_count: contributions.len(),
+            largest_backer_share_bps: contributions.first().map(|c| c.share_bps).unwrap_or(0),
+            self_stake,
+            self_stake_share_bps: share(self_stake),
+            contributions,
+        }
+    }
+
+    /// Count the validator's bonded stake as its self-stake
+    ///
+    /// Snapshots read from a node carry the self-vote in the voter list, and
+    /// its allocation is kept. Other sources only record the bond on the
+    /// candidate; without a self-vote allocation, `bonded` is added to the
+    /// contributions and the total backing, as the chain's exposure would.
+    pub fn with_bonded_self_stake(mut self, bonded: u128) -> Self {
+        if self.self_stake > 0 || bonded == 0 {
+            return self;
+        }
+        self.total_backing = self.total_backing.saturating_add(bonded);
+        self.contributions.push(BackerContribution {
+            nominator_id: self.validator_id.clone(),
+            amount: bonded,
+            share_bps: 0,
+        });
+        let total_backing = self.total_backing;
+        let share = |amount: u128| amount.saturating_mul(10_000).checked_div(total_backing).unwrap_or(0) as u32;
+        for contribution in &mut self.contributions {
+            contribution.share_bps = share(contribution.amount);
+        }
+        self.contributions.sort_by(|a, b| {
+            b.amount
+                .cmp(&a.amount)
+                .then_with(|| a.nominator_id.cmp(&b.nominator_id))
+        });
+        self.backer_count = self.contributions.len();
+        self.largest_backer_share_bps = self.contributions.first().map(|c| c.share_bps).unwrap_or(0);
+        self.self_stake = bonded;
+        self.self_stake_share_bps = share(bonded);
+        self
+    }
+}
+
+/// Where a single nominator's stake ended up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NominatorAllocations {
+    /// Account ID of the nominator
+    pub nominator_id: String,
+    /// The nominator's total stake
+    pub stake: u128,
+    /// Stake allocated across elected targets
+    pub allocated: u128,
+    /// Stake left unallocated, e.g. because no target was elected
+    pub unallocated: u128,
+    /// Each target in the nominator's vote order, followed by any other
+    /// validator that received stake from the nominator
+    pub targets: Vec<TargetAllocation>,
+}
+
+/// Stake a nominator allocated to one of its targets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetAllocation {
+    /// Account ID of the target
+    pub validator_id: String,
+    /// Amount allocated (zero for unelected targets)
+    pub amount: u128,
+    /// Whether the target was elected
+    pub elected: bool,
+}
+
+impl NominatorAllocations {
+    /// Build from a nominator's allocations and an election status lookup
+    ///
+    /// Allocations made by other nominators are ignored.
+    pub fn from_allocations<'a>(
+        nominator: &Nominator,
+        allocations: impl IntoIterator<Item = &'a StakeAllocation>,
+        is_elected: impl Fn(&str) -> bool,
+    ) -> Self {
+        let mut targets: Vec<TargetAllocation> = nominator
+            .targets
+            .iter()
+            .map(|target| TargetAllocation {
+                validator_id: target.clone(),
+                amount: 0,
+                elected: is_elected(target),
+            })
+            .collect();
+
+        for allocation in allocations {
+            if allocation.nominator_id != nominator.account_id {
+                continue;
+            }
+            match targets.iter_mut().find(|t| t.validator_id == allocation.validator_id) {
+                Some(target) => target.amount = target.amount.saturating_add(allocation.amount),
+                None => targets.push(TargetAllocation {
+                    validator_id: allocation.validator_id.clone(),
+                    amount: allocation.amount,
+                    elected: is_elected(&allocation.validator_id),
+                }),
+            }
+        }
+
+        let allocated = targets.iter().fold(0u128, |acc, t| acc.saturating_add(t.amount));
+        Self {
+            nominator_id: nominator.account_id.clone(),
+            stake: nominator.stake,
+            allocated,
+            unallocated: nominator.stake.saturating_sub(allocated),
+            targets,
+        }
+    }
+}
+
+/// Election score as used by `sp-npos-elections` to compare solutions
+///
+/// Scores are compared lexicographically: a higher minimal stake is better, then a higher
+/// total stake, then a lower sum of squared stakes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ElectionScore {
+    /// Smallest total backing among selected validators
+    pub minimal_stake: u128,
+    /// Sum of total backing across selected validators
+    pub sum_stake: u128,
+    /// Sum of squared total backing across selected validators (saturating)
+    pub sum_stake_squared: u128,
+}
+
+impl ElectionScore {
+    /// Compute the score of a set of winners from their total backing
+    pub fn from_backings(backings: impl IntoIterator<Item = u128>) -> Self {
+        let mut score = ElectionScore::default();
+        let mut minimal_stake: Option<u128> = None;
+        for backing in backings {
+            minimal_stake = Some(minimal_stake.map_or(backing, |m| m.min(backing)));
+            score.sum_stake = score.sum_stake.saturating_add(backing);
+            score.sum_stake_squared = score
+                .sum_stake_squared
+                .saturating_add(backing.saturating_mul(backing));
+        }
+        score.minimal_stake = minimal_stake.unwrap_or(0);
+        score
+    }
+
+    /// Whether this score is strictly better than `other`
+    ///
+    /// Compares lexicographically: higher minimal stake, then higher total stake,
+    /// then lower sum of squared stakes.
+    pub fn is_better_than(&self, other: &ElectionScore) -> bool {
+        (self.minimal_stake, self.sum_stake, std::cmp::Reverse(self.sum_stake_squared))
+            > (other.minimal_stake, other.sum_stake, std::cmp::Reverse(other.sum_stake_squared))
+    }
+
+    /// How far this score is from `other`, field by field
+    pub fn margin_over(&self, other: &ElectionScore) -> ScoreMargin {
+        ScoreMargin {
+            minimal_stake: signed_difference(self.minimal_stake, other.minimal_stake),
+            sum_stake: signed_difference(self.sum_stake, other.sum_stake),
+            sum_stake_squared: signed_difference(self.sum_stake_squared, other.sum_stake_squared),
+            minimal_stake_bps: signed_difference(self.minimal_stake, other.minimal_stake)
+                .saturating_mul(10_000)
+                .checked_div(other.minimal_stake.min(i128::MAX as u128) as i128)
+                .map(|bps| bps.clamp(i32::MIN as i128, i32::MAX as i128) as i32),
+        }
+    }
+}
+
+/// Field-by-field difference between two scores
+///
+/// Differences beyond the range of `i128` saturate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreMargin {
+    /// Difference in minimal stake
+    pub minimal_stake: i128,
+    /// Difference in total stake
+    pub sum_stake: i128,
+    /// Difference in the sum of squared stakes; negative is better
+    pub sum_stake_squared: i128,
+    /// Minimal stake difference relative to the other score's, in basis
+    /// points; `None` when the other minimal stake is zero
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimal_stake_bps: Option<i32>,
+}
+
+/// `a - b` as a signed value, saturating beyond the range of `i128`
+pub(crate) fn signed_difference(a: u128, b: u128) -> i128 {
+    if a >= b {
+        i128::try_from(a - b).unwrap_or(i128::MAX)
+    } else {
+        i128::try_from(b - a).map_or(i128::MIN, |d| -d)
+    }
 }
 
 impl ElectionResult {
@@ -76,12 +477,10 @@ impl ElectionResult {
             stake_distribution,
             total_stake,
             algorithm_used,
-            execution_metadata: ExecutionMetadata {
-                block_number: None,
-                execution_timestamp: None,
-                data_source: None,
-            },
+            execution_metadata: ExecutionMetadata::default(),
             diagnostics: None,
+            issues: Vec::new(),
+            raw_solution: None,
         }
     }
 
@@ -128,6 +527,39 @@ impl ElectionResult {
             .find(|v| v.rank == Some(rank))
     }
 
+    /// Get a page of selected validators in active set order (zero-based page)
+    pub fn winners_page(&self, page: usize, page_size: usize) -> Page<&SelectedValidator> {
+        Page::from_slice(&self.selected_validators, page, page_size)
+    }
+
+    /// Re-encode the account IDs of the winners and stake allocations
+    ///
+    /// `network_prefix` is the SS58 prefix used by [`AccountFormat::Network`].
+    /// Diagnostics keep the IDs the election ran on.
+    pub fn reencode_accounts(&mut self, format: AccountFormat, network_prefix: u16) {
+        if format.is_as_loaded() {
+            return;
+        }
+        for validator in &mut self.selected_validators {
+            validator.account_id = format.encode(&validator.account_id, network_prefix);
+        }
+        for allocation in &mut self.stake_distribution {
+            allocation.nominator_id = format.encode(&allocation.nominator_id, network_prefix);
+            allocation.validator_id = format.encode(&allocation.validator_id, network_prefix);
+        }
+        if let Some(raw) = self.raw_solution.as_mut() {
+            raw.map_accounts(|id| format.encode(id, network_prefix));
+        }
+    }
+
+    /// Index winners and stake allocations for repeated lookups
+    ///
+    /// The per-validator and per-nominator accessors below scan the whole
+    /// stake distribution on every call; prefer the index when querying many.
+    pub fn indexed(self) -> IndexedElectionResult {
+        IndexedElectionResult::new(self)
+    }
+
     /// Get all stake allocations for a specific validator
     pub fn allocations_for_validator(&self, validator_id: &str) -> Vec<&StakeAllocation> {
         self.stake_distribution
@@ -144,6 +576,77 @@ impl ElectionResult {
             .collect()
     }
 
+    /// SHA-256 of the serialized result as hex, identical for the same election on any platform
+    ///
+    /// Leaves out what describes a particular run rather than the election:
+    /// the execution timestamp, the solver duration, the crate version and
+    /// whether the result came from the cache.
+    pub fn content_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let mut canonical = self.clone();
+        let metadata = &mut canonical.execution_metadata;
+        metadata.execution_timestamp = None;
+        metadata.algorithm_duration_micros = None;
+        metadata.crate_version = None;
+        metadata.from_cache = false;
+        // Every field serializes; floats are written in their shortest exact form
+        hex::encode(Sha256::digest(serde_json::to_vec(&canonical).unwrap_or_default()))
+    }
+
+    /// Round each allocation's proportion with `policy`
+    ///
+    /// Amounts stay exact; only the derived ratios change.
+    pub fn apply_rounding(&mut self, policy: &RoundingPolicy) {
+        if policy.is_exact() {
+            return;
+        }
+        for allocation in &mut self.stake_distribution {
+            allocation.proportion = policy.apply(allocation.proportion);
+        }
+    }
+
+    /// Mark allocations beyond each validator's `max_rewarded` largest as unrewarded
+    ///
+    /// Before paged exposures, `pallet-staking` paid only the top
+    /// `MaxNominatorRewardedPerValidator` nominators of an oversubscribed
+    /// validator. Nominators are ranked by the stake they allocated, largest
+    /// first with ties broken by account, as the payout simulator does; a
+    /// validator's self-vote is its own stake and always rewarded.
+    pub fn mark_unrewarded(&mut self, max_rewarded: u32) {
+        let mut by_validator: std::collections::HashMap<&str, Vec<usize>> = std::collections::HashMap::new();
+        for (index, allocation) in self.stake_distribution.iter().enumerate() {
+            if allocation.amount > 0 && allocation.nominator_id != allocation.validator_id {
+                by_validator.entry(allocation.validator_id.as_str()).or_default().push(index);
+            }
+        }
+
+        let mut unrewarded = Vec::new();
+        for mut exposure in by_validator.into_values() {
+            if exposure.len() <= max_rewarded as usize {
+                continue;
+            }
+            let allocations = &self.stake_distribution;
+            exposure.sort_by(|a, b| {
+                allocations[*b]
+                    .amount
+                    .cmp(&allocations[*a].amount)
+                    .then_with(|| allocations[*a].nominator_id.cmp(&allocations[*b].nominator_id))
+            });
+            unrewarded.extend_from_slice(&exposure[max_rewarded as usize..]);
+        }
+        for index in unrewarded {
+            self.stake_distribution[index].unrewarded = true;
+        }
+    }
+
+    /// Stake allocated outside validators' rewarded exposures
+    pub fn unrewarded_stake(&self) -> u128 {
+        self.stake_distribution
+            .iter()
+            .filter(|alloc| alloc.unrewarded)
+            .fold(0u128, |acc, alloc| acc.saturating_add(alloc.amount))
+    }
+
     /// Get the total stake allocated to a specific validator
     pub fn total_stake_for_validator(&self, validator_id: &str) -> u128 {
         self.allocations_for_validator(validator_id)
@@ -152,10 +655,43 @@ impl ElectionResult {
             .sum()
     }
 
+    /// Break down the backing of a validator by backer
+    ///
+    /// Validators without allocations (including ones that were not elected)
+    /// yield an empty breakdown. Scans the stake distribution; use
+    /// [`IndexedElectionResult::backing_of`] for repeated queries.
+    pub fn backing_of(&self, validator_id: &str) -> BackingBreakdown {
+        BackingBreakdown::from_allocations(validator_id, &self.stake_distribution)
+    }
+
+    /// Show where a nominator's stake went
+    ///
+    /// The result does not record voter stakes or targets, so the nominator
+    /// comes from the election input. Scans the stake distribution; use
+    /// [`IndexedElectionResult::allocations_of`] for repeated queries.
+    pub fn allocations_of(&self, nominator: &Nominator) -> NominatorAllocations {
+        NominatorAllocations::from_allocations(nominator, &self.stake_distribution, |id| {
+            self.selected_validators.iter().any(|v| v.account_id == id)
+        })
+    }
+
+    /// Compute the election score of the selected validators
+    pub fn score(&self) -> ElectionScore {
+        ElectionScore::from_backings(self.selected_validators.iter().map(|v| v.total_backing_stake))
+    }
+
+    /// Whether this result was produced from `data`, judged by the data hash
+    /// recorded at run time; `None` for results that predate the hash
+    pub fn produced_from(&self, data: &crate::models::election_data::ElectionData) -> Option<bool> {
+        let recorded = self.execution_metadata.data_hash.as_deref()?;
+        Some(recorded == data.content_hash())
+    }
+
     /// Convert result to JSON string
     pub fn to_json(&self) -> Result<String, crate::error::ElectionError> {
         serde_json::to_string_pretty(self).map_err(|e| crate::error::ElectionError::InvalidData {
             message: format!("Failed to serialize result to JSON: {}", e),
+            source: Some(crate::error::ErrorSource::new(e)),
         })
     }
 }
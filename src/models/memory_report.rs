@@ -0,0 +1,168 @@
+//! Estimated heap usage of election data
+//!
+//! A chain snapshot holds a few million target strings, and CI runners often
+//! have a few GB of memory. [`ElectionData::memory_report`] estimates what a
+//! loaded data set occupies, by component, so runs can be planned before the
+//! election itself allocates its working set.
+
+use crate::models::election_data::ElectionData;
+use crate::models::nominator::NominatorMetadata;
+use crate::models::validator::CandidateMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::mem::size_of;
+
+/// Estimated heap bytes used by an [`ElectionData`], by component
+///
+/// Counts allocated capacity, not length, so vectors grown while loading are
+/// reported at their real size. Allocator overhead and the cached statistics
+/// and account index are not included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryReport {
+    /// Candidate and nominator records, without the strings they own
+    pub records: usize,
+    /// Candidate and nominator account ID strings
+    pub account_ids: usize,
+    /// Nominator target lists and their strings
+    pub targets: usize,
+    /// Candidate and nominator metadata, invulnerables and snapshot metadata
+    pub metadata: usize,
+    /// Bytes allocated but unused, included in the components above;
+    /// [`ElectionData::shrink_to_fit`] releases them
+    pub spare_capacity: usize,
+    /// Bytes of target strings repeating an account ID already held elsewhere
+    ///
+    /// Every target is a separate `String`, so a candidate named by many
+    /// nominators is stored once per vote. This is what sharing the strings
+    /// would save; it is included in `targets`.
+    pub repeated_targets: usize,
+}
+
+impl MemoryReport {
+    /// Estimate the heap usage of `data`
+    pub fn compute(data: &ElectionData) -> Self {
+        let mut report = Self {
+            records: vec_bytes(&data.candidates) + vec_bytes(&data.nominators),
+            spare_capacity: spare_bytes(&data.candidates) + spare_bytes(&data.nominators),
+            ..Self::default()
+        };
+        let mut seen: HashSet<&str> = HashSet::with_capacity(data.candidates.len());
+
+        for candidate in &data.candidates {
+            report.add_string(Component::AccountIds, &candidate.account_id);
+            seen.insert(&candidate.account_id);
+            if let Some(ref metadata) = candidate.metadata {
+                report.add_candidate_metadata(metadata);
+            }
+        }
+        for nominator in &data.nominators {
+            report.add_string(Component::AccountIds, &nominator.account_id);
+            report.targets += vec_bytes(&nominator.targets);
+            report.spare_capacity += spare_bytes(&nominator.targets);
+            for target in &nominator.targets {
+                report.add_string(Component::Targets, target);
+                if !seen.insert(target) {
+                    report.repeated_targets += target.capacity();
+                }
+            }
+            if let Some(ref metadata) = nominator.metadata {
+                report.add_nominator_metadata(metadata);
+            }
+        }
+
+        report.metadata += vec_bytes(&data.invulnerables);
+        report.spare_capacity += spare_bytes(&data.invulnerables);
+        for account_id in &data.invulnerables {
+            report.add_string(Component::Metadata, account_id);
+        }
+        if let Some(ref metadata) = data.metadata {
+            report.add_optional(&metadata.chain);
+            report.metadata += vec_bytes(&metadata.load_failures);
+            report.spare_capacity += spare_bytes(&metadata.load_failures);
+            for failure in &metadata.load_failures {
+                report.add_string(Component::Metadata, failure);
+            }
+        }
+        report
+    }
+
+    /// Estimated heap bytes across all components
+    pub fn total(&self) -> usize {
+        self.records + self.account_ids + self.targets + self.metadata
+    }
+
+    fn add_string(&mut self, component: Component, value: &String) {
+        let bytes = match component {
+            Component::AccountIds => &mut self.account_ids,
+            Component::Targets => &mut self.targets,
+            Component::Metadata => &mut self.metadata,
+        };
+        *bytes += value.capacity();
+        self.spare_capacity += value.capacity() - value.len();
+    }
+
+    fn add_optional(&mut self, value: &Option<String>) {
+        if let Some(value) = value {
+            self.add_string(Component::Metadata, value);
+        }
+    }
+
+    fn add_candidate_metadata(&mut self, metadata: &CandidateMetadata) {
+        self.metadata += size_of::<CandidateMetadata>();
+        for value in [
+            &metadata.on_chain_status,
+            &metadata.identity_display,
+            &metadata.identity_parent,
+            &metadata.country,
+            &metadata.city,
+            &metadata.hosting_provider,
+            &metadata.controller,
+        ] {
+            self.add_optional(value);
+        }
+        if let Some(ref activity) = metadata.activity {
+            self.metadata += vec_bytes(&activity.eras);
+            self.spare_capacity += spare_bytes(&activity.eras);
+        }
+    }
+
+    fn add_nominator_metadata(&mut self, metadata: &NominatorMetadata) {
+        self.metadata += size_of::<NominatorMetadata>();
+        self.add_optional(&metadata.controller);
+        self.metadata += metadata.extra.capacity() * size_of::<(String, serde_json::Value)>();
+        for (key, value) in &metadata.extra {
+            self.metadata += key.capacity() + json_bytes(value);
+        }
+    }
+}
+
+/// Component a string is counted under
+#[derive(Clone, Copy)]
+enum Component {
+    AccountIds,
+    Targets,
+    Metadata,
+}
+
+fn vec_bytes<T>(values: &Vec<T>) -> usize {
+    values.capacity() * size_of::<T>()
+}
+
+fn spare_bytes<T>(values: &Vec<T>) -> usize {
+    (values.capacity() - values.len()) * size_of::<T>()
+}
+
+/// Heap bytes held by a JSON value, excluding the value itself
+fn json_bytes(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::String(text) => text.capacity(),
+        // With arbitrary precision enabled, numbers keep their digits as text
+        serde_json::Value::Number(number) => number.to_string().len(),
+        serde_json::Value::Array(values) => vec_bytes(values) + values.iter().map(json_bytes).sum::<usize>(),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| size_of::<(String, serde_json::Value)>() + key.capacity() + json_bytes(value))
+            .sum(),
+        serde_json::Value::Null | serde_json::Value::Bool(_) => 0,
+    }
+}
@@ -1,19 +1,70 @@
 //! Data models for election data, configuration, and results
 
+pub mod account_format;
+pub mod allocation_matrix;
+pub mod anonymize;
+pub mod balance;
+#[cfg(feature = "sign")]
+pub mod bundle_signing;
+pub mod candidate_rules;
+pub mod chain_comparison;
+pub mod conviction;
+pub mod data_statistics;
 pub mod election_config;
 pub mod election_data;
 pub mod election_overrides;
 pub mod election_result;
+pub mod memory_report;
 pub mod nominator;
+pub mod onchain_solution;
+pub mod override_impact;
+pub mod polkadot_js;
+pub mod rank_stability;
+pub mod raw_solution;
+pub mod result_bundle;
+pub mod result_index;
+pub mod rounding;
+pub mod runtime_behavior;
+pub mod snapshot_delta;
+pub mod stake_flow;
+pub mod stake_redistribution;
+pub mod sybil_scenario;
+pub mod validation;
 pub mod validator;
 pub mod voting_edge;
+pub mod whale_impact;
 
-pub use election_config::ElectionConfiguration;
-pub use election_data::ElectionData;
-pub use election_overrides::ElectionOverrides;
-pub use election_result::ElectionResult;
+pub use account_format::AccountFormat;
+pub use allocation_matrix::{AllocationMatrix, StakeBucket, StakeBucketing};
+pub use anonymize::Anonymizer;
+pub use balance::{Balance, TokenUnit};
+pub use candidate_rules::{CandidateRule, CandidateRules, RuleApplication};
+pub use chain_comparison::{ChainComparison, ChainProfile};
+pub use conviction::{Conviction, ConvictionWeights};
+pub use data_statistics::DataStatistics;
+pub use election_config::{ElectionConfiguration, ElectionConfigurationBuilder};
+pub use election_data::{ElectionData, MergePolicy};
+pub use election_overrides::{ElectionOverrides, OverrideApplication};
+pub use election_result::{ElectionResult, ResultIssue, ResultIssueKind};
+pub use memory_report::MemoryReport;
 pub use nominator::Nominator;
+pub use onchain_solution::{OnchainSolution, ScoreReport, SolutionVerification, SubmittedSolution};
+pub use override_impact::OverrideImpact;
+pub use polkadot_js::PolkadotJsStaking;
+pub use rank_stability::{Perturbation, PerturbedRun, RankStability, StabilityScenario, ValidatorStability};
+pub use result_bundle::{BundleManifest, BundleSignature, BundleVerification, ResultBundle, SignatureScheme};
+pub use result_index::{IndexedElectionResult, Page};
+pub use rounding::RoundingPolicy;
+pub use runtime_behavior::{RuntimeBehavior, RuntimeVersion};
+pub use snapshot_delta::SnapshotDelta;
+pub use stake_flow::{EraProjection, StakeFlow, StakeFlowAction, StakeFlowProjection, StakeFlowScenario};
+pub use stake_redistribution::{OverBackedValidator, StakeMove, StakeRedistribution};
+pub use sybil_scenario::{AttackEstimate, SybilPoint, SybilReport, SybilScenario, SybilSplit};
+pub use validation::{
+    DataIssue, DataIssueKind, DedupPolicy, DuplicateEntry, DuplicateKind, DuplicateReport, ValidationProfile,
+};
 pub use validator::ValidatorCandidate;
 pub use voting_edge::VotingEdge;
+pub use whale_impact::{WhaleImpact, WhaleRemoval, WhaleRemovalMode};
 
 
@@ -17,8 +17,11 @@ pub struct Nominator {
 }
 
 /// Metadata for a nominator
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NominatorMetadata {
+    /// Controller account of this stash, when it differs from the stash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller: Option<String>,
     /// Additional metadata fields as needed
     #[serde(flatten)]
     pub extra: std::collections::HashMap<String, serde_json::Value>,
@@ -46,6 +49,11 @@ impl Nominator {
     pub fn remove_target(&mut self, candidate_id: &str) {
         self.targets.retain(|id| id != candidate_id);
     }
+
+    /// Controller account, if it is known and differs from the stash
+    pub fn controller(&self) -> Option<&str> {
+        self.metadata.as_ref().and_then(|m| m.controller.as_deref())
+    }
 }
 
 
@@ -0,0 +1,427 @@
+//! On-chain election solutions and snapshots from `pallet-election-provider-multi-phase`
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::election_data::{ElectionData, ElectionMetadata};
+use crate::models::election_result::{ElectionScore, ScoreMargin};
+use crate::models::nominator::Nominator;
+use crate::models::validator::ValidatorCandidate;
+use crate::types::AlgorithmType;
+use parity_scale_codec::{Compact, Decode};
+use serde::{Deserialize, Serialize};
+use sp_runtime::{PerU16, Saturating};
+use std::collections::{HashMap, HashSet};
+
+/// Votes per voter of the compact solution types of Polkadot (16) and Kusama (24)
+pub const SOLUTION_MAX_VOTES: [u32; 2] = [16, 24];
+
+/// Solution queued on-chain (`ElectionProviderMultiPhase::QueuedSolution`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnchainSolution {
+    /// Backing of each winner
+    pub supports: Vec<OnchainSupport>,
+    /// Score claimed by the submitter
+    pub claimed_score: ElectionScore,
+    /// How the solution was computed (onchain, signed, unsigned, fallback, emergency)
+    pub compute: String,
+}
+
+/// Backing of a single winner in an on-chain solution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnchainSupport {
+    /// Account ID of the winner
+    pub validator_id: String,
+    /// Total backing claimed for the winner
+    pub total: u128,
+    /// Stake assigned to the winner by each voter
+    pub backers: Vec<(String, u128)>,
+}
+
+/// Result of independently verifying an on-chain solution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolutionVerification {
+    /// Block at which the solution and snapshot were read
+    pub block_number: u64,
+    /// How the queued solution was computed
+    pub compute: String,
+    /// Score claimed by the submitter
+    pub claimed_score: ElectionScore,
+    /// Score recomputed from the solution's supports
+    pub computed_score: ElectionScore,
+    /// Whether the claimed score matches the recomputed score
+    pub score_matches: bool,
+    /// Whether the solution is feasible against the snapshot
+    pub feasible: bool,
+    /// Reasons the solution is not feasible
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feasibility_issues: Vec<String>,
+    /// Best score found by the tool's own solvers on the same snapshot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_alternative_score: Option<ElectionScore>,
+    /// Algorithm that produced the best alternative
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_alternative_algorithm: Option<AlgorithmType>,
+    /// Whether the alternative beats the queued solution
+    pub better_solution_found: bool,
+}
+
+/// Externally produced `RawSolution`, as submitted to the multi-phase pallet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmittedSolution {
+    /// Supports the pallet derives from the solution's assignments, with the
+    /// score the submitter claimed
+    pub solution: OnchainSolution,
+    /// Election round the solution was computed for
+    pub round: u32,
+    /// Votes per voter of the compact solution type it was decoded with
+    pub max_votes: u32,
+}
+
+/// A submitted solution refereed against the snapshot and this tool's solvers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreReport {
+    /// Election round the solution was computed for
+    pub round: u32,
+    /// Votes per voter of the compact solution type it was decoded with
+    pub max_votes: u32,
+    /// Score and feasibility of the solution and the best alternative found
+    pub verification: SolutionVerification,
+    /// How far the best alternative's score is from the solution's computed
+    /// score; positive fields favour the alternative
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin: Option<ScoreMargin>,
+}
+
+impl ScoreReport {
+    /// Report on `submitted` from its verification
+    pub fn new(submitted: &SubmittedSolution, verification: SolutionVerification) -> Self {
+        let margin = verification
+            .best_alternative_score
+            .map(|best| best.margin_over(&verification.computed_score));
+        Self {
+            round: submitted.round,
+            max_votes: submitted.max_votes,
+            verification,
+            margin,
+        }
+    }
+}
+
+#[derive(Decode)]
+struct RawSupport {
+    total: u128,
+    voters: Vec<([u8; 32], u128)>,
+}
+
+#[derive(Decode)]
+struct RawScore {
+    minimal_stake: u128,
+    sum_stake: u128,
+    sum_stake_squared: u128,
+}
+
+#[derive(Decode)]
+struct RawReadySolution {
+    supports: Vec<([u8; 32], RawSupport)>,
+    score: RawScore,
+    compute: u8,
+}
+
+#[derive(Decode)]
+struct RawSnapshot {
+    voters: Vec<([u8; 32], u64, Vec<[u8; 32]>)>,
+    targets: Vec<[u8; 32]>,
+}
+
+impl OnchainSolution {
+    /// Decode a SCALE-encoded `ReadySolution`
+    pub fn decode(bytes: &[u8]) -> Result<Self, ElectionError> {
+        let raw = RawReadySolution::decode(&mut &bytes[..]).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to decode queued solution: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+
+        let compute = match raw.compute {
+            0 => "onchain",
+            1 => "signed",
+            2 => "unsigned",
+            3 => "fallback",
+            4 => "emergency",
+            _ => "unknown",
+        };
+
+        Ok(Self {
+            supports: raw
+                .supports
+                .into_iter()
+                .map(|(who, support)| OnchainSupport {
+                    validator_id: account_hex(&who),
+                    total: support.total,
+                    backers: support
+                        .voters
+                        .into_iter()
+                        .map(|(voter, amount)| (account_hex(&voter), amount))
+                        .collect(),
+                })
+                .collect(),
+            claimed_score: ElectionScore {
+                minimal_stake: raw.score.minimal_stake,
+                sum_stake: raw.score.sum_stake,
+                sum_stake_squared: raw.score.sum_stake_squared,
+            },
+            compute: compute.to_string(),
+        })
+    }
+
+    /// Winners of the solution
+    pub fn winners(&self) -> Vec<String> {
+        self.supports.iter().map(|s| s.validator_id.clone()).collect()
+    }
+
+    /// Score recomputed from the supports
+    pub fn computed_score(&self) -> ElectionScore {
+        ElectionScore::from_backings(self.supports.iter().map(|s| s.total))
+    }
+
+    /// Check the solution against the snapshot it was computed from
+    ///
+    /// Mirrors the pallet's feasibility check: winners must be snapshot
+    /// targets, every backer must be a snapshot voter who voted for that
+    /// winner and did not assign more than their stake, and each support's
+    /// total must equal the sum of its backers. Returns the issues found.
+    pub fn check_feasibility(&self, snapshot: &ElectionData, desired_targets: Option<u32>) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if let Some(desired) = desired_targets {
+            if self.supports.len() != desired as usize {
+                issues.push(format!(
+                    "Solution has {} winners but {} are desired",
+                    self.supports.len(),
+                    desired
+                ));
+            }
+        }
+
+        let targets: HashSet<&String> = snapshot.candidates.iter().map(|c| &c.account_id).collect();
+        let voters: HashMap<&String, &Nominator> =
+            snapshot.nominators.iter().map(|n| (&n.account_id, n)).collect();
+        let mut assigned: HashMap<&String, u128> = HashMap::new();
+        let mut seen_winners = HashSet::new();
+
+        for support in &self.supports {
+            if !seen_winners.insert(&support.validator_id) {
+                issues.push(format!("Duplicate winner {}", support.validator_id));
+            }
+            if !targets.contains(&support.validator_id) {
+                issues.push(format!("Winner {} is not a snapshot target", support.validator_id));
+            }
+
+            let mut backing: u128 = 0;
+            for (voter_id, amount) in &support.backers {
+                backing = backing.saturating_add(*amount);
+                match voters.get(voter_id) {
+                    Some(voter) if voter.targets.contains(&support.validator_id) => {
+                        let total = assigned.entry(voter_id).or_insert(0);
+                        *total = total.saturating_add(*amount);
+                    }
+                    Some(_) => issues.push(format!(
+                        "Voter {} did not vote for winner {}",
+                        voter_id, support.validator_id
+                    )),
+                    None => issues.push(format!("Backer {} is not a snapshot voter", voter_id)),
+                }
+            }
+            if backing != support.total {
+                issues.push(format!(
+                    "Support total {} for {} does not match its backers ({})",
+                    support.total, support.validator_id, backing
+                ));
+            }
+        }
+
+        for (voter_id, total) in assigned {
+            let stake = voters.get(voter_id).map(|v| v.stake).unwrap_or(0);
+            if total > stake {
+                issues.push(format!(
+                    "Voter {} assigned {} but only has {} stake",
+                    voter_id, total, stake
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+impl SubmittedSolution {
+    /// Decode a SCALE-encoded `RawSolution` against the snapshot it indexes
+    ///
+    /// The compact solution refers to voters and targets by their position in
+    /// the snapshot, with `u32` voter and `u16` target indices and `PerU16`
+    /// ratios, as on Polkadot and Kusama. Its number of votes per voter is
+    /// part of the runtime's type: when `max_votes` is `None`, each of
+    /// [`SOLUTION_MAX_VOTES`] is tried and the one that decodes the whole
+    /// input is used. Supports are then derived as the pallet does, by
+    /// spreading each voter's snapshot stake by its ratios and normalizing.
+    pub fn decode(bytes: &[u8], snapshot: &ElectionData, max_votes: Option<u32>) -> Result<Self, ElectionError> {
+        let candidates = max_votes.map_or_else(|| SOLUTION_MAX_VOTES.to_vec(), |max| vec![max]);
+        let mut last_error = None;
+        for max_votes in candidates {
+            match decode_raw_solution(bytes, max_votes) {
+                Ok((votes, score, round)) => {
+                    let solution = solution_supports(&votes, snapshot, score)?;
+                    return Ok(Self { solution, round, max_votes });
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        let e = last_error.unwrap_or_else(|| "no vote count to try".into());
+        Err(ElectionError::InvalidData {
+            message: format!("Failed to decode submitted solution: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+}
+
+/// One voter's votes in a compact solution: the voter, all targets but the
+/// last with their ratio, and the last target, which takes the remainder
+type CompactVote = (u32, Vec<(u16, PerU16)>, u16);
+
+/// Decode a `RawSolution { solution, score, round }` whose compact solution
+/// has `max_votes` vote fields, requiring the whole input to be consumed
+fn decode_raw_solution(
+    bytes: &[u8],
+    max_votes: u32,
+) -> Result<(Vec<CompactVote>, ElectionScore, u32), parity_scale_codec::Error> {
+    let input = &mut &bytes[..];
+    let mut votes = Vec::new();
+    for targets in 1..=max_votes {
+        let count = Compact::<u32>::decode(input)?.0;
+        for _ in 0..count {
+            let voter = Compact::<u32>::decode(input)?.0;
+            let mut ratios = Vec::with_capacity(targets as usize - 1);
+            for _ in 1..targets {
+                let target = Compact::<u16>::decode(input)?.0;
+                let ratio = Compact::<PerU16>::decode(input)?.0;
+                ratios.push((target, ratio));
+            }
+            let last = Compact::<u16>::decode(input)?.0;
+            votes.push((voter, ratios, last));
+        }
+    }
+    let score = RawScore::decode(input)?;
+    let round = u32::decode(input)?;
+    if !input.is_empty() {
+        return Err("trailing bytes after the solution".into());
+    }
+    let score = ElectionScore {
+        minimal_stake: score.minimal_stake,
+        sum_stake: score.sum_stake,
+        sum_stake_squared: score.sum_stake_squared,
+    };
+    Ok((votes, score, round))
+}
+
+/// Supports of a compact solution, from the voters' snapshot stakes
+fn solution_supports(
+    votes: &[CompactVote],
+    snapshot: &ElectionData,
+    claimed_score: ElectionScore,
+) -> Result<OnchainSolution, ElectionError> {
+    let out_of_bounds = |kind: &str, index: u32| ElectionError::InvalidData {
+        message: format!("Solution names {} index {}, outside the snapshot", kind, index),
+        source: None,
+    };
+
+    // Voters and targets share one identifier type in `sp-npos-elections`;
+    // targets are offset past the voters to keep them apart
+    let offset = snapshot.nominators.len() as u32;
+    let mut assignments = Vec::with_capacity(votes.len());
+    for (voter, ratios, last) in votes {
+        if *voter >= offset {
+            return Err(out_of_bounds("voter", *voter));
+        }
+        let mut distribution = Vec::with_capacity(ratios.len() + 1);
+        let mut sum = PerU16::zero();
+        for (target, ratio) in ratios.iter().map(|(t, r)| (*t, *r)).chain([(*last, PerU16::zero())]) {
+            if target as usize >= snapshot.candidates.len() {
+                return Err(out_of_bounds("target", target as u32));
+            }
+            distribution.push((offset + target as u32, ratio));
+            sum = sum.saturating_add(ratio);
+        }
+        // The last target takes what the others leave, as in the pallet
+        if let Some((_, ratio)) = distribution.last_mut() {
+            *ratio = PerU16::one().saturating_sub(sum);
+        }
+        assignments.push(sp_npos_elections::Assignment { who: *voter, distribution });
+    }
+
+    let staked = sp_npos_elections::assignment_ratio_to_staked_normalized(assignments, |who: &u32| {
+        snapshot.nominators[*who as usize].stake.min(u64::MAX as u128) as u64
+    })
+    .map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to convert the solution to staked assignments: {:?}", e),
+        source: None,
+    })?;
+
+    let supports = sp_npos_elections::to_supports(&staked)
+        .into_iter()
+        .map(|(target, support)| OnchainSupport {
+            validator_id: snapshot.candidates[(target - offset) as usize].account_id.clone(),
+            total: support.total,
+            backers: support
+                .voters
+                .into_iter()
+                .map(|(voter, amount)| (snapshot.nominators[voter as usize].account_id.clone(), amount))
+                .collect(),
+        })
+        .collect();
+
+    Ok(OnchainSolution {
+        supports,
+        claimed_score,
+        compute: "submitted".to_string(),
+    })
+}
+
+/// Decode a SCALE-encoded `RoundSnapshot` into election data
+///
+/// Targets become candidates with zero stake; validator self-stake is carried
+/// by the validator's own self-vote in the voter list, as on-chain.
+pub fn decode_snapshot(bytes: &[u8], block_number: Option<u64>) -> Result<ElectionData, ElectionError> {
+    let raw = RawSnapshot::decode(&mut &bytes[..]).map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to decode election snapshot: {}", e),
+        source: Some(ErrorSource::new(e)),
+    })?;
+
+    Ok(ElectionData {
+        candidates: raw
+            .targets
+            .iter()
+            .map(|target| ValidatorCandidate::new(account_hex(target), 0))
+            .collect(),
+        nominators: raw
+            .voters
+            .into_iter()
+            .map(|(who, stake, targets)| {
+                let mut voter = Nominator::new(account_hex(&who), stake as u128);
+                voter.targets = targets.iter().map(account_hex).collect();
+                voter
+            })
+            .collect(),
+        invulnerables: Vec::new(),
+        metadata: Some(ElectionMetadata {
+            block_number,
+            chain: None,
+            rpc_retries: None,
+            era: None,
+            load_failures: Vec::new(),
+            stake_source: None,
+        }),
+        ..ElectionData::new()
+    })
+}
+
+fn account_hex(account: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(account))
+}
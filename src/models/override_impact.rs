@@ -0,0 +1,160 @@
+//! Override impact model describing how overrides change an election outcome
+
+use crate::models::election_result::{ElectionResult, ElectionScore};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Delta between a baseline election and the same election with overrides applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverrideImpact {
+    /// Validators elected only with the overrides applied
+    pub validators_added: Vec<String>,
+    /// Validators elected only in the baseline
+    pub validators_removed: Vec<String>,
+    /// Score of the baseline election
+    pub baseline_score: ElectionScore,
+    /// Score of the election with overrides applied
+    pub overridden_score: ElectionScore,
+    /// Change in score (overridden minus baseline)
+    pub score_delta: ScoreDelta,
+    /// Nominators whose stake allocation changed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub affected_nominators: Vec<NominatorImpact>,
+}
+
+/// Signed change in each election score component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreDelta {
+    /// Change in minimal stake
+    pub minimal_stake: i128,
+    /// Change in total stake
+    pub sum_stake: i128,
+    /// Change in sum of squared stakes
+    pub sum_stake_squared: i128,
+}
+
+/// How a single nominator's allocation changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NominatorImpact {
+    /// Account ID of the nominator
+    pub nominator_id: String,
+    /// Stake allocated to elected validators in the baseline
+    pub baseline_active_stake: u128,
+    /// Stake allocated to elected validators with overrides applied
+    pub overridden_active_stake: u128,
+    /// Validators this nominator backs only with the overrides applied
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_gained: Vec<String>,
+    /// Validators this nominator backs only in the baseline
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_lost: Vec<String>,
+}
+
+impl ScoreDelta {
+    /// Compute the delta between two scores
+    pub fn between(baseline: &ElectionScore, overridden: &ElectionScore) -> Self {
+        Self {
+            minimal_stake: signed_delta(baseline.minimal_stake, overridden.minimal_stake),
+            sum_stake: signed_delta(baseline.sum_stake, overridden.sum_stake),
+            sum_stake_squared: signed_delta(baseline.sum_stake_squared, overridden.sum_stake_squared),
+        }
+    }
+}
+
+impl OverrideImpact {
+    /// Compute the impact between a baseline result and an overridden result
+    pub fn between(baseline: &ElectionResult, overridden: &ElectionResult) -> Self {
+        let baseline_set: BTreeSet<&String> = baseline
+            .selected_validators
+            .iter()
+            .map(|v| &v.account_id)
+            .collect();
+        let overridden_set: BTreeSet<&String> = overridden
+            .selected_validators
+            .iter()
+            .map(|v| &v.account_id)
+            .collect();
+
+        let baseline_allocations = allocations_by_nominator(baseline);
+        let overridden_allocations = allocations_by_nominator(overridden);
+        let nominator_ids: BTreeSet<&String> = baseline_allocations
+            .keys()
+            .chain(overridden_allocations.keys())
+            .copied()
+            .collect();
+
+        let empty = BTreeMap::new();
+        let mut affected_nominators = Vec::new();
+        for nominator_id in nominator_ids {
+            let before = baseline_allocations.get(nominator_id).unwrap_or(&empty);
+            let after = overridden_allocations.get(nominator_id).unwrap_or(&empty);
+            if before == after {
+                continue;
+            }
+
+            affected_nominators.push(NominatorImpact {
+                nominator_id: nominator_id.clone(),
+                baseline_active_stake: before.values().sum(),
+                overridden_active_stake: after.values().sum(),
+                validators_gained: after
+                    .keys()
+                    .filter(|id| !before.contains_key(*id))
+                    .map(|id| (*id).clone())
+                    .collect(),
+                validators_lost: before
+                    .keys()
+                    .filter(|id| !after.contains_key(*id))
+                    .map(|id| (*id).clone())
+                    .collect(),
+            });
+        }
+
+        let baseline_score = baseline.score();
+        let overridden_score = overridden.score();
+
+        Self {
+            validators_added: overridden_set
+                .difference(&baseline_set)
+                .map(|id| (*id).clone())
+                .collect(),
+            validators_removed: baseline_set
+                .difference(&overridden_set)
+                .map(|id| (*id).clone())
+                .collect(),
+            baseline_score,
+            overridden_score,
+            score_delta: ScoreDelta::between(&baseline_score, &overridden_score),
+            affected_nominators,
+        }
+    }
+
+    /// Whether the overrides changed the elected set
+    pub fn set_changed(&self) -> bool {
+        !self.validators_added.is_empty() || !self.validators_removed.is_empty()
+    }
+}
+
+/// Group non-zero allocations by nominator, then by validator
+fn allocations_by_nominator(result: &ElectionResult) -> BTreeMap<&String, BTreeMap<&String, u128>> {
+    let mut allocations: BTreeMap<&String, BTreeMap<&String, u128>> = BTreeMap::new();
+    for alloc in &result.stake_distribution {
+        if alloc.amount == 0 {
+            continue;
+        }
+        *allocations
+            .entry(&alloc.nominator_id)
+            .or_default()
+            .entry(&alloc.validator_id)
+            .or_insert(0) += alloc.amount;
+    }
+    allocations
+}
+
+/// Signed difference `after - before`, saturating at the i128 bounds
+fn signed_delta(before: u128, after: u128) -> i128 {
+    if after >= before {
+        i128::try_from(after - before).unwrap_or(i128::MAX)
+    } else {
+        i128::try_from(before - after).map(|d| -d).unwrap_or(i128::MIN)
+    }
+}
@@ -0,0 +1,132 @@
+//! Election results in the JSON shapes of polkadot-js staking queries
+//!
+//! Dashboards and scripts built on `api.query.staking.erasStakers` and
+//! `api.query.session.validators` can read this export unchanged.
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::account_format::AccountFormat;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::{BackingBreakdown, ElectionResult, StakeAllocation};
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+/// Winners and exposures of an election as polkadot-js would return them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolkadotJsStaking {
+    /// Era the exposures are for, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub era: Option<u32>,
+    /// Elected validators in rank order, like `session.validators`
+    pub validators: Vec<String>,
+    /// Exposure of each elected validator, like `staking.erasStakers(era, validator)`
+    pub eras_stakers: BTreeMap<String, Exposure>,
+}
+
+/// A validator's exposure (`SpStakingExposure`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Exposure {
+    /// Total backing, own stake included
+    pub total: JsBalance,
+    /// Stake from the validator's self-vote
+    pub own: JsBalance,
+    /// Stake from each nominator, largest first
+    pub others: Vec<IndividualExposure>,
+}
+
+/// A nominator's part of an exposure (`SpStakingIndividualExposure`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IndividualExposure {
+    /// Nominator account
+    pub who: String,
+    /// Stake exposed to the validator
+    pub value: JsBalance,
+}
+
+/// A balance serialized as polkadot-js `toJSON()` does
+///
+/// Values that fit in 52 bits are plain numbers; larger values are 128-bit
+/// hex strings, as JavaScript numbers cannot hold them exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsBalance(pub u128);
+
+impl Serialize for JsBalance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0 < 1 << 52 {
+            serializer.serialize_u64(self.0 as u64)
+        } else {
+            serializer.serialize_str(&format!("0x{:032x}", self.0))
+        }
+    }
+}
+
+impl PolkadotJsStaking {
+    /// Build the export from an election result
+    ///
+    /// A validator's own stake is what its self-vote allocated to it or, if
+    /// `data` has no self-vote for it, its bonded stake, which then counts
+    /// towards `total` too. Every other backer becomes an entry in `others`.
+    pub fn from_result(result: &ElectionResult, data: &ElectionData, era: Option<u32>) -> Self {
+        let bonded: HashMap<&str, u128> = data.candidates.iter().map(|c| (c.account_id.as_str(), c.stake)).collect();
+        let mut allocations: HashMap<&str, Vec<&StakeAllocation>> = HashMap::new();
+        for allocation in &result.stake_distribution {
+            allocations.entry(allocation.validator_id.as_str()).or_default().push(allocation);
+        }
+        let eras_stakers = result
+            .selected_validators
+            .iter()
+            .map(|validator| {
+                let backing = BackingBreakdown::from_allocations(
+                    &validator.account_id,
+                    allocations.get(validator.account_id.as_str()).into_iter().flatten().copied(),
+                )
+                .with_bonded_self_stake(bonded.get(validator.account_id.as_str()).copied().unwrap_or(0));
+                let others = backing
+                    .contributions
+                    .iter()
+                    .filter(|c| c.nominator_id != validator.account_id)
+                    .map(|c| IndividualExposure { who: c.nominator_id.clone(), value: JsBalance(c.amount) })
+                    .collect();
+                let exposure = Exposure {
+                    total: JsBalance(backing.total_backing),
+                    own: JsBalance(backing.self_stake),
+                    others,
+                };
+                (validator.account_id.clone(), exposure)
+            })
+            .collect();
+
+        Self {
+            era,
+            validators: result.selected_validators.iter().map(|v| v.account_id.clone()).collect(),
+            eras_stakers,
+        }
+    }
+
+    /// Rewrite every account ID in the export to `format`
+    pub fn reencode_accounts(&mut self, format: AccountFormat, network_prefix: u16) {
+        if format.is_as_loaded() {
+            return;
+        }
+        for validator in &mut self.validators {
+            *validator = format.encode(validator, network_prefix);
+        }
+        self.eras_stakers = std::mem::take(&mut self.eras_stakers)
+            .into_iter()
+            .map(|(validator, mut exposure)| {
+                for other in &mut exposure.others {
+                    other.who = format.encode(&other.who, network_prefix);
+                }
+                (format.encode(&validator, network_prefix), exposure)
+            })
+            .collect();
+    }
+
+    /// Convert the export to a JSON string
+    pub fn to_json(&self) -> Result<String, ElectionError> {
+        serde_json::to_string_pretty(self).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize polkadot-js export to JSON: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+}
@@ -0,0 +1,364 @@
+//! Rank-order stability of the elected set
+//!
+//! An operator near the cutoff wants to know whether a small change to the
+//! election, one seat more or less or a little stake moving around, would
+//! cost them their seat. A [`StabilityScenario`] describes such changes;
+//! [`ElectionEngine::rank_stability`](crate::engine::ElectionEngine::rank_stability)
+//! re-runs the election under each of them and compares every winner
+//! ordering with the baseline by Kendall's tau.
+
+use crate::error::ElectionError;
+use crate::models::election_data::ElectionData;
+use crate::models::snapshot_delta::SnapshotDelta;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default number of seats the active set size is moved by
+pub const DEFAULT_SET_SIZE_DELTA: u32 = 1;
+/// Default largest stake change per nominator, in basis points
+pub const DEFAULT_STAKE_NOISE_BPS: u32 = 100;
+/// Default number of stake noise runs
+pub const DEFAULT_NOISE_RUNS: usize = 5;
+
+/// Perturbations to re-run an election under
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StabilityScenario {
+    /// Seats added to and removed from the active set size; 0 skips these runs
+    pub set_size_delta: u32,
+    /// Largest change to each nominator's stake, in basis points
+    pub stake_noise_bps: u32,
+    /// Number of runs with independently drawn stake noise
+    pub noise_runs: usize,
+    /// Seed of the first noise run; run `i` uses `seed + i`
+    pub seed: u64,
+}
+
+/// One change made to an election
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Perturbation {
+    /// The election fills a different number of seats
+    SetSize {
+        /// Active set size of the run
+        active_set_size: u32,
+    },
+    /// Every nominator's stake is scaled by a random factor
+    StakeNoise {
+        /// Seed the factors are drawn from
+        seed: u64,
+        /// Largest change per nominator, in basis points
+        max_bps: u32,
+    },
+}
+
+/// Winner ordering of one perturbed run against the baseline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PerturbedRun {
+    /// Change made to the election
+    pub perturbation: Perturbation,
+    /// Kendall's tau between the baseline and perturbed ranks of the
+    /// validators elected in both, in basis points (10000 keeps the order,
+    /// -10000 reverses it)
+    pub kendall_tau_bps: i32,
+    /// Validators elected in both runs
+    pub common_winners: usize,
+    /// Validators elected only in the perturbed run
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_added: Vec<String>,
+    /// Validators elected only in the baseline
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_removed: Vec<String>,
+}
+
+/// How one validator's seat held up across the perturbed runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorStability {
+    /// Account ID of the validator
+    pub validator_id: String,
+    /// Rank in the baseline, 1 being the first elected; `None` if not elected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline_rank: Option<u32>,
+    /// Perturbed runs that elected the validator
+    pub elected_runs: usize,
+    /// Best rank across the perturbed runs that elected it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_rank: Option<u32>,
+    /// Worst rank across the perturbed runs that elected it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worst_rank: Option<u32>,
+    /// Whether some perturbation flips the validator's selection
+    ///
+    /// Losing a seat to a smaller active set, or gaining one in a larger
+    /// set, is expected at the cutoff and does not count.
+    pub fragile: bool,
+}
+
+/// Outcome of re-running an election under a [`StabilityScenario`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankStability {
+    /// Active set size of the baseline
+    pub active_set_size: u32,
+    /// Baseline winners in rank order
+    pub baseline_winners: Vec<String>,
+    /// One entry per perturbed run: set size runs first, then noise runs
+    pub runs: Vec<PerturbedRun>,
+    /// Mean of the runs' Kendall's tau, in basis points
+    pub mean_kendall_tau_bps: i32,
+    /// Validators elected in the baseline or any run: baseline winners in
+    /// rank order, then the others by account
+    pub validators: Vec<ValidatorStability>,
+}
+
+impl StabilityScenario {
+    /// Create a scenario with the defaults: one seat either way and five
+    /// runs with up to 1% stake noise
+    pub fn new() -> Self {
+        Self {
+            set_size_delta: DEFAULT_SET_SIZE_DELTA,
+            stake_noise_bps: DEFAULT_STAKE_NOISE_BPS,
+            noise_runs: DEFAULT_NOISE_RUNS,
+            seed: 0,
+        }
+    }
+
+    /// Set the seats added to and removed from the active set size
+    pub fn set_size_delta(mut self, delta: u32) -> Self {
+        self.set_size_delta = delta;
+        self
+    }
+
+    /// Set the largest stake change per nominator, in basis points
+    pub fn stake_noise_bps(mut self, bps: u32) -> Self {
+        self.stake_noise_bps = bps;
+        self
+    }
+
+    /// Set the number of stake noise runs
+    pub fn noise_runs(mut self, runs: usize) -> Self {
+        self.noise_runs = runs;
+        self
+    }
+
+    /// Set the seed of the first noise run
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Check the scenario perturbs anything
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        let invalid = |message: String| ElectionError::ValidationError {
+            message,
+            field: Some("stability_scenario".to_string()),
+        };
+        if self.stake_noise_bps > 10_000 {
+            return Err(invalid(format!(
+                "Stake noise is {} bps, but a stake cannot change by more than 10000 bps",
+                self.stake_noise_bps
+            )));
+        }
+        let noise = self.noise_runs > 0 && self.stake_noise_bps > 0;
+        if self.set_size_delta == 0 && !noise {
+            return Err(invalid("Stability scenario needs a set size delta or stake noise runs".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Perturbations of an election filling `active_set_size` seats, in run order
+    ///
+    /// A smaller set that would have no seats is left out.
+    pub fn perturbations(&self, active_set_size: u32) -> Vec<Perturbation> {
+        let mut perturbations = Vec::new();
+        if self.set_size_delta > 0 {
+            if active_set_size > self.set_size_delta {
+                perturbations.push(Perturbation::SetSize {
+                    active_set_size: active_set_size - self.set_size_delta,
+                });
+            }
+            perturbations.push(Perturbation::SetSize {
+                active_set_size: active_set_size.saturating_add(self.set_size_delta),
+            });
+        }
+        if self.stake_noise_bps > 0 {
+            perturbations.extend((0..self.noise_runs).map(|run| Perturbation::StakeNoise {
+                seed: self.seed.wrapping_add(run as u64),
+                max_bps: self.stake_noise_bps,
+            }));
+        }
+        perturbations
+    }
+}
+
+impl Default for StabilityScenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Perturbation {
+    /// Stake overrides scaling every nominator of `data` by up to `max_bps`
+    /// either way, at most 10000; empty for set size changes
+    ///
+    /// Each factor is drawn from the seed and the nominator's account, so the
+    /// same seed always scales the same nominator by the same factor, whatever
+    /// order or subset of nominators the data holds.
+    pub fn delta(&self, data: &ElectionData) -> SnapshotDelta {
+        let mut delta = SnapshotDelta::default();
+        let Perturbation::StakeNoise { seed, max_bps } = *self else {
+            return delta;
+        };
+        let max_bps = max_bps.min(10_000);
+        let span = 2 * max_bps as u64 + 1;
+        for nominator in &data.nominators {
+            let factor = 10_000 - max_bps as u128 + (noise(seed, &nominator.account_id) % span) as u128;
+            let stake = nominator.stake.saturating_mul(factor) / 10_000;
+            if stake != nominator.stake {
+                delta.overrides.nominator_stakes.insert(nominator.account_id.clone(), stake);
+            }
+        }
+        delta
+    }
+
+    /// Whether losing a seat under this perturbation is expected at the cutoff
+    fn shrinks(&self, baseline_size: u32) -> bool {
+        matches!(*self, Perturbation::SetSize { active_set_size } if active_set_size < baseline_size)
+    }
+
+    /// Whether gaining a seat under this perturbation is expected at the cutoff
+    fn grows(&self, baseline_size: u32) -> bool {
+        matches!(*self, Perturbation::SetSize { active_set_size } if active_set_size > baseline_size)
+    }
+}
+
+impl std::fmt::Display for Perturbation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Perturbation::SetSize { active_set_size } => write!(f, "{} seats", active_set_size),
+            Perturbation::StakeNoise { seed, max_bps } => {
+                write!(f, "±{:.2}% stake noise (seed {})", *max_bps as f64 / 100.0, seed)
+            }
+        }
+    }
+}
+
+impl PerturbedRun {
+    /// Compare the winners of a perturbed run with the baseline's, both in rank order
+    pub fn between(perturbation: Perturbation, baseline: &[String], winners: &[String]) -> Self {
+        let ranks: HashMap<&str, usize> = winners.iter().enumerate().map(|(rank, id)| (id.as_str(), rank)).collect();
+        let common: Vec<usize> = baseline.iter().filter_map(|id| ranks.get(id.as_str()).copied()).collect();
+        let in_baseline: std::collections::HashSet<&str> = baseline.iter().map(String::as_str).collect();
+        Self {
+            perturbation,
+            kendall_tau_bps: kendall_tau_bps(&common),
+            common_winners: common.len(),
+            validators_added: winners.iter().filter(|id| !in_baseline.contains(id.as_str())).cloned().collect(),
+            validators_removed: baseline.iter().filter(|id| !ranks.contains_key(id.as_str())).cloned().collect(),
+        }
+    }
+}
+
+impl RankStability {
+    /// Summarize perturbed runs given each run's winners in rank order
+    pub fn from_runs(
+        active_set_size: u32,
+        baseline_winners: Vec<String>,
+        runs: Vec<(Perturbation, Vec<String>)>,
+    ) -> Self {
+        let mut validators: Vec<ValidatorStability> = baseline_winners
+            .iter()
+            .enumerate()
+            .map(|(rank, id)| ValidatorStability::new(id.clone(), Some(rank as u32 + 1)))
+            .collect();
+        let mut others: Vec<&String> = runs
+            .iter()
+            .flat_map(|(_, winners)| winners)
+            .filter(|id| !baseline_winners.contains(id))
+            .collect();
+        others.sort();
+        others.dedup();
+        validators.extend(others.into_iter().map(|id| ValidatorStability::new(id.clone(), None)));
+
+        for (perturbation, winners) in &runs {
+            let ranks: HashMap<&str, u32> =
+                winners.iter().enumerate().map(|(rank, id)| (id.as_str(), rank as u32 + 1)).collect();
+            for validator in &mut validators {
+                match ranks.get(validator.validator_id.as_str()) {
+                    Some(&rank) => {
+                        validator.elected_runs += 1;
+                        validator.best_rank = Some(validator.best_rank.map_or(rank, |best| best.min(rank)));
+                        validator.worst_rank = Some(validator.worst_rank.map_or(rank, |worst| worst.max(rank)));
+                        if validator.baseline_rank.is_none() && !perturbation.grows(active_set_size) {
+                            validator.fragile = true;
+                        }
+                    }
+                    None => {
+                        if validator.baseline_rank.is_some() && !perturbation.shrinks(active_set_size) {
+                            validator.fragile = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let runs: Vec<PerturbedRun> = runs
+            .iter()
+            .map(|(perturbation, winners)| PerturbedRun::between(*perturbation, &baseline_winners, winners))
+            .collect();
+        let mean_kendall_tau_bps = if runs.is_empty() {
+            10_000
+        } else {
+            (runs.iter().map(|run| run.kendall_tau_bps as i64).sum::<i64>() / runs.len() as i64) as i32
+        };
+        Self {
+            active_set_size,
+            baseline_winners,
+            runs,
+            mean_kendall_tau_bps,
+            validators,
+        }
+    }
+
+    /// Validators whose selection some perturbation flips
+    pub fn fragile(&self) -> impl Iterator<Item = &ValidatorStability> {
+        self.validators.iter().filter(|v| v.fragile)
+    }
+}
+
+impl ValidatorStability {
+    fn new(validator_id: String, baseline_rank: Option<u32>) -> Self {
+        Self {
+            validator_id,
+            baseline_rank,
+            elected_runs: 0,
+            best_rank: None,
+            worst_rank: None,
+            fragile: false,
+        }
+    }
+}
+
+/// Kendall's tau of a ranking against the identity, in basis points
+///
+/// `ranks[i]` is the perturbed rank of the baseline's `i`-th common winner.
+/// Ranks are distinct, so there are no ties; fewer than two winners keep
+/// their order trivially.
+fn kendall_tau_bps(ranks: &[usize]) -> i32 {
+    let pairs = ranks.len() * ranks.len().saturating_sub(1) / 2;
+    if pairs == 0 {
+        return 10_000;
+    }
+    let mut concordant = 0i64;
+    for (i, a) in ranks.iter().enumerate() {
+        concordant += ranks[i + 1..].iter().filter(|b| a < b).count() as i64;
+    }
+    let discordant = pairs as i64 - concordant;
+    ((concordant - discordant) * 10_000 / pairs as i64) as i32
+}
+
+/// Deterministic noise for one nominator under `seed`
+fn noise(seed: u64, account_id: &str) -> u64 {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::new().chain_update(seed.to_le_bytes()).chain_update(account_id.as_bytes()).finalize();
+    u64::from_le_bytes(digest[..8].try_into().expect("digest is 32 bytes"))
+}
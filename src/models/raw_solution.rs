@@ -0,0 +1,118 @@
+//! Solver output as `sp-npos-elections` produced it
+//!
+//! A result's `selected_validators` and `stake_distribution` are derived from
+//! the solver's solution: portions become floating point proportions and
+//! amounts are taken of each nominator's full stake. Tooling that checks an
+//! election at the lowest level, such as a staking miner or a runtime test,
+//! compares against the solver's own structures instead. With
+//! [`ResultDetail::Full`] a result carries them as a [`RawSolution`], with
+//! only the solver's candidate and voter indices replaced by account IDs.
+
+use serde::{Deserialize, Serialize};
+
+/// How much of the solver's output a result includes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResultDetail {
+    /// Winners and stake distribution only
+    #[default]
+    Summary,
+    /// Also the raw solution: ratio and staked assignments and the supports map
+    Full,
+}
+
+impl ResultDetail {
+    /// Whether the result leaves the raw solution out
+    pub fn is_summary(&self) -> bool {
+        *self == Self::Summary
+    }
+}
+
+impl std::fmt::Display for ResultDetail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Summary => write!(f, "summary"),
+            Self::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl std::str::FromStr for ResultDetail {
+    type Err = String;
+
+    /// Parse "summary" or "full"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "summary" => Ok(Self::Summary),
+            "full" => Ok(Self::Full),
+            other => Err(format!("Invalid result detail '{}': expected summary or full", other)),
+        }
+    }
+}
+
+/// The solver's solution with account IDs in place of indices
+///
+/// Taken after balancing, if the configuration asks for it. Amounts are in
+/// plancks of the voter's stake as the solver saw it, which is capped to
+/// `u64::MAX`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawSolution {
+    /// Winners in election order, each with the backing the solver reported
+    pub winners: Vec<(String, u128)>,
+    /// Each voter's distribution in parts per billion (`Perbill`)
+    pub assignments: Vec<RawAssignment<u32>>,
+    /// Each voter's distribution in plancks, normalized so it sums to the
+    /// voter's stake (`assignment_ratio_to_staked_normalized`)
+    pub staked_assignments: Vec<RawAssignment<u128>>,
+    /// Backing of each winner with at least one voter (`to_supports`), in
+    /// candidate order
+    pub supports: Vec<(String, RawSupport)>,
+}
+
+/// One voter's distribution over its targets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawAssignment<T> {
+    /// Account ID of the voter
+    pub who: String,
+    /// Target and share of the voter's stake
+    pub distribution: Vec<(String, T)>,
+}
+
+/// Backing of one winner
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawSupport {
+    /// Sum of the voters' stakes on the winner
+    pub total: u128,
+    /// Stake each voter put on the winner
+    pub voters: Vec<(String, u128)>,
+}
+
+impl RawSolution {
+    /// Rewrite every account ID with `encode`
+    pub(crate) fn map_accounts(&mut self, encode: impl Fn(&str) -> String) {
+        for (winner, _) in &mut self.winners {
+            *winner = encode(winner);
+        }
+        for assignment in &mut self.assignments {
+            assignment.map_accounts(&encode);
+        }
+        for assignment in &mut self.staked_assignments {
+            assignment.map_accounts(&encode);
+        }
+        for (winner, support) in &mut self.supports {
+            *winner = encode(winner);
+            for (voter, _) in &mut support.voters {
+                *voter = encode(voter);
+            }
+        }
+    }
+}
+
+impl<T> RawAssignment<T> {
+    fn map_accounts(&mut self, encode: &impl Fn(&str) -> String) {
+        self.who = encode(&self.who);
+        for (target, _) in &mut self.distribution {
+            *target = encode(target);
+        }
+    }
+}
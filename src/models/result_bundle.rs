@@ -0,0 +1,340 @@
+//! Result provenance bundles
+//!
+//! A bundle is a directory holding an election result together with the exact
+//! data and configuration it was computed from, plus a manifest of hashes, so
+//! anyone can re-run the election and check they get the same result.
+
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::ElectionResult;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Version of the bundle layout written by [`ElectionResult::export_bundle`]
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+/// The election result
+pub const RESULT_FILE: &str = "result.json";
+/// The election data after validation, overrides and candidate rules
+pub const DATA_FILE: &str = "election_data.json";
+/// The configuration that reproduces the result on [`DATA_FILE`]
+pub const CONFIG_FILE: &str = "config.json";
+/// The [`BundleManifest`]
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// Optional [`BundleSignature`] over the manifest
+pub const SIGNATURE_FILE: &str = "signature.json";
+
+/// Hashes and provenance of the files in a bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Bundle layout version
+    pub format_version: u32,
+    /// Version of this crate that wrote the bundle
+    pub crate_version: String,
+    /// When the bundle was written (RFC 3339)
+    pub created_at: String,
+    /// SHA-256 of each bundled file's bytes, by file name
+    pub files: BTreeMap<String, String>,
+    /// [`ElectionConfiguration::content_hash`] of the bundled configuration
+    pub config_hash: String,
+    /// [`ElectionData::content_hash`] of the bundled data
+    pub data_hash: String,
+}
+
+/// Signature over a bundle's `manifest.json`
+///
+/// The manifest hashes every other bundled file, so signing its exact bytes
+/// covers the whole bundle. Signing and checking signatures requires the
+/// `sign` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleSignature {
+    /// Signature scheme of the key
+    pub scheme: SignatureScheme,
+    /// Public key of the signer, 0x-prefixed hex
+    pub public_key: String,
+    /// Signature over the manifest bytes, 0x-prefixed hex
+    pub signature: String,
+    /// When the bundle was signed (RFC 3339)
+    pub signed_at: String,
+}
+
+/// Key type used to sign a bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    /// Schnorrkel/Ristretto, the default for Substrate accounts
+    Sr25519,
+    /// Edwards curve signatures
+    Ed25519,
+}
+
+impl std::str::FromStr for SignatureScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sr25519" => Ok(SignatureScheme::Sr25519),
+            "ed25519" => Ok(SignatureScheme::Ed25519),
+            _ => Err(format!("Unknown signature scheme: {} (expected sr25519 or ed25519)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for SignatureScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureScheme::Sr25519 => write!(f, "sr25519"),
+            SignatureScheme::Ed25519 => write!(f, "ed25519"),
+        }
+    }
+}
+
+/// A bundle read back from disk
+#[derive(Debug, Clone)]
+pub struct ResultBundle {
+    /// Directory the bundle was read from
+    pub path: PathBuf,
+    /// Bundle manifest
+    pub manifest: BundleManifest,
+    /// Bundled result
+    pub result: ElectionResult,
+    /// Bundled post-override election data
+    pub data: ElectionData,
+    /// Bundled configuration
+    pub config: ElectionConfiguration,
+    /// Signature over the manifest, if the bundle is signed
+    pub signature: Option<BundleSignature>,
+    /// SHA-256 of each bundled file as read
+    file_hashes: BTreeMap<String, String>,
+    /// Manifest exactly as read, which is what signatures cover
+    #[cfg_attr(not(feature = "sign"), allow(dead_code))]
+    pub(crate) manifest_bytes: Vec<u8>,
+}
+
+/// Outcome of [`ResultBundle::verify`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleVerification {
+    /// Files whose hash differs from the manifest, or that the manifest does not list
+    pub mismatched_files: Vec<String>,
+    /// Whether the content hashes of the bundled data and configuration match the manifest
+    pub content_hashes_match: bool,
+    /// How the re-computed result differs from the bundled one
+    pub result_differences: Vec<String>,
+    /// Whether the signature is valid; `None` if the bundle is unsigned or
+    /// the crate was built without the `sign` feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_valid: Option<bool>,
+}
+
+impl BundleVerification {
+    /// Whether the files are intact and the election reproduces the bundled result
+    pub fn is_valid(&self) -> bool {
+        self.mismatched_files.is_empty()
+            && self.content_hashes_match
+            && self.result_differences.is_empty()
+            && self.signature_valid != Some(false)
+    }
+}
+
+impl ElectionResult {
+    /// Write a provenance bundle to the directory `path`
+    ///
+    /// `config` and `data` must be what the result was computed from. The
+    /// bundle holds the result, the data after validation, overrides and
+    /// candidate rules (see [`ElectionEngine::resolve_input`]), the matching
+    /// configuration and a manifest of hashes. The directory is created if
+    /// needed and existing bundle files in it are replaced.
+    pub fn export_bundle(
+        &self,
+        path: impl AsRef<Path>,
+        config: &ElectionConfiguration,
+        data: &ElectionData,
+    ) -> Result<BundleManifest, ElectionError> {
+        let path = path.as_ref();
+        let (resolved_config, resolved_data) = ElectionEngine::new().resolve_input(config, data)?;
+        std::fs::create_dir_all(path).map_err(|e| ElectionError::FileError {
+            message: format!("Failed to create bundle directory: {}", e),
+            path: path.to_path_buf(),
+            source: Some(ErrorSource::new(e)),
+        })?;
+
+        let mut files = BTreeMap::new();
+        files.insert(RESULT_FILE.to_string(), write_json(path, RESULT_FILE, self)?);
+        files.insert(DATA_FILE.to_string(), write_json(path, DATA_FILE, &resolved_data)?);
+        files.insert(CONFIG_FILE.to_string(), write_json(path, CONFIG_FILE, &resolved_config)?);
+
+        let manifest = BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            files,
+            config_hash: resolved_config.content_hash(),
+            data_hash: resolved_data.content_hash(),
+        };
+        write_json(path, MANIFEST_FILE, &manifest)?;
+        Ok(manifest)
+    }
+}
+
+impl ResultBundle {
+    /// Read a bundle written by [`ElectionResult::export_bundle`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ElectionError> {
+        let path = path.as_ref();
+        let manifest_bytes = read_file(path, MANIFEST_FILE)?;
+        let manifest: BundleManifest = parse_json(path, MANIFEST_FILE, &manifest_bytes)?;
+        if manifest.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(ElectionError::SchemaMismatch {
+                message: format!("Unsupported bundle format version in {:?}", path.join(MANIFEST_FILE)),
+                expected: BUNDLE_FORMAT_VERSION.to_string(),
+                found: manifest.format_version.to_string(),
+            });
+        }
+
+        let mut file_hashes = BTreeMap::new();
+        let (result, hash) = read_json(path, RESULT_FILE)?;
+        file_hashes.insert(RESULT_FILE.to_string(), hash);
+        let (data, hash) = read_json(path, DATA_FILE)?;
+        file_hashes.insert(DATA_FILE.to_string(), hash);
+        let (config, hash) = read_json(path, CONFIG_FILE)?;
+        file_hashes.insert(CONFIG_FILE.to_string(), hash);
+        let signature = match path.join(SIGNATURE_FILE).exists() {
+            true => Some(read_json(path, SIGNATURE_FILE)?.0),
+            false => None,
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            manifest,
+            result,
+            data,
+            config,
+            signature,
+            file_hashes,
+            manifest_bytes,
+        })
+    }
+
+    /// Check the bundle's hashes and re-run the election it records
+    ///
+    /// Returns an error only if the election cannot be run at all; hash
+    /// mismatches and differing results are reported in the verification.
+    pub fn verify(&self) -> Result<BundleVerification, ElectionError> {
+        let mut verification = BundleVerification {
+            content_hashes_match: self.manifest.config_hash == self.config.content_hash()
+                && self.manifest.data_hash == self.data.content_hash(),
+            ..Default::default()
+        };
+        for (file, hash) in &self.file_hashes {
+            if self.manifest.files.get(file) != Some(hash) {
+                verification.mismatched_files.push(file.clone());
+            }
+        }
+
+        #[cfg(feature = "sign")]
+        {
+            verification.signature_valid = self.signature_valid();
+        }
+
+        let rerun = ElectionEngine::new().execute(&self.config, &self.data)?;
+        verification.result_differences = result_differences(&self.result, &rerun);
+        Ok(verification)
+    }
+}
+
+/// Differences in winners, backings and allocations between two results
+fn result_differences(bundled: &ElectionResult, rerun: &ElectionResult) -> Vec<String> {
+    let mut differences = Vec::new();
+    let winners = |result: &ElectionResult| -> BTreeMap<String, u128> {
+        result
+            .selected_validators
+            .iter()
+            .map(|v| (v.account_id.clone(), v.total_backing_stake))
+            .collect()
+    };
+    let (expected, actual) = (winners(bundled), winners(rerun));
+    for (account_id, backing) in &expected {
+        match actual.get(account_id) {
+            None => differences.push(format!("{} is elected in the bundle but not when re-run", account_id)),
+            Some(rerun_backing) if rerun_backing != backing => differences.push(format!(
+                "{} is backed by {} in the bundle but {} when re-run",
+                account_id, backing, rerun_backing
+            )),
+            Some(_) => {}
+        }
+    }
+    for account_id in actual.keys().filter(|account_id| !expected.contains_key(*account_id)) {
+        differences.push(format!("{} is elected when re-run but not in the bundle", account_id));
+    }
+
+    let allocations = |result: &ElectionResult| -> BTreeMap<(String, String), u128> {
+        let mut allocations = BTreeMap::new();
+        for allocation in &result.stake_distribution {
+            *allocations
+                .entry((allocation.nominator_id.clone(), allocation.validator_id.clone()))
+                .or_insert(0u128) += allocation.amount;
+        }
+        allocations
+    };
+    let (expected, actual) = (allocations(bundled), allocations(rerun));
+    let changed = expected.iter().filter(|(edge, amount)| actual.get(*edge) != Some(*amount)).count();
+    if changed > 0 || expected.len() != actual.len() {
+        differences.push(format!(
+            "Stake distribution differs ({} allocations in the bundle, {} when re-run, {} changed)",
+            bundled.stake_distribution.len(),
+            rerun.stake_distribution.len(),
+            changed
+        ));
+    }
+    if bundled.total_stake != rerun.total_stake {
+        differences.push(format!(
+            "Total stake is {} in the bundle but {} when re-run",
+            bundled.total_stake, rerun.total_stake
+        ));
+    }
+    differences
+}
+
+/// Write `value` as pretty JSON to `dir/file`, returning the SHA-256 of the bytes written
+fn write_json<T: Serialize>(dir: &Path, file: &str, value: &T) -> Result<String, ElectionError> {
+    let path = dir.join(file);
+    let bytes = serde_json::to_vec_pretty(value).map_err(|e| ElectionError::InvalidData {
+        message: format!("Failed to serialize {}: {}", file, e),
+        source: Some(ErrorSource::new(e)),
+    })?;
+    std::fs::write(&path, &bytes).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to write bundle file: {}", e),
+        path: path.clone(),
+        source: Some(ErrorSource::new(e)),
+    })?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+/// Read `dir/file` as JSON, returning the value and the SHA-256 of the bytes read
+fn read_json<T: DeserializeOwned>(dir: &Path, file: &str) -> Result<(T, String), ElectionError> {
+    let bytes = read_file(dir, file)?;
+    let value = parse_json(dir, file, &bytes)?;
+    Ok((value, hex::encode(Sha256::digest(&bytes))))
+}
+
+/// Read the bytes of `dir/file`
+fn read_file(dir: &Path, file: &str) -> Result<Vec<u8>, ElectionError> {
+    let path = dir.join(file);
+    std::fs::read(&path).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to read bundle file: {}", e),
+        path,
+        source: Some(ErrorSource::new(e)),
+    })
+}
+
+/// Parse the bytes of `dir/file` as JSON
+fn parse_json<T: DeserializeOwned>(dir: &Path, file: &str, bytes: &[u8]) -> Result<T, ElectionError> {
+    serde_json::from_slice(bytes).map_err(|e| ElectionError::FileError {
+        message: format!("Failed to parse bundle file: {}", e),
+        path: dir.join(file),
+        source: Some(ErrorSource::new(e)),
+    })
+}
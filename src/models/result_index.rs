@@ -0,0 +1,196 @@
+//! Paginated and indexed access to election results
+//!
+//! Results for large chains carry hundreds of thousands of stake allocations.
+//! [`IndexedElectionResult`] builds lookup tables once so that per-validator
+//! and per-nominator queries no longer scan the whole `stake_distribution`.
+
+use crate::models::election_result::{
+    BackingBreakdown, ElectionResult, NominatorAllocations, SelectedValidator, StakeAllocation,
+};
+use crate::models::nominator::Nominator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// `a / b` rounded up; `usize::div_ceil` needs a newer compiler than the MSRV
+pub(crate) fn div_ceil(a: usize, b: usize) -> usize {
+    a / b + usize::from(a % b != 0)
+}
+
+/// One page of a larger list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Items on this page
+    pub items: Vec<T>,
+    /// Zero-based page number
+    pub page: usize,
+    /// Maximum number of items per page
+    pub page_size: usize,
+    /// Number of items across all pages
+    pub total_items: usize,
+    /// Number of pages
+    pub total_pages: usize,
+}
+
+impl<T> Page<T> {
+    /// Slice `page` (zero-based) out of `items`
+    ///
+    /// A page past the end is empty; a zero page size is treated as one.
+    pub fn from_slice(items: &[T], page: usize, page_size: usize) -> Page<&T> {
+        let page_size = page_size.max(1);
+        let start = page.saturating_mul(page_size).min(items.len());
+        let end = start.saturating_add(page_size).min(items.len());
+        Page {
+            items: items[start..end].iter().collect(),
+            page,
+            page_size,
+            total_items: items.len(),
+            total_pages: div_ceil(items.len(), page_size),
+        }
+    }
+
+    /// Convert the items on this page
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            page: self.page,
+            page_size: self.page_size,
+            total_items: self.total_items,
+            total_pages: self.total_pages,
+        }
+    }
+
+    /// Whether there is a page after this one
+    pub fn has_next(&self) -> bool {
+        self.page + 1 < self.total_pages
+    }
+}
+
+/// Election result with lookup tables over winners and stake allocations
+///
+/// The result is owned and only exposed immutably, so the index cannot go stale.
+#[derive(Debug, Clone)]
+pub struct IndexedElectionResult {
+    result: ElectionResult,
+    winner_positions: HashMap<String, usize>,
+    allocations_by_validator: HashMap<String, Vec<usize>>,
+    allocations_by_nominator: HashMap<String, Vec<usize>>,
+}
+
+impl IndexedElectionResult {
+    /// Index a result in a single pass over its winners and allocations
+    pub fn new(result: ElectionResult) -> Self {
+        let winner_positions = result
+            .selected_validators
+            .iter()
+            .enumerate()
+            .map(|(position, v)| (v.account_id.clone(), position))
+            .collect();
+
+        let mut allocations_by_validator: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut allocations_by_nominator: HashMap<String, Vec<usize>> = HashMap::new();
+        for (position, allocation) in result.stake_distribution.iter().enumerate() {
+            allocations_by_validator
+                .entry(allocation.validator_id.clone())
+                .or_default()
+                .push(position);
+            allocations_by_nominator
+                .entry(allocation.nominator_id.clone())
+                .or_default()
+                .push(position);
+        }
+
+        Self {
+            result,
+            winner_positions,
+            allocations_by_validator,
+            allocations_by_nominator,
+        }
+    }
+
+    /// The underlying result
+    pub fn result(&self) -> &ElectionResult {
+        &self.result
+    }
+
+    /// Take back the underlying result
+    pub fn into_inner(self) -> ElectionResult {
+        self.result
+    }
+
+    /// Look up a winner by account ID
+    pub fn winner(&self, validator_id: &str) -> Option<&SelectedValidator> {
+        self.winner_positions
+            .get(validator_id)
+            .map(|&position| &self.result.selected_validators[position])
+    }
+
+    /// A page of winners in active set order
+    pub fn winners_page(&self, page: usize, page_size: usize) -> Page<&SelectedValidator> {
+        self.result.winners_page(page, page_size)
+    }
+
+    /// Stake allocations backing a validator
+    pub fn allocations_for_validator(&self, validator_id: &str) -> Vec<&StakeAllocation> {
+        self.allocations_at(self.allocations_by_validator.get(validator_id))
+    }
+
+    /// Stake allocations made by a nominator
+    pub fn allocations_for_nominator(&self, nominator_id: &str) -> Vec<&StakeAllocation> {
+        self.allocations_at(self.allocations_by_nominator.get(nominator_id))
+    }
+
+    /// A page of the stake allocations backing a validator
+    pub fn validator_allocations_page(
+        &self,
+        validator_id: &str,
+        page: usize,
+        page_size: usize,
+    ) -> Page<&StakeAllocation> {
+        let positions = self
+            .allocations_by_validator
+            .get(validator_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+        Page::from_slice(positions, page, page_size)
+            .map(|&position| &self.result.stake_distribution[position])
+    }
+
+    /// Break down the backing of a validator by backer
+    pub fn backing_of(&self, validator_id: &str) -> BackingBreakdown {
+        BackingBreakdown::from_allocations(validator_id, self.allocations_for_validator(validator_id))
+    }
+
+    /// Show where a nominator's stake went
+    pub fn allocations_of(&self, nominator: &Nominator) -> NominatorAllocations {
+        NominatorAllocations::from_allocations(
+            nominator,
+            self.allocations_for_nominator(&nominator.account_id),
+            |id| self.winner_positions.contains_key(id),
+        )
+    }
+
+    /// Total stake allocated to a validator
+    pub fn total_stake_for_validator(&self, validator_id: &str) -> u128 {
+        self.allocations_for_validator(validator_id)
+            .iter()
+            .map(|allocation| allocation.amount)
+            .sum()
+    }
+
+    fn allocations_at(&self, positions: Option<&Vec<usize>>) -> Vec<&StakeAllocation> {
+        positions
+            .map(|positions| {
+                positions
+                    .iter()
+                    .map(|&position| &self.result.stake_distribution[position])
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl From<ElectionResult> for IndexedElectionResult {
+    fn from(result: ElectionResult) -> Self {
+        Self::new(result)
+    }
+}
@@ -0,0 +1,175 @@
+//! Rounding of derived floating point values in results and reports
+//!
+//! Stakes are integers, but some exported values are derived ratios, such as
+//! each allocation's `proportion` or the staking rate of an era payout. Their
+//! last digits depend on the order of floating point operations, so identical
+//! elections can serialize differently across platforms and builds and show up
+//! as spurious diffs in golden files. A [`RoundingPolicy`] fixes how many
+//! digits are kept.
+//!
+//! Rounding works on the shortest decimal representation of a value, the one
+//! `Display` prints, so `0.3` truncates to `0.3` and `2.675` rounds half-even
+//! to `2.68` at two decimals, as they read. The result is parsed back into the
+//! nearest `f64`, which is exact and platform independent.
+
+use serde::{Deserialize, Serialize};
+
+/// How derived `f64` values are rounded before they are exported or displayed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RoundingPolicy {
+    /// Keep values as computed
+    #[default]
+    Exact,
+    /// Drop digits after this many decimals, rounding toward zero
+    Truncate(u8),
+    /// Round to this many decimals, ties to the even digit
+    RoundHalfEven(u8),
+    /// Round to this many significant digits, ties to the even digit
+    SignificantDigits(u8),
+}
+
+impl RoundingPolicy {
+    /// Round `value` according to the policy
+    ///
+    /// NaN and infinities are returned as they are; a negative value rounded to
+    /// zero becomes positive zero.
+    pub fn apply(&self, value: f64) -> f64 {
+        if !value.is_finite() {
+            return value;
+        }
+        let rounded = match *self {
+            Self::Exact => return value,
+            Self::Truncate(decimals) => round_decimal(value, Precision::Decimals(decimals), false),
+            Self::RoundHalfEven(decimals) => round_decimal(value, Precision::Decimals(decimals), true),
+            Self::SignificantDigits(digits) => round_decimal(value, Precision::Significant(digits), true),
+        };
+        if rounded == 0.0 {
+            0.0
+        } else {
+            rounded
+        }
+    }
+
+    /// Round `value` and format it for display
+    ///
+    /// Decimal policies always print their number of decimals, so columns
+    /// line up; `Exact` and `SignificantDigits` print the shortest form.
+    pub fn format(&self, value: f64) -> String {
+        let rounded = self.apply(value);
+        match *self {
+            Self::Truncate(decimals) | Self::RoundHalfEven(decimals) if rounded.is_finite() => {
+                format!("{:.*}", decimals as usize, rounded)
+            }
+            _ => rounded.to_string(),
+        }
+    }
+
+    /// Whether the policy leaves values unchanged
+    pub fn is_exact(&self) -> bool {
+        *self == Self::Exact
+    }
+}
+
+impl std::fmt::Display for RoundingPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact => write!(f, "exact"),
+            Self::Truncate(decimals) => write!(f, "truncate:{}", decimals),
+            Self::RoundHalfEven(decimals) => write!(f, "round-half-even:{}", decimals),
+            Self::SignificantDigits(digits) => write!(f, "significant-digits:{}", digits),
+        }
+    }
+}
+
+impl std::str::FromStr for RoundingPolicy {
+    type Err = String;
+
+    /// Parse "exact", "truncate:N", "round-half-even:N" or "significant-digits:N"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if s == "exact" {
+            return Ok(Self::Exact);
+        }
+        let expected = "expected exact, truncate:N, round-half-even:N or significant-digits:N";
+        let (mode, count) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid rounding policy '{}': {}", s, expected))?;
+        let count: u8 = count
+            .trim()
+            .parse()
+            .map_err(|e| format!("Invalid digit count in rounding policy '{}': {}", s, e))?;
+        match mode.trim() {
+            "truncate" => Ok(Self::Truncate(count)),
+            "round-half-even" | "half-even" => Ok(Self::RoundHalfEven(count)),
+            "significant-digits" | "significant" if count > 0 => Ok(Self::SignificantDigits(count)),
+            "significant-digits" | "significant" => {
+                Err(format!("Invalid rounding policy '{}': keep at least one significant digit", s))
+            }
+            _ => Err(format!("Invalid rounding policy '{}': {}", s, expected)),
+        }
+    }
+}
+
+/// Where the kept digits end
+#[derive(Clone, Copy)]
+enum Precision {
+    Decimals(u8),
+    Significant(u8),
+}
+
+/// Round the decimal digits of a finite `value`, half-even or toward zero
+fn round_decimal(value: f64, precision: Precision, half_even: bool) -> f64 {
+    // `Display` prints the shortest representation that parses back to the
+    // same value, without an exponent
+    let text = value.abs().to_string();
+    let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    let mut digits: Vec<u8> = integer.bytes().chain(fraction.bytes()).map(|b| b - b'0').collect();
+    let mut point = integer.len();
+
+    let keep = match precision {
+        Precision::Decimals(decimals) => point + decimals as usize,
+        Precision::Significant(count) => match digits.iter().position(|&d| d != 0) {
+            Some(first) => first + count as usize,
+            None => return value,
+        },
+    };
+    if keep >= digits.len() {
+        return value;
+    }
+
+    let dropped = digits.split_off(keep);
+    let round_up = half_even
+        && match dropped[0].cmp(&5) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => {
+                dropped[1..].iter().any(|&d| d != 0) || digits.last().is_some_and(|&d| d % 2 == 1)
+            }
+        };
+    if round_up {
+        let carried = digits.iter_mut().rev().try_for_each(|digit| {
+            if *digit == 9 {
+                *digit = 0;
+                Ok(())
+            } else {
+                *digit += 1;
+                Err(())
+            }
+        });
+        if carried.is_ok() {
+            digits.insert(0, 1);
+            point += 1;
+        }
+    }
+
+    let text: String = digits.iter().map(|&d| char::from(b'0' + d)).collect();
+    let rounded: f64 = if digits.len() > point {
+        format!("{}.{}", &text[..point], &text[point..]).parse()
+    } else {
+        // Significant digits left of the point: pad with zeros
+        format!("{}{}", text, "0".repeat(point - digits.len())).parse()
+    }
+    .unwrap_or(value.abs());
+    rounded.copysign(value)
+}
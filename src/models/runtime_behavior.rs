@@ -0,0 +1,327 @@
+//! Election rules of past runtimes
+//!
+//! The rules around an election changed across runtime upgrades: how many
+//! targets a nominator may vote for, how many voters make it into the
+//! election snapshot, how far the unsigned miner balances its solution. A
+//! backtest of an old era should run under the rules that were active then,
+//! not today's. Each change is recorded as a dated [`BehaviorFlag`], like a
+//! browser's compatibility flags, and [`RuntimeBehavior::for_runtime`] picks
+//! the flags in force at a chain's `spec_version`.
+
+use crate::models::election_config::{BalancingSettings, ElectionConfiguration};
+use crate::models::election_data::ElectionData;
+use serde::{Deserialize, Serialize};
+
+/// Runtime an election is replayed under
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeVersion {
+    /// Chain name, e.g. "polkadot" or "kusama"
+    pub chain: String,
+    /// Runtime `spec_version`
+    pub spec_version: u32,
+}
+
+impl RuntimeVersion {
+    /// Runtime `spec_version` of `chain`
+    pub fn new(chain: impl Into<String>, spec_version: u32) -> Self {
+        Self {
+            chain: chain.into(),
+            spec_version,
+        }
+    }
+
+    /// Whether [`BEHAVIOR_FLAGS`] records any rules for the chain
+    pub fn is_recorded(&self) -> bool {
+        let chain = self.normalized_chain();
+        BEHAVIOR_FLAGS.iter().any(|flag| flag.chain == chain)
+    }
+
+    fn normalized_chain(&self) -> String {
+        self.chain.trim().to_lowercase()
+    }
+}
+
+/// One election rule a runtime sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuntimeRule {
+    /// Targets each nominator may vote for (`MaxNominations`); later targets are dropped
+    MaxNominations(u32),
+    /// Voters in the election snapshot (`MaxElectingVoters`), validators' self-votes included
+    MaxElectingVoters(u32),
+    /// Voters are read from bags-list, whose bags start at this existential weight
+    VoterBags(u64),
+    /// Balancing rounds the unsigned miner runs on its solution (`MinerMaxIterations`)
+    BalancingIterations(usize),
+}
+
+impl RuntimeRule {
+    /// Whether two rules set the same parameter
+    fn same_parameter(&self, other: &RuntimeRule) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// A rule a chain's runtimes apply from `since_spec_version` on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BehaviorFlag {
+    /// Stable name, as listed in `execution_metadata.behavior_flags`
+    pub name: &'static str,
+    /// Chain whose runtimes apply the rule
+    pub chain: &'static str,
+    /// First `spec_version` applying the rule
+    pub since_spec_version: u32,
+    /// The rule
+    pub rule: RuntimeRule,
+}
+
+/// Known rule changes, oldest first per chain
+///
+/// A later flag setting the same parameter replaces an earlier one.
+pub const BEHAVIOR_FLAGS: &[BehaviorFlag] = &[
+    // `MAX_NOMINATIONS` of pallet-staking, sized by `NposCompactSolution16`
+    // since Polkadot's genesis runtime
+    BehaviorFlag {
+        name: "polkadot-max-nominations-16",
+        chain: "polkadot",
+        since_spec_version: 0,
+        rule: RuntimeRule::MaxNominations(16),
+    },
+    // `MinerMaxIterations = 10`, set with the multi-phase election provider
+    // in release v0.9.5 (spec 9050)
+    BehaviorFlag {
+        name: "polkadot-miner-balancing-10",
+        chain: "polkadot",
+        since_spec_version: 9050,
+        rule: RuntimeRule::BalancingIterations(10),
+    },
+    // `MaxElectingVoters = 22_500` in release v0.9.19 (spec 9190)
+    BehaviorFlag {
+        name: "polkadot-max-electing-voters-22500",
+        chain: "polkadot",
+        since_spec_version: 9190,
+        rule: RuntimeRule::MaxElectingVoters(22_500),
+    },
+    // Bags of `runtime/polkadot/src/bag_thresholds.rs`: 200 bags from an
+    // `EXISTENTIAL_WEIGHT` of 10_000_000_000. Voters were listed by bag before
+    // spec 9190, but their order only matters once the snapshot is capped
+    BehaviorFlag {
+        name: "polkadot-voter-bags",
+        chain: "polkadot",
+        since_spec_version: 9190,
+        rule: RuntimeRule::VoterBags(10_000_000_000),
+    },
+    // Kusama's genesis runtime sized `NposCompactSolution16` like Polkadot's
+    BehaviorFlag {
+        name: "kusama-max-nominations-16",
+        chain: "kusama",
+        since_spec_version: 0,
+        rule: RuntimeRule::MaxNominations(16),
+    },
+    // `MinerMaxIterations = 10`, as on Polkadot (spec 9050)
+    BehaviorFlag {
+        name: "kusama-miner-balancing-10",
+        chain: "kusama",
+        since_spec_version: 9050,
+        rule: RuntimeRule::BalancingIterations(10),
+    },
+    // `NposCompactSolution24` raised `MAX_NOMINATIONS` to 24 in release
+    // v0.9.8 (spec 9080)
+    BehaviorFlag {
+        name: "kusama-max-nominations-24",
+        chain: "kusama",
+        since_spec_version: 9080,
+        rule: RuntimeRule::MaxNominations(24),
+    },
+    // `MaxElectingVoters = 12_500` in release v0.9.19 (spec 9190)
+    BehaviorFlag {
+        name: "kusama-max-electing-voters-12500",
+        chain: "kusama",
+        since_spec_version: 9190,
+        rule: RuntimeRule::MaxElectingVoters(12_500),
+    },
+    // Bags of `runtime/kusama/src/bag_thresholds.rs`: 200 bags from an
+    // `EXISTENTIAL_WEIGHT` of 33_333_333
+    BehaviorFlag {
+        name: "kusama-voter-bags",
+        chain: "kusama",
+        since_spec_version: 9190,
+        rule: RuntimeRule::VoterBags(33_333_333),
+    },
+];
+
+/// The rules in force at one runtime version
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeBehavior {
+    /// Names of the flags in force, in table order
+    pub flags: Vec<String>,
+    /// Targets each nominator may vote for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_nominations: Option<u32>,
+    /// Voters kept in the snapshot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_electing_voters: Option<u32>,
+    /// Existential weight of the bags voters are read from; voters are
+    /// ordered by exact stake if unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voter_bags: Option<u64>,
+    /// Balancing rounds run on the solution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balancing_iterations: Option<usize>,
+}
+
+impl RuntimeBehavior {
+    /// The flags of [`BEHAVIOR_FLAGS`] in force at `runtime`
+    ///
+    /// Chains without recorded flags run under no extra rules;
+    /// [`ElectionConfiguration::issues`] reports them.
+    pub fn for_runtime(runtime: &RuntimeVersion) -> Self {
+        let chain = runtime.normalized_chain();
+        let active: Vec<&BehaviorFlag> = BEHAVIOR_FLAGS
+            .iter()
+            .filter(|flag| flag.chain == chain && flag.since_spec_version <= runtime.spec_version)
+            .collect();
+
+        let mut behavior = Self::default();
+        for (index, flag) in active.iter().enumerate() {
+            // Superseded by a later flag setting the same parameter
+            if active[index + 1..].iter().any(|later| later.rule.same_parameter(&flag.rule)) {
+                continue;
+            }
+            behavior.flags.push(flag.name.to_string());
+            match flag.rule {
+                RuntimeRule::MaxNominations(max) => behavior.max_nominations = Some(max),
+                RuntimeRule::MaxElectingVoters(max) => behavior.max_electing_voters = Some(max),
+                RuntimeRule::VoterBags(existential_weight) => behavior.voter_bags = Some(existential_weight),
+                RuntimeRule::BalancingIterations(rounds) => behavior.balancing_iterations = Some(rounds),
+            }
+        }
+        behavior
+    }
+
+    /// Whether the nomination or voter limits cut anything from `data`
+    pub fn limits(&self, data: &ElectionData) -> bool {
+        let too_many_targets = self
+            .max_nominations
+            .is_some_and(|max| data.nominators.iter().any(|n| n.targets.len() > max as usize));
+        let too_many_voters = self
+            .max_electing_voters
+            .is_some_and(|max| data.nominators.len() + self_voters(data).len() > max as usize);
+        too_many_targets || too_many_voters
+    }
+
+    /// `config` balancing as the runtime's miner did, unless it sets its own balancing
+    pub fn adjusted_config(&self, config: ElectionConfiguration) -> ElectionConfiguration {
+        match self.balancing_iterations {
+            Some(rounds) if config.balancing.is_none() => ElectionConfiguration {
+                balancing: Some(BalancingSettings { max_iterations: rounds, tolerance: 0 }),
+                ..config
+            },
+            _ => config,
+        }
+    }
+
+    /// Cut `data` to the nomination and voter limits
+    ///
+    /// Nominators keep their first targets. The snapshot holds validators'
+    /// self-votes as well as nominators: a validator with stake and no
+    /// nominator entry of its own takes a voter slot. When there are more
+    /// voters than the snapshot holds, the runtime takes them in bags-list
+    /// order: by bag, heaviest first, and within a bag in list order, which is
+    /// approximated by data order, nominators before self-votes. Without
+    /// [`RuntimeRule::VoterBags`], voters are ranked by exact stake instead.
+    /// Kept nominators stay in their original order.
+    pub fn apply(&self, data: &mut ElectionData) {
+        if let Some(max) = self.max_nominations {
+            for nominator in data.nominators_mut() {
+                nominator.targets.truncate(max as usize);
+            }
+        }
+        if let Some(max) = self.max_electing_voters {
+            let max = max as usize;
+            let self_voters = self_voters(data);
+            if data.nominators.len() + self_voters.len() > max {
+                // Voter stakes in data order: nominators, then self-votes
+                let stakes: Vec<u128> = data
+                    .nominators
+                    .iter()
+                    .map(|n| n.stake)
+                    .chain(self_voters.iter().map(|c| data.candidates[*c].stake))
+                    .collect();
+                let ranks: Vec<u128> = match self.voter_bags {
+                    Some(existential_weight) => {
+                        let thresholds = bag_thresholds(existential_weight);
+                        stakes.iter().map(|stake| bag_of(&thresholds, *stake) as u128).collect()
+                    }
+                    None => stakes,
+                };
+                let mut order: Vec<usize> = (0..ranks.len()).collect();
+                order.sort_by(|a, b| ranks[*b].cmp(&ranks[*a]).then(a.cmp(b)));
+                let mut kept = vec![false; ranks.len()];
+                for index in &order[..max] {
+                    kept[*index] = true;
+                }
+                let mut index = 0;
+                data.nominators_mut().retain(|_| {
+                    index += 1;
+                    kept[index - 1]
+                });
+            }
+        }
+    }
+}
+
+/// Bags pallet-bags-list's threshold generator lays out
+const VOTER_BAG_COUNT: usize = 200;
+
+/// Indices of candidates voting for themselves outside `data.nominators`
+fn self_voters(data: &ElectionData) -> Vec<usize> {
+    let nominators: std::collections::HashSet<&str> =
+        data.nominators.iter().map(|n| n.account_id.as_str()).collect();
+    data.candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.stake > 0 && !nominators.contains(c.account_id.as_str()))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Upper bounds of the bags, as the generator lays them out: a geometric
+/// series from `existential_weight` whose last bag ends at `u64::MAX`
+///
+/// The generator uses floats; here the ratio is the smallest 64-bit
+/// fixed-point one whose series reaches `u64::MAX` at the last bag, so the
+/// bounds come out the same on every platform. Like the generator, each bound
+/// is rounded and at least one above the previous.
+fn bag_thresholds(existential_weight: u64) -> Vec<u64> {
+    let first = existential_weight.max(1);
+    let series = |fraction: u128| -> Vec<u64> {
+        std::iter::successors(Some(first), |t| {
+            let growth = ((*t as u128 * fraction + (1 << 63)) >> 64).max(1);
+            Some((*t as u128 + growth).min(u64::MAX as u128) as u64)
+        })
+        .take(VOTER_BAG_COUNT)
+        .collect()
+    };
+    // Ratio minus one, in 64-bit fixed point: below 2 for any existential weight
+    let (mut low, mut high) = (0u128, 1u128 << 64);
+    while low < high {
+        let mid = (low + high) / 2;
+        if series(mid)[VOTER_BAG_COUNT - 1] == u64::MAX {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    let mut thresholds = series(low);
+    thresholds[VOTER_BAG_COUNT - 1] = u64::MAX;
+    thresholds
+}
+
+/// Bag of a voter: the first whose upper bound its vote weight does not
+/// exceed. While issuance is below `u64::MAX` plancks the weight is the stake.
+fn bag_of(thresholds: &[u64], stake: u128) -> usize {
+    let weight = stake.min(u64::MAX as u128) as u64;
+    thresholds.partition_point(|threshold| *threshold < weight).min(thresholds.len() - 1)
+}
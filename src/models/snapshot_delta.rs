@@ -0,0 +1,212 @@
+//! Structured change sets between two election data snapshots
+
+use crate::models::election_data::ElectionData;
+use crate::models::election_overrides::{EdgeAction, ElectionOverrides};
+use crate::models::nominator::Nominator;
+use crate::models::validator::ValidatorCandidate;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Difference between two snapshots, e.g. era N and era N+1
+///
+/// Changes to accounts present in both snapshots are expressed as
+/// [`ElectionOverrides`] (stake changes, blocked flags and voting edges), and
+/// accounts that appear or disappear are listed separately. Applying the delta
+/// to the first snapshot reproduces the second; applying it to another
+/// baseline replays the same churn there.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDelta {
+    /// Stake, blocked flag and voting edge changes of existing accounts
+    #[serde(default)]
+    pub overrides: ElectionOverrides,
+    /// Candidates only present in the second snapshot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_candidates: Vec<ValidatorCandidate>,
+    /// Candidates only present in the first snapshot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_candidates: Vec<String>,
+    /// Nominators only present in the second snapshot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_nominators: Vec<Nominator>,
+    /// Nominators only present in the first snapshot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_nominators: Vec<String>,
+}
+
+impl SnapshotDelta {
+    /// Compute the changes turning `before` into `after`
+    pub fn between(before: &ElectionData, after: &ElectionData) -> Self {
+        let mut delta = Self::default();
+
+        let before_candidates: HashMap<&str, &ValidatorCandidate> =
+            before.candidates.iter().map(|c| (c.account_id.as_str(), c)).collect();
+        let after_candidates: HashSet<&str> = after.candidates.iter().map(|c| c.account_id.as_str()).collect();
+        for candidate in &after.candidates {
+            match before_candidates.get(candidate.account_id.as_str()) {
+                None => delta.added_candidates.push(candidate.clone()),
+                Some(previous) => {
+                    if previous.stake != candidate.stake {
+                        delta.overrides.candidate_stakes.insert(candidate.account_id.clone(), candidate.stake);
+                    }
+                    if previous.is_blocked() != candidate.is_blocked() {
+                        delta
+                            .overrides
+                            .candidate_blocked
+                            .insert(candidate.account_id.clone(), candidate.is_blocked());
+                    }
+                }
+            }
+        }
+        delta.removed_candidates = before
+            .candidates
+            .iter()
+            .filter(|c| !after_candidates.contains(c.account_id.as_str()))
+            .map(|c| c.account_id.clone())
+            .collect();
+
+        let before_nominators: HashMap<&str, &Nominator> =
+            before.nominators.iter().map(|n| (n.account_id.as_str(), n)).collect();
+        let after_nominators: HashSet<&str> = after.nominators.iter().map(|n| n.account_id.as_str()).collect();
+        for nominator in &after.nominators {
+            let Some(previous) = before_nominators.get(nominator.account_id.as_str()) else {
+                delta.added_nominators.push(nominator.clone());
+                continue;
+            };
+            if previous.stake != nominator.stake {
+                delta.overrides.nominator_stakes.insert(nominator.account_id.clone(), nominator.stake);
+            }
+            for target in previous.targets.iter().filter(|t| !nominator.targets.contains(t)) {
+                let _ = delta.overrides.remove_voting_edge(nominator.account_id.clone(), target.clone());
+            }
+            for target in nominator.targets.iter().filter(|t| !previous.targets.contains(t)) {
+                let _ = delta.overrides.add_voting_edge(nominator.account_id.clone(), target.clone());
+            }
+        }
+        delta.removed_nominators = before
+            .nominators
+            .iter()
+            .filter(|n| !after_nominators.contains(n.account_id.as_str()))
+            .map(|n| n.account_id.clone())
+            .collect();
+
+        delta
+    }
+
+    /// Whether the two snapshots were identical in candidates and nominators
+    pub fn is_empty(&self) -> bool {
+        self.overrides == ElectionOverrides::default()
+            && self.added_candidates.is_empty()
+            && self.removed_candidates.is_empty()
+            && self.added_nominators.is_empty()
+            && self.removed_nominators.is_empty()
+    }
+
+    /// Replay the delta onto a snapshot
+    ///
+    /// Changes to accounts the snapshot does not have are skipped, accounts
+    /// that are already present are not added again, and votes for candidates
+    /// missing from the result are dropped so the data stays valid. Returns
+    /// the number of changes that were applied.
+    pub fn apply(&self, data: &mut ElectionData) -> usize {
+        data.invalidate_caches();
+        let mut applied = 0;
+
+        let removed_candidates: HashSet<&String> = self.removed_candidates.iter().collect();
+        let removed_nominators: HashSet<&String> = self.removed_nominators.iter().collect();
+        let candidates_before = data.candidates.len();
+        let nominators_before = data.nominators.len();
+        data.candidates.retain(|c| !removed_candidates.contains(&c.account_id));
+        data.nominators.retain(|n| !removed_nominators.contains(&n.account_id));
+        data.invulnerables.retain(|id| !removed_candidates.contains(id));
+        applied += candidates_before - data.candidates.len() + nominators_before - data.nominators.len();
+
+        for candidate in &self.added_candidates {
+            if !data.candidates.iter().any(|c| c.account_id == candidate.account_id) {
+                data.candidates.push(candidate.clone());
+                applied += 1;
+            }
+        }
+        for nominator in &self.added_nominators {
+            if !data.nominators.iter().any(|n| n.account_id == nominator.account_id) {
+                data.nominators.push(nominator.clone());
+                applied += 1;
+            }
+        }
+
+        for candidate in &mut data.candidates {
+            if let Some(&stake) = self.overrides.candidate_stakes.get(&candidate.account_id) {
+                candidate.stake = stake;
+                applied += 1;
+            }
+            if let Some(&blocked) = self.overrides.candidate_blocked.get(&candidate.account_id) {
+                candidate.set_blocked(blocked);
+                applied += 1;
+            }
+        }
+        for nominator in &mut data.nominators {
+            if let Some(&stake) = self.overrides.nominator_stakes.get(&nominator.account_id) {
+                nominator.stake = stake;
+                applied += 1;
+            }
+        }
+        for edge in &self.overrides.voting_edges {
+            let Some(nominator) = data.nominators.iter_mut().find(|n| n.account_id == edge.nominator_id) else {
+                continue;
+            };
+            match edge.action {
+                EdgeAction::Add | EdgeAction::Modify => nominator.add_target(edge.candidate_id.clone()),
+                EdgeAction::Remove => nominator.remove_target(&edge.candidate_id),
+            }
+            applied += 1;
+        }
+
+        let candidate_ids: HashSet<String> = data.candidates.iter().map(|c| c.account_id.clone()).collect();
+        for nominator in &mut data.nominators {
+            nominator.targets.retain(|target| candidate_ids.contains(target));
+        }
+
+        applied
+    }
+
+    /// Total nominator stake of `data` once the delta is applied, without applying it
+    pub fn nominator_stake(&self, data: &ElectionData) -> u128 {
+        let removed: HashSet<&str> = self.removed_nominators.iter().map(String::as_str).collect();
+        let stake = |nominator: &Nominator| {
+            self.overrides.nominator_stakes.get(&nominator.account_id).copied().unwrap_or(nominator.stake)
+        };
+        let kept: u128 = data
+            .nominators
+            .iter()
+            .filter(|n| !removed.contains(n.account_id.as_str()))
+            .map(stake)
+            .sum();
+
+        let mut added_ids = HashSet::new();
+        let added: u128 = self
+            .added_nominators
+            .iter()
+            .filter(|n| {
+                let present = data.get_nominator(&n.account_id).is_some() && !removed.contains(n.account_id.as_str());
+                !present && added_ids.insert(n.account_id.as_str())
+            })
+            .map(stake)
+            .sum();
+        kept + added
+    }
+
+    /// `nominator` with the stake and vote changes of the delta applied, as
+    /// [`apply`](Self::apply) applies them, votes for missing candidates aside
+    pub(crate) fn changed_nominator(&self, nominator: &Nominator) -> Nominator {
+        let mut changed = nominator.clone();
+        if let Some(&stake) = self.overrides.nominator_stakes.get(&nominator.account_id) {
+            changed.stake = stake;
+        }
+        for edge in self.overrides.voting_edges.iter().filter(|edge| edge.nominator_id == nominator.account_id) {
+            match edge.action {
+                EdgeAction::Add | EdgeAction::Modify => changed.add_target(edge.candidate_id.clone()),
+                EdgeAction::Remove => changed.remove_target(&edge.candidate_id),
+            }
+        }
+        changed
+    }
+}
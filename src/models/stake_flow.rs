@@ -0,0 +1,319 @@
+//! Bonding and unbonding scheduled over future eras
+//!
+//! A [`StakeFlowScenario`] lists bonds and unbonds that take effect at eras
+//! after the snapshot (era 1 is the next era). Running it with
+//! [`ElectionEngine::project_stake_flows`](crate::engine::ElectionEngine::project_stake_flows)
+//! elects a validator set for every era under the flows scheduled up to it,
+//! showing when the stake movements change the active set.
+//!
+//! ```json
+//! {
+//!   "flows": [
+//!     { "era": 2, "action": "unbond", "account_id": "whale", "amount": "50000 DOT" },
+//!     { "era": 3, "action": "bond", "account_id": "newcomer", "amount": 1000, "targets": ["v1", "v2"] }
+//!   ]
+//! }
+//! ```
+
+use crate::error::ElectionError;
+use crate::models::balance::TokenUnit;
+use crate::models::election_data::ElectionData;
+use crate::models::election_overrides::json_amount;
+use crate::models::election_result::ElectionScore;
+use crate::models::nominator::Nominator;
+use crate::models::snapshot_delta::SnapshotDelta;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Direction of a stake flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StakeFlowAction {
+    /// Add to the account's stake, or bond a new nominator
+    Bond,
+    /// Take from the account's stake; a nominator left with none stops nominating
+    Unbond,
+}
+
+/// A bond or unbond taking effect at a future era
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeFlow {
+    /// Eras after the snapshot at which the flow takes effect (at least 1)
+    pub era: u32,
+    /// Bond or unbond
+    pub action: StakeFlowAction,
+    /// Candidate (self-stake) or nominator account
+    pub account_id: String,
+    /// Amount bonded or unbonded, in plancks
+    pub amount: u128,
+    /// Targets of a bonding nominator; replace the current ones if given, and
+    /// are required to bond an account not in the snapshot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+}
+
+/// Stake flows to project the validator set under
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeFlowScenario {
+    /// Scheduled flows; flows of the same era apply in list order
+    pub flows: Vec<StakeFlow>,
+}
+
+/// Validator sets elected in each era of a stake flow projection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeFlowProjection {
+    /// Era 0 (the snapshot) followed by each projected era
+    pub eras: Vec<EraProjection>,
+}
+
+/// Election in one projected era
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EraProjection {
+    /// Eras after the snapshot
+    pub era: u32,
+    /// Flows that took effect in this era
+    pub flows_applied: usize,
+    /// Stake of all nominators in this era
+    pub nominator_stake: u128,
+    /// Elected validators in result order
+    pub selected_validators: Vec<String>,
+    /// Validators elected this era but not the one before
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_added: Vec<String>,
+    /// Validators elected the era before but not this one
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_removed: Vec<String>,
+    /// Score of the election
+    pub score: ElectionScore,
+    /// Flows up to this era naming accounts that could not take them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmatched_flows: Vec<String>,
+}
+
+impl StakeFlowScenario {
+    /// Create an empty scenario
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bond `amount` more for `account_id` at `era`, nominating `targets` if given
+    pub fn bond(
+        mut self,
+        era: u32,
+        account_id: impl Into<String>,
+        amount: u128,
+        targets: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.flows.push(StakeFlow {
+            era,
+            action: StakeFlowAction::Bond,
+            account_id: account_id.into(),
+            amount,
+            targets: targets.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Unbond `amount` of `account_id`'s stake at `era`
+    pub fn unbond(mut self, era: u32, account_id: impl Into<String>, amount: u128) -> Self {
+        self.flows.push(StakeFlow {
+            era,
+            action: StakeFlowAction::Unbond,
+            account_id: account_id.into(),
+            amount,
+            targets: Vec::new(),
+        });
+        self
+    }
+
+    /// Parse a scenario file
+    ///
+    /// Amounts are plancks or amount strings like "1.5 DOT"; with a `unit`,
+    /// amounts in another token are rejected.
+    pub fn from_json_str(content: &str, unit: Option<&TokenUnit>) -> Result<Self, ElectionError> {
+        let parse_error = |e: serde_json::Error| ElectionError::ValidationError {
+            message: format!("Failed to parse stake flow scenario JSON: {}", e),
+            field: None,
+        };
+        let mut value: serde_json::Value = serde_json::from_str(content).map_err(parse_error)?;
+
+        let mut amounts = Vec::new();
+        if let Some(serde_json::Value::Array(flows)) = value.get_mut("flows") {
+            for (index, flow) in flows.iter_mut().enumerate() {
+                let field = format!("flows[{}].amount", index);
+                match flow.get_mut("amount") {
+                    Some(amount) => {
+                        amounts.push(json_amount(amount, unit, &field)?);
+                        *amount = serde_json::json!(0);
+                    }
+                    None => amounts.push(0),
+                }
+            }
+        }
+
+        let mut scenario: Self = serde_json::from_value(value).map_err(parse_error)?;
+        for (flow, amount) in scenario.flows.iter_mut().zip(amounts) {
+            flow.amount = amount;
+        }
+        Ok(scenario)
+    }
+
+    /// Last era a flow takes effect at, 0 if there are none
+    pub fn last_era(&self) -> u32 {
+        self.flows.iter().map(|flow| flow.era).max().unwrap_or(0)
+    }
+
+    /// Check every flow can be scheduled
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        for (index, flow) in self.flows.iter().enumerate() {
+            let invalid = |field: &str, message: String| ElectionError::ValidationError {
+                message,
+                field: Some(format!("flows[{}].{}", index, field)),
+            };
+            if flow.era == 0 {
+                return Err(invalid(
+                    "era",
+                    "Stake flows take effect after the snapshot: era must be at least 1".to_string(),
+                ));
+            }
+            if flow.account_id.is_empty() {
+                return Err(invalid("account_id", "Stake flow account ID cannot be empty".to_string()));
+            }
+            if flow.amount == 0 {
+                return Err(invalid("amount", format!("Stake flow for {} moves no stake", flow.account_id)));
+            }
+            if flow.action == StakeFlowAction::Unbond && !flow.targets.is_empty() {
+                return Err(invalid("targets", format!("Unbond for {} cannot name targets", flow.account_id)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of flows taking effect at exactly `era`
+    pub fn flows_at(&self, era: u32) -> usize {
+        self.flows.iter().filter(|flow| flow.era == era).count()
+    }
+
+    /// Snapshot with every flow up to and including `era` applied
+    ///
+    /// Flows apply in era order. Flows naming an account that is neither a
+    /// candidate nor a nominator (or a new account bonding without targets)
+    /// are skipped and returned as descriptions. Votes for accounts that are
+    /// not candidates are dropped, as [`SnapshotDelta::apply`] drops them.
+    pub fn data_at(&self, data: &ElectionData, era: u32) -> (ElectionData, Vec<String>) {
+        let (delta, unmatched) = self.delta_at(data, era);
+        let mut data = data.clone();
+        delta.apply(&mut data);
+        (data, unmatched)
+    }
+
+    /// Every flow up to and including `era`, as a change to `data`
+    ///
+    /// See [`data_at`](Self::data_at); a nominator unbonding all its stake
+    /// is removed, and bonding again adds it back at the end.
+    pub(crate) fn delta_at(&self, data: &ElectionData, era: u32) -> (SnapshotDelta, Vec<String>) {
+        let mut flows: Vec<&StakeFlow> = self.flows.iter().filter(|flow| flow.era <= era).collect();
+        flows.sort_by_key(|flow| flow.era);
+
+        let mut candidate_stakes: BTreeMap<&str, u128> = BTreeMap::new();
+        // Snapshot nominators as the flows leave them, and the ones they remove
+        let mut changed: BTreeMap<&str, Nominator> = BTreeMap::new();
+        let mut removed: BTreeSet<&str> = BTreeSet::new();
+        let mut added: Vec<Nominator> = Vec::new();
+        let mut unmatched = Vec::new();
+        for flow in flows {
+            let account_id = flow.account_id.as_str();
+            if let Some(candidate) = data.get_candidate(account_id) {
+                let stake = candidate_stakes.entry(account_id).or_insert(candidate.stake);
+                *stake = match flow.action {
+                    StakeFlowAction::Bond => stake.saturating_add(flow.amount),
+                    StakeFlowAction::Unbond => stake.saturating_sub(flow.amount),
+                };
+                continue;
+            }
+            let in_snapshot = data.get_nominator(account_id).filter(|_| !removed.contains(account_id));
+            let nominator = match in_snapshot {
+                Some(nominator) => Some(changed.entry(account_id).or_insert_with(|| nominator.clone())),
+                None => added.iter_mut().find(|n| n.account_id == account_id),
+            };
+            match (flow.action, nominator) {
+                (StakeFlowAction::Bond, Some(nominator)) => {
+                    nominator.stake = nominator.stake.saturating_add(flow.amount);
+                    if !flow.targets.is_empty() {
+                        nominator.targets.clear();
+                        for target in &flow.targets {
+                            nominator.add_target(target.clone());
+                        }
+                    }
+                }
+                (StakeFlowAction::Bond, None) if !flow.targets.is_empty() => {
+                    let mut nominator = Nominator::new(flow.account_id.clone(), flow.amount);
+                    for target in &flow.targets {
+                        nominator.add_target(target.clone());
+                    }
+                    added.push(nominator);
+                }
+                (StakeFlowAction::Unbond, Some(nominator)) => {
+                    nominator.stake = nominator.stake.saturating_sub(flow.amount);
+                    if nominator.stake == 0 {
+                        if in_snapshot.is_some() {
+                            changed.remove(account_id);
+                            removed.insert(account_id);
+                        } else {
+                            added.retain(|n| n.account_id != account_id);
+                        }
+                    }
+                }
+                (StakeFlowAction::Bond, None) => unmatched.push(format!(
+                    "Era {} bond for unknown account {} names no targets",
+                    flow.era, flow.account_id
+                )),
+                (StakeFlowAction::Unbond, None) => unmatched.push(format!(
+                    "Era {} unbond for unknown account {}",
+                    flow.era, flow.account_id
+                )),
+            }
+        }
+
+        let mut delta = SnapshotDelta {
+            added_nominators: added,
+            removed_nominators: removed.iter().map(|id| id.to_string()).collect(),
+            ..SnapshotDelta::default()
+        };
+        for (account_id, stake) in candidate_stakes {
+            delta.overrides.candidate_stakes.insert(account_id.to_string(), stake);
+        }
+        for (account_id, nominator) in changed {
+            let Some(before) = data.get_nominator(account_id) else {
+                continue;
+            };
+            if nominator.stake != before.stake {
+                delta.overrides.nominator_stakes.insert(account_id.to_string(), nominator.stake);
+            }
+            if nominator.targets != before.targets {
+                // Removing every old vote first leaves the new ones in their order
+                for target in &before.targets {
+                    let _ = delta.overrides.remove_voting_edge(account_id.to_string(), target.clone());
+                }
+                for target in &nominator.targets {
+                    let _ = delta.overrides.add_voting_edge(account_id.to_string(), target.clone());
+                }
+            }
+        }
+        (delta, unmatched)
+    }
+}
+
+impl StakeFlowProjection {
+    /// Eras whose elected set differs from the era before
+    pub fn set_changes(&self) -> impl Iterator<Item = &EraProjection> {
+        self.eras.iter().filter(|era| era.set_changed())
+    }
+}
+
+impl EraProjection {
+    /// Whether the elected set differs from the era before
+    pub fn set_changed(&self) -> bool {
+        !self.validators_added.is_empty() || !self.validators_removed.is_empty()
+    }
+}
@@ -0,0 +1,169 @@
+//! Stake redistribution recommendations for over-concentrated validators
+//!
+//! Nominator communities coordinate to move stake from validators backed far
+//! above the average to elected validators backed below it, which raises the
+//! minimal stake and evens out the election score. A [`StakeRedistribution`]
+//! finds the over-backed validators of a result, suggests which nominators
+//! move how much stake where, and reports the score the winners would have
+//! with the moved stake.
+
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::{ElectionResult, ElectionScore};
+use crate::models::override_impact::ScoreDelta;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Default backing above the average, in basis points, before a validator
+/// counts as over-backed
+pub const DEFAULT_TOLERANCE_BPS: u32 = 1_000;
+
+/// A validator backed more than the tolerance above the average
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverBackedValidator {
+    /// Account ID of the validator
+    pub validator_id: String,
+    /// Total backing in the result
+    pub backing: u128,
+    /// Backing above the average
+    pub excess: u128,
+}
+
+/// Stake a nominator should move from an over-backed validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeMove {
+    /// Account ID of the nominator
+    pub nominator_id: String,
+    /// Over-backed validator the stake leaves
+    pub from_validator: String,
+    /// Under-backed elected validator the stake goes to
+    pub to_validator: String,
+    /// Stake moved
+    pub amount: u128,
+    /// Whether the nominator already nominates `to_validator`, so only the
+    /// election's allocation, not its nominations, has to change
+    pub already_nominated: bool,
+}
+
+/// Recommended moves of excess backing to under-backed elected validators
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeRedistribution {
+    /// Backing above the average tolerated before a validator is over-backed,
+    /// in basis points
+    pub tolerance_bps: u32,
+    /// Average backing of the elected validators, which moves aim for
+    pub target_backing: u128,
+    /// Over-backed validators, most backed first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub over_backed: Vec<OverBackedValidator>,
+    /// Recommended moves, in the order they were chosen
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub moves: Vec<StakeMove>,
+    /// Stake moved in total
+    pub moved_stake: u128,
+    /// Score of the result
+    pub baseline_score: ElectionScore,
+    /// Score of the same winners after the moves
+    pub redistributed_score: ElectionScore,
+    /// Change in score from the moves
+    pub score_delta: ScoreDelta,
+}
+
+impl StakeRedistribution {
+    /// Recommend moves for the winners of `result`
+    ///
+    /// A validator backed more than `tolerance_bps` above the average backing
+    /// is over-backed, and its backing above the average can move. Its
+    /// nominators, largest allocation first, move stake to the least-backed
+    /// elected validator until either is at the average; validators' own
+    /// stake stays put. `data` tells which moves the nominators' existing
+    /// nominations already allow. The election is not re-run, so the
+    /// redistributed score is that of the same winners.
+    pub fn recommend(
+        result: &ElectionResult,
+        data: &ElectionData,
+        tolerance_bps: u32,
+    ) -> Self {
+        let mut backings: Vec<(&str, u128)> = result
+            .selected_validators
+            .iter()
+            .map(|v| (v.account_id.as_str(), v.total_backing_stake))
+            .collect();
+        let total = backings.iter().fold(0u128, |total, (_, backing)| total.saturating_add(*backing));
+        let target_backing = total.checked_div(backings.len() as u128).unwrap_or(0);
+        let threshold =
+            target_backing.saturating_add(target_backing.saturating_mul(tolerance_bps as u128) / 10_000);
+
+        let mut over: Vec<usize> = (0..backings.len()).filter(|&i| backings[i].1 > threshold).collect();
+        over.sort_by(|&a, &b| backings[b].1.cmp(&backings[a].1).then(a.cmp(&b)));
+        let over_backed: Vec<OverBackedValidator> = over
+            .iter()
+            .map(|&i| OverBackedValidator {
+                validator_id: backings[i].0.to_string(),
+                backing: backings[i].1,
+                excess: backings[i].1 - target_backing,
+            })
+            .collect();
+
+        let targets: HashMap<&str, HashSet<&str>> = data
+            .nominators
+            .iter()
+            .map(|n| (n.account_id.as_str(), n.targets.iter().map(String::as_str).collect()))
+            .collect();
+        let mut moves: Vec<StakeMove> = Vec::new();
+        for &from in &over {
+            let validator_id = backings[from].0;
+            let mut exposure: Vec<(&str, u128)> = result
+                .stake_distribution
+                .iter()
+                .filter(|a| a.validator_id == validator_id && a.nominator_id != validator_id && a.amount > 0)
+                .map(|a| (a.nominator_id.as_str(), a.amount))
+                .collect();
+            exposure.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+            for (nominator_id, mut remaining) in exposure {
+                while remaining > 0 && backings[from].1 > target_backing {
+                    // Least-backed winner, the higher-ranked one on ties
+                    let Some(to) = (0..backings.len())
+                        .filter(|&i| backings[i].1 < target_backing)
+                        .min_by_key(|&i| (backings[i].1, i))
+                    else {
+                        break;
+                    };
+                    let amount = remaining
+                        .min(backings[from].1 - target_backing)
+                        .min(target_backing - backings[to].1);
+                    remaining -= amount;
+                    backings[from].1 -= amount;
+                    backings[to].1 += amount;
+                    moves.push(StakeMove {
+                        nominator_id: nominator_id.to_string(),
+                        from_validator: validator_id.to_string(),
+                        to_validator: backings[to].0.to_string(),
+                        amount,
+                        already_nominated: targets
+                            .get(nominator_id)
+                            .is_some_and(|targets| targets.contains(backings[to].0)),
+                    });
+                }
+            }
+        }
+
+        let baseline_score = result.score();
+        let redistributed_score = ElectionScore::from_backings(backings.iter().map(|(_, backing)| *backing));
+        Self {
+            tolerance_bps,
+            target_backing,
+            over_backed,
+            moved_stake: moves.iter().map(|m| m.amount).sum(),
+            moves,
+            baseline_score,
+            redistributed_score,
+            score_delta: ScoreDelta::between(&baseline_score, &redistributed_score),
+        }
+    }
+
+    /// Moves that need a nominator to change its nominations
+    pub fn renominations(&self) -> impl Iterator<Item = &StakeMove> {
+        self.moves.iter().filter(|m| !m.already_nominated)
+    }
+}
@@ -0,0 +1,270 @@
+//! Sybil stress test scenarios
+//!
+//! A [`SybilScenario`] injects candidates controlled by a single attacker into
+//! a snapshot and backs them with the attacker's stake, split across
+//! nominator accounts in one of several ways. Running it for a range of
+//! attacker stakes with [`ElectionEngine::sybil_stress`](crate::engine::ElectionEngine::sybil_stress)
+//! shows how many sybils enter the active set as the attacker's stake grows.
+
+use crate::error::ElectionError;
+use crate::models::election_data::ElectionData;
+use crate::models::nominator::Nominator;
+use crate::models::snapshot_delta::SnapshotDelta;
+use crate::models::validator::ValidatorCandidate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Default prefix of injected account IDs
+pub const DEFAULT_SYBIL_PREFIX: &str = "sybil";
+/// Default precision of the minimum attack stake search, in basis points of the estimate
+pub const DEFAULT_ATTACK_TOLERANCE_BPS: u32 = 10;
+
+/// How the attacker's stake is split across nominator accounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SybilSplit {
+    /// One nominator with all the stake nominates every sybil
+    Pooled,
+    /// One nominator per sybil, each with an equal share
+    Even,
+    /// One nominator per sybil, each with half the stake of the previous one
+    Halving,
+}
+
+/// Sybil candidates and attacker stakes to test
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SybilScenario {
+    /// Number of sybil candidates injected
+    pub sybil_count: usize,
+    /// Attacker stakes to test, in plancks
+    pub attacker_stakes: Vec<u128>,
+    /// Ways of splitting the attacker's stake to test
+    pub splits: Vec<SybilSplit>,
+    /// Prefix of injected candidate and nominator account IDs
+    pub id_prefix: String,
+}
+
+/// Sybils elected for every attacker stake and split
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SybilReport {
+    /// Number of sybil candidates injected
+    pub sybil_count: usize,
+    /// Active set size of the elections
+    pub active_set_size: u32,
+    /// Nominator stake in the snapshot before injection
+    pub honest_stake: u128,
+    /// One election per split and attacker stake, in scenario order
+    pub points: Vec<SybilPoint>,
+}
+
+/// Outcome of one election with injected sybils
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SybilPoint {
+    /// How the attacker's stake was split
+    pub split: SybilSplit,
+    /// Attacker stake, in plancks
+    pub attacker_stake: u128,
+    /// Attacker share of all nominator stake, in basis points
+    pub attacker_share_bps: u32,
+    /// Number of sybils in the active set
+    pub sybils_elected: usize,
+    /// Share of the active set held by sybils, in basis points
+    pub seat_share_bps: u32,
+    /// Sybils in the active set
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub elected_sybils: Vec<String>,
+}
+
+/// Approximate minimum stake an attacker needs to win a number of seats
+///
+/// Found by searching over attacker stakes, assuming more stake never wins
+/// fewer seats. The true minimum lies between `max_failing_stake` (exclusive)
+/// and `min_stake`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttackEstimate {
+    /// Seats the attacker tries to win
+    pub seats: usize,
+    /// Assumed split of the attacker's stake across its nominators
+    pub split: SybilSplit,
+    /// Smallest stake found that wins the seats, or `None` if no stake up to `u64::MAX` does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_stake: Option<u128>,
+    /// Largest stake found that does not win the seats
+    pub max_failing_stake: u128,
+    /// Attacker share of all nominator stake at `min_stake`, in basis points
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attacker_share_bps: Option<u32>,
+    /// Nominator stake in the snapshot before injection
+    pub honest_stake: u128,
+    /// Number of elections run by the search
+    pub elections_run: usize,
+}
+
+impl SybilScenario {
+    /// Create a scenario injecting `sybil_count` candidates, testing every split
+    pub fn new(sybil_count: usize) -> Self {
+        Self {
+            sybil_count,
+            attacker_stakes: Vec::new(),
+            splits: vec![SybilSplit::Pooled, SybilSplit::Even, SybilSplit::Halving],
+            id_prefix: DEFAULT_SYBIL_PREFIX.to_string(),
+        }
+    }
+
+    /// Add an attacker stake to test
+    pub fn attacker_stake(mut self, stake: u128) -> Self {
+        self.attacker_stakes.push(stake);
+        self
+    }
+
+    /// Test only the given splits
+    pub fn splits(mut self, splits: Vec<SybilSplit>) -> Self {
+        self.splits = splits;
+        self
+    }
+
+    /// Set the prefix of injected account IDs
+    pub fn id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.id_prefix = prefix.into();
+        self
+    }
+
+    /// Check the scenario can run against `data`
+    pub fn validate(&self, data: &ElectionData) -> Result<(), ElectionError> {
+        let invalid = |message: String| ElectionError::ValidationError {
+            message,
+            field: Some("sybil_scenario".to_string()),
+        };
+        if self.sybil_count == 0 {
+            return Err(invalid("Sybil scenario needs at least one sybil candidate".to_string()));
+        }
+        if self.attacker_stakes.is_empty() || self.splits.is_empty() {
+            return Err(invalid("Sybil scenario needs at least one attacker stake and split".to_string()));
+        }
+        self.check_prefix(data)
+    }
+
+    /// Check no account in `data` uses the sybil prefix
+    pub(crate) fn check_prefix(&self, data: &ElectionData) -> Result<(), ElectionError> {
+        let prefix = format!("{}-", self.id_prefix);
+        let taken = data
+            .candidates
+            .iter()
+            .map(|c| &c.account_id)
+            .chain(data.nominators.iter().map(|n| &n.account_id))
+            .find(|id| id.starts_with(&prefix));
+        if let Some(id) = taken {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Account {} already uses the sybil prefix '{}'; choose another prefix",
+                    id, self.id_prefix
+                ),
+                field: Some("sybil_scenario".to_string()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Account ID of the `index`-th sybil candidate
+    pub fn candidate_id(&self, index: usize) -> String {
+        format!("{}-candidate-{}", self.id_prefix, index)
+    }
+
+    /// Account ID of the `index`-th attacker nominator
+    pub fn nominator_id(&self, index: usize) -> String {
+        format!("{}-nominator-{}", self.id_prefix, index)
+    }
+
+    /// Copy of `data` with the sybils and the attacker's nominators added
+    pub fn inject(&self, data: &ElectionData, attacker_stake: u128, split: SybilSplit) -> ElectionData {
+        let delta = self.delta(attacker_stake, split);
+        let mut injected = data.clone();
+        injected.candidates.extend(delta.added_candidates);
+        injected.nominators.extend(delta.added_nominators);
+        injected
+    }
+
+    /// The sybils and the attacker's nominators, as a change to a snapshot
+    pub(crate) fn delta(&self, attacker_stake: u128, split: SybilSplit) -> SnapshotDelta {
+        let sybils: Vec<String> = (0..self.sybil_count).map(|i| self.candidate_id(i)).collect();
+        let mut delta = SnapshotDelta {
+            added_candidates: sybils.iter().map(|id| ValidatorCandidate::new(id.clone(), 0)).collect(),
+            ..SnapshotDelta::default()
+        };
+
+        let mut add_nominator = |index: usize, stake: u128, targets: &[String]| {
+            let mut nominator = Nominator::new(self.nominator_id(index), stake);
+            for target in targets {
+                nominator.add_target(target.clone());
+            }
+            delta.added_nominators.push(nominator);
+        };
+        match split {
+            SybilSplit::Pooled => add_nominator(0, attacker_stake, &sybils),
+            SybilSplit::Even => {
+                let share = attacker_stake / sybils.len() as u128;
+                let remainder = attacker_stake % sybils.len() as u128;
+                for (index, sybil) in sybils.iter().enumerate() {
+                    let extra = u128::from((index as u128) < remainder);
+                    add_nominator(index, share + extra, std::slice::from_ref(sybil));
+                }
+            }
+            SybilSplit::Halving => {
+                let mut remaining = attacker_stake;
+                for (index, sybil) in sybils.iter().enumerate() {
+                    // The last nominator takes what is left so no stake is lost
+                    let stake = if index + 1 == sybils.len() { remaining } else { remaining - remaining / 2 };
+                    remaining -= stake;
+                    add_nominator(index, stake, std::slice::from_ref(sybil));
+                }
+            }
+        }
+        delta
+    }
+
+    /// Account IDs of the injected sybil candidates
+    pub(crate) fn sybil_ids(&self) -> HashSet<String> {
+        (0..self.sybil_count).map(|i| self.candidate_id(i)).collect()
+    }
+}
+
+impl SybilReport {
+    /// Points for one split, in increasing attacker stake
+    pub fn curve(&self, split: SybilSplit) -> Vec<&SybilPoint> {
+        let mut points: Vec<&SybilPoint> = self.points.iter().filter(|p| p.split == split).collect();
+        points.sort_by_key(|p| p.attacker_stake);
+        points
+    }
+
+    /// Smallest tested attacker stake that elected at least `sybils` sybils with `split`
+    pub fn min_stake_for(&self, split: SybilSplit, sybils: usize) -> Option<u128> {
+        self.points
+            .iter()
+            .filter(|p| p.split == split && p.sybils_elected >= sybils)
+            .map(|p| p.attacker_stake)
+            .min()
+    }
+}
+
+impl std::str::FromStr for SybilSplit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pooled" => Ok(SybilSplit::Pooled),
+            "even" => Ok(SybilSplit::Even),
+            "halving" => Ok(SybilSplit::Halving),
+            _ => Err(format!("Unknown sybil split: {} (expected pooled, even or halving)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for SybilSplit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SybilSplit::Pooled => write!(f, "pooled"),
+            SybilSplit::Even => write!(f, "even"),
+            SybilSplit::Halving => write!(f, "halving"),
+        }
+    }
+}
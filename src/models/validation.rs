@@ -0,0 +1,214 @@
+//! Validation strictness profiles for election data
+
+use serde::{Deserialize, Serialize};
+
+/// How strictly election data is validated before an election
+///
+/// Structural problems (no candidates, duplicate account IDs) are always
+/// errors. The profile decides whether each [`DataIssueKind`] fails the
+/// election or is reported as a warning:
+///
+/// | Issue | Strict | ChainMirroring | Permissive |
+/// |-------|--------|----------------|------------|
+/// | Zero-stake nominator | error | warning | warning |
+/// | Duplicate target | error | warning | warning |
+/// | Self-nomination | error | warning | warning |
+/// | Dangling edge | error | warning | warning |
+/// | Role conflict | error | warning | warning |
+///
+/// Duplicate targets and dangling edges that are only warnings are removed
+/// before the election runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ValidationProfile {
+    /// Every issue is an error; for auditing snapshots
+    Strict,
+    /// Tolerate what the chain tolerates; votes for accounts that are not
+    /// candidates are dropped, as the runtime drops nominations of chilled
+    /// validators when it builds the voter snapshot
+    #[default]
+    ChainMirroring,
+    /// Only structural problems are errors; for exploring hand-made data
+    Permissive,
+}
+
+/// Kind of data issue a validation profile can tolerate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DataIssueKind {
+    /// Nominator with zero stake
+    ZeroStakeNominator,
+    /// Nominator listing the same target more than once
+    DuplicateTarget,
+    /// Candidate that also nominates itself
+    SelfNomination,
+    /// Vote for an account that is not a candidate
+    DanglingEdge,
+    /// Account listed as both candidate and nominator with different stakes
+    RoleConflict,
+}
+
+/// A tolerated data issue, reported as a warning
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataIssue {
+    /// Kind of issue
+    pub kind: DataIssueKind,
+    /// Account the issue was found on
+    pub account_id: String,
+    /// Target involved, for target-related issues
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ValidationProfile {
+    /// Whether an issue of this kind fails validation under this profile
+    ///
+    /// Every kind of issue also turns up in snapshots of a live chain, so
+    /// only [`Strict`](ValidationProfile::Strict) rejects any of them.
+    pub fn is_error(&self, _kind: DataIssueKind) -> bool {
+        match self {
+            ValidationProfile::Strict => true,
+            ValidationProfile::ChainMirroring | ValidationProfile::Permissive => false,
+        }
+    }
+}
+
+impl std::str::FromStr for ValidationProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(ValidationProfile::Strict),
+            "chain-mirroring" | "chain" => Ok(ValidationProfile::ChainMirroring),
+            "permissive" => Ok(ValidationProfile::Permissive),
+            _ => Err(format!(
+                "Unknown validation profile: {} (expected strict, chain-mirroring or permissive)",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationProfile::Strict => write!(f, "strict"),
+            ValidationProfile::ChainMirroring => write!(f, "chain-mirroring"),
+            ValidationProfile::Permissive => write!(f, "permissive"),
+        }
+    }
+}
+
+/// Account IDs that appear more than once in election data
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateReport {
+    /// One entry per duplicated account and role
+    pub entries: Vec<DuplicateEntry>,
+}
+
+/// An account listed more than once
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateEntry {
+    /// Where the account is duplicated
+    pub kind: DuplicateKind,
+    /// Duplicated account ID
+    pub account_id: String,
+    /// Number of entries for the account
+    pub occurrences: usize,
+    /// Stake of each entry, in order of appearance
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stakes: Vec<u128>,
+    /// Whether the entries disagree (stakes, targets or metadata differ)
+    pub conflicting: bool,
+}
+
+/// Where an account is duplicated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateKind {
+    /// Listed more than once as a candidate
+    Candidate,
+    /// Listed more than once as a nominator
+    Nominator,
+    /// Listed more than once as an invulnerable
+    Invulnerable,
+    /// Listed as both a candidate and a nominator; `stakes` holds the
+    /// candidate stake followed by the nominator stake
+    CandidateAndNominator,
+}
+
+/// How [`ElectionData::dedup`](crate::models::election_data::ElectionData::dedup)
+/// resolves duplicated candidates and nominators
+///
+/// The resolved entry takes the position of the first occurrence. Accounts
+/// listed as both candidate and nominator are only reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DedupPolicy {
+    /// Keep the first entry
+    #[default]
+    KeepFirst,
+    /// Keep the last entry
+    KeepLast,
+    /// Keep the entry with the highest stake, the first one on ties
+    KeepHighestStake,
+    /// Add stakes together; nominators vote for the union of their targets
+    Sum,
+}
+
+impl DuplicateReport {
+    /// Whether no account is duplicated
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries whose duplicates disagree with each other
+    pub fn conflicting(&self) -> impl Iterator<Item = &DuplicateEntry> {
+        self.entries.iter().filter(|e| e.conflicting)
+    }
+
+    /// Entries of one kind
+    pub fn of_kind(&self, kind: DuplicateKind) -> impl Iterator<Item = &DuplicateEntry> {
+        self.entries.iter().filter(move |e| e.kind == kind)
+    }
+
+    /// One-line description, e.g. "3 duplicated accounts, 1 with conflicting entries"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} duplicated account{}, {} with conflicting entries",
+            self.entries.len(),
+            if self.entries.len() == 1 { "" } else { "s" },
+            self.conflicting().count()
+        )
+    }
+}
+
+impl std::str::FromStr for DedupPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keep-first" | "first" => Ok(DedupPolicy::KeepFirst),
+            "keep-last" | "last" => Ok(DedupPolicy::KeepLast),
+            "keep-highest-stake" | "highest-stake" => Ok(DedupPolicy::KeepHighestStake),
+            "sum" => Ok(DedupPolicy::Sum),
+            _ => Err(format!(
+                "Unknown dedup policy: {} (expected keep-first, keep-last, keep-highest-stake or sum)",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for DedupPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DedupPolicy::KeepFirst => write!(f, "keep-first"),
+            DedupPolicy::KeepLast => write!(f, "keep-last"),
+            DedupPolicy::KeepHighestStake => write!(f, "keep-highest-stake"),
+            DedupPolicy::Sum => write!(f, "sum"),
+        }
+    }
+}
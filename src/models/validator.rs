@@ -15,14 +15,93 @@ pub struct ValidatorCandidate {
 }
 
 /// Metadata for a validator candidate
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CandidateMetadata {
-    /// Commission rate (0-100)
+    /// Commission rate (0-100); on-chain Perbill rates are rounded to the nearest percent
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commission_rate: Option<u8>,
     /// On-chain status
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on_chain_status: Option<String>,
+    /// Whether the validator blocks new nominations (`ValidatorPrefs::blocked`)
+    ///
+    /// Existing nominations still count towards the election; only new
+    /// nominations targeting a blocked validator are rejected by the chain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<bool>,
+    /// On-chain identity display name (or sub-identity name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_display: Option<String>,
+    /// Account ID of the parent identity when this validator is a sub-identity
+    ///
+    /// Validators sharing a parent are run by the same operator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_parent: Option<String>,
+    /// Country the validator runs in (from external attributes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// City the validator runs in (from external attributes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// Hosting provider of the validator (from external attributes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hosting_provider: Option<String>,
+    /// Era points earned in recent eras (`Staking::ErasRewardPoints`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activity: Option<CandidateActivity>,
+    /// Controller account of this stash, when it differs from the stash
+    ///
+    /// Older runtimes paired each stash with a separate controller, which
+    /// historical records such as `Staking::Ledger` keys refer to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller: Option<String>,
+}
+
+/// Era points a candidate earned in recent eras
+///
+/// Validators earn points for authoring blocks and other work, so an era
+/// without points means the candidate was not in the active set, or was
+/// chilled or offline while in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandidateActivity {
+    /// Points per era, oldest era first
+    pub eras: Vec<EraActivity>,
+}
+
+/// Era points of a candidate in one era
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EraActivity {
+    /// Era index
+    pub era: u32,
+    /// Points earned in the era; 0 if the candidate did not validate
+    pub points: u32,
+}
+
+impl CandidateActivity {
+    /// Number of eras in which the candidate earned points
+    pub fn active_eras(&self) -> usize {
+        self.eras.iter().filter(|era| era.points > 0).count()
+    }
+
+    /// Most recent era in which the candidate earned points
+    pub fn last_active_era(&self) -> Option<u32> {
+        self.eras.iter().rev().find(|era| era.points > 0).map(|era| era.era)
+    }
+
+    /// Points earned over all recorded eras
+    pub fn total_points(&self) -> u64 {
+        self.eras.iter().map(|era| era.points as u64).sum()
+    }
+
+    /// Whether the candidate earned no points in its `eras` most recent
+    /// recorded eras
+    ///
+    /// Fewer recorded eras are judged on what is there; an empty record is
+    /// never inactive, since there is nothing to judge.
+    pub fn inactive_for(&self, eras: usize) -> bool {
+        let recent = &self.eras[self.eras.len().saturating_sub(eras)..];
+        !recent.is_empty() && recent.iter().all(|era| era.points == 0)
+    }
 }
 
 impl ValidatorCandidate {
@@ -47,6 +126,47 @@ impl ValidatorCandidate {
             metadata: Some(metadata),
         }
     }
+
+    /// Whether this candidate rejects new nominations
+    pub fn is_blocked(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.blocked)
+            .unwrap_or(false)
+    }
+
+    /// Era points history, if it was loaded
+    pub fn activity(&self) -> Option<&CandidateActivity> {
+        self.metadata.as_ref().and_then(|m| m.activity.as_ref())
+    }
+
+    /// Controller account, if it is known and differs from the stash
+    pub fn controller(&self) -> Option<&str> {
+        self.metadata.as_ref().and_then(|m| m.controller.as_deref())
+    }
+
+    /// Operator this candidate belongs to
+    ///
+    /// The parent identity for sub-identities, otherwise the candidate's own account.
+    pub fn operator_id(&self) -> &str {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.identity_parent.as_deref())
+            .unwrap_or(&self.account_id)
+    }
+
+    /// Set the blocked flag, creating metadata if necessary
+    pub fn set_blocked(&mut self, blocked: bool) {
+        match self.metadata {
+            Some(ref mut metadata) => metadata.blocked = Some(blocked),
+            None => {
+                self.metadata = Some(CandidateMetadata {
+                    blocked: Some(blocked),
+                    ..Default::default()
+                })
+            }
+        }
+    }
 }
 
 
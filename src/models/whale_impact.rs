@@ -0,0 +1,95 @@
+//! Whale impact model describing how the elected set depends on its largest nominators
+
+use crate::models::election_result::ElectionScore;
+use crate::models::override_impact::ScoreDelta;
+use serde::{Deserialize, Serialize};
+
+/// How nominators are removed in a whale impact experiment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WhaleRemovalMode {
+    /// Remove each of the largest nominators on its own
+    #[default]
+    Individual,
+    /// Remove the largest nominator, then the two largest, and so on
+    Cumulative,
+}
+
+/// Outcome of removing the largest nominators from an election
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhaleImpact {
+    /// How nominators were removed
+    pub mode: WhaleRemovalMode,
+    /// Score of the election with every nominator
+    pub baseline_score: ElectionScore,
+    /// Stake of all nominators in the baseline
+    pub total_nominator_stake: u128,
+    /// One re-run per removal, largest nominator first
+    pub steps: Vec<WhaleRemoval>,
+}
+
+/// Elected set change after removing one or more nominators
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhaleRemoval {
+    /// Nominators removed in this re-run
+    pub removed_nominators: Vec<String>,
+    /// Combined stake of the removed nominators
+    pub removed_stake: u128,
+    /// Share of all nominator stake removed, in basis points
+    pub removed_stake_bps: u32,
+    /// Validators elected only without the removed nominators
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_added: Vec<String>,
+    /// Validators elected only with the removed nominators
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_removed: Vec<String>,
+    /// Change in score against the baseline
+    pub score_delta: ScoreDelta,
+}
+
+impl WhaleImpact {
+    /// Removals that changed the elected set
+    pub fn set_changes(&self) -> impl Iterator<Item = &WhaleRemoval> {
+        self.steps.iter().filter(|step| step.set_changed())
+    }
+
+    /// Removal that swapped the most validators, the earliest one on ties
+    pub fn most_disruptive(&self) -> Option<&WhaleRemoval> {
+        self.set_changes()
+            .fold(None, |best: Option<&WhaleRemoval>, step| match best {
+                Some(best) if best.validators_removed.len() >= step.validators_removed.len() => Some(best),
+                _ => Some(step),
+            })
+    }
+}
+
+impl WhaleRemoval {
+    /// Whether the removal changed the elected set
+    pub fn set_changed(&self) -> bool {
+        !self.validators_added.is_empty() || !self.validators_removed.is_empty()
+    }
+}
+
+impl std::str::FromStr for WhaleRemovalMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "individual" => Ok(WhaleRemovalMode::Individual),
+            "cumulative" => Ok(WhaleRemovalMode::Cumulative),
+            _ => Err(format!(
+                "Unknown whale removal mode: {} (expected individual or cumulative)",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for WhaleRemovalMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WhaleRemovalMode::Individual => write!(f, "individual"),
+            WhaleRemovalMode::Cumulative => write!(f, "cumulative"),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Notifications for watch and serve modes
+//!
+//! Delivers [`NotificationEvent`]s to webhooks (HTTP POST) and command hooks
+//! (shell commands receiving the payload on stdin). Payloads default to the
+//! event as JSON and can be customised with a `{{field}}` template.
+
+pub mod models;
+pub mod notifier;
+
+pub use models::{NotificationConfig, NotificationEvent};
+pub use notifier::{Notifier, EVENT_ENV_VAR};
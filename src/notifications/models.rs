@@ -0,0 +1,188 @@
+//! Notification data models
+
+use crate::error::{ElectionError, ErrorSource};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Event that can trigger a notification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum NotificationEvent {
+    /// A validator is predicted to enter the active set
+    ValidatorEntering {
+        /// Account ID of the validator
+        validator_id: String,
+        /// Block at which the prediction was made
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_number: Option<u64>,
+    },
+    /// A validator is predicted to leave the active set
+    ValidatorLeaving {
+        /// Account ID of the validator
+        validator_id: String,
+        /// Block at which the prediction was made
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_number: Option<u64>,
+    },
+    /// A served election job completed
+    JobCompleted {
+        /// Election ID assigned by the server
+        election_id: String,
+        /// Number of validators selected
+        validator_count: usize,
+        /// Total stake in the result (as string to handle large numbers)
+        total_stake: String,
+        /// Execution time in milliseconds
+        #[serde(skip_serializing_if = "Option::is_none")]
+        execution_time_ms: Option<u64>,
+    },
+    /// A scheduled backtest ran its election
+    ScheduledRunCompleted {
+        /// Name of the scheduled job
+        job: String,
+        /// Block the snapshot was taken at, for chain snapshots
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_number: Option<u64>,
+        /// Number of validators selected
+        validator_count: usize,
+        /// Selected validators also in the chain's active set, if compared
+        #[serde(skip_serializing_if = "Option::is_none")]
+        matching_validators: Option<usize>,
+        /// Election ID the result is served under, if the daemon serves it
+        #[serde(skip_serializing_if = "Option::is_none")]
+        election_id: Option<String>,
+    },
+    /// A scheduled backtest failed
+    ScheduledRunFailed {
+        /// Name of the scheduled job
+        job: String,
+        /// What went wrong
+        error: String,
+    },
+}
+
+impl NotificationEvent {
+    /// Kebab-case name of the event kind
+    pub fn kind(&self) -> &'static str {
+        match self {
+            NotificationEvent::ValidatorEntering { .. } => "validator-entering",
+            NotificationEvent::ValidatorLeaving { .. } => "validator-leaving",
+            NotificationEvent::JobCompleted { .. } => "job-completed",
+            NotificationEvent::ScheduledRunCompleted { .. } => "scheduled-run-completed",
+            NotificationEvent::ScheduledRunFailed { .. } => "scheduled-run-failed",
+        }
+    }
+
+    /// Default JSON payload for this event
+    pub fn to_json(&self) -> Result<String, ElectionError> {
+        serde_json::to_string(self).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize notification event: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+
+    /// Render a payload template for this event
+    ///
+    /// Each `{{field}}` placeholder is replaced with the matching event field
+    /// (`{{event}}` is the event kind). `{{payload}}` expands to the default JSON
+    /// payload. Unknown placeholders are left untouched.
+    pub fn render(&self, template: &str) -> Result<String, ElectionError> {
+        let payload = self.to_json()?;
+        let mut rendered = template.replace("{{payload}}", &payload);
+
+        if let Ok(Value::Object(fields)) = serde_json::to_value(self) {
+            for (key, value) in fields {
+                let text = match value {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                rendered = rendered.replace(&format!("{{{{{}}}}}", key), &text);
+            }
+        }
+
+        Ok(rendered)
+    }
+}
+
+/// Configuration for notification sinks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// Webhook URLs receiving a POST per event
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<String>,
+    /// Shell commands run per event, with the payload on stdin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+    /// Payload template (default: the event as JSON)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Validators whose set changes trigger notifications (empty = all validators)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watched_validators: Vec<String>,
+}
+
+impl NotificationConfig {
+    /// Create an empty notification configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a webhook URL
+    pub fn webhook(mut self, url: impl Into<String>) -> Self {
+        self.webhooks.push(url.into());
+        self
+    }
+
+    /// Add a command hook
+    pub fn command(mut self, command: impl Into<String>) -> Self {
+        self.commands.push(command.into());
+        self
+    }
+
+    /// Set the payload template
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Only notify about set changes for this validator (can be repeated)
+    pub fn watch_validator(mut self, validator_id: impl Into<String>) -> Self {
+        self.watched_validators.push(validator_id.into());
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(self) -> Result<Self, ElectionError> {
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Validate the configuration
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        for url in &self.webhooks {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(ElectionError::ValidationError {
+                    message: format!("Webhook URL must start with http:// or https://: {}", url),
+                    field: Some("webhooks".to_string()),
+                });
+            }
+        }
+        if self.commands.iter().any(|c| c.trim().is_empty()) {
+            return Err(ElectionError::ValidationError {
+                message: "Notification command cannot be empty".to_string(),
+                field: Some("commands".to_string()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether any sink is configured
+    pub fn has_sinks(&self) -> bool {
+        !self.webhooks.is_empty() || !self.commands.is_empty()
+    }
+
+    /// Whether set changes for this validator should be notified
+    pub fn is_watched(&self, validator_id: &str) -> bool {
+        self.watched_validators.is_empty() || self.watched_validators.iter().any(|v| v == validator_id)
+    }
+}
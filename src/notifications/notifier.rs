@@ -0,0 +1,157 @@
+//! Notification delivery to webhooks and command hooks
+
+use crate::error::ElectionError;
+use crate::notifications::models::{NotificationConfig, NotificationEvent};
+use std::collections::HashSet;
+use tokio::io::AsyncWriteExt;
+
+/// Environment variable holding the event kind for command hooks
+pub const EVENT_ENV_VAR: &str = "OFFLINE_ELECTION_EVENT";
+
+/// Delivers notification events to the configured sinks
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    config: NotificationConfig,
+    client: reqwest::Client,
+}
+
+impl Notifier {
+    /// Create a notifier for a validated configuration
+    pub fn new(config: NotificationConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Notification configuration
+    pub fn config(&self) -> &NotificationConfig {
+        &self.config
+    }
+
+    /// Events for watched validators entering or leaving the set
+    ///
+    /// `current` is the set the validators were in before; `predicted` is the
+    /// set they will be in after the election.
+    pub fn set_change_events(
+        &self,
+        current: &[String],
+        predicted: &[String],
+        block_number: Option<u64>,
+    ) -> Vec<NotificationEvent> {
+        let current_set: HashSet<&String> = current.iter().collect();
+        let predicted_set: HashSet<&String> = predicted.iter().collect();
+
+        let entering = predicted
+            .iter()
+            .filter(|id| !current_set.contains(id) && self.config.is_watched(id))
+            .map(|id| NotificationEvent::ValidatorEntering {
+                validator_id: id.clone(),
+                block_number,
+            });
+        let leaving = current
+            .iter()
+            .filter(|id| !predicted_set.contains(id) && self.config.is_watched(id))
+            .map(|id| NotificationEvent::ValidatorLeaving {
+                validator_id: id.clone(),
+                block_number,
+            });
+
+        entering.chain(leaving).collect()
+    }
+
+    /// Send an event to every configured sink
+    ///
+    /// All sinks are attempted even if one fails; the returned error lists every failure.
+    pub async fn send(&self, event: &NotificationEvent) -> Result<(), ElectionError> {
+        let payload = match self.config.template {
+            Some(ref template) => event.render(template)?,
+            None => event.to_json()?,
+        };
+
+        let mut failures = Vec::new();
+        for url in &self.config.webhooks {
+            if let Err(e) = self.post_webhook(url, &payload).await {
+                failures.push(e);
+            }
+        }
+        for command in &self.config.commands {
+            if let Err(e) = self.run_command(command, event, &payload).await {
+                failures.push(e);
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ElectionError::InvalidData {
+                message: format!("Notification delivery failed: {}", failures.join("; ")),
+                source: None,
+            })
+        }
+    }
+
+    /// Send several events, reporting failures as warnings
+    pub async fn send_all(&self, events: &[NotificationEvent]) {
+        for event in events {
+            if let Err(e) = self.send(event).await {
+                eprintln!("⚠ Warning: {}", e);
+            }
+        }
+    }
+
+    async fn post_webhook(&self, url: &str, payload: &str) -> Result<(), String> {
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(payload.to_string())
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| format!("webhook {}: {}", url, e))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("webhook {} returned {}", url, response.status()))
+        }
+    }
+
+    async fn run_command(&self, command: &str, event: &NotificationEvent, payload: &str) -> Result<(), String> {
+        let mut child = shell_command(command)
+            .env(EVENT_ENV_VAR, event.kind())
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("command '{}': {}", command, e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that ignores stdin may exit before reading; that is not a failure
+            let _ = stdin.write_all(payload.as_bytes()).await;
+        }
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| format!("command '{}': {}", command, e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("command '{}' exited with {}", command, status))
+        }
+    }
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
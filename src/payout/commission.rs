@@ -0,0 +1,196 @@
+//! Commission bound proposals
+//!
+//! Governance regularly debates a minimum (or maximum) validator commission.
+//! A [`CommissionPolicy`] describes such a bound and how candidates respond
+//! to it: raise or lower their commission into the bound, or refuse and
+//! stop validating. [`ElectionEngine::commission_scenario`](crate::engine::ElectionEngine::commission_scenario)
+//! applies it, re-runs the election and payout, and reports what nominators
+//! gain or lose in a [`CommissionImpact`].
+
+use crate::error::ElectionError;
+use crate::models::election_result::signed_difference;
+use crate::payout::models::PayoutResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Which candidates outside the bound stop validating instead of complying
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommissionRefusal {
+    /// Every candidate moves its commission into the bound
+    #[default]
+    None,
+    /// Every candidate outside the bound drops out
+    OutOfBounds,
+    /// The listed candidates drop out if outside the bound; the rest comply
+    Accounts(Vec<String>),
+}
+
+/// A minimum and/or maximum commission enforced on every candidate
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CommissionPolicy {
+    /// Lowest commission allowed, in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<u8>,
+    /// Highest commission allowed, in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<u8>,
+    /// Candidates that refuse the bound
+    #[serde(default)]
+    pub refusal: CommissionRefusal,
+}
+
+/// A candidate's commission before and after the policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommissionChange {
+    /// Account ID of the candidate
+    pub validator_id: String,
+    /// Commission before, in percent
+    pub from: u8,
+    /// Commission after, in percent
+    pub to: u8,
+}
+
+/// A nominator's era reward without and with the policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NominatorRewardChange {
+    /// Account ID of the nominator
+    pub nominator_id: String,
+    /// Reward under the current commissions
+    pub baseline_reward: u128,
+    /// Reward under the policy
+    pub scenario_reward: u128,
+    /// `scenario_reward - baseline_reward`, saturating beyond the range of `i128`
+    pub change: i128,
+}
+
+/// Outcome of enforcing a [`CommissionPolicy`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommissionImpact {
+    /// The enforced policy
+    pub policy: CommissionPolicy,
+    /// Candidates that moved their commission into the bound, by account
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub adjusted: Vec<CommissionChange>,
+    /// Candidates that refused and dropped out, by account
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dropped: Vec<String>,
+    /// Validators elected only under the policy
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_added: Vec<String>,
+    /// Validators elected only without the policy
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validators_removed: Vec<String>,
+    /// Commission kept by validators under the current commissions
+    pub baseline_commission: u128,
+    /// Commission kept by validators under the policy
+    pub scenario_commission: u128,
+    /// Reward paid to nominators under the current commissions
+    pub baseline_nominator_rewards: u128,
+    /// Reward paid to nominators under the policy
+    pub scenario_nominator_rewards: u128,
+    /// Nominators whose reward changed, largest loss first
+    pub nominators: Vec<NominatorRewardChange>,
+}
+
+impl CommissionPolicy {
+    /// Create a policy without bounds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the lowest commission allowed
+    pub fn min(mut self, percent: u8) -> Self {
+        self.min = Some(percent);
+        self
+    }
+
+    /// Set the highest commission allowed
+    pub fn max(mut self, percent: u8) -> Self {
+        self.max = Some(percent);
+        self
+    }
+
+    /// Set which candidates refuse the bound
+    pub fn refusal(mut self, refusal: CommissionRefusal) -> Self {
+        self.refusal = refusal;
+        self
+    }
+
+    /// Check the policy sets a bound within 0 to 100 percent
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        let invalid = |message: String| ElectionError::ValidationError {
+            message,
+            field: Some("commission_policy".to_string()),
+        };
+        if self.min.is_none() && self.max.is_none() {
+            return Err(invalid("Commission policy needs a minimum or a maximum".to_string()));
+        }
+        if let Some(percent) = self.min.into_iter().chain(self.max).find(|percent| *percent > 100) {
+            return Err(invalid(format!("Commission bound is {}%, but commission is at most 100%", percent)));
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(invalid(format!("Minimum commission {}% is above the maximum {}%", min, max)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Commission moved into the bound
+    pub fn bounded(&self, commission: u8) -> u8 {
+        let raised = self.min.map_or(commission, |min| commission.max(min));
+        self.max.map_or(raised, |max| raised.min(max))
+    }
+
+    /// Whether a candidate with `commission` outside the bound drops out
+    pub fn refuses(&self, validator_id: &str, commission: u8) -> bool {
+        if self.bounded(commission) == commission {
+            return false;
+        }
+        match &self.refusal {
+            CommissionRefusal::None => false,
+            CommissionRefusal::OutOfBounds => true,
+            CommissionRefusal::Accounts(account_ids) => account_ids.iter().any(|id| id == validator_id),
+        }
+    }
+}
+
+impl NominatorRewardChange {
+    /// Reward changes of every nominator paid in either payout, largest loss
+    /// first, then by account
+    pub fn between(baseline: &PayoutResult, scenario: &PayoutResult) -> Vec<Self> {
+        let mut rewards: BTreeMap<&str, (u128, u128)> = BTreeMap::new();
+        for payout in &baseline.nominator_payouts {
+            rewards.entry(payout.nominator_id.as_str()).or_default().0 = payout.total_reward;
+        }
+        for payout in &scenario.nominator_payouts {
+            rewards.entry(payout.nominator_id.as_str()).or_default().1 = payout.total_reward;
+        }
+        let mut changes: Vec<Self> = rewards
+            .into_iter()
+            .filter(|(_, (baseline, scenario))| baseline != scenario)
+            .map(|(nominator_id, (baseline_reward, scenario_reward))| Self {
+                nominator_id: nominator_id.to_string(),
+                baseline_reward,
+                scenario_reward,
+                change: signed_difference(scenario_reward, baseline_reward),
+            })
+            .collect();
+        // The sort is stable, so equal changes stay in account order
+        changes.sort_by_key(|change| change.change);
+        changes
+    }
+}
+
+impl CommissionImpact {
+    /// Nominators whose reward went down
+    pub fn losers(&self) -> impl Iterator<Item = &NominatorRewardChange> {
+        self.nominators.iter().filter(|n| n.change < 0)
+    }
+
+    /// Nominators whose reward went up
+    pub fn gainers(&self) -> impl Iterator<Item = &NominatorRewardChange> {
+        self.nominators.iter().filter(|n| n.change > 0)
+    }
+}
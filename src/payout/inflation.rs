@@ -0,0 +1,174 @@
+//! NPoS inflation model for deriving era rewards
+//!
+//! Implements the staking reward curve used by the Polkadot relay chain: inflation grows
+//! linearly with the staking rate up to the ideal rate, and decays exponentially beyond it.
+//! The difference between the maximum inflation and the staking inflation goes to the treasury.
+
+use crate::error::ElectionError;
+use crate::models::rounding::RoundingPolicy;
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds in a Julian year (365.25 days)
+const MILLISECONDS_PER_YEAR: u64 = 1000 * 3600 * 24 * 36525 / 100;
+
+/// Parameters of the NPoS inflation curve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InflationParams {
+    /// Annual inflation when nothing is staked (0.0 to 1.0)
+    pub min_annual_inflation: f64,
+    /// Annual inflation at the ideal staking rate (0.0 to 1.0)
+    pub max_annual_inflation: f64,
+    /// Staking rate at which staking inflation peaks (0.0 to 1.0)
+    pub ideal_staking_rate: f64,
+    /// Exponential decay rate of inflation above the ideal staking rate
+    pub falloff: f64,
+    /// Duration of one era in milliseconds
+    pub era_duration_ms: u64,
+}
+
+/// Era payout derived from the inflation curve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EraPayout {
+    /// Reward paid to validators and nominators for the era
+    pub staking_payout: u128,
+    /// Remainder of the maximum inflation sent to the treasury
+    pub treasury_payout: u128,
+    /// Fraction of total issuance that is staked (0.0 to 1.0)
+    pub staking_rate: f64,
+    /// Annual inflation paid to stakers at this staking rate (0.0 to 1.0)
+    pub annual_staking_inflation: f64,
+}
+
+impl InflationParams {
+    /// Polkadot relay chain parameters (24 hour eras)
+    pub fn polkadot() -> Self {
+        Self {
+            min_annual_inflation: 0.025,
+            max_annual_inflation: 0.10,
+            ideal_staking_rate: 0.75,
+            falloff: 0.05,
+            era_duration_ms: 24 * 3600 * 1000,
+        }
+    }
+
+    /// Kusama parameters (6 hour eras)
+    pub fn kusama() -> Self {
+        Self {
+            era_duration_ms: 6 * 3600 * 1000,
+            ..Self::polkadot()
+        }
+    }
+
+    /// Set the ideal staking rate
+    pub fn ideal_staking_rate(mut self, rate: f64) -> Self {
+        self.ideal_staking_rate = rate;
+        self
+    }
+
+    /// Set the era duration in milliseconds
+    pub fn era_duration_ms(mut self, duration: u64) -> Self {
+        self.era_duration_ms = duration;
+        self
+    }
+
+    /// Validate the parameters
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        let in_unit_range = |value: f64| (0.0..=1.0).contains(&value);
+
+        if !in_unit_range(self.min_annual_inflation) || !in_unit_range(self.max_annual_inflation) {
+            return Err(ElectionError::ValidationError {
+                message: "Inflation rates must be between 0.0 and 1.0".to_string(),
+                field: Some("inflation".to_string()),
+            });
+        }
+
+        if self.min_annual_inflation > self.max_annual_inflation {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Minimum inflation ({}) cannot exceed maximum inflation ({})",
+                    self.min_annual_inflation, self.max_annual_inflation
+                ),
+                field: Some("inflation.min_annual_inflation".to_string()),
+            });
+        }
+
+        if self.ideal_staking_rate <= 0.0 || self.ideal_staking_rate > 1.0 {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Ideal staking rate must be in (0.0, 1.0], but got {}",
+                    self.ideal_staking_rate
+                ),
+                field: Some("inflation.ideal_staking_rate".to_string()),
+            });
+        }
+
+        if self.falloff <= 0.0 {
+            return Err(ElectionError::ValidationError {
+                message: format!("Falloff must be positive, but got {}", self.falloff),
+                field: Some("inflation.falloff".to_string()),
+            });
+        }
+
+        if self.era_duration_ms == 0 {
+            return Err(ElectionError::ValidationError {
+                message: "Era duration must be positive".to_string(),
+                field: Some("inflation.era_duration_ms".to_string()),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Annual staking inflation at the given staking rate
+    pub fn annual_staking_inflation(&self, staking_rate: f64) -> f64 {
+        let adjustment = if staking_rate <= self.ideal_staking_rate {
+            staking_rate / self.ideal_staking_rate
+        } else {
+            2f64.powf((self.ideal_staking_rate - staking_rate) / self.falloff)
+        };
+        self.min_annual_inflation
+            + (self.max_annual_inflation - self.min_annual_inflation) * adjustment
+    }
+
+    /// Compute the era payout for the given total staked amount and total issuance
+    pub fn era_payout(&self, total_staked: u128, total_issuance: u128) -> Result<EraPayout, ElectionError> {
+        self.validate()?;
+
+        if total_issuance == 0 {
+            return Err(ElectionError::ValidationError {
+                message: "Total issuance must be positive".to_string(),
+                field: Some("total_issuance".to_string()),
+            });
+        }
+
+        let staking_rate = (total_staked as f64 / total_issuance as f64).min(1.0);
+        let annual_staking_inflation = self.annual_staking_inflation(staking_rate);
+        let period_fraction = self.era_duration_ms as f64 / MILLISECONDS_PER_YEAR as f64;
+
+        let issuance = total_issuance as f64;
+        let max_payout = (period_fraction * self.max_annual_inflation * issuance) as u128;
+        let staking_payout = (period_fraction * annual_staking_inflation * issuance) as u128;
+
+        Ok(EraPayout {
+            staking_payout,
+            treasury_payout: max_payout.saturating_sub(staking_payout),
+            staking_rate,
+            annual_staking_inflation,
+        })
+    }
+}
+
+impl EraPayout {
+    /// Round the staking rate and inflation with `policy`
+    pub fn rounded(mut self, policy: &RoundingPolicy) -> Self {
+        self.staking_rate = policy.apply(self.staking_rate);
+        self.annual_staking_inflation = policy.apply(self.annual_staking_inflation);
+        self
+    }
+}
+
+impl Default for InflationParams {
+    fn default() -> Self {
+        Self::polkadot()
+    }
+}
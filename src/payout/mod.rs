@@ -0,0 +1,21 @@
+//! Era payout simulation for election results
+//!
+//! Turns an [`ElectionResult`](crate::models::election_result::ElectionResult) into
+//! per-validator and per-nominator rewards for a single era. The era reward can be
+//! provided directly or derived from total issuance via [`InflationParams`], and split
+//! between validators by era points or an experimental [`RewardCurve`].
+
+pub mod commission;
+pub mod inflation;
+pub mod models;
+pub mod reward_curve;
+pub mod simulator;
+
+pub use commission::{CommissionChange, CommissionImpact, CommissionPolicy, CommissionRefusal, NominatorRewardChange};
+pub use inflation::{EraPayout, InflationParams};
+pub use models::{
+    EraPoints, NominatorPayout, PageLimitMode, PayoutConfig, PayoutResult, RewardDestination,
+    ValidatorPayout, ValidatorRewardShare, DEFAULT_EXPOSURE_PAGE_SIZE,
+};
+pub use reward_curve::{CappedBackingCurve, EraPointsCurve, RewardCurve, RewardCurveInput, SqrtBackingCurve};
+pub use simulator::PayoutSimulator;
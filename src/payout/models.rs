@@ -0,0 +1,369 @@
+//! Payout simulation data models
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::models::election_data::ElectionData;
+use crate::payout::inflation::InflationParams;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maximum number of nominators paid per exposure page on Polkadot/Kusama
+pub const DEFAULT_EXPOSURE_PAGE_SIZE: u32 = 512;
+
+/// Where a staker's rewards are paid to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum RewardDestination {
+    /// Rewards are added to the bonded stake (compounding)
+    #[default]
+    Staked,
+    /// Rewards are paid to the stash account without being bonded
+    Stash,
+    /// Rewards are paid to an arbitrary account
+    Account(String),
+    /// Rewards are not paid out
+    None,
+}
+
+/// Era points distribution assumption
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum EraPoints {
+    /// Every elected validator earns the same number of points
+    #[default]
+    Uniform,
+    /// Explicit points per validator (validators not listed earn zero points)
+    Custom {
+        /// Era points per validator account ID
+        points: HashMap<String, u32>,
+    },
+    /// Points per validator in past eras; each validator is expected to earn
+    /// its average, and validators without history the average of those with one
+    Historical {
+        /// Era points per validator account ID, one entry per past era
+        history: HashMap<String, Vec<u32>>,
+    },
+}
+
+impl EraPoints {
+    /// Historical points from the candidates' loaded era activity
+    ///
+    /// Only eras in which a candidate earned points count: an era without
+    /// points is one it was not elected in, not one it performed badly in.
+    /// Candidates with no such era are left out, so they are expected to
+    /// perform like the average validator.
+    pub fn from_activity(data: &ElectionData) -> Self {
+        let history = data
+            .candidates
+            .iter()
+            .filter_map(|c| {
+                let points: Vec<u32> =
+                    c.activity()?.eras.iter().map(|era| era.points).filter(|&points| points > 0).collect();
+                (!points.is_empty()).then(|| (c.account_id.clone(), points))
+            })
+            .collect();
+        EraPoints::Historical { history }
+    }
+
+    /// Points each of the given validators is expected to earn in the era
+    pub fn points_for(&self, validator_ids: &[&str]) -> Vec<u32> {
+        match self {
+            EraPoints::Uniform => vec![1; validator_ids.len()],
+            EraPoints::Custom { points } => validator_ids
+                .iter()
+                .map(|id| points.get(*id).copied().unwrap_or(0))
+                .collect(),
+            EraPoints::Historical { history } => {
+                let average = |eras: &[u32]| {
+                    (!eras.is_empty()).then(|| eras.iter().map(|&p| p as u64).sum::<u64>() / eras.len() as u64)
+                };
+                let known: Vec<u64> = history.values().filter_map(|eras| average(eras)).collect();
+                // With no history at all, every validator is assumed to perform alike
+                let fallback = if known.is_empty() {
+                    1
+                } else {
+                    known.iter().sum::<u64>() / known.len() as u64
+                };
+                validator_ids
+                    .iter()
+                    .map(|id| {
+                        let points = history.get(*id).and_then(|eras| average(eras)).unwrap_or(fallback);
+                        points.min(u32::MAX as u64) as u32
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// How exposure page limits affect who gets paid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PageLimitMode {
+    /// Paged exposures: all nominators not marked `unrewarded` by the election are
+    /// paid, split over pages of `page_size`
+    #[default]
+    Paged,
+    /// Legacy clipped exposures: only the top `page_size` nominators by stake are paid,
+    /// and the share of the clipped ones is left unpaid
+    TopOnly,
+}
+
+/// Configuration for an era payout simulation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutConfig {
+    /// Total reward paid to validators and nominators for the era
+    pub era_reward: u128,
+    /// Commission rate per validator (0-100)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub commissions: HashMap<String, u8>,
+    /// Commission rate for validators not listed in `commissions` (0-100)
+    #[serde(default)]
+    pub default_commission: u8,
+    /// Validator self-stake counted as "own" exposure
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub self_stakes: HashMap<String, u128>,
+    /// Era points assumption
+    #[serde(default)]
+    pub era_points: EraPoints,
+    /// Number of nominators per exposure page
+    pub page_size: u32,
+    /// How page limits are applied
+    #[serde(default)]
+    pub page_limit_mode: PageLimitMode,
+    /// Reward destination per staker (defaults to [`RewardDestination::Staked`])
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub reward_destinations: HashMap<String, RewardDestination>,
+}
+
+impl PayoutConfig {
+    /// Create a new payout configuration for the given era reward
+    pub fn new(era_reward: u128) -> Self {
+        Self {
+            era_reward,
+            commissions: HashMap::new(),
+            default_commission: 0,
+            self_stakes: HashMap::new(),
+            era_points: EraPoints::Uniform,
+            page_size: DEFAULT_EXPOSURE_PAGE_SIZE,
+            page_limit_mode: PageLimitMode::Paged,
+            reward_destinations: HashMap::new(),
+        }
+    }
+
+    /// Create a payout configuration whose era reward is derived from the inflation curve
+    ///
+    /// `total_staked` is typically [`ElectionResult::total_stake`](crate::models::election_result::ElectionResult::total_stake).
+    pub fn from_inflation(
+        params: &InflationParams,
+        total_staked: u128,
+        total_issuance: u128,
+    ) -> Result<Self, ElectionError> {
+        let era_payout = params.era_payout(total_staked, total_issuance)?;
+        Ok(Self::new(era_payout.staking_payout))
+    }
+
+    /// Set the commission rate for a validator
+    pub fn commission(mut self, validator_id: String, commission: u8) -> Self {
+        self.commissions.insert(validator_id, commission);
+        self
+    }
+
+    /// Set the default commission rate
+    pub fn default_commission(mut self, commission: u8) -> Self {
+        self.default_commission = commission;
+        self
+    }
+
+    /// Set the era points assumption
+    pub fn era_points(mut self, era_points: EraPoints) -> Self {
+        self.era_points = era_points;
+        self
+    }
+
+    /// Set the exposure page size
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Set the page limit mode
+    pub fn page_limit_mode(mut self, mode: PageLimitMode) -> Self {
+        self.page_limit_mode = mode;
+        self
+    }
+
+    /// Set the reward destination for a staker
+    pub fn reward_destination(mut self, account_id: String, destination: RewardDestination) -> Self {
+        self.reward_destinations.insert(account_id, destination);
+        self
+    }
+
+    /// Take commissions and self-stakes from candidate data
+    ///
+    /// Commissions are read from candidate metadata when available.
+    pub fn with_candidate_data(mut self, data: &ElectionData) -> Self {
+        for candidate in &data.candidates {
+            if let Some(commission) = candidate.metadata.as_ref().and_then(|m| m.commission_rate) {
+                self.commissions.insert(candidate.account_id.clone(), commission);
+            }
+            if candidate.stake > 0 {
+                self.self_stakes.insert(candidate.account_id.clone(), candidate.stake);
+            }
+        }
+        self
+    }
+
+    /// Build and validate the configuration
+    pub fn build(self) -> Result<Self, ElectionError> {
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Validate the configuration
+    pub fn validate(&self) -> Result<(), ElectionError> {
+        if self.page_size == 0 {
+            return Err(ElectionError::ValidationError {
+                message: "Exposure page size must be positive".to_string(),
+                field: Some("page_size".to_string()),
+            });
+        }
+
+        if self.default_commission > 100 {
+            return Err(ElectionError::ValidationError {
+                message: format!(
+                    "Default commission must be between 0 and 100, but got {}",
+                    self.default_commission
+                ),
+                field: Some("default_commission".to_string()),
+            });
+        }
+
+        for (validator_id, commission) in &self.commissions {
+            if *commission > 100 {
+                return Err(ElectionError::ValidationError {
+                    message: format!(
+                        "Commission for validator '{}' must be between 0 and 100, but got {}",
+                        validator_id, commission
+                    ),
+                    field: Some("commissions".to_string()),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Commission rate for a validator
+    pub fn commission_for(&self, validator_id: &str) -> u8 {
+        self.commissions
+            .get(validator_id)
+            .copied()
+            .unwrap_or(self.default_commission)
+    }
+
+    /// Reward destination for a staker
+    pub fn destination_for(&self, account_id: &str) -> RewardDestination {
+        self.reward_destinations
+            .get(account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Outcome of an era payout simulation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutResult {
+    /// Era reward that was distributed
+    pub era_reward: u128,
+    /// Total amount paid to validators and nominators
+    pub total_paid: u128,
+    /// Amount not paid out (rounding, zero points or clipped nominators)
+    pub unpaid: u128,
+    /// Payouts per elected validator
+    pub validator_payouts: Vec<ValidatorPayout>,
+    /// Payouts per nominator
+    pub nominator_payouts: Vec<NominatorPayout>,
+}
+
+/// Payout for a single elected validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorPayout {
+    /// Account ID of the validator
+    pub validator_id: String,
+    /// Era points assumed for this validator
+    pub era_points: u32,
+    /// Total reward assigned to this validator's exposure
+    pub total_reward: u128,
+    /// Commission kept by the validator
+    pub commission_reward: u128,
+    /// Reward earned on the validator's own stake
+    pub own_stake_reward: u128,
+    /// Reward paid to nominators
+    pub nominators_reward: u128,
+    /// Number of nominators backing this validator
+    pub nominator_count: u32,
+    /// Number of nominators that received a reward
+    pub rewarded_nominator_count: u32,
+    /// Number of exposure pages (one `payout_stakers` call each)
+    pub pages: u32,
+}
+
+/// Payout for a single nominator across all validators it backs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NominatorPayout {
+    /// Account ID of the nominator
+    pub nominator_id: String,
+    /// Total reward across all backed validators
+    pub total_reward: u128,
+    /// Where the reward is paid to
+    pub destination: RewardDestination,
+    /// Reward share per backed validator
+    pub rewards: Vec<ValidatorRewardShare>,
+}
+
+/// Reward a nominator receives through a single validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorRewardShare {
+    /// Account ID of the validator
+    pub validator_id: String,
+    /// Stake exposed to this validator
+    pub stake: u128,
+    /// Reward amount
+    pub amount: u128,
+    /// Exposure page this nominator falls into (None if clipped out)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+impl PayoutResult {
+    /// Get payout for a specific validator
+    pub fn validator_payout(&self, validator_id: &str) -> Option<&ValidatorPayout> {
+        self.validator_payouts
+            .iter()
+            .find(|p| p.validator_id == validator_id)
+    }
+
+    /// Get payout for a specific nominator
+    pub fn nominator_payout(&self, nominator_id: &str) -> Option<&NominatorPayout> {
+        self.nominator_payouts
+            .iter()
+            .find(|p| p.nominator_id == nominator_id)
+    }
+
+    /// Stake increase per nominator whose rewards are compounded
+    pub fn compounded_stakes(&self) -> HashMap<String, u128> {
+        self.nominator_payouts
+            .iter()
+            .filter(|p| p.destination == RewardDestination::Staked && p.total_reward > 0)
+            .map(|p| (p.nominator_id.clone(), p.total_reward))
+            .collect()
+    }
+
+    /// Convert result to JSON string
+    pub fn to_json(&self) -> Result<String, ElectionError> {
+        serde_json::to_string_pretty(self).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize payout result to JSON: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })
+    }
+}
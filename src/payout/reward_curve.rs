@@ -0,0 +1,87 @@
+//! Per-validator reward curves
+//!
+//! `pallet-staking` splits the era reward between validators by era points
+//! alone, however much stake backs them. Incentive proposals often change
+//! that, e.g. to pay more for backing but with decreasing marginal rewards.
+//! A [`RewardCurve`] turns each elected validator's points and backing into
+//! a weight, and [`PayoutSimulator::with_reward_curve`](crate::payout::PayoutSimulator::with_reward_curve)
+//! splits the era reward by those weights instead, so payouts under a
+//! proposal can be compared with the current ones on real election results.
+
+use sp_arithmetic::traits::IntegerSquareRoot;
+
+/// What a reward curve knows about an elected validator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RewardCurveInput<'a> {
+    /// Account ID of the validator
+    pub validator_id: &'a str,
+    /// Era points assumed for the validator
+    pub era_points: u32,
+    /// Total stake backing the validator in the election result
+    pub backing: u128,
+}
+
+/// Weight of each elected validator in the era reward split
+///
+/// Each validator receives `weight / total weight` of the era reward; the
+/// commission and nominator split of its share is unchanged.
+pub trait RewardCurve: Send + Sync {
+    /// Weight of a validator in the era reward split
+    fn weight(&self, validator: &RewardCurveInput<'_>) -> u128;
+
+    /// Get the name of the curve
+    fn name(&self) -> &'static str;
+}
+
+/// The era points split of `pallet-staking`; backing is ignored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EraPointsCurve;
+
+/// Era points times the square root of the backing, so every additional
+/// unit of backing earns less than the one before
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SqrtBackingCurve;
+
+/// Era points times the backing up to `cap`; backing above it earns nothing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CappedBackingCurve {
+    /// Backing beyond which a validator's weight stops growing
+    pub cap: u128,
+}
+
+impl RewardCurve for EraPointsCurve {
+    fn weight(&self, validator: &RewardCurveInput<'_>) -> u128 {
+        validator.era_points as u128
+    }
+
+    fn name(&self) -> &'static str {
+        "era-points"
+    }
+}
+
+impl RewardCurve for SqrtBackingCurve {
+    fn weight(&self, validator: &RewardCurveInput<'_>) -> u128 {
+        (validator.era_points as u128).saturating_mul(validator.backing.integer_sqrt())
+    }
+
+    fn name(&self) -> &'static str {
+        "sqrt-backing"
+    }
+}
+
+impl CappedBackingCurve {
+    /// Create a curve capping backing at `cap`
+    pub fn new(cap: u128) -> Self {
+        Self { cap }
+    }
+}
+
+impl RewardCurve for CappedBackingCurve {
+    fn weight(&self, validator: &RewardCurveInput<'_>) -> u128 {
+        (validator.era_points as u128).saturating_mul(validator.backing.min(self.cap))
+    }
+
+    fn name(&self) -> &'static str {
+        "capped-backing"
+    }
+}
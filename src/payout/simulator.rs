@@ -0,0 +1,216 @@
+//! Era payout simulator
+//!
+//! Mirrors the reward split performed by `pallet-staking` when `payout_stakers` is called:
+//! the era reward is divided between validators by era points, each validator keeps its
+//! commission, and the remainder is shared pro-rata across the exposure (own stake plus
+//! nominator stake). A [`RewardCurve`] can replace the era points split between validators.
+
+use crate::error::ElectionError;
+use crate::models::election_result::ElectionResult;
+use crate::models::result_index::div_ceil;
+use crate::payout::models::{
+    NominatorPayout, PageLimitMode, PayoutConfig, PayoutResult, ValidatorPayout,
+    ValidatorRewardShare,
+};
+use crate::payout::reward_curve::{EraPointsCurve, RewardCurve, RewardCurveInput};
+use sp_runtime::Perbill;
+use std::collections::HashMap;
+
+/// Simulator for per-era staking payouts
+pub struct PayoutSimulator {
+    curve: Box<dyn RewardCurve>,
+}
+
+impl PayoutSimulator {
+    /// Create a new payout simulator splitting the era reward by era points
+    pub fn new() -> Self {
+        Self {
+            curve: Box::new(EraPointsCurve),
+        }
+    }
+
+    /// Split the era reward between validators by `curve` instead of era points
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use offline_election::input::SyntheticDataBuilder;
+    /// use offline_election::payout::{PayoutConfig, PayoutResult, PayoutSimulator, SqrtBackingCurve};
+    /// use offline_election::{ElectionConfiguration, ElectionEngine};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut builder = SyntheticDataBuilder::new();
+    /// builder
+    ///     .add_candidate("a".to_string(), 100)?
+    ///     .add_candidate("b".to_string(), 100)?
+    ///     .add_nominator("n1".to_string(), 900, vec!["a".to_string()])?
+    ///     .add_nominator("n2".to_string(), 100, vec!["b".to_string()])?;
+    /// let config = ElectionConfiguration::builder().active_set_size(2).build()?;
+    /// let result = ElectionEngine::new().execute(&config, &builder.build()?)?;
+    ///
+    /// let config = PayoutConfig::new(1_000_000_000_000);
+    /// let current = PayoutSimulator::new().simulate(&result, &config)?;
+    /// let proposed = PayoutSimulator::new()
+    ///     .with_reward_curve(SqrtBackingCurve)
+    ///     .simulate(&result, &config)?;
+    ///
+    /// // Equal era points split the reward evenly; the curve favours `a`'s larger backing
+    /// let reward_of_a = |payout: &PayoutResult| {
+    ///     payout.validator_payouts.iter().find(|p| p.validator_id == "a").map(|p| p.total_reward)
+    /// };
+    /// assert!(reward_of_a(&proposed) > reward_of_a(&current));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_reward_curve(mut self, curve: impl RewardCurve + 'static) -> Self {
+        self.curve = Box::new(curve);
+        self
+    }
+
+    /// Name of the curve splitting the era reward between validators
+    pub fn reward_curve(&self) -> &'static str {
+        self.curve.name()
+    }
+
+    /// Simulate the payout of one era for the given election result
+    pub fn simulate(
+        &self,
+        result: &ElectionResult,
+        config: &PayoutConfig,
+    ) -> Result<PayoutResult, ElectionError> {
+        config.validate()?;
+
+        let validator_ids: Vec<&str> =
+            result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+        let points = config.era_points.points_for(&validator_ids);
+        let weights: Vec<u128> = result
+            .selected_validators
+            .iter()
+            .zip(&points)
+            .map(|(validator, era_points)| {
+                self.curve.weight(&RewardCurveInput {
+                    validator_id: &validator.account_id,
+                    era_points: *era_points,
+                    backing: validator.total_backing_stake,
+                })
+            })
+            .collect();
+        let total_weight = weights.iter().fold(0u128, |total, weight| total.saturating_add(*weight));
+
+        let mut validator_payouts = Vec::with_capacity(result.selected_validators.len());
+        let mut nominator_payouts: Vec<NominatorPayout> = Vec::new();
+        let mut nominator_index: HashMap<String, usize> = HashMap::new();
+        let mut total_paid: u128 = 0;
+
+        for ((validator, era_points), weight) in result.selected_validators.iter().zip(points).zip(weights) {
+            let total_reward = if total_weight > 0 {
+                Perbill::from_rational(weight, total_weight) * config.era_reward
+            } else {
+                0
+            };
+
+            let commission = Perbill::from_percent(config.commission_for(&validator.account_id) as u32);
+            let commission_reward = commission * total_reward;
+            let leftover = total_reward.saturating_sub(commission_reward);
+
+            // Exposure sorted by stake, largest first, with whether each
+            // allocation was marked unrewarded by the election
+            let mut exposure: Vec<(&String, u128, bool)> = result
+                .stake_distribution
+                .iter()
+                .filter(|alloc| alloc.validator_id == validator.account_id && alloc.amount > 0)
+                .map(|alloc| (&alloc.nominator_id, alloc.amount, alloc.unrewarded))
+                .collect();
+            exposure.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+            let nominator_count = exposure.len() as u32;
+            let page_size = config.page_size as usize;
+            let rewarded = match config.page_limit_mode {
+                PageLimitMode::Paged => exposure.len(),
+                PageLimitMode::TopOnly => exposure.len().min(page_size),
+            };
+            let pages = match config.page_limit_mode {
+                PageLimitMode::Paged => div_ceil(exposure.len(), page_size).max(1) as u32,
+                PageLimitMode::TopOnly => 1,
+            };
+
+            let own_stake = config
+                .self_stakes
+                .get(&validator.account_id)
+                .copied()
+                .unwrap_or(0);
+            // Clipped exposures keep the full total, so the share of clipped
+            // and unrewarded nominators is left unpaid rather than going to
+            // the rewarded ones
+            let exposure_total: u128 =
+                own_stake + exposure.iter().map(|(_, stake, _)| *stake).sum::<u128>();
+
+            let own_stake_reward = if exposure_total > 0 {
+                Perbill::from_rational(own_stake, exposure_total) * leftover
+            } else {
+                // No exposure at all: the validator keeps the whole reward
+                leftover
+            };
+
+            let mut nominators_reward: u128 = 0;
+            let mut rewarded_nominator_count: u32 = 0;
+            for (position, (nominator_id, stake, unrewarded)) in exposure.iter().enumerate() {
+                let (amount, page) = if position < rewarded && !unrewarded {
+                    rewarded_nominator_count += 1;
+                    let amount = Perbill::from_rational(*stake, exposure_total) * leftover;
+                    (amount, Some((position / page_size) as u32))
+                } else {
+                    (0, None)
+                };
+                nominators_reward += amount;
+
+                let index = *nominator_index
+                    .entry((*nominator_id).clone())
+                    .or_insert_with(|| {
+                        nominator_payouts.push(NominatorPayout {
+                            nominator_id: (*nominator_id).clone(),
+                            total_reward: 0,
+                            destination: config.destination_for(nominator_id),
+                            rewards: Vec::new(),
+                        });
+                        nominator_payouts.len() - 1
+                    });
+                let payout = &mut nominator_payouts[index];
+                payout.total_reward += amount;
+                payout.rewards.push(ValidatorRewardShare {
+                    validator_id: validator.account_id.clone(),
+                    stake: *stake,
+                    amount,
+                    page,
+                });
+            }
+
+            total_paid += commission_reward + own_stake_reward + nominators_reward;
+            validator_payouts.push(ValidatorPayout {
+                validator_id: validator.account_id.clone(),
+                era_points,
+                total_reward,
+                commission_reward,
+                own_stake_reward,
+                nominators_reward,
+                nominator_count,
+                rewarded_nominator_count,
+                pages,
+            });
+        }
+
+        Ok(PayoutResult {
+            era_reward: config.era_reward,
+            total_paid,
+            unpaid: config.era_reward.saturating_sub(total_paid),
+            validator_payouts,
+            nominator_payouts,
+        })
+    }
+}
+
+impl Default for PayoutSimulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
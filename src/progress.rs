@@ -0,0 +1,95 @@
+//! Progress reporting from running elections
+//!
+//! Elections over a full Kusama snapshot can take many minutes. The engine
+//! reports each round of winner selection and balancing to a
+//! [`ProgressObserver`] so frontends can show that a run is moving. A
+//! [`RunControl`] carries the observer, together with the run's
+//! [`CancellationToken`], into the algorithms.
+
+use crate::cancellation::{CancellationToken, ElectionPhase};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A finished round of an election phase, reported to the engine's progress observer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressEvent {
+    /// Phase the round belongs to
+    pub phase: ElectionPhase,
+    /// Rounds finished so far, from 1
+    pub round: u32,
+    /// Most rounds the phase will run
+    pub total_rounds: u32,
+    /// Time since the phase started
+    pub elapsed: Duration,
+    /// Smallest backing among the candidates elected so far, in plancks
+    ///
+    /// During sequential Phragmén selection this is the lower bound the
+    /// method guarantees, the inverse of the latest winning score; during
+    /// balancing it is the exact smallest backing after the round.
+    pub minimum_support: Option<u128>,
+}
+
+/// Callback notified of every finished round
+pub type ProgressObserver = Arc<dyn Fn(&ProgressEvent) + Send + Sync>;
+
+/// Cancellation token and progress observer for one election run
+#[derive(Clone, Default)]
+pub struct RunControl {
+    /// Token checked between phases and rounds
+    pub cancel: CancellationToken,
+    /// Observer notified of every finished round, if any
+    pub progress: Option<ProgressObserver>,
+}
+
+impl std::fmt::Debug for RunControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunControl")
+            .field("cancel", &self.cancel)
+            .field("progress", &self.progress.as_ref().map(|_| "observer"))
+            .finish()
+    }
+}
+
+impl RunControl {
+    /// A run that is never cancelled and reports no progress
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A run stopped by `cancel`
+    pub fn cancellable(cancel: CancellationToken) -> Self {
+        Self { cancel, progress: None }
+    }
+
+    /// Report progress to `observer`
+    pub fn with_progress(mut self, observer: ProgressObserver) -> Self {
+        self.progress = Some(observer);
+        self
+    }
+
+    /// Whether anyone listens for progress; lets callers skip computing
+    /// event fields nobody reads
+    pub fn reports_progress(&self) -> bool {
+        self.progress.is_some()
+    }
+
+    /// Notify the observer, if any, that `round` of `total_rounds` of `phase` finished
+    pub fn report(
+        &self,
+        phase: ElectionPhase,
+        round: u32,
+        total_rounds: u32,
+        started: Instant,
+        minimum_support: Option<u128>,
+    ) {
+        if let Some(ref observer) = self.progress {
+            observer(&ProgressEvent {
+                phase,
+                round,
+                total_rounds,
+                elapsed: started.elapsed(),
+                minimum_support,
+            });
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Golden regression fixtures shipped with the crate
+//!
+//! Each fixture is a frozen election snapshot, stored zlib-compressed under
+//! `tests/fixtures/golden`, with the verified results of electing from it.
+//! The `regression` feature embeds both in the library, so [`verify_fixture`]
+//! checks this build's election pipeline against them without network or file
+//! access, e.g. when packaging the tool for a distribution. Besides winners and backings, each
+//! election records the hash of its whole serialized result, so a build on
+//! another platform must reproduce every allocation byte for byte.
+//!
+//! New fixtures are made with [`freeze_fixture`] and added to the fixture
+//! table. Expected results must be checked independently before they are
+//! committed, e.g. with the `selftest` comparison against `sp-npos-elections`.
+
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::JsonLoader;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::ElectionResult;
+use crate::types::AlgorithmType;
+use serde::{Deserialize, Serialize};
+
+/// Largest decompressed snapshot accepted, guarding against corrupt fixtures
+const MAX_SNAPSHOT_BYTES: usize = 64 * 1024 * 1024;
+
+/// zlib level fixtures are frozen with
+const COMPRESSION_LEVEL: u8 = 9;
+
+/// A fixture embedded in the crate: compressed snapshot and expected results
+struct GoldenFixture {
+    name: &'static str,
+    snapshot: &'static [u8],
+    expected: &'static str,
+}
+
+macro_rules! golden_fixture {
+    ($name:literal) => {
+        GoldenFixture {
+            name: $name,
+            snapshot: include_bytes!(concat!("../tests/fixtures/golden/", $name, ".json.zz")),
+            expected: include_str!(concat!("../tests/fixtures/golden/", $name, ".expected.json")),
+        }
+    };
+}
+
+static FIXTURES: &[GoldenFixture] = &[
+    golden_fixture!("self-staked-small"),
+    golden_fixture!("long-tail-medium"),
+    golden_fixture!("concentrated-stake"),
+];
+
+/// Verified results of electing from a fixture
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedResults {
+    /// Fixture name
+    pub name: String,
+    /// What the snapshot contains and where it came from
+    pub description: String,
+    /// Content hash of the snapshot, see [`ElectionData::content_hash`]
+    pub data_hash: String,
+    /// Elections run on the snapshot and their outcomes
+    pub elections: Vec<ExpectedElection>,
+}
+
+/// Outcome of one election on a fixture
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedElection {
+    /// Algorithm used
+    pub algorithm: AlgorithmType,
+    /// Number of validators elected
+    pub active_set_size: u32,
+    /// Elected validators in result order
+    pub winners: Vec<ExpectedWinner>,
+    /// Total stake backing the elected set
+    pub total_stake: u128,
+    /// [`ElectionResult::content_hash`] of the full result, allocations included
+    pub result_hash: String,
+}
+
+/// An elected validator and its backing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedWinner {
+    /// Account ID of the validator
+    pub account_id: String,
+    /// Total stake backing the validator
+    pub total_backing_stake: u128,
+}
+
+/// Outcome of checking a build against one fixture
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FixtureVerification {
+    /// Fixture name
+    pub name: String,
+    /// Number of elections run
+    pub elections: usize,
+    /// Every difference from the expected results
+    pub mismatches: Vec<String>,
+}
+
+impl FixtureVerification {
+    /// Whether every election matched its expected results
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Names of the fixtures embedded in the crate
+pub fn fixture_names() -> Vec<&'static str> {
+    FIXTURES.iter().map(|fixture| fixture.name).collect()
+}
+
+/// Decompress a fixture's snapshot and parse its expected results
+pub fn load_fixture(name: &str) -> Result<(ElectionData, ExpectedResults), ElectionError> {
+    let fixture = FIXTURES
+        .iter()
+        .find(|fixture| fixture.name == name)
+        .ok_or_else(|| ElectionError::ValidationError {
+            message: format!("Unknown regression fixture '{}' (known: {})", name, fixture_names().join(", ")),
+            field: Some("fixture".to_string()),
+        })?;
+
+    let data = decompress_snapshot(fixture.snapshot)?;
+    let expected: ExpectedResults =
+        serde_json::from_str(fixture.expected).map_err(|e| ElectionError::InvalidData {
+            message: format!("Expected results of fixture '{}' are malformed: {}", name, e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+    Ok((data, expected))
+}
+
+/// Run every election recorded for a fixture and compare the outcomes
+///
+/// Errors only if the fixture is unknown or unreadable; differences from the
+/// expected results, including an election that fails, are reported in the
+/// returned verification.
+pub fn verify_fixture(name: &str) -> Result<FixtureVerification, ElectionError> {
+    let (data, expected) = load_fixture(name)?;
+    let mut verification = FixtureVerification {
+        name: name.to_string(),
+        elections: expected.elections.len(),
+        mismatches: Vec::new(),
+    };
+
+    let data_hash = data.content_hash();
+    if data_hash != expected.data_hash {
+        verification
+            .mismatches
+            .push(format!("snapshot hash {} does not match the recorded {}", data_hash, expected.data_hash));
+        return Ok(verification);
+    }
+
+    let engine = ElectionEngine::new();
+    for election in &expected.elections {
+        let label = format!("{} electing {}", election.algorithm, election.active_set_size);
+        let config = ElectionConfiguration::builder()
+            .algorithm(election.algorithm)
+            .active_set_size(election.active_set_size)
+            .build()?;
+        match engine.execute(&config, &data) {
+            Ok(result) => {
+                let outcome = ExpectedElection::from_result(election.algorithm, election.active_set_size, &result);
+                verification.mismatches.extend(outcome.differences_from(election, &label));
+            }
+            Err(e) => verification.mismatches.push(format!("{}: election failed: {}", label, e)),
+        }
+    }
+    Ok(verification)
+}
+
+/// Verify every embedded fixture
+pub fn verify_all() -> Result<Vec<FixtureVerification>, ElectionError> {
+    FIXTURES.iter().map(|fixture| verify_fixture(fixture.name)).collect()
+}
+
+/// Freeze a snapshot as a fixture: its compressed bytes and expected results as JSON
+///
+/// Every `(algorithm, active_set_size)` election is run with this build, so
+/// the results must be verified by other means before the fixture is shipped.
+pub fn freeze_fixture(
+    name: &str,
+    description: &str,
+    data: &ElectionData,
+    elections: &[(AlgorithmType, u32)],
+) -> Result<(Vec<u8>, String), ElectionError> {
+    let engine = ElectionEngine::new();
+    let mut expected = ExpectedResults {
+        name: name.to_string(),
+        description: description.to_string(),
+        data_hash: data.content_hash(),
+        elections: Vec::new(),
+    };
+    for &(algorithm, active_set_size) in elections {
+        let config = ElectionConfiguration::builder()
+            .algorithm(algorithm)
+            .active_set_size(active_set_size)
+            .build()?;
+        let result = engine.execute(&config, data)?;
+        expected
+            .elections
+            .push(ExpectedElection::from_result(algorithm, active_set_size, &result));
+    }
+
+    let serialize_error = |e: serde_json::Error| ElectionError::InvalidData {
+        message: format!("Failed to serialize fixture '{}': {}", name, e),
+        source: Some(ErrorSource::new(e)),
+    };
+    let snapshot = serde_json::to_vec(data).map_err(serialize_error)?;
+    let expected = serde_json::to_string_pretty(&expected).map_err(serialize_error)?;
+    Ok((miniz_oxide::deflate::compress_to_vec_zlib(&snapshot, COMPRESSION_LEVEL), expected))
+}
+
+/// Inflate and load a compressed snapshot, validated like any JSON input
+fn decompress_snapshot(bytes: &[u8]) -> Result<ElectionData, ElectionError> {
+    let json = miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(bytes, MAX_SNAPSHOT_BYTES).map_err(|e| {
+        ElectionError::InvalidData {
+            message: format!("Failed to decompress fixture snapshot: {:?}", e.status),
+            source: None,
+        }
+    })?;
+    let json = String::from_utf8(json).map_err(|e| ElectionError::InvalidData {
+        message: format!("Fixture snapshot is not UTF-8: {}", e),
+        source: Some(ErrorSource::new(e)),
+    })?;
+    JsonLoader::new().load_from_str(&json)
+}
+
+impl ExpectedElection {
+    fn from_result(
+        algorithm: AlgorithmType,
+        active_set_size: u32,
+        result: &ElectionResult,
+    ) -> Self {
+        Self {
+            algorithm,
+            active_set_size,
+            winners: result
+                .selected_validators
+                .iter()
+                .map(|validator| ExpectedWinner {
+                    account_id: validator.account_id.clone(),
+                    total_backing_stake: validator.total_backing_stake,
+                })
+                .collect(),
+            total_stake: result.total_stake,
+            result_hash: result.content_hash(),
+        }
+    }
+
+    /// Describe how `self` differs from `expected`
+    fn differences_from(&self, expected: &ExpectedElection, label: &str) -> Vec<String> {
+        let mut differences = Vec::new();
+        if self.winners.len() != expected.winners.len() {
+            differences.push(format!(
+                "{}: {} winners, expected {}",
+                label,
+                self.winners.len(),
+                expected.winners.len()
+            ));
+        }
+        for (position, (ours, theirs)) in self.winners.iter().zip(&expected.winners).enumerate() {
+            if ours != theirs {
+                differences.push(format!(
+                    "{}: winner {} is {} backed by {}, expected {} backed by {}",
+                    label,
+                    position + 1,
+                    ours.account_id,
+                    ours.total_backing_stake,
+                    theirs.account_id,
+                    theirs.total_backing_stake
+                ));
+            }
+        }
+        if self.total_stake != expected.total_stake {
+            differences.push(format!(
+                "{}: total stake {}, expected {}",
+                label, self.total_stake, expected.total_stake
+            ));
+        }
+        // Same winners but different bytes: allocations or metadata changed
+        if differences.is_empty() && self.result_hash != expected.result_hash {
+            differences.push(format!(
+                "{}: result hash {}, expected {}",
+                label, self.result_hash, expected.result_hash
+            ));
+        }
+        differences
+    }
+}
@@ -0,0 +1,700 @@
+//! Recurring backtests for the daemon
+//!
+//! A TOML schedule file lists jobs, each run every era or at a fixed
+//! interval: fetch a snapshot, run the election, optionally compare the
+//! winners with the chain's solution of the same election, and notify
+//! webhooks or command hooks.
+//! Every run is kept in a [`RunHistory`], which can be persisted to a JSON
+//! file so it survives restarts.
+//!
+//! ```toml
+//! history_file = "/var/lib/offline-election/runs.json"
+//!
+//! [[jobs]]
+//! name = "polkadot-era"
+//! every = "era"
+//! rpc_url = "https://rpc.polkadot.io"
+//! compare_with_chain = true
+//! notify_webhook = ["https://hooks.example.com/backtests"]
+//! ```
+
+use crate::api::handlers::HandlerState;
+use crate::api::lifecycle::Lifecycle;
+use crate::engine::ElectionEngine;
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::json::JsonLoader;
+use crate::input::rpc::RpcLoader;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::notifications::{NotificationConfig, NotificationEvent, Notifier};
+use crate::types::AlgorithmType;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Runs kept in the history unless the schedule says otherwise
+pub const DEFAULT_HISTORY_LIMIT: usize = 1000;
+
+/// Seconds between checks for a new era unless the schedule says otherwise
+pub const DEFAULT_ERA_POLL_SECS: u64 = 60;
+
+/// How often a job runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Every {
+    /// Whenever the chain's active era changes: `"era"`
+    Era,
+    /// At a fixed interval: `"30s"`, `"15m"`, `"6h"` or `"1d"`
+    Interval(Duration),
+}
+
+impl FromStr for Every {
+    type Err = ElectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ElectionError::ValidationError {
+            message: format!("Invalid schedule '{}': expected \"era\" or an interval such as \"30s\", \"15m\", \"6h\" or \"1d\"", s),
+            field: Some("every".to_string()),
+        };
+        let s = s.trim();
+        if s == "era" {
+            return Ok(Every::Era);
+        }
+        let split = s.len().checked_sub(1).filter(|&i| s.is_char_boundary(i)).ok_or_else(invalid)?;
+        let (count, unit) = s.split_at(split);
+        let count: u64 = count.parse().map_err(|_| invalid())?;
+        let unit_secs = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        match count.checked_mul(unit_secs) {
+            Some(secs) if secs > 0 => Ok(Every::Interval(Duration::from_secs(secs))),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for Every {
+    type Error = ElectionError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Every> for String {
+    fn from(every: Every) -> Self {
+        match every {
+            Every::Era => "era".to_string(),
+            Every::Interval(interval) => format!("{}s", interval.as_secs()),
+        }
+    }
+}
+
+/// Schedule file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    /// JSON file the run history is kept in; kept in memory only if absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_file: Option<PathBuf>,
+    /// Most runs kept in the history, oldest dropped first
+    #[serde(default = "default_history_limit")]
+    pub history_limit: usize,
+    /// Seconds between checks for a new era
+    #[serde(default = "default_era_poll_secs")]
+    pub era_poll_secs: u64,
+    /// Jobs to run
+    #[serde(default)]
+    pub jobs: Vec<ScheduledJob>,
+}
+
+fn default_history_limit() -> usize {
+    DEFAULT_HISTORY_LIMIT
+}
+
+fn default_era_poll_secs() -> u64 {
+    DEFAULT_ERA_POLL_SECS
+}
+
+fn default_algorithm() -> String {
+    "sequential-phragmen".to_string()
+}
+
+impl ScheduleConfig {
+    /// Load a schedule file
+    pub fn load_from_file(path: &Path) -> Result<Self, ElectionError> {
+        let file_error = |message: String, source: ErrorSource| ElectionError::FileError {
+            message,
+            path: path.to_path_buf(),
+            source: Some(source),
+        };
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| file_error(format!("Failed to read schedule file: {}", e), ErrorSource::new(e)))?;
+        toml::from_str(&content)
+            .map_err(|e| file_error(format!("Failed to parse schedule file: {}", e), ErrorSource::new(e)))
+    }
+}
+
+/// One recurring backtest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// Name used in the history, notifications and election IDs
+    pub name: String,
+    /// How often the job runs
+    pub every: Every,
+    /// RPC endpoint to fetch the latest snapshot from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    /// Snapshot file to run on instead of fetching one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_file: Option<PathBuf>,
+    /// Election algorithm
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String,
+    /// Number of validators to select (default: the chain's desired targets, else the size of its active set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_set_size: Option<u32>,
+    /// Elect from the snapshot of the chain's last concluded election and
+    /// compare the winners with the solution the chain queued for it
+    #[serde(default)]
+    pub compare_with_chain: bool,
+    /// Webhook URLs notified after every run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notify_webhook: Vec<String>,
+    /// Shell commands run after every run, payload on stdin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notify_command: Vec<String>,
+}
+
+/// Outcome of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunStatus {
+    /// The election ran
+    Succeeded,
+    /// Fetching, solving or comparing failed
+    Failed,
+}
+
+/// Winners compared with the chain's solution of the same election
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainComparison {
+    /// Winners also elected on-chain
+    pub matching: usize,
+    /// Winners not elected on-chain
+    pub only_predicted: Vec<String>,
+    /// Validators elected on-chain but not among the winners
+    pub only_on_chain: Vec<String>,
+}
+
+impl ChainComparison {
+    /// Compare `predicted` winners with the validators elected `on_chain`
+    pub fn new(predicted: &[String], on_chain: &[String]) -> Self {
+        let predicted_set: HashSet<&String> = predicted.iter().collect();
+        let on_chain_set: HashSet<&String> = on_chain.iter().collect();
+        Self {
+            matching: predicted.iter().filter(|id| on_chain_set.contains(id)).count(),
+            only_predicted: predicted.iter().filter(|id| !on_chain_set.contains(id)).cloned().collect(),
+            only_on_chain: on_chain.iter().filter(|id| !predicted_set.contains(id)).cloned().collect(),
+        }
+    }
+}
+
+/// One run of a scheduled job
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Name of the job
+    pub job: String,
+    /// When the run started (RFC 3339)
+    pub started_at: String,
+    /// How long the run took
+    pub duration_ms: u64,
+    /// Whether the election ran
+    pub status: RunStatus,
+    /// Block the snapshot was taken at, for chain snapshots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Era whose start triggered the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era: Option<u32>,
+    /// Content hash of the snapshot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_hash: Option<String>,
+    /// Number of validators selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator_count: Option<usize>,
+    /// Winners compared with the chain, if the job compares
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comparison: Option<ChainComparison>,
+    /// Election ID the result is served under, if the daemon serves it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub election_id: Option<String>,
+    /// What went wrong, for failed runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RunRecord {
+    /// Notification announcing the run
+    pub fn notification(&self) -> NotificationEvent {
+        match self.status {
+            RunStatus::Succeeded => NotificationEvent::ScheduledRunCompleted {
+                job: self.job.clone(),
+                block_number: self.block_number,
+                validator_count: self.validator_count.unwrap_or_default(),
+                matching_validators: self.comparison.as_ref().map(|c| c.matching),
+                election_id: self.election_id.clone(),
+            },
+            RunStatus::Failed => NotificationEvent::ScheduledRunFailed {
+                job: self.job.clone(),
+                error: self.error.clone().unwrap_or_default(),
+            },
+        }
+    }
+}
+
+/// Runs of scheduled jobs, oldest first
+///
+/// Clones share the same runs. With a file, every recorded run rewrites it.
+#[derive(Debug, Clone)]
+pub struct RunHistory {
+    path: Option<PathBuf>,
+    limit: usize,
+    runs: Arc<Mutex<VecDeque<RunRecord>>>,
+}
+
+impl RunHistory {
+    /// Keep at most `limit` runs in memory only
+    pub fn in_memory(limit: usize) -> Self {
+        Self { path: None, limit, runs: Arc::default() }
+    }
+
+    /// Keep at most `limit` runs in `path`, starting from the runs already there
+    pub fn open(path: impl Into<PathBuf>, limit: usize) -> Result<Self, ElectionError> {
+        let path = path.into();
+        let runs: VecDeque<RunRecord> = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| ElectionError::FileError {
+                message: format!("Failed to parse run history: {}", e),
+                path: path.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => VecDeque::new(),
+            Err(e) => {
+                return Err(ElectionError::FileError {
+                    message: format!("Failed to read run history: {}", e),
+                    path,
+                    source: Some(ErrorSource::new(e)),
+                })
+            }
+        };
+        let history = Self { path: Some(path), limit, runs: Arc::new(Mutex::new(runs)) };
+        history.trim(&mut history.runs.lock().unwrap());
+        Ok(history)
+    }
+
+    /// Add a run, dropping the oldest beyond the limit, and write the file if any
+    pub fn record(&self, run: RunRecord) -> Result<(), ElectionError> {
+        let mut runs = self.runs.lock().unwrap();
+        runs.push_back(run);
+        self.trim(&mut runs);
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let file_error = |message: String, source: ErrorSource| ElectionError::FileError {
+            message,
+            path: path.clone(),
+            source: Some(source),
+        };
+        let json = serde_json::to_string_pretty(&*runs)
+            .map_err(|e| file_error(format!("Failed to serialize run history: {}", e), ErrorSource::new(e)))?;
+        // Replace the file in one step so a crash never leaves it half written
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, json)
+            .and_then(|_| std::fs::rename(&partial, path))
+            .map_err(|e| file_error(format!("Failed to write run history: {}", e), ErrorSource::new(e)))
+    }
+
+    /// Every run kept, oldest first
+    pub fn runs(&self) -> Vec<RunRecord> {
+        self.runs.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Runs of the job called `job`, oldest first
+    pub fn runs_of(&self, job: &str) -> Vec<RunRecord> {
+        self.runs.lock().unwrap().iter().filter(|run| run.job == job).cloned().collect()
+    }
+
+    fn trim(&self, runs: &mut VecDeque<RunRecord>) {
+        while runs.len() > self.limit {
+            runs.pop_front();
+        }
+    }
+}
+
+/// A job with its algorithm and notifier resolved
+#[derive(Debug)]
+struct Job {
+    config: ScheduledJob,
+    algorithm: AlgorithmType,
+    notifier: Option<Notifier>,
+}
+
+/// What started a run
+#[derive(Debug, Clone, Copy, Default)]
+struct Trigger {
+    block_number: Option<u64>,
+    era: Option<u32>,
+}
+
+/// What a successful run produced
+struct Backtest {
+    block_number: Option<u64>,
+    data_hash: String,
+    validator_count: usize,
+    comparison: Option<ChainComparison>,
+    election_id: Option<String>,
+}
+
+/// Runs the jobs of a schedule
+///
+/// Clones share the jobs and history.
+#[derive(Clone)]
+pub struct Scheduler {
+    jobs: Arc<Vec<Arc<Job>>>,
+    history: RunHistory,
+    era_poll: Duration,
+    lifecycle: Lifecycle,
+    state: Option<HandlerState>,
+}
+
+impl Scheduler {
+    /// Check a schedule and open its history
+    ///
+    /// Fails if a job name is empty or repeated, a job has both or neither of
+    /// `rpc_url` and `input_file`, runs every era or compares with the chain
+    /// without `rpc_url`, reads a file without `active_set_size`, names an
+    /// unknown algorithm or has an invalid notification sink.
+    pub fn new(config: ScheduleConfig) -> Result<Self, ElectionError> {
+        let mut names = HashSet::new();
+        let mut jobs = Vec::new();
+        for (index, job) in config.jobs.into_iter().enumerate() {
+            let invalid = |message: String, field: &str| ElectionError::ValidationError {
+                message,
+                field: Some(format!("jobs[{}].{}", index, field)),
+            };
+            if job.name.is_empty() || !names.insert(job.name.clone()) {
+                return Err(invalid(format!("Job names must be unique and non-empty: '{}'", job.name), "name"));
+            }
+            match (&job.rpc_url, &job.input_file) {
+                (Some(url), None) => {
+                    RpcLoader::new(url)?;
+                }
+                (None, Some(_)) => {
+                    if job.every == Every::Era || job.compare_with_chain {
+                        return Err(invalid(
+                            format!("Job '{}' follows the chain and needs an rpc_url", job.name),
+                            "rpc_url",
+                        ));
+                    }
+                    if job.active_set_size.is_none() {
+                        return Err(invalid(
+                            format!("Job '{}' reads a file and needs an active_set_size", job.name),
+                            "active_set_size",
+                        ));
+                    }
+                }
+                _ => {
+                    return Err(invalid(
+                        format!("Job '{}' needs exactly one of rpc_url and input_file", job.name),
+                        "rpc_url",
+                    ))
+                }
+            }
+            let algorithm = job.algorithm.parse::<AlgorithmType>()
+                .map_err(|e| invalid(format!("Invalid algorithm: {}", e), "algorithm"))?;
+            let mut notifications = NotificationConfig::new();
+            for url in &job.notify_webhook {
+                notifications = notifications.webhook(url.trim());
+            }
+            for command in &job.notify_command {
+                notifications = notifications.command(command.as_str());
+            }
+            let notifications = notifications.build()?;
+            let notifier = notifications.has_sinks().then(|| Notifier::new(notifications));
+            jobs.push(Arc::new(Job { config: job, algorithm, notifier }));
+        }
+
+        let history = match config.history_file {
+            Some(path) => RunHistory::open(path, config.history_limit)?,
+            None => RunHistory::in_memory(config.history_limit),
+        };
+        Ok(Self {
+            jobs: Arc::new(jobs),
+            history,
+            era_poll: Duration::from_secs(config.era_poll_secs.max(1)),
+            lifecycle: Lifecycle::new(),
+            state: None,
+        })
+    }
+
+    /// Load and check a schedule file
+    pub fn load_from_file(path: &Path) -> Result<Self, ElectionError> {
+        Self::new(ScheduleConfig::load_from_file(path)?)
+    }
+
+    /// Serve each run's result through `state`'s API and count runs among its
+    /// jobs, so a draining server waits for them and cancels them on timeout
+    pub fn with_handler_state(mut self, state: HandlerState) -> Self {
+        self.lifecycle = state.lifecycle().clone();
+        self.state = Some(state);
+        self
+    }
+
+    /// Runs so far
+    pub fn history(&self) -> &RunHistory {
+        &self.history
+    }
+
+    /// Names of the jobs, in schedule order
+    pub fn job_names(&self) -> Vec<&str> {
+        self.jobs.iter().map(|job| job.config.name.as_str()).collect()
+    }
+
+    /// Run the job called `name` now, outside its schedule
+    pub async fn run_now(&self, name: &str) -> Result<RunRecord, ElectionError> {
+        let job = self
+            .jobs
+            .iter()
+            .find(|job| job.config.name == name)
+            .ok_or_else(|| ElectionError::ValidationError {
+                message: format!("No scheduled job named '{}'", name),
+                field: Some("job".to_string()),
+            })?;
+        Ok(self.execute(job, Trigger::default()).await)
+    }
+
+    /// Run every job on its schedule in the background until the tasks are
+    /// aborted or the server starts draining
+    pub fn spawn(&self) -> Vec<JoinHandle<()>> {
+        self.jobs
+            .iter()
+            .map(|job| {
+                let (scheduler, job) = (self.clone(), Arc::clone(job));
+                tokio::spawn(async move { scheduler.run_job(&job).await })
+            })
+            .collect()
+    }
+
+    /// Wait for each of a job's triggers and run it
+    async fn run_job(&self, job: &Job) {
+        match job.config.every {
+            Every::Interval(period) => {
+                let mut ticks = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                loop {
+                    ticks.tick().await;
+                    if self.lifecycle.is_draining() {
+                        return;
+                    }
+                    self.execute(job, Trigger::default()).await;
+                }
+            }
+            Every::Era => {
+                let loader = match job.config.rpc_url.as_deref().map(RpcLoader::new) {
+                    Some(Ok(loader)) => loader,
+                    _ => return,
+                };
+                let mut last_era = None;
+                loop {
+                    tokio::time::sleep(self.era_poll).await;
+                    if self.lifecycle.is_draining() {
+                        return;
+                    }
+                    match next_era(&loader, &mut last_era).await {
+                        Ok(Some(trigger)) => {
+                            self.execute(job, trigger).await;
+                        }
+                        Ok(None) => {}
+                        Err(e) => eprintln!("⚠ Warning: Job '{}' could not read the active era: {}", job.config.name, e),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run a job once, recording and announcing the outcome
+    async fn execute(&self, job: &Job, trigger: Trigger) -> RunRecord {
+        let _running = self.lifecycle.begin_job();
+        let started = Instant::now();
+        let started_at = chrono::Utc::now().to_rfc3339();
+        let outcome = self.backtest(job, trigger).await;
+        let mut record = RunRecord {
+            job: job.config.name.clone(),
+            started_at,
+            duration_ms: started.elapsed().as_millis() as u64,
+            status: RunStatus::Succeeded,
+            block_number: trigger.block_number,
+            era: trigger.era,
+            data_hash: None,
+            validator_count: None,
+            comparison: None,
+            election_id: None,
+            error: None,
+        };
+        match outcome {
+            Ok(backtest) => {
+                record.block_number = backtest.block_number;
+                record.data_hash = Some(backtest.data_hash);
+                record.validator_count = Some(backtest.validator_count);
+                record.comparison = backtest.comparison;
+                record.election_id = backtest.election_id;
+            }
+            Err(e) => {
+                eprintln!("⚠ Warning: Scheduled job '{}' failed: {}", job.config.name, e);
+                record.status = RunStatus::Failed;
+                record.error = Some(e.to_string());
+            }
+        }
+
+        if let Err(e) = self.history.record(record.clone()) {
+            eprintln!("⚠ Warning: {}", e);
+        }
+        if let Some(ref notifier) = job.notifier {
+            notifier.send_all(&[record.notification()]).await;
+        }
+        record
+    }
+
+    /// Fetch the snapshot, run the election and compare it with the chain
+    async fn backtest(&self, job: &Job, trigger: Trigger) -> Result<Backtest, ElectionError> {
+        // The chain the snapshot came from, with its block; `None` for files
+        let (data, chain) = match job.config.rpc_url {
+            Some(ref url) => {
+                let loader = RpcLoader::new(url)?;
+                let block_number = match trigger.block_number {
+                    Some(block_number) => block_number,
+                    None => loader.get_latest_block_number().await?,
+                };
+                if job.config.compare_with_chain {
+                    let (snapshot, election_block) = last_election_snapshot(&loader, block_number).await?;
+                    (snapshot, Some((loader, election_block)))
+                } else {
+                    (loader.load_at_block(block_number).await?, Some((loader, block_number)))
+                }
+            }
+            None => {
+                let path = job.config.input_file.clone().unwrap_or_default();
+                (JsonLoader::new().load_from_file(path)?, None)
+            }
+        };
+        let block_number = chain.as_ref().map(|(_, block_number)| *block_number);
+
+        let active_set_size = match (job.config.active_set_size, &chain) {
+            (Some(size), _) => size,
+            (None, Some((loader, block_number))) => chain_active_set_size(loader, *block_number).await?,
+            (None, None) => chain_active_set_size_missing()?,
+        };
+        let mut config = ElectionConfiguration::builder().algorithm(job.algorithm).active_set_size(active_set_size);
+        if let Some(block_number) = block_number {
+            config = config.block_number(block_number);
+        }
+        let config = config.build()?;
+
+        let data = Arc::new(data);
+        let solved = Arc::clone(&data);
+        let cancel = self.lifecycle.cancellation().clone();
+        let algorithm = job.algorithm;
+        let result = tokio::task::spawn_blocking(move || ElectionEngine::new().execute_cancellable(&config, &solved, &cancel))
+            .await
+            .map_err(|e| ElectionError::AlgorithmError {
+                message: format!("Election task failed: {}", e),
+                algorithm,
+            })??;
+
+        let comparison = match (job.config.compare_with_chain, &chain) {
+            (true, Some((loader, block_number))) => match loader.queued_solution_at(*block_number).await? {
+                Some(solution) => {
+                    let on_chain: Vec<String> = solution.supports.into_iter().map(|s| s.validator_id).collect();
+                    let predicted: Vec<String> =
+                        result.selected_validators.iter().map(|v| v.account_id.clone()).collect();
+                    Some(ChainComparison::new(&predicted, &on_chain))
+                }
+                None => {
+                    eprintln!(
+                        "⚠ Warning: job '{}': no queued solution at block {}; the chain fell back to another election",
+                        job.config.name, block_number
+                    );
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        let validator_count = result.validator_count();
+        let data_hash = result.execution_metadata.data_hash.clone().unwrap_or_else(|| data.content_hash());
+        let election_id = match self.state {
+            Some(ref state) => {
+                let election_id = format!("{}-{}", job.config.name, Uuid::new_v4());
+                state.record_election(&election_id, data, result).await;
+                Some(election_id)
+            }
+            None => None,
+        };
+        Ok(Backtest { block_number, data_hash, validator_count, comparison, election_id })
+    }
+}
+
+/// Snapshot of the last election concluded at or before `block_number`, with
+/// the block it was read at
+///
+/// Comparing against the chain only makes sense for the election the chain
+/// ran on the same input, so comparing jobs elect from that election's
+/// `ElectionProviderMultiPhase::Snapshot` rather than from live storage.
+async fn last_election_snapshot(loader: &RpcLoader, block_number: u64) -> Result<(ElectionData, u64), ElectionError> {
+    let no_election = || ElectionError::ValidationError {
+        message: format!("No concluded election with a snapshot at or before block {}", block_number),
+        field: Some("compare_with_chain".to_string()),
+    };
+    let election_block = loader.last_election_block(block_number).await?.ok_or_else(no_election)?;
+    let snapshot = loader.snapshot_at(election_block).await?.ok_or_else(no_election)?;
+    Ok((snapshot, election_block))
+}
+
+/// The chain's desired targets at a block, or the size of its active set outside an election
+async fn chain_active_set_size(loader: &RpcLoader, block_number: u64) -> Result<u32, ElectionError> {
+    if let Some(targets) = loader.desired_targets_at(block_number).await? {
+        return Ok(targets);
+    }
+    match loader.active_validators_at(block_number).await?.len() {
+        0 => chain_active_set_size_missing(),
+        size => Ok(size as u32),
+    }
+}
+
+fn chain_active_set_size_missing() -> Result<u32, ElectionError> {
+    Err(ElectionError::ValidationError {
+        message: "No active set size: set active_set_size for jobs without desired targets on-chain".to_string(),
+        field: Some("active_set_size".to_string()),
+    })
+}
+
+/// The trigger for a newly started era, if the era changed since `last_era`
+///
+/// The first era seen only sets the baseline, so a restart does not rerun the current era.
+async fn next_era(loader: &RpcLoader, last_era: &mut Option<u32>) -> Result<Option<Trigger>, ElectionError> {
+    let block_number = loader.get_latest_block_number().await?;
+    let Some(era) = loader.active_era_at(block_number).await? else {
+        return Ok(None);
+    };
+    let started = last_era.is_some_and(|last| last != era);
+    *last_era = Some(era);
+    Ok(started.then_some(Trigger { block_number: Some(block_number), era: Some(era) }))
+}
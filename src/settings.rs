@@ -0,0 +1,355 @@
+//! Layered settings for election runs
+//!
+//! The settings of a run are resolved from four layers, each overriding the
+//! ones before it: built-in defaults, the preset of a known chain, a TOML
+//! configuration file and the command line. [`SettingsResolver`] records the
+//! layer every effective value came from, which `config show --resolve`
+//! prints.
+//!
+//! ```toml
+//! chain = "kusama"
+//! algorithm = "parallel-phragmen"
+//! active_set_size = 1000
+//! rounding = "round-half-even:9"
+//! account_format = "network"
+//! rpc_timeout = 60
+//! ```
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::input::indexer::IndexerQueries;
+use crate::models::account_format::AccountFormat;
+use crate::models::balance::TokenUnit;
+use crate::models::rounding::RoundingPolicy;
+use crate::models::validation::ValidationProfile;
+use crate::types::{AlgorithmType, ElectionKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Chains with a preset, selected with `chain = "..."` or `--chain`
+pub const CHAIN_PRESETS: &[&str] = &["polkadot", "kusama", "westend"];
+
+/// Where an effective setting came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "layer", rename_all = "kebab-case")]
+pub enum SettingSource {
+    /// Built-in default
+    Default,
+    /// Preset of a known chain
+    ChainPreset {
+        /// Chain name
+        chain: String,
+    },
+    /// Configuration file
+    File {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// Command line flag
+    Cli,
+}
+
+impl std::fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::ChainPreset { chain } => write!(f, "{} preset", chain),
+            Self::File { path } => write!(f, "file {}", path.display()),
+            Self::Cli => write!(f, "command line"),
+        }
+    }
+}
+
+/// One layer of run settings; unset fields fall through to lower layers
+///
+/// Values are kept as they are written on the command line, so a file and a
+/// flag are parsed and reported the same way.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunSettings {
+    /// Chain whose preset is applied beneath the file and command line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// Election algorithm
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    /// Number of validators to select
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_set_size: Option<u32>,
+    /// Kind of election: staking or council
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Validation strictness profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_profile: Option<String>,
+    /// Rounding policy for derived ratios
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rounding: Option<String>,
+    /// Continue past recoverable problems
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_results: Option<bool>,
+    /// Most balancing rounds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balancing_iterations: Option<usize>,
+    /// Balancing tolerance, in plancks or e.g. "0.1 DOT"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balancing_tolerance: Option<String>,
+    /// Token unit for amounts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Encoding of account IDs in results: as-loaded, network, ss58:N or public-key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_format: Option<String>,
+    /// RPC endpoint snapshots are fetched from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    /// RPC storage queries in flight at once
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_concurrency: Option<usize>,
+    /// Seconds a single RPC request may take
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_timeout: Option<u64>,
+    /// GraphQL query returning an era's candidates from an indexer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexer_candidates_query: Option<String>,
+    /// GraphQL query returning an era's nominators from an indexer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexer_nominators_query: Option<String>,
+    /// GraphQL query returning an era's elected validators from an indexer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexer_elected_query: Option<String>,
+}
+
+impl RunSettings {
+    /// Built-in defaults, the lowest layer
+    ///
+    /// The active set size has no default: it must come from a file or the
+    /// command line.
+    pub fn defaults() -> Self {
+        Self {
+            algorithm: Some(AlgorithmType::SequentialPhragmen.to_string()),
+            kind: Some("staking".to_string()),
+            validation_profile: Some("chain-mirroring".to_string()),
+            rounding: Some(RoundingPolicy::Exact.to_string()),
+            account_format: Some(AccountFormat::AsLoaded.to_string()),
+            partial_results: Some(false),
+            rpc_concurrency: Some(crate::input::rpc::DEFAULT_RPC_CONCURRENCY),
+            rpc_timeout: Some(crate::input::rpc::DEFAULT_REQUEST_TIMEOUT.as_secs()),
+            ..Self::default()
+        }
+    }
+
+    /// Preset of a known chain: its public RPC endpoint and token
+    ///
+    /// Active set sizes change with governance, so presets leave them unset.
+    pub fn chain_preset(chain: &str) -> Option<Self> {
+        let (rpc_url, token) = match chain.trim().to_lowercase().as_str() {
+            "polkadot" => ("https://rpc.polkadot.io", "DOT"),
+            "kusama" => ("https://kusama-rpc.polkadot.io", "KSM"),
+            "westend" => ("https://westend-rpc.polkadot.io", "WND"),
+            _ => return None,
+        };
+        Some(Self {
+            rpc_url: Some(rpc_url.to_string()),
+            token: Some(token.to_string()),
+            ..Self::default()
+        })
+    }
+
+    /// Load a configuration file
+    pub fn load_from_file(path: &Path) -> Result<Self, ElectionError> {
+        let file_error = |message: String, source: ErrorSource| ElectionError::FileError {
+            message,
+            path: path.to_path_buf(),
+            source: Some(source),
+        };
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| file_error(format!("Failed to read configuration file: {}", e), ErrorSource::new(e)))?;
+        toml::from_str(&content)
+            .map_err(|e| file_error(format!("Failed to parse configuration file: {}", e), ErrorSource::new(e)))
+    }
+}
+
+/// Merges the layers of run settings
+#[derive(Debug, Clone, Default)]
+pub struct SettingsResolver {
+    file: Option<(PathBuf, RunSettings)>,
+    cli: RunSettings,
+}
+
+impl SettingsResolver {
+    /// Resolver with only the built-in defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the settings of a configuration file
+    pub fn with_file(mut self, path: PathBuf, settings: RunSettings) -> Self {
+        self.file = Some((path, settings));
+        self
+    }
+
+    /// Add the settings given on the command line
+    pub fn with_cli(mut self, settings: RunSettings) -> Self {
+        self.cli = settings;
+        self
+    }
+
+    /// Merge the layers, recording where each effective value came from
+    ///
+    /// The chain preset applied is the one named on the command line, or
+    /// else in the file; an unknown chain is an error.
+    pub fn resolve(&self) -> Result<ResolvedSettings, ElectionError> {
+        let mut layers = vec![(SettingSource::Default, RunSettings::defaults())];
+        let chain = self
+            .cli
+            .chain
+            .as_ref()
+            .or_else(|| self.file.as_ref().and_then(|(_, file)| file.chain.as_ref()));
+        if let Some(chain) = chain {
+            let preset = RunSettings::chain_preset(chain).ok_or_else(|| ElectionError::ValidationError {
+                message: format!("Unknown chain '{}' (known: {})", chain, CHAIN_PRESETS.join(", ")),
+                field: Some("chain".to_string()),
+            })?;
+            layers.push((SettingSource::ChainPreset { chain: chain.trim().to_lowercase() }, preset));
+        }
+        if let Some((path, file)) = &self.file {
+            layers.push((SettingSource::File { path: path.clone() }, file.clone()));
+        }
+        layers.push((SettingSource::Cli, self.cli.clone()));
+
+        let mut merged = serde_json::Map::new();
+        let mut sources = BTreeMap::new();
+        for (source, layer) in layers {
+            if let serde_json::Value::Object(values) = serde_json::to_value(&layer).map_err(serialize_error)? {
+                for (key, value) in values {
+                    sources.insert(key.clone(), source.clone());
+                    merged.insert(key, value);
+                }
+            }
+        }
+        let settings = serde_json::from_value(serde_json::Value::Object(merged)).map_err(serialize_error)?;
+        Ok(ResolvedSettings { settings, sources })
+    }
+}
+
+fn serialize_error(e: serde_json::Error) -> ElectionError {
+    ElectionError::InvalidData {
+        message: format!("Failed to merge settings: {}", e),
+        source: Some(ErrorSource::new(e)),
+    }
+}
+
+/// Effective run settings and the layer each value came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedSettings {
+    /// Effective values
+    pub settings: RunSettings,
+    /// Layer of each set value, by setting name
+    pub sources: BTreeMap<String, SettingSource>,
+}
+
+impl ResolvedSettings {
+    /// Layer the effective value of a setting came from, if it is set
+    pub fn source(&self, key: &str) -> Option<&SettingSource> {
+        self.sources.get(key)
+    }
+
+    /// Election algorithm
+    pub fn algorithm(&self) -> Result<AlgorithmType, ElectionError> {
+        self.parse("algorithm", self.settings.algorithm.as_deref())
+    }
+
+    /// Number of validators to select, which some layer must set
+    pub fn active_set_size(&self) -> Result<u32, ElectionError> {
+        self.settings.active_set_size.ok_or_else(|| ElectionError::ValidationError {
+            message: "No active set size: pass --active-set-size or set active_set_size in the configuration file"
+                .to_string(),
+            field: Some("active_set_size".to_string()),
+        })
+    }
+
+    /// Kind of election
+    pub fn kind(&self) -> Result<ElectionKind, ElectionError> {
+        self.parse("kind", self.settings.kind.as_deref())
+    }
+
+    /// Validation strictness profile
+    pub fn validation_profile(&self) -> Result<ValidationProfile, ElectionError> {
+        self.parse("validation_profile", self.settings.validation_profile.as_deref())
+    }
+
+    /// Rounding policy for derived ratios
+    pub fn rounding(&self) -> Result<RoundingPolicy, ElectionError> {
+        self.parse("rounding", self.settings.rounding.as_deref())
+    }
+
+    /// Token unit, if any layer names one
+    pub fn token_unit(&self) -> Result<Option<TokenUnit>, ElectionError> {
+        match self.settings.token {
+            Some(ref token) => self.parse("token", Some(token)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Encoding of account IDs in results
+    pub fn account_format(&self) -> Result<AccountFormat, ElectionError> {
+        self.parse("account_format", self.settings.account_format.as_deref())
+    }
+
+    /// Indexer queries, with the defaults for any not set
+    pub fn indexer_queries(&self) -> IndexerQueries {
+        let defaults = IndexerQueries::default();
+        IndexerQueries {
+            candidates: self.settings.indexer_candidates_query.clone().unwrap_or(defaults.candidates),
+            nominators: self.settings.indexer_nominators_query.clone().unwrap_or(defaults.nominators),
+            elected: self.settings.indexer_elected_query.clone().unwrap_or(defaults.elected),
+        }
+    }
+
+    /// SS58 prefix of the chain named in the settings, if any
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        self.settings.chain.as_deref().map(crate::models::account_format::chain_prefix)
+    }
+
+    /// Render the effective settings as a TOML configuration file
+    ///
+    /// With `with_sources`, each line ends in a comment naming its layer.
+    pub fn to_toml(&self, with_sources: bool) -> Result<String, ElectionError> {
+        let table = toml::Table::try_from(&self.settings).map_err(|e| ElectionError::InvalidData {
+            message: format!("Failed to serialize settings: {}", e),
+            source: Some(ErrorSource::new(e)),
+        })?;
+        let lines: Vec<String> = table.iter().map(|(key, value)| format!("{} = {}", key, value)).collect();
+        let width = lines.iter().map(String::len).max().unwrap_or(0);
+        let mut output = String::new();
+        for ((key, _), line) in table.iter().zip(lines) {
+            match self.sources.get(key) {
+                Some(source) if with_sources => {
+                    output.push_str(&format!("{:<width$}  # {}\n", line, source, width = width))
+                }
+                _ => output.push_str(&format!("{}\n", line)),
+            }
+        }
+        Ok(output)
+    }
+
+    /// Parse a setting, naming its layer if the value is invalid
+    fn parse<T>(&self, key: &str, value: Option<&str>) -> Result<T, ElectionError>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let value = value.ok_or_else(|| ElectionError::ValidationError {
+            message: format!("No value for setting '{}'", key),
+            field: Some(key.to_string()),
+        })?;
+        value.parse::<T>().map_err(|e| {
+            let source = self.sources.get(key).map(ToString::to_string).unwrap_or_default();
+            ElectionError::ValidationError {
+                message: format!("Invalid {} '{}' from {}: {}", key.replace('_', " "), value, source, e),
+                field: Some(key.to_string()),
+            }
+        })
+    }
+}
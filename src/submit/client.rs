@@ -0,0 +1,143 @@
+//! RPC client for dry-running and broadcasting submissions
+
+use crate::error::{ElectionError, ErrorSource};
+use crate::submit::extrinsic::{decode_hex, SigningContext};
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use serde_json::Value;
+
+/// Outcome of a `system_dryRun` call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DryRunOutcome {
+    /// The extrinsic would be included and dispatched successfully
+    Success,
+    /// The extrinsic would be included but dispatch fails (encoded `DispatchError`)
+    DispatchError(String),
+    /// The extrinsic would be rejected by the pool (encoded `TransactionValidityError`)
+    Invalid(String),
+}
+
+impl DryRunOutcome {
+    /// Decode a SCALE-encoded `ApplyExtrinsicResult`
+    pub fn decode(bytes: &[u8]) -> Result<Self, ElectionError> {
+        match bytes {
+            [0, 0, ..] => Ok(DryRunOutcome::Success),
+            [0, 1, rest @ ..] => Ok(DryRunOutcome::DispatchError(format!("0x{}", hex::encode(rest)))),
+            [1, rest @ ..] => Ok(DryRunOutcome::Invalid(format!("0x{}", hex::encode(rest)))),
+            _ => Err(ElectionError::InvalidData {
+                message: format!("Unexpected dry-run result: 0x{}", hex::encode(bytes)),
+                source: None,
+            }),
+        }
+    }
+
+    /// Whether the dry run succeeded
+    pub fn is_success(&self) -> bool {
+        matches!(self, DryRunOutcome::Success)
+    }
+}
+
+/// RPC client for submitting solutions
+pub struct SubmitClient {
+    client: HttpClient,
+    url: String,
+}
+
+impl SubmitClient {
+    /// Create a client for an HTTP RPC endpoint
+    pub fn new(url: impl Into<String>) -> Result<Self, ElectionError> {
+        let url = url.into();
+        let client = HttpClientBuilder::default()
+            .request_timeout(std::time::Duration::from_secs(30))
+            .build(&url)
+            .map_err(|e| ElectionError::RpcError {
+                message: format!("Failed to create RPC client: {}", e),
+                url: url.clone(),
+                source: Some(ErrorSource::new(e)),
+            })?;
+
+        Ok(Self { client, url })
+    }
+
+    /// Fetch runtime versions, genesis hash and the signer's next nonce
+    pub async fn signing_context(
+        &self,
+        account: &str,
+        tip: u128,
+        metadata_hash_extension: bool,
+    ) -> Result<SigningContext, ElectionError> {
+        let version: Value = self.request("state_getRuntimeVersion", Vec::<String>::new()).await?;
+        let read_version = |field: &str| {
+            version
+                .get(field)
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32)
+                .ok_or_else(|| ElectionError::RpcError {
+                    message: format!("Runtime version is missing {}", field),
+                    url: self.url.clone(),
+                    source: None,
+                })
+        };
+        let spec_version = read_version("specVersion")?;
+        let transaction_version = read_version("transactionVersion")?;
+
+        let genesis: Value = self.request("chain_getBlockHash", (0u64,)).await?;
+        let genesis_bytes = decode_hex(genesis.as_str().unwrap_or_default())?;
+        let genesis_hash: [u8; 32] = genesis_bytes.try_into().map_err(|_| ElectionError::RpcError {
+            message: "Invalid genesis hash".to_string(),
+            url: self.url.clone(),
+            source: None,
+        })?;
+
+        let nonce: Value = self.request("system_accountNextIndex", (account,)).await?;
+        let nonce = nonce.as_u64().ok_or_else(|| ElectionError::RpcError {
+            message: "Invalid account nonce".to_string(),
+            url: self.url.clone(),
+            source: None,
+        })? as u32;
+
+        Ok(SigningContext {
+            spec_version,
+            transaction_version,
+            genesis_hash,
+            nonce,
+            tip,
+            metadata_hash_extension,
+        })
+    }
+
+    /// Dry-run an extrinsic against the latest block
+    pub async fn dry_run(&self, extrinsic: &[u8]) -> Result<DryRunOutcome, ElectionError> {
+        let result: Value = self
+            .request("system_dryRun", (format!("0x{}", hex::encode(extrinsic)),))
+            .await?;
+        let bytes = decode_hex(result.as_str().unwrap_or_default())?;
+        DryRunOutcome::decode(&bytes)
+    }
+
+    /// Broadcast an extrinsic, returning its hash
+    pub async fn submit(&self, extrinsic: &[u8]) -> Result<String, ElectionError> {
+        let hash: Value = self
+            .request("author_submitExtrinsic", (format!("0x{}", hex::encode(extrinsic)),))
+            .await?;
+        hash.as_str().map(str::to_string).ok_or_else(|| ElectionError::RpcError {
+            message: "Invalid extrinsic hash response".to_string(),
+            url: self.url.clone(),
+            source: None,
+        })
+    }
+
+    async fn request<P>(&self, method: &str, params: P) -> Result<Value, ElectionError>
+    where
+        P: jsonrpsee::core::traits::ToRpcParams + Send,
+    {
+        self.client
+            .request(method, params)
+            .await
+            .map_err(|e| ElectionError::RpcError {
+                message: format!("{} failed: {}", method, e),
+                url: self.url.clone(),
+                source: Some(ErrorSource::new(e)),
+            })
+    }
+}
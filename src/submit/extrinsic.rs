@@ -0,0 +1,168 @@
+//! Extrinsic construction for `pallet-election-provider-multi-phase` submissions
+
+use crate::error::{ElectionError, ErrorSource};
+use parity_scale_codec::{Compact, Encode};
+use sp_core::crypto::Pair as _;
+use sp_core::sr25519;
+
+/// Extrinsic format version 4
+const EXTRINSIC_VERSION: u8 = 4;
+/// Bit set on the version byte of signed extrinsics
+const SIGNED_BIT: u8 = 0b1000_0000;
+/// Signing payloads longer than this are hashed before signing
+const MAX_UNHASHED_PAYLOAD_LEN: usize = 256;
+
+/// Default call index of `submit_unsigned` in the multi-phase pallet
+pub const DEFAULT_SUBMIT_UNSIGNED_CALL_INDEX: u8 = 0;
+/// Default call index of `submit` in the multi-phase pallet
+pub const DEFAULT_SUBMIT_CALL_INDEX: u8 = 3;
+
+/// Witness passed to `submit_unsigned` (`SolutionOrSnapshotSize`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolutionWitness {
+    /// Number of voters in the snapshot
+    pub voters: u32,
+    /// Number of targets in the snapshot
+    pub targets: u32,
+}
+
+/// Chain state needed to sign an extrinsic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningContext {
+    /// Runtime spec version
+    pub spec_version: u32,
+    /// Runtime transaction version
+    pub transaction_version: u32,
+    /// Genesis block hash
+    pub genesis_hash: [u8; 32],
+    /// Account nonce of the signer
+    pub nonce: u32,
+    /// Tip paid to the block author
+    pub tip: u128,
+    /// Whether the runtime uses the `CheckMetadataHash` signed extension
+    pub metadata_hash_extension: bool,
+}
+
+/// Builds submission extrinsics from a SCALE-encoded `RawSolution`
+#[derive(Debug, Clone)]
+pub struct SubmissionBuilder {
+    pallet_index: u8,
+    submit_call_index: u8,
+    submit_unsigned_call_index: u8,
+    raw_solution: Vec<u8>,
+}
+
+impl SubmissionBuilder {
+    /// Create a builder for a SCALE-encoded `RawSolution` and the pallet's index in the runtime
+    pub fn new(pallet_index: u8, raw_solution: Vec<u8>) -> Self {
+        Self {
+            pallet_index,
+            submit_call_index: DEFAULT_SUBMIT_CALL_INDEX,
+            submit_unsigned_call_index: DEFAULT_SUBMIT_UNSIGNED_CALL_INDEX,
+            raw_solution,
+        }
+    }
+
+    /// Override the call index of `submit`
+    pub fn submit_call_index(mut self, index: u8) -> Self {
+        self.submit_call_index = index;
+        self
+    }
+
+    /// Override the call index of `submit_unsigned`
+    pub fn submit_unsigned_call_index(mut self, index: u8) -> Self {
+        self.submit_unsigned_call_index = index;
+        self
+    }
+
+    /// Encoded `submit(raw_solution)` call
+    pub fn submit_call(&self) -> Vec<u8> {
+        let mut call = vec![self.pallet_index, self.submit_call_index];
+        call.extend_from_slice(&self.raw_solution);
+        call
+    }
+
+    /// Encoded `submit_unsigned(raw_solution, witness)` call
+    pub fn submit_unsigned_call(&self, witness: SolutionWitness) -> Vec<u8> {
+        let mut call = vec![self.pallet_index, self.submit_unsigned_call_index];
+        call.extend_from_slice(&self.raw_solution);
+        Compact(witness.voters).encode_to(&mut call);
+        Compact(witness.targets).encode_to(&mut call);
+        call
+    }
+
+    /// Unsigned extrinsic carrying `submit_unsigned`
+    pub fn unsigned_extrinsic(&self, witness: SolutionWitness) -> Vec<u8> {
+        let mut body = vec![EXTRINSIC_VERSION];
+        body.extend_from_slice(&self.submit_unsigned_call(witness));
+        length_prefixed(body)
+    }
+
+    /// Signed extrinsic carrying `submit`, using an immortal era
+    pub fn signed_extrinsic(&self, signer: &sr25519::Pair, context: &SigningContext) -> Vec<u8> {
+        let call = self.submit_call();
+
+        let mut extra = Vec::new();
+        // Immortal era
+        extra.push(0u8);
+        Compact(context.nonce).encode_to(&mut extra);
+        Compact(context.tip).encode_to(&mut extra);
+        if context.metadata_hash_extension {
+            // CheckMetadataHash mode: disabled
+            extra.push(0u8);
+        }
+
+        let mut additional = Vec::new();
+        context.spec_version.encode_to(&mut additional);
+        context.transaction_version.encode_to(&mut additional);
+        additional.extend_from_slice(&context.genesis_hash);
+        // Immortal transactions are checked against the genesis hash
+        additional.extend_from_slice(&context.genesis_hash);
+        if context.metadata_hash_extension {
+            // No metadata hash
+            additional.push(0u8);
+        }
+
+        let mut payload = call.clone();
+        payload.extend_from_slice(&extra);
+        payload.extend_from_slice(&additional);
+        let signature = if payload.len() > MAX_UNHASHED_PAYLOAD_LEN {
+            signer.sign(&sp_core::hashing::blake2_256(&payload))
+        } else {
+            signer.sign(&payload)
+        };
+
+        let mut body = vec![EXTRINSIC_VERSION | SIGNED_BIT];
+        // MultiAddress::Id
+        body.push(0u8);
+        body.extend_from_slice(signer.public().as_ref());
+        // MultiSignature::Sr25519
+        body.push(1u8);
+        body.extend_from_slice(signature.as_ref());
+        body.extend_from_slice(&extra);
+        body.extend_from_slice(&call);
+        length_prefixed(body)
+    }
+}
+
+/// Load a signing key from a secret URI (mnemonic, hex seed or dev URI such as `//Alice`)
+pub fn signer_from_suri(suri: &str) -> Result<sr25519::Pair, ElectionError> {
+    sr25519::Pair::from_string(suri.trim(), None).map_err(|e| ElectionError::ValidationError {
+        message: format!("Invalid signing key: {:?}", e),
+        field: Some("suri".to_string()),
+    })
+}
+
+/// Decode a 0x-prefixed hex string
+pub fn decode_hex(value: &str) -> Result<Vec<u8>, ElectionError> {
+    hex::decode(value.trim().trim_start_matches("0x")).map_err(|e| ElectionError::InvalidData {
+        message: format!("Invalid hex: {}", e),
+        source: Some(ErrorSource::new(e)),
+    })
+}
+
+fn length_prefixed(body: Vec<u8>) -> Vec<u8> {
+    let mut extrinsic = Compact(body.len() as u32).encode();
+    extrinsic.extend_from_slice(&body);
+    extrinsic
+}
@@ -0,0 +1,14 @@
+//! Solution submission helper for miners (requires the `submit` feature)
+//!
+//! Wraps an exported, SCALE-encoded `RawSolution` in a `submit` (signed) or
+//! `submit_unsigned` extrinsic for `pallet-election-provider-multi-phase`,
+//! dry-runs it over RPC and optionally broadcasts it.
+
+pub mod client;
+pub mod extrinsic;
+
+pub use client::{DryRunOutcome, SubmitClient};
+pub use extrinsic::{
+    signer_from_suri, SigningContext, SolutionWitness, SubmissionBuilder,
+    DEFAULT_SUBMIT_CALL_INDEX, DEFAULT_SUBMIT_UNSIGNED_CALL_INDEX,
+};
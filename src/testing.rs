@@ -0,0 +1,329 @@
+//! Differential testing against `sp-npos-elections` (requires the `testing` feature)
+//!
+//! Generates random elections, runs each through this crate's full pipeline
+//! ([`ElectionEngine`] with its data preparation, solver and result
+//! conversion) and through a direct call into `sp_npos_elections` on the raw
+//! account IDs, and reports every case where the two disagree. Divergences
+//! point at the conversion layers or the round-by-round reimplementation of
+//! sequential Phragmén rather than at upstream.
+//!
+//! Sequential Phragmén and multi-phase are compared with `seq_phragmen`, and
+//! parallel Phragmén with `phragmms`, on winners, backings and supports. MMS
+//! balances its winners with its own max-flow step, so only its winners are
+//! compared with those of `phragmms`.
+//!
+//! Every case is generated from its own seed, reported with any divergence,
+//! so a failing case can be regenerated with [`random_election`].
+
+use crate::algorithms::mms::MMS_BALANCING_ITERATIONS;
+use crate::engine::ElectionEngine;
+use crate::error::ElectionError;
+use crate::models::election_config::ElectionConfiguration;
+use crate::models::election_data::ElectionData;
+use crate::models::election_result::ElectionResult;
+use crate::models::nominator::Nominator;
+use crate::models::validator::ValidatorCandidate;
+use crate::types::AlgorithmType;
+use serde::Serialize;
+use sp_npos_elections::BalancingConfig;
+use sp_runtime::Perbill;
+use std::collections::BTreeMap;
+
+/// How many random elections to generate and how large they get
+#[derive(Debug, Clone)]
+pub struct SelftestConfig {
+    /// Number of random elections
+    pub cases: usize,
+    /// Seed the per-case seeds are derived from
+    pub seed: u64,
+    /// Most candidates in an election (at least 1)
+    pub max_candidates: usize,
+    /// Most voters in an election, self-votes included
+    pub max_voters: usize,
+    /// Most targets a nominator votes for
+    pub max_votes: usize,
+    /// Algorithms to compare
+    pub algorithms: Vec<AlgorithmType>,
+}
+
+impl Default for SelftestConfig {
+    fn default() -> Self {
+        Self {
+            cases: 100,
+            seed: 0,
+            max_candidates: 40,
+            max_voters: 200,
+            max_votes: 16,
+            algorithms: vec![
+                AlgorithmType::SequentialPhragmen,
+                AlgorithmType::ParallelPhragmen,
+                AlgorithmType::MultiPhase,
+                AlgorithmType::Mms,
+            ],
+        }
+    }
+}
+
+/// A case where the pipeline and the reference disagree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Divergence {
+    /// Index of the case in the run
+    pub case: usize,
+    /// Seed that regenerates the case with [`random_election`]
+    pub case_seed: u64,
+    /// Algorithm that diverged
+    pub algorithm: AlgorithmType,
+    /// Number of candidates in the case
+    pub candidates: usize,
+    /// Number of voters in the case
+    pub voters: usize,
+    /// Number of winners asked for
+    pub to_elect: usize,
+    /// First difference found
+    pub detail: String,
+}
+
+/// Outcome of a differential testing run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelftestReport {
+    /// Seed the run was generated from
+    pub seed: u64,
+    /// Number of random elections generated
+    pub cases: usize,
+    /// Number of pipeline runs compared with the reference
+    pub comparisons: usize,
+    /// Every disagreement found
+    pub divergences: Vec<Divergence>,
+}
+
+impl SelftestReport {
+    /// Whether every comparison agreed
+    pub fn passed(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Generate `config.cases` random elections and compare every algorithm on each
+pub fn run_selftest(config: &SelftestConfig) -> Result<SelftestReport, ElectionError> {
+    let mut seeds = SplitMix64(config.seed);
+    let mut report = SelftestReport {
+        seed: config.seed,
+        cases: config.cases,
+        comparisons: 0,
+        divergences: Vec::new(),
+    };
+    for case in 0..config.cases {
+        let case_seed = seeds.next_u64();
+        let (data, to_elect) = random_election(case_seed, config);
+        for &algorithm in &config.algorithms {
+            report.comparisons += 1;
+            if let Some(detail) = compare_with_reference(&data, to_elect, algorithm)? {
+                report.divergences.push(Divergence {
+                    case,
+                    case_seed,
+                    algorithm,
+                    candidates: data.candidates.len(),
+                    voters: data.nominators.len(),
+                    to_elect,
+                    detail,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Random election data and number of winners for `seed`
+///
+/// Candidates carry no stake of their own; as in an on-chain snapshot, most
+/// back themselves with a self-vote. Nominators vote for distinct candidates,
+/// with stakes spread over several orders of magnitude. No more winners are
+/// asked for than there are backed candidates: `phragmms` would elect fewer,
+/// which the pipeline rejects by design.
+pub fn random_election(seed: u64, config: &SelftestConfig) -> (ElectionData, usize) {
+    let mut rng = SplitMix64(seed);
+    let candidate_count = 1 + rng.below(config.max_candidates.max(1));
+    let candidates: Vec<String> = (0..candidate_count).map(|i| format!("candidate-{}", i)).collect();
+
+    let mut nominators = Vec::new();
+    for candidate in &candidates {
+        if nominators.len() < config.max_voters && rng.below(4) != 0 {
+            nominators.push(Nominator {
+                targets: vec![candidate.clone()],
+                ..Nominator::new(candidate.clone(), rng.stake())
+            });
+        }
+    }
+    let nominator_count = rng.below(config.max_voters.saturating_sub(nominators.len()) + 1);
+    for i in 0..nominator_count {
+        let vote_count = 1 + rng.below(config.max_votes.max(1).min(candidate_count));
+        // Partial Fisher-Yates shuffle picks distinct targets
+        let mut pool: Vec<usize> = (0..candidate_count).collect();
+        let targets = (0..vote_count)
+            .map(|picked| {
+                let swap = picked + rng.below(candidate_count - picked);
+                pool.swap(picked, swap);
+                candidates[pool[picked]].clone()
+            })
+            .collect();
+        nominators.push(Nominator {
+            targets,
+            ..Nominator::new(format!("nominator-{}", i), rng.stake())
+        });
+    }
+    if nominators.is_empty() {
+        nominators.push(Nominator {
+            targets: vec![candidates[0].clone()],
+            ..Nominator::new(candidates[0].clone(), rng.stake())
+        });
+    }
+    let backed: std::collections::HashSet<&String> = nominators.iter().flat_map(|n| &n.targets).collect();
+    let to_elect = 1 + rng.below(backed.len());
+
+    let data = ElectionData {
+        candidates: candidates.into_iter().map(|id| ValidatorCandidate::new(id, 0)).collect(),
+        nominators,
+        invulnerables: Vec::new(),
+        metadata: None,
+        ..ElectionData::new()
+    };
+    (data, to_elect)
+}
+
+/// Run `algorithm` through the pipeline and the reference, describing the first difference
+pub fn compare_with_reference(
+    data: &ElectionData,
+    to_elect: usize,
+    algorithm: AlgorithmType,
+) -> Result<Option<String>, ElectionError> {
+    let config = ElectionConfiguration::builder()
+        .algorithm(algorithm)
+        .active_set_size(to_elect as u32)
+        .build()?;
+    // Failing where the reference succeeds, or the other way round, is a divergence too
+    let (pipeline, reference) = match (
+        ElectionEngine::new().execute(&config, data),
+        reference_outcome(data, to_elect, algorithm),
+    ) {
+        (Ok(pipeline), Ok(reference)) => (Outcome::from_result(&pipeline), reference),
+        (Err(_), Err(_)) => return Ok(None),
+        (Err(e), Ok(_)) => return Ok(Some(format!("pipeline failed: {}", e))),
+        (Ok(_), Err(e)) => return Ok(Some(format!("pipeline succeeded, reference failed: {}", e))),
+    };
+
+    if algorithm == AlgorithmType::Mms {
+        let winners = |outcome: &Outcome| outcome.winners.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        return Ok(
+            (winners(&pipeline) != winners(&reference))
+                .then(|| format!("winners {:?}, reference {:?}", winners(&pipeline), winners(&reference))),
+        );
+    }
+    if pipeline.winners != reference.winners {
+        return Ok(Some(format!("winners {:?}, reference {:?}", pipeline.winners, reference.winners)));
+    }
+    for (winner, support) in &reference.supports {
+        let ours = pipeline.supports.get(winner);
+        if ours != Some(support) {
+            return Ok(Some(format!("support of {}: {:?}, reference {:?}", winner, ours, support)));
+        }
+    }
+    if let Some(extra) = pipeline.supports.keys().find(|winner| !reference.supports.contains_key(*winner)) {
+        return Ok(Some(format!("support of {} missing from the reference", extra)));
+    }
+    Ok(None)
+}
+
+/// Winners with their backing, and each backed candidate's total and voters
+#[derive(Debug, PartialEq, Eq)]
+struct Outcome {
+    winners: Vec<(String, u128)>,
+    supports: BTreeMap<String, (u128, Vec<(String, u128)>)>,
+}
+
+impl Outcome {
+    fn from_result(result: &ElectionResult) -> Self {
+        let winners = result
+            .selected_validators
+            .iter()
+            .map(|validator| (validator.account_id.clone(), validator.total_backing_stake))
+            .collect();
+        let mut supports: BTreeMap<String, (u128, Vec<(String, u128)>)> = BTreeMap::new();
+        // Zero portions are dropped when upstream converts ratios to stake
+        for allocation in result.stake_distribution.iter().filter(|allocation| allocation.proportion > 0.0) {
+            let support = supports.entry(allocation.validator_id.clone()).or_default();
+            support.0 += allocation.amount;
+            support.1.push((allocation.nominator_id.clone(), allocation.amount));
+        }
+        Self::sorted(winners, supports)
+    }
+
+    fn sorted(winners: Vec<(String, u128)>, mut supports: BTreeMap<String, (u128, Vec<(String, u128)>)>) -> Self {
+        for (_, voters) in supports.values_mut() {
+            voters.sort();
+        }
+        Self { winners, supports }
+    }
+}
+
+/// Solve with `sp_npos_elections` directly on the account IDs
+fn reference_outcome(data: &ElectionData, to_elect: usize, algorithm: AlgorithmType) -> Result<Outcome, ElectionError> {
+    let candidates: Vec<String> = data.candidates.iter().map(|c| c.account_id.clone()).collect();
+    let voters: Vec<(String, u64, Vec<String>)> = data
+        .nominators
+        .iter()
+        .map(|n| (n.account_id.clone(), n.stake as u64, n.targets.clone()))
+        .collect();
+    let stakes: BTreeMap<String, u64> = voters.iter().map(|(who, stake, _)| (who.clone(), *stake)).collect();
+
+    let solution = match algorithm {
+        AlgorithmType::SequentialPhragmen | AlgorithmType::MultiPhase => {
+            sp_npos_elections::seq_phragmen::<String, Perbill>(to_elect, candidates, voters, None)
+        }
+        AlgorithmType::ParallelPhragmen => {
+            sp_npos_elections::phragmms::<String, Perbill>(to_elect, candidates, voters, None)
+        }
+        AlgorithmType::Mms => sp_npos_elections::phragmms::<String, Perbill>(
+            to_elect,
+            candidates,
+            voters,
+            Some(BalancingConfig { iterations: MMS_BALANCING_ITERATIONS, tolerance: 0 }),
+        ),
+    }
+    .map_err(|e| ElectionError::AlgorithmError {
+        message: format!("Reference solver failed: {:?}", e),
+        algorithm,
+    })?;
+
+    let staked = sp_npos_elections::assignment_ratio_to_staked(solution.assignments, |who: &String| {
+        stakes.get(who).copied().unwrap_or_default()
+    });
+    let supports = sp_npos_elections::to_supports(&staked)
+        .into_iter()
+        .map(|(winner, support)| (winner, (support.total, support.voters)))
+        .collect();
+    Ok(Outcome::sorted(solution.winners, supports))
+}
+
+/// Small deterministic generator, so a seed always yields the same case
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..bound`; `bound` must be positive
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Stake between 1 and 10^15 plancks, log-uniform
+    fn stake(&mut self) -> u128 {
+        let magnitude = 10u128.pow(self.below(15) as u32);
+        magnitude + (self.next_u64() as u128 % (magnitude * 9))
+    }
+}
@@ -12,6 +12,8 @@ pub enum AlgorithmType {
     ParallelPhragmen,
     /// Multi-phase election algorithm
     MultiPhase,
+    /// Experimental maximin support approximation (PhragMMS with max-flow balancing)
+    Mms,
 }
 
 impl std::str::FromStr for AlgorithmType {
@@ -22,7 +24,11 @@ impl std::str::FromStr for AlgorithmType {
             "sequential-phragmen" | "sequential" => Ok(AlgorithmType::SequentialPhragmen),
             "parallel-phragmen" | "parallel" => Ok(AlgorithmType::ParallelPhragmen),
             "multi-phase" | "multiphase" => Ok(AlgorithmType::MultiPhase),
-            _ => Err(format!("Unknown algorithm type: {}", s)),
+            "mms" | "maximin-support" => Ok(AlgorithmType::Mms),
+            _ => Err(format!(
+                "Unknown algorithm type: {} (expected sequential-phragmen, parallel-phragmen, multi-phase or mms)",
+                s
+            )),
         }
     }
 }
@@ -33,6 +39,44 @@ impl std::fmt::Display for AlgorithmType {
             AlgorithmType::SequentialPhragmen => write!(f, "sequential-phragmen"),
             AlgorithmType::ParallelPhragmen => write!(f, "parallel-phragmen"),
             AlgorithmType::MultiPhase => write!(f, "multi-phase"),
+            AlgorithmType::Mms => write!(f, "mms"),
+        }
+    }
+}
+
+/// Kind of election being simulated
+///
+/// Staking elections select validators backed by nominators. Council
+/// elections (`pallet-elections-phragmen`) select members backed by voters'
+/// locked balances; candidates have no self-stake, and candidates nobody
+/// backs do not take a seat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ElectionKind {
+    /// NPoS validator election (`pallet-staking`)
+    #[default]
+    Staking,
+    /// Council-style election (`pallet-elections-phragmen`)
+    Council,
+}
+
+impl std::str::FromStr for ElectionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "staking" => Ok(ElectionKind::Staking),
+            "council" | "elections-phragmen" => Ok(ElectionKind::Council),
+            _ => Err(format!("Unknown election kind: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ElectionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElectionKind::Staking => write!(f, "staking"),
+            ElectionKind::Council => write!(f, "council"),
         }
     }
 }
@@ -116,12 +116,11 @@ pub fn run_benchmark_with_algorithm(
     use offline_election::models::election_config::ElectionConfiguration;
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: active_set_size as u32,
-        algorithm,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(algorithm)
+        .active_set_size(active_set_size as u32)
+        .build()
+        .unwrap();
     
     let (result, duration) = measure_execution_time(|| {
         engine.execute(&config, election_data)
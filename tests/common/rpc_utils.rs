@@ -72,10 +72,12 @@ pub async fn fetch_chain_snapshot(
         algorithm_used: AlgorithmType::SequentialPhragmen,
         execution_metadata: ExecutionMetadata {
             block_number: Some(block_number),
-            execution_timestamp: None,
             data_source: Some("chain_snapshot".to_string()),
+            ..Default::default()
         },
         diagnostics: None,
+        issues: Vec::new(),
+        raw_solution: None,
     };
     
     Ok(ChainSnapshot {
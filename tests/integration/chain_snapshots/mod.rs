@@ -48,12 +48,12 @@ pub async fn run_chain_snapshot_test_from_fixture(
     };
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: snapshot.expected_result.selected_validators.len() as u32,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: Some(snapshot.metadata.block_number),
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(snapshot.expected_result.selected_validators.len() as u32)
+        .block_number(snapshot.metadata.block_number)
+        .build()
+        .unwrap();
     
     // Execute election
     let result = match engine.execute(&config, &snapshot.election_data) {
@@ -24,12 +24,12 @@ async fn test_westend_block_1() {
         .expect("Failed to load chain snapshot");
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: snapshot.expected_result.selected_validators.len() as u32,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: Some(snapshot.metadata.block_number),
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(snapshot.expected_result.selected_validators.len() as u32)
+        .block_number(snapshot.metadata.block_number)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &snapshot.election_data)
         .expect("Election execution should succeed");
@@ -52,12 +52,12 @@ async fn test_westend_block_2() {
         .expect("Failed to load chain snapshot");
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: snapshot.expected_result.selected_validators.len() as u32,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: Some(snapshot.metadata.block_number),
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(snapshot.expected_result.selected_validators.len() as u32)
+        .block_number(snapshot.metadata.block_number)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &snapshot.election_data)
         .expect("Election execution should succeed");
@@ -52,12 +52,11 @@ mod test_runner {
         let fixture = load_test_fixture(&path)?;
         
         let engine = ElectionEngine::new();
-        let config = ElectionConfiguration {
-            active_set_size: 3, // Default, may be overridden by test
-            algorithm: fixture.metadata.algorithm,
-            overrides: None,
-            block_number: None,
-        };
+        let config = ElectionConfiguration::builder()
+            .algorithm(fixture.metadata.algorithm)
+            .active_set_size(3) // Default, may be overridden by test
+            .build()
+            .unwrap();
         
         // Validate input data
         let validation_result = fixture.input.validate();
@@ -43,12 +43,11 @@ fn test_algorithm_convergence_small_dataset() {
     
     election_data.add_nominator(nominator).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 3,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(3)
+        .build()
+        .unwrap();
     
     // Algorithm should converge and produce valid results
     let result = engine.execute(&config, &election_data)
@@ -84,12 +83,11 @@ fn test_algorithm_convergence_large_dataset() {
     let engine = ElectionEngine::new();
     let election_data = generate_synthetic_election_data(100, 1_000);
     
-    let config = ElectionConfiguration {
-        active_set_size: 50,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(50)
+        .build()
+        .unwrap();
     
     // Algorithm should converge even with larger datasets
     let result = engine.execute(&config, &election_data)
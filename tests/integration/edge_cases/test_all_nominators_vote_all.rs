@@ -54,12 +54,11 @@ fn test_all_nominators_vote_all_candidates() {
     election_data.add_nominator(nominator1).unwrap();
     election_data.add_nominator(nominator2).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 3,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(3)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
@@ -29,11 +29,11 @@ fn test_empty_voting_edges_should_succeed() {
         election_data.add_nominator(nominator).unwrap();
     }
     
-    let config = ElectionConfiguration {
-        active_set_size: 2,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
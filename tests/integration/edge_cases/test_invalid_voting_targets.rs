@@ -30,12 +30,11 @@ fn test_nominator_votes_for_nonexistent_candidate() {
     
     election_data.add_nominator(nominator).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 1,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(1)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
@@ -75,12 +74,11 @@ fn test_nominator_votes_for_multiple_nonexistent_candidates() {
     
     election_data.add_nominator(nominator).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 1,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(1)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
@@ -33,11 +33,11 @@ fn test_max_active_set_size_should_succeed() {
     }
     
     // Set active set size equal to candidate count
-    let config = ElectionConfiguration {
-        active_set_size: candidate_count,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(candidate_count)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
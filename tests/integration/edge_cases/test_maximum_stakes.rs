@@ -55,12 +55,11 @@ fn test_maximum_u128_stakes() {
     election_data.add_nominator(nominator1).unwrap();
     election_data.add_nominator(nominator2).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 3,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(3)
+        .build()
+        .unwrap();
     
     // The election should handle maximum stake values without panicking
     // Note: This might take longer to compute due to large numbers
@@ -107,12 +106,11 @@ fn test_very_large_stakes() {
     
     election_data.add_nominator(nominator).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 1,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(1)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
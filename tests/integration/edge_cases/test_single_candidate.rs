@@ -25,11 +25,11 @@ fn test_single_candidate_should_succeed() {
     };
     election_data.add_nominator(nominator).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 1,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(1)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
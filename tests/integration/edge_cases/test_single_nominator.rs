@@ -30,11 +30,11 @@ fn test_single_nominator_should_succeed() {
     };
     election_data.add_nominator(nominator).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 2,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
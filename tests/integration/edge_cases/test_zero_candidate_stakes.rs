@@ -30,11 +30,11 @@ fn test_zero_candidate_stakes_should_fail() {
     };
     election_data.add_nominator(nominator).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 3,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(3)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
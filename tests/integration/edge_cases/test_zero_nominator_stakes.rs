@@ -52,12 +52,11 @@ fn test_all_nominators_zero_stake() {
     election_data.add_nominator(nominator1).unwrap();
     election_data.add_nominator(nominator2).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 3,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(3)
+        .build()
+        .unwrap();
     
     // Election should succeed even with zero-stake nominators
     // The algorithm should select validators based on their own stake
@@ -17,11 +17,11 @@ fn test_zero_nominators_should_succeed() {
     };
     election_data.add_candidate(candidate).unwrap();
     
-    let config = ElectionConfiguration {
-        active_set_size: 1,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(1)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &election_data);
     
@@ -17,12 +17,11 @@ async fn test_concurrent_election_execution() {
     println!("Testing {} concurrent election executions", CONCURRENT_TASKS);
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 50,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(50)
+        .build()
+        .unwrap();
     
     // Generate election data
     let election_data = generate_large_scale_election_data(
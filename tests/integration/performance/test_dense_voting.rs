@@ -52,12 +52,11 @@ fn test_dense_voting_patterns() {
     }
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 100,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(100)
+        .build()
+        .unwrap();
     
     println!("Executing election...");
     let (result, duration) = measure_execution_time(|| {
@@ -24,12 +24,11 @@ fn test_large_scale_5k_candidates_50k_nominators() {
     );
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 100, // Standard active set size
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(100) // Standard active set size
+        .build()
+        .unwrap();
     
     println!("Executing election...");
     let (result, duration) = measure_execution_time(|| {
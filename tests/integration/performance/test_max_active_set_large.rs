@@ -24,12 +24,11 @@ fn test_max_active_set_size_large_dataset() {
     );
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: ACTIVE_SET_SIZE,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(ACTIVE_SET_SIZE)
+        .build()
+        .unwrap();
     
     println!("Executing election...");
     let (result, duration) = measure_execution_time(|| {
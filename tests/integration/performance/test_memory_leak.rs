@@ -19,12 +19,11 @@ fn test_memory_leak_100_consecutive_elections() {
     println!("Dataset: {} candidates, {} nominators", CANDIDATE_COUNT, NOMINATOR_COUNT);
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 50,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(50)
+        .build()
+        .unwrap();
     
     let mut execution_times = Vec::new();
     let mut memory_measurements = Vec::new();
@@ -16,12 +16,11 @@ fn test_deterministic_results_multiple_runs() {
     
     let election_data = generate_synthetic_election_data(CANDIDATE_COUNT, NOMINATOR_COUNT);
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 25,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(25)
+        .build()
+        .unwrap();
     
     // Run election multiple times
     let mut results = Vec::new();
@@ -49,12 +48,11 @@ fn test_deterministic_results_different_instances() {
     println!("Testing deterministic results with different engine instances");
     
     let election_data = generate_synthetic_election_data(CANDIDATE_COUNT, NOMINATOR_COUNT);
-    let config = ElectionConfiguration {
-        active_set_size: 15,
-        algorithm: AlgorithmType::SequentialPhragmen,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(15)
+        .build()
+        .unwrap();
     
     // Create multiple engine instances
     let engine1 = ElectionEngine::new();
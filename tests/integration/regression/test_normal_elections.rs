@@ -21,12 +21,11 @@ fn test_regression_normal_election_5x5() {
         .expect("Failed to load test fixture");
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 3,
-        algorithm: fixture.metadata.algorithm,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(fixture.metadata.algorithm)
+        .active_set_size(3)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &fixture.input)
         .expect("Election execution should succeed");
@@ -50,12 +49,11 @@ fn test_regression_normal_election_10x10() {
         .expect("Failed to load test fixture");
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 5,
-        algorithm: fixture.metadata.algorithm,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(fixture.metadata.algorithm)
+        .active_set_size(5)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &fixture.input)
         .expect("Election execution should succeed");
@@ -79,12 +77,11 @@ fn test_regression_normal_election_20x20() {
         .expect("Failed to load test fixture");
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 10,
-        algorithm: fixture.metadata.algorithm,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(fixture.metadata.algorithm)
+        .active_set_size(10)
+        .build()
+        .unwrap();
     
     let result = engine.execute(&config, &fixture.input)
         .expect("Election execution should succeed");
@@ -16,12 +16,11 @@ pub fn run_regression_test_from_fixture(fixture_path: &str) -> Result<(), String
     let fixture = load_test_fixture(&path)?;
     
     let engine = ElectionEngine::new();
-    let config = ElectionConfiguration {
-        active_set_size: 100, // Default, may be overridden by fixture
-        algorithm: fixture.metadata.algorithm,
-        overrides: None,
-        block_number: None,
-    };
+    let config = ElectionConfiguration::builder()
+        .algorithm(fixture.metadata.algorithm)
+        .active_set_size(100) // Default, may be overridden by fixture
+        .build()
+        .unwrap();
     
     // Execute election
     let result = engine.execute(&config, &fixture.input)
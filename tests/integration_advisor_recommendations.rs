@@ -0,0 +1,75 @@
+//! Advisor test: sweeping set sizes and nomination caps against targets
+
+use offline_election::advisor::{AdvisorTargets, ParameterAdvisor, ParameterGrid};
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_data::ElectionData;
+
+/// Six candidates; one nominator per candidate with 600, 500 ... 100, and a
+/// large nominator naming every candidate, weakest first
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    let candidates: Vec<String> = (0..6).map(|i| format!("c{}", i)).collect();
+    for (index, candidate) in candidates.iter().enumerate() {
+        builder
+            .add_candidate(candidate.clone(), 0).unwrap()
+            .add_nominator(format!("n{}", index), 600 - 100 * index as u128, vec![candidate.clone()]).unwrap();
+    }
+    builder
+        .add_nominator("whale".to_string(), 1_200, candidates.iter().rev().cloned().collect()).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_ranges_meet_the_minimum_backing() {
+    let data = election_data();
+    let grid = ParameterGrid::new([2, 3, 4, 5, 6]);
+    let targets = AdvisorTargets { min_nakamoto_coefficient: None, min_backing: Some(400) };
+
+    let recommendation = ParameterAdvisor::new().recommend(&data, &grid, &targets).unwrap();
+    assert_eq!(recommendation.points.len(), 5);
+    // Minimum backing only falls as seats are added
+    let minimums: Vec<u128> = recommendation.points.iter().map(|p| p.minimal_backing).collect();
+    assert!(minimums.windows(2).all(|w| w[0] >= w[1]));
+    for point in &recommendation.points {
+        assert_eq!(point.meets_targets, point.minimal_backing >= 400);
+    }
+    assert_eq!(recommendation.ranges.len(), 1);
+    assert_eq!(recommendation.ranges[0].min_set_size, 2);
+    let suggested = recommendation.suggested.unwrap();
+    assert!(suggested.meets_targets);
+    // The most decentralized qualifying set, the strongest backed on ties
+    let best = recommendation.points.iter().filter(|p| p.meets_targets).map(|p| p.nakamoto_coefficient).max();
+    assert_eq!(Some(suggested.nakamoto_coefficient), best);
+    assert!(suggested.set_size <= recommendation.ranges[0].max_set_size);
+}
+
+#[test]
+fn test_nomination_caps_limit_the_large_nominator() {
+    let data = election_data();
+    let grid = ParameterGrid::new([6]).nomination_caps([1, 6]);
+
+    let recommendation = ParameterAdvisor::new().recommend(&data, &grid, &AdvisorTargets::default()).unwrap();
+    let capped = &recommendation.points[0];
+    let uncapped = &recommendation.points[1];
+    assert_eq!(capped.nomination_cap, Some(1));
+    // With one target the whale backs only the weakest candidate
+    assert_eq!(capped.minimal_backing, 200);
+    assert!(uncapped.minimal_backing > capped.minimal_backing);
+    assert!(recommendation.is_satisfiable());
+}
+
+#[test]
+fn test_unreachable_targets_and_invalid_grids() {
+    let data = election_data();
+    let targets = AdvisorTargets { min_nakamoto_coefficient: Some(10), min_backing: None };
+    let recommendation = ParameterAdvisor::new()
+        .recommend(&data, &ParameterGrid::new([3, 6]), &targets)
+        .unwrap();
+    assert!(recommendation.ranges.is_empty());
+    assert!(!recommendation.is_satisfiable());
+
+    assert!(ParameterAdvisor::new().recommend(&data, &ParameterGrid::new([]), &targets).is_err());
+    assert!(ParameterAdvisor::new()
+        .recommend(&data, &ParameterGrid::new([3]).nomination_caps([0]), &targets)
+        .is_err());
+}
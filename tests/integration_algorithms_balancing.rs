@@ -0,0 +1,82 @@
+//! Algorithm test: configurable stake balancing and its convergence metadata
+
+use offline_election::diagnostics::FindingCode;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+
+/// v1 backs both "a" and "b"; only "a" has a second backer
+fn shared_voter_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_nominator("v1".to_string(), 100, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("v2".to_string(), 10, vec!["a".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+/// A chain a - b - c - d of voters sharing targets, which takes several
+/// rounds to settle
+fn chain_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in ["a", "b", "c", "d"] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+    }
+    builder
+        .add_nominator("v1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("v2".to_string(), 1_000, vec!["b".to_string(), "c".to_string()]).unwrap()
+        .add_nominator("v3".to_string(), 1_000, vec!["c".to_string(), "d".to_string()]).unwrap()
+        .add_nominator("v4".to_string(), 10, vec!["d".to_string()]).unwrap()
+        .add_nominator("v5".to_string(), 3_000, vec!["a".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_balancing_converges_to_even_backings() {
+    let data = shared_voter_data();
+    let config = ElectionConfiguration::builder().active_set_size(2).balancing(10, 0).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    let backings: Vec<u128> = result.selected_validators.iter().map(|v| v.total_backing_stake).collect();
+    assert_eq!(backings, vec![55, 55]);
+    let metadata = &result.execution_metadata;
+    assert_eq!(metadata.balancing_converged, Some(true));
+    assert_eq!(metadata.balancing_residual, Some(0));
+    assert!(metadata.balancing_iterations.unwrap() >= 1 && metadata.balancing_iterations.unwrap() <= 10);
+
+    // Without balancing nothing is recorded beyond the zero iteration count
+    let unbalanced = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(2).build().unwrap(), &data)
+        .unwrap();
+    assert_eq!(unbalanced.execution_metadata.balancing_iterations, Some(0));
+    assert_eq!(unbalanced.execution_metadata.balancing_converged, None);
+
+    let invalid = ElectionConfiguration::builder().active_set_size(2).balancing(0, 0).build();
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn test_iteration_cap_is_flagged_in_diagnostics() {
+    let data = chain_data();
+    let engine = ElectionEngine::new();
+
+    let capped = ElectionConfiguration::builder().active_set_size(4).balancing(1, 0).build().unwrap();
+    let result = engine.execute_with_diagnostics(&capped, &data, true).unwrap();
+    let metadata = &result.execution_metadata;
+    assert_eq!(metadata.balancing_iterations, Some(1));
+    assert_eq!(metadata.balancing_converged, Some(false));
+    assert!(metadata.balancing_residual.unwrap() > 0);
+    let diagnostics = result.diagnostics.as_ref().unwrap();
+    let findings: Vec<_> = diagnostics.findings_with_code(FindingCode::BalancingIterationCap).collect();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].data["iterations"], 1);
+
+    // A loose tolerance lets the same run count as converged
+    let loose = ElectionConfiguration::builder().active_set_size(4).balancing(1, 1_000_000).build().unwrap();
+    let result = engine.execute_with_diagnostics(&loose, &data, true).unwrap();
+    assert_eq!(result.execution_metadata.balancing_converged, Some(true));
+    let diagnostics = result.diagnostics.as_ref().unwrap();
+    assert_eq!(diagnostics.findings_with_code(FindingCode::BalancingIterationCap).count(), 0);
+}
@@ -0,0 +1,70 @@
+//! Algorithm test: experimental MMS algorithm and maximin support gap
+
+use clap::Parser;
+use offline_election::algorithms::mms::balance_optimally;
+use offline_election::algorithms::prepared::PreparedElectionData;
+use offline_election::cli::RunCommand;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::types::AlgorithmType;
+
+/// "a" has a dedicated backer; "b" depends on the shared voter
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("v1".to_string(), 100, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("v2".to_string(), 10, vec!["a".to_string()]).unwrap()
+        .add_nominator("v3".to_string(), 20, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_balance_optimally_equalizes_backings() {
+    let data = election_data();
+    let prepared = PreparedElectionData::new(&data);
+
+    let balanced = balance_optimally(&prepared, &[0, 1]);
+    assert_eq!(balanced.minimal_support, 55);
+    assert_eq!(balanced.backings, vec![55, 55]);
+    let allocated: u128 = balanced.allocations.iter().map(|(_, _, amount)| amount).sum();
+    assert_eq!(allocated, 110);
+}
+
+#[test]
+fn test_mms_reaches_at_least_the_phragmen_minimal_support() {
+    let data = election_data();
+    let engine = ElectionEngine::new();
+    let config = ElectionConfiguration::builder().active_set_size(2).maximin_gap(true).build().unwrap();
+    let phragmen = engine.execute_with_diagnostics(&config, &data, true).unwrap();
+    let mms = engine
+        .execute(
+            &ElectionConfiguration::builder().active_set_size(2).algorithm(AlgorithmType::Mms).build().unwrap(),
+            &data,
+        )
+        .unwrap();
+
+    assert_eq!(mms.algorithm_used, AlgorithmType::Mms);
+    let mms_minimal = mms.score().minimal_stake;
+    assert!(mms_minimal >= phragmen.score().minimal_stake);
+
+    let gap = phragmen.diagnostics.as_ref().unwrap().maximin_gap().unwrap();
+    assert_eq!(gap.mms_minimal_support, mms_minimal);
+    assert!(gap.upper_bound >= gap.mms_minimal_support);
+    assert!(gap.balanced_minimal_support >= gap.minimal_support);
+    assert!(gap.gap_bps <= 10_000);
+}
+
+#[test]
+fn test_maximin_gap_is_only_computed_when_asked_for() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &data, true).unwrap();
+    assert!(result.diagnostics.unwrap().maximin_gap().is_none());
+    assert!(RunCommand::try_parse_from(["run", "--synthetic", "--maximin-gap"]).is_err());
+    assert!(RunCommand::try_parse_from(["run", "--synthetic", "--diagnostics", "--maximin-gap"]).unwrap().maximin_gap);
+}
@@ -0,0 +1,95 @@
+//! Algorithm test: round-by-round sequential Phragmén and its progress events
+
+use offline_election::algorithms::phragmen::seq_phragmen;
+use offline_election::algorithms::prepared::PreparedElectionData;
+use offline_election::cli::format_progress;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::progress::RunControl;
+use offline_election::types::AlgorithmType;
+use offline_election::{ElectionPhase, ProgressEvent};
+use sp_runtime::Perbill;
+use std::sync::{Arc, Mutex};
+
+/// 40 candidates and 300 nominators with pseudo-random stakes and votes,
+/// including repeated votes and a zero-stake nominator
+fn election_data(seed: u64) -> ElectionData {
+    let mut state = seed;
+    let mut next = move |bound: u64| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) % bound
+    };
+
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in 0..40 {
+        builder.add_candidate(format!("c{}", candidate), next(1_000) as u128).unwrap();
+    }
+    for nominator in 0..300 {
+        let targets = (0..1 + next(16)).map(|_| format!("c{}", next(40))).collect();
+        let stake = if nominator == 0 { 0 } else { 1 + next(1_000_000_000) as u128 };
+        builder.add_nominator(format!("n{}", nominator), stake, targets).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_round_by_round_phragmen_matches_upstream() {
+    for seed in [1, 7, 42] {
+        let data = election_data(seed);
+        let prepared = PreparedElectionData::new(&data);
+        let voters: Vec<(u32, u64, Vec<u32>)> = (0..prepared.voter_count())
+            .map(|voter| (voter as u32, prepared.voter_stake(voter), prepared.voter_targets(voter).to_vec()))
+            .collect();
+
+        for to_elect in [1, 10, 25, 40, 60] {
+            let upstream = sp_npos_elections::seq_phragmen::<u32, Perbill>(
+                to_elect,
+                (0..prepared.candidate_count() as u32).collect(),
+                voters.clone(),
+                None,
+            )
+            .unwrap();
+            let ours = seq_phragmen(&prepared, to_elect, AlgorithmType::SequentialPhragmen, &RunControl::new()).unwrap();
+
+            assert_eq!(ours.winners, upstream.winners, "seed {} size {}", seed, to_elect);
+            assert_eq!(ours.assignments, upstream.assignments, "seed {} size {}", seed, to_elect);
+        }
+    }
+}
+
+#[test]
+fn test_engine_reports_selection_and_balancing_rounds() {
+    let data = election_data(3);
+    let events: Arc<Mutex<Vec<ProgressEvent>>> = Arc::default();
+    let sink = Arc::clone(&events);
+    let engine = ElectionEngine::new().with_progress_observer(move |event| sink.lock().unwrap().push(event.clone()));
+
+    let config = ElectionConfiguration::builder().active_set_size(10).balancing(3, 0).build().unwrap();
+    let result = engine.execute(&config, &data).unwrap();
+    let events = events.lock().unwrap();
+
+    let selection: Vec<&ProgressEvent> = events.iter().filter(|e| e.phase == ElectionPhase::Selection).collect();
+    assert_eq!(selection.len(), 10);
+    for (index, event) in selection.iter().enumerate() {
+        assert_eq!((event.round, event.total_rounds), (index as u32 + 1, 10));
+        assert!(event.minimum_support.unwrap() > 0);
+    }
+    // Later winners are elected at higher scores, so the guarantee only shrinks
+    assert!(selection.windows(2).all(|w| w[0].minimum_support >= w[1].minimum_support));
+    assert!(selection.windows(2).all(|w| w[0].elapsed <= w[1].elapsed));
+    let line = format_progress(selection[0], None);
+    assert!(line.starts_with("selection round 1/10 ("), "{}", line);
+    assert!(line.ends_with(" plancks)"), "{}", line);
+
+    let balancing: Vec<&ProgressEvent> = events.iter().filter(|e| e.phase == ElectionPhase::Balancing).collect();
+    assert!(!balancing.is_empty() && balancing.len() <= 3);
+    assert_eq!(balancing[0].total_rounds, 3);
+    let min_backing = result.selected_validators.iter().map(|v| v.total_backing_stake).min();
+    assert_eq!(balancing.last().unwrap().minimum_support, min_backing);
+
+    // Without an observer nothing changes in the result
+    let unobserved = ElectionEngine::new().execute(&config, &data).unwrap();
+    assert_eq!(unobserved.selected_validators, result.selected_validators);
+}
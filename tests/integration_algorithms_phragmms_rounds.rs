@@ -0,0 +1,107 @@
+//! Algorithm test: round-by-round PhragMMS, its progress events and cancellation
+
+use offline_election::algorithms::phragmms::phragmms;
+use offline_election::algorithms::prepared::PreparedElectionData;
+use offline_election::algorithms::{ElectionAlgorithm, Mms, ParallelPhragmen};
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::progress::RunControl;
+use offline_election::types::AlgorithmType;
+use offline_election::{CancellationToken, ElectionPhase, ProgressEvent};
+use sp_npos_elections::BalancingConfig;
+use sp_runtime::Perbill;
+use std::sync::{Arc, Mutex};
+
+/// 40 candidates and 300 nominators with pseudo-random stakes and votes,
+/// including repeated votes and a zero-stake nominator
+fn election_data(seed: u64) -> ElectionData {
+    let mut state = seed;
+    let mut next = move |bound: u64| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) % bound
+    };
+
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in 0..40 {
+        builder.add_candidate(format!("c{}", candidate), next(1_000) as u128).unwrap();
+    }
+    for nominator in 0..300 {
+        let targets = (0..1 + next(16)).map(|_| format!("c{}", next(40))).collect();
+        let stake = if nominator == 0 { 0 } else { 1 + next(1_000_000_000) as u128 };
+        builder.add_nominator(format!("n{}", nominator), stake, targets).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_round_by_round_phragmms_matches_upstream() {
+    for seed in [1, 7, 42] {
+        let data = election_data(seed);
+        let prepared = PreparedElectionData::new(&data);
+        let voters: Vec<(u32, u64, Vec<u32>)> = (0..prepared.voter_count())
+            .map(|voter| (voter as u32, prepared.voter_stake(voter), prepared.voter_targets(voter).to_vec()))
+            .collect();
+
+        for balancing in [None, Some(BalancingConfig { iterations: 2, tolerance: 0 })] {
+            for to_elect in [1, 10, 25, 40, 60] {
+                let upstream = sp_npos_elections::phragmms::<u32, Perbill>(
+                    to_elect,
+                    (0..prepared.candidate_count() as u32).collect(),
+                    voters.clone(),
+                    balancing,
+                )
+                .unwrap();
+                let ours = phragmms(&prepared, to_elect, balancing, AlgorithmType::Mms, &RunControl::new())
+                    .unwrap();
+
+                let case = format!("seed {} size {} balanced {}", seed, to_elect, balancing.is_some());
+                assert_eq!(ours.winners, upstream.winners, "{}", case);
+                assert_eq!(ours.assignments, upstream.assignments, "{}", case);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_phragmms_reports_and_can_be_stopped_between_rounds() {
+    let data = election_data(3);
+    let events: Arc<Mutex<Vec<ProgressEvent>>> = Arc::default();
+    let sink = Arc::clone(&events);
+    let engine = ElectionEngine::new().with_progress_observer(move |event| sink.lock().unwrap().push(event.clone()));
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::ParallelPhragmen)
+        .active_set_size(10)
+        .build()
+        .unwrap();
+    engine.execute(&config, &data).unwrap();
+    let selection: Vec<(u32, u32)> = events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|e| e.phase == ElectionPhase::Selection)
+        .map(|e| (e.round, e.total_rounds))
+        .collect();
+    assert_eq!(selection, (1..=10).map(|round| (round, 10)).collect::<Vec<_>>());
+
+    // Cancelling from the observer stops the run before the next round
+    let prepared = PreparedElectionData::new(&data);
+    for algorithm in [&ParallelPhragmen as &dyn ElectionAlgorithm, &Mms] {
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        let control = RunControl::cancellable(cancel).with_progress(Arc::new(move |event: &ProgressEvent| {
+            if event.round == 3 {
+                trigger.cancel();
+            }
+        }));
+        let err = algorithm.execute_controlled(&prepared, &config, &control).unwrap_err();
+        match err {
+            ElectionError::Cancelled { phase, rounds_completed, .. } => {
+                assert_eq!((phase, rounds_completed), (Some(ElectionPhase::Selection), Some(3)));
+            }
+            other => panic!("expected a cancellation, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! API test: API keys, roles, rate limits and concurrent job quotas
+
+use offline_election::api::auth::{ApiKeyConfig, ApiKeyEntry, ApiKeys, ApiRole};
+use offline_election::api::handlers::ApiError;
+use offline_election::api::ApiServer;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use reqwest::StatusCode;
+
+fn entry(name: &str, key: &str, role: ApiRole) -> ApiKeyEntry {
+    ApiKeyEntry {
+        name: name.to_string(),
+        key: key.to_string(),
+        role,
+        requests_per_minute: None,
+        max_concurrent_jobs: None,
+    }
+}
+
+/// Serve the API with `keys` on a free local port
+async fn serve(keys: ApiKeys) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = ApiServer::new(addr.port()).with_api_keys(keys).router();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+async fn error_code(response: reqwest::Response) -> String {
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["error"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_server_checks_keys_roles_and_rate_limits() {
+    let reader = ApiKeyEntry { requests_per_minute: Some(3), ..entry("dashboard", "read-key", ApiRole::ReadOnly) };
+    let keys = ApiKeys::new(ApiKeyConfig { keys: vec![reader, entry("research", "submit-key", ApiRole::Submit)] })
+        .unwrap();
+    let base = serve(keys).await;
+    let client = reqwest::Client::new();
+
+    // Health checks stay open; everything else needs a known key
+    let response = client.get(format!("{}/health", base)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = client.get(format!("{}/datasets", base)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()["www-authenticate"], "Bearer");
+    assert_eq!(error_code(response).await, "UNAUTHORIZED");
+    let response = client.get(format!("{}/datasets", base)).bearer_auth("guess").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Read-only keys can read but not submit
+    let response = client.get(format!("{}/datasets", base)).header("X-API-Key", "read-key").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string()]).unwrap();
+    let body = serde_json::to_string(&builder.build().unwrap()).unwrap();
+    let upload = |key: &str| {
+        client
+            .put(format!("{}/datasets/snapshot", base))
+            .bearer_auth(key)
+            .header("content-type", "application/json")
+            .body(body.clone())
+    };
+    let response = upload("read-key").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(error_code(response).await, "FORBIDDEN");
+    let response = upload("submit-key").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // The reader's third request this minute is its last
+    let response = client.get(format!("{}/datasets", base)).bearer_auth("read-key").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = client.get(format!("{}/datasets", base)).bearer_auth("read-key").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response.headers()["retry-after"].to_str().unwrap().parse().unwrap();
+    assert!((1..=60).contains(&retry_after));
+    assert_eq!(error_code(response).await, "RATE_LIMITED");
+
+    // Other keys are unaffected
+    let response = client
+        .post(format!("{}/elections/run", base))
+        .bearer_auth("submit-key")
+        .json(&serde_json::json!({
+            "algorithm": "sequential-phragmen",
+            "active_set_size": 1,
+            "data_source": {"type": "dataset", "name": "snapshot"},
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_json_rpc_checks_the_role_per_method() {
+    let keys = ApiKeys::new(ApiKeyConfig { keys: vec![entry("dashboard", "read-key", ApiRole::ReadOnly)] }).unwrap();
+    let base = serve(keys).await;
+    let client = reqwest::Client::new();
+    let call = |method: &'static str, params: serde_json::Value| {
+        let request = client
+            .post(format!("{}/", base))
+            .bearer_auth("read-key")
+            .json(&serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}));
+        async move { request.send().await.unwrap().json::<serde_json::Value>().await.unwrap() }
+    };
+
+    let response = call("rpc_methods", serde_json::json!([])).await;
+    assert!(response["result"]["methods"].is_array());
+    let response = call("offline_compare", serde_json::json!(["a", "b"])).await;
+    assert_eq!(response["error"]["data"]["error"], "NOT_FOUND");
+
+    for (method, params) in [
+        ("offline_runElection", serde_json::json!({})),
+        ("offline_fetchSnapshot", serde_json::json!(["ws://127.0.0.1:1"])),
+    ] {
+        let response = call(method, params).await;
+        assert_eq!(response["error"]["data"]["error"], "FORBIDDEN", "{}", method);
+    }
+}
+
+#[test]
+fn test_key_files_are_checked_and_jobs_count_against_quotas() {
+    let limited = ApiKeyEntry { max_concurrent_jobs: Some(1), ..entry("ci", "ci-key", ApiRole::Submit) };
+    let keys = ApiKeys::new(ApiKeyConfig { keys: vec![limited] }).unwrap();
+    let caller = keys.authenticate("ci-key").unwrap();
+    assert_eq!((caller.name(), caller.role()), ("ci", ApiRole::Submit));
+
+    let permit = caller.start_job().unwrap();
+    let err = keys.authenticate("ci-key").unwrap().start_job().unwrap_err();
+    assert!(matches!(err, ApiError::QuotaExceeded(_)));
+    drop(permit);
+    assert!(caller.start_job().is_ok());
+
+    let field = |config: ApiKeyConfig| match ApiKeys::new(config).unwrap_err() {
+        ElectionError::ValidationError { field, .. } => field.unwrap(),
+        other => panic!("expected a validation error, got {:?}", other),
+    };
+    let duplicate = vec![entry("a", "same", ApiRole::Submit), entry("b", "same", ApiRole::ReadOnly)];
+    assert_eq!(field(ApiKeyConfig { keys: duplicate }), "keys[1].key");
+    let no_requests = ApiKeyEntry { requests_per_minute: Some(0), ..entry("a", "k", ApiRole::Submit) };
+    assert_eq!(field(ApiKeyConfig { keys: vec![no_requests] }), "keys[0].requests_per_minute");
+
+    let path = std::env::temp_dir().join(format!("api-keys-{}.json", std::process::id()));
+    std::fs::write(&path, r#"{"keys": [{"name": "ui", "key": "k1", "role": "read-only", "requests_per_minute": 600}]}"#)
+        .unwrap();
+    let keys = ApiKeys::load_from_file(&path).unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys.authenticate("k1").unwrap().role(), ApiRole::ReadOnly);
+    std::fs::write(&path, r#"{"keys": [{"name": "ui", "key": "k1", "role": "admin"}]}"#).unwrap();
+    assert!(matches!(ApiKeys::load_from_file(&path), Err(ElectionError::FileError { .. })));
+    std::fs::remove_file(&path).unwrap();
+}
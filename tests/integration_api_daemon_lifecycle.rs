@@ -0,0 +1,181 @@
+//! API test: readiness, draining and predictions of a long-running server
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use clap::Parser;
+use offline_election::api::handlers::{get_election_events, put_dataset, start_election, HandlerState};
+use offline_election::api::models::{DatasetUploadQuery, ElectionRequest, ElectionResponse};
+use offline_election::api::{ApiServer, Lifecycle};
+use offline_election::cli::DaemonCommand;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use reqwest::StatusCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn request() -> serde_json::Value {
+    serde_json::json!({
+        "algorithm": "sequential-phragmen",
+        "active_set_size": 1,
+        "data_source": {"type": "dataset", "name": "snapshot"},
+    })
+}
+
+#[tokio::test]
+async fn test_draining_server_reports_unready_refuses_jobs_and_stops_gracefully() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let lifecycle = Lifecycle::new();
+    let server = ApiServer::new(port).with_lifecycle(lifecycle.clone());
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let served = tokio::spawn(async move {
+        server.start_with_shutdown(async move {
+            stopped.await.ok();
+        })
+        .await
+    });
+    let base = format!("http://127.0.0.1:{}", port);
+    let client = reqwest::Client::new();
+    let mut ready = None;
+    for _ in 0..50 {
+        if let Ok(response) = client.get(format!("{}/ready", base)).send().await {
+            ready = Some(response);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let ready = ready.expect("server did not start");
+    assert_eq!(ready.status(), StatusCode::OK);
+    assert_eq!(ready.text().await.unwrap(), "READY");
+
+    lifecycle.set_ready(false);
+    let response = client.get(format!("{}/ready", base)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.text().await.unwrap(), "NOT_READY");
+    lifecycle.set_ready(true);
+
+    // Once draining, probes see it and new elections are refused, but the process stays alive
+    lifecycle.drain();
+    let response = client.get(format!("{}/ready", base)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.text().await.unwrap(), "DRAINING");
+    let response = client.get(format!("{}/health", base)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = client.post(format!("{}/elections/start", base)).json(&request()).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "UNAVAILABLE");
+
+    stop.send(()).unwrap();
+    tokio::time::timeout(Duration::from_secs(10), served).await.unwrap().unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_cancelled_jobs_fail_and_running_jobs_are_awaited() {
+    let lifecycle = Lifecycle::new();
+    let state = HandlerState::new().with_lifecycle(lifecycle.clone());
+    let (status, _) = put_dataset(
+        State(state.clone()),
+        Path("snapshot".to_string()),
+        Query(DatasetUploadQuery::default()),
+        Json(election_data()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(status, axum::http::StatusCode::CREATED);
+
+    let running = lifecycle.begin_job();
+    assert_eq!(lifecycle.running_jobs(), 1);
+    assert!(!lifecycle.wait_idle(Duration::from_millis(20)).await);
+    let waiting = tokio::spawn({
+        let lifecycle = lifecycle.clone();
+        async move { lifecycle.wait_idle(Duration::from_secs(10)).await }
+    });
+    drop(running);
+    assert!(waiting.await.unwrap());
+
+    // Past the shutdown timeout, jobs stop at their next check and fail as cancelled
+    lifecycle.cancel_jobs();
+    let request: ElectionRequest = serde_json::from_value(request()).unwrap();
+    let (_, Json(accepted)) = start_election(State(state.clone()), None, Json(request)).await.unwrap();
+    let response = get_election_events(State(state.clone()), Path(accepted.election_id.clone())).await.unwrap();
+    let body = axum::body::to_bytes(axum::response::IntoResponse::into_response(response).into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("event: failed"));
+    assert!(body.contains("\"error\":\"CANCELLED\""));
+    assert!(lifecycle.wait_idle(Duration::from_secs(10)).await);
+}
+
+#[tokio::test]
+async fn test_recorded_predictions_are_served_as_latest() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = ApiServer::new(addr.port());
+    let state = server.state().clone();
+    let app = server.router();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    let latest = format!("http://{}/predictions/latest", addr);
+
+    let response = reqwest::get(&latest).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let data = election_data();
+    let config = ElectionConfiguration::builder().active_set_size(1).block_number(100).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    let election_id = state.record_prediction(100, Arc::new(data.clone()), result.clone()).await;
+    assert_eq!(election_id, "prediction-100");
+    let config = ElectionConfiguration::builder().active_set_size(2).block_number(200).build().unwrap();
+    let later = ElectionEngine::new().execute(&config, &data).unwrap();
+    state.record_prediction(200, Arc::new(data), later).await;
+
+    let response: ElectionResponse = reqwest::get(&latest).await.unwrap().json().await.unwrap();
+    assert_eq!(response.election_id, "prediction-200");
+    assert_eq!(response.result.selected_validators.len(), 2);
+    let earlier: ElectionResponse = reqwest::get(format!("http://{}/elections/prediction-100/results", addr))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(earlier.result.selected_validators, result.selected_validators);
+    let events = reqwest::get(format!("http://{}/elections/prediction-100/events", addr))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(events.contains("event: completed"));
+}
+
+#[test]
+fn test_daemon_is_configured_from_environment_variables() {
+    std::env::set_var("OFFLINE_ELECTION_PORT", "8080");
+    std::env::set_var("OFFLINE_ELECTION_SHUTDOWN_TIMEOUT", "5");
+    std::env::set_var("OFFLINE_ELECTION_WS_URL", "wss://rpc.example.org");
+    std::env::set_var("OFFLINE_ELECTION_NOTIFY_WEBHOOK", "http://a.example/hook,http://b.example/hook");
+
+    let command = DaemonCommand::try_parse_from(["daemon"]).unwrap();
+    assert_eq!((command.port, command.shutdown_timeout), (8080, 5));
+    assert_eq!(command.ws_url.as_deref(), Some("wss://rpc.example.org"));
+    assert_eq!(command.result_cache_size, 32);
+    assert_eq!(command.notifications().notify_webhook, ["http://a.example/hook", "http://b.example/hook"]);
+
+    // Flags take precedence over the environment
+    let command = DaemonCommand::try_parse_from(["daemon", "--port", "9000"]).unwrap();
+    assert_eq!(command.port, 9000);
+
+    std::env::set_var("OFFLINE_ELECTION_PORT", "not-a-port");
+    assert!(DaemonCommand::try_parse_from(["daemon"]).is_err());
+}
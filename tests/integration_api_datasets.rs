@@ -0,0 +1,165 @@
+//! API test: uploading datasets once and running elections on them by name
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use offline_election::api::datasets::DatasetRegistry;
+use offline_election::api::handlers::{
+    delete_dataset, get_dataset, get_nominator_allocations, list_datasets, put_dataset, run_election, ApiError,
+    HandlerState,
+};
+use offline_election::api::models::{DataSource, DatasetUploadQuery, ElectionRequest};
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_data::ElectionData;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_candidate("c".to_string(), 10).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 400, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn request(data_source: serde_json::Value) -> Json<ElectionRequest> {
+    Json(
+        serde_json::from_value(serde_json::json!({
+            "algorithm": "sequential-phragmen",
+            "active_set_size": 2,
+            "data_source": data_source,
+        }))
+        .unwrap(),
+    )
+}
+
+fn label(label: &str) -> Query<DatasetUploadQuery> {
+    Query(DatasetUploadQuery { label: Some(label.to_string()) })
+}
+
+#[tokio::test]
+async fn test_elections_run_on_uploaded_datasets_by_name() {
+    let state = HandlerState::new();
+    let data = election_data();
+
+    let (status, Json(summary)) =
+        put_dataset(State(state.clone()), Path("era-1".to_string()), label("first"), Json(data.clone()))
+            .await
+            .unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(summary.data_hash, data.content_hash());
+    assert_eq!((summary.candidate_count, summary.nominator_count), (3, 2));
+    assert_eq!(summary.label.as_deref(), Some("first"));
+
+    let (status, Json(summary)) =
+        put_dataset(State(state.clone()), Path("era-1".to_string()), label("relabelled"), Json(data.clone()))
+            .await
+            .unwrap();
+    assert_eq!(status, StatusCode::OK);
+    let Json(listed) = list_datasets(State(state.clone())).await;
+    assert_eq!(listed, vec![summary.clone()]);
+    let Json(fetched) = get_dataset(State(state.clone()), Path("era-1".to_string())).await.unwrap();
+    assert_eq!(fetched, summary);
+
+    // Running by name matches running on the same data sent inline
+    let by_name = run_election(
+        State(state.clone()),
+        None,
+        request(serde_json::json!({"type": "dataset", "name": "era-1"})),
+    )
+    .await
+    .unwrap();
+    let mut inline = request(serde_json::json!({"type": "dataset", "name": "unused"}));
+    inline.data_source = DataSource::Json { data };
+    let inline = run_election(State(state.clone()), None, inline).await.unwrap();
+    assert_eq!(by_name.result.selected_validators, inline.result.selected_validators);
+    assert_eq!(by_name.result.execution_metadata.data_hash, Some(summary.data_hash));
+
+    let status = delete_dataset(State(state.clone()), Path("era-1".to_string())).await.unwrap();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let err = run_election(
+        State(state.clone()),
+        None,
+        request(serde_json::json!({"type": "dataset", "name": "era-1"})),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    let err = get_dataset(State(state), Path("era-1".to_string())).await.unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_registry_rejects_bad_names_and_broken_data_and_is_shared() {
+    let registry = DatasetRegistry::new();
+    for name in ["", "has space", "../etc", &"x".repeat(65)] {
+        let err = registry.insert(name, None, election_data()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::Election(ElectionError::ValidationError { ref field, .. }) if field.as_deref() == Some("name")
+        ));
+    }
+
+    let mut empty = election_data();
+    empty.candidates.clear();
+    assert!(registry.insert("empty", None, empty).await.is_err());
+    assert!(registry.list().await.is_empty());
+
+    // A registry handed to the handler state is the one uploads land in
+    let state = HandlerState::new().with_datasets(registry.clone());
+    let (status, _) = put_dataset(State(state), Path("kusama_6123.v2".to_string()), label("ksm"), Json(election_data()))
+        .await
+        .unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(registry.get("kusama_6123.v2").await.is_some());
+    assert!(registry.remove("kusama_6123.v2").await);
+    assert!(!registry.remove("kusama_6123.v2").await);
+}
+
+#[tokio::test]
+async fn test_nominator_allocations_are_read_from_the_stored_election() {
+    let state = HandlerState::new();
+    let mut source = request(serde_json::json!({"type": "dataset", "name": "unused"}));
+    source.data_source = DataSource::Json { data: election_data() };
+    let response = run_election(State(state.clone()), None, source).await.unwrap();
+
+    let path = |nominator: &str| Path((response.election_id.clone(), nominator.to_string()));
+    let Json(allocations) = get_nominator_allocations(State(state.clone()), path("n1")).await.unwrap();
+    assert_eq!((allocations.nominator_id.as_str(), allocations.stake), ("n1", 1_000));
+    assert_eq!(allocations.allocated + allocations.unallocated, 1_000);
+
+    let err = get_nominator_allocations(State(state.clone()), path("n9")).await.unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+    let err = get_nominator_allocations(State(state), Path(("missing".to_string(), "n1".to_string())))
+        .await
+        .unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_registry_limits_dataset_count_and_memory() {
+    let registry = DatasetRegistry::new().with_limits(2, usize::MAX);
+    registry.insert("one", None, election_data()).await.unwrap();
+    registry.insert("two", None, election_data()).await.unwrap();
+    let err = registry.insert("three", None, election_data()).await.unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    // Replacing a dataset does not add to the count
+    assert!(registry.insert("two", None, election_data()).await.unwrap().1);
+
+    let bytes = election_data().memory_report().total();
+    let registry = DatasetRegistry::new().with_limits(16, bytes * 3 / 2);
+    let state = HandlerState::new().with_datasets(registry.clone());
+    let (status, _) = put_dataset(State(state.clone()), Path("one".to_string()), label("first"), Json(election_data()))
+        .await
+        .unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    let err = put_dataset(State(state), Path("two".to_string()), label("second"), Json(election_data()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(registry.remove("one").await);
+    registry.insert("two", None, election_data()).await.unwrap();
+}
@@ -0,0 +1,163 @@
+//! API test: following elections through their server-sent event streams
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use offline_election::api::handlers::{
+    get_election_events, get_election_results, put_dataset, run_election, start_election, HandlerState,
+};
+use offline_election::api::models::{DatasetUploadQuery, ElectionRequest, JobEvent, JobWarning};
+use offline_election::input::SyntheticDataBuilder;
+
+/// Upload three candidates and two nominators, one with zero stake, as "snapshot"
+async fn state_with_dataset() -> HandlerState {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_candidate("c".to_string(), 10).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 0, vec!["c".to_string()]).unwrap();
+    let state = HandlerState::new();
+    let (status, _) = put_dataset(
+        State(state.clone()),
+        Path("snapshot".to_string()),
+        Query(DatasetUploadQuery::default()),
+        Json(builder.build().unwrap()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    state
+}
+
+fn request(algorithm: &str, dataset: &str) -> Json<ElectionRequest> {
+    Json(
+        serde_json::from_value(serde_json::json!({
+            "algorithm": algorithm,
+            "active_set_size": 2,
+            "data_source": {"type": "dataset", "name": dataset},
+        }))
+        .unwrap(),
+    )
+}
+
+/// Read an election's whole event stream, which ends after the final event
+async fn read_events(state: &HandlerState, election_id: &str) -> Vec<(String, JobEvent)> {
+    let response = get_election_events(State(state.clone()), Path(election_id.to_string()))
+        .await
+        .unwrap()
+        .into_response();
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec())
+        .unwrap()
+        .split("\n\n")
+        .filter(|frame| frame.starts_with("event: "))
+        .map(|frame| {
+            let (name, data) = frame.split_once('\n').unwrap();
+            let event = serde_json::from_str(data.strip_prefix("data: ").unwrap()).unwrap();
+            (name["event: ".len()..].to_string(), event)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_finished_election_replays_progress_warnings_and_completion() {
+    let state = state_with_dataset().await;
+    let Json(response) = run_election(State(state.clone()), None, request("sequential-phragmen", "snapshot"))
+        .await
+        .unwrap();
+
+    let events = read_events(&state, &response.election_id).await;
+    let names: Vec<&str> = events.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["progress", "warning", "completed"]);
+    for (name, event) in &events {
+        assert_eq!(name, event.name());
+    }
+
+    // Only the latest progress event is replayed
+    match &events[0].1 {
+        JobEvent::Progress(update) => {
+            assert_eq!((update.round, update.total_rounds), (2, 2));
+            assert!(update.minimum_support.is_some());
+        }
+        other => panic!("expected progress, got {:?}", other),
+    }
+    match &events[1].1 {
+        JobEvent::Warning(JobWarning::Data(issue)) => assert_eq!(issue.account_id, "n2"),
+        other => panic!("expected a data warning, got {:?}", other),
+    }
+    assert_eq!(
+        events[2].1,
+        JobEvent::Completed {
+            election_id: response.election_id.clone(),
+            execution_time_ms: response.execution_time_ms.unwrap(),
+        }
+    );
+
+    let err = get_election_events(State(state), Path("unknown".to_string())).await.unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_started_election_streams_until_completed_or_failed() {
+    let state = state_with_dataset().await;
+
+    let (status, Json(accepted)) = start_election(State(state.clone()), None, request("multi-phase", "snapshot"))
+        .await
+        .unwrap();
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let events = read_events(&state, &accepted.election_id).await;
+    assert!(matches!(events.last().unwrap().1, JobEvent::Completed { .. }));
+    let Json(results) = get_election_results(State(state.clone()), Path(accepted.election_id.clone()))
+        .await
+        .unwrap();
+    assert_eq!(results.result.selected_validators.len(), 2);
+
+    // Problems found while running arrive as the final event
+    let (_, Json(accepted)) = start_election(State(state.clone()), None, request("multi-phase", "missing"))
+        .await
+        .unwrap();
+    match &read_events(&state, &accepted.election_id).await[..] {
+        [(_, JobEvent::Failed { error })] => {
+            assert_eq!(error.error, "VALIDATION_ERROR");
+            assert_eq!(error.field.as_deref(), Some("data_source.name"));
+        }
+        other => panic!("expected a single failed event, got {:?}", other),
+    }
+
+    // Invalid requests are rejected before a job starts
+    let err = start_election(State(state), None, request("no-such-algorithm", "snapshot")).await.unwrap_err();
+    assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_only_the_latest_finished_jobs_are_kept() {
+    use futures::StreamExt;
+    use offline_election::api::jobs::ElectionJobs;
+
+    let completed = |id: &str| JobEvent::Completed { election_id: id.to_string(), execution_time_ms: 1 };
+    let jobs = ElectionJobs::with_max_finished(2);
+    for id in ["running", "first", "second", "third"] {
+        jobs.register(id);
+    }
+    let following_first = jobs.subscribe("first").unwrap();
+
+    jobs.publish("first", completed("first"));
+    jobs.publish("second", completed("second"));
+    assert_eq!(jobs.len(), 4);
+    jobs.publish("third", completed("third"));
+
+    // The job that finished first is evicted; running jobs are never
+    assert_eq!(jobs.len(), 3);
+    assert!(jobs.subscribe("first").is_none());
+    assert!(jobs.subscribe("running").is_some());
+    let replayed: Vec<JobEvent> = jobs.subscribe("second").unwrap().collect().await;
+    assert_eq!(replayed, [completed("second")]);
+
+    // A stream opened before the eviction still ends with the final event
+    let followed: Vec<JobEvent> = following_first.collect().await;
+    assert_eq!(followed, [completed("first")]);
+}
@@ -0,0 +1,146 @@
+//! API test: JSON-RPC 2.0 calls, batches and errors
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::StatusCode;
+use offline_election::api::handlers::HandlerState;
+use offline_election::api::lifecycle::Lifecycle;
+use offline_election::api::jsonrpc::{json_rpc, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR, SERVER_ERROR};
+use serde_json::{json, Value};
+
+fn election_request(active_set_size: u32) -> Value {
+    json!({
+        "algorithm": "sequential-phragmen",
+        "active_set_size": active_set_size,
+        "data_source": {
+            "type": "synthetic",
+            "candidates": [
+                {"account_id": "a", "stake": "0"},
+                {"account_id": "b", "stake": "0"},
+                {"account_id": "c", "stake": "0"}
+            ],
+            "nominators": [
+                {"account_id": "n1", "stake": "3000", "targets": ["a"]},
+                {"account_id": "n2", "stake": "2000", "targets": ["b"]},
+                {"account_id": "n3", "stake": "1000", "targets": ["c"]}
+            ]
+        }
+    })
+}
+
+/// Send `body` and return the status and parsed response
+async fn call(state: &HandlerState, body: Value) -> (StatusCode, Value) {
+    call_raw(state, body.to_string()).await
+}
+
+async fn call_raw(state: &HandlerState, body: String) -> (StatusCode, Value) {
+    let response = json_rpc(State(state.clone()), None, Bytes::from(body)).await;
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_run_and_compare_elections() {
+    let state = HandlerState::new();
+
+    // Positional and named parameters
+    let (status, two) = call(
+        &state,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "offline_runElection", "params": [election_request(2)]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(two["id"], 1);
+    assert_eq!(two["result"]["result"]["selected_validators"].as_array().unwrap().len(), 2);
+    let (_, three) = call(
+        &state,
+        json!({"jsonrpc": "2.0", "id": "x", "method": "offline_runElection", "params": election_request(3)}),
+    )
+    .await;
+    assert_eq!(three["id"], "x");
+
+    let (_, compared) = call(
+        &state,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "offline_compare",
+            "params": [two["result"]["election_id"], three["result"]["election_id"]]
+        }),
+    )
+    .await;
+    assert_eq!(compared["result"]["validators_added"], json!(["c"]));
+    assert_eq!(compared["result"]["score_delta"]["sum_stake"], 1000);
+}
+
+#[tokio::test]
+async fn test_batches_and_notifications() {
+    let state = HandlerState::new();
+    let (status, responses) = call(
+        &state,
+        json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "rpc_methods"},
+            {"jsonrpc": "2.0", "method": "rpc_methods"},
+            {"jsonrpc": "2.0", "id": 2, "method": "offline_unknown"}
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let responses = responses.as_array().unwrap();
+    assert_eq!(responses.len(), 2, "notifications get no response");
+    assert!(responses[0]["result"]["methods"].as_array().unwrap().contains(&json!("offline_runElection")));
+    assert_eq!(responses[1]["error"]["code"], METHOD_NOT_FOUND);
+
+    let (status, _) = call(&state, json!({"jsonrpc": "2.0", "method": "rpc_methods"})).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_errors_follow_json_rpc_codes() {
+    let state = HandlerState::new();
+
+    let (_, response) = call_raw(&state, "{\"jsonrpc\": ".to_string()).await;
+    assert_eq!((response["error"]["code"].clone(), response["id"].clone()), (json!(PARSE_ERROR), Value::Null));
+
+    let (_, response) = call(&state, json!({"jsonrpc": "1.0", "id": 1, "method": "rpc_methods"})).await;
+    assert_eq!(response["error"]["code"], INVALID_REQUEST);
+    let (_, response) = call(&state, json!([])).await;
+    assert_eq!(response["error"]["code"], INVALID_REQUEST);
+
+    // Requests the REST API rejects with 400 are invalid params, with the REST error as data
+    let (_, response) = call(
+        &state,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "offline_runElection", "params": [election_request(0)]}),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    assert_eq!(response["error"]["data"]["error"], "VALIDATION_ERROR");
+
+    let (_, response) = call(&state, json!({"jsonrpc": "2.0", "id": 1, "method": "offline_compare", "params": ["a"]})).await;
+    assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    let (_, response) = call(
+        &state,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "offline_compare", "params": {"baseline": "a", "other": "b"}}),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], SERVER_ERROR);
+    assert_eq!(response["error"]["data"]["error"], "NOT_FOUND");
+
+    let (_, response) = call(&state, json!({"jsonrpc": "2.0", "id": 1, "method": "offline_fetchSnapshot", "params": []})).await;
+    assert_eq!(response["error"]["code"], INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_snapshot_fetches_are_refused_while_draining() {
+    let lifecycle = Lifecycle::new();
+    let state = HandlerState::new().with_lifecycle(lifecycle.clone());
+    lifecycle.drain();
+
+    let (_, response) = call(
+        &state,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "offline_fetchSnapshot", "params": ["ws://127.0.0.1:1"]}),
+    )
+    .await;
+    assert_eq!(response["error"]["data"]["error"], "UNAVAILABLE");
+}
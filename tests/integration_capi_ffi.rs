@@ -0,0 +1,78 @@
+//! C ABI tests: running an election through the exported functions
+#![cfg(feature = "capi")]
+
+use offline_election::capi::*;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_result::ElectionResult;
+use std::ffi::{c_char, CStr};
+use std::ptr;
+
+fn data_json() -> String {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 3_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 2_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 1_000, vec!["c".to_string()]).unwrap();
+    serde_json::to_string(&builder.build().unwrap()).unwrap()
+}
+
+fn last_error() -> String {
+    let message = offline_election_last_error();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }.to_string_lossy().into_owned()
+}
+
+#[test]
+fn test_run_election_through_the_c_abi() {
+    let data_json = data_json();
+    let config_json = ElectionConfiguration::builder().active_set_size(2).build().unwrap().to_json().unwrap();
+    unsafe {
+        let engine = offline_election_engine_new();
+        let data = offline_election_data_from_json(data_json.as_ptr(), data_json.len());
+        assert!(!data.is_null());
+
+        let mut result_json: *mut c_char = ptr::null_mut();
+        let status = offline_election_run(engine, data, config_json.as_ptr(), config_json.len(), &mut result_json);
+        assert_eq!(status, OFFLINE_ELECTION_OK);
+        let result: ElectionResult = serde_json::from_str(CStr::from_ptr(result_json).to_str().unwrap()).unwrap();
+        let winners: Vec<&str> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+        assert_eq!(winners, vec!["a", "b"]);
+
+        offline_election_string_free(result_json);
+        offline_election_data_free(data);
+        offline_election_engine_free(engine);
+    }
+}
+
+#[test]
+fn test_errors_return_exit_codes_and_messages() {
+    let data_json = data_json();
+    unsafe {
+        let broken = b"{\"candidates\": [";
+        assert!(offline_election_data_from_json(broken.as_ptr(), broken.len()).is_null());
+        assert!(last_error().contains("Failed to parse JSON"));
+
+        let engine = offline_election_engine_new();
+        let data = offline_election_data_from_json(data_json.as_ptr(), data_json.len());
+        let mut config: serde_json::Value =
+            serde_json::from_str(&ElectionConfiguration::default().to_json().unwrap()).unwrap();
+        config["active_set_size"] = 0.into();
+        let config_json = config.to_string();
+        let mut result_json: *mut c_char = ptr::null_mut();
+        let status = offline_election_run(engine, data, config_json.as_ptr(), config_json.len(), &mut result_json);
+        assert_eq!(status, 3, "validation errors use the CLI exit code");
+        assert!(result_json.is_null());
+        assert!(!last_error().is_empty());
+
+        let status = offline_election_run(ptr::null(), data, config_json.as_ptr(), config_json.len(), &mut result_json);
+        assert_eq!(status, 3);
+        assert!(last_error().contains("must not be null"));
+
+        offline_election_data_free(data);
+        offline_election_engine_free(engine);
+    }
+}
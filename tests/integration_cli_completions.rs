@@ -0,0 +1,48 @@
+//! CLI test: shell completions and man page generated from the clap definitions
+
+use clap::{CommandFactory, Parser};
+use offline_election::cli::completions::{completion_script, CompletionsCommand, Shell};
+use offline_election::cli::man_page::{man_page, ManCommand};
+use offline_election::cli::RunCommand;
+
+fn command() -> clap::Command {
+    clap::Command::new("offline-election")
+        .about("Offline NPoS Election Tool")
+        .subcommand(RunCommand::command())
+        .subcommand(CompletionsCommand::command())
+        .subcommand(ManCommand::command())
+}
+
+#[test]
+fn test_completion_scripts_cover_subcommands_and_flags() {
+    let bash = completion_script(command(), Shell::Bash);
+    assert!(bash.contains("offline__election,run)"));
+    assert!(bash.contains("--active-set-size"));
+    assert!(bash.contains("complete -F _offline__election"));
+
+    let zsh = completion_script(command(), Shell::Zsh);
+    assert!(zsh.starts_with("#compdef offline-election\n"));
+    assert!(zsh.contains("'run:Run an election simulation'"));
+    assert!(zsh.contains("'*--override-layer=[Apply a named override layer"));
+
+    let fish = completion_script(command(), Shell::Fish);
+    assert!(fish.contains(
+        "-n \"__fish_offline_election_using_subcommand run\" -l input-file -d 'Input file path (JSON format)' -r -F"
+    ));
+
+    let parsed = CompletionsCommand::try_parse_from(["completions", "powershell"]).unwrap();
+    assert_eq!(parsed.shell, Shell::PowerShell);
+    assert!(parsed.generate(command()).contains("Register-ArgumentCompleter"));
+    assert!(CompletionsCommand::try_parse_from(["completions", "tcsh"]).is_err());
+}
+
+#[test]
+fn test_man_page_lists_commands_and_options() {
+    let page = man_page(command());
+    assert!(page.starts_with(".TH OFFLINE\\-ELECTION 1"));
+    assert!(page.contains(".SH NAME\noffline\\-election \\- Offline NPoS Election Tool\n"));
+    assert!(page.contains(".SS \"offline\\-election run\"\nRun an election simulation\n"));
+    assert!(page.contains("\\fB\\-\\-active\\-set\\-size\\fR <\\fIACTIVE_SET_SIZE\\fR>"));
+    assert!(page.contains("[default: json]"));
+    assert!(page.contains("<\\fISHELL\\fR>\nShell to generate completions for [possible values: bash, elvish, fish, powershell, zsh]"));
+}
@@ -0,0 +1,140 @@
+//! CLI test: run settings resolved from defaults, chain presets, a config file and flags
+
+use clap::Parser;
+use offline_election::cli::RunCommand;
+use offline_election::error::ElectionError;
+use offline_election::input::IndexerQueries;
+use offline_election::settings::{RunSettings, SettingSource, SettingsResolver};
+use offline_election::types::AlgorithmType;
+use std::path::PathBuf;
+
+fn write_config(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("offline-election-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_later_layers_override_earlier_ones_and_record_their_source() {
+    let path = PathBuf::from("election.toml");
+    let file = RunSettings {
+        chain: Some("kusama".to_string()),
+        algorithm: Some("parallel-phragmen".to_string()),
+        active_set_size: Some(1000),
+        rpc_timeout: Some(60),
+        ..RunSettings::default()
+    };
+    let cli = RunSettings {
+        algorithm: Some("mms".to_string()),
+        ..RunSettings::default()
+    };
+    let resolved = SettingsResolver::new().with_file(path.clone(), file).with_cli(cli).resolve().unwrap();
+
+    assert_eq!(resolved.algorithm().unwrap(), AlgorithmType::Mms);
+    assert_eq!(resolved.source("algorithm"), Some(&SettingSource::Cli));
+    assert_eq!(resolved.active_set_size().unwrap(), 1000);
+    assert_eq!(resolved.source("rpc_timeout"), Some(&SettingSource::File { path }));
+    assert_eq!(resolved.settings.rpc_url.as_deref(), Some("https://kusama-rpc.polkadot.io"));
+    assert_eq!(
+        resolved.source("token"),
+        Some(&SettingSource::ChainPreset { chain: "kusama".to_string() })
+    );
+    assert_eq!(resolved.source("validation_profile"), Some(&SettingSource::Default));
+    assert_eq!(resolved.source("balancing_iterations"), None);
+}
+
+#[test]
+fn test_chain_presets_are_selected_by_the_highest_layer_naming_a_chain() {
+    let file = RunSettings { chain: Some("kusama".to_string()), ..RunSettings::default() };
+    let cli = RunSettings { chain: Some("Polkadot".to_string()), ..RunSettings::default() };
+    let resolved = SettingsResolver::new()
+        .with_file(PathBuf::from("election.toml"), file)
+        .with_cli(cli)
+        .resolve()
+        .unwrap();
+    assert_eq!(resolved.token_unit().unwrap().unwrap().symbol, "DOT");
+    assert_eq!(resolved.settings.rpc_url.as_deref(), Some("https://rpc.polkadot.io"));
+
+    // Defaults alone leave the active set size for the caller to choose
+    let defaults = SettingsResolver::new().resolve().unwrap();
+    assert_eq!(defaults.settings.rpc_url, None);
+    assert!(matches!(defaults.active_set_size(), Err(ElectionError::ValidationError { .. })));
+
+    let unknown = RunSettings { chain: Some("rococo".to_string()), ..RunSettings::default() };
+    let error = SettingsResolver::new().with_cli(unknown).resolve().unwrap_err();
+    assert!(error.to_string().contains("Unknown chain 'rococo'"));
+}
+
+#[test]
+fn test_run_command_reads_the_config_file_beneath_its_flags() {
+    let path = write_config(
+        "run",
+        "chain = \"westend\"\nalgorithm = \"parallel-phragmen\"\nactive_set_size = 16\nrounding = \"truncate:3\"\n",
+    );
+    let command = RunCommand::try_parse_from([
+        "run", "--config", path.to_str().unwrap(), "--active-set-size", "20", "--partial-results",
+    ])
+    .unwrap();
+    let resolved = command.resolve_settings().unwrap();
+    assert_eq!(resolved.algorithm().unwrap(), AlgorithmType::ParallelPhragmen);
+    assert_eq!(resolved.active_set_size().unwrap(), 20);
+    assert_eq!(resolved.settings.partial_results, Some(true));
+    assert_eq!(resolved.source("partial_results"), Some(&SettingSource::Cli));
+    assert_eq!(resolved.token_unit().unwrap().unwrap().symbol, "WND");
+
+    // RPC tuning flags need an RPC URL, which a chain preset or file provides
+    assert!(RunCommand::try_parse_from(["run", "--synthetic", "--rpc-timeout", "5"]).is_err());
+    assert!(RunCommand::try_parse_from(["run", "--chain", "polkadot", "--rpc-timeout", "5"]).is_ok());
+
+    // Invalid values name the layer they came from
+    std::fs::write(&path, "kind = \"treasury\"\n").unwrap();
+    let error = command.resolve_settings().unwrap().kind().unwrap_err().to_string();
+    assert!(error.contains(&format!("from file {}", path.display())), "{}", error);
+
+    std::fs::write(&path, "algoritm = \"mms\"\n").unwrap();
+    assert!(matches!(command.resolve_settings(), Err(ElectionError::FileError { .. })));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_resolved_settings_render_as_a_reusable_config_file() {
+    let cli = RunSettings {
+        chain: Some("polkadot".to_string()),
+        active_set_size: Some(600),
+        ..RunSettings::default()
+    };
+    let resolved = SettingsResolver::new().with_cli(cli).resolve().unwrap();
+
+    let plain = resolved.to_toml(false).unwrap();
+    let reparsed: RunSettings = toml::from_str(&plain).unwrap();
+    assert_eq!(reparsed, resolved.settings);
+
+    let annotated = resolved.to_toml(true).unwrap();
+    assert!(annotated.lines().any(|line| line.starts_with("active_set_size = 600") && line.ends_with("# command line")));
+    assert!(annotated.lines().any(|line| line.starts_with("token = \"DOT\"") && line.ends_with("# polkadot preset")));
+    assert!(annotated.lines().any(|line| line.starts_with("kind = \"staking\"") && line.ends_with("# default")));
+    let reparsed: RunSettings = toml::from_str(&annotated).unwrap();
+    assert_eq!(reparsed, resolved.settings);
+}
+
+#[test]
+fn test_indexer_queries_come_from_the_config_file() {
+    let path = write_config(
+        "indexer-queries",
+        "indexer_candidates_query = \"\"\"\nquery($era: Int!, $limit: Int!, $offset: Int!) {\n  \
+         candidates: validators(era: $era, first: $limit, offset: $offset) { accountId: id stake: ownStake }\n}\"\"\"\n",
+    );
+    let command = RunCommand::try_parse_from(["run", "--config", path.to_str().unwrap(), "--synthetic"]).unwrap();
+    let resolved = command.resolve_settings().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let queries = resolved.indexer_queries();
+    assert!(queries.candidates.contains("candidates: validators(era: $era"));
+    assert_eq!(resolved.source("indexer_candidates_query"), Some(&SettingSource::File { path }));
+    // Queries left out keep their defaults
+    assert_eq!(queries.nominators, IndexerQueries::default().nominators);
+    assert_eq!(queries.elected, IndexerQueries::default().elected);
+
+    let reparsed: RunSettings = toml::from_str(&resolved.to_toml(true).unwrap()).unwrap();
+    assert_eq!(reparsed, resolved.settings);
+}
@@ -0,0 +1,95 @@
+//! CLI output test: CSV export of the elected validators
+
+use offline_election::cli::output::format_csv;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::account_format::AccountFormat;
+use offline_election::models::balance::TokenUnit;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::{ElectionData, ElectionMetadata};
+use offline_election::models::validator::CandidateMetadata;
+
+const ZERO_ACCOUNT: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A Polkadot validator with a self-vote and an identity, and one without either
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate(ZERO_ACCOUNT.to_string(), 0).unwrap()
+        .add_candidate("other".to_string(), 0).unwrap()
+        .add_nominator(ZERO_ACCOUNT.to_string(), 20_000_000_000, vec![ZERO_ACCOUNT.to_string()]).unwrap()
+        .add_nominator("n1".to_string(), 15_000_000_000, vec![ZERO_ACCOUNT.to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 10_000_000_000, vec!["other".to_string()]).unwrap();
+    let mut data = builder.build().unwrap();
+    data.candidates[0].metadata = Some(CandidateMetadata {
+        identity_display: Some("Zero, Inc.".to_string()),
+        commission_rate: Some(3),
+        ..Default::default()
+    });
+    data.metadata = Some(ElectionMetadata {
+        chain: Some("polkadot".to_string()),
+        ..Default::default()
+    });
+    data
+}
+
+#[test]
+fn test_csv_lists_winners_with_key_metrics() {
+    let data = election_data();
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(2).build().unwrap(), &data)
+        .unwrap();
+
+    let csv = format_csv(&result, &data, data.token_unit().as_ref(), AccountFormat::Network, data.ss58_prefix());
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Rank,Validator,Identity,Self Stake (DOT),Total Backing (DOT),Nominators,Commission (%)",
+            "1,111111111111111111111111111111111HC1,\"Zero, Inc.\",2,3.5,1,3",
+            "2,other,,0,1,1,",
+        ]
+    );
+}
+
+#[test]
+fn test_csv_amounts_in_plancks_without_a_unit() {
+    let mut data = election_data();
+    data.metadata = None;
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(1).build().unwrap(), &data)
+        .unwrap();
+
+    let csv = format_csv(&result, &data, None, AccountFormat::Network, data.ss58_prefix());
+    let lines: Vec<&str> = csv.lines().collect();
+    assert!(lines[0].contains("Total Backing (planck)"));
+    // Without a known chain, addresses use the generic Substrate prefix
+    assert!(lines[1].starts_with("1,5C4hrfjw9DjXZTzV3MwzrrAr9P1MJhSrvWGWqi1eSuyUpnhM,"));
+    assert!(lines[1].contains(",20000000000,35000000000,1,3"));
+
+    let kusama = format_csv(&result, &data, Some(&TokenUnit::kusama()), AccountFormat::Network, 42);
+    assert!(kusama.starts_with("Rank,Validator,Identity,Self Stake (KSM)"));
+}
+
+#[test]
+fn test_csv_writes_public_keys_when_asked() {
+    let data = election_data();
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(1).build().unwrap(), &data)
+        .unwrap();
+
+    let csv = format_csv(&result, &data, None, AccountFormat::PublicKey, data.ss58_prefix());
+    assert!(csv.lines().nth(1).unwrap().starts_with(&format!("1,{},\"Zero, Inc.\"", ZERO_ACCOUNT)));
+}
+
+#[test]
+fn test_csv_counts_a_bond_without_self_vote_as_self_stake() {
+    let mut data = election_data();
+    data.candidates[1].stake = 5_000_000_000;
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(2).build().unwrap(), &data)
+        .unwrap();
+
+    let csv = format_csv(&result, &data, data.token_unit().as_ref(), AccountFormat::Network, data.ss58_prefix());
+    assert_eq!(csv.lines().nth(2), Some("2,other,,0.5,1.5,1,"));
+}
@@ -0,0 +1,56 @@
+//! CLI test: exit codes and machine-readable error output
+
+use offline_election::cli::error_output::{error_json, format_error, ErrorFormat};
+use offline_election::types::AlgorithmType;
+use offline_election::{ElectionError, ErrorCategory};
+use std::path::PathBuf;
+
+#[test]
+fn test_error_categories_have_distinct_exit_codes() {
+    let cases = [
+        (
+            ElectionError::ValidationError { message: "bad".to_string(), field: Some("active_set_size".to_string()) },
+            ErrorCategory::Validation,
+            3,
+        ),
+        (ElectionError::InsufficientCandidates { requested: 10, available: 2 }, ErrorCategory::Validation, 3),
+        (ElectionError::InvalidData { message: "bad".to_string(), source: None }, ErrorCategory::Validation, 3),
+        (
+            ElectionError::RpcError { message: "down".to_string(), url: "ws://localhost:9944".to_string(), source: None },
+            ErrorCategory::Rpc,
+            4,
+        ),
+        (
+            ElectionError::AlgorithmError { message: "failed".to_string(), algorithm: AlgorithmType::MultiPhase },
+            ErrorCategory::Algorithm,
+            5,
+        ),
+        (
+            ElectionError::FileError { message: "missing".to_string(), path: PathBuf::from("/tmp/x.json"), source: None },
+            ErrorCategory::Io,
+            6,
+        ),
+    ];
+    for (error, category, code) in cases {
+        assert_eq!(error.category(), category);
+        assert_eq!(error.exit_code(), code);
+    }
+}
+
+#[test]
+fn test_json_error_format_includes_fields() {
+    let error = ElectionError::AlgorithmError { message: "did not converge".to_string(), algorithm: AlgorithmType::Mms };
+    let value = error_json(&error);
+    assert_eq!(value["category"], "algorithm");
+    assert_eq!(value["exit_code"], 5);
+    assert_eq!(value["message"], error.to_string());
+    assert_eq!(value["error"]["type"], "AlgorithmError");
+    assert_eq!(value["error"]["message"], "did not converge");
+    assert_eq!(value["error"]["algorithm"], "mms");
+
+    let error = ElectionError::ValidationError { message: "must be positive".to_string(), field: None };
+    let printed: serde_json::Value = serde_json::from_str(&format_error(&error, ErrorFormat::Json)).unwrap();
+    assert_eq!(printed["error"]["field"], serde_json::Value::Null);
+    assert_eq!(format_error(&error, ErrorFormat::Human), "Error: Validation error: must be positive");
+    assert_eq!("JSON".parse::<ErrorFormat>().unwrap(), ErrorFormat::Json);
+}
@@ -0,0 +1,90 @@
+//! CLI test: `--json-io` request documents and responses
+
+use offline_election::cli::json_io::{respond, JsonIoOperation};
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_data::ElectionData;
+use serde_json::json;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 9_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 1_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 500, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn config() -> serde_json::Value {
+    json!({ "algorithm": "sequential-phragmen", "active_set_size": 2 })
+}
+
+#[tokio::test]
+async fn test_run_with_inline_data() {
+    let request = json!({
+        "id": "job-1",
+        "operation": "run",
+        "config": config(),
+        "data": { "inline": election_data() },
+    });
+    let response = respond(&request.to_string()).await;
+
+    assert!(response.ok, "{:?}", response.error);
+    assert_eq!(response.id, Some(json!("job-1")));
+    assert_eq!(response.operation, Some(JsonIoOperation::Run));
+    assert_eq!(response.exit_code, 0);
+    let winners = response.result.unwrap()["selected_validators"].as_array().unwrap().len();
+    assert_eq!(winners, 2);
+}
+
+#[tokio::test]
+async fn test_hash_and_statistics_of_a_file() {
+    let data = election_data();
+    let path = std::env::temp_dir().join(format!("json-io-{}.json", std::process::id()));
+    std::fs::write(&path, serde_json::to_string(&data).unwrap()).unwrap();
+    let reference = json!({ "file": { "path": path } });
+
+    let hash = respond(&json!({ "operation": "hash", "data": reference }).to_string()).await;
+    let statistics = respond(&json!({ "operation": "statistics", "data": reference }).to_string()).await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(hash.result.unwrap()["data_hash"], json!(data.content_hash()));
+    assert_eq!(statistics.result.unwrap()["edge_count"], json!(4));
+}
+
+#[tokio::test]
+async fn test_redistribution_uses_tolerance() {
+    let request = json!({
+        "operation": "redistribution",
+        "config": config(),
+        "data": { "inline": election_data() },
+        "tolerance_bps": 0,
+    });
+    let response = respond(&request.to_string()).await;
+
+    assert!(response.ok, "{:?}", response.error);
+    assert_eq!(response.result.unwrap()["tolerance_bps"], json!(0));
+}
+
+#[tokio::test]
+async fn test_failures_carry_the_error_and_exit_code() {
+    let missing_config = json!({ "id": 7, "operation": "run", "data": { "inline": election_data() } });
+    let response = respond(&missing_config.to_string()).await;
+    assert!(!response.ok);
+    assert_eq!(response.id, Some(json!(7)));
+    assert_eq!(response.exit_code, 3);
+    assert_eq!(response.error.unwrap()["error"]["field"], json!("config"));
+
+    let missing_file = json!({ "operation": "hash", "data": { "file": { "path": "/nonexistent/data.json" } } });
+    let response = respond(&missing_file.to_string()).await;
+    assert_eq!(response.exit_code, 6);
+    assert_eq!(response.error.unwrap()["code"], json!("FILE_ERROR"));
+
+    let response = respond(r#"{"id": "bad", "operation": "vote"}"#).await;
+    assert!(!response.ok);
+    assert_eq!(response.id, Some(json!("bad")));
+    assert_eq!(response.operation, None);
+    assert_eq!(response.exit_code, 3);
+}
@@ -0,0 +1,57 @@
+//! CLI output test: compact diff printed by `run --watch`
+
+use offline_election::cli::output::format_result_diff;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::types::AlgorithmType;
+
+fn run(stake_3: u128) -> ElectionResult {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("validator-a".to_string(), 0).unwrap()
+        .add_candidate("validator-b".to_string(), 0).unwrap()
+        .add_candidate("validator-c".to_string(), 0).unwrap()
+        .add_nominator(
+            "nominator-1".to_string(),
+            1_000,
+            vec!["validator-a".to_string(), "validator-c".to_string()],
+        ).unwrap()
+        .add_nominator(
+            "nominator-2".to_string(),
+            2_000,
+            vec!["validator-a".to_string(), "validator-b".to_string()],
+        ).unwrap()
+        .add_nominator(
+            "nominator-3".to_string(),
+            stake_3,
+            vec!["validator-b".to_string(), "validator-c".to_string()],
+        ).unwrap();
+    let data = builder.build().unwrap();
+
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .build()
+        .unwrap();
+
+    ElectionEngine::new().execute(&config, &data).unwrap()
+}
+
+#[test]
+fn test_result_diff_lists_entering_and_leaving_validators() {
+    let diff = format_result_diff(&run(500), &run(5_000));
+
+    assert!(diff.contains("+ validator-c"));
+    assert!(diff.contains("- validator-a"));
+}
+
+#[test]
+fn test_result_diff_reports_unchanged_set() {
+    let result = run(500);
+    let diff = format_result_diff(&result, &result);
+
+    assert!(diff.contains("Elected set unchanged"));
+    assert!(diff.contains("Nominators affected: 0"));
+}
@@ -0,0 +1,83 @@
+//! CLI test: snapshot hashes and checking which snapshot a result came from
+
+use offline_election::cli::HashCommand;
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use std::path::PathBuf;
+
+fn election_data(top_stake: u128) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), top_stake, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 1_000, vec!["b".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("snapshot-hash-{}-{}.json", name, std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn hash_command(input_file: PathBuf, verify_result: Option<PathBuf>) -> HashCommand {
+    HashCommand {
+        input_file,
+        verify_result,
+        format: "json".to_string(),
+    }
+}
+
+#[test]
+fn test_snapshot_hash_matches_the_hash_a_run_records() {
+    let data = election_data(3_000);
+    let snapshot = temp_file("snapshot", &serde_json::to_string(&data).unwrap());
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(1).build().unwrap(), &data)
+        .unwrap();
+    assert_eq!(result.produced_from(&data), Some(true));
+    let result_file = temp_file("result", &result.to_json().unwrap());
+
+    let hash = hash_command(snapshot.clone(), Some(result_file.clone())).hash().unwrap();
+    assert_eq!(hash.data_hash, data.content_hash());
+    assert_eq!(hash.result_data_hash.as_deref(), Some(hash.data_hash.as_str()));
+    assert_eq!(hash.matches, Some(true));
+
+    let plain = hash_command(snapshot.clone(), None).hash().unwrap();
+    assert_eq!(plain.data_hash, hash.data_hash);
+    assert_eq!(plain.matches, None);
+
+    std::fs::remove_file(snapshot).unwrap();
+    std::fs::remove_file(result_file).unwrap();
+}
+
+#[tokio::test]
+async fn test_result_from_another_snapshot_fails_verification() {
+    let data = election_data(3_000);
+    let other = election_data(3_001);
+    let snapshot = temp_file("other-snapshot", &serde_json::to_string(&other).unwrap());
+    let mut result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(1).build().unwrap(), &data)
+        .unwrap();
+    assert_eq!(result.produced_from(&other), Some(false));
+    let result_file = temp_file("other-result", &result.to_json().unwrap());
+
+    let hash = hash_command(snapshot.clone(), Some(result_file.clone())).hash().unwrap();
+    assert_eq!(hash.matches, Some(false));
+    let err = hash_command(snapshot.clone(), Some(result_file.clone())).execute().await.unwrap_err();
+    assert!(matches!(err, ElectionError::ValidationError { .. }));
+
+    // Results written before data hashes were recorded cannot be checked
+    result.execution_metadata.data_hash = None;
+    assert_eq!(result.produced_from(&other), None);
+    std::fs::write(&result_file, result.to_json().unwrap()).unwrap();
+    let err = hash_command(snapshot.clone(), Some(result_file.clone())).hash().unwrap_err();
+    assert!(err.to_string().contains("records no data hash"));
+
+    std::fs::remove_file(snapshot).unwrap();
+    std::fs::remove_file(result_file).unwrap();
+}
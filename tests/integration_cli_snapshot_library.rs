@@ -0,0 +1,157 @@
+//! CLI test: the local snapshot library and its list, tag and prune commands
+
+use chrono::{Duration, Utc};
+use clap::Parser;
+use offline_election::cli::snapshots::{format_entries, SnapshotsAction, SnapshotsCommand};
+use offline_election::error::ElectionError;
+use offline_election::input::{PrunePolicy, SnapshotStore, SyntheticDataBuilder};
+use offline_election::models::election_data::{ElectionData, ElectionMetadata};
+use std::path::PathBuf;
+
+fn snapshot(chain: &str, block_number: u64) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap();
+    let mut data = builder.build().unwrap();
+    data.metadata = Some(ElectionMetadata {
+        block_number: Some(block_number),
+        chain: Some(chain.to_string()),
+        era: Some((block_number / 1000) as u32),
+        ..Default::default()
+    });
+    data
+}
+
+fn store_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn tags(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(|tag| tag.to_string()).collect()
+}
+
+#[test]
+fn test_snapshots_are_stored_once_with_their_metadata() {
+    let dir = store_dir("snapshot-library-add");
+    let store = SnapshotStore::open(&dir).unwrap();
+    assert!(store.list().unwrap().is_empty());
+
+    let data = snapshot("polkadot", 20_000);
+    let (entry, added) = store.add(&data, Some("snapshot.json".to_string()), &tags(&["baseline"])).unwrap();
+    assert!(added);
+    assert_eq!(entry.id, data.content_hash());
+    assert_eq!((entry.chain.as_deref(), entry.block_number, entry.era), (Some("polkadot"), Some(20_000), Some(20)));
+    assert_eq!((entry.candidate_count, entry.nominator_count), (2, 1));
+    assert_eq!(entry.size_bytes, std::fs::metadata(dir.join(format!("{}.json", entry.id))).unwrap().len());
+
+    // The same data again only gains the new tags
+    let (again, added) = store.add(&data, None, &tags(&["incident"])).unwrap();
+    assert!(!added);
+    assert_eq!(again.tags.iter().cloned().collect::<Vec<_>>(), tags(&["baseline", "incident"]));
+    assert_eq!(store.list().unwrap().len(), 1);
+
+    // Snapshots are found by a unique prefix and load back unchanged
+    assert_eq!(store.get(&entry.id[..8]).unwrap().id, entry.id);
+    assert_eq!(store.load(&entry.id[..8]).unwrap().content_hash(), entry.id);
+    assert!(matches!(store.get(&entry.id[..3]), Err(ElectionError::ValidationError { .. })));
+    assert!(store.get("ffffffffffff").is_err());
+    assert!(store.add(&snapshot("kusama", 1), None, &tags(&["no spaces"])).is_err());
+
+    // A reopened store sees the same library
+    store.add(&snapshot("kusama", 30_000), None, &[]).unwrap();
+    let reopened = SnapshotStore::open(&dir).unwrap();
+    let chains: Vec<Option<String>> = reopened.list().unwrap().into_iter().map(|entry| entry.chain).collect();
+    assert_eq!(chains, [Some("kusama".to_string()), Some("polkadot".to_string())]);
+    assert!(format_entries(&reopened.list().unwrap()).contains("baseline,incident"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_tags_are_added_and_removed() {
+    let dir = store_dir("snapshot-library-tag");
+    let store = SnapshotStore::open(&dir).unwrap();
+    let (entry, _) = store.add(&snapshot("polkadot", 1_000), None, &[]).unwrap();
+
+    let tagged = store.tag(&entry.id, &tags(&["baseline", "q1"]), &[]).unwrap();
+    assert_eq!(tagged.tags.len(), 2);
+    let untagged = store.tag(&entry.id[..10], &[], &tags(&["q1", "missing"])).unwrap();
+    assert_eq!(untagged.tags.iter().cloned().collect::<Vec<_>>(), tags(&["baseline"]));
+    assert_eq!(store.get(&entry.id).unwrap(), untagged);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_pruning_keeps_the_newest_and_tagged_snapshots() {
+    let dir = store_dir("snapshot-library-prune");
+    let store = SnapshotStore::open(&dir).unwrap();
+    let ids: Vec<String> = (1..=4)
+        .map(|block| store.add(&snapshot("polkadot", block), None, &[]).unwrap().0.id)
+        .collect();
+    store.tag(&ids[0], &tags(&["baseline"]), &[]).unwrap();
+
+    // Without a criterion nothing is removed
+    assert!(store.prune(&PrunePolicy::default(), false).is_err());
+
+    let keep_one = PrunePolicy { keep_latest: Some(1), ..PrunePolicy::default() };
+    let would_remove = store.prune(&keep_one, true).unwrap();
+    assert_eq!(would_remove.iter().map(|e| e.id.clone()).collect::<Vec<_>>(), [ids[2].clone(), ids[1].clone()]);
+    assert_eq!(store.list().unwrap().len(), 4);
+
+    // Snapshots added after the cut-off are kept
+    let too_recent = PrunePolicy { added_before: Some(Utc::now() - Duration::days(1)), ..PrunePolicy::default() };
+    assert!(store.prune(&too_recent, false).unwrap().is_empty());
+
+    store.prune(&keep_one, false).unwrap();
+    let left: Vec<String> = store.list().unwrap().into_iter().map(|entry| entry.id).collect();
+    assert_eq!(left, [ids[0].clone(), ids[3].clone()]);
+    assert!(!dir.join(format!("{}.json", ids[1])).exists());
+
+    let everything = PrunePolicy {
+        added_before: Some(Utc::now() + Duration::minutes(1)),
+        include_tagged: true,
+        ..PrunePolicy::default()
+    };
+    assert_eq!(store.prune(&everything, false).unwrap().len(), 2);
+    assert!(store.list().unwrap().is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_snapshots_command_manages_the_store() {
+    let dir = store_dir("snapshot-library-command");
+    let file = std::env::temp_dir().join(format!("snapshot-library-input-{}.json", std::process::id()));
+    std::fs::write(&file, serde_json::to_string(&snapshot("westend", 5_000)).unwrap()).unwrap();
+    let run = |args: &[&str]| {
+        let mut argv = vec!["snapshots", "--store", dir.to_str().unwrap()];
+        argv.extend_from_slice(args);
+        SnapshotsCommand::try_parse_from(argv)
+    };
+
+    run(&["add", file.to_str().unwrap(), "--tag", "weekly"]).unwrap().execute().await.unwrap();
+    let stored = SnapshotStore::open(&dir).unwrap().list().unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].source.as_deref(), file.to_str());
+    run(&["tag", &stored[0].id[..8], "keep"]).unwrap().execute().await.unwrap();
+    run(&["list", "--tag", "keep", "--format", "json"]).unwrap().execute().await.unwrap();
+    run(&["prune", "--keep-latest", "0"]).unwrap().execute().await.unwrap();
+    assert_eq!(SnapshotStore::open(&dir).unwrap().list().unwrap().len(), 1);
+
+    let command = run(&["prune", "--older-than-days", "30", "--dry-run"]).unwrap();
+    assert!(matches!(
+        command.action,
+        SnapshotsAction::Prune { keep_latest: None, older_than_days: Some(30), dry_run: true, .. }
+    ));
+    assert!(run(&["prune"]).is_err());
+    assert!(run(&["add"]).is_err());
+    assert!(run(&["info", "abc"]).unwrap().execute().await.is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    std::fs::remove_file(&file).unwrap();
+}
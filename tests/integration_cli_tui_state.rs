@@ -0,0 +1,122 @@
+//! CLI test: TUI state and key handling, without a terminal
+
+use crossterm::event::KeyCode;
+use offline_election::cli::tui::{Churn, SortColumn, TuiApp};
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::nominator::Nominator;
+use offline_election::models::validator::ValidatorCandidate;
+
+/// Three candidates backed 1_100, 700 and 400; two seats go to `a` and `b`
+fn app() -> TuiApp {
+    let mut data = ElectionData::new();
+    for (account_id, stake) in [("a", 100), ("b", 200), ("c", 300)] {
+        data.add_candidate(ValidatorCandidate::new(account_id.to_string(), stake)).unwrap();
+    }
+    for (account_id, stake, target) in [("n1", 1_000, "a"), ("n2", 500, "b"), ("n3", 100, "c")] {
+        let mut nominator = Nominator::new(account_id.to_string(), stake);
+        nominator.add_target(target.to_string());
+        data.add_nominator(nominator).unwrap();
+    }
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let mut app = TuiApp::new(data, config);
+    app.rerun();
+    app
+}
+
+fn elected(app: &TuiApp) -> Vec<String> {
+    let mut elected: Vec<String> = app
+        .result()
+        .unwrap()
+        .selected_validators
+        .iter()
+        .map(|v| v.account_id.clone())
+        .collect();
+    elected.sort();
+    elected
+}
+
+fn churn(app: &TuiApp, account_id: &str) -> Churn {
+    app.rows().into_iter().find(|row| row.account_id == account_id).unwrap().churn
+}
+
+#[test]
+fn test_first_run_elects_without_churn() {
+    let app = app();
+    assert_eq!(elected(&app), vec!["a", "b"]);
+    assert_eq!(app.status(), "Elected 2 validators");
+    assert!(app.messages().is_empty());
+    assert!(app.rows().iter().all(|row| row.churn == Churn::Unchanged));
+}
+
+#[test]
+fn test_removing_a_winner_shows_churn() {
+    let mut app = app();
+    assert_eq!(app.selected_account().as_deref(), Some("a"));
+
+    assert!(!app.handle_key(KeyCode::Char('x')));
+    assert_eq!(elected(&app), vec!["b", "c"]);
+    assert_eq!(app.status(), "Re-ran election: 1 entered, 1 left");
+    assert_eq!(churn(&app, "a"), Churn::Left);
+    assert_eq!(churn(&app, "c"), Churn::Entered);
+    assert!(app.rows().iter().any(|row| row.account_id == "a" && row.removed));
+}
+
+#[test]
+fn test_reduced_active_set_is_a_message_not_stderr() {
+    let mut app = app();
+    app.handle_key(KeyCode::Char('x'));
+    app.handle_key(KeyCode::Char('x'));
+    assert_eq!(elected(&app), vec!["c"]);
+    assert_eq!(app.messages(), ["Requested 2 validators but only 1 candidates remain"]);
+
+    app.handle_key(KeyCode::Char('r'));
+    assert_eq!(elected(&app), vec!["a", "b"]);
+    assert!(app.messages().is_empty());
+    assert!(app.rows().iter().all(|row| !row.removed));
+}
+
+#[test]
+fn test_stake_keys_step_the_selected_candidate_by_ten_percent() {
+    let mut app = app();
+    let self_stake = |app: &TuiApp| app.rows().into_iter().find(|row| row.account_id == "a").unwrap().self_stake;
+
+    app.handle_key(KeyCode::Char('+'));
+    assert_eq!(self_stake(&app), 110);
+    app.handle_key(KeyCode::Char('-'));
+    assert_eq!(self_stake(&app), 99);
+
+    app.handle_key(KeyCode::Char('r'));
+    assert_eq!(self_stake(&app), 100);
+}
+
+#[test]
+fn test_sort_key_cycles_columns() {
+    let mut app = app();
+    assert_eq!(app.sort(), SortColumn::Backing);
+    app.handle_key(KeyCode::Char('s'));
+    assert_eq!(app.sort(), SortColumn::Rank);
+    app.handle_key(KeyCode::Char('s'));
+    assert_eq!(app.sort(), SortColumn::Commission);
+    app.handle_key(KeyCode::Char('s'));
+    assert_eq!(app.sort(), SortColumn::Backing);
+}
+
+#[test]
+fn test_selection_stays_within_the_table() {
+    let mut app = app();
+    app.handle_key(KeyCode::Up);
+    assert_eq!(app.selected_account().as_deref(), Some("a"));
+    app.handle_key(KeyCode::PageDown);
+    assert_eq!(app.selected_account().as_deref(), Some("c"));
+    app.handle_key(KeyCode::Char('k'));
+    assert_eq!(app.selected_account().as_deref(), Some("b"));
+}
+
+#[test]
+fn test_quit_keys() {
+    let mut app = app();
+    assert!(!app.handle_key(KeyCode::Char('z')));
+    assert!(app.handle_key(KeyCode::Char('q')));
+    assert!(app.handle_key(KeyCode::Esc));
+}
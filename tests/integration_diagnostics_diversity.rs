@@ -0,0 +1,86 @@
+//! Diagnostics test: country and hosting provider concentration from external attributes
+
+use offline_election::diagnostics::DiagnosticsGenerator;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::{CandidateAttributeMap, SyntheticDataBuilder};
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::types::AlgorithmType;
+
+fn election() -> (ElectionData, ElectionResult) {
+    let mut builder = SyntheticDataBuilder::new();
+    for (candidate, stake) in [("a", 4_000), ("b", 2_000), ("c", 2_000), ("d", 2_000)] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+        builder
+            .add_nominator(format!("nominator-{}", candidate), stake, vec![candidate.to_string()])
+            .unwrap();
+    }
+    let mut data = builder.build().unwrap();
+
+    let attributes = CandidateAttributeMap::from_csv_str(
+        "account_id,country,city,provider,notes\n\
+         a,DE,Frankfurt,Hetzner,\"primary, bare metal\"\n\
+         b,DE,Berlin,Hetzner,\n\
+         c,US,,\"OVH\"\n\
+         unknown,FR,Paris,OVH\n",
+    )
+    .unwrap();
+    assert_eq!(attributes.len(), 4);
+    assert_eq!(attributes.apply(&mut data), 3);
+
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(4)
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    (data, result)
+}
+
+#[test]
+fn test_diversity_measures_provider_and_country_concentration() {
+    let (data, result) = election();
+
+    let diversity = DiagnosticsGenerator::new()
+        .generate(&result, &data)
+        .unwrap()
+        .diversity()
+        .cloned()
+        .unwrap();
+
+    let providers = &diversity.by_hosting_provider;
+    assert_eq!(providers.unknown_count, 1);
+    assert_eq!(providers.groups[0].value, "Hetzner");
+    assert_eq!(providers.groups[0].elected_count, 2);
+    assert_eq!(providers.groups[0].backing_share_bps, 6_000);
+    assert_eq!(providers.largest_share_bps, 6_666);
+    // (2/3)^2 + (1/3)^2
+    assert_eq!(providers.herfindahl_index, 5_555);
+
+    assert_eq!(diversity.by_country.groups[0].value, "DE");
+    assert_eq!(diversity.by_city.groups.len(), 2);
+    assert_eq!(diversity.by_city.unknown_count, 2);
+}
+
+#[test]
+fn test_diversity_absent_without_attributes() {
+    let (mut data, result) = election();
+    for candidate in &mut data.candidates {
+        candidate.metadata = None;
+    }
+
+    let diagnostics = DiagnosticsGenerator::new().generate(&result, &data).unwrap();
+    assert!(diagnostics.diversity().is_none());
+}
+
+#[test]
+fn test_candidate_attributes_from_json_map_and_rows() {
+    let map = CandidateAttributeMap::from_json_str(r#"{"a": {"country": "CH", "hosting_provider": "Self"}}"#)
+        .unwrap();
+    assert_eq!(map.get("a").unwrap().country.as_deref(), Some("CH"));
+
+    let rows = CandidateAttributeMap::from_json_str(r#"[{"account_id": "b", "city": "Zug"}]"#).unwrap();
+    assert_eq!(rows.get("b").unwrap().city.as_deref(), Some("Zug"));
+    assert!(rows.get("b").unwrap().country.is_none());
+}
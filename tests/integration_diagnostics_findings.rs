@@ -0,0 +1,99 @@
+//! Diagnostics test: typed findings with stable codes and severities
+
+use offline_election::diagnostics::{DiagnosticsGenerator, FindingCode, Severity};
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_overrides::ElectionOverrides;
+
+/// "a" gets most of the backing; every nominator's target is elected
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_candidate("d".to_string(), 0).unwrap()
+        .add_nominator("whale".to_string(), 8_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 1_000, vec!["c".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 1_000, vec!["d".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_findings_flag_concentration_and_trimmed_voters() {
+    let data = election_data();
+    // The whale's stake is overridden down, so against the original snapshot part of it is unused
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_nominator_stake("whale".to_string(), 6_000).unwrap();
+    let config = ElectionConfiguration::builder().active_set_size(4).overrides(overrides).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    let diagnostics = DiagnosticsGenerator::new().generate(&result, &data).unwrap();
+
+    let concentration: Vec<_> = diagnostics.findings_with_code(FindingCode::StakeConcentrationHigh).collect();
+    assert_eq!(concentration.len(), 1);
+    assert_eq!(concentration[0].severity, Severity::Warning);
+    assert_eq!(concentration[0].subject.as_deref(), Some("a"));
+    assert_eq!(concentration[0].data["stake_share_bps"], 6_666);
+
+    let trimmed: Vec<_> = diagnostics.findings_with_code(FindingCode::VoterTrimmed).collect();
+    assert_eq!(trimmed.len(), 1);
+    assert_eq!(trimmed[0].subject.as_deref(), Some("whale"));
+    assert_eq!(trimmed[0].data["active_stake"], 6_000);
+
+    // Most severe first, and warnings mirror findings of warning severity or above
+    let severities: Vec<Severity> = diagnostics.findings.iter().map(|f| f.severity).collect();
+    assert!(severities.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert_eq!(diagnostics.warnings, vec![concentration[0].message.clone()]);
+    assert_eq!(diagnostics.findings_at_least(Severity::Warning).count(), 1);
+}
+
+#[test]
+fn test_finding_codes_serialize_as_stable_strings() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder().active_set_size(4).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &data, true).unwrap();
+    let json = serde_json::to_value(result.diagnostics.unwrap()).unwrap();
+
+    let finding = &json["findings"][0];
+    assert_eq!(finding["code"], "STAKE_CONCENTRATION_HIGH");
+    assert_eq!(finding["severity"], "warning");
+    assert_eq!(finding["subject"], "a");
+    assert_eq!(FindingCode::VoterTrimmed.to_string(), "VOTER_TRIMMED");
+    assert_eq!(
+        serde_json::from_value::<FindingCode>(serde_json::json!("VOTER_TRIMMED")).unwrap(),
+        FindingCode::VoterTrimmed
+    );
+    assert!(Severity::Critical > Severity::Warning && Severity::Warning > Severity::Info);
+}
+
+#[test]
+fn test_rounding_dust_is_not_reported_as_trimmed() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator(
+            "splitter".to_string(),
+            1_000_000_000,
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        )
+        .unwrap();
+    let data = builder.build().unwrap();
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let mut result = ElectionEngine::new().execute(&config, &data).unwrap();
+    // Perbill ratios of 1/3 each lose a planck on every edge
+    for alloc in result.stake_distribution.iter_mut().filter(|a| a.nominator_id == "splitter") {
+        alloc.amount -= 1;
+    }
+    let diagnostics = DiagnosticsGenerator::new().generate(&result, &data).unwrap();
+    assert_eq!(diagnostics.findings_with_code(FindingCode::VoterTrimmed).count(), 0);
+
+    // More than a planck per edge is unused stake
+    result.stake_distribution.iter_mut().find(|a| a.nominator_id == "splitter").unwrap().amount -= 10;
+    let diagnostics = DiagnosticsGenerator::new().generate(&result, &data).unwrap();
+    assert_eq!(diagnostics.findings_with_code(FindingCode::VoterTrimmed).count(), 1);
+}
@@ -0,0 +1,96 @@
+//! Diagnostics test: smallest nominator allocation backing each elected validator
+
+use offline_election::diagnostics::{DiagnosticsGenerator, MinimumActiveStake, ValidatorMinimumStake};
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+
+fn elect(data: &ElectionData, active_set_size: u32) -> ElectionResult {
+    let config = ElectionConfiguration::builder().active_set_size(active_set_size).build().unwrap();
+    ElectionEngine::new().execute(&config, data).unwrap()
+}
+
+/// Each nominator backs a single candidate so allocations equal stakes
+fn election_data(candidates: &[(&str, u128)], nominators: &[(&str, u128, &str)]) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for (candidate, stake) in candidates {
+        builder.add_candidate(candidate.to_string(), *stake).unwrap();
+    }
+    for (nominator, stake, target) in nominators {
+        builder.add_nominator(nominator.to_string(), *stake, vec![target.to_string()]).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+fn minimum_active_stake(result: &ElectionResult, data: &ElectionData) -> MinimumActiveStake {
+    let diagnostics = DiagnosticsGenerator::new().generate(result, data).unwrap();
+    diagnostics.minimum_active_stake().cloned().unwrap()
+}
+
+fn per_validator(minimum: &MinimumActiveStake, validator_id: &str) -> ValidatorMinimumStake {
+    minimum.per_validator.iter().find(|v| v.validator_id == validator_id).cloned().unwrap()
+}
+
+#[test]
+fn test_smallest_allocation_per_validator_and_overall() {
+    let data = election_data(
+        &[("a", 0), ("b", 0)],
+        &[("n1", 6_000, "a"), ("n2", 1_000, "a"), ("n3", 3_000, "b"), ("n4", 2_000, "b")],
+    );
+    let result = elect(&data, 2);
+    let minimum = minimum_active_stake(&result, &data);
+
+    let a = per_validator(&minimum, "a");
+    assert_eq!(a.minimum_allocation, Some(1_000));
+    assert_eq!(a.nominator_id.as_deref(), Some("n2"));
+    let b = per_validator(&minimum, "b");
+    assert_eq!(b.minimum_allocation, Some(2_000));
+    assert_eq!(b.nominator_id.as_deref(), Some("n4"));
+    assert_eq!(minimum.global_minimum, Some(1_000));
+}
+
+#[test]
+fn test_per_validator_entries_follow_the_active_set_order() {
+    let data = election_data(
+        &[("a", 0), ("b", 0), ("c", 0)],
+        &[("n1", 1_000, "c"), ("n2", 3_000, "a"), ("n3", 2_000, "b")],
+    );
+    let result = elect(&data, 3);
+    let minimum = minimum_active_stake(&result, &data);
+
+    let expected: Vec<&str> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+    let actual: Vec<&str> = minimum.per_validator.iter().map(|v| v.validator_id.as_str()).collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_validator_without_allocations_has_no_minimum() {
+    let data = election_data(&[("a", 0)], &[("n1", 1_000, "a")]);
+    let mut result = elect(&data, 1);
+    result.stake_distribution.clear();
+    let minimum = minimum_active_stake(&result, &data);
+
+    let a = per_validator(&minimum, "a");
+    assert_eq!(a.minimum_allocation, None);
+    assert_eq!(a.nominator_id, None);
+    assert_eq!(minimum.global_minimum, None);
+
+    let json = serde_json::to_value(&minimum).unwrap();
+    assert!(json.get("global_minimum").is_none());
+    assert!(json["per_validator"][0].get("minimum_allocation").is_none());
+}
+
+#[test]
+fn test_zero_allocations_are_ignored() {
+    let data = election_data(&[("a", 0)], &[("n1", 1_000, "a"), ("n2", 500, "a")]);
+    let mut result = elect(&data, 1);
+    for allocation in result.stake_distribution.iter_mut().filter(|a| a.nominator_id == "n2") {
+        allocation.amount = 0;
+    }
+    let minimum = minimum_active_stake(&result, &data);
+
+    assert_eq!(per_validator(&minimum, "a").minimum_allocation, Some(1_000));
+    assert_eq!(minimum.global_minimum, Some(1_000));
+}
@@ -0,0 +1,48 @@
+//! Diagnostics test: nominators clustered by overlapping target sets
+
+use offline_election::diagnostics::DiagnosticsGenerator;
+use offline_election::input::SyntheticDataBuilder;
+
+fn targets(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+#[test]
+fn test_cluster_nominators_groups_overlapping_target_sets() {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in ["a", "b", "c", "d", "e", "f"] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+    }
+    builder
+        // Bloc: identical or near-identical target sets
+        .add_nominator("bloc-1".to_string(), 3_000, targets(&["a", "b", "c", "d"])).unwrap()
+        .add_nominator("bloc-2".to_string(), 2_000, targets(&["a", "b", "c", "d"])).unwrap()
+        .add_nominator("bloc-3".to_string(), 1_000, targets(&["a", "b", "c", "d", "e"])).unwrap()
+        // Independent nominators
+        .add_nominator("solo-1".to_string(), 2_000, targets(&["e", "f"])).unwrap()
+        .add_nominator("solo-2".to_string(), 2_000, targets(&["a", "f"])).unwrap();
+    let data = builder.build().unwrap();
+
+    let clusters = DiagnosticsGenerator::new().cluster_nominators(&data, 80, 3);
+
+    assert_eq!(clusters.clusters.len(), 1);
+    let bloc = &clusters.clusters[0];
+    assert_eq!(bloc.nominator_ids, targets(&["bloc-1", "bloc-2", "bloc-3"]));
+    assert_eq!(bloc.shared_targets, targets(&["a", "b", "c", "d"]));
+    assert_eq!(bloc.total_stake, 6_000);
+    assert_eq!(bloc.stake_share_bps, 6_000);
+}
+
+#[test]
+fn test_cluster_nominators_respects_min_cluster_size() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_nominator("n-1".to_string(), 100, targets(&["a"])).unwrap()
+        .add_nominator("n-2".to_string(), 100, targets(&["a"])).unwrap();
+    let data = builder.build().unwrap();
+
+    let generator = DiagnosticsGenerator::new();
+    assert!(generator.cluster_nominators(&data, 80, 3).clusters.is_empty());
+    assert_eq!(generator.cluster_nominators(&data, 80, 2).clusters.len(), 1);
+}
@@ -0,0 +1,77 @@
+//! Diagnostics test: elected validators grouped by operator identity
+
+use offline_election::diagnostics::DiagnosticsGenerator;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::validator::CandidateMetadata;
+use offline_election::types::AlgorithmType;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for (candidate, stake) in [("a", 3_000), ("b", 3_000), ("c", 2_000), ("d", 2_000)] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+        builder
+            .add_nominator(format!("nominator-{}", candidate), stake, vec![candidate.to_string()])
+            .unwrap();
+    }
+    let mut data = builder.build().unwrap();
+
+    // a and b are sub-identities of the same operator
+    for candidate in data.candidates.iter_mut().filter(|c| c.account_id == "a" || c.account_id == "b") {
+        candidate.metadata = Some(CandidateMetadata {
+            identity_display: Some(format!("Operator/{}", candidate.account_id)),
+            identity_parent: Some("operator-1".to_string()),
+            ..Default::default()
+        });
+    }
+    data
+}
+
+#[test]
+fn test_operator_groups_merge_sub_identities() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(4)
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    let diagnostics = DiagnosticsGenerator::new().generate(&result, &data).unwrap();
+    let groups = diagnostics.operator_groups().unwrap();
+
+    assert_eq!(groups.elected_operator_count, 3);
+    let operator = &groups.operators[0];
+    assert_eq!(operator.operator_id, "operator-1");
+    assert_eq!(operator.display_name.as_deref(), Some("Operator/a"));
+    assert_eq!(operator.candidate_count, 2);
+    assert_eq!(operator.elected_validator_ids.len(), 2);
+    assert_eq!(operator.total_backing, 6_000);
+    assert_eq!(operator.stake_share_bps, 6_000);
+
+    // One operator holds more than a third, but two validators are needed
+    assert_eq!(groups.nakamoto_coefficient, 1);
+    assert_eq!(groups.validator_nakamoto_coefficient, 2);
+}
+
+#[test]
+fn test_operator_groups_without_identities_match_validators() {
+    let mut data = election_data();
+    for candidate in &mut data.candidates {
+        candidate.metadata = None;
+    }
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(4)
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    let groups = DiagnosticsGenerator::new().group_by_operator(&result, &data);
+
+    assert_eq!(groups.elected_operator_count, 4);
+    assert!(groups.operators.iter().all(|o| o.elected_validator_ids.len() == 1));
+    assert_eq!(groups.nakamoto_coefficient, groups.validator_nakamoto_coefficient);
+}
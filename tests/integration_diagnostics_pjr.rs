@@ -0,0 +1,139 @@
+//! Diagnostics test: proportional justified representation check
+
+use offline_election::diagnostics::{DiagnosticsGenerator, FindingCode};
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::{ElectionResult, SelectedValidator, StakeAllocation};
+use offline_election::types::AlgorithmType;
+
+/// Voters of "a" hold most of the stake; "c" has a small following
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("v1".to_string(), 600, vec!["a".to_string()]).unwrap()
+        .add_nominator("v2".to_string(), 300, vec!["b".to_string()]).unwrap()
+        .add_nominator("v3".to_string(), 100, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn validator(account_id: &str, total_backing_stake: u128) -> SelectedValidator {
+    SelectedValidator { account_id: account_id.to_string(), total_backing_stake, nominator_count: 1, rank: None }
+}
+
+fn allocation(nominator_id: &str, validator_id: &str, amount: u128) -> StakeAllocation {
+    StakeAllocation {
+        nominator_id: nominator_id.to_string(),
+        validator_id: validator_id.to_string(),
+        amount,
+        proportion: 1.0,
+        unrewarded: false,
+    }
+}
+
+#[test]
+fn test_phragmen_result_satisfies_pjr() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &data, true).unwrap();
+    let diagnostics = result.diagnostics.as_ref().unwrap();
+
+    let pjr = diagnostics.pjr().unwrap();
+    assert!(pjr.satisfied && pjr.standard_threshold);
+    assert_eq!(pjr.threshold, 500);
+    assert!(pjr.violations.is_empty());
+    assert!(pjr.locally_satisfied && pjr.local_violations.is_empty());
+    assert_eq!(diagnostics.findings_with_code(FindingCode::PjrViolated).count(), 0);
+
+    // Checked against a threshold below c's following, c's voters are under-represented
+    let strict = DiagnosticsGenerator::new().check_pjr(&result, &data, Some(50));
+    assert!(!strict.satisfied && !strict.standard_threshold);
+    assert_eq!(strict.counter_example.as_deref(), Some("c"));
+    assert_eq!(strict.violations[0].voter_ids, vec!["v3".to_string()]);
+    assert_eq!(strict.violations[0].pre_score, 100);
+}
+
+#[test]
+fn test_result_leaving_out_the_largest_group_violates_pjr() {
+    let data = election_data();
+    // Electing b and c ignores v1, who alone holds more than a seat's worth of stake
+    let result = ElectionResult::new(
+        vec![validator("b", 300), validator("c", 100)],
+        vec![allocation("v2", "b", 300), allocation("v3", "c", 100)],
+        1_000,
+        AlgorithmType::SequentialPhragmen,
+    );
+    let diagnostics = DiagnosticsGenerator::new().generate(&result, &data).unwrap();
+
+    let pjr = diagnostics.pjr().unwrap();
+    assert!(!pjr.satisfied);
+    assert_eq!(pjr.counter_example.as_deref(), Some("a"));
+    assert_eq!(pjr.violations.len(), 1);
+    assert_eq!(pjr.violations[0].pre_score, 600);
+
+    let findings: Vec<_> = diagnostics.findings_with_code(FindingCode::PjrViolated).collect();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].subject.as_deref(), Some("a"));
+    assert_eq!(findings[0].data["threshold"], 500);
+
+    // v1 alone is a seat's worth of stake behind "a" and backs nothing elected
+    assert!(!pjr.locally_satisfied);
+    let local: Vec<_> = diagnostics.findings_with_code(FindingCode::LocalPjrViolated).collect();
+    assert_eq!(local.len(), 1);
+    assert_eq!(local[0].subject.as_deref(), Some("a"));
+}
+
+#[test]
+fn test_group_sharing_two_candidates_is_owed_two_seats() {
+    // v1 and v2 hold two seats' worth of stake and both vote for "a" and "b"
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_candidate("d".to_string(), 0).unwrap()
+        .add_nominator("v1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("v2".to_string(), 1_000, vec!["b".to_string(), "a".to_string()]).unwrap()
+        .add_nominator("v3".to_string(), 500, vec!["c".to_string()]).unwrap()
+        .add_nominator("v4".to_string(), 500, vec!["d".to_string(), "b".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let result = ElectionResult::new(
+        vec![validator("a", 2_000), validator("c", 500), validator("d", 500)],
+        vec![
+            allocation("v1", "a", 1_000),
+            allocation("v2", "a", 1_000),
+            allocation("v3", "c", 500),
+            allocation("v4", "d", 500),
+        ],
+        3_000,
+        AlgorithmType::SequentialPhragmen,
+    );
+
+    let pjr = DiagnosticsGenerator::new().check_pjr(&result, &data, None);
+    assert_eq!(pjr.threshold, 1_000);
+    assert!(!pjr.locally_satisfied);
+    // "b" is also voted for by v4, who only shares "b" with them, so only a's voters are owed two seats
+    assert_eq!(pjr.local_violations.len(), 1);
+    let violation = &pjr.local_violations[0];
+    assert_eq!(violation.candidate_id, "a");
+    assert_eq!((violation.group_stake, violation.owed_seats, violation.elected_seats), (2_000, 2, 1));
+    assert_eq!(violation.voter_ids, vec!["v1".to_string(), "v2".to_string()]);
+
+    // Electing "b" in place of "d" gives the group its second seat
+    let fair = ElectionResult::new(
+        vec![validator("a", 1_000), validator("b", 1_500), validator("c", 500)],
+        vec![
+            allocation("v1", "a", 1_000),
+            allocation("v2", "b", 1_000),
+            allocation("v3", "c", 500),
+            allocation("v4", "b", 500),
+        ],
+        3_000,
+        AlgorithmType::SequentialPhragmen,
+    );
+    assert!(DiagnosticsGenerator::new().check_pjr(&fair, &data, None).locally_satisfied);
+}
@@ -0,0 +1,82 @@
+//! Diagnostics test: backing margins of validators around the cutoff
+
+use offline_election::diagnostics::DiagnosticsGenerator;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+
+/// "a", "b" and "c" are elected; "d" and "e" only share voters with them
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_candidate("d".to_string(), 0).unwrap()
+        .add_candidate("e".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 5_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 3_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 2_000, vec!["c".to_string()]).unwrap()
+        .add_nominator("n4".to_string(), 0, vec!["d".to_string(), "d".to_string()]).unwrap()
+        .add_nominator("n5".to_string(), 0, vec!["e".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_margins_compare_backing_with_runner_up_approval() {
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &election_data()).unwrap();
+    // Give the runners-up approval stake in the snapshot the result is examined against
+    let mut data = election_data();
+    data.nominators.iter_mut().find(|n| n.account_id == "n1").unwrap().targets.push("d".to_string());
+    data.nominators.iter_mut().find(|n| n.account_id == "n3").unwrap().targets.push("e".to_string());
+    let sensitivity = DiagnosticsGenerator::new().stake_sensitivity(&result, &data, 2);
+
+    assert_eq!(sensitivity.cutoff_backing, Some(2_000));
+    assert_eq!(sensitivity.runner_up_approval, Some(5_000));
+
+    // Weakest first; both could be overtaken by "d" if its voters moved
+    let elected: Vec<(&str, u128)> =
+        sensitivity.elected.iter().map(|m| (m.validator_id.as_str(), m.margin)).collect();
+    assert_eq!(elected, vec![("c", 0), ("b", 0)]);
+
+    // "d" already has more approval stake than the cutoff; "e" needs 1 more than c's backing
+    let near_misses: Vec<(&str, u128, u128)> = sensitivity
+        .near_misses
+        .iter()
+        .map(|n| (n.candidate_id.as_str(), n.approval_stake, n.shortfall))
+        .collect();
+    assert_eq!(near_misses, vec![("d", 5_000, 0), ("e", 2_000, 1)]);
+}
+
+#[test]
+fn test_sensitivity_is_part_of_generated_diagnostics() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &data, true).unwrap();
+    let diagnostics = result.diagnostics.unwrap();
+    let sensitivity = diagnostics.stake_sensitivity().unwrap();
+
+    // Runners-up have no approval stake, so every elected validator keeps its whole backing
+    let margins: Vec<(&str, u128)> =
+        sensitivity.elected.iter().map(|m| (m.validator_id.as_str(), m.margin)).collect();
+    assert_eq!(margins, vec![("c", 2_000), ("b", 3_000), ("a", 5_000)]);
+    assert_eq!(sensitivity.near_misses.len(), 2);
+    assert!(sensitivity.near_misses.iter().all(|n| n.approval_stake == 0 && n.shortfall == 2_001));
+}
+
+#[test]
+fn test_shortfall_counts_bonded_self_stake_without_a_self_vote() {
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &election_data()).unwrap();
+    let mut data = election_data();
+    data.candidates.iter_mut().find(|c| c.account_id == "d").unwrap().stake = 500;
+    data.candidates.iter_mut().find(|c| c.account_id == "e").unwrap().stake = 5_000;
+    let sensitivity = DiagnosticsGenerator::new().stake_sensitivity(&result, &data, 2);
+
+    // "d" needs c's backing plus one, less its bond; "e" is bonded past the cutoff
+    let shortfalls: Vec<(&str, u128)> =
+        sensitivity.near_misses.iter().map(|n| (n.candidate_id.as_str(), n.shortfall)).collect();
+    assert_eq!(shortfalls, vec![("d", 1_501), ("e", 0)]);
+}
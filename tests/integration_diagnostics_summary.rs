@@ -0,0 +1,56 @@
+//! Diagnostics test: plain-language summaries of election results
+
+use offline_election::diagnostics::DiagnosticsGenerator;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+
+fn elect(data: &ElectionData) -> ElectionResult {
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    ElectionEngine::new().execute(&config, data).unwrap()
+}
+
+/// Each nominator backs a single candidate so backings are easy to predict
+fn election_data(nominators: &[(&str, u128, &str)]) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in ["a", "b", "c", "d"] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+    }
+    for (nominator, stake, target) in nominators {
+        builder.add_nominator(nominator.to_string(), *stake, vec![target.to_string()]).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_summary_names_over_and_under_backed_validators() {
+    let result = elect(&election_data(&[("n1", 6_000, "a"), ("n2", 3_000, "b"), ("n3", 1_000, "c")]));
+    let summary = DiagnosticsGenerator::new().summary(&result, None);
+
+    let lines: Vec<&str> = summary.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("elected 3 validators backed by 10000 in total"));
+    assert!(lines[0].contains("from 1000 to 6000"));
+    assert_eq!(lines[1], "Most over-backed: a (6000, 180% of average).");
+    assert_eq!(lines[2], "Most under-backed: c (1000, 30% of average).");
+}
+
+#[test]
+fn test_summary_describes_changes_against_a_baseline() {
+    let baseline = elect(&election_data(&[("n1", 6_000, "a"), ("n2", 3_000, "b"), ("n3", 1_000, "c")]));
+    // "c" loses its backer to newcomer "d", and "a" grows
+    let result = elect(&election_data(&[("n1", 8_000, "a"), ("n2", 3_000, "b"), ("n3", 4_000, "d")]));
+    let summary = DiagnosticsGenerator::new().summary(&result, Some(&baseline));
+
+    assert!(summary.contains("Compared to the baseline: 1 entered and 1 left the elected set."));
+    assert!(summary.contains("Notable new entrants: d (backing 4000)."));
+    assert!(summary.contains("Largest stake movements: d +4000 (entered the set), a +2000 (6000 -> 8000), c -1000 (left the set)."));
+    assert!(summary.contains("Minimal stake 3000 (+2000), total stake 15000 (+5000)"));
+    assert!(summary.ends_with("the score is better than the baseline."));
+
+    let unchanged = DiagnosticsGenerator::new().summary(&baseline, Some(&baseline));
+    assert!(unchanged.contains("No validator's backing changed."));
+    assert!(unchanged.ends_with("the score is equal to the baseline."));
+}
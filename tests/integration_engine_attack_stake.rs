@@ -0,0 +1,47 @@
+//! Engine test: minimum stake an attacker needs to win seats
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::SybilSplit;
+
+/// Three honest validators, each backed by 1,000
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for index in 0..3 {
+        builder
+            .add_candidate(format!("honest-{}", index), 0).unwrap()
+            .add_nominator(format!("voter-{}", index), 1_000, vec![format!("honest-{}", index)]).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_estimate_brackets_the_stake_that_outpolls_honest_validators() {
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let engine = ElectionEngine::new();
+
+    // Each of three evenly backed sybils needs more than an honest validator's 1,000
+    let all_seats = engine.min_attack_stake(&config, &election_data(), 3, SybilSplit::Even).unwrap();
+    let stake = all_seats.min_stake.unwrap();
+    assert!((3_000..=3_006).contains(&stake), "{}", stake);
+    assert!(all_seats.max_failing_stake < stake && stake - all_seats.max_failing_stake <= 3);
+    assert_eq!(all_seats.honest_stake, 3_000);
+    assert_eq!(all_seats.attacker_share_bps, Some((stake * 10_000 / (3_000 + stake)) as u32));
+
+    // One seat is far cheaper
+    let one_seat = engine.min_attack_stake(&config, &election_data(), 1, SybilSplit::Even).unwrap();
+    let stake = one_seat.min_stake.unwrap();
+    assert!((1_000..=1_002).contains(&stake), "{}", stake);
+    assert!(one_seat.elections_run > 1);
+}
+
+#[test]
+fn test_seats_outside_the_active_set_are_rejected() {
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let engine = ElectionEngine::new();
+    assert!(engine.min_attack_stake(&config, &election_data(), 0, SybilSplit::Pooled).is_err());
+    let err = engine.min_attack_stake(&config, &election_data(), 4, SybilSplit::Pooled).unwrap_err();
+    assert!(err.to_string().contains("between 1 and the active set size of 3"), "{}", err);
+}
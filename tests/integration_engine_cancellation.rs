@@ -0,0 +1,86 @@
+//! Engine test: stopping elections with a cancellation token
+
+use offline_election::algorithms::balancing::balance;
+use offline_election::algorithms::{ElectionAlgorithm, PreparedElectionData, SequentialPhragmen};
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::{BalancingSettings, ElectionConfiguration};
+use offline_election::models::election_data::ElectionData;
+use offline_election::progress::RunControl;
+use offline_election::{CancellationToken, ElectionPhase};
+
+/// Two validators sharing a nominator, so balancing has work to do
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 3_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 1_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 500, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn cancelled_phase(err: &ElectionError) -> (Option<ElectionPhase>, Option<u32>) {
+    match err {
+        ElectionError::Cancelled { phase, rounds_completed, .. } => (*phase, *rounds_completed),
+        other => panic!("expected a cancellation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cancelled_token_stops_the_run_and_reports_the_phase() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder().active_set_size(2).balancing(10, 0).build().unwrap();
+    let engine = ElectionEngine::new();
+
+    // An untouched token changes nothing
+    let cancel = CancellationToken::new();
+    let result = engine.execute_cancellable(&config, &data, &cancel).unwrap();
+    let expected = engine.execute(&config, &data).unwrap();
+    assert_eq!(result.selected_validators, expected.selected_validators);
+    assert!(!cancel.is_cancelled());
+
+    // Clones share the flag
+    cancel.clone().cancel();
+    assert!(cancel.is_cancelled());
+    let err = engine.execute_cancellable(&config, &data, &cancel).unwrap_err();
+    assert_eq!(cancelled_phase(&err), (Some(ElectionPhase::Input), None));
+    assert_eq!(err.code(), "CANCELLED");
+    assert_eq!(err.exit_code(), 130);
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["phase"], "input");
+    assert!(json.get("rounds_completed").is_none());
+}
+
+#[test]
+fn test_algorithms_check_the_token_between_selection_and_balancing_rounds() {
+    let data = election_data();
+    let prepared = PreparedElectionData::new(&data);
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let control = RunControl::cancellable(cancel);
+
+    let err = SequentialPhragmen.execute_controlled(&prepared, &config, &control).unwrap_err();
+    assert_eq!(cancelled_phase(&err), (Some(ElectionPhase::Selection), Some(0)));
+
+    let mut solution = sp_npos_elections::seq_phragmen::<u32, sp_runtime::Perbill>(
+        2,
+        vec![0, 1, 2],
+        vec![(0, 3_000, vec![0, 1].into_iter()), (1, 1_000, vec![1].into_iter())],
+        None,
+    )
+    .unwrap();
+    let before = solution.winners.clone();
+    let settings = BalancingSettings { max_iterations: 10, tolerance: 0 };
+    let err = balance(&prepared, &mut solution, &settings, &control).unwrap_err();
+    assert_eq!(cancelled_phase(&err), (Some(ElectionPhase::Balancing), Some(0)));
+    assert!(err.to_string().contains("balancing after 0 rounds"));
+    assert_eq!(solution.winners, before);
+
+    let outcome = balance(&prepared, &mut solution, &settings, &RunControl::new()).unwrap();
+    assert!(outcome.iterations >= 1);
+}
@@ -0,0 +1,147 @@
+//! Engine test: pre-election candidate inclusion and exclusion rules
+
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::candidate_rules::{CandidateRule, CandidateRules};
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::models::validator::CandidateMetadata;
+use offline_election::types::AlgorithmType;
+
+fn targets(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+fn build(candidates: &[&str], nominators: &[(&str, u128, &[&str])]) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in candidates {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+    }
+    for (id, stake, votes) in nominators {
+        builder.add_nominator(id.to_string(), *stake, targets(votes)).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+fn run(data: &ElectionData, active_set_size: u32, rules: CandidateRules) -> Result<ElectionResult, ElectionError> {
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(active_set_size)
+        .candidate_rules(rules)
+        .build()?;
+    ElectionEngine::new().execute(&config, data)
+}
+
+fn winners(result: &ElectionResult) -> Vec<String> {
+    let mut ids: Vec<String> = result.selected_validators.iter().map(|v| v.account_id.clone()).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_exclusion_rules_record_affected_candidates() {
+    let mut data = build(
+        &["a", "b", "c", "d"],
+        &[
+            ("all", 1_000, &["a", "b", "c", "d"]),
+            ("only-a-b", 5_000, &["a", "b"]),
+            ("only-c-d", 100, &["c", "d"]),
+        ],
+    );
+    data.candidates[0].metadata = Some(CandidateMetadata {
+        commission_rate: Some(100),
+        ..Default::default()
+    });
+
+    let rules = CandidateRules::new().exclude_commission_at_least(100).exclude(["b"]);
+    let result = run(&data, 2, rules).unwrap();
+
+    assert_eq!(winners(&result), targets(&["c", "d"]));
+    let applications = &result.execution_metadata.rule_applications;
+    assert_eq!(applications.len(), 2);
+    assert_eq!(applications[0].affected, targets(&["a"]));
+    assert_eq!(applications[1].affected, targets(&["b"]));
+    // The nominator with no remaining targets no longer takes part
+    assert_eq!(result.total_stake, 1_100);
+    assert!(result.stake_distribution.iter().all(|a| a.nominator_id != "only-a-b"));
+}
+
+#[test]
+fn test_max_per_operator_keeps_highest_approval_candidates() {
+    let mut data = build(
+        &["a", "b", "c", "d"],
+        &[
+            ("all", 1_000, &["a", "b", "c", "d"]),
+            ("n-a", 3_000, &["a"]),
+            ("n-b", 2_000, &["b"]),
+            ("n-c", 500, &["c"]),
+        ],
+    );
+    for candidate in data.candidates.iter_mut().take(3) {
+        candidate.metadata = Some(CandidateMetadata {
+            identity_parent: Some("operator".to_string()),
+            ..Default::default()
+        });
+    }
+
+    let result = run(&data, 3, CandidateRules::new().max_per_operator(2)).unwrap();
+
+    assert_eq!(result.execution_metadata.rule_applications[0].affected, targets(&["c"]));
+    assert_eq!(winners(&result), targets(&["a", "b", "d"]));
+}
+
+#[test]
+fn test_force_include_seats_losing_candidate() {
+    let data = build(
+        &["a", "b", "c"],
+        &[
+            ("all", 1_000, &["a", "b", "c"]),
+            ("n-a", 5_000, &["a", "b"]),
+            ("n-b", 4_000, &["b", "a"]),
+        ],
+    );
+
+    let unconstrained = run(&data, 2, CandidateRules::new()).unwrap();
+    assert_eq!(winners(&unconstrained), targets(&["a", "b"]));
+
+    // Forced candidates are also protected from exclusion rules
+    let rules = CandidateRules::new().exclude(["c"]).force_include(["c"]);
+    let result = run(&data, 2, rules).unwrap();
+
+    assert!(winners(&result).contains(&"c".to_string()));
+    assert_eq!(result.selected_validators.len(), 2);
+    assert!(result.execution_metadata.rule_applications[0].affected.is_empty());
+}
+
+#[test]
+fn test_force_include_rejects_unknown_and_excess_candidates() {
+    let data = build(&["a", "b"], &[("all", 1_000, &["a", "b"])]);
+
+    let unknown = run(&data, 1, CandidateRules::new().force_include(["z"]));
+    assert!(matches!(unknown, Err(ElectionError::ValidationError { .. })));
+
+    let excess = run(&data, 1, CandidateRules::new().force_include(["a", "b"]));
+    assert!(matches!(excess, Err(ElectionError::ValidationError { .. })));
+}
+
+#[test]
+fn test_candidate_rules_deserialize_from_json_array() {
+    let rules: CandidateRules = serde_json::from_str(
+        r#"[
+            {"rule": "exclude-commission-at-least", "percent": 100},
+            {"rule": "max-per-operator", "max": 5},
+            {"rule": "force-include", "account_ids": ["a"]}
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        rules,
+        CandidateRules::new()
+            .rule(CandidateRule::ExcludeCommissionAtLeast { percent: 100 })
+            .max_per_operator(5)
+            .force_include(["a"])
+    );
+}
@@ -0,0 +1,82 @@
+//! Engine test: council-style elections leave unbacked seats empty and rank seats by backing
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::types::{AlgorithmType, ElectionKind};
+
+/// Council candidates carry no stake; "d" receives no votes
+fn council_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_candidate("d".to_string(), 0).unwrap()
+        .add_nominator("v1".to_string(), 3_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("v2".to_string(), 5_000, vec!["a".to_string(), "c".to_string()]).unwrap()
+        .add_nominator("v3".to_string(), 9_000, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn run(data: &ElectionData, kind: ElectionKind) -> ElectionResult {
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(4)
+        .kind(kind)
+        .force_invulnerables(true)
+        .build()
+        .unwrap();
+    ElectionEngine::new().execute(&config, data).unwrap()
+}
+
+fn seats(result: &ElectionResult) -> Vec<(&str, Option<u32>)> {
+    result
+        .selected_validators
+        .iter()
+        .map(|v| (v.account_id.as_str(), v.rank))
+        .collect()
+}
+
+#[test]
+fn test_unbacked_candidates_do_not_take_council_seats() {
+    let data = council_data();
+
+    let staking = run(&data, ElectionKind::Staking);
+    assert_eq!(staking.selected_validators.len(), 4);
+
+    let council = run(&data, ElectionKind::Council);
+    assert!(council.selected_validators.iter().all(|v| v.total_backing_stake > 0));
+    assert!(council.stake_distribution.iter().all(|a| a.amount > 0));
+    let backing: Vec<u128> = council.selected_validators.iter().map(|v| v.total_backing_stake).collect();
+    assert!(backing.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert_eq!(seats(&council)[0], ("c", Some(1)));
+    assert!(!seats(&council).iter().any(|(id, _)| *id == "d"));
+
+    // The prepared path applies the same seat rules
+    let engine = ElectionEngine::new();
+    let prepared = engine.prepare(&data).unwrap();
+    let config = ElectionConfiguration::builder().active_set_size(4).kind(ElectionKind::Council).build().unwrap();
+    let from_prepared = engine.execute_prepared(&config, &prepared, false).unwrap();
+    assert_eq!(seats(&from_prepared), seats(&council));
+}
+
+#[test]
+fn test_council_elections_ignore_invulnerables_and_kind_round_trips() {
+    let mut data = council_data();
+    data.invulnerables = vec!["d".to_string()];
+    assert!(run(&data, ElectionKind::Staking).selected_validators.iter().any(|v| v.account_id == "d"));
+    assert!(!run(&data, ElectionKind::Council).selected_validators.iter().any(|v| v.account_id == "d"));
+
+    assert_eq!("council".parse::<ElectionKind>(), Ok(ElectionKind::Council));
+    assert!("senate".parse::<ElectionKind>().is_err());
+    let config = ElectionConfiguration::builder().active_set_size(100).kind(ElectionKind::Council).build().unwrap();
+    let json = serde_json::to_value(&config).unwrap();
+    assert_eq!(json["kind"], "council");
+    let staking = serde_json::to_value(ElectionConfiguration::builder().active_set_size(100).build().unwrap()).unwrap();
+    assert!(staking.get("kind").is_none());
+    let parsed: ElectionConfiguration = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.kind, ElectionKind::Council);
+}
@@ -0,0 +1,109 @@
+//! Engine test: results serialize identically on every platform
+
+use offline_election::cache::ResultCache;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::types::AlgorithmType;
+
+fn data(stakes: &[(&str, u128, &[&str])]) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in ["a", "b", "c"] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+    }
+    for (nominator, stake, targets) in stakes {
+        builder
+            .add_nominator(nominator.to_string(), *stake, targets.iter().map(|t| t.to_string()).collect())
+            .unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_result_hash_ignores_run_specific_metadata() {
+    let data = data(&[("n1", 1_000, &["a", "b"]), ("n2", 700, &["b", "c"]), ("n3", 300, &["a", "c"])]);
+    let config =
+        ElectionConfiguration::builder().algorithm(AlgorithmType::SequentialPhragmen).active_set_size(2).build().unwrap();
+    let engine = ElectionEngine::new().with_result_cache(ResultCache::new(4));
+
+    let first = engine.execute(&config, &data).unwrap();
+    let mut second = engine.execute(&config, &data).unwrap();
+    assert!(second.execution_metadata.from_cache);
+    assert_eq!(second.content_hash(), first.content_hash());
+
+    second.execution_metadata.execution_timestamp = Some("1970-01-01T00:00:00Z".to_string());
+    second.execution_metadata.algorithm_duration_micros = Some(u64::MAX);
+    second.execution_metadata.crate_version = Some("0.0.0".to_string());
+    assert_eq!(second.content_hash(), first.content_hash());
+
+    second.stake_distribution[0].proportion = f64::from_bits(second.stake_distribution[0].proportion.to_bits() + 1);
+    assert_ne!(second.content_hash(), first.content_hash());
+}
+
+#[cfg(feature = "regression")]
+#[test]
+fn test_golden_results_reproduce_their_recorded_hashes() {
+    use offline_election::regression::{fixture_names, load_fixture};
+
+    let engine = ElectionEngine::new();
+    for name in fixture_names() {
+        let (data, expected) = load_fixture(name).unwrap();
+        for election in &expected.elections {
+            let config = ElectionConfiguration::builder()
+                .algorithm(election.algorithm)
+                .active_set_size(election.active_set_size)
+                .build()
+                .unwrap();
+            let result = engine.execute(&config, &data).unwrap();
+            assert_eq!(result.content_hash(), election.result_hash, "{} {}", name, election.algorithm);
+        }
+    }
+}
+
+/// Candidates and nominators with uneven stakes and overlapping votes
+fn uneven_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in 0..40 {
+        builder.add_candidate(format!("c{}", candidate), 0).unwrap();
+    }
+    let mut seed: u64 = 7;
+    for nominator in 0..300 {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        let stake = u128::from(seed >> 24) % 1_000_000_000_000 + 1;
+        let targets = (0..16).map(|i| format!("c{}", (seed >> (i * 3)) % 40)).collect();
+        builder.add_nominator(format!("n{}", nominator), stake, targets).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_proportions_are_exact_billionths() {
+    let data = uneven_data();
+    for algorithm in [AlgorithmType::SequentialPhragmen, AlgorithmType::ParallelPhragmen, AlgorithmType::Mms] {
+        let config = ElectionConfiguration::builder().algorithm(algorithm).active_set_size(24).build().unwrap();
+        let result = ElectionEngine::new().execute(&config, &data).unwrap();
+        for allocation in &result.stake_distribution {
+            // The nearest double to parts / 10^9, for integer parts
+            let parts = (allocation.proportion * 1e9).round();
+            assert!((0.0..=1e9).contains(&parts));
+            assert_eq!(parts / 1e9, allocation.proportion, "{:?}", allocation);
+        }
+    }
+}
+
+#[test]
+fn test_top_tier_stake_uses_an_exact_threshold() {
+    // Backings of 1000, 800 and 799: 80% of the highest is top tier, just below is not
+    let data = data(&[("n1", 1_000, &["a"]), ("n2", 800, &["b"]), ("n3", 799, &["c"])]);
+    let config =
+        ElectionConfiguration::builder().algorithm(AlgorithmType::SequentialPhragmen).active_set_size(3).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &data, true).unwrap();
+    let explanations = result.diagnostics.unwrap().validator_explanations;
+    let top_tier = |id: &str| {
+        let explanation = explanations.iter().find(|e| e.account_id == id).unwrap();
+        explanation.key_factors.iter().any(|factor| factor == "Top tier stake")
+    };
+    assert!(top_tier("b"));
+    assert!(!top_tier("c"));
+}
@@ -0,0 +1,53 @@
+//! What-if evaluation test: override impact against a baseline election
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::types::AlgorithmType;
+
+#[test]
+fn test_evaluate_override_reports_set_and_score_changes() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("validator-a".to_string(), 0).unwrap()
+        .add_candidate("validator-b".to_string(), 0).unwrap()
+        .add_candidate("validator-c".to_string(), 0).unwrap()
+        .add_nominator("nominator-1".to_string(), 5_000, vec!["validator-a".to_string()]).unwrap()
+        .add_nominator(
+            "nominator-2".to_string(),
+            3_000,
+            vec!["validator-b".to_string(), "validator-c".to_string()],
+        ).unwrap()
+        .add_nominator(
+            "nominator-3".to_string(),
+            1_000,
+            vec!["validator-c".to_string(), "validator-a".to_string()],
+        ).unwrap();
+    let data = builder.build().unwrap();
+
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .build()
+        .unwrap();
+
+    let mut overrides = ElectionOverrides::new();
+    overrides.remove_voting_edge("nominator-2".to_string(), "validator-c".to_string()).unwrap();
+
+    let impact = ElectionEngine::new()
+        .evaluate_override(&data, &config, &overrides)
+        .unwrap();
+
+    assert!(impact.set_changed());
+    assert_eq!(impact.validators_added, vec!["validator-b".to_string()]);
+    assert_eq!(impact.validators_removed, vec!["validator-c".to_string()]);
+
+    let nominator_2 = impact
+        .affected_nominators
+        .iter()
+        .find(|n| n.nominator_id == "nominator-2")
+        .expect("nominator-2 should be affected");
+    assert_eq!(nominator_2.validators_gained, vec!["validator-b".to_string()]);
+    assert_eq!(nominator_2.validators_lost, vec!["validator-c".to_string()]);
+}
@@ -0,0 +1,103 @@
+//! Engine test: execution metadata records what reproducibility audits need
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::{ElectionData, ElectionMetadata};
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::models::election_result::ElectionCounts;
+
+/// "ghost" votes for an unknown candidate and "idle" for nobody, so both are
+/// trimmed; they have no stake as the engine allocates all nominator stake
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in ["a", "b", "c"] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+    }
+    builder
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 2_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("idle".to_string(), 0, vec![]).unwrap();
+    let mut data = builder.build().unwrap();
+    let mut ghost = data.nominators[0].clone();
+    ghost.account_id = "ghost".to_string();
+    ghost.stake = 0;
+    ghost.targets = vec!["nobody".to_string()];
+    data.nominators.push(ghost);
+    data
+}
+
+#[test]
+fn test_metadata_records_counts_version_and_hashes() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder()
+        .active_set_size(2)
+        .validation_profile(offline_election::models::validation::ValidationProfile::Permissive)
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    let metadata = &result.execution_metadata;
+
+    assert_eq!(metadata.input_counts, Some(ElectionCounts { voters: 4, candidates: 3, edges: 3 }));
+    assert_eq!(metadata.solver_counts, Some(ElectionCounts { voters: 2, candidates: 3, edges: 2 }));
+    assert_eq!(metadata.balancing_iterations, Some(0));
+    assert!(metadata.algorithm_duration_micros.is_some());
+    assert_eq!(metadata.crate_version.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+    assert_eq!(metadata.data_hash.as_deref(), Some(data.content_hash().as_str()));
+    assert_eq!(metadata.config_hash.as_deref(), Some(config.content_hash().as_str()));
+    assert_eq!(metadata.data_hash.as_ref().unwrap().len(), 64);
+
+    // The prepared path records the same hashes
+    let engine = ElectionEngine::new();
+    let prepared_data = {
+        let mut clean = data.clone();
+        clean.sanitize();
+        clean
+    };
+    let prepared = engine.prepare(&prepared_data).unwrap();
+    let default_config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let prepared_result = engine.execute_prepared(&default_config, &prepared, false).unwrap();
+    assert_eq!(prepared_result.execution_metadata.data_hash.as_deref(), Some(prepared_data.content_hash().as_str()));
+}
+
+#[test]
+fn test_content_hashes_ignore_map_order_but_not_content() {
+    let mut forward = ElectionOverrides::new();
+    let mut backward = ElectionOverrides::new();
+    let accounts = ["a", "b", "c", "d", "e", "f", "g", "h"];
+    for (stake, account) in accounts.iter().enumerate() {
+        forward.set_candidate_stake(account.to_string(), stake as u128).unwrap();
+    }
+    for (stake, account) in accounts.iter().enumerate().rev() {
+        backward.set_candidate_stake(account.to_string(), stake as u128).unwrap();
+    }
+    let config = |overrides| ElectionConfiguration::builder().active_set_size(2).overrides(overrides).build().unwrap();
+    assert_eq!(config(forward.clone()).content_hash(), config(backward).content_hash());
+    forward.set_candidate_stake("a".to_string(), 99).unwrap();
+    assert_ne!(config(forward.clone()).content_hash(), config(ElectionOverrides::new()).content_hash());
+
+    let data = election_data();
+    let mut changed = data.clone();
+    changed.nominators[0].stake += 1;
+    assert_eq!(data.content_hash(), data.clone().content_hash());
+    assert_ne!(data.content_hash(), changed.content_hash());
+}
+
+#[test]
+fn test_data_hash_ignores_load_order_and_fetch_metadata() {
+    let data = election_data();
+    let mut refetched = data.clone();
+    refetched.nominators.reverse();
+    refetched.candidates.reverse();
+    refetched.metadata = Some(ElectionMetadata {
+        rpc_retries: Some(3),
+        load_failures: vec!["Staking::Ledger(n1)".to_string()],
+        ..Default::default()
+    });
+    let mut plain = data.clone();
+    plain.metadata = Some(ElectionMetadata { rpc_retries: None, load_failures: Vec::new(), ..refetched.metadata.clone().unwrap() });
+
+    assert_eq!(plain.content_hash(), refetched.content_hash());
+    refetched.metadata.as_mut().unwrap().block_number = Some(7);
+    assert_ne!(plain.content_hash(), refetched.content_hash());
+}
@@ -0,0 +1,93 @@
+//! Engine test: forced invulnerables consume active set slots before the algorithm runs
+
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::types::AlgorithmType;
+
+fn election_data(invulnerables: &[&str]) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("all".to_string(), 1_000, vec!["a".to_string(), "b".to_string(), "c".to_string()]).unwrap()
+        .add_nominator("n-a".to_string(), 5_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n-b".to_string(), 4_000, vec!["b".to_string()]).unwrap();
+    let mut data = builder.build().unwrap();
+    data.invulnerables = invulnerables.iter().map(|id| id.to_string()).collect();
+    data
+}
+
+fn config(active_set_size: u32) -> ElectionConfiguration {
+    ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(active_set_size)
+        .force_invulnerables(true)
+        .build()
+        .unwrap()
+}
+
+fn run(data: &ElectionData, active_set_size: u32) -> Result<ElectionResult, ElectionError> {
+    ElectionEngine::new().execute(&config(active_set_size), data)
+}
+
+fn winners(result: &ElectionResult) -> Vec<&str> {
+    let mut ids: Vec<&str> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_invulnerable_takes_a_seat_before_the_algorithm_runs() {
+    assert_eq!(winners(&run(&election_data(&[]), 2).unwrap()), vec!["a", "b"]);
+
+    let result = run(&election_data(&["c"]), 2).unwrap();
+
+    assert_eq!(winners(&result), vec!["a", "c"]);
+    // The invulnerable still receives stake from its own nominators
+    let c = result.selected_validators.iter().find(|v| v.account_id == "c").unwrap();
+    assert!(c.total_backing_stake > 0);
+    // n-b only voted for b, which lost its seat to the invulnerable
+    assert_eq!(result.total_stake, 6_000);
+}
+
+#[test]
+fn test_invulnerables_only_take_seats_when_forced() {
+    // pallet-staking never seats invulnerables, so by default they run like any candidate
+    let config = ElectionConfiguration { force_invulnerables: false, ..config(2) };
+    let result = ElectionEngine::new().execute(&config, &election_data(&["c"])).unwrap();
+    assert_eq!(winners(&result), vec!["a", "b"]);
+}
+
+#[test]
+fn test_diagnostics_of_forced_runs_see_every_candidate() {
+    let result = ElectionEngine::new().execute_with_diagnostics(&config(1), &election_data(&["c"]), true).unwrap();
+    assert_eq!(winners(&result), vec!["c"]);
+
+    // "a" and "b" lost to the invulnerable but are still near misses
+    let sensitivity = result.diagnostics.unwrap().stake_sensitivity.unwrap();
+    let near_misses: Vec<&str> = sensitivity.near_misses.iter().map(|m| m.candidate_id.as_str()).collect();
+    assert_eq!(near_misses, vec!["a", "b"]);
+}
+
+#[test]
+fn test_invulnerables_that_are_not_candidates_are_ignored() {
+    let result = run(&election_data(&["not-a-candidate"]), 2).unwrap();
+    assert_eq!(winners(&result), vec!["a", "b"]);
+}
+
+#[test]
+fn test_more_invulnerables_than_seats_is_rejected() {
+    let result = run(&election_data(&["a", "c"]), 1);
+    assert!(matches!(result, Err(ElectionError::ValidationError { .. })));
+}
+
+#[test]
+fn test_duplicate_invulnerables_fail_validation() {
+    let data = election_data(&["c", "c"]);
+    assert!(matches!(data.validate(), Err(ElectionError::ValidationError { .. })));
+}
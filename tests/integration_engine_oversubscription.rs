@@ -0,0 +1,69 @@
+//! Engine test: oversubscribed validators reward only their largest nominators
+
+use offline_election::diagnostics::FindingCode;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+
+/// One candidate voting for itself and `nominators` nominators backing it alone
+fn election_data(nominators: usize) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder.add_candidate("v".to_string(), 0).unwrap();
+    builder.add_nominator("v".to_string(), 50, vec!["v".to_string()]).unwrap();
+    for i in 0..nominators {
+        builder
+            .add_nominator(format!("n{}", i), 100 * (i as u128 + 1), vec!["v".to_string()])
+            .unwrap();
+    }
+    // Ties with n1 and ranks after it by account
+    builder.add_nominator("n9".to_string(), 200, vec!["v".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_allocations_beyond_the_limit_are_unrewarded() {
+    let config = ElectionConfiguration::builder().active_set_size(1).max_rewarded_nominators(3).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &election_data(4), true).unwrap();
+
+    let mut unrewarded: Vec<&str> = result
+        .stake_distribution
+        .iter()
+        .filter(|a| a.unrewarded)
+        .map(|a| a.nominator_id.as_str())
+        .collect();
+    unrewarded.sort();
+    // n3, n2 and n1 are paid; the self-vote is always paid
+    assert_eq!(unrewarded, vec!["n0", "n9"]);
+    assert_eq!(result.unrewarded_stake(), 300);
+
+    let diagnostics = result.diagnostics.as_ref().unwrap();
+    let stake = diagnostics.unrewarded_stake().unwrap();
+    assert_eq!(stake.total_unrewarded, 300);
+    assert_eq!(stake.nominator_count, 2);
+    assert_eq!(stake.share_bps, 300 * 10_000 / 1_250);
+    assert_eq!(stake.validators[0].nominator_count, 5);
+    assert_eq!(stake.validators[0].unrewarded_count, 2);
+    assert_eq!(stake.validators[0].rewarded_minimum, 200);
+    assert_eq!(diagnostics.findings_with_code(FindingCode::ValidatorOversubscribed).count(), 1);
+}
+
+#[test]
+fn test_no_limit_marks_nothing() {
+    let config = ElectionConfiguration::builder().active_set_size(1).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &election_data(4), true).unwrap();
+    assert!(result.stake_distribution.iter().all(|a| !a.unrewarded));
+    assert!(result.diagnostics.as_ref().unwrap().unrewarded_stake().is_none());
+    assert!(!result.to_json().unwrap().contains("unrewarded"));
+
+    // A limit the validator stays within marks nothing either
+    let config = ElectionConfiguration::builder().active_set_size(1).max_rewarded_nominators(5).build().unwrap();
+    let result = ElectionEngine::new().execute_with_diagnostics(&config, &election_data(4), true).unwrap();
+    assert_eq!(result.unrewarded_stake(), 0);
+}
+
+#[test]
+fn test_zero_limit_is_rejected() {
+    let error = ElectionConfiguration::builder().active_set_size(1).max_rewarded_nominators(0).build().unwrap_err();
+    assert!(error.to_string().contains("Max rewarded nominators"), "{}", error);
+}
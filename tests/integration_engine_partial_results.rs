@@ -0,0 +1,121 @@
+//! Engine test: partial-result mode lists recoverable problems instead of failing
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::candidate_rules::CandidateRules;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::{ElectionData, ElectionMetadata};
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::models::election_result::ResultIssueKind;
+use offline_election::types::{AlgorithmType, ElectionKind};
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n-a".to_string(), 5_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n-b".to_string(), 4_000, vec!["b".to_string(), "c".to_string()]).unwrap();
+    let mut data = builder.build().unwrap();
+    data.metadata = Some(ElectionMetadata {
+        load_failures: vec!["1 of 3 Staking::Nominators queries failed\n    - n-x: connection reset".to_string()],
+        ..Default::default()
+    });
+    data
+}
+
+fn config(partial_results: bool) -> ElectionConfiguration {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_stake("a".to_string(), 100).unwrap();
+    overrides.set_nominator_stake("ghost".to_string(), 1).unwrap();
+    overrides.add_voting_edge("nobody".to_string(), "a".to_string()).unwrap();
+    ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .overrides(overrides)
+        .candidate_rules(CandidateRules::new().force_include(["c", "missing"]))
+        .partial_results(partial_results)
+        .force_invulnerables(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_partial_results_list_issues_instead_of_failing() {
+    let mut data = election_data();
+    data.invulnerables = vec!["retired".to_string()];
+    let engine = ElectionEngine::new();
+
+    // Force-including a non-candidate fails the election unless partial results are allowed
+    assert!(engine.execute(&config(false), &data).is_err());
+
+    let result = engine.execute(&config(true), &data).unwrap();
+    assert!(result.selected_validators.iter().any(|v| v.account_id == "c"));
+    let kinds: Vec<ResultIssueKind> = result.issues.iter().map(|issue| issue.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ResultIssueKind::LoadFailure,
+            ResultIssueKind::UnmatchedOverride,
+            ResultIssueKind::UnmatchedOverride,
+            ResultIssueKind::UnknownForcedCandidate,
+            ResultIssueKind::UnknownInvulnerable,
+        ]
+    );
+    assert!(result.issues[1].message.contains("ghost"));
+    assert!(result.issues[2].message.contains("nobody"));
+    assert!(result.issues[3].message.contains("missing"));
+
+    let json = result.to_json().unwrap();
+    assert!(json.contains("\"kind\": \"unknown-forced-candidate\""));
+}
+
+#[test]
+fn test_issues_are_only_attached_in_partial_mode() {
+    let data = election_data();
+    let engine = ElectionEngine::new();
+    let plain = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+
+    let result = engine.execute(&plain, &data).unwrap();
+    assert!(result.issues.is_empty());
+    assert!(!result.to_json().unwrap().contains("\"issues\""));
+
+    let partial = ElectionConfiguration::builder().active_set_size(2).partial_results(true).build().unwrap();
+    assert_ne!(plain.content_hash(), partial.content_hash());
+    let prepared = engine.prepare(&data).unwrap();
+    let result = engine.execute_prepared(&partial, &prepared, false).unwrap();
+    assert_eq!(result.issues.len(), 1);
+    assert_eq!(result.issues[0].kind, ResultIssueKind::LoadFailure);
+    assert!(result.issues[0].message.contains("connection reset"));
+}
+
+#[test]
+fn test_forced_candidate_without_backing_is_reported() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("unbacked".to_string(), 0).unwrap()
+        .add_nominator("v1".to_string(), 5_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("v2".to_string(), 4_000, vec!["b".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let config = |partial_results| {
+        ElectionConfiguration::builder()
+            .kind(ElectionKind::Council)
+            .active_set_size(2)
+            .candidate_rules(CandidateRules::new().force_include(["unbacked"]))
+            .partial_results(partial_results)
+            .build()
+            .unwrap()
+    };
+    let engine = ElectionEngine::new();
+
+    let error = engine.execute(&config(false), &data).unwrap_err();
+    assert!(error.to_string().contains("Forced candidate unbacked was not elected"), "{}", error);
+
+    let result = engine.execute(&config(true), &data).unwrap();
+    assert!(result.selected_validators.iter().all(|v| v.account_id != "unbacked"));
+    let issue = result.issues.iter().find(|i| i.kind == ResultIssueKind::ForcedCandidateNotElected).unwrap();
+    assert!(issue.message.contains("unbacked"));
+}
@@ -0,0 +1,88 @@
+//! Engine test: prepared solver input reused across runs
+
+use offline_election::algorithms::PreparedElectionData;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::candidate_rules::CandidateRules;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::types::AlgorithmType;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in ["a", "b", "c", "d"] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+    }
+    builder
+        .add_nominator("n1".to_string(), 5_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 3_000, vec!["b".to_string(), "c".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 2_000, vec!["c".to_string(), "d".to_string()]).unwrap()
+        .add_nominator("n4".to_string(), 1_000, vec!["a".to_string(), "d".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn config(algorithm: AlgorithmType, active_set_size: u32) -> ElectionConfiguration {
+    ElectionConfiguration::builder()
+        .algorithm(algorithm)
+        .active_set_size(active_set_size)
+        .build()
+        .unwrap()
+}
+
+fn without_timestamp(mut result: ElectionResult) -> ElectionResult {
+    result.execution_metadata.execution_timestamp = None;
+    result.execution_metadata.algorithm_duration_micros = None;
+    result
+}
+
+#[test]
+fn test_prepared_data_builds_csr_edges_for_known_candidates() {
+    let mut data = election_data();
+    data.nominators[1].targets.push("gone".to_string());
+    data.nominators[1].targets.push("b".to_string());
+    let prepared = PreparedElectionData::new(&data);
+
+    assert_eq!(prepared.candidate_count(), 4);
+    assert_eq!(prepared.voter_count(), 4);
+    // The vote for an unknown candidate is dropped
+    assert_eq!(prepared.edge_count(), 9);
+    assert_eq!(prepared.voter_targets(1), &[1, 2, 1]);
+}
+
+#[test]
+fn test_execute_prepared_matches_execute_for_every_algorithm_and_size() {
+    let data = election_data();
+    let engine = ElectionEngine::new();
+    let prepared = engine.prepare(&data).unwrap();
+
+    for algorithm in [
+        AlgorithmType::SequentialPhragmen,
+        AlgorithmType::ParallelPhragmen,
+        AlgorithmType::MultiPhase,
+    ] {
+        for active_set_size in 1..=5 {
+            let config = config(algorithm, active_set_size);
+            // Small active sets leave some nominators unbacked and fail
+            // result validation; both paths must agree on that too
+            let direct = engine.execute(&config, &data).map(without_timestamp);
+            let reused = engine.execute_prepared(&config, &prepared, false).map(without_timestamp);
+            assert_eq!(reused.map_err(|e| e.to_string()), direct.map_err(|e| e.to_string()));
+        }
+    }
+}
+
+#[test]
+fn test_execute_prepared_applies_rules_through_the_full_path() {
+    let data = election_data();
+    let engine = ElectionEngine::new();
+    let prepared = engine.prepare(&data).unwrap();
+
+    let mut config = config(AlgorithmType::SequentialPhragmen, 2);
+    config.candidate_rules = Some(CandidateRules::new().exclude(["b"]));
+    let result = engine.execute_prepared(&config, &prepared, true).unwrap();
+
+    assert!(result.selected_validators.iter().all(|v| v.account_id != "b"));
+    assert_eq!(result.execution_metadata.rule_applications.len(), 1);
+    assert!(result.diagnostics.is_some());
+}
@@ -0,0 +1,126 @@
+//! Engine test: winner order stability under set size changes and stake noise
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::rank_stability::{Perturbation, PerturbedRun, RankStability, StabilityScenario};
+
+fn ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+/// One nominator per candidate; v1 and v2 are within half a percent of each other
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for (index, stake) in [10_000u128, 3_000, 2_990, 1_000].into_iter().enumerate() {
+        let candidate = format!("v{}", index);
+        builder.add_candidate(candidate.clone(), 0).unwrap();
+        builder.add_nominator(format!("n{}", index), stake, vec![candidate]).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_kendall_tau_of_winner_orderings() {
+    let noise = Perturbation::StakeNoise { seed: 0, max_bps: 100 };
+    let baseline = ids(&["a", "b", "c"]);
+
+    assert_eq!(PerturbedRun::between(noise, &baseline, &ids(&["a", "b", "c"])).kendall_tau_bps, 10_000);
+    assert_eq!(PerturbedRun::between(noise, &baseline, &ids(&["c", "b", "a"])).kendall_tau_bps, -10_000);
+    // One swapped pair out of three
+    assert_eq!(PerturbedRun::between(noise, &baseline, &ids(&["a", "c", "b"])).kendall_tau_bps, 3_333);
+
+    let run = PerturbedRun::between(noise, &baseline, &ids(&["b", "a", "d"]));
+    assert_eq!(run.common_winners, 2);
+    assert_eq!(run.kendall_tau_bps, -10_000);
+    assert_eq!(run.validators_added, ids(&["d"]));
+    assert_eq!(run.validators_removed, ids(&["c"]));
+}
+
+#[test]
+fn test_cutoff_changes_of_set_size_runs_are_not_fragile() {
+    let stability = RankStability::from_runs(
+        2,
+        ids(&["a", "b"]),
+        vec![
+            (Perturbation::SetSize { active_set_size: 1 }, ids(&["a"])),
+            (Perturbation::SetSize { active_set_size: 3 }, ids(&["a", "b", "c"])),
+            (Perturbation::StakeNoise { seed: 0, max_bps: 100 }, ids(&["a", "d"])),
+        ],
+    );
+
+    let fragile: Vec<&str> = stability.fragile().map(|v| v.validator_id.as_str()).collect();
+    assert_eq!(fragile, vec!["b", "d"]);
+    let b = &stability.validators[1];
+    assert_eq!((b.baseline_rank, b.elected_runs, b.best_rank, b.worst_rank), (Some(2), 1, Some(2), Some(2)));
+    assert_eq!(stability.validators.iter().map(|v| v.validator_id.as_str()).collect::<Vec<_>>(), vec!["a", "b", "c", "d"]);
+    assert_eq!(stability.mean_kendall_tau_bps, 10_000);
+}
+
+#[test]
+fn test_near_tie_at_the_cutoff_is_fragile() {
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let scenario = StabilityScenario::new().noise_runs(20).seed(7);
+    let engine = ElectionEngine::new();
+    let stability = engine.rank_stability(&config, &election_data(), &scenario).unwrap();
+
+    assert_eq!(stability.baseline_winners, ids(&["v0", "v1"]));
+    assert_eq!(stability.runs.len(), 22);
+    let fragile: Vec<&str> = stability.fragile().map(|v| v.validator_id.as_str()).collect();
+    assert_eq!(fragile, vec!["v1", "v2"]);
+    let v0 = &stability.validators[0];
+    assert_eq!(v0.elected_runs, 22);
+    assert_eq!(v0.worst_rank, Some(1));
+
+    // The same seed draws the same noise
+    assert_eq!(engine.rank_stability(&config, &election_data(), &scenario).unwrap(), stability);
+}
+
+#[test]
+fn test_noise_follows_the_nominator_not_its_position() {
+    let data = election_data();
+    let mut reversed = SyntheticDataBuilder::new();
+    for candidate in &data.candidates {
+        reversed.add_candidate(candidate.account_id.clone(), 0).unwrap();
+    }
+    for nominator in data.nominators.iter().rev() {
+        reversed.add_nominator(nominator.account_id.clone(), nominator.stake, nominator.targets.clone()).unwrap();
+    }
+    let reversed = reversed.build().unwrap();
+
+    let noise = Perturbation::StakeNoise { seed: 3, max_bps: 500 };
+    let stake_of = |data: &ElectionData, account_id: &str| {
+        data.nominators.iter().find(|n| n.account_id == account_id).unwrap().stake
+    };
+    let perturb = |data: &ElectionData| {
+        let mut perturbed = data.clone();
+        noise.delta(data).apply(&mut perturbed);
+        perturbed
+    };
+    let (perturbed, perturbed_reversed) = (perturb(&data), perturb(&reversed));
+    for nominator in &data.nominators {
+        let stake = stake_of(&perturbed, &nominator.account_id);
+        assert_eq!(stake, stake_of(&perturbed_reversed, &nominator.account_id));
+        assert!(stake.abs_diff(nominator.stake) * 20 <= nominator.stake);
+    }
+}
+
+#[test]
+fn test_scenario_must_perturb_something() {
+    assert!(StabilityScenario::new().stake_noise_bps(10_001).validate().is_err());
+    assert!(StabilityScenario::new().set_size_delta(0).noise_runs(0).validate().is_err());
+    assert!(StabilityScenario::new().noise_runs(0).validate().is_ok());
+    assert_eq!(StabilityScenario::new().perturbations(1).len(), 6, "no empty set below one seat");
+}
+
+#[test]
+fn test_noise_beyond_full_stake_is_clamped() {
+    let data = election_data();
+    let delta = Perturbation::StakeNoise { seed: 1, max_bps: u32::MAX }.delta(&data);
+    for nominator in &data.nominators {
+        let stake = delta.overrides.nominator_stakes.get(&nominator.account_id).copied().unwrap_or(nominator.stake);
+        assert!(stake <= nominator.stake * 2);
+    }
+    assert!(Perturbation::SetSize { active_set_size: 3 }.delta(&data).is_empty());
+}
@@ -0,0 +1,134 @@
+//! Engine test: full result detail carries the solver's raw solution
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::account_format::AccountFormat;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::raw_solution::ResultDetail;
+use offline_election::types::AlgorithmType;
+use sp_runtime::Perbill;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 600, vec!["b".to_string(), "c".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 300, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn config(algorithm: AlgorithmType, detail: ResultDetail) -> ElectionConfiguration {
+    ElectionConfiguration::builder()
+        .algorithm(algorithm)
+        .active_set_size(2)
+        .result_detail(detail)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_summary_detail_leaves_raw_solution_out() {
+    let result = ElectionEngine::new()
+        .execute(&config(AlgorithmType::SequentialPhragmen, ResultDetail::Summary), &election_data())
+        .unwrap();
+    assert!(result.raw_solution.is_none());
+    assert!(!serde_json::to_string(&result).unwrap().contains("raw_solution"));
+}
+
+#[test]
+fn test_full_detail_matches_converted_result() {
+    let data = election_data();
+    for algorithm in [AlgorithmType::SequentialPhragmen, AlgorithmType::ParallelPhragmen, AlgorithmType::Mms] {
+        let result = ElectionEngine::new().execute(&config(algorithm, ResultDetail::Full), &data).unwrap();
+        let raw = result.raw_solution.as_ref().expect("raw solution");
+
+        let winners: Vec<&str> = raw.winners.iter().map(|(winner, _)| winner.as_str()).collect();
+        let selected: Vec<&str> = result.selected_validators.iter().map(|v| v.account_id.as_str()).collect();
+        assert_eq!(winners, selected, "{}", algorithm);
+
+        // Ratio assignments sum to one and staked ones to the voter's stake
+        for assignment in &raw.assignments {
+            assert_eq!(assignment.distribution.iter().map(|(_, parts)| *parts as u64).sum::<u64>(), 1_000_000_000);
+        }
+        for assignment in &raw.staked_assignments {
+            let stake = data.nominators.iter().find(|n| n.account_id == assignment.who).unwrap().stake;
+            assert_eq!(assignment.distribution.iter().map(|(_, s)| *s).sum::<u128>(), stake, "{}", algorithm);
+        }
+
+        // Every support is the sum of its voters, and only winners are supported
+        for (winner, support) in &raw.supports {
+            assert!(winners.contains(&winner.as_str()));
+            assert_eq!(support.voters.iter().map(|(_, s)| *s).sum::<u128>(), support.total);
+        }
+    }
+}
+
+#[test]
+fn test_full_detail_matches_upstream_sp_npos_elections() {
+    let data = election_data();
+    let result = ElectionEngine::new()
+        .execute(&config(AlgorithmType::SequentialPhragmen, ResultDetail::Full), &data)
+        .unwrap();
+    let raw = result.raw_solution.unwrap();
+
+    let candidates: Vec<String> = data.candidates.iter().map(|c| c.account_id.clone()).collect();
+    let voters: Vec<(String, u64, Vec<String>)> = data
+        .nominators
+        .iter()
+        .map(|n| (n.account_id.clone(), n.stake as u64, n.targets.clone()))
+        .collect();
+    let upstream = sp_npos_elections::seq_phragmen::<String, Perbill>(2, candidates, voters.clone(), None).unwrap();
+    let stake_of = |who: &String| voters.iter().find(|(id, _, _)| id == who).unwrap().1;
+    let staked = sp_npos_elections::assignment_ratio_to_staked_normalized(upstream.assignments.clone(), stake_of).unwrap();
+    let supports = sp_npos_elections::to_supports(&staked);
+
+    assert_eq!(raw.winners, upstream.winners);
+    assert_eq!(
+        raw.assignments.iter().map(|a| (a.who.clone(), a.distribution.clone())).collect::<Vec<_>>(),
+        upstream
+            .assignments
+            .iter()
+            .map(|a| (a.who.clone(), a.distribution.iter().map(|(t, p)| (t.clone(), p.deconstruct())).collect()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        raw.staked_assignments.iter().map(|a| (a.who.clone(), a.distribution.clone())).collect::<Vec<_>>(),
+        staked.into_iter().map(|a| (a.who, a.distribution)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        raw.supports.iter().map(|(w, s)| (w.clone(), s.total, s.voters.clone())).collect::<Vec<_>>(),
+        supports.into_iter().map(|(w, s)| (w, s.total, s.voters)).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_raw_solution_is_reencoded_with_the_result() {
+    let mut builder = SyntheticDataBuilder::new();
+    let key = |byte: u8| format!("0x{}", hex::encode([byte; 32]));
+    builder
+        .add_candidate(key(1), 0).unwrap()
+        .add_nominator(key(2), 500, vec![key(1)]).unwrap();
+    let data = builder.build().unwrap();
+    let config = ElectionConfiguration::builder().active_set_size(1).result_detail(ResultDetail::Full).build().unwrap();
+    let mut result = ElectionEngine::new().execute(&config, &data).unwrap();
+    result.reencode_accounts(AccountFormat::Ss58(0), 42);
+
+    let raw = result.raw_solution.unwrap();
+    assert_eq!(raw.winners[0].0, result.selected_validators[0].account_id);
+    assert_eq!(raw.supports[0].1.voters[0].0, result.stake_distribution[0].nominator_id);
+    assert!(raw.staked_assignments[0].who.starts_with('1'));
+}
+
+#[test]
+fn test_result_detail_round_trips_in_configuration() {
+    let config = config(AlgorithmType::SequentialPhragmen, ResultDetail::Full);
+    let json = config.to_json().unwrap();
+    assert!(json.contains("\"result_detail\": \"full\""));
+    assert_eq!(ElectionConfiguration::from_json_str(&json).unwrap(), config);
+    assert_eq!("full".parse::<ResultDetail>().unwrap(), ResultDetail::Full);
+    assert!("verbose".parse::<ResultDetail>().is_err());
+}
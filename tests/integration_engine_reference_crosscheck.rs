@@ -0,0 +1,75 @@
+//! Engine test: differential testing against sp-npos-elections on random inputs
+#![cfg(feature = "testing")]
+
+use clap::Parser;
+use offline_election::cli::SelftestCommand;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::testing::{compare_with_reference, random_election, run_selftest, SelftestConfig};
+use offline_election::types::AlgorithmType;
+use std::collections::HashSet;
+
+#[test]
+fn test_pipeline_matches_the_reference_on_random_elections() {
+    // Seed 1 includes elections whose rounded allocations exceed the total
+    // stake by a few plancks, which the engine used to reject
+    for seed in [1, 4] {
+        let config = SelftestConfig { cases: 25, seed, ..SelftestConfig::default() };
+        let report = run_selftest(&config).unwrap();
+        assert_eq!(report.comparisons, 100);
+        assert!(report.passed(), "{:#?}", report.divergences);
+    }
+}
+
+#[test]
+fn test_random_elections_are_reproducible_and_well_formed() {
+    let config = SelftestConfig::default();
+    for seed in 0..50 {
+        let (data, to_elect) = random_election(seed, &config);
+        assert_eq!(random_election(seed, &config).0, data);
+        assert!(data.candidates.len() <= config.max_candidates);
+        assert!(data.nominators.len() <= config.max_voters);
+
+        let backed: HashSet<&String> = data.nominators.iter().flat_map(|n| &n.targets).collect();
+        assert!((1..=backed.len()).contains(&to_elect));
+        for nominator in &data.nominators {
+            let distinct: HashSet<&String> = nominator.targets.iter().collect();
+            assert_eq!(distinct.len(), nominator.targets.len());
+            assert!(nominator.targets.len() <= config.max_votes);
+            assert!(nominator.stake > 0);
+        }
+    }
+    assert_ne!(random_election(1, &config).0, random_election(2, &config).0);
+}
+
+#[test]
+fn test_disagreements_are_reported() {
+    // Only one candidate is backed: phragmms elects it alone, while the
+    // pipeline rejects a result with fewer winners than asked for
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 100, vec!["a".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+
+    let detail = compare_with_reference(&data, 2, AlgorithmType::ParallelPhragmen).unwrap().unwrap();
+    assert!(detail.starts_with("pipeline failed"), "{}", detail);
+    assert_eq!(compare_with_reference(&data, 1, AlgorithmType::ParallelPhragmen).unwrap(), None);
+    assert_eq!(compare_with_reference(&data, 1, AlgorithmType::SequentialPhragmen).unwrap(), None);
+}
+
+#[test]
+fn test_selftest_command_builds_its_configuration() {
+    let command = SelftestCommand::try_parse_from([
+        "selftest", "--cases", "5", "--seed", "42", "--algorithm", "mms", "--algorithm", "sequential-phragmen",
+    ])
+    .unwrap();
+    let config = command.config().unwrap();
+    assert_eq!((config.cases, config.seed), (5, 42));
+    assert_eq!(config.algorithms, [AlgorithmType::Mms, AlgorithmType::SequentialPhragmen]);
+
+    let command = SelftestCommand::try_parse_from(["selftest", "--algorithm", "random"]).unwrap();
+    assert!(command.config().is_err());
+    let command = SelftestCommand::try_parse_from(["selftest", "--max-candidates", "0"]).unwrap();
+    assert!(command.config().is_err());
+}
@@ -0,0 +1,110 @@
+//! Engine test: answering repeated elections from a result cache
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use offline_election::api::handlers::{put_dataset, run_election, HandlerState};
+use offline_election::api::models::{DatasetUploadQuery, ElectionRequest};
+use offline_election::cache::{CacheStats, ResultCache};
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_candidate("c".to_string(), 10).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 400, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_engine_reuses_results_for_identical_data_and_config() {
+    let rounds = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&rounds);
+    let cache = ResultCache::new(2);
+    let engine = ElectionEngine::new()
+        .with_progress_observer(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .with_result_cache(cache.clone());
+    let data = election_data();
+    let two = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+
+    let first = engine.execute(&two, &data).unwrap();
+    assert!(!first.execution_metadata.from_cache);
+    let observed = rounds.load(Ordering::SeqCst);
+    assert!(observed > 0);
+
+    // An identical run, even from another engine sharing the cache, is answered without solving
+    let again = ElectionEngine::new().with_result_cache(cache.clone()).execute(&two, &data).unwrap();
+    assert!(again.execution_metadata.from_cache);
+    assert_eq!(again.selected_validators, first.selected_validators);
+    assert_eq!(again.execution_metadata.execution_timestamp, first.execution_metadata.execution_timestamp);
+    assert_eq!(engine.execute(&two, &data).unwrap().selected_validators, first.selected_validators);
+    assert_eq!(rounds.load(Ordering::SeqCst), observed);
+    assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 1, entries: 1 });
+
+    // Different configuration, data or diagnostics each miss
+    let one = ElectionConfiguration::builder().active_set_size(1).build().unwrap();
+    assert!(!engine.execute(&one, &data).unwrap().execution_metadata.from_cache);
+    let mut changed = data.clone();
+    changed.nominators[1].stake = 401;
+    assert!(!engine.execute(&two, &changed).unwrap().execution_metadata.from_cache);
+    assert!(!engine.execute_with_diagnostics(&two, &data, true).unwrap().execution_metadata.from_cache);
+
+    // Only the two most recently used results are kept
+    let stats = cache.stats();
+    assert_eq!((stats.misses, stats.entries), (4, 2));
+    assert!(!engine.execute(&one, &data).unwrap().execution_metadata.from_cache);
+    assert!(engine.execute_with_diagnostics(&two, &data, true).unwrap().execution_metadata.from_cache);
+
+    cache.clear();
+    assert_eq!(cache.stats().entries, 0);
+    let disabled = ResultCache::new(0);
+    let engine = ElectionEngine::new().with_result_cache(disabled.clone());
+    engine.execute(&two, &data).unwrap();
+    assert!(!engine.execute(&two, &data).unwrap().execution_metadata.from_cache);
+    assert_eq!(disabled.stats().entries, 0);
+}
+
+#[tokio::test]
+async fn test_server_answers_repeated_requests_from_the_cache() {
+    let cache = ResultCache::new(8);
+    let state = HandlerState::new().with_result_cache(cache.clone());
+    let (_, Json(dataset)) = put_dataset(
+        State(state.clone()),
+        Path("era".to_string()),
+        Query(DatasetUploadQuery::default()),
+        Json(election_data()),
+    )
+    .await
+    .unwrap();
+    let request = || -> Json<ElectionRequest> {
+        Json(
+            serde_json::from_value(serde_json::json!({
+                "algorithm": "sequential-phragmen",
+                "active_set_size": 2,
+                "data_source": {"type": "dataset", "name": "era"},
+            }))
+            .unwrap(),
+        )
+    };
+
+    let Json(first) = run_election(State(state.clone()), None, request()).await.unwrap();
+    let Json(second) = run_election(State(state.clone()), None, request()).await.unwrap();
+    assert!(!first.result.execution_metadata.from_cache);
+    assert!(second.result.execution_metadata.from_cache);
+    assert_ne!(first.election_id, second.election_id);
+    assert_eq!(first.result.selected_validators, second.result.selected_validators);
+    assert_eq!(cache.stats().hits, 1);
+    assert_eq!(second.result.execution_metadata.data_hash, Some(dataset.data_hash));
+
+    assert!(serde_json::to_string(&second.result).unwrap().contains("\"from_cache\":true"));
+    assert!(!serde_json::to_string(&first.result).unwrap().contains("from_cache"));
+}
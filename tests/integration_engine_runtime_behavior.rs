@@ -0,0 +1,173 @@
+//! Engine test: elections replayed under the rules of past runtimes
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::runtime_behavior::{RuntimeBehavior, RuntimeVersion};
+
+fn election_data(voters: usize, targets: usize) -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    let candidates: Vec<String> = (0..targets).map(|i| format!("v{}", i)).collect();
+    for candidate in &candidates {
+        builder.add_candidate(candidate.clone(), 0).unwrap();
+    }
+    for i in 0..voters {
+        builder
+            .add_nominator(format!("n{}", i), 1_000 + i as u128, candidates.clone())
+            .unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_flags_in_force_depend_on_spec_version() {
+    let old = RuntimeBehavior::for_runtime(&RuntimeVersion::new("Polkadot", 9000));
+    assert_eq!(old.flags, vec!["polkadot-max-nominations-16"]);
+    assert_eq!(old.max_nominations, Some(16));
+    assert_eq!(old.balancing_iterations, None);
+    assert_eq!(old.max_electing_voters, None);
+
+    let new = RuntimeBehavior::for_runtime(&RuntimeVersion::new("kusama", 9190));
+    assert_eq!(new.max_nominations, Some(24));
+    assert_eq!(new.balancing_iterations, Some(10));
+    assert_eq!(new.max_electing_voters, Some(12_500));
+
+    assert_eq!(RuntimeBehavior::for_runtime(&RuntimeVersion::new("westend", 9999)), RuntimeBehavior::default());
+}
+
+#[test]
+fn test_runtime_rules_cut_nominations_and_balance() {
+    let data = election_data(3, 20);
+    let config = ElectionConfiguration::builder().active_set_size(20).runtime("polkadot", 9100).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    let metadata = &result.execution_metadata;
+    assert_eq!(metadata.behavior_flags, vec!["polkadot-max-nominations-16", "polkadot-miner-balancing-10"]);
+    assert!(metadata.balancing_iterations.unwrap() > 0);
+    // Only the first 16 targets are voted for
+    assert!(result.stake_distribution.iter().all(|a| a.validator_id != "v16" && a.validator_id != "v19"));
+
+    let (resolved_config, resolved_data) = ElectionEngine::new().resolve_input(&config, &data).unwrap();
+    assert!(resolved_config.runtime.is_none());
+    assert!(resolved_config.balancing.is_some());
+    assert!(resolved_data.nominators.iter().all(|n| n.targets.len() == 16));
+}
+
+#[test]
+fn test_explicit_balancing_wins_over_runtime() {
+    let data = election_data(3, 4);
+    let config = ElectionConfiguration::builder()
+        .active_set_size(2)
+        .balancing(2, 0)
+        .runtime("kusama", 9200)
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    assert!(result.execution_metadata.balancing_iterations.unwrap() <= 2);
+}
+
+#[test]
+fn test_voter_cap_keeps_highest_stakes_in_order() {
+    let mut data = election_data(5, 2);
+    let behavior = RuntimeBehavior {
+        max_electing_voters: Some(3),
+        ..RuntimeBehavior::default()
+    };
+    assert!(behavior.limits(&data));
+    behavior.apply(&mut data);
+    let kept: Vec<&str> = data.nominators.iter().map(|n| n.account_id.as_str()).collect();
+    assert_eq!(kept, vec!["n2", "n3", "n4"]);
+    assert!(!behavior.limits(&data));
+}
+
+#[test]
+fn test_runtime_round_trips_in_configuration() {
+    let config = ElectionConfiguration::builder().active_set_size(2).runtime("polkadot", 9180).build().unwrap();
+    let json = config.to_json().unwrap();
+    assert!(json.contains("\"spec_version\": 9180"));
+    assert_eq!(ElectionConfiguration::from_json_str(&json).unwrap(), config);
+}
+
+#[test]
+fn test_kusama_nominations_grew_from_16_to_24() {
+    let genesis = RuntimeBehavior::for_runtime(&RuntimeVersion::new("kusama", 0));
+    assert_eq!(genesis.flags, vec!["kusama-max-nominations-16"]);
+    assert_eq!(genesis.max_nominations, Some(16));
+    let later = RuntimeBehavior::for_runtime(&RuntimeVersion::new("kusama", 9080));
+    assert_eq!(later.max_nominations, Some(24));
+    assert!(!later.flags.contains(&"kusama-max-nominations-16".to_string()));
+}
+
+#[test]
+fn test_validator_self_votes_take_voter_slots() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder.add_candidate("v0".to_string(), 5_000).unwrap().add_candidate("v1".to_string(), 0).unwrap();
+    for i in 0..3 {
+        builder.add_nominator(format!("n{}", i), 1_000 + i as u128, vec!["v1".to_string()]).unwrap();
+    }
+    let mut data = builder.build().unwrap();
+    let behavior = RuntimeBehavior {
+        max_electing_voters: Some(3),
+        ..RuntimeBehavior::default()
+    };
+    assert!(behavior.limits(&data));
+    behavior.apply(&mut data);
+    let kept: Vec<&str> = data.nominators.iter().map(|n| n.account_id.as_str()).collect();
+    assert_eq!(kept, vec!["n1", "n2"]);
+}
+
+#[test]
+fn test_voters_in_one_bag_are_taken_in_list_order() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder.add_candidate("v0".to_string(), 0).unwrap();
+    for (account, stake) in [("n0", 900), ("n1", 1_000), ("n2", 1_000_000)] {
+        builder.add_nominator(account.to_string(), stake, vec!["v0".to_string()]).unwrap();
+    }
+    let data = builder.build().unwrap();
+
+    // With an existential weight of 1 the bag bounds grow by about 1.25,
+    // putting 900 and 1,000 in the same bag
+    let bags = RuntimeBehavior {
+        max_electing_voters: Some(2),
+        voter_bags: Some(1),
+        ..RuntimeBehavior::default()
+    };
+    let mut by_bag = data.clone();
+    bags.apply(&mut by_bag);
+    let kept: Vec<&str> = by_bag.nominators.iter().map(|n| n.account_id.as_str()).collect();
+    assert_eq!(kept, vec!["n0", "n2"]);
+
+    let by_stake = RuntimeBehavior { voter_bags: None, ..bags };
+    let mut exact = data;
+    by_stake.apply(&mut exact);
+    let kept: Vec<&str> = exact.nominators.iter().map(|n| n.account_id.as_str()).collect();
+    assert_eq!(kept, vec!["n1", "n2"]);
+}
+
+#[test]
+fn test_kusama_bag_bounds_match_the_runtime_table() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder.add_candidate("v0".to_string(), 0).unwrap();
+    // Kusama's second bag ends at 38,184,666
+    for (account, stake) in [("n0", 38_184_600), ("n1", 38_184_666), ("n2", 38_184_667)] {
+        builder.add_nominator(account.to_string(), stake, vec!["v0".to_string()]).unwrap();
+    }
+    let mut data = builder.build().unwrap();
+    let bags = RuntimeBehavior {
+        max_electing_voters: Some(2),
+        voter_bags: Some(33_333_333),
+        ..RuntimeBehavior::default()
+    };
+    bags.apply(&mut data);
+    let kept: Vec<&str> = data.nominators.iter().map(|n| n.account_id.as_str()).collect();
+    assert_eq!(kept, vec!["n0", "n2"]);
+}
+
+#[test]
+fn test_unknown_chain_is_a_configuration_issue() {
+    let error = ElectionConfiguration::builder().active_set_size(2).runtime("polkdot", 9180).build().unwrap_err();
+    assert!(error.to_string().contains("polkdot"));
+    assert!(RuntimeVersion::new(" Kusama", 0).is_recorded());
+    assert!(!RuntimeVersion::new("westend", 9999).is_recorded());
+}
@@ -0,0 +1,91 @@
+//! Score test: submitted compact solution refereed against its snapshot
+
+use offline_election::engine::ElectionEngine;
+use offline_election::models::onchain_solution::{decode_snapshot, ScoreReport, SubmittedSolution};
+use parity_scale_codec::{Compact, Encode};
+
+fn account(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+fn account_id(byte: u8) -> String {
+    format!("0x{}", hex::encode(account(byte)))
+}
+
+fn snapshot_bytes() -> Vec<u8> {
+    let (a, b, c) = (account(1), account(2), account(3));
+    let voters: Vec<([u8; 32], u64, Vec<[u8; 32]>)> = vec![
+        (account(11), 100, vec![a, b]),
+        (account(12), 60, vec![b, c]),
+        (account(13), 30, vec![a, c]),
+    ];
+    (voters, vec![a, b, c]).encode()
+}
+
+/// `RawSolution { solution, score, round }` with a `max_votes` compact solution
+///
+/// Voter 0 backs target 0, voter 1 target 2, and voter 2 splits `split`
+/// (in parts per 65535) to target 0 and the rest to target 2.
+fn raw_solution_bytes(max_votes: u32, split: u16, score: (u128, u128, u128)) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    vec![(Compact(0u32), Compact(0u16)), (Compact(1u32), Compact(2u16))].encode_to(&mut bytes);
+    Compact(1u32).encode_to(&mut bytes);
+    (Compact(2u32), Compact(0u16), Compact(split), Compact(2u16)).encode_to(&mut bytes);
+    for _ in 3..=max_votes {
+        Compact(0u32).encode_to(&mut bytes);
+    }
+    score.encode_to(&mut bytes);
+    7u32.encode_to(&mut bytes);
+    bytes
+}
+
+#[test]
+fn test_submitted_solution_decodes_into_supports() {
+    let snapshot = decode_snapshot(&snapshot_bytes(), None).unwrap();
+    let bytes = raw_solution_bytes(24, u16::MAX / 2, (75, 190, 115 * 115 + 75 * 75));
+    let submitted = SubmittedSolution::decode(&bytes, &snapshot, None).unwrap();
+
+    assert_eq!(submitted.round, 7);
+    assert_eq!(submitted.max_votes, 24);
+    assert_eq!(submitted.solution.winners(), vec![account_id(1), account_id(3)]);
+    // Voter 13's 30 is split in half; normalization keeps its total exact
+    let totals: Vec<u128> = submitted.solution.supports.iter().map(|s| s.total).collect();
+    assert_eq!(totals.iter().sum::<u128>(), 190);
+    assert!(totals[0] == 114 || totals[0] == 115, "{:?}", totals);
+
+    // The wrong vote count does not decode the whole input
+    assert!(SubmittedSolution::decode(&bytes, &snapshot, Some(16)).is_err());
+}
+
+#[test]
+fn test_score_report_compares_with_best_alternative() {
+    let snapshot = decode_snapshot(&snapshot_bytes(), None).unwrap();
+    let bytes = raw_solution_bytes(16, u16::MAX / 2, (0, 0, 0));
+    let submitted = SubmittedSolution::decode(&bytes, &snapshot, None).unwrap();
+    let verification = ElectionEngine::new().verify_solution(&submitted.solution, &snapshot, Some(2), 0);
+    let report = ScoreReport::new(&submitted, verification);
+
+    assert!(!report.verification.score_matches, "the claimed score is wrong");
+    assert!(report.verification.feasible, "{:?}", report.verification.feasibility_issues);
+    let margin = report.margin.unwrap();
+    let best = report.verification.best_alternative_score.unwrap();
+    assert_eq!(
+        margin.minimal_stake,
+        best.minimal_stake as i128 - report.verification.computed_score.minimal_stake as i128
+    );
+    assert_eq!(report.verification.better_solution_found, best.is_better_than(&report.verification.computed_score));
+}
+
+#[test]
+fn test_out_of_bounds_voter_is_rejected() {
+    let snapshot = decode_snapshot(&snapshot_bytes(), None).unwrap();
+    let mut bytes = Vec::new();
+    vec![(Compact(9u32), Compact(0u16))].encode_to(&mut bytes);
+    for _ in 2..=16 {
+        Compact(0u32).encode_to(&mut bytes);
+    }
+    (0u128, 0u128, 0u128, 1u32).encode_to(&mut bytes);
+
+    let error = SubmittedSolution::decode(&bytes, &snapshot, Some(16)).unwrap_err();
+    assert!(error.to_string().contains("voter index 9"), "{}", error);
+}
@@ -0,0 +1,45 @@
+//! Engine test: single-shot elections through the top-level functions
+
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::{
+    run_election, run_election_from_rpc, AlgorithmType, ElectionConfiguration, ElectionData, ElectionEngine,
+    ElectionError,
+};
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 600, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_run_election_matches_a_default_engine() {
+    let data = election_data();
+    for algorithm in [AlgorithmType::SequentialPhragmen, AlgorithmType::ParallelPhragmen, AlgorithmType::Mms] {
+        let config = ElectionConfiguration::builder().algorithm(algorithm).active_set_size(2).build().unwrap();
+        let result = run_election(&data, &config).unwrap();
+        let expected = ElectionEngine::new().execute(&config, &data).unwrap();
+        assert_eq!(result.validator_count(), 2);
+        assert_eq!(result.content_hash(), expected.content_hash());
+    }
+
+    // Errors surface as from the engine
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    assert!(run_election(&ElectionData::new(), &config).is_err());
+}
+
+#[tokio::test]
+async fn test_run_election_from_rpc_checks_the_configuration_before_fetching() {
+    // Nothing listens on the URL, so only a configuration error can come back
+    let mut config = ElectionConfiguration::builder().active_set_size(1).build().unwrap();
+    config.active_set_size = 0;
+    match run_election_from_rpc("http://127.0.0.1:9", Some(1), &config).await {
+        Err(ElectionError::ValidationError { field, .. }) => assert_eq!(field.as_deref(), Some("active_set_size")),
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
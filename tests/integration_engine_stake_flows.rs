@@ -0,0 +1,124 @@
+//! Engine test: projecting validator sets over eras of scheduled bonds and unbonds
+
+use clap::Parser;
+use offline_election::cli::RunCommand;
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::balance::TokenUnit;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::{StakeFlowAction, StakeFlowScenario};
+
+/// "a" and "b" are elected; "c" waits with a small backing
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("whale".to_string(), 10_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n1".to_string(), 3_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 1_000, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_flows_change_the_set_from_the_era_they_take_effect() {
+    let scenario = StakeFlowScenario::new()
+        .unbond(2, "whale", 10_000)
+        .bond(3, "newcomer", 5_000, ["a"])
+        .unbond(3, "ghost", 1);
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let projection = ElectionEngine::new()
+        .project_stake_flows(&config, &election_data(), &scenario, 4)
+        .unwrap();
+
+    let eras: Vec<u32> = projection.eras.iter().map(|era| era.era).collect();
+    assert_eq!(eras, [0, 1, 2, 3, 4]);
+    assert_eq!(projection.eras[0].nominator_stake, 14_000);
+    assert!(!projection.eras[1].set_changed());
+
+    // The whale leaves in era 2 and "c" takes the seat "a" held
+    let unbonded = &projection.eras[2];
+    assert_eq!(unbonded.flows_applied, 1);
+    assert_eq!(unbonded.nominator_stake, 4_000);
+    assert_eq!(unbonded.validators_added, ["c"]);
+    assert_eq!(unbonded.validators_removed, ["a"]);
+
+    // A new nominator brings "a" back; the unknown account is skipped
+    let bonded = &projection.eras[3];
+    assert_eq!(bonded.validators_added, ["a"]);
+    assert_eq!(bonded.validators_removed, ["c"]);
+    assert_eq!(bonded.unmatched_flows, ["Era 3 unbond for unknown account ghost"]);
+    assert_eq!(projection.set_changes().count(), 2);
+}
+
+#[test]
+fn test_scenario_files_accept_token_amounts_and_are_validated() {
+    let scenario = StakeFlowScenario::from_json_str(
+        r#"{"flows": [
+            {"era": 1, "action": "bond", "account_id": "n1", "amount": "1.5 DOT"},
+            {"era": 4, "action": "unbond", "account_id": "whale", "amount": 700}
+        ]}"#,
+        Some(&TokenUnit::polkadot()),
+    )
+    .unwrap();
+    assert_eq!(scenario.flows[0].amount, 15_000_000_000);
+    assert_eq!(scenario.flows[1].action, StakeFlowAction::Unbond);
+    assert_eq!(scenario.last_era(), 4);
+
+    let (data, unmatched) = scenario.data_at(&election_data(), 4);
+    assert!(unmatched.is_empty());
+    assert_eq!(data.nominators.iter().find(|n| n.account_id == "whale").unwrap().stake, 9_300);
+
+    let wrong_token = StakeFlowScenario::from_json_str(
+        r#"{"flows": [{"era": 1, "action": "bond", "account_id": "n1", "amount": "1 KSM"}]}"#,
+        Some(&TokenUnit::polkadot()),
+    );
+    assert!(matches!(wrong_token, Err(ElectionError::ValidationError { field: Some(f), .. }) if f == "flows[0].amount"));
+
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let now = StakeFlowScenario::new().unbond(0, "whale", 1);
+    match ElectionEngine::new().project_stake_flows(&config, &election_data(), &now, 1) {
+        Err(ElectionError::ValidationError { field, .. }) => assert_eq!(field.as_deref(), Some("flows[0].era")),
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_projection_eras_require_a_scenario_file() {
+    assert!(RunCommand::try_parse_from(["run", "--synthetic", "--projection-eras", "3"]).is_err());
+    let command =
+        RunCommand::try_parse_from(["run", "--synthetic", "--stake-flows", "flows.json", "--projection-eras", "3"])
+            .unwrap();
+    assert_eq!(command.projection_eras, Some(3));
+}
+
+#[test]
+fn test_projected_eras_match_elections_on_changed_copies() {
+    let scenario = StakeFlowScenario::new()
+        .bond(1, "n1", 500, ["c", "a"])
+        .bond(1, "b", 2_000, Vec::<&str>::new())
+        .unbond(2, "whale", 10_000)
+        .bond(3, "whale", 4_000, ["c"])
+        .bond(3, "newcomer", 2_500, ["b", "b"]);
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let data = election_data();
+    let engine = ElectionEngine::new();
+    let projection = engine.project_stake_flows(&config, &data, &scenario, 3).unwrap();
+
+    for era in &projection.eras {
+        let (changed, _) = scenario.data_at(&data, era.era);
+        let expected = engine.execute(&config, &changed).unwrap();
+        let winners: Vec<String> = expected.selected_validators.iter().map(|v| v.account_id.clone()).collect();
+        assert_eq!(era.selected_validators, winners, "era {}", era.era);
+        assert_eq!(era.score, expected.score(), "era {}", era.era);
+        assert_eq!(era.nominator_stake, changed.statistics().total_nominator_stake, "era {}", era.era);
+    }
+    let (changed, _) = scenario.data_at(&data, 3);
+    let order: Vec<&str> = changed.nominators.iter().map(|n| n.account_id.as_str()).collect();
+    assert_eq!(order, ["n1", "n2", "whale", "newcomer"]);
+    assert_eq!(changed.nominators[0].targets, ["c", "a"]);
+    assert_eq!(changed.nominators[3].targets, ["b"]);
+}
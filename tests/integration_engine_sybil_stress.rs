@@ -0,0 +1,64 @@
+//! Engine test: sybil candidates injected with an attacker's stake
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::{SybilScenario, SybilSplit};
+
+/// Three honest validators, each backed by 1,000
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for index in 0..3 {
+        builder
+            .add_candidate(format!("honest-{}", index), 0).unwrap()
+            .add_nominator(format!("voter-{}", index), 1_000, vec![format!("honest-{}", index)]).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_sybils_elected_grow_with_attacker_stake() {
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let scenario = SybilScenario::new(3).attacker_stake(3_600).attacker_stake(500);
+    let report = ElectionEngine::new().sybil_stress(&config, &election_data(), &scenario).unwrap();
+
+    assert_eq!(report.honest_stake, 3_000);
+    assert_eq!(report.points.len(), 6);
+
+    // Too little stake elects nobody, whatever the split
+    for split in [SybilSplit::Pooled, SybilSplit::Even, SybilSplit::Halving] {
+        let curve = report.curve(split);
+        assert_eq!(curve[0].attacker_stake, 500);
+        assert_eq!(curve[0].sybils_elected, 0);
+    }
+
+    // With 3,600 an even split out-polls every honest validator, while halving
+    // (1,800 / 900 / 900) only gets its first sybil in
+    let elected_at = |split| report.curve(split)[1].sybils_elected;
+    assert_eq!(elected_at(SybilSplit::Pooled), 3);
+    assert_eq!(elected_at(SybilSplit::Even), 3);
+    assert_eq!(elected_at(SybilSplit::Halving), 1);
+    assert_eq!(report.curve(SybilSplit::Halving)[1].elected_sybils, vec!["sybil-candidate-0".to_string()]);
+    assert_eq!(report.curve(SybilSplit::Even)[1].attacker_share_bps, 5_454);
+    assert_eq!(report.min_stake_for(SybilSplit::Even, 3), Some(3_600));
+    assert_eq!(report.min_stake_for(SybilSplit::Halving, 2), None);
+}
+
+#[test]
+fn test_scenario_rejects_prefix_already_in_snapshot() {
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let scenario = SybilScenario::new(2).attacker_stake(1_000).id_prefix("honest");
+    let err = ElectionEngine::new().sybil_stress(&config, &election_data(), &scenario).unwrap_err();
+    assert!(err.to_string().contains("already uses the sybil prefix"), "{}", err);
+
+    // Injection keeps every planck of the attacker's stake
+    let data = SybilScenario::new(3).inject(&election_data(), 1_001, SybilSplit::Even);
+    let attacker: Vec<u128> = data
+        .nominators
+        .iter()
+        .filter(|n| n.account_id.starts_with("sybil-nominator-"))
+        .map(|n| n.stake)
+        .collect();
+    assert_eq!(attacker, vec![334, 334, 333]);
+}
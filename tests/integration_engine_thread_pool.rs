@@ -0,0 +1,73 @@
+//! Engine test: batch runs on a dedicated solver thread pool
+
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::{SybilScenario, WhaleRemovalMode};
+use offline_election::types::AlgorithmType;
+
+/// Five validators with one nominator each, backed 1,000 to 5,000
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for index in 0..5 {
+        builder
+            .add_candidate(format!("v{}", index), 0).unwrap()
+            .add_nominator(format!("n{}", index), 1_000 * (index as u128 + 1), vec![format!("v{}", index)]).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+fn winners(result: &offline_election::models::election_result::ElectionResult) -> Vec<&str> {
+    result.selected_validators.iter().map(|v| v.account_id.as_str()).collect()
+}
+
+#[test]
+fn test_batch_matches_individual_runs_in_order() {
+    let engine = ElectionEngine::with_thread_pool(2).unwrap();
+    assert_eq!(engine.thread_count(), 2);
+    assert_eq!(engine.clone().thread_count(), 2);
+
+    let data = election_data();
+    let configs: Vec<ElectionConfiguration> = [1, 3, 5]
+        .into_iter()
+        .flat_map(|size| {
+            [AlgorithmType::SequentialPhragmen, AlgorithmType::Mms]
+                .map(|algorithm| {
+                    ElectionConfiguration::builder().active_set_size(size).algorithm(algorithm).build().unwrap()
+                })
+        })
+        .collect();
+
+    let batch = engine.execute_batch(&configs, &data).unwrap();
+    assert_eq!(batch.len(), configs.len());
+    for (config, result) in configs.iter().zip(&batch) {
+        let single = ElectionEngine::new().execute(config, &data).unwrap();
+        assert_eq!(winners(result), winners(&single));
+        assert_eq!(result.algorithm_used(), config.algorithm);
+        assert_eq!(result.execution_metadata.data_hash, single.execution_metadata.data_hash);
+    }
+    assert_eq!(winners(&batch[0]), vec!["v4"]);
+
+    let err = ElectionEngine::with_thread_pool(0).unwrap_err();
+    assert!(matches!(err, ElectionError::ValidationError { ref field, .. } if field.as_deref() == Some("num_threads")));
+}
+
+#[test]
+fn test_pooled_stress_tests_match_the_global_pool() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder().active_set_size(3).build().unwrap();
+    let pooled = ElectionEngine::with_thread_pool(1).unwrap();
+    let global = ElectionEngine::new();
+
+    let whales = pooled.whale_impact(&config, &data, 4, WhaleRemovalMode::Cumulative).unwrap();
+    assert_eq!(whales, global.whale_impact(&config, &data, 4, WhaleRemovalMode::Cumulative).unwrap());
+    assert_eq!(whales.steps.len(), 4);
+    assert_eq!(whales.steps[0].removed_nominators, vec!["n4"]);
+
+    let scenario = SybilScenario::new(2).attacker_stake(500).attacker_stake(20_000);
+    let report = pooled.sybil_stress(&config, &data, &scenario).unwrap();
+    assert_eq!(report, global.sybil_stress(&config, &data, &scenario).unwrap());
+    assert_eq!(report.points.len(), scenario.splits.len() * 2);
+}
@@ -0,0 +1,81 @@
+//! On-chain verification test: queued solution checked against its snapshot
+
+use offline_election::engine::ElectionEngine;
+use offline_election::models::onchain_solution::{decode_snapshot, OnchainSolution};
+use parity_scale_codec::Encode;
+
+fn account(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+fn account_id(byte: u8) -> String {
+    format!("0x{}", hex::encode(account(byte)))
+}
+
+/// Encoded `(winner, Support { total, voters })`
+type RawSupport = ([u8; 32], (u128, Vec<([u8; 32], u128)>));
+
+fn snapshot_bytes() -> Vec<u8> {
+    let (a, b, c) = (account(1), account(2), account(3));
+    let voters: Vec<([u8; 32], u64, Vec<[u8; 32]>)> = vec![
+        (account(11), 100, vec![a, b]),
+        (account(12), 60, vec![b, c]),
+        (account(13), 30, vec![a, c]),
+    ];
+    (voters, vec![a, b, c]).encode()
+}
+
+fn solution_bytes(supports: Vec<RawSupport>, score: (u128, u128, u128)) -> Vec<u8> {
+    // ReadySolution { supports, score, compute: Signed }
+    (supports, score, 1u8).encode()
+}
+
+#[test]
+fn test_verify_solution_finds_better_alternative() {
+    let snapshot = decode_snapshot(&snapshot_bytes(), Some(100)).unwrap();
+    let solution = OnchainSolution::decode(&solution_bytes(
+        vec![
+            (account(1), (130, vec![(account(11), 100), (account(13), 30)])),
+            (account(3), (60, vec![(account(12), 60)])),
+        ],
+        (60, 190, 130 * 130 + 60 * 60),
+    ))
+    .unwrap();
+
+    assert_eq!(solution.compute, "signed");
+    assert_eq!(solution.winners(), vec![account_id(1), account_id(3)]);
+
+    let verification = ElectionEngine::new().verify_solution(&solution, &snapshot, Some(2), 100);
+
+    assert!(verification.score_matches);
+    assert!(verification.feasible, "{:?}", verification.feasibility_issues);
+    assert!(verification.better_solution_found);
+    assert!(verification.best_alternative_score.unwrap().minimal_stake > 60);
+}
+
+#[test]
+fn test_verify_solution_reports_infeasible_backing() {
+    let snapshot = decode_snapshot(&snapshot_bytes(), Some(100)).unwrap();
+    let solution = OnchainSolution::decode(&solution_bytes(
+        vec![
+            // Voter 12 did not vote for validator 1 and voter 13 over-assigns
+            (account(1), (100, vec![(account(12), 60), (account(13), 40)])),
+            (account(2), (100, vec![(account(11), 100)])),
+        ],
+        (100, 200, 20_000),
+    ))
+    .unwrap();
+
+    let verification = ElectionEngine::new().verify_solution(&solution, &snapshot, Some(2), 100);
+
+    assert!(verification.score_matches);
+    assert!(!verification.feasible);
+    assert!(verification
+        .feasibility_issues
+        .iter()
+        .any(|issue| issue.contains("did not vote for winner")));
+    assert!(verification
+        .feasibility_issues
+        .iter()
+        .any(|issue| issue.contains("only has 30 stake")));
+}
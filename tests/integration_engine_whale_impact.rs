@@ -0,0 +1,60 @@
+//! Engine test: re-running elections without the largest nominators
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::WhaleRemovalMode;
+
+/// "a" is elected only thanks to the whale; "c" takes its seat without it
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("whale".to_string(), 10_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n1".to_string(), 3_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 1_000, vec!["b".to_string(), "c".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 500, vec!["b".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_individual_removal_finds_the_validator_a_whale_carries() {
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let impact = ElectionEngine::new()
+        .whale_impact(&config, &election_data(), 2, WhaleRemovalMode::Individual)
+        .unwrap();
+
+    assert_eq!(impact.total_nominator_stake, 14_500);
+    assert_eq!(impact.steps.len(), 2);
+
+    let whale = &impact.steps[0];
+    assert_eq!(whale.removed_nominators, vec!["whale".to_string()]);
+    assert_eq!(whale.removed_stake_bps, 6_896);
+    assert_eq!(whale.validators_added, vec!["c".to_string()]);
+    assert_eq!(whale.validators_removed, vec!["a".to_string()]);
+    assert!(whale.score_delta.sum_stake < 0);
+
+    // Without n1, "b" still out-polls "c"
+    assert_eq!(impact.steps[1].removed_nominators, vec!["n1".to_string()]);
+    assert!(!impact.steps[1].set_changed());
+    assert_eq!(impact.most_disruptive(), Some(whale));
+}
+
+#[test]
+fn test_cumulative_removal_grows_the_removed_set() {
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let impact = ElectionEngine::new()
+        .whale_impact(&config, &election_data(), 3, WhaleRemovalMode::Cumulative)
+        .unwrap();
+
+    let removed: Vec<usize> = impact.steps.iter().map(|s| s.removed_nominators.len()).collect();
+    assert_eq!(removed, vec![1, 2, 3]);
+    assert_eq!(impact.steps[1].removed_stake, 13_000);
+    assert!(impact.steps[..2].iter().all(|s| s.validators_removed == vec!["a".to_string()]));
+
+    let json = serde_json::to_value(&impact).unwrap();
+    assert_eq!(json["mode"], "cumulative");
+}
@@ -0,0 +1,43 @@
+//! Error test: underlying sources are kept and context is added without losing them
+
+use offline_election::cli::error_output::{format_error, ErrorFormat};
+use offline_election::error::{ErrorContext, ErrorSource};
+use offline_election::input::json::JsonLoader;
+use offline_election::input::rpc::{RetryPolicy, RpcLoader};
+use offline_election::ElectionError;
+use std::error::Error;
+use std::path::PathBuf;
+
+#[test]
+fn test_file_errors_keep_the_io_error_and_context_keeps_the_variant() {
+    let error = JsonLoader::new().load_from_file(PathBuf::from("/nonexistent/election.json")).unwrap_err();
+    let io_error = error.source().and_then(|source| source.downcast_ref::<ErrorSource>()).unwrap();
+    let io_error = io_error.get_ref().downcast_ref::<std::io::Error>().unwrap();
+    assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound);
+
+    let result: Result<(), ElectionError> = Err(error);
+    let error = result.context("Loading baseline snapshot").unwrap_err();
+    assert_eq!(error.code(), "FILE_ERROR");
+    assert!(error.to_string().starts_with("File error: Loading baseline snapshot: Failed to read file"));
+    assert!(error.source().is_some());
+
+    let serialized = serde_json::to_value(&error).unwrap();
+    assert!(serialized["source"].as_str().unwrap().contains("No such file or directory"));
+
+    let unchanged = ElectionError::InsufficientCandidates { requested: 3, available: 1 }.context("ignored");
+    assert_eq!(unchanged.to_string(), "Insufficient candidates: requested 3, available 1");
+}
+
+#[tokio::test]
+async fn test_rpc_errors_report_the_root_cause() {
+    let loader = RpcLoader::new("http://127.0.0.1:1").unwrap().with_retry_policy(RetryPolicy::none());
+    let error = loader.get_latest_block_number().await.unwrap_err();
+    assert_eq!(error.code(), "RPC_ERROR");
+    assert!(error.source().is_some());
+
+    let chain = error.chain();
+    assert!(chain.iter().any(|message| message.to_lowercase().contains("connection refused")), "{:?}", chain);
+    let printed = format_error(&error, ErrorFormat::Human);
+    assert!(printed.starts_with("Error: RPC error: "));
+    assert_eq!(printed.lines().filter(|line| line.starts_with("  Caused by: ")).count(), chain.len() - 1);
+}
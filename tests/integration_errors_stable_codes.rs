@@ -0,0 +1,79 @@
+//! Error test: stable error codes and serialized representation
+
+use offline_election::api::models::ErrorResponse;
+use offline_election::{ElectionError, ErrorCategory};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+fn all_variants() -> Vec<ElectionError> {
+    vec![
+        ElectionError::ValidationError { message: "m".to_string(), field: None },
+        ElectionError::RpcError { message: "m".to_string(), url: "u".to_string(), source: None },
+        ElectionError::AlgorithmError {
+            message: "m".to_string(),
+            algorithm: offline_election::AlgorithmType::SequentialPhragmen,
+        },
+        ElectionError::InsufficientCandidates { requested: 2, available: 1 },
+        ElectionError::InvalidData { message: "m".to_string(), source: None },
+        ElectionError::FileError { message: "m".to_string(), path: PathBuf::from("p"), source: None },
+        ElectionError::Timeout { message: "m".to_string(), timeout_ms: 200 },
+        ElectionError::Cancelled { message: "m".to_string(), phase: None, rounds_completed: None },
+        ElectionError::SchemaMismatch { message: "m".to_string(), expected: "1".to_string(), found: "2".to_string() },
+    ]
+}
+
+#[test]
+fn test_every_variant_has_unique_code_and_serializes_with_type() {
+    let codes: Vec<&str> = all_variants().iter().map(|e| e.code()).collect();
+    assert_eq!(
+        codes,
+        vec![
+            "VALIDATION_ERROR",
+            "RPC_ERROR",
+            "ALGORITHM_ERROR",
+            "INSUFFICIENT_CANDIDATES",
+            "INVALID_DATA",
+            "FILE_ERROR",
+            "TIMEOUT",
+            "CANCELLED",
+            "SCHEMA_MISMATCH",
+        ]
+    );
+    assert_eq!(codes.iter().collect::<HashSet<_>>().len(), codes.len());
+
+    let value = serde_json::to_value(ElectionError::Timeout { message: "loading".to_string(), timeout_ms: 200 }).unwrap();
+    assert_eq!(value, serde_json::json!({ "type": "Timeout", "message": "loading", "timeout_ms": 200 }));
+    let value = serde_json::to_value(ElectionError::SchemaMismatch {
+        message: "bundle".to_string(),
+        expected: "1".to_string(),
+        found: "2".to_string(),
+    })
+    .unwrap();
+    assert_eq!(value["type"], "SchemaMismatch");
+    assert_eq!(value["found"], "2");
+
+    assert_eq!(ElectionError::Timeout { message: "m".to_string(), timeout_ms: 1 }.category(), ErrorCategory::Rpc);
+    assert_eq!(ElectionError::Cancelled { message: "m".to_string(), phase: None, rounds_completed: None }.exit_code(), 130);
+}
+
+#[test]
+fn test_api_error_response_uses_stable_code_and_fields() {
+    let error = ElectionError::ValidationError {
+        message: "active set size must be positive".to_string(),
+        field: Some("active_set_size".to_string()),
+    };
+    let response = ErrorResponse::from_election_error(&error);
+    assert_eq!(response.error, "VALIDATION_ERROR");
+    assert_eq!(response.message, "active set size must be positive");
+    assert_eq!(response.field.as_deref(), Some("active_set_size"));
+
+    let response = ErrorResponse::from_election_error(&ElectionError::FileError {
+        message: "missing".to_string(),
+        path: PathBuf::from("/tmp/data.json"),
+        source: None,
+    });
+    assert_eq!(response.error, "FILE_ERROR");
+    let details = response.details.unwrap();
+    assert_eq!(details["type"], "FileError");
+    assert_eq!(details["path"], "/tmp/data.json");
+}
@@ -0,0 +1,132 @@
+//! Input test: staking-miner snapshot dumps as election data
+
+use clap::Parser;
+use offline_election::cli::RunCommand;
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::{StakingMinerLoader, SyntheticDataBuilder};
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::validation::ValidationProfile;
+use parity_scale_codec::Encode;
+
+fn account(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+fn hex_account(byte: u8) -> String {
+    format!("0x{}", hex::encode(account(byte)))
+}
+
+/// SCALE-encoded `RoundSnapshot`: two targets backed by their self-votes and one nominator
+fn scale_snapshot() -> Vec<u8> {
+    let voters: Vec<([u8; 32], u64, Vec<[u8; 32]>)> = vec![
+        (account(1), 500, vec![account(1)]),
+        (account(2), 300, vec![account(2)]),
+        (account(9), 1_000, vec![account(1), account(2)]),
+    ];
+    let targets = vec![account(1), account(2)];
+    (voters, targets).encode()
+}
+
+#[test]
+fn test_json_dumps_become_election_data() {
+    let dump = serde_json::json!({
+        "voters": [
+            ["15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", 500, ["15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"]],
+            ["14ShUZUYUR35RBZW6uVVt1zXDxmSQddkeDdXf1JkMA6P721N", 1000, ["15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"]]
+        ],
+        "targets": ["15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"],
+        "desired_targets": 1,
+        "block_number": 20_000_000
+    });
+    let snapshot = StakingMinerLoader::new().load_from_bytes(dump.to_string().as_bytes()).unwrap();
+    assert_eq!(snapshot.desired_targets, Some(1));
+    assert_eq!(snapshot.data.metadata.as_ref().unwrap().block_number, Some(20_000_000));
+    assert_eq!(snapshot.data.candidates.len(), 1);
+    assert_eq!(snapshot.data.candidates[0].stake, 0);
+    assert_eq!(snapshot.data.nominators[1].stake, 1_000);
+    assert_eq!(snapshot.data.nominators[1].targets, ["15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"]);
+
+    assert!(matches!(
+        StakingMinerLoader::new().load_from_bytes(br#"{"voters": [], "candidates": []}"#),
+        Err(ElectionError::InvalidData { .. })
+    ));
+    let unknown_target = br#"{"voters": [["a", 10, ["missing"]]], "targets": ["b"]}"#;
+    assert!(StakingMinerLoader::new().load_from_bytes(unknown_target).is_ok());
+    let strict = StakingMinerLoader::new().with_validation_profile(ValidationProfile::Strict);
+    assert!(strict.load_from_bytes(unknown_target).is_err());
+}
+
+#[test]
+fn test_scale_dumps_load_as_raw_hex_or_rpc_response() {
+    let scale = scale_snapshot();
+    let hex_dump = format!("0x{}\n", hex::encode(&scale));
+    let response = serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": hex_dump.trim()}).to_string();
+
+    let loader = StakingMinerLoader::new();
+    let raw = loader.load_from_bytes(&scale).unwrap().data;
+    assert_eq!(loader.load_from_bytes(hex_dump.as_bytes()).unwrap().data, raw);
+    assert_eq!(loader.load_from_bytes(response.as_bytes()).unwrap().data, raw);
+    assert_eq!(raw.candidates.iter().map(|c| c.account_id.clone()).collect::<Vec<_>>(), [hex_account(1), hex_account(2)]);
+    assert_eq!(raw.nominators[2].targets, [hex_account(1), hex_account(2)]);
+
+    assert!(loader.load_from_bytes(b"0xnothex").is_err());
+    assert!(loader.load_from_bytes(&scale[..scale.len() - 5]).is_err());
+}
+
+#[test]
+fn test_imported_snapshot_elects_like_the_same_data_built_by_hand() {
+    let imported = StakingMinerLoader::new().load_from_bytes(&scale_snapshot()).unwrap().data;
+
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate(hex_account(1), 0).unwrap()
+        .add_candidate(hex_account(2), 0).unwrap()
+        .add_nominator(hex_account(1), 500, vec![hex_account(1)]).unwrap()
+        .add_nominator(hex_account(2), 300, vec![hex_account(2)]).unwrap()
+        .add_nominator(hex_account(9), 1_000, vec![hex_account(1), hex_account(2)]).unwrap();
+    let built = builder.build().unwrap();
+
+    let config = ElectionConfiguration::builder().active_set_size(1).build().unwrap();
+    let engine = ElectionEngine::new();
+    let from_dump = engine.execute(&config, &imported).unwrap();
+    let from_builder = engine.execute(&config, &built).unwrap();
+    assert_eq!(from_dump.selected_validators, from_builder.selected_validators);
+    assert_eq!(from_dump.total_stake, from_builder.total_stake);
+}
+
+#[tokio::test]
+async fn test_run_reads_staking_miner_dumps() {
+    let path = std::env::temp_dir().join(format!("staking-miner-dump-{}.scale", std::process::id()));
+    std::fs::write(&path, scale_snapshot()).unwrap();
+    let output = std::env::temp_dir().join(format!("staking-miner-result-{}.json", std::process::id()));
+
+    let command = RunCommand::try_parse_from([
+        "run",
+        "--algorithm",
+        "sequential-phragmen",
+        "--active-set-size",
+        "2",
+        "--input-file",
+        path.to_str().unwrap(),
+        "--input-format",
+        "staking-miner",
+        "--output-file",
+        output.to_str().unwrap(),
+    ])
+    .unwrap();
+    command.execute().await.unwrap();
+    let result: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+    assert_eq!(result["selected_validators"].as_array().unwrap().len(), 2);
+
+    // Without the flag the file is read as `ElectionData` JSON
+    let command = RunCommand::try_parse_from(["run", "--algorithm", "mms", "--active-set-size", "2", "--input-file", "x.json"]);
+    assert_eq!(command.unwrap().input_format, None);
+    let command = RunCommand::try_parse_from([
+        "run", "--algorithm", "mms", "--active-set-size", "2", "--input-file", "x.json", "--input-format", "csv",
+    ]);
+    assert!(command.is_err());
+
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&output).unwrap();
+}
@@ -0,0 +1,54 @@
+//! Models test: re-encoding account IDs for output
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::account_format::{public_key, ss58_prefix, AccountFormat};
+use offline_election::models::election_config::ElectionConfiguration;
+
+const ZERO_ACCOUNT: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+const ZERO_POLKADOT: &str = "111111111111111111111111111111111HC1";
+const ZERO_SUBSTRATE: &str = "5C4hrfjw9DjXZTzV3MwzrrAr9P1MJhSrvWGWqi1eSuyUpnhM";
+
+#[test]
+fn test_formats_parse_and_encode() {
+    assert_eq!("network".parse::<AccountFormat>().unwrap(), AccountFormat::Network);
+    assert_eq!("SS58:2".parse::<AccountFormat>().unwrap(), AccountFormat::Ss58(2));
+    assert_eq!("public-key".parse::<AccountFormat>().unwrap(), AccountFormat::PublicKey);
+    assert!("ss58:20000".parse::<AccountFormat>().is_err());
+    assert!("base64".parse::<AccountFormat>().is_err());
+    assert_eq!(AccountFormat::Ss58(2).to_string(), "ss58:2");
+
+    // Any encoding of a key re-encodes to any other
+    assert_eq!(AccountFormat::Network.encode(ZERO_ACCOUNT, 0), ZERO_POLKADOT);
+    assert_eq!(AccountFormat::Ss58(42).encode(ZERO_POLKADOT, 0), ZERO_SUBSTRATE);
+    assert_eq!(AccountFormat::PublicKey.encode(ZERO_SUBSTRATE, 0), ZERO_ACCOUNT);
+    assert_eq!(AccountFormat::AsLoaded.encode(ZERO_ACCOUNT, 0), ZERO_ACCOUNT);
+    // Names that are not keys pass through
+    assert_eq!(AccountFormat::Network.encode("alice", 0), "alice");
+
+    assert_eq!(public_key(ZERO_POLKADOT), Some([0; 32]));
+    assert_eq!(ss58_prefix(ZERO_SUBSTRATE), Some(42));
+    assert_eq!(ss58_prefix(ZERO_ACCOUNT), None);
+}
+
+#[test]
+fn test_result_accounts_are_reencoded_and_mixed_prefixes_detected() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate(ZERO_ACCOUNT.to_string(), 10).unwrap()
+        .add_nominator(ZERO_POLKADOT.to_string(), 100, vec![ZERO_ACCOUNT.to_string()]).unwrap()
+        .add_nominator(ZERO_SUBSTRATE.to_string(), 50, vec![ZERO_ACCOUNT.to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    assert_eq!(data.address_prefixes().into_iter().collect::<Vec<_>>(), vec![(0, 1), (42, 1)]);
+
+    let mut result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(1).build().unwrap(), &data)
+        .unwrap();
+    result.reencode_accounts(AccountFormat::Ss58(2), 0);
+    let kusama_zero = AccountFormat::Ss58(2).encode(ZERO_ACCOUNT, 0);
+    assert_eq!(result.selected_validators[0].account_id, kusama_zero);
+    assert!(result
+        .stake_distribution
+        .iter()
+        .all(|a| a.validator_id == kusama_zero && a.nominator_id == kusama_zero));
+}
@@ -0,0 +1,76 @@
+//! Models test: candidate and nominator lookups by account ID
+
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::models::nominator::Nominator;
+use offline_election::models::validator::ValidatorCandidate;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 400, vec!["b".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_lookups_and_duplicate_checks_use_the_index() {
+    let mut data = election_data();
+    assert_eq!(data.get_candidate("b").unwrap().stake, 50);
+    assert_eq!(data.get_nominator("n1").unwrap().stake, 1_000);
+    assert!(data.get_candidate("n1").is_none());
+    assert!(data.get_nominator("missing").is_none());
+
+    // Accounts added after the index was built are found, and duplicates rejected
+    data.add_candidate(ValidatorCandidate::new("c".to_string(), 10)).unwrap();
+    data.add_nominator(Nominator::new("n3".to_string(), 5)).unwrap();
+    assert_eq!(data.get_candidate("c").unwrap().stake, 10);
+    assert_eq!(data.get_nominator("n3").unwrap().stake, 5);
+    assert!(data.add_candidate(ValidatorCandidate::new("c".to_string(), 1)).is_err());
+    assert!(data.add_nominator(Nominator::new("n1".to_string(), 1)).is_err());
+    assert_eq!(data.candidates.len(), 3);
+    assert_eq!(data.nominators.len(), 3);
+}
+
+#[test]
+fn test_direct_writes_to_the_fields_are_noticed() {
+    let mut data = election_data();
+    assert!(data.get_candidate("a").is_some());
+
+    // Pushing or removing through the public fields changes the counts
+    data.candidates.push(ValidatorCandidate::new("pushed".to_string(), 7));
+    assert_eq!(data.get_candidate("pushed").unwrap().stake, 7);
+    data.candidates.retain(|c| c.account_id != "a");
+    assert!(data.get_candidate("a").is_none());
+    assert_eq!(data.get_candidate("b").unwrap().stake, 50);
+
+    // A rename keeps the counts, so the caches must be dropped explicitly
+    data.nominators[0].account_id = "renamed".to_string();
+    assert!(data.get_nominator("n1").is_none());
+    data.invalidate_caches();
+    assert_eq!(data.get_nominator("renamed").unwrap().stake, 1_000);
+
+    // Mutable access drops the index
+    data.candidates_mut()[0].account_id = "b2".to_string();
+    assert!(data.get_candidate("b2").is_some());
+    assert!(data.get_candidate("b").is_none());
+}
+
+#[test]
+fn test_overrides_apply_through_indexed_lookups() {
+    let mut data = election_data();
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_stake("b".to_string(), 75).unwrap();
+    overrides.set_nominator_stake("n2".to_string(), 300).unwrap();
+    overrides.set_nominator_stake("ghost".to_string(), 1).unwrap();
+    overrides.add_voting_edge("n1".to_string(), "b".to_string()).unwrap();
+
+    let unmatched = overrides.apply(&mut data).unmatched;
+    assert_eq!(unmatched, ["Nominator stake override for unknown nominator ghost"]);
+    assert_eq!(data.get_candidate("b").unwrap().stake, 75);
+    assert_eq!(data.get_nominator("n2").unwrap().stake, 300);
+    assert_eq!(data.get_nominator("n1").unwrap().targets, ["a", "b"]);
+}
@@ -0,0 +1,88 @@
+//! Model test: nominator stake bucket by validator allocation matrix
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::allocation_matrix::{AllocationMatrix, StakeBucketing};
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+
+/// Single-target nominators, so every allocation is the nominator's whole stake
+fn election() -> (ElectionData, ElectionResult) {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 100, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 200, vec!["a".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 300, vec!["b".to_string()]).unwrap()
+        .add_nominator("n4".to_string(), 400, vec!["c".to_string()]).unwrap()
+        .add_nominator("n5".to_string(), 400, vec!["b".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(3).build().unwrap(), &data)
+        .unwrap();
+    (data, result)
+}
+
+/// Cells of one row by validator
+fn row(matrix: &AllocationMatrix, row: usize) -> Vec<(&str, u128)> {
+    let mut cells: Vec<(&str, u128)> = matrix
+        .validators
+        .iter()
+        .map(String::as_str)
+        .zip(matrix.cells[row].iter().copied())
+        .collect();
+    cells.sort();
+    cells
+}
+
+#[test]
+fn test_quantile_buckets_split_nominators_evenly() {
+    let (data, result) = election();
+    let matrix = AllocationMatrix::from_result(&result, &data, &StakeBucketing::Quantiles(2), None).unwrap();
+
+    assert_eq!(matrix.validators, vec!["b", "c", "a"]);
+    assert_eq!(matrix.buckets.len(), 2);
+    assert_eq!((matrix.buckets[0].lower, matrix.buckets[0].upper), (100, Some(300)));
+    assert_eq!((matrix.buckets[1].lower, matrix.buckets[1].upper), (300, None));
+    assert_eq!(matrix.buckets[0].nominator_count, 2);
+    assert_eq!(matrix.buckets[1].allocated, 1_100);
+    assert_eq!(row(&matrix, 0), vec![("a", 300), ("b", 0), ("c", 0)]);
+    assert_eq!(row(&matrix, 1), vec![("a", 0), ("b", 700), ("c", 400)]);
+    assert!(matrix.other_validators.is_none());
+}
+
+#[test]
+fn test_equal_stakes_share_a_bucket() {
+    let (data, result) = election();
+    let matrix = AllocationMatrix::from_result(&result, &data, &StakeBucketing::Quantiles(5), None).unwrap();
+    let lowers: Vec<u128> = matrix.buckets.iter().map(|b| b.lower).collect();
+    assert_eq!(lowers, vec![100, 200, 300, 400]);
+    assert_eq!(matrix.buckets[3].nominator_count, 2);
+}
+
+#[test]
+fn test_bounds_and_limited_columns() {
+    let (data, result) = election();
+    let matrix = AllocationMatrix::from_result(&result, &data, &StakeBucketing::Bounds(vec![250]), Some(1)).unwrap();
+
+    assert_eq!(matrix.validators, vec!["b"]);
+    assert_eq!(matrix.other_validators, Some(vec![300, 400]));
+    assert_eq!(
+        matrix.to_csv().lines().collect::<Vec<_>>(),
+        vec!["Lower,Upper,Nominators,b,Other", "0,250,2,0,300", "250,,3,700,400"]
+    );
+
+    assert!(AllocationMatrix::from_result(&result, &data, &StakeBucketing::Bounds(vec![5, 5]), None).is_err());
+    assert!(AllocationMatrix::from_result(&result, &data, &StakeBucketing::Quantiles(0), None).is_err());
+}
+
+#[test]
+fn test_csv_header_quotes_validator_names() {
+    let (data, result) = election();
+    let mut matrix = AllocationMatrix::from_result(&result, &data, &StakeBucketing::Bounds(vec![250]), Some(1)).unwrap();
+    matrix.validators[0] = "b,\"one\"".to_string();
+    assert_eq!(matrix.to_csv().lines().next(), Some("Lower,Upper,Nominators,\"b,\"\"one\"\"\",Other"));
+}
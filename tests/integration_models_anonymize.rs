@@ -0,0 +1,97 @@
+//! Model test: pseudonymizing snapshots for public sharing
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::anonymize::Anonymizer;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::{ElectionData, ElectionMetadata};
+use offline_election::models::validator::CandidateMetadata;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("alice".to_string(), 5_000).unwrap()
+        .add_candidate("bob".to_string(), 1_000).unwrap()
+        .add_candidate("carol".to_string(), 2_000).unwrap()
+        .add_nominator("dave".to_string(), 10_000, vec!["alice".to_string()]).unwrap()
+        .add_nominator("eve".to_string(), 3_000, vec!["carol".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_pseudonyms_are_deterministic_and_depend_on_the_key() {
+    let anonymizer = Anonymizer::new(b"research").unwrap();
+    let pseudonym = anonymizer.pseudonym("alice");
+    assert_eq!(pseudonym, Anonymizer::new(b"research").unwrap().pseudonym("alice"));
+    assert_eq!(pseudonym.len(), 2 + 64);
+    assert!(pseudonym.starts_with("0x"));
+    assert_ne!(pseudonym, anonymizer.pseudonym("bob"));
+    assert_ne!(pseudonym, Anonymizer::new(b"other").unwrap().pseudonym("alice"));
+    assert!(Anonymizer::new(b"").is_err());
+}
+
+#[test]
+fn test_anonymized_snapshot_keeps_stakes_votes_and_election_outcome() {
+    let data = election_data();
+    let anonymizer = Anonymizer::new(b"research").unwrap();
+    let anonymized = anonymizer.anonymize(&data);
+    anonymized.validate().unwrap();
+
+    let dave = &anonymized.nominators[0];
+    assert_eq!(dave.account_id, anonymizer.pseudonym("dave"));
+    assert_eq!(dave.stake, 10_000);
+    assert_eq!(dave.targets, vec![anonymizer.pseudonym("alice")]);
+    let original_ids = ["alice", "bob", "carol", "dave", "eve"];
+    let json = serde_json::to_string(&anonymized).unwrap();
+    assert!(original_ids.iter().all(|id| !json.contains(&format!("\"{}\"", id))));
+
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let engine = ElectionEngine::new();
+    let original = engine.execute(&config, &data).unwrap();
+    let result = engine.execute(&config, &anonymized).unwrap();
+    let expected: Vec<String> = original
+        .selected_validators
+        .iter()
+        .map(|v| anonymizer.pseudonym(&v.account_id))
+        .collect();
+    let winners: Vec<String> = result.selected_validators.iter().map(|v| v.account_id.clone()).collect();
+    assert_eq!(winners, expected);
+    assert_eq!(result.total_stake, original.total_stake);
+
+    let mut with_invulnerables = data.clone();
+    with_invulnerables.invulnerables = vec!["bob".to_string()];
+    assert_eq!(anonymizer.anonymize(&with_invulnerables).invulnerables, vec![anonymizer.pseudonym("bob")]);
+}
+
+#[test]
+fn test_every_spelling_of_an_account_is_pseudonymized_alike() {
+    const ZERO_ACCOUNT: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+    const ZERO_POLKADOT: &str = "111111111111111111111111111111111HC1";
+    const ZERO_SUBSTRATE: &str = "5C4hrfjw9DjXZTzV3MwzrrAr9P1MJhSrvWGWqi1eSuyUpnhM";
+    let anonymizer = Anonymizer::new(b"research").unwrap();
+    assert_eq!(anonymizer.pseudonym(ZERO_ACCOUNT), anonymizer.pseudonym(ZERO_POLKADOT));
+    assert_eq!(anonymizer.pseudonym(ZERO_ACCOUNT), anonymizer.pseudonym(ZERO_SUBSTRATE));
+
+    let mut data = election_data();
+    data.candidates[0].metadata = Some(CandidateMetadata {
+        controller: Some(ZERO_SUBSTRATE.to_string()),
+        ..Default::default()
+    });
+    data.metadata = Some(ElectionMetadata {
+        block_number: Some(1),
+        chain: None,
+        rpc_retries: None,
+        era: None,
+        load_failures: vec![format!("2 Staking::Ledger queries failed ({}, {})", ZERO_POLKADOT, "alice")],
+        stake_source: None,
+    });
+    let anonymized = anonymizer.anonymize(&data);
+
+    let controller = anonymized.candidates[0].metadata.as_ref().unwrap().controller.clone();
+    assert_eq!(controller, Some(anonymizer.pseudonym(ZERO_ACCOUNT)));
+    let failure = &anonymized.metadata.unwrap().load_failures[0];
+    assert_eq!(
+        failure,
+        &format!("2 Staking::Ledger queries failed ({}, alice)", anonymizer.pseudonym(ZERO_ACCOUNT))
+    );
+}
@@ -0,0 +1,55 @@
+//! Models test: per-validator backing breakdown
+
+use offline_election::models::election_result::{ElectionResult, SelectedValidator, StakeAllocation};
+use offline_election::types::AlgorithmType;
+
+fn allocation(nominator_id: &str, validator_id: &str, amount: u128) -> StakeAllocation {
+    StakeAllocation {
+        nominator_id: nominator_id.to_string(),
+        validator_id: validator_id.to_string(),
+        amount,
+        proportion: 1.0,
+        unrewarded: false,
+    }
+}
+
+fn result() -> ElectionResult {
+    let selected_validators = vec![SelectedValidator {
+        account_id: "validator".to_string(),
+        total_backing_stake: 1_000,
+        nominator_count: 3,
+        rank: Some(1),
+    }];
+    let stake_distribution = vec![
+        allocation("small", "validator", 100),
+        allocation("validator", "validator", 300),
+        allocation("large", "validator", 600),
+        allocation("large", "other", 400),
+    ];
+    ElectionResult::new(selected_validators, stake_distribution, 1_400, AlgorithmType::SequentialPhragmen)
+}
+
+#[test]
+fn test_backing_of_sorts_contributions_and_splits_self_stake() {
+    let breakdown = result().backing_of("validator");
+
+    assert_eq!(breakdown.total_backing, 1_000);
+    assert_eq!(breakdown.backer_count, 3);
+    let order: Vec<&str> = breakdown.contributions.iter().map(|c| c.nominator_id.as_str()).collect();
+    assert_eq!(order, vec!["large", "validator", "small"]);
+    assert_eq!(breakdown.contributions[0].share_bps, 6_000);
+    assert_eq!(breakdown.largest_backer_share_bps, 6_000);
+    assert_eq!(breakdown.self_stake, 300);
+    assert_eq!(breakdown.self_stake_share_bps, 3_000);
+}
+
+#[test]
+fn test_backing_of_matches_indexed_lookup_and_handles_unknown_validators() {
+    let result = result();
+    assert_eq!(result.clone().indexed().backing_of("validator"), result.backing_of("validator"));
+
+    let unknown = result.backing_of("unknown");
+    assert_eq!(unknown.total_backing, 0);
+    assert!(unknown.contributions.is_empty());
+    assert_eq!(unknown.largest_backer_share_bps, 0);
+}
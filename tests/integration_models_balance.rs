@@ -0,0 +1,65 @@
+//! Models test: token amounts with chain decimals
+
+use offline_election::models::balance::{parse_amount, Balance, TokenUnit};
+use offline_election::models::election_overrides::ElectionOverrides;
+
+#[test]
+fn test_token_unit_parses_and_formats_token_amounts() {
+    let dot = TokenUnit::polkadot();
+    assert_eq!(parse_amount("1.5 DOT", Some(&dot)).unwrap(), 15_000_000_000);
+    assert_eq!(parse_amount("2dot", Some(&dot)).unwrap(), 20_000_000_000);
+    assert_eq!(parse_amount("1_000", Some(&dot)).unwrap(), 1_000);
+    assert_eq!(parse_amount("42 plancks", Some(&dot)).unwrap(), 42);
+    assert_eq!(dot.format(15_000_000_000), "1.5 DOT");
+    assert_eq!(dot.format(1), "0.0000000001 DOT");
+    assert_eq!(dot.to_decimal_string(20_000_000_000), "2");
+
+    // Wrong token, too many decimals and fractional plancks are rejected
+    assert!(parse_amount("1 KSM", Some(&dot)).unwrap_err().to_string().contains("amount is in KSM but the chain's token is DOT"));
+    assert!(parse_amount("0.00000000001 DOT", Some(&dot)).is_err());
+    assert!(parse_amount("1.5", Some(&dot)).is_err());
+
+    // Without a chain unit, known symbols are accepted
+    assert_eq!(parse_amount("1 KSM", None).unwrap(), 1_000_000_000_000);
+    assert!(parse_amount("1 XYZ", None).is_err());
+    assert_eq!("XYZ:6".parse::<TokenUnit>(), Ok(TokenUnit::new("XYZ", 6)));
+}
+
+#[test]
+fn test_balances_keep_their_token_apart() {
+    let dot = TokenUnit::polkadot();
+    let stake = Balance::parse("1.5 DOT", &dot).unwrap();
+    assert_eq!(stake.plancks(), 15_000_000_000);
+    assert_eq!(stake.to_string(), "1.5 DOT");
+
+    let total = stake.checked_add(&Balance::from_plancks(5_000_000_000, dot)).unwrap();
+    assert_eq!(total.to_decimal_string(), "2");
+
+    // The same number of plancks in another token does not add
+    let ksm = Balance::from_plancks(15_000_000_000, TokenUnit::kusama());
+    assert!(stake.checked_add(&ksm).unwrap_err().to_string().contains("different token units"));
+    assert_eq!(Balance::from_plancks(42, TokenUnit::planck()).to_string(), "42 planck");
+}
+
+#[test]
+fn test_override_files_accept_token_amounts() {
+    let dot = TokenUnit::polkadot();
+    let json = r#"{
+        "candidate_stakes": {"a": "2 DOT"},
+        "nominator_stakes": {"n1": 500, "n2": "0.25 DOT"},
+        "voting_edges": [{"action": "modify", "nominator_id": "n1", "candidate_id": "a", "weight": "1 DOT"}]
+    }"#;
+    let overrides = ElectionOverrides::from_json_str(json, Some(&dot)).unwrap();
+    assert_eq!(overrides.candidate_stakes["a"], 20_000_000_000);
+    assert_eq!(overrides.nominator_stakes["n1"], 500);
+    assert_eq!(overrides.nominator_stakes["n2"], 2_500_000_000);
+    assert_eq!(overrides.voting_edges[0].weight, Some(10_000_000_000));
+
+    let mixed = r#"{"candidate_stakes": {"a": "2 KSM"}, "nominator_stakes": {}}"#;
+    let error = ElectionOverrides::from_json_str(mixed, Some(&dot)).unwrap_err().to_string();
+    assert!(error.contains("amount is in KSM"));
+
+    // Plain plancks keep full u128 precision
+    let large = r#"{"candidate_stakes": {"a": 340282366920938463463374607431768211455}, "nominator_stakes": {}, "voting_edges": []}"#;
+    assert_eq!(ElectionOverrides::from_json_str(large, None).unwrap().candidate_stakes["a"], u128::MAX);
+}
@@ -0,0 +1,63 @@
+//! Models test: signing result bundles and checking their signatures
+#![cfg(feature = "sign")]
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::bundle_signing::BundleSigner;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::result_bundle::{ResultBundle, SignatureScheme, MANIFEST_FILE};
+use std::path::PathBuf;
+
+fn export_bundle(name: &str) -> PathBuf {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 3_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 2_000, vec!["b".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    let dir = std::env::temp_dir().join(format!("signed-bundle-{}-{}", name, std::process::id()));
+    result.export_bundle(&dir, &config, &data).unwrap();
+    dir
+}
+
+#[test]
+fn test_signed_bundles_verify_with_both_schemes() {
+    for scheme in [SignatureScheme::Sr25519, SignatureScheme::Ed25519] {
+        let dir = export_bundle(&scheme.to_string());
+        let signer = BundleSigner::from_suri(scheme, "//Alice").unwrap();
+        let mut bundle = ResultBundle::load(&dir).unwrap();
+        assert_eq!(bundle.signature_valid(), None);
+        bundle.sign(&signer).unwrap();
+
+        let reloaded = ResultBundle::load(&dir).unwrap();
+        let signature = reloaded.signature.as_ref().unwrap();
+        assert_eq!(signature.scheme, scheme);
+        assert_eq!(signature.public_key, signer.public_key());
+        let verification = reloaded.verify().unwrap();
+        assert_eq!(verification.signature_valid, Some(true));
+        assert!(verification.is_valid());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+#[test]
+fn test_signature_breaks_when_the_manifest_changes() {
+    let dir = export_bundle("tampered");
+    let signer = BundleSigner::from_suri(SignatureScheme::Sr25519, "//Alice").unwrap();
+    ResultBundle::load(&dir).unwrap().sign(&signer).unwrap();
+
+    // Re-formatting the manifest keeps its content but changes the signed bytes
+    let path = dir.join(MANIFEST_FILE);
+    let manifest: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::write(&path, serde_json::to_string(&manifest).unwrap()).unwrap();
+
+    let verification = ResultBundle::load(&dir).unwrap().verify().unwrap();
+    assert_eq!(verification.signature_valid, Some(false));
+    assert!(verification.mismatched_files.is_empty());
+    assert!(!verification.is_valid());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,62 @@
+//! Models test: profiling elections for a side-by-side chain comparison
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::balance::TokenUnit;
+use offline_election::models::chain_comparison::ChainProfile;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+
+/// Four candidates backed 400, 300, 200 and 100 by one nominator each
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for (index, stake) in [400u128, 300, 200, 100].into_iter().enumerate() {
+        let candidate = format!("c{}", index);
+        builder
+            .add_candidate(candidate.clone(), 0).unwrap()
+            .add_nominator(format!("n{}", index), stake, vec![candidate]).unwrap();
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_profile_measures_utilization_and_concentration() {
+    let data = election_data();
+    let config = ElectionConfiguration::builder().active_set_size(4).build().unwrap();
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    let profile = ChainProfile::from_result("test", &data, &result, 5);
+    assert_eq!(profile.elected_count, 4);
+    assert_eq!(profile.set_utilization_bps, 8_000);
+    assert_eq!(profile.candidates_per_seat_bps, 8_000);
+    assert_eq!(profile.total_stake.plancks(), 1_000);
+    // The data names no chain, so amounts are plain plancks
+    assert_eq!(profile.total_stake.unit(), &TokenUnit::planck());
+    assert_eq!(profile.elected_backing.plancks(), 1_000);
+    assert_eq!(profile.stake_utilization_bps, 10_000);
+    assert_eq!(profile.minimal_backing.plancks(), 100);
+    assert_eq!(profile.median_backing.plancks(), 250);
+    assert_eq!(profile.minimal_to_median_bps, 4_000);
+    // 400 alone is over a third of 1000
+    assert_eq!(profile.nakamoto_coefficient, 1);
+    assert_eq!(profile.nakamoto_share_bps, 2_500);
+    assert_eq!(profile.top_decile_share_bps, 4_000);
+    // 40² + 30² + 20² + 10² percent
+    assert_eq!(profile.herfindahl_index, 3_000);
+}
+
+#[test]
+fn test_profile_of_an_empty_set_has_zero_ratios() {
+    let data = ElectionData::new();
+    let result = offline_election::models::election_result::ElectionResult::new(
+        Vec::new(),
+        Vec::new(),
+        0,
+        offline_election::types::AlgorithmType::SequentialPhragmen,
+    );
+    let profile = ChainProfile::from_result("empty", &data, &result, 10);
+    assert_eq!(profile.elected_count, 0);
+    assert_eq!(profile.median_backing.plancks(), 0);
+    assert_eq!(profile.nakamoto_coefficient, 0);
+    assert_eq!(profile.top_decile_share_bps, 0);
+}
@@ -0,0 +1,93 @@
+//! Models test: the typestate configuration builder
+
+use offline_election::engine::ElectionEngine;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::candidate_rules::{CandidateRule, CandidateRules};
+use offline_election::models::election_config::{BalancingSettings, ElectionConfiguration};
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::models::rounding::RoundingPolicy;
+use offline_election::models::validation::ValidationProfile;
+use offline_election::types::{AlgorithmType, ElectionKind};
+
+#[test]
+fn test_builder_sets_every_option_and_defaults_the_rest() {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_stake("a".to_string(), 5).unwrap();
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::ParallelPhragmen)
+        .kind(ElectionKind::Council)
+        .overrides(overrides.clone())
+        .override_layer("upgrade", ElectionOverrides::new())
+        .candidate_rule(CandidateRule::ExcludeBlocked)
+        .candidate_rule(CandidateRule::MaxPerOperator { max: 2 })
+        .validation_profile(ValidationProfile::Strict)
+        .balancing(10, 3)
+        .partial_results(true)
+        .rounding(RoundingPolicy::Truncate(4))
+        .block_number(42)
+        .active_set_size(13)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.active_set_size, 13);
+    assert_eq!(config.algorithm, AlgorithmType::ParallelPhragmen);
+    assert_eq!(config.kind, ElectionKind::Council);
+    assert_eq!(config.overrides, Some(overrides));
+    assert_eq!(config.override_layers[0].name, "upgrade");
+    assert_eq!(
+        config.candidate_rules.unwrap().rules,
+        [CandidateRule::ExcludeBlocked, CandidateRule::MaxPerOperator { max: 2 }]
+    );
+    assert_eq!(config.validation_profile, ValidationProfile::Strict);
+    assert_eq!(config.balancing, Some(BalancingSettings { max_iterations: 10, tolerance: 3 }));
+    assert!(config.partial_results);
+    assert_eq!(config.rounding, RoundingPolicy::Truncate(4));
+    assert_eq!(config.block_number, Some(42));
+
+    // Options left out keep the defaults
+    let minimal = ElectionConfiguration::builder().active_set_size(100).build().unwrap();
+    assert_eq!(minimal, ElectionConfiguration::default());
+}
+
+#[test]
+fn test_engine_rejects_configurations_changed_after_building() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 100, vec!["a".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+
+    let mut config = ElectionConfiguration::builder().active_set_size(1).build().unwrap();
+    config.active_set_size = 0;
+    assert!(matches!(
+        ElectionEngine::new().execute(&config, &data),
+        Err(ElectionError::ValidationError { field: Some(ref field), .. }) if field == "active_set_size"
+    ));
+}
+
+#[test]
+fn test_builder_validates_on_build() {
+    let zero = ElectionConfiguration::builder().active_set_size(0).build();
+    assert!(matches!(
+        zero,
+        Err(ElectionError::ValidationError { field: Some(ref field), .. }) if field == "active_set_size"
+    ));
+
+    let rules = CandidateRules::new().exclude_commission_at_least(150);
+    let error = ElectionConfiguration::builder().candidate_rules(rules).active_set_size(5).build().unwrap_err();
+    assert!(error.to_string().contains("commission is at most 100%"), "{}", error);
+}
+
+#[test]
+fn test_candidate_rule_appends_to_rules_already_set() {
+    let config = ElectionConfiguration::builder()
+        .active_set_size(100)
+        .candidate_rules(CandidateRules::new().exclude(["a"]))
+        .candidate_rule(CandidateRule::ExcludeBlocked)
+        .build()
+        .unwrap();
+    let rules = config.candidate_rules.unwrap().rules;
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[1], CandidateRule::ExcludeBlocked);
+}
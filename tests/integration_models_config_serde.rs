@@ -0,0 +1,111 @@
+//! Models test: election configurations round-trip through JSON and TOML
+
+use offline_election::error::ElectionError;
+use offline_election::models::candidate_rules::CandidateRule;
+use offline_election::models::conviction::{Conviction, ConvictionWeights};
+use offline_election::models::election_config::{ElectionConfiguration, CONFIG_VERSION};
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::models::rounding::RoundingPolicy;
+use offline_election::types::{AlgorithmType, ElectionKind};
+
+fn full_config() -> ElectionConfiguration {
+    let mut overrides = ElectionOverrides::new();
+    for (index, account) in ["c", "a", "b", "d"].iter().enumerate() {
+        overrides.set_candidate_stake(account.to_string(), 1_000 + index as u128).unwrap();
+    }
+    overrides.set_nominator_stake("n1".to_string(), 7).unwrap();
+    overrides.add_voting_edge("n1".to_string(), "a".to_string()).unwrap();
+    let mut convictions = ConvictionWeights::new();
+    convictions.default = Some(Conviction::Locked1x);
+    convictions.nominators.insert("n1".to_string(), Conviction::Locked6x);
+
+    ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::ParallelPhragmen)
+        .active_set_size(16)
+        .kind(ElectionKind::Council)
+        .overrides(overrides.clone())
+        .override_layer("upgrade", overrides)
+        .convictions(convictions)
+        .candidate_rule(CandidateRule::ExcludeCommissionAtLeast { percent: 20 })
+        .candidate_rule(CandidateRule::MaxPerOperator { max: 2 })
+        .balancing(10, 5)
+        .partial_results(true)
+        .rounding(RoundingPolicy::RoundHalfEven(9))
+        .block_number(12_345)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_configuration_round_trips_through_json_and_toml() {
+    let config = full_config();
+
+    let json = config.to_json().unwrap();
+    assert_eq!(ElectionConfiguration::from_json_str(&json).unwrap(), config);
+    // Maps are written in key order, so equal configurations serialize identically
+    assert_eq!(full_config().to_json().unwrap(), json);
+    let positions: Vec<usize> = ["\"a\"", "\"b\"", "\"c\"", "\"d\""].iter().map(|key| json.find(key).unwrap()).collect();
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]), "{}", json);
+
+    let toml = config.to_toml().unwrap();
+    assert!(toml.lines().any(|line| line == "version = 1"), "{}", toml);
+    assert_eq!(ElectionConfiguration::from_toml_str(&toml).unwrap(), config);
+
+    // u128 stakes survive JSON exactly; TOML refuses rather than truncating
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_stake("whale".to_string(), u128::MAX).unwrap();
+    let huge = ElectionConfiguration::builder().active_set_size(100).overrides(overrides).build().unwrap();
+    assert_eq!(ElectionConfiguration::from_json_str(&huge.to_json().unwrap()).unwrap(), huge);
+    assert!(matches!(huge.to_toml(), Err(ElectionError::InvalidData { .. })));
+}
+
+#[test]
+fn test_versions_and_unknown_fields_are_checked() {
+    let unversioned = ElectionConfiguration::from_json_str(r#"{"algorithm": "mms", "active_set_size": 3}"#).unwrap();
+    assert_eq!(unversioned.version, CONFIG_VERSION);
+    assert_eq!(unversioned.algorithm, AlgorithmType::Mms);
+
+    // The example of the configuration schema reference
+    let example = ElectionConfiguration::from_toml_str(
+        "version = 1\nalgorithm = \"parallel-phragmen\"\nactive_set_size = 297\n\
+         candidate_rules = [{ rule = \"max-per-operator\", max = 2 }]\n\n\
+         [balancing]\nmax_iterations = 10\ntolerance = 0\n",
+    )
+    .unwrap();
+    assert_eq!(example.candidate_rules.unwrap().rules, [CandidateRule::MaxPerOperator { max: 2 }]);
+    assert_eq!(example.balancing.unwrap().max_iterations, 10);
+
+    let future = format!(r#"{{"version": {}, "algorithm": "mms", "active_set_size": 3}}"#, CONFIG_VERSION + 1);
+    let error = ElectionConfiguration::from_json_str(&future).unwrap_err().to_string();
+    assert!(error.contains("unsupported configuration version"), "{}", error);
+
+    let typo = ElectionConfiguration::from_toml_str("algorithm = \"mms\"\nactive_set_size = 3\nbalanceing = 4\n");
+    assert!(typo.unwrap_err().to_string().contains("unknown field `balanceing`"));
+
+    let mut config = ElectionConfiguration::builder().active_set_size(100).build().unwrap();
+    config.version = 0;
+    assert!(matches!(
+        config.validate(),
+        Err(ElectionError::ValidationError { field: Some(ref field), .. }) if field == "version"
+    ));
+}
+
+#[test]
+fn test_load_from_file_picks_the_format_by_extension() {
+    let config = full_config();
+    let dir = std::env::temp_dir().join(format!("offline-election-config-serde-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (toml_path, json_path) = (dir.join("config.toml"), dir.join("config.json"));
+    std::fs::write(&toml_path, config.to_toml().unwrap()).unwrap();
+    std::fs::write(&json_path, config.to_json().unwrap()).unwrap();
+
+    assert_eq!(ElectionConfiguration::load_from_file(&toml_path).unwrap(), config);
+    assert_eq!(ElectionConfiguration::load_from_file(&json_path).unwrap(), config);
+
+    std::fs::write(&json_path, "algorithm = \"mms\"").unwrap();
+    assert!(matches!(
+        ElectionConfiguration::load_from_file(&json_path),
+        Err(ElectionError::FileError { .. })
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,87 @@
+//! Models test: cached dataset statistics and their invalidation
+
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_data::{ElectionData, MergePolicy};
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::models::nominator::Nominator;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_candidate("c".to_string(), 10).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 400, vec!["b".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_statistics_aggregate_stakes_and_votes() {
+    let mut data = election_data();
+    // A repeated target counts once; votes for non-candidates are left out
+    data.nominators[1].targets = vec!["b".to_string(), "b".to_string(), "ghost".to_string()];
+
+    let statistics = data.statistics();
+    assert_eq!(statistics.total_candidate_stake, 160);
+    assert_eq!(statistics.total_nominator_stake, 1_400);
+    assert_eq!(statistics.approval_of("a"), 1_000);
+    assert_eq!(statistics.approval_of("b"), 1_400);
+    assert_eq!(statistics.approval_of("c"), 0);
+    assert_eq!(statistics.degree_of("b"), 2);
+    assert_eq!(statistics.degree_of("ghost"), 0);
+    assert_eq!(statistics.edge_count, 3);
+    assert_eq!(statistics.max_nominator_degree, 3);
+
+    // The cached value is returned until the data changes
+    assert!(std::ptr::eq(&*data.statistics(), &*data.statistics()));
+}
+
+#[test]
+fn test_mutations_drop_the_cached_statistics() {
+    let mut data = election_data();
+    assert_eq!(data.statistics().total_nominator_stake, 1_400);
+
+    data.add_nominator(Nominator::new("n3".to_string(), 600)).unwrap();
+    assert_eq!(data.statistics().total_nominator_stake, 2_000);
+
+    data.nominators_mut()[0].stake = 0;
+    assert_eq!(data.statistics().total_nominator_stake, 1_000);
+
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_nominator_stake("n2".to_string(), 100).unwrap();
+    overrides.apply(&mut data);
+    assert_eq!(data.statistics().approval_of("b"), 100);
+
+    data.merge(election_data(), MergePolicy::Prefix("x:".to_string())).unwrap();
+    assert_eq!(data.statistics().total_candidate_stake, 320);
+
+    // Removing an account through the public fields changes the count, and
+    // the recomputed statistics are cached in place of the stale ones
+    data.nominators.retain(|n| n.account_id != "x:n2");
+    assert_eq!(data.statistics().total_nominator_stake, 1_700);
+    assert!(std::ptr::eq(&*data.statistics(), &*data.statistics()));
+
+    // Direct field writes keeping the counts need an explicit invalidation
+    data.candidates[0].stake = 0;
+    assert_eq!(data.statistics().total_candidate_stake, 320);
+    data.invalidate_statistics();
+    assert_eq!(data.statistics().total_candidate_stake, 220);
+}
+
+#[test]
+fn test_clones_recompute_and_caches_do_not_affect_equality() {
+    let data = election_data();
+    let _ = data.statistics();
+
+    let mut copy = data.clone();
+    assert_eq!(copy, data);
+    copy.nominators[0].stake = 1;
+    assert_eq!(copy.statistics().total_nominator_stake, 401);
+    assert_eq!(data.statistics().total_nominator_stake, 1_400);
+
+    // Statistics are not part of the serialized data
+    let json = serde_json::to_string(&data).unwrap();
+    assert!(!json.contains("statistics"));
+    assert_eq!(serde_json::from_str::<ElectionData>(&json).unwrap(), data);
+}
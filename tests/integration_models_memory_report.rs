@@ -0,0 +1,46 @@
+//! Models test: memory footprint report and releasing spare capacity
+
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_data::ElectionData;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 400, vec!["b".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_report_counts_strings_by_component() {
+    let data = election_data();
+    let report = data.memory_report();
+    assert!(report.records > 0);
+    assert!(report.account_ids >= "a".len() + "b".len() + "n1".len() + "n2".len());
+    // Three one-byte targets, each naming a candidate already held elsewhere
+    assert!(report.targets >= 3);
+    assert!(report.repeated_targets >= 3);
+    assert_eq!(
+        report.total(),
+        report.records + report.account_ids + report.targets + report.metadata
+    );
+}
+
+#[test]
+fn test_shrink_to_fit_releases_spare_capacity_and_keeps_data() {
+    let mut data = election_data();
+    data.nominators[0].targets.reserve(1_000);
+    data.candidates[0].account_id.reserve(1_000);
+    let before = data.memory_report();
+    assert!(before.spare_capacity >= 1_000);
+
+    let released = data.shrink_to_fit();
+    let after = data.memory_report();
+    assert!(released >= 1_000);
+    assert_eq!(after.total(), before.total() - released);
+    assert!(after.spare_capacity < before.spare_capacity);
+    assert_eq!(data.nominators[0].targets, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(data.get_candidate("a").unwrap().stake, 100);
+}
@@ -0,0 +1,84 @@
+//! Model test: merging election data sets into composed scenarios
+
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_data::{ElectionData, MergePolicy};
+
+fn polkadot() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 1_000).unwrap()
+        .add_candidate("b".to_string(), 2_000).unwrap()
+        .add_nominator("n1".to_string(), 5_000, vec!["a".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn kusama() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("b".to_string(), 300).unwrap()
+        .add_candidate("k".to_string(), 400).unwrap()
+        .add_nominator("n1".to_string(), 700, vec!["b".to_string(), "k".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 900, vec!["k".to_string()]).unwrap();
+    let mut data = builder.build().unwrap();
+    data.invulnerables = vec!["k".to_string()];
+    data
+}
+
+fn stakes(data: &ElectionData) -> Vec<(String, u128)> {
+    data.candidates
+        .iter()
+        .map(|c| (c.account_id.clone(), c.stake))
+        .chain(data.nominators.iter().map(|n| (n.account_id.clone(), n.stake)))
+        .collect()
+}
+
+#[test]
+fn test_merge_policies_resolve_shared_account_ids() {
+    let mut summed = polkadot();
+    assert_eq!(summed.merge(kusama(), MergePolicy::Sum).unwrap(), 2);
+    summed.validate().unwrap();
+    assert_eq!(
+        stakes(&summed),
+        vec![
+            ("a".to_string(), 1_000),
+            ("b".to_string(), 2_300),
+            ("k".to_string(), 400),
+            ("n1".to_string(), 5_700),
+            ("n2".to_string(), 900),
+        ]
+    );
+    assert_eq!(summed.nominators[0].targets, vec!["a", "b", "k"]);
+    assert_eq!(summed.invulnerables, vec!["k"]);
+
+    let mut kept = polkadot();
+    kept.merge(kusama(), MergePolicy::KeepExisting).unwrap();
+    assert_eq!(kept.candidates[1].stake, 2_000);
+    assert_eq!(kept.nominators[0].targets, vec!["a"]);
+
+    let mut replaced = polkadot();
+    replaced.merge(kusama(), MergePolicy::Replace).unwrap();
+    assert_eq!(replaced.candidates[1].stake, 300);
+    assert_eq!(replaced.nominators[0].targets, vec!["b", "k"]);
+
+    // A rejected merge leaves the data untouched
+    let mut rejected = polkadot();
+    assert!(rejected.merge(kusama(), MergePolicy::Reject).is_err());
+    assert_eq!(rejected, polkadot());
+}
+
+#[test]
+fn test_prefix_policy_keeps_both_data_sets_apart() {
+    let mut migration = polkadot();
+    let collisions = migration.merge(kusama(), MergePolicy::Prefix("ksm:".to_string())).unwrap();
+    assert_eq!(collisions, 0);
+    migration.validate().unwrap();
+
+    assert_eq!(migration.candidates.len(), 4);
+    assert_eq!(migration.nominators.len(), 3);
+    let n1 = migration.nominators.iter().find(|n| n.account_id == "ksm:n1").unwrap();
+    assert_eq!(n1.targets, vec!["ksm:b", "ksm:k"]);
+    assert_eq!(migration.invulnerables, vec!["ksm:k"]);
+
+    // An empty prefix still collides
+    assert!(polkadot().merge(kusama(), MergePolicy::Prefix(String::new())).is_err());
+}
@@ -0,0 +1,80 @@
+//! Models test: per-nominator allocation query
+
+use offline_election::models::election_result::{ElectionResult, SelectedValidator, StakeAllocation};
+use offline_election::models::nominator::Nominator;
+use offline_election::types::AlgorithmType;
+
+fn winner(account_id: &str) -> SelectedValidator {
+    SelectedValidator {
+        account_id: account_id.to_string(),
+        total_backing_stake: 0,
+        nominator_count: 1,
+        rank: None,
+    }
+}
+
+fn allocation(nominator_id: &str, validator_id: &str, amount: u128) -> StakeAllocation {
+    StakeAllocation {
+        nominator_id: nominator_id.to_string(),
+        validator_id: validator_id.to_string(),
+        amount,
+        proportion: 0.0,
+        unrewarded: false,
+    }
+}
+
+fn nominator(account_id: &str, stake: u128, targets: &[&str]) -> Nominator {
+    let mut nominator = Nominator::new(account_id.to_string(), stake);
+    for target in targets {
+        nominator.add_target(target.to_string());
+    }
+    nominator
+}
+
+fn result() -> ElectionResult {
+    ElectionResult::new(
+        vec![winner("a"), winner("b")],
+        vec![
+            allocation("alice", "b", 300),
+            allocation("alice", "a", 500),
+            allocation("bob", "a", 1_000),
+        ],
+        1_800,
+        AlgorithmType::SequentialPhragmen,
+    )
+}
+
+#[test]
+fn test_allocations_of_lists_targets_in_vote_order_with_remainder() {
+    let alice = nominator("alice", 1_000, &["a", "c", "b"]);
+
+    let allocations = result().allocations_of(&alice);
+
+    assert_eq!(allocations.stake, 1_000);
+    assert_eq!(allocations.allocated, 800);
+    assert_eq!(allocations.unallocated, 200);
+    let targets: Vec<(&str, u128, bool)> = allocations
+        .targets
+        .iter()
+        .map(|t| (t.validator_id.as_str(), t.amount, t.elected))
+        .collect();
+    assert_eq!(targets, vec![("a", 500, true), ("c", 0, false), ("b", 300, true)]);
+}
+
+#[test]
+fn test_allocations_of_matches_indexed_lookup() {
+    let result = result();
+    let indexed = result.clone().indexed();
+
+    for nominator in [
+        nominator("alice", 1_000, &["a", "c", "b"]),
+        nominator("bob", 1_000, &["a"]),
+        nominator("carol", 50, &["c"]),
+    ] {
+        assert_eq!(indexed.allocations_of(&nominator), result.allocations_of(&nominator));
+    }
+
+    let carol = indexed.allocations_of(&nominator("carol", 50, &["c"]));
+    assert_eq!(carol.unallocated, 50);
+    assert!(!carol.targets[0].elected);
+}
@@ -0,0 +1,97 @@
+//! Models test: the summary returned by applying overrides
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_overrides::{ElectionOverrides, OverrideApplication};
+use offline_election::models::election_result::ResultIssueKind;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_candidate("c".to_string(), 10).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 400, vec!["b".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_application_counts_each_kind_and_lists_unmatched_in_order() {
+    let mut data = election_data();
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_stake("a".to_string(), 7).unwrap();
+    overrides.set_candidate_stake("zed".to_string(), 1).unwrap();
+    overrides.set_candidate_stake("ghost".to_string(), 1).unwrap();
+    overrides.set_nominator_stake("n2".to_string(), 300).unwrap();
+    overrides.set_candidate_blocked("b".to_string(), true).unwrap();
+    overrides.add_voting_edge("n1".to_string(), "c".to_string()).unwrap();
+    overrides.remove_voting_edge("n2".to_string(), "b".to_string()).unwrap();
+    overrides.add_voting_edge("nobody".to_string(), "a".to_string()).unwrap();
+
+    let application = overrides.apply(&mut data);
+    assert_eq!(
+        application,
+        OverrideApplication {
+            candidate_stakes: 1,
+            nominator_stakes: 1,
+            candidate_blocked: 1,
+            voting_edges: 2,
+            rejected_edges: Vec::new(),
+            unmatched: vec![
+                "Candidate stake override for unknown candidate ghost".to_string(),
+                "Candidate stake override for unknown candidate zed".to_string(),
+                "Voting edge override for unknown nominator nobody".to_string(),
+            ],
+        }
+    );
+    assert_eq!(application.applied(), 5);
+    assert!(!application.is_complete());
+    assert_eq!(data.candidates[0].stake, 7);
+    assert_eq!(data.nominators[0].targets, ["a", "c"]);
+    assert!(data.nominators[1].targets.is_empty());
+}
+
+#[test]
+fn test_votes_for_blocking_candidates_are_rejected_not_dropped_silently() {
+    let mut data = election_data();
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_blocked("c".to_string(), true).unwrap();
+    overrides.add_voting_edge("n1".to_string(), "c".to_string()).unwrap();
+    // An existing vote for a blocking candidate is kept
+    overrides.add_voting_edge("n1".to_string(), "a".to_string()).unwrap();
+
+    let application = overrides.apply(&mut data);
+    assert_eq!(application.voting_edges, 1);
+    assert_eq!(
+        application.rejected_edges,
+        ["Candidate c blocks new nominations; ignored new vote from n1"]
+    );
+    assert_eq!(data.nominators[0].targets, ["a"]);
+
+    // In partial-result mode the engine reports rejected votes as issues
+    let config = ElectionConfiguration::builder()
+        .overrides(overrides)
+        .partial_results(true)
+        .active_set_size(2)
+        .build()
+        .unwrap();
+    let result = ElectionEngine::new().execute(&config, &election_data()).unwrap();
+    let rejected: Vec<&str> = result
+        .issues
+        .iter()
+        .filter(|issue| issue.kind == ResultIssueKind::RejectedOverride)
+        .map(|issue| issue.message.as_str())
+        .collect();
+    assert_eq!(rejected, ["Candidate c blocks new nominations; ignored new vote from n1"]);
+}
+
+#[test]
+fn test_application_serializes_only_non_empty_lists() {
+    let application = OverrideApplication { candidate_stakes: 2, ..OverrideApplication::default() };
+    let json = serde_json::to_value(&application).unwrap();
+    assert!(json.get("unmatched").is_none());
+    assert_eq!(serde_json::from_value::<OverrideApplication>(json).unwrap(), application);
+}
@@ -0,0 +1,70 @@
+//! Model test: export of results in polkadot-js staking JSON shapes
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::PolkadotJsStaking;
+
+#[test]
+fn test_export_matches_eras_stakers_shape() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_nominator("a".to_string(), 500, vec!["a".to_string()]).unwrap()
+        .add_nominator("n1".to_string(), 300, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 200, vec!["b".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(2).build().unwrap(), &data)
+        .unwrap();
+
+    let export = PolkadotJsStaking::from_result(&result, &data, Some(42));
+    let json: serde_json::Value = serde_json::from_str(&export.to_json().unwrap()).unwrap();
+    assert_eq!(json["era"], 42);
+    assert_eq!(json["validators"], serde_json::json!(["a", "b"]));
+    assert_eq!(
+        json["erasStakers"]["a"],
+        serde_json::json!({ "total": 800, "own": 500, "others": [{ "who": "n1", "value": 300 }] })
+    );
+    assert_eq!(
+        json["erasStakers"]["b"],
+        serde_json::json!({ "total": 200, "own": 0, "others": [{ "who": "n2", "value": 200 }] })
+    );
+}
+
+#[test]
+fn test_large_balances_are_hex_like_polkadot_js() {
+    let mut builder = SyntheticDataBuilder::new();
+    let stake = 10_000_000_000_000_000u128;
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), stake, vec!["a".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(1).build().unwrap(), &data)
+        .unwrap();
+
+    let json = serde_json::to_value(PolkadotJsStaking::from_result(&result, &data, None)).unwrap();
+    assert!(json.get("era").is_none());
+    assert_eq!(json["erasStakers"]["a"]["total"], format!("0x{:032x}", stake));
+    assert_eq!(json["erasStakers"]["a"]["own"], 0);
+}
+
+#[test]
+fn test_bonded_stake_without_a_self_vote_is_own_stake() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 400).unwrap()
+        .add_nominator("n1".to_string(), 300, vec!["a".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(1).build().unwrap(), &data)
+        .unwrap();
+
+    let json = serde_json::to_value(PolkadotJsStaking::from_result(&result, &data, None)).unwrap();
+    assert_eq!(
+        json["erasStakers"]["a"],
+        serde_json::json!({ "total": 700, "own": 400, "others": [{ "who": "n1", "value": 300 }] })
+    );
+}
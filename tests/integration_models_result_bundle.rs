@@ -0,0 +1,74 @@
+//! Models test: result provenance bundles can be exported, re-run and checked
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::models::result_bundle::{ResultBundle, CONFIG_FILE, DATA_FILE, MANIFEST_FILE, RESULT_FILE};
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for candidate in ["a", "b", "c"] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+    }
+    builder
+        .add_nominator("n1".to_string(), 3_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 2_000, vec!["b".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 1_000, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+fn bundle_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("result-bundle-{}-{}", name, std::process::id()))
+}
+
+/// Overrides make "c" the best-backed candidate, so the bundle must carry them
+fn config() -> ElectionConfiguration {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_nominator_stake("n3".to_string(), 9_000).unwrap();
+    ElectionConfiguration::builder().active_set_size(3).overrides(overrides).build().unwrap()
+}
+
+#[test]
+fn test_exported_bundle_reproduces_the_result() {
+    let dir = bundle_dir("reproduce");
+    let (data, config) = (election_data(), config());
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    let manifest = result.export_bundle(&dir, &config, &data).unwrap();
+
+    for file in [RESULT_FILE, DATA_FILE, CONFIG_FILE, MANIFEST_FILE] {
+        assert!(dir.join(file).exists(), "{} missing", file);
+    }
+    assert_eq!(manifest.files.len(), 3);
+    assert_eq!(manifest.crate_version, env!("CARGO_PKG_VERSION"));
+
+    let bundle = ResultBundle::load(&dir).unwrap();
+    // The bundled data is post-override and the config no longer applies them
+    assert_eq!(bundle.data.nominators[2].stake, 9_000);
+    assert!(bundle.config.overrides.is_none());
+    assert_eq!(bundle.result.selected_validators, result.selected_validators);
+    assert!(bundle.verify().unwrap().is_valid());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_verify_reports_tampered_bundles() {
+    let dir = bundle_dir("tampered");
+    let (data, config) = (election_data(), config());
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+    result.export_bundle(&dir, &config, &data).unwrap();
+
+    // Give "b" more stake than the bundled result was computed with
+    let path = dir.join(DATA_FILE);
+    let mut tampered: ElectionData = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    tampered.nominators[1].stake = 20_000;
+    std::fs::write(&path, serde_json::to_string_pretty(&tampered).unwrap()).unwrap();
+
+    let verification = ResultBundle::load(&dir).unwrap().verify().unwrap();
+    assert!(!verification.is_valid());
+    assert_eq!(verification.mismatched_files, vec![DATA_FILE.to_string()]);
+    assert!(!verification.content_hashes_match);
+    assert!(verification.result_differences.iter().any(|d| d.contains("b is backed by 2000 in the bundle but 20000 when re-run")));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
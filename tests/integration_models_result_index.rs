@@ -0,0 +1,74 @@
+//! Models test: paginated winners and indexed stake allocation lookups
+
+use offline_election::models::election_result::{ElectionResult, SelectedValidator, StakeAllocation};
+use offline_election::models::result_index::Page;
+use offline_election::types::AlgorithmType;
+
+fn result() -> ElectionResult {
+    let selected_validators = (0..5)
+        .map(|i| SelectedValidator {
+            account_id: format!("validator-{}", i),
+            total_backing_stake: 100,
+            nominator_count: 2,
+            rank: Some(i + 1),
+        })
+        .collect();
+    let stake_distribution = (0..10)
+        .map(|i| StakeAllocation {
+            nominator_id: format!("nominator-{}", i % 4),
+            validator_id: format!("validator-{}", i % 5),
+            amount: 50,
+            proportion: 0.5,
+            unrewarded: false,
+        })
+        .collect();
+    ElectionResult::new(selected_validators, stake_distribution, 500, AlgorithmType::SequentialPhragmen)
+}
+
+#[test]
+fn test_winners_page_slices_active_set() {
+    let result = result();
+
+    let first = result.winners_page(0, 2);
+    assert_eq!(first.total_items, 5);
+    assert_eq!(first.total_pages, 3);
+    assert!(first.has_next());
+    assert_eq!(first.items[0].account_id, "validator-0");
+
+    let last = result.winners_page(2, 2);
+    assert_eq!(last.items.len(), 1);
+    assert!(!last.has_next());
+
+    assert!(result.winners_page(9, 2).items.is_empty());
+}
+
+#[test]
+fn test_index_matches_scanning_accessors() {
+    let scanned = result();
+    let indexed = result().indexed();
+
+    for i in 0..5 {
+        let id = format!("validator-{}", i);
+        assert_eq!(indexed.allocations_for_validator(&id), scanned.allocations_for_validator(&id));
+        assert_eq!(indexed.total_stake_for_validator(&id), scanned.total_stake_for_validator(&id));
+        assert_eq!(indexed.winner(&id).unwrap().rank, Some(i + 1));
+    }
+    assert_eq!(
+        indexed.allocations_for_nominator("nominator-1"),
+        scanned.allocations_for_nominator("nominator-1")
+    );
+    assert!(indexed.allocations_for_validator("unknown").is_empty());
+    assert!(indexed.winner("unknown").is_none());
+}
+
+#[test]
+fn test_validator_allocations_page() {
+    let indexed = result().indexed();
+
+    let page = indexed.validator_allocations_page("validator-3", 1, 1);
+    assert_eq!(page.total_items, 2);
+    assert_eq!(page.items[0].nominator_id, "nominator-0");
+
+    let empty: Page<&StakeAllocation> = indexed.validator_allocations_page("unknown", 0, 10);
+    assert_eq!(empty.total_pages, 0);
+}
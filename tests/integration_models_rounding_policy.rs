@@ -0,0 +1,116 @@
+//! Models test: rounding of derived ratios in results and reports
+
+use clap::Parser;
+use offline_election::cli::RunCommand;
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::rounding::RoundingPolicy;
+use offline_election::payout::InflationParams;
+use offline_election::types::AlgorithmType;
+
+#[test]
+fn test_policies_round_the_decimal_digits_as_printed() {
+    let truncate = RoundingPolicy::Truncate(2);
+    assert_eq!(truncate.apply(0.3), 0.3);
+    assert_eq!(truncate.apply(0.129), 0.12);
+    assert_eq!(truncate.apply(-0.129), -0.12);
+    assert_eq!(truncate.apply(0.009).to_bits(), 0.0f64.to_bits());
+    assert_eq!(truncate.apply(-0.009).to_bits(), 0.0f64.to_bits());
+
+    let half_even = RoundingPolicy::RoundHalfEven(2);
+    assert_eq!(half_even.apply(2.675), 2.68);
+    assert_eq!(half_even.apply(0.125), 0.12);
+    assert_eq!(half_even.apply(0.135), 0.14);
+    assert_eq!(half_even.apply(0.1251), 0.13);
+    assert_eq!(half_even.apply(9.995), 10.0);
+    assert_eq!(RoundingPolicy::RoundHalfEven(0).apply(2.5), 2.0);
+
+    let significant = RoundingPolicy::SignificantDigits(3);
+    assert_eq!(significant.apply(0.000123456), 0.000123);
+    assert_eq!(significant.apply(123456.0), 123000.0);
+    assert_eq!(significant.apply(99950.0), 100000.0);
+    assert_eq!(significant.apply(0.0), 0.0);
+
+    assert_eq!(RoundingPolicy::Exact.apply(0.1 + 0.2), 0.1 + 0.2);
+    assert!(half_even.apply(f64::NAN).is_nan());
+    assert_eq!(half_even.apply(f64::INFINITY), f64::INFINITY);
+
+    // Display keeps trailing zeros for decimal policies
+    assert_eq!(half_even.format(0.5), "0.50");
+    assert_eq!(truncate.format(1.0 / 3.0), "0.33");
+    assert_eq!(significant.format(1.0 / 3.0), "0.333");
+    assert_eq!(RoundingPolicy::Exact.format(0.25), "0.25");
+}
+
+#[test]
+fn test_policies_parse_display_and_serialize() {
+    for (text, policy) in [
+        ("exact", RoundingPolicy::Exact),
+        ("truncate:6", RoundingPolicy::Truncate(6)),
+        ("round-half-even:4", RoundingPolicy::RoundHalfEven(4)),
+        ("significant-digits:3", RoundingPolicy::SignificantDigits(3)),
+    ] {
+        assert_eq!(text.parse::<RoundingPolicy>().unwrap(), policy);
+        assert_eq!(policy.to_string(), text);
+    }
+    assert_eq!("Half-Even:2".parse::<RoundingPolicy>().unwrap(), RoundingPolicy::RoundHalfEven(2));
+    for invalid in ["", "round", "truncate", "truncate:x", "truncate:-1", "significant:0", "ceil:2"] {
+        assert!(invalid.parse::<RoundingPolicy>().is_err(), "{}", invalid);
+    }
+
+    assert_eq!(serde_json::to_string(&RoundingPolicy::RoundHalfEven(6)).unwrap(), r#"{"round-half-even":6}"#);
+    assert_eq!(serde_json::from_str::<RoundingPolicy>(r#""exact""#).unwrap(), RoundingPolicy::Exact);
+
+    // The default policy leaves configuration hashes unchanged
+    let builder = ElectionConfiguration::builder().active_set_size(100);
+    let config = builder.clone().build().unwrap();
+    let rounded = |policy| builder.clone().rounding(policy).build().unwrap().content_hash();
+    assert_eq!(rounded(RoundingPolicy::Exact), config.content_hash());
+    assert_ne!(rounded(RoundingPolicy::Truncate(4)), config.content_hash());
+}
+
+#[test]
+fn test_engine_rounds_allocation_proportions() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 300, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 100, vec!["b".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+
+    let builder = ElectionConfiguration::builder().algorithm(AlgorithmType::SequentialPhragmen).active_set_size(2);
+    let exact = ElectionEngine::new().execute(&builder.clone().build().unwrap(), &data).unwrap();
+    let policy = RoundingPolicy::RoundHalfEven(3);
+    let rounded = ElectionEngine::new().execute(&builder.rounding(policy).build().unwrap(), &data).unwrap();
+
+    assert_eq!(rounded.selected_validators, exact.selected_validators);
+    for (rounded, exact) in rounded.stake_distribution.iter().zip(&exact.stake_distribution) {
+        assert_eq!(rounded.amount, exact.amount);
+        assert_eq!(rounded.proportion, policy.apply(exact.proportion));
+        assert_eq!(policy.apply(rounded.proportion), rounded.proportion);
+    }
+}
+
+#[test]
+fn test_run_command_and_payouts_take_a_policy() {
+    let parse = |rounding: &str| {
+        RunCommand::try_parse_from([
+            "run", "--algorithm", "sequential-phragmen", "--active-set-size", "2", "--rounding", rounding,
+        ])
+        .unwrap()
+    };
+    let policy = |command: RunCommand| command.resolve_settings().unwrap().rounding().unwrap();
+    assert_eq!(policy(parse("truncate:4")), RoundingPolicy::Truncate(4));
+    assert_eq!(
+        policy(RunCommand::try_parse_from(["run", "--algorithm", "mms", "--active-set-size", "2"]).unwrap()),
+        RoundingPolicy::Exact
+    );
+
+    let payout = InflationParams::polkadot().era_payout(517, 1_000).unwrap();
+    let rounded = payout.clone().rounded(&RoundingPolicy::SignificantDigits(2));
+    assert_eq!(rounded.staking_rate, 0.52);
+    assert_eq!(rounded.staking_payout, payout.staking_payout);
+    assert_eq!(rounded.annual_staking_inflation, RoundingPolicy::SignificantDigits(2).apply(payout.annual_staking_inflation));
+}
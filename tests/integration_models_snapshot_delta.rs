@@ -0,0 +1,87 @@
+//! Model test: snapshot deltas between two blocks
+
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_overrides::EdgeAction;
+use offline_election::models::snapshot_delta::SnapshotDelta;
+
+fn era_n() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 1_000).unwrap()
+        .add_candidate("b".to_string(), 2_000).unwrap()
+        .add_candidate("c".to_string(), 3_000).unwrap()
+        .add_nominator("n1".to_string(), 5_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 7_000, vec!["c".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 9_000, vec!["c".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+/// "c" leaves, "d" joins, n1 bonds more and moves a vote, n3 unbonds, n4 joins
+fn era_n_plus_one() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 1_000).unwrap()
+        .add_candidate("b".to_string(), 2_500).unwrap()
+        .add_candidate("d".to_string(), 4_000).unwrap()
+        .add_nominator("n1".to_string(), 6_000, vec!["a".to_string(), "d".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 7_000, vec![]).unwrap()
+        .add_nominator("n4".to_string(), 1_000, vec!["d".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_delta_records_churn_and_reproduces_the_later_snapshot() {
+    let before = era_n();
+    let after = era_n_plus_one();
+    let delta = SnapshotDelta::between(&before, &after);
+
+    assert_eq!(delta.removed_candidates, vec!["c"]);
+    assert_eq!(delta.added_candidates.iter().map(|c| c.account_id.as_str()).collect::<Vec<_>>(), vec!["d"]);
+    assert_eq!(delta.removed_nominators, vec!["n3"]);
+    assert_eq!(delta.added_nominators.iter().map(|n| n.account_id.as_str()).collect::<Vec<_>>(), vec!["n4"]);
+    assert_eq!(delta.overrides.candidate_stakes.get("b"), Some(&2_500));
+    assert_eq!(delta.overrides.candidate_stakes.len(), 1);
+    assert_eq!(delta.overrides.nominator_stakes.get("n1"), Some(&6_000));
+    let edges: Vec<(EdgeAction, &str, &str)> = delta
+        .overrides
+        .voting_edges
+        .iter()
+        .map(|e| (e.action, e.nominator_id.as_str(), e.candidate_id.as_str()))
+        .collect();
+    assert_eq!(
+        edges,
+        vec![(EdgeAction::Remove, "n1", "b"), (EdgeAction::Add, "n1", "d"), (EdgeAction::Remove, "n2", "c")]
+    );
+
+    let mut replayed = before.clone();
+    delta.apply(&mut replayed);
+    assert_eq!(replayed, after);
+
+    let json = serde_json::to_string(&delta).unwrap();
+    assert_eq!(serde_json::from_str::<SnapshotDelta>(&json).unwrap(), delta);
+    assert!(SnapshotDelta::between(&after, &after).is_empty());
+}
+
+#[test]
+fn test_delta_replays_onto_another_baseline() {
+    let delta = SnapshotDelta::between(&era_n(), &era_n_plus_one());
+
+    // A baseline without "b", "n2" or "n3"; "c" disappears so n5 loses its vote
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("c".to_string(), 300).unwrap()
+        .add_nominator("n1".to_string(), 500, vec!["a".to_string()]).unwrap()
+        .add_nominator("n5".to_string(), 800, vec!["a".to_string(), "c".to_string()]).unwrap();
+    let mut baseline = builder.build().unwrap();
+
+    assert!(delta.apply(&mut baseline) > 0);
+    baseline.validate().unwrap();
+    let ids: Vec<&str> = baseline.candidates.iter().map(|c| c.account_id.as_str()).collect();
+    assert_eq!(ids, vec!["a", "d"]);
+    let n1 = &baseline.nominators[0];
+    assert_eq!((n1.stake, n1.targets.clone()), (6_000, vec!["a".to_string(), "d".to_string()]));
+    assert_eq!(baseline.nominators[1].targets, vec!["a"]);
+    assert_eq!(baseline.nominators[2].account_id, "n4");
+}
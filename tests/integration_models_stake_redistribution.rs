@@ -0,0 +1,64 @@
+//! Model test: recommending stake moves away from over-backed validators
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::models::stake_redistribution::StakeRedistribution;
+
+/// Validator a is backed by 900, b by 200 and c by 100
+fn election() -> (ElectionData, ElectionResult) {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 600, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 300, vec!["a".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 200, vec!["b".to_string()]).unwrap()
+        .add_nominator("n4".to_string(), 100, vec!["c".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let result = ElectionEngine::new()
+        .execute(&ElectionConfiguration::builder().active_set_size(3).build().unwrap(), &data)
+        .unwrap();
+    (data, result)
+}
+
+#[test]
+fn test_excess_moves_to_least_backed_validators() {
+    let (mut data, result) = election();
+    // n1 already nominates c; the election just did not use it
+    data.nominators[0].targets.push("c".to_string());
+    let redistribution = StakeRedistribution::recommend(&result, &data, 1_000);
+
+    assert_eq!(redistribution.target_backing, 400);
+    assert_eq!(redistribution.over_backed.len(), 1);
+    assert_eq!(redistribution.over_backed[0].excess, 500);
+
+    let moves: Vec<(&str, &str, &str, u128, bool)> = redistribution
+        .moves
+        .iter()
+        .map(|m| (m.nominator_id.as_str(), m.from_validator.as_str(), m.to_validator.as_str(), m.amount, m.already_nominated))
+        .collect();
+    assert_eq!(moves, vec![("n1", "a", "c", 300, true), ("n1", "a", "b", 200, false)]);
+    assert_eq!(redistribution.renominations().count(), 1);
+    assert_eq!(redistribution.moved_stake, 500);
+
+    assert_eq!(redistribution.baseline_score.minimal_stake, 100);
+    assert_eq!(redistribution.redistributed_score.minimal_stake, 400);
+    assert_eq!(redistribution.score_delta.minimal_stake, 300);
+    assert_eq!(redistribution.score_delta.sum_stake, 0);
+    assert!(redistribution.score_delta.sum_stake_squared < 0);
+}
+
+#[test]
+fn test_tolerance_decides_who_is_over_backed() {
+    let (data, result) = election();
+    // 900 is 125% above the average of 400
+    let tolerant = StakeRedistribution::recommend(&result, &data, 12_500);
+    assert!(tolerant.over_backed.is_empty() && tolerant.moves.is_empty());
+    assert_eq!(tolerant.redistributed_score, tolerant.baseline_score);
+
+    assert_eq!(StakeRedistribution::recommend(&result, &data, 12_499).over_backed.len(), 1);
+}
@@ -0,0 +1,66 @@
+//! Notification tests: payload templates, set change events and command hooks
+
+use offline_election::notifications::{NotificationConfig, NotificationEvent, Notifier};
+
+#[test]
+fn test_template_placeholders_are_replaced_with_event_fields() {
+    let event = NotificationEvent::ValidatorLeaving {
+        validator_id: "validator-a".to_string(),
+        block_number: Some(42),
+    };
+
+    let rendered = event
+        .render(r#"{"text": "{{validator_id}} leaves at block {{block_number}} ({{event}})"}"#)
+        .unwrap();
+
+    assert_eq!(rendered, r#"{"text": "validator-a leaves at block 42 (validator-leaving)"}"#);
+}
+
+#[test]
+fn test_set_change_events_only_include_watched_validators() {
+    let config = NotificationConfig::new()
+        .webhook("http://localhost:9/hook")
+        .watch_validator("validator-b")
+        .build()
+        .unwrap();
+    let notifier = Notifier::new(config);
+
+    let current = vec!["validator-a".to_string(), "validator-b".to_string()];
+    let predicted = vec!["validator-a".to_string(), "validator-c".to_string()];
+    let events = notifier.set_change_events(&current, &predicted, None);
+
+    assert_eq!(
+        events,
+        vec![NotificationEvent::ValidatorLeaving {
+            validator_id: "validator-b".to_string(),
+            block_number: None,
+        }]
+    );
+}
+
+#[test]
+fn test_invalid_webhook_url_is_rejected() {
+    assert!(NotificationConfig::new().webhook("ftp://example.com").build().is_err());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_command_hook_receives_payload_on_stdin() {
+    let output = std::env::temp_dir().join(format!("notify-hook-{}.json", std::process::id()));
+    let config = NotificationConfig::new()
+        .command(format!("cat > {}", output.display()))
+        .build()
+        .unwrap();
+    let event = NotificationEvent::JobCompleted {
+        election_id: "job-1".to_string(),
+        validator_count: 3,
+        total_stake: "1000".to_string(),
+        execution_time_ms: None,
+    };
+
+    Notifier::new(config).send(&event).await.unwrap();
+
+    let payload = std::fs::read_to_string(&output).unwrap();
+    std::fs::remove_file(&output).ok();
+    assert_eq!(payload, event.to_json().unwrap());
+}
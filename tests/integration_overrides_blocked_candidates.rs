@@ -0,0 +1,62 @@
+//! Override test: blocked candidates reject new nominations
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::types::AlgorithmType;
+
+#[test]
+fn test_blocked_candidate_rejects_new_nominations() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("validator-a".to_string(), 1_000).unwrap()
+        .add_candidate("validator-b".to_string(), 1_000).unwrap()
+        .add_nominator("nominator-1".to_string(), 5_000, vec!["validator-a".to_string()]).unwrap()
+        .add_nominator("nominator-2".to_string(), 100, vec!["validator-a".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_blocked("validator-b".to_string(), true).unwrap();
+    overrides.add_voting_edge("nominator-1".to_string(), "validator-b".to_string()).unwrap();
+
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .overrides(overrides)
+        .build()
+        .unwrap();
+
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    // The new nomination to the blocked candidate must have been dropped
+    assert!(
+        result.allocations_for_validator("validator-b").is_empty(),
+        "Blocked candidate should not receive stake from new nominations"
+    );
+}
+
+#[test]
+fn test_blocked_candidate_keeps_existing_nominations() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("validator-a".to_string(), 1_000).unwrap()
+        .add_candidate("validator-b".to_string(), 1_000).unwrap()
+        .add_nominator("nominator-1".to_string(), 5_000, vec!["validator-b".to_string()]).unwrap()
+        .add_nominator("nominator-2".to_string(), 100, vec!["validator-a".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_blocked("validator-b".to_string(), true).unwrap();
+
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .overrides(overrides)
+        .build()
+        .unwrap();
+
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    assert_eq!(result.total_stake_for_validator("validator-b"), 5_000);
+}
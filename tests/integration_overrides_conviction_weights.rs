@@ -0,0 +1,67 @@
+//! Override test: conviction multipliers applied to nominator stakes
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::conviction::{Conviction, ConvictionWeights};
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_overrides::ElectionOverrides;
+
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("whale".to_string(), 10_000, vec!["a".to_string()]).unwrap()
+        .add_nominator("minnow".to_string(), 2_000, vec!["b".to_string()]).unwrap();
+    builder.build().unwrap()
+}
+
+#[test]
+fn test_conviction_multipliers_range_from_a_tenth_to_six() {
+    assert_eq!(Conviction::None.weigh(1_005), 100);
+    assert_eq!(Conviction::Locked1x.weigh(1_005), 1_005);
+    assert_eq!(Conviction::Locked3x.weigh(1_005), 3_015);
+    assert_eq!(Conviction::Locked6x.weigh(1_005), 6_030);
+    assert_eq!(Conviction::Locked6x.weigh(u128::MAX), u128::MAX);
+    assert_eq!(Conviction::Locked6x.lock_periods(), 32);
+    assert_eq!("6x".parse::<Conviction>(), Ok(Conviction::Locked6x));
+    assert_eq!("none".parse::<Conviction>(), Ok(Conviction::None));
+    assert!("7x".parse::<Conviction>().is_err());
+}
+
+#[test]
+fn test_convictions_are_applied_after_overrides_and_change_the_winner() {
+    let data = election_data();
+    let engine = ElectionEngine::new();
+    let winner = |config: &ElectionConfiguration| {
+        let result = engine.execute(config, &data).unwrap();
+        (result.selected_validators[0].account_id.clone(), result.total_stake)
+    };
+
+    let base = ElectionConfiguration::builder().active_set_size(2);
+    assert_eq!(winner(&base.clone().build().unwrap()), ("a".to_string(), 12_000));
+
+    // The whale votes without locking (0.1x), the minnow locks for 6x
+    let convictions = ConvictionWeights::new()
+        .default_conviction(Conviction::None)
+        .nominator("minnow", Conviction::Locked6x);
+    let weighted = base.convictions(convictions.clone());
+    assert_eq!(winner(&weighted.clone().build().unwrap()), ("b".to_string(), 1_000 + 12_000));
+
+    // Stake overrides are weighted too
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_nominator_stake("whale".to_string(), 500_000).unwrap();
+    assert_eq!(winner(&weighted.clone().overrides(overrides).build().unwrap()), ("a".to_string(), 50_000 + 12_000));
+    let weighted = weighted.build().unwrap();
+
+    // The prepared path falls back to applying them as well
+    let prepared = engine.prepare(&data).unwrap();
+    let result = engine.execute_prepared(&weighted, &prepared, false).unwrap();
+    assert_eq!(result.selected_validators[0].account_id, "b");
+
+    let json = serde_json::to_value(&weighted).unwrap();
+    assert_eq!(json["convictions"]["default"], "none");
+    assert_eq!(json["convictions"]["nominators"]["minnow"], "locked6x");
+}
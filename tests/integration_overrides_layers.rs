@@ -0,0 +1,48 @@
+//! Override test: named override layers are applied in order
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::types::AlgorithmType;
+
+#[test]
+fn test_override_layers_apply_in_order_and_are_recorded() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("validator-a".to_string(), 0).unwrap()
+        .add_nominator("nominator-1".to_string(), 1_000, vec!["validator-a".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+
+    let mut whale_unbonds = ElectionOverrides::new();
+    whale_unbonds.set_nominator_stake("nominator-1".to_string(), 100).unwrap();
+    let mut whale_returns = ElectionOverrides::new();
+    whale_returns.set_nominator_stake("nominator-1".to_string(), 700).unwrap();
+
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(1)
+        .override_layer("whale-unbonds", whale_unbonds)
+        .override_layer("whale-returns", whale_returns)
+        .build()
+        .unwrap();
+
+    let result = ElectionEngine::new().execute(&config, &data).unwrap();
+
+    assert_eq!(result.total_stake, 700);
+    assert_eq!(
+        result.execution_metadata.applied_override_layers,
+        vec!["whale-unbonds".to_string(), "whale-returns".to_string()]
+    );
+}
+
+#[test]
+fn test_duplicate_override_layer_names_are_rejected() {
+    let config = ElectionConfiguration::builder()
+        .active_set_size(100)
+        .override_layer("slash-validator-x", ElectionOverrides::new())
+        .override_layer("slash-validator-x", ElectionOverrides::new())
+        .build();
+
+    assert!(config.is_err());
+}
@@ -0,0 +1,90 @@
+//! Payout test: enforcing commission bounds and its effect on nominator rewards
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::validator::CandidateMetadata;
+use offline_election::payout::{CommissionChange, CommissionPolicy, CommissionRefusal, PayoutConfig};
+
+/// Candidates a, b, c and d with commissions 0%, 10%, 3% and 20%, each
+/// backed by one nominator; a and b win the two seats
+fn election_data() -> ElectionData {
+    let mut builder = SyntheticDataBuilder::new();
+    for (candidate, stake) in [("a", 300u128), ("b", 200), ("c", 100), ("d", 50)] {
+        builder.add_candidate(candidate.to_string(), 0).unwrap();
+        builder
+            .add_nominator(format!("n-{}", candidate), stake, vec![candidate.to_string()])
+            .unwrap();
+    }
+    let mut data = builder.build().unwrap();
+    for (candidate, commission) in data.candidates.iter_mut().zip([0u8, 10, 3, 20]) {
+        candidate.metadata = Some(CandidateMetadata {
+            commission_rate: Some(commission),
+            ..Default::default()
+        });
+    }
+    data
+}
+
+fn config() -> ElectionConfiguration {
+    ElectionConfiguration::builder().active_set_size(2).build().unwrap()
+}
+
+#[test]
+fn test_minimum_commission_moves_rewards_from_nominators() {
+    let policy = CommissionPolicy::new().min(5);
+    let impact = ElectionEngine::new()
+        .commission_scenario(&config(), &election_data(), &policy, &PayoutConfig::new(1_000_000))
+        .unwrap();
+
+    assert_eq!(
+        impact.adjusted,
+        vec![
+            CommissionChange { validator_id: "a".to_string(), from: 0, to: 5 },
+            CommissionChange { validator_id: "c".to_string(), from: 3, to: 5 },
+        ]
+    );
+    assert!(impact.dropped.is_empty());
+    assert!(impact.validators_added.is_empty() && impact.validators_removed.is_empty());
+    assert_eq!((impact.baseline_commission, impact.scenario_commission), (50_000, 75_000));
+    assert_eq!((impact.baseline_nominator_rewards, impact.scenario_nominator_rewards), (950_000, 925_000));
+
+    let losers: Vec<(&str, i128)> = impact.losers().map(|n| (n.nominator_id.as_str(), n.change)).collect();
+    assert_eq!(losers, vec![("n-a", -25_000)]);
+    assert_eq!(impact.gainers().count(), 0);
+}
+
+#[test]
+fn test_refusing_candidates_drop_out_of_the_election() {
+    let engine = ElectionEngine::new();
+    let payout = PayoutConfig::new(1_000_000);
+
+    let policy = CommissionPolicy::new().min(5).refusal(CommissionRefusal::OutOfBounds);
+    let impact = engine.commission_scenario(&config(), &election_data(), &policy, &payout).unwrap();
+    assert!(impact.adjusted.is_empty());
+    assert_eq!(impact.dropped, vec!["a", "c"]);
+    assert_eq!(impact.validators_added, vec!["d"]);
+    assert_eq!(impact.validators_removed, vec!["a"]);
+    let changes: Vec<(&str, i128)> = impact.nominators.iter().map(|n| (n.nominator_id.as_str(), n.change)).collect();
+    assert_eq!(changes, vec![("n-a", -500_000), ("n-d", 400_000)]);
+
+    // Only c refuses; a complies and keeps its seat
+    let policy = CommissionPolicy::new().min(5).refusal(CommissionRefusal::Accounts(vec!["c".to_string()]));
+    let impact = engine.commission_scenario(&config(), &election_data(), &policy, &payout).unwrap();
+    assert_eq!(impact.adjusted.len(), 1);
+    assert_eq!(impact.dropped, vec!["c"]);
+    assert!(impact.validators_added.is_empty());
+}
+
+#[test]
+fn test_policy_bounds() {
+    let policy = CommissionPolicy::new().min(5).max(15);
+    assert_eq!((policy.bounded(0), policy.bounded(10), policy.bounded(20)), (5, 10, 15));
+    assert!(policy.validate().is_ok());
+    assert!(!policy.refuses("a", 0), "candidates comply unless a refusal is set");
+
+    assert!(CommissionPolicy::new().validate().is_err());
+    assert!(CommissionPolicy::new().max(101).validate().is_err());
+    assert!(CommissionPolicy::new().min(10).max(5).validate().is_err());
+}
@@ -0,0 +1,88 @@
+//! Payout test: splitting the era reward between validators by reward curves
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::payout::{
+    CappedBackingCurve, PayoutConfig, PayoutResult, PayoutSimulator, RewardCurve, RewardCurveInput,
+    SqrtBackingCurve,
+};
+
+/// Validator a is backed by 900, validator b by 100
+fn run_election() -> ElectionResult {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 600, vec!["a".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 300, vec!["a".to_string()]).unwrap()
+        .add_nominator("n3".to_string(), 100, vec!["b".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+    let config = ElectionConfiguration::builder().active_set_size(2).build().unwrap();
+    ElectionEngine::new().execute(&config, &data).unwrap()
+}
+
+fn rewards(payout: &PayoutResult) -> Vec<(&str, u128)> {
+    payout
+        .validator_payouts
+        .iter()
+        .map(|p| (p.validator_id.as_str(), p.total_reward))
+        .collect()
+}
+
+/// Pays only validators on a list, to check curves can be plugged in
+struct Allowlist(Vec<&'static str>);
+
+impl RewardCurve for Allowlist {
+    fn weight(&self, validator: &RewardCurveInput<'_>) -> u128 {
+        self.0.contains(&validator.validator_id) as u128 * validator.era_points as u128
+    }
+
+    fn name(&self) -> &'static str {
+        "allowlist"
+    }
+}
+
+#[test]
+fn test_backing_curves_change_the_split_between_validators() {
+    let result = run_election();
+    let config = PayoutConfig::new(1_000_000);
+
+    let current = PayoutSimulator::new().simulate(&result, &config).unwrap();
+    assert_eq!(rewards(&current), vec![("a", 500_000), ("b", 500_000)]);
+
+    // sqrt(900) : sqrt(100) = 3 : 1
+    let simulator = PayoutSimulator::new().with_reward_curve(SqrtBackingCurve);
+    assert_eq!(simulator.reward_curve(), "sqrt-backing");
+    let sqrt = simulator.simulate(&result, &config).unwrap();
+    assert_eq!(rewards(&sqrt), vec![("a", 750_000), ("b", 250_000)]);
+    // The validator's share is still split pro rata across its exposure
+    assert_eq!(sqrt.nominator_payout("n1").unwrap().total_reward, 500_000);
+
+    let capped = PayoutSimulator::new()
+        .with_reward_curve(CappedBackingCurve::new(300))
+        .simulate(&result, &config)
+        .unwrap();
+    assert_eq!(rewards(&capped), vec![("a", 750_000), ("b", 250_000)]);
+}
+
+#[test]
+fn test_custom_curve_weights_validators() {
+    let result = run_election();
+    let config = PayoutConfig::new(1_000_000);
+
+    let payout = PayoutSimulator::new()
+        .with_reward_curve(Allowlist(vec!["b"]))
+        .simulate(&result, &config)
+        .unwrap();
+    assert_eq!(rewards(&payout), vec![("a", 0), ("b", 1_000_000)]);
+
+    // Zero total weight pays nothing
+    let payout = PayoutSimulator::new()
+        .with_reward_curve(Allowlist(vec![]))
+        .simulate(&result, &config)
+        .unwrap();
+    assert_eq!(payout.total_paid, 0);
+    assert_eq!(payout.unpaid, 1_000_000);
+}
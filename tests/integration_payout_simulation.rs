@@ -0,0 +1,179 @@
+//! Payout simulation tests
+
+use offline_election::engine::ElectionEngine;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_result::ElectionResult;
+use offline_election::models::validator::{CandidateActivity, CandidateMetadata, EraActivity};
+use offline_election::payout::{EraPoints, InflationParams, PageLimitMode, PayoutConfig, PayoutSimulator};
+use std::collections::HashMap;
+use offline_election::types::AlgorithmType;
+
+fn run_election() -> ElectionResult {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("validator-a".to_string(), 0).unwrap()
+        .add_candidate("validator-b".to_string(), 0).unwrap()
+        .add_nominator("nominator-1".to_string(), 3_000, vec!["validator-a".to_string()]).unwrap()
+        .add_nominator("nominator-2".to_string(), 1_000, vec!["validator-a".to_string()]).unwrap()
+        .add_nominator("nominator-3".to_string(), 2_000, vec!["validator-b".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+
+    let config = ElectionConfiguration::builder()
+        .algorithm(AlgorithmType::SequentialPhragmen)
+        .active_set_size(2)
+        .build()
+        .unwrap();
+
+    ElectionEngine::new().execute(&config, &data).unwrap()
+}
+
+#[test]
+fn test_payout_splits_commission_and_nominator_rewards() {
+    let result = run_election();
+    let config = PayoutConfig::new(1_000_000)
+        .commission("validator-a".to_string(), 10)
+        .build()
+        .unwrap();
+
+    let payout = PayoutSimulator::new().simulate(&result, &config).unwrap();
+
+    let validator_a = payout.validator_payout("validator-a").unwrap();
+    assert_eq!(validator_a.total_reward, 500_000);
+    assert_eq!(validator_a.commission_reward, 50_000);
+    assert_eq!(validator_a.nominators_reward, 450_000);
+
+    // Nominator 1 holds 3/4 of validator A's exposure
+    assert_eq!(payout.nominator_payout("nominator-1").unwrap().total_reward, 337_500);
+    assert_eq!(payout.nominator_payout("nominator-3").unwrap().total_reward, 500_000);
+    assert_eq!(payout.total_paid + payout.unpaid, 1_000_000);
+}
+
+#[test]
+fn test_top_only_page_limit_clips_smallest_nominators() {
+    let result = run_election();
+    let config = PayoutConfig::new(1_000_000)
+        .page_size(1)
+        .page_limit_mode(PageLimitMode::TopOnly)
+        .build()
+        .unwrap();
+
+    let payout = PayoutSimulator::new().simulate(&result, &config).unwrap();
+
+    let validator_a = payout.validator_payout("validator-a").unwrap();
+    assert_eq!(validator_a.nominator_count, 2);
+    assert_eq!(validator_a.rewarded_nominator_count, 1);
+    // The clipped nominator's quarter of the exposure is not paid to anyone
+    assert_eq!(payout.nominator_payout("nominator-1").unwrap().total_reward, 375_000);
+    assert_eq!(payout.nominator_payout("nominator-2").unwrap().total_reward, 0);
+    assert_eq!(validator_a.nominators_reward, 375_000);
+    assert_eq!(payout.unpaid, 125_000);
+}
+
+#[test]
+fn test_unrewarded_allocations_are_left_unpaid() {
+    let mut result = run_election();
+    result.mark_unrewarded(1);
+    let config = PayoutConfig::new(1_000_000).build().unwrap();
+
+    let payout = PayoutSimulator::new().simulate(&result, &config).unwrap();
+
+    let validator_a = payout.validator_payout("validator-a").unwrap();
+    assert_eq!(validator_a.nominator_count, 2);
+    assert_eq!(validator_a.rewarded_nominator_count, 1);
+    // Nominator 2 was marked unrewarded, so its quarter of the exposure is not paid
+    assert_eq!(payout.nominator_payout("nominator-1").unwrap().total_reward, 375_000);
+    assert_eq!(payout.nominator_payout("nominator-2").unwrap().total_reward, 0);
+    assert_eq!(payout.unpaid, 125_000);
+}
+
+#[test]
+fn test_paged_mode_counts_pages() {
+    let result = run_election();
+    let config = PayoutConfig::new(1_000_000).page_size(1).build().unwrap();
+
+    let payout = PayoutSimulator::new().simulate(&result, &config).unwrap();
+
+    assert_eq!(payout.validator_payout("validator-a").unwrap().pages, 2);
+    assert_eq!(payout.validator_payout("validator-b").unwrap().pages, 1);
+}
+
+#[test]
+fn test_historical_era_points_weight_rewards_by_average_performance() {
+    let result = run_election();
+    let history = HashMap::from([
+        ("validator-a".to_string(), vec![60, 100, 80]),
+        ("validator-b".to_string(), vec![40]),
+    ]);
+    let config = PayoutConfig::new(1_000_000)
+        .era_points(EraPoints::Historical { history })
+        .build()
+        .unwrap();
+
+    let payout = PayoutSimulator::new().simulate(&result, &config).unwrap();
+
+    // Averages of 80 and 40 points split the reward 2:1
+    let validator_a = payout.validator_payout("validator-a").unwrap();
+    assert_eq!(validator_a.era_points, 80);
+    assert_eq!(validator_a.total_reward, 666_667);
+    assert_eq!(payout.nominator_payout("nominator-3").unwrap().total_reward, 333_333);
+
+    // Validators without history are expected to perform like the average one
+    let partial = EraPoints::Historical {
+        history: HashMap::from([("a".to_string(), vec![30]), ("b".to_string(), vec![10])]),
+    };
+    assert_eq!(partial.points_for(&["a", "b", "new"]), vec![30, 10, 20]);
+    let empty = EraPoints::Historical { history: HashMap::new() };
+    assert_eq!(empty.points_for(&["a", "b"]), EraPoints::Uniform.points_for(&["a", "b"]));
+}
+
+#[test]
+fn test_era_points_can_come_from_loaded_candidate_activity() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("validator-a".to_string(), 0).unwrap()
+        .add_candidate("validator-b".to_string(), 0).unwrap()
+        .add_candidate("validator-c".to_string(), 0).unwrap()
+        .add_nominator("nominator-1".to_string(), 1_000, vec!["validator-a".to_string(), "validator-b".to_string()]).unwrap();
+    let mut data = builder.build().unwrap();
+    let activity = |points: [u32; 2]| {
+        Some(CandidateMetadata {
+            activity: Some(CandidateActivity {
+                eras: vec![EraActivity { era: 9, points: points[0] }, EraActivity { era: 10, points: points[1] }],
+            }),
+            ..Default::default()
+        })
+    };
+    data.candidates[0].metadata = activity([0, 50]);
+    data.candidates[1].metadata = activity([70, 90]);
+    data.candidates[2].metadata = activity([0, 0]);
+
+    let points = EraPoints::from_activity(&data);
+    let EraPoints::Historical { ref history } = points else {
+        panic!("expected historical era points");
+    };
+    // Eras without points are eras out of the active set, not poor performance
+    assert_eq!(
+        *history,
+        HashMap::from([("validator-a".to_string(), vec![50]), ("validator-b".to_string(), vec![70, 90])])
+    );
+    // A candidate never elected is expected to earn the network mean
+    assert_eq!(points.points_for(&["validator-a", "validator-b", "validator-c"]), vec![50, 80, 65]);
+}
+
+#[test]
+fn test_inflation_curve_peaks_at_ideal_staking_rate() {
+    let params = InflationParams::polkadot();
+    let issuance = 1_000_000_000_000_000_000u128;
+
+    let at_ideal = params.era_payout(issuance / 4 * 3, issuance).unwrap();
+    assert!((at_ideal.annual_staking_inflation - 0.10).abs() < 1e-9);
+    assert_eq!(at_ideal.treasury_payout, 0);
+
+    let below_ideal = params.era_payout(issuance / 2, issuance).unwrap();
+    assert!(below_ideal.staking_payout < at_ideal.staking_payout);
+    assert!(below_ideal.treasury_payout > 0);
+
+    let config = PayoutConfig::from_inflation(&params, issuance / 2, issuance).unwrap();
+    assert_eq!(config.era_reward, below_ideal.staking_payout);
+}
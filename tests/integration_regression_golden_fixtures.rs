@@ -0,0 +1,76 @@
+//! Regression test: golden fixtures embedded in the crate
+#![cfg(feature = "regression")]
+
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::regression::{fixture_names, freeze_fixture, load_fixture, verify_all, verify_fixture, ExpectedResults};
+use offline_election::types::AlgorithmType;
+use offline_election::ElectionError;
+
+#[test]
+fn test_every_golden_fixture_verifies() {
+    let verifications = verify_all().unwrap();
+    assert_eq!(verifications.len(), fixture_names().len());
+    assert!(verifications.len() >= 3);
+    for verification in verifications {
+        assert!(verification.passed(), "{}: {:#?}", verification.name, verification.mismatches);
+        assert_eq!(verification.elections, 3);
+    }
+}
+
+#[test]
+fn test_fixtures_load_with_their_recorded_hash() {
+    for name in fixture_names() {
+        let (data, expected) = load_fixture(name).unwrap();
+        assert_eq!(expected.name, name);
+        assert_eq!(data.content_hash(), expected.data_hash);
+        for election in &expected.elections {
+            assert_eq!(election.winners.len(), election.active_set_size as usize);
+            assert!(election.winners.iter().all(|winner| data.candidates.iter().any(|c| c.account_id == winner.account_id)));
+        }
+    }
+
+    assert!(matches!(
+        verify_fixture("no-such-fixture"),
+        Err(ElectionError::ValidationError { field: Some(ref field), .. }) if field == "fixture"
+    ));
+}
+
+#[test]
+fn test_frozen_fixtures_round_trip() {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 0).unwrap()
+        .add_candidate("b".to_string(), 0).unwrap()
+        .add_candidate("c".to_string(), 0).unwrap()
+        .add_nominator("n1".to_string(), 300, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 100, vec!["b".to_string(), "c".to_string()]).unwrap();
+    let data = builder.build().unwrap();
+
+    let elections = [(AlgorithmType::SequentialPhragmen, 2), (AlgorithmType::ParallelPhragmen, 1)];
+    let (snapshot, expected) = freeze_fixture("tiny", "Three candidates", &data, &elections).unwrap();
+    let expected: ExpectedResults = serde_json::from_str(&expected).unwrap();
+    assert_eq!((expected.name.as_str(), expected.data_hash.clone()), ("tiny", data.content_hash()));
+    assert_eq!(expected.elections.len(), 2);
+    assert_eq!(expected.elections[0].total_stake, 400);
+
+    let json = miniz_oxide::inflate::decompress_to_vec_zlib(&snapshot).unwrap();
+    assert_eq!(serde_json::from_slice::<offline_election::ElectionData>(&json).unwrap(), data);
+
+    // Elections that cannot run are not frozen
+    assert!(freeze_fixture("tiny", "", &data, &[(AlgorithmType::SequentialPhragmen, 0)]).is_err());
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_expected_results_agree_with_the_reference() {
+    use offline_election::testing::compare_with_reference;
+
+    for name in fixture_names() {
+        let (data, expected) = load_fixture(name).unwrap();
+        for election in &expected.elections {
+            let divergence =
+                compare_with_reference(&data, election.active_set_size as usize, election.algorithm).unwrap();
+            assert_eq!(divergence, None, "{} {}", name, election.algorithm);
+        }
+    }
+}
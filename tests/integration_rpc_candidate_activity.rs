@@ -0,0 +1,112 @@
+//! RPC test: candidates' recent era points and the exclude-inactive rule
+
+use axum::{routing::post, Json, Router};
+use offline_election::input::{RpcLoader, SyntheticDataBuilder};
+use offline_election::models::candidate_rules::CandidateRules;
+use offline_election::models::validator::{CandidateActivity, CandidateMetadata, EraActivity};
+use parity_scale_codec::Encode;
+use serde_json::{json, Value};
+use std::hash::Hasher;
+
+fn twox(data: &[u8], bytes: usize) -> Vec<u8> {
+    (0..bytes / 8)
+        .flat_map(|seed| {
+            let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+            hasher.write(data);
+            hasher.finish().to_le_bytes()
+        })
+        .collect()
+}
+
+fn era_points_key(era: u32) -> String {
+    format!(
+        "0x{}{}{}{}",
+        hex::encode(twox(b"Staking", 16)),
+        hex::encode(twox(b"ErasRewardPoints", 16)),
+        hex::encode(twox(&era.to_le_bytes(), 8)),
+        hex::encode(era.to_le_bytes())
+    )
+}
+
+/// Era 7 is stored, with points for accounts 1 and 2; era 6 has been pruned
+async fn serve_node() -> String {
+    async fn rpc(Json(request): Json<Value>) -> Json<Value> {
+        let result = match request["method"].as_str().unwrap_or_default() {
+            "chain_getBlockHash" => json!(format!("0x{}", "ab".repeat(32))),
+            "state_getStorage" if request["params"][0].as_str() == Some(era_points_key(7).as_str()) => {
+                let points = (100u32, vec![([1u8; 32], 60u32), ([2u8; 32], 40u32)]);
+                json!(format!("0x{}", hex::encode(points.encode())))
+            }
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, Router::new().route("/", post(rpc))).await.unwrap();
+    });
+    url
+}
+
+fn activity(points: &[u32]) -> CandidateActivity {
+    CandidateActivity {
+        eras: points.iter().enumerate().map(|(era, &points)| EraActivity { era: era as u32, points }).collect(),
+    }
+}
+
+#[tokio::test]
+async fn test_era_points_are_read_per_validator() {
+    let loader = RpcLoader::new(serve_node().await).unwrap().with_activity_eras(2);
+
+    let points = loader.era_points_at(100, 7).await.unwrap().unwrap();
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[&format!("0x{}", "01".repeat(32))], 60);
+    assert_eq!(points[&format!("0x{}", "02".repeat(32))], 40);
+
+    assert_eq!(loader.era_points_at(100, 6).await.unwrap(), None);
+}
+
+#[test]
+fn test_activity_summary_looks_at_the_most_recent_eras() {
+    let recovered = activity(&[0, 0, 20]);
+    assert_eq!(recovered.active_eras(), 1);
+    assert_eq!(recovered.last_active_era(), Some(2));
+    assert_eq!(recovered.total_points(), 20);
+    assert!(!recovered.inactive_for(1));
+    assert!(!recovered.inactive_for(3));
+
+    let chilled = activity(&[15, 0, 0]);
+    assert!(chilled.inactive_for(2));
+    assert!(!chilled.inactive_for(3));
+
+    // No loaded eras is not evidence of inactivity
+    assert!(!activity(&[]).inactive_for(2));
+}
+
+#[test]
+fn test_exclude_inactive_drops_only_candidates_known_to_be_idle() {
+    let mut builder = SyntheticDataBuilder::new();
+    for id in ["active", "idle", "unknown"] {
+        builder.add_candidate(id.to_string(), 0).unwrap();
+    }
+    builder
+        .add_nominator("n".to_string(), 1_000, vec!["active".to_string(), "idle".to_string(), "unknown".to_string()])
+        .unwrap();
+    let mut data = builder.build().unwrap();
+    for candidate in &mut data.candidates {
+        let points = match candidate.account_id.as_str() {
+            "active" => Some(activity(&[0, 30])),
+            "idle" => Some(activity(&[30, 0])),
+            _ => None,
+        };
+        candidate.metadata = Some(CandidateMetadata { activity: points, ..Default::default() });
+    }
+
+    let applications = CandidateRules::new().exclude_inactive(1).apply(&mut data);
+    assert_eq!(applications[0].rule.describe(), "exclude inactive in last 1 eras");
+    assert_eq!(applications[0].affected, vec!["idle".to_string()]);
+    let remaining: Vec<&str> = data.candidates.iter().map(|c| c.account_id.as_str()).collect();
+    assert_eq!(remaining, ["active", "unknown"]);
+}
@@ -0,0 +1,137 @@
+//! RPC test: bounded concurrency settings and aggregated fetch failures
+
+use axum::extract::State;
+use axum::{routing::post, Json, Router};
+use offline_election::input::rpc::{FetchFailures, RpcLoader, DEFAULT_RPC_CONCURRENCY};
+use parity_scale_codec::Encode;
+use serde_json::{json, Value};
+use std::hash::Hasher;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Voters in the mock node, each one storage query
+const VOTERS: u8 = 40;
+
+fn twox(data: &[u8], bytes: usize) -> Vec<u8> {
+    (0..bytes / 8)
+        .flat_map(|seed| {
+            let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+            hasher.write(data);
+            hasher.finish().to_le_bytes()
+        })
+        .collect()
+}
+
+fn storage_key(pallet: &str, item: &str) -> String {
+    format!("0x{}{}", hex::encode(twox(pallet.as_bytes(), 16)), hex::encode(twox(item.as_bytes(), 16)))
+}
+
+/// Member 1 and `VOTERS` voters backing it with stakes 1, 2, 3, ...
+fn storage() -> Vec<(String, Vec<u8>)> {
+    let voting_prefix = storage_key("Council", "Voting");
+    let mut entries = vec![(storage_key("Council", "Members"), vec![([1u8; 32], 10u128, 5u128)].encode())];
+    for voter in 100..100 + VOTERS {
+        let key = format!("{}{}{}", voting_prefix, hex::encode(twox(&[voter; 32], 8)), hex::encode([voter; 32]));
+        entries.push((key, (vec![[1u8; 32]], u128::from(voter - 99), 1u128).encode()));
+    }
+    entries
+}
+
+/// Storage queries the mock node is answering, and the most it answered at once
+#[derive(Default)]
+struct InFlight {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+    served: AtomicUsize,
+}
+
+/// A node that answers storage queries slowly, so concurrent queries overlap
+async fn serve_node(in_flight: Arc<InFlight>) -> String {
+    async fn rpc(State(in_flight): State<Arc<InFlight>>, Json(request): Json<Value>) -> Json<Value> {
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap_or_default() {
+            "chain_getBlockHash" => json!(format!("0x{}", "ab".repeat(32))),
+            "state_getStorage" => {
+                let current = in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+                in_flight.peak.fetch_max(current, Ordering::SeqCst);
+                // Uneven delays so queries finish out of order
+                let key = params[0].as_str().unwrap_or_default();
+                let delay = key.bytes().last().map_or(0, u64::from) % 15;
+                tokio::time::sleep(Duration::from_millis(5 + delay)).await;
+                in_flight.current.fetch_sub(1, Ordering::SeqCst);
+                in_flight.served.fetch_add(1, Ordering::SeqCst);
+                storage()
+                    .into_iter()
+                    .find(|(entry_key, _)| entry_key == key)
+                    .map_or(Value::Null, |(_, value)| json!(format!("0x{}", hex::encode(value))))
+            }
+            "state_getKeysPaged" => {
+                let prefix = params[0].as_str().unwrap_or_default();
+                json!(storage().into_iter().map(|(key, _)| key).filter(|key| key.starts_with(prefix)).collect::<Vec<_>>())
+            }
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let app = Router::new().route("/", post(rpc)).with_state(in_flight);
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    url
+}
+
+#[test]
+fn test_concurrency_defaults_and_is_at_least_one() {
+    let loader = RpcLoader::new("http://127.0.0.1:9933").unwrap();
+    assert_eq!(loader.concurrency(), DEFAULT_RPC_CONCURRENCY);
+
+    let loader = loader.with_concurrency(64);
+    assert_eq!(loader.concurrency(), 64);
+    assert_eq!(loader.with_concurrency(0).concurrency(), 1);
+}
+
+#[test]
+fn test_fetch_failures_summarize_first_few_errors() {
+    let mut failures = FetchFailures::new("Staking::Ledger", 100);
+    assert!(failures.summary().is_none());
+
+    for i in 0..7 {
+        failures.record(format!("0x{:02}: 503 Service Unavailable", i));
+    }
+
+    assert_eq!(failures.len(), 7);
+    let summary = failures.summary().unwrap();
+    assert!(summary.starts_with("7 of 100 Staking::Ledger queries failed"));
+    assert!(summary.contains("0x04: 503"));
+    assert!(!summary.contains("0x05: 503"));
+    assert!(summary.ends_with("... and 2 more"));
+}
+
+#[tokio::test]
+async fn test_contending_queries_stay_within_the_concurrency_limit() {
+    for concurrency in [1, 4] {
+        let in_flight = Arc::new(InFlight::default());
+        let loader = RpcLoader::new(serve_node(Arc::clone(&in_flight)).await)
+            .unwrap()
+            .with_elections_pallet("Council")
+            .with_concurrency(concurrency);
+        let data = loader.load_council_at_block(100).await.unwrap();
+
+        let peak = in_flight.peak.load(Ordering::SeqCst);
+        assert!(peak <= concurrency, "{} queries in flight with a limit of {}", peak, concurrency);
+        if concurrency > 1 {
+            assert!(peak > 1, "queries never overlapped");
+        }
+        assert!(in_flight.served.load(Ordering::SeqCst) >= usize::from(VOTERS));
+
+        // Every voter is loaded with its own stake, whatever order the queries finished in
+        let mut stakes: Vec<u128> = data.nominators.iter().map(|n| n.stake).collect();
+        stakes.sort_unstable();
+        assert_eq!(stakes, (1..=u128::from(VOTERS)).collect::<Vec<_>>());
+        assert!(data.metadata.unwrap().load_failures.is_empty());
+    }
+}
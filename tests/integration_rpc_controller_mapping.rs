@@ -0,0 +1,104 @@
+//! RPC test: mapping stashes to their controllers for historical snapshots
+
+use axum::{routing::post, Json, Router};
+use offline_election::input::RpcLoader;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::nominator::{Nominator, NominatorMetadata};
+use offline_election::models::validator::{CandidateMetadata, ValidatorCandidate};
+use parity_scale_codec::Encode;
+use serde_json::{json, Value};
+use std::hash::Hasher;
+
+fn twox(data: &[u8], bytes: usize) -> Vec<u8> {
+    (0..bytes / 8)
+        .flat_map(|seed| {
+            let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+            hasher.write(data);
+            hasher.finish().to_le_bytes()
+        })
+        .collect()
+}
+
+fn bonded_key(stash: u8) -> String {
+    format!(
+        "0x{}{}{}{}",
+        hex::encode(twox(b"Staking", 16)),
+        hex::encode(twox(b"Bonded", 16)),
+        hex::encode(twox(&[stash; 32], 8)),
+        hex::encode([stash; 32])
+    )
+}
+
+fn account_id(byte: u8) -> String {
+    format!("0x{}", hex::encode([byte; 32]))
+}
+
+/// Stash 1 is controlled by 11, stash 2 controls itself and 3 is not bonded
+async fn serve_node() -> String {
+    async fn rpc(Json(request): Json<Value>) -> Json<Value> {
+        let key = request["params"][0].as_str().unwrap_or_default().to_string();
+        let result = match request["method"].as_str().unwrap_or_default() {
+            "chain_getBlockHash" => json!(format!("0x{}", "ab".repeat(32))),
+            "state_getStorage" if key == bonded_key(1) => json!(format!("0x{}", hex::encode([11u8; 32].encode()))),
+            "state_getStorage" if key == bonded_key(2) => json!(format!("0x{}", hex::encode([2u8; 32].encode()))),
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, Router::new().route("/", post(rpc))).await.unwrap();
+    });
+    url
+}
+
+#[tokio::test]
+async fn test_controllers_are_read_for_bonded_stashes() {
+    let loader = RpcLoader::new(serve_node().await).unwrap().with_controller_mapping(true);
+    let stashes = [account_id(1), account_id(2), account_id(3)];
+
+    let controllers = loader.controllers_at(100, &stashes).await.unwrap();
+    assert_eq!(controllers.len(), 2);
+    assert_eq!(controllers[&account_id(1)], account_id(11));
+    assert_eq!(controllers[&account_id(2)], account_id(2));
+
+    assert!(loader.controllers_at(100, &["not-hex".to_string()]).await.is_err());
+}
+
+#[test]
+fn test_stash_of_resolves_controllers_to_stashes() {
+    let mut data = ElectionData::new();
+    let mut validator = ValidatorCandidate::new("v-stash".to_string(), 100);
+    validator.metadata = Some(CandidateMetadata { controller: Some("v-controller".to_string()), ..Default::default() });
+    data.add_candidate(validator).unwrap();
+    let mut nominator = Nominator::new("n-stash".to_string(), 50);
+    nominator.targets.push("v-stash".to_string());
+    nominator.metadata = Some(NominatorMetadata { controller: Some("n-controller".to_string()), ..Default::default() });
+    data.add_nominator(nominator).unwrap();
+
+    assert_eq!(data.stash_of("v-controller"), Some("v-stash"));
+    assert_eq!(data.stash_of("n-controller"), Some("n-stash"));
+    assert_eq!(data.stash_of("n-stash"), Some("n-stash"));
+    assert_eq!(data.stash_of("stranger"), None);
+    assert_eq!(data.candidates[0].controller(), Some("v-controller"));
+}
+
+#[test]
+fn test_nominator_controllers_round_trip_beside_extra_metadata() {
+    let nominator: Nominator = serde_json::from_value(json!({
+        "account_id": "n",
+        "stake": 5,
+        "targets": ["v"],
+        "metadata": { "controller": "c", "pool": 7 }
+    }))
+    .unwrap();
+    let metadata = nominator.metadata.as_ref().unwrap();
+    assert_eq!(nominator.controller(), Some("c"));
+    assert_eq!(metadata.extra.get("pool"), Some(&json!(7)));
+    assert!(!metadata.extra.contains_key("controller"));
+
+    let reparsed: Nominator = serde_json::from_str(&serde_json::to_string(&nominator).unwrap()).unwrap();
+    assert_eq!(reparsed, nominator);
+}
@@ -0,0 +1,100 @@
+//! RPC test: loading council elections from pallet-elections-phragmen storage
+
+use axum::{routing::post, Json, Router};
+use offline_election::input::RpcLoader;
+use parity_scale_codec::Encode;
+use serde_json::{json, Value};
+use std::hash::Hasher;
+
+fn twox(data: &[u8], bytes: usize) -> Vec<u8> {
+    (0..bytes / 8)
+        .flat_map(|seed| {
+            let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+            hasher.write(data);
+            hasher.finish().to_le_bytes()
+        })
+        .collect()
+}
+
+fn storage_key(pallet: &str, item: &str) -> String {
+    format!("0x{}{}", hex::encode(twox(pallet.as_bytes(), 16)), hex::encode(twox(item.as_bytes(), 16)))
+}
+
+fn account(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+fn account_id(byte: u8) -> String {
+    format!("0x{}", hex::encode(account(byte)))
+}
+
+/// A node with member 1, runner-up 2, applicant 3 and three voters
+fn storage() -> Vec<(String, Vec<u8>)> {
+    let voting_prefix = storage_key("Council", "Voting");
+    // (who, stake, deposit) seat holders and (votes, stake, deposit) voters
+    let mut entries = vec![
+        (storage_key("Council", "Members"), vec![(account(1), 10u128, 5u128)].encode()),
+        (storage_key("Council", "RunnersUp"), vec![(account(2), 4u128, 5u128)].encode()),
+        (storage_key("Council", "Candidates"), vec![(account(3), 5u128)].encode()),
+    ];
+    for (voter, votes, stake) in [
+        (10u8, vec![account(1), account(9)], 700u128),
+        (11, vec![account(2), account(3)], 300),
+        (12, vec![account(9)], 900),
+    ] {
+        let key = format!("{}{}{}", voting_prefix, hex::encode(twox(&[voter; 32], 8)), hex::encode([voter; 32]));
+        entries.push((key, (votes, stake, 1u128).encode()));
+    }
+    entries
+}
+
+async fn serve_node() -> String {
+    async fn rpc(Json(request): Json<Value>) -> Json<Value> {
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap_or_default() {
+            "chain_getBlockHash" => json!(format!("0x{}", "ab".repeat(32))),
+            "state_getStorage" => storage()
+                .into_iter()
+                .find(|(key, _)| Some(key.as_str()) == params[0].as_str())
+                .map_or(Value::Null, |(_, value)| json!(format!("0x{}", hex::encode(value)))),
+            "state_getKeysPaged" => {
+                let prefix = params[0].as_str().unwrap_or_default();
+                json!(storage().into_iter().map(|(key, _)| key).filter(|key| key.starts_with(prefix)).collect::<Vec<_>>())
+            }
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, Router::new().route("/", post(rpc))).await.unwrap();
+    });
+    url
+}
+
+#[tokio::test]
+async fn test_council_candidates_and_voters_are_read_from_the_elections_pallet() {
+    let loader = RpcLoader::new(serve_node().await).unwrap().with_elections_pallet("Council");
+    let data = loader.load_council_at_block(100).await.unwrap();
+
+    let candidates: Vec<(String, u128)> = data.candidates.iter().map(|c| (c.account_id.clone(), c.stake)).collect();
+    assert_eq!(candidates, vec![(account_id(1), 0), (account_id(2), 0), (account_id(3), 0)]);
+
+    // Votes for non-candidates are dropped, and so is a voter left with none
+    let mut voters: Vec<(String, u128, Vec<String>)> = data
+        .nominators
+        .iter()
+        .map(|n| (n.account_id.clone(), n.stake, n.targets.clone()))
+        .collect();
+    voters.sort();
+    assert_eq!(
+        voters,
+        vec![
+            (account_id(10), 700, vec![account_id(1)]),
+            (account_id(11), 300, vec![account_id(2), account_id(3)]),
+        ]
+    );
+    assert_eq!(data.metadata.unwrap().block_number, Some(100));
+}
@@ -0,0 +1,83 @@
+//! Chain watch test: decoding the multi-phase election provider phase
+
+use axum::{routing::post, Json, Router};
+use offline_election::input::chain_watch::http_url_for;
+use offline_election::input::{ElectionPhase, RpcLoader};
+use serde_json::{json, Value};
+use std::hash::Hasher;
+
+fn twox128(data: &[u8]) -> Vec<u8> {
+    (0..2u64)
+        .flat_map(|seed| {
+            let mut hasher = twox_hash::XxHash64::with_seed(seed);
+            hasher.write(data);
+            hasher.finish().to_le_bytes()
+        })
+        .collect()
+}
+
+/// Round 2 starts at block 5_000 and round 3 at block 9_000; block hashes
+/// carry their number so storage can be answered per block
+async fn serve_rounds() -> String {
+    async fn rpc(Json(request): Json<Value>) -> Json<Value> {
+        let round_key =
+            format!("0x{}{}", hex::encode(twox128(b"ElectionProviderMultiPhase")), hex::encode(twox128(b"Round")));
+        let result = match request["method"].as_str().unwrap_or_default() {
+            "chain_getBlockHash" => {
+                let block = u64::from_str_radix(&request["params"][0].as_str().unwrap()[2..], 16).unwrap();
+                json!(format!("0x{:064x}", block))
+            }
+            "state_getStorage" if request["params"][0].as_str() == Some(round_key.as_str()) => {
+                let block = u64::from_str_radix(&request["params"][1].as_str().unwrap()[2..], 16).unwrap();
+                let round: u32 = match block {
+                    0..=4_999 => 1,
+                    5_000..=8_999 => 2,
+                    _ => 3,
+                };
+                json!(format!("0x{}", hex::encode(round.to_le_bytes())))
+            }
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, Router::new().route("/", post(rpc))).await.unwrap();
+    });
+    url
+}
+
+#[test]
+fn test_election_phase_decodes_scale_variants() {
+    assert_eq!(ElectionPhase::decode(&[0]).unwrap(), ElectionPhase::Off);
+    assert_eq!(ElectionPhase::decode(&[1]).unwrap(), ElectionPhase::Signed);
+    assert_eq!(
+        ElectionPhase::decode(&[2, 1, 0x10, 0x27, 0, 0]).unwrap(),
+        ElectionPhase::Unsigned { open: true, started_at: 10_000 }
+    );
+    assert_eq!(ElectionPhase::decode(&[3]).unwrap(), ElectionPhase::Emergency);
+    assert!(ElectionPhase::decode(&[2, 1]).is_err());
+    assert!(ElectionPhase::decode(&[]).is_err());
+
+    assert!(!ElectionPhase::Off.is_snapshot_open());
+    assert!(ElectionPhase::Signed.is_snapshot_open());
+    assert!(!ElectionPhase::Emergency.is_snapshot_open());
+}
+
+#[test]
+fn test_http_url_is_derived_from_websocket_url() {
+    assert_eq!(http_url_for("wss://rpc.polkadot.io"), "https://rpc.polkadot.io");
+    assert_eq!(http_url_for("ws://127.0.0.1:9944"), "http://127.0.0.1:9944");
+}
+
+#[tokio::test]
+async fn test_last_election_block_is_the_end_of_the_previous_round() {
+    let loader = RpcLoader::new(serve_rounds().await).unwrap();
+    assert_eq!(loader.election_round_at(9_500).await.unwrap(), Some(3));
+    assert_eq!(loader.last_election_block(9_500).await.unwrap(), Some(8_999));
+    assert_eq!(loader.last_election_block(9_000).await.unwrap(), Some(8_999));
+    assert_eq!(loader.last_election_block(8_999).await.unwrap(), Some(4_999));
+    assert_eq!(loader.last_election_block(4_000).await.unwrap(), None);
+}
@@ -0,0 +1,80 @@
+//! RPC test: loading historical eras from a GraphQL indexer
+
+use axum::{routing::post, Json, Router};
+use offline_election::input::IndexerLoader;
+use serde_json::{json, Value};
+
+/// Serve canned GraphQL responses, paged by the `limit` and `offset` variables
+async fn serve_indexer() -> String {
+    async fn graphql(Json(request): Json<Value>) -> Json<Value> {
+        let query = request["query"].as_str().unwrap_or_default();
+        let variables = &request["variables"];
+        if variables["era"] != 1400 {
+            return Json(json!({ "errors": [{ "message": "unknown era" }] }));
+        }
+        let (field, rows) = if query.contains("candidates:") {
+            (
+                "candidates",
+                json!([
+                    { "accountId": "alice", "stake": "1000000000000000000000", "commission": 5.0 },
+                    { "accountId": "bob", "stake": 2000, "blocked": true },
+                    { "accountId": "carol", "stake": "3000" }
+                ]),
+            )
+        } else if query.contains("nominators:") {
+            (
+                "nominators",
+                json!([
+                    { "accountId": "dave", "stake": "500", "targets": ["alice", "zed", "alice"] },
+                    { "accountId": "erin", "stake": "700", "targets": ["zed"] },
+                    { "accountId": "frank", "stake": 900, "targets": ["bob", "carol"] }
+                ]),
+            )
+        } else {
+            ("elected", json!([{ "accountId": "alice" }, { "accountId": "carol" }]))
+        };
+        let offset = variables["offset"].as_u64().unwrap() as usize;
+        let limit = variables["limit"].as_u64().unwrap() as usize;
+        let page: Vec<Value> = rows.as_array().unwrap().iter().skip(offset).take(limit).cloned().collect();
+        Json(json!({ "data": { field: page } }))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, Router::new().route("/graphql", post(graphql))).await.unwrap();
+    });
+    url
+}
+
+#[tokio::test]
+async fn test_era_is_loaded_across_pages() {
+    let loader = IndexerLoader::new(serve_indexer().await).unwrap().with_page_size(2);
+
+    let data = loader.load_era(1400).await.unwrap();
+    let stakes: Vec<(&str, u128)> = data.candidates.iter().map(|c| (c.account_id.as_str(), c.stake)).collect();
+    assert_eq!(stakes, vec![("alice", 1_000_000_000_000_000_000_000), ("bob", 2000), ("carol", 3000)]);
+    let alice = data.candidates[0].metadata.as_ref().unwrap();
+    assert_eq!(alice.commission_rate, Some(5));
+    assert_eq!(data.candidates[1].metadata.as_ref().unwrap().blocked, Some(true));
+
+    // Votes for non-candidates are dropped, and so is a nominator left with none
+    let votes: Vec<(&str, Vec<&str>)> = data
+        .nominators
+        .iter()
+        .map(|n| (n.account_id.as_str(), n.targets.iter().map(String::as_str).collect()))
+        .collect();
+    assert_eq!(votes, vec![("dave", vec!["alice"]), ("frank", vec!["bob", "carol"])]);
+    assert_eq!(data.metadata.unwrap().era, Some(1400));
+
+    assert_eq!(loader.elected_at_era(1400).await.unwrap(), vec!["alice", "carol"]);
+}
+
+#[tokio::test]
+async fn test_graphql_errors_and_bad_urls_are_reported() {
+    let loader = IndexerLoader::new(serve_indexer().await).unwrap();
+    let error = loader.load_era(7).await.unwrap_err().to_string();
+    assert!(error.contains("Indexer query failed: unknown era"), "{}", error);
+
+    assert!(IndexerLoader::new("not a url").is_err());
+}
@@ -0,0 +1,65 @@
+//! RPC test: per-request timeout, overall deadline and rate limit
+
+use offline_election::error::ElectionError;
+use offline_election::input::rpc::{RetryPolicy, RpcLoader, DEFAULT_REQUEST_TIMEOUT, MIN_RATE_LIMIT};
+use std::net::TcpListener;
+use std::time::{Duration, Instant};
+
+/// An endpoint that accepts connections but never answers
+fn silent_endpoint() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    (listener, url)
+}
+
+#[tokio::test]
+async fn test_request_timeout_is_retried_and_deadline_stops_the_load() {
+    let (_listener, url) = silent_endpoint();
+    let loader = RpcLoader::new(&url)
+        .unwrap()
+        .with_retry_policy(RetryPolicy::new().max_attempts(2).initial_delay(Duration::from_millis(1)).jitter(0.0))
+        .with_retry_observer(|_| {})
+        .with_request_timeout(Duration::from_millis(100));
+    assert_eq!(loader.request_timeout(), Duration::from_millis(100));
+
+    let error = loader.get_latest_block_number().await.unwrap_err();
+    assert!(matches!(error, ElectionError::Timeout { timeout_ms: 100, .. }), "{}", error);
+    assert_eq!(loader.retry_count(), 1);
+
+    let loader = RpcLoader::new(&url).unwrap().with_deadline(Duration::from_millis(200));
+    assert_eq!(loader.request_timeout(), DEFAULT_REQUEST_TIMEOUT);
+    let started = Instant::now();
+    let error = loader.load_latest().await.unwrap_err().to_string();
+    assert!(error.contains("Deadline of 0.2 seconds exceeded"), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_rate_limit_spaces_out_requests() {
+    let loader = RpcLoader::new("http://127.0.0.1:1")
+        .unwrap()
+        .with_retry_policy(RetryPolicy::none())
+        .with_rate_limit(20.0)
+        .unwrap();
+    assert_eq!(loader.rate_limit(), Some(20.0));
+
+    let started = Instant::now();
+    for _ in 0..5 {
+        assert!(loader.get_latest_block_number().await.is_err());
+    }
+    // The first request starts immediately, the other four 50ms apart
+    assert!(started.elapsed() >= Duration::from_millis(200));
+
+    assert_eq!(loader.with_rate_limit(0.0).unwrap().rate_limit(), None);
+}
+
+#[test]
+fn test_rate_limits_below_one_an_hour_are_rejected() {
+    let loader = || RpcLoader::new("http://127.0.0.1:1").unwrap();
+    for invalid in [1e-20, -1.0, f64::NAN, f64::NEG_INFINITY] {
+        let error = loader().with_rate_limit(invalid).err().unwrap();
+        assert!(matches!(error, ElectionError::ValidationError { ref field, .. } if field.as_deref() == Some("rpc_rate_limit")));
+    }
+    assert!(loader().with_rate_limit(f64::INFINITY).unwrap().rate_limit().is_none());
+    assert!(loader().with_rate_limit(MIN_RATE_LIMIT).unwrap().rate_limit().is_some());
+}
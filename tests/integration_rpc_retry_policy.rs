@@ -0,0 +1,83 @@
+//! RPC test: retry and backoff policy applied to RPC calls
+
+use offline_election::input::rpc::{RetryEvent, RetryPolicy, RpcLoader};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[test]
+fn test_backoff_doubles_up_to_the_cap_and_jitter_stays_in_bounds() {
+    let policy = RetryPolicy::new()
+        .initial_delay(Duration::from_secs(1))
+        .max_delay(Duration::from_secs(5))
+        .jitter(0.0);
+    assert_eq!(policy.delay(0), Duration::from_secs(1));
+    assert_eq!(policy.delay(2), Duration::from_secs(4));
+    assert_eq!(policy.delay(3), Duration::from_secs(5));
+    assert_eq!(policy.delay(40), Duration::from_secs(5));
+
+    let jittered = policy.jitter(0.5);
+    for retry in 0..10 {
+        let delay = jittered.delay(retry);
+        let base = jittered.base_delay(retry);
+        assert!(delay >= base.mul_f64(0.5) && delay <= base.mul_f64(1.5));
+        // Jitter never pushes a delay past the cap
+        assert!(delay <= Duration::from_secs(5));
+    }
+    let full_jitter = policy.jitter(1.0);
+    assert!((0..100).all(|_| full_jitter.delay(10) <= Duration::from_secs(5)));
+    assert_eq!(RetryPolicy::none().max_attempts, 1);
+}
+
+#[tokio::test]
+async fn test_retry_stops_on_success_or_non_retryable_errors() {
+    let policy = RetryPolicy::new().max_attempts(4).initial_delay(Duration::from_millis(1)).jitter(0.0);
+
+    let mut calls = 0;
+    let mut retries = Vec::new();
+    let result: Result<u32, &str> = policy
+        .retry(
+            || {
+                calls += 1;
+                let call = calls;
+                async move { if call < 3 { Err("busy") } else { Ok(call) } }
+            },
+            |_| true,
+            |_, attempt, _| retries.push(attempt),
+        )
+        .await;
+    assert_eq!(result, Ok(3));
+    assert_eq!(retries, vec![1, 2]);
+
+    let mut calls = 0;
+    let result: Result<(), &str> = policy
+        .retry(
+            || {
+                calls += 1;
+                async { Err("invalid") }
+            },
+            |e| *e != "invalid",
+            |_, _, _| {},
+        )
+        .await;
+    assert_eq!(result, Err("invalid"));
+    assert_eq!(calls, 1);
+}
+
+#[tokio::test]
+async fn test_loader_reports_and_counts_retried_calls() {
+    let events: Arc<Mutex<Vec<RetryEvent>>> = Arc::default();
+    let recorded = Arc::clone(&events);
+    // Nothing listens on port 1, so every call fails with a connection error
+    let loader = RpcLoader::new("http://127.0.0.1:1")
+        .unwrap()
+        .with_retry_policy(RetryPolicy::new().max_attempts(3).initial_delay(Duration::from_millis(1)))
+        .with_retry_observer(move |event| recorded.lock().unwrap().push(event.clone()));
+
+    assert!(loader.get_latest_block_number().await.is_err());
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].method, "chain_getHeader");
+    assert_eq!((events[1].attempt, events[1].max_attempts), (2, 3));
+    assert_eq!(loader.retry_count(), 2);
+}
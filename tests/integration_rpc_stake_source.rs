@@ -0,0 +1,113 @@
+//! RPC test: nominator stakes from the bonded ledger or active era exposures
+
+use axum::{routing::post, Json, Router};
+use offline_election::input::RpcLoader;
+use offline_election::models::election_data::{ElectionMetadata, StakeSource};
+use parity_scale_codec::{Compact, Encode};
+use serde_json::{json, Value};
+use std::hash::Hasher;
+
+fn twox(data: &[u8], bytes: usize) -> Vec<u8> {
+    (0..bytes / 8)
+        .flat_map(|seed| {
+            let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+            hasher.write(data);
+            hasher.finish().to_le_bytes()
+        })
+        .collect()
+}
+
+fn twox_64_concat(data: &[u8]) -> String {
+    format!("{}{}", hex::encode(twox(data, 8)), hex::encode(data))
+}
+
+fn storage_key(pallet: &str, item: &str) -> String {
+    format!("0x{}{}", hex::encode(twox(pallet.as_bytes(), 16)), hex::encode(twox(item.as_bytes(), 16)))
+}
+
+fn account_id(byte: u8) -> String {
+    format!("0x{}", hex::encode([byte; 32]))
+}
+
+fn exposure_key(item: &str, era: u32, validator: u8, page: Option<u32>) -> String {
+    let page = page.map(|page| twox_64_concat(&page.to_le_bytes())).unwrap_or_default();
+    format!(
+        "{}{}{}{}",
+        storage_key("Staking", item),
+        twox_64_concat(&era.to_le_bytes()),
+        twox_64_concat(&[validator; 32]),
+        page
+    )
+}
+
+/// Era 5 has paged exposures: nominator 10 backs validators 1 and 2, nominator 11 only 2.
+/// Era 4 is only in the legacy `ErasStakers` map.
+fn storage() -> Vec<(String, Vec<u8>)> {
+    let page = |others: Vec<(u8, u128)>| {
+        let total: u128 = others.iter().map(|(_, stake)| stake).sum();
+        let others: Vec<([u8; 32], Compact<u128>)> =
+            others.into_iter().map(|(who, stake)| ([who; 32], Compact(stake))).collect();
+        (Compact(total), others).encode()
+    };
+    let legacy: Vec<([u8; 32], Compact<u128>)> = vec![([10; 32], Compact(70))];
+    vec![
+        (exposure_key("ErasStakersPaged", 5, 1, Some(0)), page(vec![(10, 300)])),
+        (exposure_key("ErasStakersPaged", 5, 2, Some(0)), page(vec![(10, 200), (11, 50)])),
+        (exposure_key("ErasStakers", 4, 1, None), (Compact(170u128), Compact(100u128), legacy).encode()),
+    ]
+}
+
+async fn serve_node() -> String {
+    async fn rpc(Json(request): Json<Value>) -> Json<Value> {
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap_or_default() {
+            "chain_getBlockHash" => json!(format!("0x{}", "ab".repeat(32))),
+            "state_getStorage" => storage()
+                .into_iter()
+                .find(|(key, _)| Some(key.as_str()) == params[0].as_str())
+                .map_or(Value::Null, |(_, value)| json!(format!("0x{}", hex::encode(value)))),
+            "state_getKeysPaged" => {
+                let prefix = params[0].as_str().unwrap_or_default();
+                json!(storage().into_iter().map(|(key, _)| key).filter(|key| key.starts_with(prefix)).collect::<Vec<_>>())
+            }
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, Router::new().route("/", post(rpc))).await.unwrap();
+    });
+    url
+}
+
+#[tokio::test]
+async fn test_exposures_are_summed_per_nominator_across_validators() {
+    let loader = RpcLoader::new(serve_node().await).unwrap().with_stake_source(StakeSource::Exposure);
+
+    let exposures = loader.exposures_at(100, 5).await.unwrap();
+    assert_eq!(exposures.len(), 2);
+    assert_eq!(exposures[&account_id(10)], 500);
+    assert_eq!(exposures[&account_id(11)], 50);
+
+    // Runtimes without paged exposures fall back to the legacy map
+    let legacy = loader.exposures_at(100, 4).await.unwrap();
+    assert_eq!(legacy.into_iter().collect::<Vec<_>>(), vec![(account_id(10), 70)]);
+    assert!(loader.exposures_at(100, 3).await.unwrap().is_empty());
+}
+
+#[test]
+fn test_stake_source_is_recorded_in_metadata() {
+    assert_eq!("exposure".parse::<StakeSource>().unwrap(), StakeSource::Exposure);
+    assert_eq!(StakeSource::default().to_string(), "ledger");
+    assert!("bonded".parse::<StakeSource>().unwrap_err().contains("expected ledger or exposure"));
+
+    let metadata: ElectionMetadata =
+        serde_json::from_value(json!({ "block_number": 7, "stake_source": "exposure" })).unwrap();
+    assert_eq!(metadata.stake_source, Some(StakeSource::Exposure));
+    let without: ElectionMetadata = serde_json::from_value(json!({ "block_number": 7 })).unwrap();
+    assert_eq!(without.stake_source, None);
+    assert!(!serde_json::to_string(&without).unwrap().contains("stake_source"));
+}
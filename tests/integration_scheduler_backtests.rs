@@ -0,0 +1,229 @@
+//! Scheduler test: recurring backtests, their history and notifications
+
+use axum::extract::{Path, Query, State};
+use axum::routing::post;
+use axum::{Json, Router};
+use offline_election::api::handlers::{get_election_results, list_scheduled_runs, HandlerState};
+use offline_election::api::models::ScheduledRunsQuery;
+use offline_election::error::ElectionError;
+use offline_election::input::SyntheticDataBuilder;
+use offline_election::scheduler::{ChainComparison, Every, RunHistory, RunStatus, ScheduleConfig, Scheduler};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Write a small snapshot and return its path
+fn snapshot_file(name: &str) -> PathBuf {
+    let mut builder = SyntheticDataBuilder::new();
+    builder
+        .add_candidate("a".to_string(), 100).unwrap()
+        .add_candidate("b".to_string(), 50).unwrap()
+        .add_candidate("c".to_string(), 10).unwrap()
+        .add_nominator("n1".to_string(), 1_000, vec!["a".to_string(), "b".to_string()]).unwrap()
+        .add_nominator("n2".to_string(), 400, vec!["c".to_string()]).unwrap();
+    let path = std::env::temp_dir().join(format!("{}-{}.json", name, std::process::id()));
+    std::fs::write(&path, serde_json::to_string(&builder.build().unwrap()).unwrap()).unwrap();
+    path
+}
+
+/// Receive webhook posts on a free local port
+async fn webhook() -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&received);
+    let app = Router::new().route(
+        "/hook",
+        post(move |Json(body): Json<serde_json::Value>| async move {
+            sink.lock().unwrap().push(body);
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, received)
+}
+
+fn field(result: Result<Scheduler, ElectionError>) -> String {
+    match result {
+        Err(ElectionError::ValidationError { field, .. }) => field.unwrap(),
+        Err(other) => panic!("expected a validation error, got {:?}", other),
+        Ok(_) => panic!("expected a validation error"),
+    }
+}
+
+#[test]
+fn test_schedules_are_parsed_and_checked() {
+    assert_eq!("era".parse::<Every>().unwrap(), Every::Era);
+    assert_eq!("15m".parse::<Every>().unwrap(), Every::Interval(Duration::from_secs(900)));
+    assert_eq!("1d".parse::<Every>().unwrap(), Every::Interval(Duration::from_secs(86_400)));
+    for invalid in ["0s", "5x", "h", "", "-1m"] {
+        assert!(invalid.parse::<Every>().is_err(), "{}", invalid);
+    }
+
+    let schedule = |jobs: &str| -> ScheduleConfig { toml::from_str(jobs).unwrap() };
+    let config = schedule(
+        r#"
+        [[jobs]]
+        name = "polkadot-era"
+        every = "era"
+        rpc_url = "https://rpc.polkadot.io"
+        compare_with_chain = true
+        notify_webhook = ["https://hooks.example.com/backtests"]
+
+        [[jobs]]
+        name = "replay"
+        every = "6h"
+        input_file = "snapshot.json"
+        active_set_size = 2
+        algorithm = "mms"
+        "#,
+    );
+    assert_eq!(config.history_limit, 1000);
+    assert_eq!(config.jobs[0].algorithm, "sequential-phragmen");
+    assert_eq!(config.jobs[1].every, Every::Interval(Duration::from_secs(6 * 3600)));
+    assert_eq!(Scheduler::new(config).unwrap().job_names(), ["polkadot-era", "replay"]);
+
+    let era_from_file = r#"
+        [[jobs]]
+        name = "replay"
+        every = "era"
+        input_file = "snapshot.json"
+        active_set_size = 2
+    "#;
+    assert_eq!(field(Scheduler::new(schedule(era_from_file))), "jobs[0].rpc_url");
+    let without_size = r#"
+        [[jobs]]
+        name = "replay"
+        every = "1h"
+        input_file = "snapshot.json"
+    "#;
+    assert_eq!(field(Scheduler::new(schedule(without_size))), "jobs[0].active_set_size");
+    let duplicate = r#"
+        [[jobs]]
+        name = "a"
+        every = "1h"
+        rpc_url = "https://rpc.polkadot.io"
+
+        [[jobs]]
+        name = "a"
+        every = "2h"
+        rpc_url = "https://rpc.polkadot.io"
+    "#;
+    assert_eq!(field(Scheduler::new(schedule(duplicate))), "jobs[1].name");
+    assert!(toml::from_str::<ScheduleConfig>("[[jobs]]\nname = \"a\"\nevery = \"weekly\"").is_err());
+
+    let path = std::env::temp_dir().join(format!("schedule-{}.toml", std::process::id()));
+    std::fs::write(&path, "[[jobs]\nname = ").unwrap();
+    assert!(matches!(Scheduler::load_from_file(&path), Err(ElectionError::FileError { .. })));
+    std::fs::remove_file(&path).unwrap();
+
+    let comparison = ChainComparison::new(
+        &["a".to_string(), "b".to_string()],
+        &["b".to_string(), "c".to_string()],
+    );
+    assert_eq!(comparison.matching, 1);
+    assert_eq!((comparison.only_predicted, comparison.only_on_chain), (vec!["a".to_string()], vec!["c".to_string()]));
+}
+
+#[tokio::test]
+async fn test_runs_are_recorded_persisted_served_and_notified() {
+    let snapshot = snapshot_file("scheduled-snapshot");
+    let history_file = std::env::temp_dir().join(format!("scheduled-runs-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&history_file);
+    let (hook, received) = webhook().await;
+    let config: ScheduleConfig = toml::from_str(&format!(
+        r#"
+        history_file = {history:?}
+        history_limit = 2
+
+        [[jobs]]
+        name = "replay"
+        every = "6h"
+        input_file = {snapshot:?}
+        active_set_size = 2
+        notify_webhook = ["{hook}"]
+
+        [[jobs]]
+        name = "broken"
+        every = "6h"
+        input_file = "/nonexistent/snapshot.json"
+        active_set_size = 2
+        notify_webhook = ["{hook}"]
+        "#,
+        history = history_file.display().to_string(),
+        snapshot = snapshot.display().to_string(),
+        hook = hook,
+    ))
+    .unwrap();
+    let state = HandlerState::new();
+    let scheduler = Scheduler::new(config).unwrap();
+    let state = state.with_run_history(scheduler.history().clone());
+    let scheduler = scheduler.with_handler_state(state.clone());
+
+    let run = scheduler.run_now("replay").await.unwrap();
+    assert_eq!((run.status, run.validator_count), (RunStatus::Succeeded, Some(2)));
+    assert!(run.data_hash.is_some());
+    let election_id = run.election_id.clone().unwrap();
+    assert!(election_id.starts_with("replay-"));
+    let Json(served) = get_election_results(State(state.clone()), Path(election_id.clone())).await.unwrap();
+    assert_eq!(served.result.selected_validators.len(), 2);
+
+    let failed = scheduler.run_now("broken").await.unwrap();
+    assert_eq!(failed.status, RunStatus::Failed);
+    assert!(failed.error.unwrap().contains("Failed to read file"));
+    assert!(scheduler.run_now("unknown").await.is_err());
+
+    let Json(runs) = list_scheduled_runs(State(state.clone()), Query(ScheduledRunsQuery::default())).await.unwrap();
+    assert_eq!(runs.iter().map(|run| run.job.as_str()).collect::<Vec<_>>(), ["replay", "broken"]);
+    let query = ScheduledRunsQuery { job: Some("replay".to_string()) };
+    let Json(runs) = list_scheduled_runs(State(state.clone()), Query(query)).await.unwrap();
+    assert_eq!(runs, vec![run.clone()]);
+    assert!(list_scheduled_runs(State(HandlerState::new()), Query(ScheduledRunsQuery::default())).await.is_err());
+
+    // Each run was announced
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 2);
+    assert_eq!(received[0]["event"], "scheduled-run-completed");
+    assert_eq!(received[0]["election_id"], election_id.as_str());
+    assert_eq!(received[1]["event"], "scheduled-run-failed");
+    assert_eq!(received[1]["job"], "broken");
+
+    // The history survives a restart, keeping only the newest runs
+    scheduler.run_now("replay").await.unwrap();
+    let reopened = RunHistory::open(&history_file, 2).unwrap();
+    let jobs: Vec<String> = reopened.runs().into_iter().map(|run| run.job).collect();
+    assert_eq!(jobs, ["broken", "replay"]);
+    assert_eq!(reopened.runs_of("broken").len(), 1);
+
+    std::fs::remove_file(&history_file).unwrap();
+    std::fs::remove_file(&snapshot).unwrap();
+}
+
+#[tokio::test]
+async fn test_interval_jobs_run_on_their_schedule() {
+    let snapshot = snapshot_file("interval-snapshot");
+    let config: ScheduleConfig = toml::from_str(&format!(
+        "[[jobs]]\nname = \"tick\"\nevery = \"1s\"\ninput_file = {:?}\nactive_set_size = 1\n",
+        snapshot.display().to_string()
+    ))
+    .unwrap();
+    let scheduler = Scheduler::new(config).unwrap();
+    let tasks = scheduler.spawn();
+
+    // Nothing runs before the first interval has passed
+    assert!(scheduler.history().runs().is_empty());
+    let mut runs = Vec::new();
+    for _ in 0..50 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        runs = scheduler.history().runs_of("tick");
+        if !runs.is_empty() {
+            break;
+        }
+    }
+    assert_eq!(runs.first().map(|run| run.status), Some(RunStatus::Succeeded));
+    assert_eq!(runs[0].election_id, None);
+
+    for task in tasks {
+        task.abort();
+    }
+    std::fs::remove_file(&snapshot).unwrap();
+}
@@ -0,0 +1,73 @@
+//! Submission helper tests: extrinsic encoding and dry-run decoding
+#![cfg(feature = "submit")]
+
+use offline_election::submit::{
+    signer_from_suri, DryRunOutcome, SigningContext, SolutionWitness, SubmissionBuilder,
+};
+use sp_core::crypto::Pair as _;
+use sp_core::sr25519;
+
+#[test]
+fn test_unsigned_extrinsic_encodes_call_and_witness() {
+    let builder = SubmissionBuilder::new(36, vec![0xaa, 0xbb]);
+
+    let extrinsic = builder.unsigned_extrinsic(SolutionWitness { voters: 1, targets: 2 });
+
+    // compact(len=7), version 4, pallet 36, call 0, raw solution, compact(1), compact(2)
+    assert_eq!(extrinsic, vec![28, 0x04, 36, 0, 0xaa, 0xbb, 4, 8]);
+}
+
+#[test]
+fn test_signed_extrinsic_carries_valid_signature() {
+    let signer = signer_from_suri("//Alice").unwrap();
+    let builder = SubmissionBuilder::new(36, vec![0xaa, 0xbb]);
+    let context = SigningContext {
+        spec_version: 1,
+        transaction_version: 2,
+        genesis_hash: [7u8; 32],
+        nonce: 5,
+        tip: 0,
+        metadata_hash_extension: true,
+    };
+
+    let extrinsic = builder.signed_extrinsic(&signer, &context);
+
+    // Skip the two-byte compact length prefix
+    let body = &extrinsic[2..];
+    assert_eq!(body.len(), 107);
+    assert_eq!(body[0], 0x84);
+    assert_eq!(&body[2..34], &signer.public().0[..]);
+
+    let mut signature = [0u8; 64];
+    signature.copy_from_slice(&body[35..99]);
+    let extra = &body[99..103];
+    assert_eq!(extra, &[0, 20, 0, 0]);
+    let call = &body[103..];
+    assert_eq!(call, &[36, 3, 0xaa, 0xbb]);
+
+    let mut payload = call.to_vec();
+    payload.extend_from_slice(extra);
+    payload.extend_from_slice(&1u32.to_le_bytes());
+    payload.extend_from_slice(&2u32.to_le_bytes());
+    payload.extend_from_slice(&[7u8; 32]);
+    payload.extend_from_slice(&[7u8; 32]);
+    payload.push(0);
+    assert!(sr25519::Pair::verify(
+        &sr25519::Signature::from_raw(signature),
+        &payload,
+        &signer.public()
+    ));
+}
+
+#[test]
+fn test_dry_run_outcome_decodes_apply_extrinsic_result() {
+    assert_eq!(DryRunOutcome::decode(&[0, 0]).unwrap(), DryRunOutcome::Success);
+    assert_eq!(
+        DryRunOutcome::decode(&[0, 1, 3]).unwrap(),
+        DryRunOutcome::DispatchError("0x03".to_string())
+    );
+    assert_eq!(
+        DryRunOutcome::decode(&[1, 0, 2]).unwrap(),
+        DryRunOutcome::Invalid("0x0002".to_string())
+    );
+}
@@ -0,0 +1,87 @@
+//! Validation test: configuration problems are reported with their field and a fix
+
+use offline_election::error::ElectionError;
+use offline_election::models::candidate_rules::CandidateRules;
+use offline_election::models::election_config::{
+    BalancingSettings, ElectionConfiguration, ElectionConfigurationBuilder, HasActiveSetSize, MAX_BALANCING_ITERATIONS,
+};
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::types::AlgorithmType;
+
+/// Builder for 100 seats
+fn builder() -> ElectionConfigurationBuilder<HasActiveSetSize> {
+    ElectionConfiguration::builder().active_set_size(100)
+}
+
+fn field_of(result: Result<ElectionConfiguration, ElectionError>) -> (String, String) {
+    match result {
+        Err(ElectionError::ValidationError { message, field }) => (field.unwrap(), message),
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_out_of_range_sizes_name_the_field_and_a_fix() {
+    let (field, message) = field_of(ElectionConfiguration::builder().active_set_size(0).build());
+    assert_eq!(field, "active_set_size");
+    assert!(message.contains("(fix: set active_set_size"), "{}", message);
+
+    for iterations in [0, MAX_BALANCING_ITERATIONS + 1] {
+        let (field, message) = field_of(builder().balancing(iterations, 0).build());
+        assert_eq!(field, "balancing.max_iterations");
+        assert!(message.contains(&format!("got {}", iterations)), "{}", message);
+    }
+    assert!(builder().balancing(MAX_BALANCING_ITERATIONS, 0).build().is_ok());
+
+    let mut overrides = ElectionOverrides::new();
+    overrides.active_set_size = Some(0);
+    let (field, _) = field_of(builder().override_layer("upgrade", overrides).build());
+    assert_eq!(field, "override_layers.upgrade.active_set_size");
+}
+
+#[test]
+fn test_contradictory_overrides_and_rules_are_rejected() {
+    let mut overrides = ElectionOverrides::new();
+    overrides.add_voting_edge("n1".to_string(), "a".to_string()).unwrap();
+    overrides.remove_voting_edge("n1".to_string(), "a".to_string()).unwrap();
+    overrides.remove_voting_edge("n1".to_string(), "b".to_string()).unwrap();
+    let (field, message) = field_of(builder().overrides(overrides).build());
+    assert_eq!(field, "overrides.voting_edges");
+    assert!(message.contains("n1 for a is both added and removed"), "{}", message);
+
+    for percent in [0, 150] {
+        let rules = CandidateRules::new().exclude_blocked().exclude_commission_at_least(percent);
+        let (field, _) = field_of(builder().candidate_rules(rules).build());
+        assert_eq!(field, "candidate_rules[1].percent");
+    }
+
+    // Force-inclusion deliberately wins over exclusion
+    let rules = CandidateRules::new().force_include(["a"]).exclude(["a"]).exclude_commission_at_least(100);
+    assert!(builder().candidate_rules(rules).build().is_ok());
+}
+
+#[test]
+fn test_issues_lists_every_problem_at_once() {
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_nominator_stake(String::new(), 5).unwrap();
+    // Built by hand, as the builder refuses to build it
+    let mut config = ElectionConfiguration::default();
+    config.active_set_size = 0;
+    config.overrides = Some(overrides);
+    config.candidate_rules = Some(CandidateRules::new().max_per_operator(0));
+    config.balancing = Some(BalancingSettings { max_iterations: 0, tolerance: 0 });
+
+    let issues = config.issues();
+    let fields: Vec<&str> = issues.iter().map(|issue| issue.field.as_str()).collect();
+    assert_eq!(
+        fields,
+        ["active_set_size", "overrides.nominator_stakes", "candidate_rules[0].max", "balancing.max_iterations"]
+    );
+    assert!(issues.iter().all(|issue| !issue.suggestion.is_empty()));
+    assert!(issues[0].to_string().starts_with("active_set_size: Active set size must be positive"));
+    assert!(ElectionConfiguration::default().issues().is_empty());
+
+    // Unknown algorithm names list the ones that exist
+    let error = "phragmms".parse::<AlgorithmType>().unwrap_err();
+    assert!(error.contains("expected sequential-phragmen, parallel-phragmen, multi-phase or mms"));
+}
@@ -0,0 +1,85 @@
+//! Validation test: duplicate and conflicting entries are reported and resolved
+
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::nominator::Nominator;
+use offline_election::models::validation::{DataIssueKind, DedupPolicy, DuplicateKind, ValidationProfile};
+use offline_election::models::validator::ValidatorCandidate;
+
+fn nominator(account_id: &str, stake: u128, targets: &[&str]) -> Nominator {
+    let mut nominator = Nominator::new(account_id.to_string(), stake);
+    nominator.targets = targets.iter().map(|t| t.to_string()).collect();
+    nominator
+}
+
+/// "a" is listed twice identically, "n1" twice with different stakes and targets
+fn duplicated_data() -> ElectionData {
+    let mut data = ElectionData::new();
+    data.candidates = vec![
+        ValidatorCandidate::new("a".to_string(), 1_000),
+        ValidatorCandidate::new("b".to_string(), 2_000),
+        ValidatorCandidate::new("a".to_string(), 1_000),
+    ];
+    data.nominators = vec![
+        nominator("n1", 100, &["a"]),
+        nominator("n2", 300, &["b"]),
+        nominator("n1", 500, &["b"]),
+    ];
+    data.invulnerables = vec!["b".to_string(), "b".to_string()];
+    data
+}
+
+#[test]
+fn test_duplicate_report_lists_duplicates_and_conflicts() {
+    let data = duplicated_data();
+    let report = data.duplicate_report();
+
+    let candidates: Vec<_> = report.of_kind(DuplicateKind::Candidate).collect();
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].occurrences, 2);
+    assert!(!candidates[0].conflicting);
+
+    let nominators: Vec<_> = report.of_kind(DuplicateKind::Nominator).collect();
+    assert_eq!(nominators[0].account_id, "n1");
+    assert_eq!(nominators[0].stakes, vec![100, 500]);
+    assert!(nominators[0].conflicting);
+    assert_eq!(report.of_kind(DuplicateKind::Invulnerable).count(), 1);
+    assert_eq!(report.conflicting().count(), 1);
+
+    let error = data.validate().unwrap_err().to_string();
+    assert!(error.contains("Duplicate candidate account ID: a (3 duplicated accounts, 1 with conflicting entries)"));
+
+    // A candidate also nominating with a different stake is a role conflict
+    let mut conflict = ElectionData::new();
+    conflict.add_candidate(ValidatorCandidate::new("a".to_string(), 1_000)).unwrap();
+    conflict.add_nominator(nominator("a", 4_000, &["a"])).unwrap();
+    let entry = &conflict.duplicate_report().entries[0];
+    assert_eq!(entry.kind, DuplicateKind::CandidateAndNominator);
+    assert_eq!(entry.stakes, vec![1_000, 4_000]);
+    let issues = conflict.validate_with_profile(ValidationProfile::Permissive).unwrap();
+    assert!(issues.iter().any(|i| i.kind == DataIssueKind::RoleConflict));
+    assert!(conflict.validate_with_profile(ValidationProfile::Strict).is_err());
+}
+
+#[test]
+fn test_dedup_policies_resolve_duplicates() {
+    let stakes = |policy: DedupPolicy| {
+        let mut data = duplicated_data();
+        data.dedup(policy);
+        data.validate().unwrap();
+        assert_eq!(data.candidates.len(), 2);
+        assert_eq!(data.invulnerables, vec!["b".to_string()]);
+        // The resolved nominator keeps the position of the first occurrence
+        assert_eq!(data.nominators[0].account_id, "n1");
+        (data.nominators[0].stake, data.nominators[0].targets.clone())
+    };
+
+    assert_eq!(stakes(DedupPolicy::KeepFirst), (100, vec!["a".to_string()]));
+    assert_eq!(stakes(DedupPolicy::KeepLast), (500, vec!["b".to_string()]));
+    assert_eq!(stakes(DedupPolicy::KeepHighestStake), (500, vec!["b".to_string()]));
+    assert_eq!(stakes(DedupPolicy::Sum), (600, vec!["a".to_string(), "b".to_string()]));
+
+    let mut clean = duplicated_data();
+    clean.dedup(DedupPolicy::Sum);
+    assert!(clean.dedup(DedupPolicy::Sum).is_empty());
+    assert_eq!("keep-highest-stake".parse::<DedupPolicy>(), Ok(DedupPolicy::KeepHighestStake));
+}
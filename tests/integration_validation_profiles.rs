@@ -0,0 +1,83 @@
+//! Validation test: strictness profiles decide which data issues are errors
+
+use offline_election::engine::ElectionEngine;
+use offline_election::models::election_config::ElectionConfiguration;
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::nominator::Nominator;
+use offline_election::models::validation::{DataIssueKind, ValidationProfile};
+use offline_election::models::validator::ValidatorCandidate;
+
+fn nominator(account_id: &str, stake: u128, targets: &[&str]) -> Nominator {
+    let mut nominator = Nominator::new(account_id.to_string(), stake);
+    nominator.targets = targets.iter().map(|t| t.to_string()).collect();
+    nominator
+}
+
+/// Zero stake, a duplicate target and a self-nomination with a different stake;
+/// every vote can be allocated
+fn quirky_data() -> ElectionData {
+    let mut data = ElectionData::new();
+    for candidate in ["a", "b"] {
+        data.add_candidate(ValidatorCandidate::new(candidate.to_string(), 1_000)).unwrap();
+    }
+    data.add_nominator(nominator("idle", 0, &["a"])).unwrap();
+    data.add_nominator(nominator("twice", 2_000, &["a", "a"])).unwrap();
+    data.add_nominator(nominator("b", 3_000, &["b"])).unwrap();
+    data
+}
+
+fn kinds(data: &ElectionData, profile: ValidationProfile) -> Vec<DataIssueKind> {
+    data.validate_with_profile(profile).unwrap().iter().map(|i| i.kind).collect()
+}
+
+#[test]
+fn test_profiles_classify_issues_as_errors_or_warnings() {
+    let data = quirky_data();
+    let issues = vec![
+        DataIssueKind::ZeroStakeNominator,
+        DataIssueKind::DuplicateTarget,
+        DataIssueKind::SelfNomination,
+        DataIssueKind::RoleConflict,
+    ];
+    assert_eq!(kinds(&data, ValidationProfile::ChainMirroring), issues);
+    assert_eq!(kinds(&data, ValidationProfile::Permissive), issues);
+    let error = data.validate_with_profile(ValidationProfile::Strict).unwrap_err();
+    assert!(error.to_string().contains("Nominator 'idle' has zero stake"));
+
+    // Votes for non-candidates are dropped as on chain, and only fail strict validation
+    let mut dangling = quirky_data();
+    dangling.nominators[1].targets.push("ghost".to_string());
+    assert!(dangling.validate().is_ok());
+    assert!(kinds(&dangling, ValidationProfile::ChainMirroring).contains(&DataIssueKind::DanglingEdge));
+    assert!(kinds(&dangling, ValidationProfile::Permissive).contains(&DataIssueKind::DanglingEdge));
+    assert!(dangling.validate_with_profile(ValidationProfile::Strict).is_err());
+    assert_eq!(dangling.clone().sanitize(), 2);
+
+    assert_eq!("strict".parse::<ValidationProfile>(), Ok(ValidationProfile::Strict));
+    assert!("lenient".parse::<ValidationProfile>().is_err());
+}
+
+#[test]
+fn test_engine_reports_tolerated_issues_and_drops_dangling_votes() {
+    let mut data = quirky_data();
+    data.nominators[1].targets.push("ghost".to_string());
+    let engine = ElectionEngine::new();
+
+    let default = ElectionConfiguration::builder().active_set_size(2);
+    let result = engine.execute(&default.clone().build().unwrap(), &data).unwrap();
+    assert!(result.execution_metadata.data_issues.iter().any(|i| i.kind == DataIssueKind::DanglingEdge));
+    assert_eq!(result.allocations_for_nominator("twice").len(), 1);
+    let strict = default.clone().validation_profile(ValidationProfile::Strict).build().unwrap();
+    assert!(engine.execute(&strict, &data).is_err());
+
+    let permissive = default.validation_profile(ValidationProfile::Permissive).build().unwrap();
+    let result = engine.execute(&permissive, &data).unwrap();
+    let reported: Vec<DataIssueKind> = result.execution_metadata.data_issues.iter().map(|i| i.kind).collect();
+    assert!(reported.contains(&DataIssueKind::DanglingEdge));
+    assert_eq!(result.total_stake, 5_000);
+    assert_eq!(result.allocations_for_nominator("twice").len(), 1);
+
+    let json = serde_json::to_value(&permissive).unwrap();
+    assert_eq!(json["validation_profile"], "permissive");
+    assert!(serde_json::to_value(ElectionConfiguration::default()).unwrap().get("validation_profile").is_none());
+}
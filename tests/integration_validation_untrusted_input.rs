@@ -0,0 +1,70 @@
+//! Validation test: the input surfaces exercised by the fuzz targets
+
+use offline_election::error::ElectionError;
+use offline_election::input::{JsonLoader, StakingMinerLoader};
+use offline_election::models::election_data::ElectionData;
+use offline_election::models::election_overrides::ElectionOverrides;
+use offline_election::models::onchain_solution::decode_snapshot;
+use offline_election::models::validation::ValidationProfile;
+use offline_election::models::OnchainSolution;
+
+const DOCUMENT: &str = r#"{
+    "candidates": [{"account_id": "a", "stake": 100}, {"account_id": "b", "stake": 50}],
+    "nominators": [{"account_id": "n1", "stake": 1000, "targets": ["a", "b"]}]
+}"#;
+
+#[test]
+fn test_json_documents_load_from_strings() {
+    let data = JsonLoader::new().load_from_str(DOCUMENT).unwrap();
+    assert_eq!((data.candidates.len(), data.nominators.len()), (2, 1));
+
+    for malformed in ["", "{", "[]", r#"{"candidates": 5}"#, r#"{"candidates": [], "nominators": [{"stake": -1}]}"#] {
+        assert!(
+            matches!(JsonLoader::new().load_from_str(malformed), Err(ElectionError::InvalidData { .. })),
+            "{}",
+            malformed
+        );
+    }
+
+    // Loaded documents are validated like files
+    let unknown_target = DOCUMENT.replace(r#"["a", "b"]"#, r#"["a", "missing"]"#);
+    assert!(JsonLoader::new().load_from_str(&unknown_target).is_ok());
+    let strict = JsonLoader::new().with_validation_profile(ValidationProfile::Strict);
+    assert!(strict.load_from_str(&unknown_target).is_err());
+}
+
+#[test]
+fn test_overrides_apply_to_data_and_report_unknown_accounts() {
+    let mut data = JsonLoader::new().load_from_str(DOCUMENT).unwrap();
+    let mut overrides = ElectionOverrides::new();
+    overrides.set_candidate_stake("a".to_string(), 7).unwrap();
+    overrides.set_nominator_stake("ghost".to_string(), 1).unwrap();
+    overrides.remove_voting_edge("n1".to_string(), "b".to_string()).unwrap();
+    overrides.add_voting_edge("ghost".to_string(), "a".to_string()).unwrap();
+
+    let unmatched = overrides.apply(&mut data).unmatched;
+    assert_eq!(data.candidates[0].stake, 7);
+    assert_eq!(data.nominators[0].targets, ["a"]);
+    assert_eq!(unmatched.len(), 2);
+    assert!(unmatched.iter().all(|message| message.contains("ghost")));
+
+    // Applying to empty data only reports
+    let mut empty = ElectionData::default();
+    assert_eq!(overrides.apply(&mut empty).unmatched.len(), 4);
+    assert_eq!(empty, ElectionData::default());
+}
+
+#[test]
+fn test_hostile_binary_snapshots_fail_to_decode() {
+    // A compact length of 2^30 voters followed by nothing
+    let huge_length = [0x02, 0x00, 0x00, 0x00, 0x01];
+    let mut truncated_voter = vec![0x04];
+    truncated_voter.extend_from_slice(&[7; 20]);
+
+    for bytes in [&[][..], &[0xff; 3][..], &huge_length[..], &truncated_voter[..]] {
+        assert!(decode_snapshot(bytes, None).is_err());
+        assert!(OnchainSolution::decode(bytes).is_err());
+        let loader = StakingMinerLoader::new().with_validation_profile(ValidationProfile::Permissive);
+        assert!(loader.load_from_bytes(bytes).is_err());
+    }
+}